@@ -26,6 +26,7 @@ mod static_file_header;
 mod stats;
 /// DB List TUI
 mod tui;
+mod verify_snapshot;
 
 /// `reth db` command
 #[derive(Debug, Parser)]
@@ -81,6 +82,9 @@ pub enum Subcommands {
     /// Migrate storage layout from v1 (MDBX-only) to v2 (static files + RocksDB)
     #[command(name = "migrate-v2")]
     MigrateV2(migrate_v2::Command),
+    /// Verifies the static files, stage checkpoints, sampled blocks and state root of the
+    /// datadir and outputs a JSON report
+    VerifySnapshot(verify_snapshot::Command),
 }
 
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
@@ -242,6 +246,11 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                 // Migrate changesets+receipts, clear tables, compact MDBX
                 command.execute::<N>(provider_factory).await?;
             }
+            Subcommands::VerifySnapshot(command) => {
+                db_exec!(self.env, tool, N, AccessRights::RoInconsistent, {
+                    command.execute(&tool)?;
+                });
+            }
         }
 
         Ok(())
@@ -79,6 +79,8 @@ pub enum SegmentArg {
     AccountHistory,
     StorageHistory,
     Bodies,
+    AddressTransactions,
+    LogIndex,
 }
 
 impl From<SegmentArg> for PruneSegment {
@@ -91,6 +93,8 @@ impl From<SegmentArg> for PruneSegment {
             SegmentArg::AccountHistory => Self::AccountHistory,
             SegmentArg::StorageHistory => Self::StorageHistory,
             SegmentArg::Bodies => Self::Bodies,
+            SegmentArg::AddressTransactions => Self::AddressTransactions,
+            SegmentArg::LogIndex => Self::LogIndex,
         }
     }
 }
@@ -122,6 +122,8 @@ pub enum StageArg {
     TransactionLookup,
     IndexStorageHistory,
     IndexAccountHistory,
+    IndexAddressTransactions,
+    IndexLogs,
     Prune,
     Finish,
 }
@@ -142,6 +144,8 @@ impl From<StageArg> for StageId {
             StageArg::TransactionLookup => Self::TransactionLookup,
             StageArg::IndexStorageHistory => Self::IndexStorageHistory,
             StageArg::IndexAccountHistory => Self::IndexAccountHistory,
+            StageArg::IndexAddressTransactions => Self::IndexAddressTransactions,
+            StageArg::IndexLogs => Self::IndexLogs,
             StageArg::Prune => Self::Prune,
             StageArg::Finish => Self::Finish,
         }
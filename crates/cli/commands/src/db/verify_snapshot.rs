@@ -0,0 +1,459 @@
+use alloy_consensus::{proofs::calculate_receipt_root, BlockHeader, TxReceipt};
+use alloy_primitives::{BlockNumber, B256};
+use clap::Parser;
+use reth_chainspec::EthereumHardforks;
+use reth_db_api::{database::Database, transaction::DbTx};
+use reth_db_common::DbTool;
+use reth_primitives_traits::{Block, BlockBody};
+use reth_provider::{
+    providers::ProviderNodeTypes, BlockReader, HeaderProvider, PruneCheckpointReader,
+    ReceiptProvider, StageCheckpointReader, StaticFileProviderFactory,
+};
+use reth_prune_types::PruneSegment;
+use reth_stages::StageId;
+use reth_static_file_types::{SegmentHeader, SegmentRangeInclusive, StaticFileSegment};
+use reth_trie::{trie_cursor::noop::NoopTrieCursorFactory, StateRoot};
+use reth_trie_db::DatabaseHashedCursorFactory;
+use serde::Serialize;
+use std::{collections::BTreeMap, fmt::Display, path::PathBuf};
+use tracing::info;
+
+/// The arguments for the `reth db verify-snapshot` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Number of blocks, evenly spread up to the tip, to recompute the transactions and receipts
+    /// roots of.
+    #[arg(long, default_value_t = 100)]
+    samples: u64,
+
+    /// Skip recomputing the state root at the tip from the hashed state, which can take hours on
+    /// large chains.
+    #[arg(long)]
+    skip_state_root: bool,
+
+    /// Write the JSON report to this file instead of stdout.
+    #[arg(long, value_name = "PATH")]
+    output: Option<PathBuf>,
+}
+
+impl Command {
+    /// Execute `db verify-snapshot` command
+    pub fn execute<N: ProviderNodeTypes>(self, tool: &DbTool<N>) -> eyre::Result<()> {
+        let provider = tool.provider_factory.provider()?;
+        let tip = provider.get_stage_checkpoint(StageId::Finish)?.unwrap_or_default().block_number;
+        info!(target: "reth::cli", tip, "Verifying snapshot");
+
+        let mut report = VerificationReport { tip, ..Default::default() };
+        let static_files = Self::verify_static_file_headers(tool, &mut report)?;
+        Self::verify_stage_checkpoints(tool, &static_files, &mut report)?;
+        self.verify_sampled_blocks(tool, tip, &mut report)?;
+        if self.skip_state_root {
+            report.push(CheckKind::StateRoot, tip, CheckStatus::Skipped, None);
+        } else {
+            Self::verify_state_root(tool, tip, &mut report)?;
+        }
+
+        report.passed = report.count(CheckStatus::Passed);
+        report.failed = report.count(CheckStatus::Failed);
+        report.skipped = report.count(CheckStatus::Skipped);
+        let json = serde_json::to_string_pretty(&report)?;
+        match &self.output {
+            Some(path) => reth_fs_util::write(path, json)?,
+            None => println!("{json}"),
+        }
+
+        info!(target: "reth::cli",
+            passed = report.passed,
+            failed = report.failed,
+            skipped = report.skipped,
+            "Snapshot verification finished"
+        );
+        eyre::ensure!(report.failed == 0, "Snapshot verification failed {} checks", report.failed);
+
+        Ok(())
+    }
+
+    /// Checks the header of every static file against the block range of its file name, and the
+    /// ranges of consecutive files of a segment against each other.
+    ///
+    /// Returns the headers of the static files by segment, sorted by block range.
+    fn verify_static_file_headers<N: ProviderNodeTypes>(
+        tool: &DbTool<N>,
+        report: &mut VerificationReport,
+    ) -> eyre::Result<BTreeMap<StaticFileSegment, Vec<SegmentHeader>>> {
+        let static_file_provider = tool.provider_factory.static_file_provider();
+
+        let mut files = Vec::new();
+        for entry in reth_fs_util::read_dir(static_file_provider.directory())? {
+            let path = entry?.path();
+            if !path.is_file() {
+                continue
+            }
+            if let Some((segment, file_range)) = path
+                .file_name()
+                .and_then(|name| StaticFileSegment::parse_filename(&name.to_string_lossy()))
+            {
+                files.push((segment, file_range, path));
+            }
+        }
+        files.sort_unstable_by_key(|(segment, file_range, _)| (*segment, file_range.end()));
+
+        let mut headers = BTreeMap::<StaticFileSegment, Vec<SegmentHeader>>::new();
+        for (segment, file_range, path) in files {
+            let subject = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+            let (header, rows) = {
+                let jar_provider =
+                    static_file_provider.get_segment_provider_for_path(&path)?.ok_or_else(
+                        || eyre::eyre!("Could not find static file segment for path: {subject}"),
+                    )?;
+                (jar_provider.user_header().clone(), jar_provider.rows() as u64)
+            };
+            // Removes from cache, since if we have many files, it may hit ulimit limits
+            static_file_provider.remove_cached_provider(segment, file_range.end());
+
+            let previous = headers.get(&segment).and_then(|headers| headers.last());
+            let error = static_file_header_error(segment, file_range, &header, rows, previous);
+            report.push_result(CheckKind::StaticFileHeader, subject, error);
+            headers.entry(segment).or_default().push(header);
+        }
+
+        Ok(headers)
+    }
+
+    /// Checks that the stage checkpoint of every segment with static files matches the highest
+    /// block of its static files.
+    fn verify_stage_checkpoints<N: ProviderNodeTypes>(
+        tool: &DbTool<N>,
+        static_files: &BTreeMap<StaticFileSegment, Vec<SegmentHeader>>,
+        report: &mut VerificationReport,
+    ) -> eyre::Result<()> {
+        let provider = tool.provider_factory.provider()?;
+        for (segment, headers) in static_files {
+            let stage_id = segment.to_stage_id();
+            let highest_block = headers.iter().rev().find_map(|header| header.block_end());
+            let checkpoint =
+                provider.get_stage_checkpoint(stage_id)?.map(|checkpoint| checkpoint.block_number);
+
+            let error = (highest_block != checkpoint).then(|| {
+                format!(
+                    "highest static file block {highest_block:?} doesn't match {stage_id} checkpoint {checkpoint:?}"
+                )
+            });
+            report.push_result(CheckKind::StageCheckpoint, segment, error);
+        }
+
+        Ok(())
+    }
+
+    /// Recomputes the transactions and receipts roots of the sampled blocks and compares them to
+    /// their headers.
+    ///
+    /// Receipts roots are only checked from Byzantium on, since the receipts of earlier blocks
+    /// committed to an intermediate state root that isn't stored.
+    fn verify_sampled_blocks<N: ProviderNodeTypes>(
+        &self,
+        tool: &DbTool<N>,
+        tip: BlockNumber,
+        report: &mut VerificationReport,
+    ) -> eyre::Result<()> {
+        let provider = tool.provider_factory.provider()?;
+        let chain_spec = tool.chain();
+        let pruned_to = |segments: &[PruneSegment]| -> eyre::Result<Option<BlockNumber>> {
+            let mut pruned_to = None;
+            for segment in segments {
+                let block = provider
+                    .get_prune_checkpoint(*segment)?
+                    .and_then(|checkpoint| checkpoint.block_number);
+                pruned_to = pruned_to.max(block);
+            }
+            Ok(pruned_to)
+        };
+        let transactions_pruned_to =
+            pruned_to(&[PruneSegment::Bodies, PruneSegment::Transactions])?;
+        let receipts_pruned_to = pruned_to(&[PruneSegment::Receipts, PruneSegment::ContractLogs])?;
+
+        for block_number in sample_blocks(tip, self.samples) {
+            let Some(header) = provider.header_by_number(block_number)? else {
+                report.push(
+                    CheckKind::TransactionsRoot,
+                    block_number,
+                    CheckStatus::Failed,
+                    Some("missing header".to_string()),
+                );
+                continue
+            };
+
+            if transactions_pruned_to.is_some_and(|pruned_to| block_number <= pruned_to) {
+                report.push(CheckKind::TransactionsRoot, block_number, CheckStatus::Skipped, None);
+            } else {
+                let error = match provider.block(block_number.into())? {
+                    Some(block) => {
+                        root_mismatch(block.body().calculate_tx_root(), header.transactions_root())
+                    }
+                    None => Some("missing block body".to_string()),
+                };
+                report.push_result(CheckKind::TransactionsRoot, block_number, error);
+            }
+
+            if skip_receipts_root(&*chain_spec, block_number, receipts_pruned_to) {
+                report.push(CheckKind::ReceiptsRoot, block_number, CheckStatus::Skipped, None);
+            } else {
+                let error = match provider.receipts_by_block(block_number.into())? {
+                    Some(receipts) => {
+                        let receipts_with_bloom =
+                            receipts.iter().map(TxReceipt::with_bloom_ref).collect::<Vec<_>>();
+                        root_mismatch(
+                            calculate_receipt_root(&receipts_with_bloom),
+                            header.receipts_root(),
+                        )
+                    }
+                    None => Some("missing receipts".to_string()),
+                };
+                report.push_result(CheckKind::ReceiptsRoot, block_number, error);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Recomputes the state root from the hashed state, without the stored trie nodes, and
+    /// compares it to the header of the tip.
+    fn verify_state_root<N: ProviderNodeTypes>(
+        tool: &DbTool<N>,
+        tip: BlockNumber,
+        report: &mut VerificationReport,
+    ) -> eyre::Result<()> {
+        let Some(header) = tool.provider_factory.provider()?.header_by_number(tip)? else {
+            report.push(
+                CheckKind::StateRoot,
+                tip,
+                CheckStatus::Failed,
+                Some("missing header".to_string()),
+            );
+            return Ok(())
+        };
+
+        info!(target: "reth::cli", tip, "Computing state root from hashed state");
+        let mut tx = tool.provider_factory.db_ref().tx()?;
+        tx.disable_long_read_transaction_safety();
+        let state_root =
+            StateRoot::new(NoopTrieCursorFactory::default(), DatabaseHashedCursorFactory::new(&tx))
+                .root()?;
+
+        report.push_result(
+            CheckKind::StateRoot,
+            tip,
+            root_mismatch(state_root, header.state_root()),
+        );
+
+        Ok(())
+    }
+}
+
+/// Returns why the static file of `segment` named after `file_range`, with the given `header` and
+/// number of `rows`, is inconsistent, if it is.
+///
+/// `previous` is the header of the static file of the segment right below it, if any.
+fn static_file_header_error(
+    segment: StaticFileSegment,
+    file_range: SegmentRangeInclusive,
+    header: &SegmentHeader,
+    rows: u64,
+    previous: Option<&SegmentHeader>,
+) -> Option<String> {
+    let expected_range = header.expected_block_range();
+    if header.segment() != segment {
+        return Some(format!("header is for segment {}", header.segment()))
+    }
+    if expected_range.end() != file_range.end() || !file_range.contains(expected_range.start()) {
+        return Some(format!("expected block range {expected_range} doesn't match file name"))
+    }
+    if let Some(block_range) = header.block_range() &&
+        (!expected_range.contains(block_range.start()) ||
+            !expected_range.contains(block_range.end()))
+    {
+        return Some(format!(
+            "block range {block_range} is outside of expected block range {expected_range}"
+        ))
+    }
+
+    let expected_rows = if segment.is_tx_based() {
+        Some(header.tx_len().unwrap_or_default())
+    } else if segment.is_block_based() {
+        Some(header.block_len().unwrap_or_default())
+    } else {
+        // Changesets have one row per change.
+        None
+    };
+    if let Some(expected_rows) = expected_rows &&
+        rows != expected_rows
+    {
+        return Some(format!("has {rows} rows, header expects {expected_rows}"))
+    }
+
+    if let Some(previous) = previous {
+        if let (Some(previous_end), Some(start)) = (previous.block_end(), header.block_start()) &&
+            start != previous_end + 1
+        {
+            return Some(format!(
+                "starts at block {start}, previous static file ends at block {previous_end}"
+            ))
+        }
+        if let (Some(previous_end), Some(start)) = (previous.tx_end(), header.tx_start()) &&
+            start != previous_end + 1
+        {
+            return Some(format!(
+                "starts at transaction {start}, previous static file ends at transaction {previous_end}"
+            ))
+        }
+    }
+
+    None
+}
+
+/// Returns `true` if the receipts root of `block_number` can't be recomputed, because its
+/// receipts are pruned or it's before Byzantium.
+fn skip_receipts_root(
+    chain_spec: impl EthereumHardforks,
+    block_number: BlockNumber,
+    receipts_pruned_to: Option<BlockNumber>,
+) -> bool {
+    receipts_pruned_to.is_some_and(|pruned_to| block_number <= pruned_to) ||
+        !chain_spec.is_byzantium_active_at_block(block_number)
+}
+
+/// Returns an error if the `computed` root doesn't match the `expected` one of the header.
+fn root_mismatch(computed: B256, expected: B256) -> Option<String> {
+    (computed != expected)
+        .then(|| format!("computed root {computed} doesn't match header {expected}"))
+}
+
+/// Returns up to `samples` block numbers evenly spread over `1..=tip`, including the tip.
+fn sample_blocks(tip: BlockNumber, samples: u64) -> Vec<BlockNumber> {
+    let samples = samples.min(tip);
+    let mut blocks = (1..=samples).map(|i| i * tip / samples).collect::<Vec<_>>();
+    blocks.dedup();
+    blocks
+}
+
+/// Report of `reth db verify-snapshot`.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct VerificationReport {
+    /// Block of the `Finish` stage checkpoint.
+    tip: BlockNumber,
+    /// Number of passed checks.
+    passed: usize,
+    /// Number of failed checks.
+    failed: usize,
+    /// Number of skipped checks, e.g. of pruned data.
+    skipped: usize,
+    /// Results of the individual checks.
+    checks: Vec<CheckReport>,
+}
+
+impl VerificationReport {
+    fn push(
+        &mut self,
+        kind: CheckKind,
+        subject: impl Display,
+        status: CheckStatus,
+        error: Option<String>,
+    ) {
+        self.checks.push(CheckReport { kind, subject: subject.to_string(), status, error });
+    }
+
+    fn push_result(&mut self, kind: CheckKind, subject: impl Display, error: Option<String>) {
+        let status = if error.is_some() { CheckStatus::Failed } else { CheckStatus::Passed };
+        self.push(kind, subject, status, error);
+    }
+
+    fn count(&self, status: CheckStatus) -> usize {
+        self.checks.iter().filter(|check| check.status == status).count()
+    }
+}
+
+/// Result of a single check.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CheckReport {
+    kind: CheckKind,
+    /// The static file, segment or block that was checked.
+    subject: String,
+    status: CheckStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+enum CheckKind {
+    /// Static file header against its file name and the previous static file.
+    StaticFileHeader,
+    /// Stage checkpoint against the highest block of the static files of a segment.
+    StageCheckpoint,
+    /// Recomputed transactions root of a block.
+    TransactionsRoot,
+    /// Recomputed receipts root of a block.
+    ReceiptsRoot,
+    /// Recomputed state root of the tip.
+    StateRoot,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+enum CheckStatus {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::MAINNET;
+
+    #[test]
+    fn sample_blocks_spreads_up_to_tip() {
+        assert_eq!(sample_blocks(0, 100), Vec::<u64>::new());
+        assert_eq!(sample_blocks(3, 100), vec![1, 2, 3]);
+        assert_eq!(sample_blocks(1_000, 4), vec![250, 500, 750, 1_000]);
+        assert_eq!(sample_blocks(1_000, 0), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn skips_receipts_root_before_byzantium() {
+        let chain_spec = &*MAINNET;
+        assert!(skip_receipts_root(chain_spec, 4_369_999, None));
+        assert!(!skip_receipts_root(chain_spec, 4_370_000, None));
+        assert!(skip_receipts_root(chain_spec, 4_370_000, Some(4_370_000)));
+        assert!(!skip_receipts_root(chain_spec, 4_370_001, Some(4_370_000)));
+    }
+
+    #[test]
+    fn static_file_header_must_follow_previous_file() {
+        let file_range = SegmentRangeInclusive::new(10, 19);
+        let mut previous = SegmentHeader::new(
+            SegmentRangeInclusive::new(0, 9),
+            Some(SegmentRangeInclusive::new(0, 9)),
+            None,
+            StaticFileSegment::Headers,
+        );
+        let header = SegmentHeader::new(
+            file_range,
+            Some(SegmentRangeInclusive::new(10, 11)),
+            None,
+            StaticFileSegment::Headers,
+        );
+        let error = |segment, rows, previous| {
+            static_file_header_error(segment, file_range, &header, rows, previous)
+        };
+
+        assert_eq!(error(StaticFileSegment::Headers, 2, Some(&previous)), None);
+        assert!(error(StaticFileSegment::Headers, 3, Some(&previous)).is_some());
+        assert!(error(StaticFileSegment::Transactions, 2, Some(&previous)).is_some());
+
+        previous.prune(1);
+        assert!(error(StaticFileSegment::Headers, 2, Some(&previous)).is_some());
+    }
+}
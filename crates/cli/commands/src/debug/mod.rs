@@ -0,0 +1,30 @@
+//! Debugging tools that operate on a running node.
+
+use crate::common::CliNodeTypes;
+use clap::{Parser, Subcommand};
+
+pub mod replay_engine;
+
+/// `reth debug` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth debug` subcommands
+#[derive(Debug, Subcommand)]
+pub enum Subcommands {
+    /// Replay engine API messages recorded with `--debug.engine-api-store` against a node.
+    #[command(name = "replay-engine")]
+    ReplayEngine(replay_engine::Command),
+}
+
+impl Command {
+    /// Execute `debug` command
+    pub async fn execute<N: CliNodeTypes>(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::ReplayEngine(command) => command.execute::<N>().await,
+        }
+    }
+}
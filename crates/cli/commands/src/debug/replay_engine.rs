@@ -0,0 +1,218 @@
+//! Replay engine API messages recorded with `--debug.engine-api-store`.
+
+use crate::common::CliNodeTypes;
+use clap::Parser;
+use eyre::WrapErr;
+use jsonrpsee::http_client::HttpClientBuilder;
+use reth_engine_util::engine_store::{
+    EngineMessageStore, StoredEngineApiMessage, StoredEngineApiResponse,
+};
+use reth_fs_util as fs;
+use reth_node_api::{ExecutionPayload, ForkchoiceStatus, NodeTypes, PayloadTypes};
+use reth_rpc_api::{clients::RethEngineApiClient, RethNewPayloadInput};
+use reth_rpc_layer::{AuthClientLayer, JwtSecret};
+use std::{path::PathBuf, time::Duration};
+use tracing::*;
+
+/// The execution data type of the node.
+type ExecutionData<N> = <<N as NodeTypes>::Payload as PayloadTypes>::ExecutionData;
+
+/// `reth debug replay-engine` command
+///
+/// Sends the engine API messages of a store directory, in the order they were received, to the
+/// authenticated engine API of a running node and compares the engine responses to the recorded
+/// ones.
+///
+/// Payloads are sent with `reth_newPayload` and forkchoice updates with `reth_forkchoiceUpdated`,
+/// so payload attributes of recorded forkchoice updates are not replayed.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The path to the directory with the engine API messages recorded by
+    /// `--debug.engine-api-store`.
+    #[arg(long = "engine-api-store", value_name = "PATH")]
+    engine_api_store: PathBuf,
+
+    /// The URL of the authenticated engine API of the node to replay the messages against.
+    #[arg(long, value_name = "URL", default_value = "http://localhost:8551")]
+    engine_rpc_url: String,
+
+    /// The path to the JWT secret used to authenticate with the engine API.
+    #[arg(long, value_name = "PATH")]
+    jwt_secret: PathBuf,
+
+    /// Replays the messages with their recorded spacing, scaled by this factor.
+    ///
+    /// For example, `2` replays twice as fast as the messages were received. By default, messages
+    /// are sent as soon as the previous one was answered.
+    #[arg(long, value_name = "FACTOR", conflicts_with = "interval")]
+    speed: Option<f64>,
+
+    /// Fixed delay between two consecutive messages, e.g. `500ms`.
+    #[arg(long, value_name = "DURATION", value_parser = humantime::parse_duration)]
+    interval: Option<Duration>,
+
+    /// Stops before replaying the first payload above this block number.
+    #[arg(long, value_name = "BLOCK")]
+    stop_block: Option<u64>,
+}
+
+impl Command {
+    /// Execute `debug replay-engine` command
+    pub async fn execute<N: CliNodeTypes>(self) -> eyre::Result<()> {
+        if self.speed.is_some_and(|speed| speed <= 0.0) {
+            eyre::bail!("--speed must be positive")
+        }
+
+        let secret = JwtSecret::from_file(&self.jwt_secret).wrap_err_with(|| {
+            format!("failed to read JWT secret from {}", self.jwt_secret.display())
+        })?;
+        let middleware = tower::ServiceBuilder::default().layer(AuthClientLayer::new(secret));
+        let client = HttpClientBuilder::default()
+            .set_http_middleware(middleware)
+            .build(&self.engine_rpc_url)?;
+
+        let store = EngineMessageStore::new(self.engine_api_store.clone());
+        let mut stats = ReplayStats::default();
+        let mut last_timestamp = None;
+        for path in store.engine_messages_iter()? {
+            let message: StoredEngineApiMessage<<N as NodeTypes>::Payload> =
+                serde_json::from_slice(&fs::read(&path)?).wrap_err_with(|| {
+                    format!("failed to decode engine API message {}", path.display())
+                })?;
+
+            if let (Some(stop_block), StoredEngineApiMessage::NewPayload { payload }) =
+                (self.stop_block, &message) &&
+                payload.block_number() > stop_block
+            {
+                info!(target: "reth::cli", stop_block, "Reached stop block");
+                break
+            }
+
+            let timestamp = EngineMessageStore::message_timestamp(&path);
+            if let Some(delay) = self.delay(last_timestamp, timestamp) {
+                tokio::time::sleep(delay).await;
+            }
+            last_timestamp = timestamp;
+
+            let replayed = match message {
+                StoredEngineApiMessage::ForkchoiceUpdated { state, payload_attrs } => {
+                    if payload_attrs.is_some() {
+                        debug!(target: "reth::cli", head = %state.head_block_hash, "Dropping payload attributes of recorded forkchoice update");
+                    }
+                    RethEngineApiClient::<ExecutionData<N>>::reth_forkchoice_updated(&client, state)
+                        .await
+                        .map(|updated| StoredEngineApiResponse::ForkchoiceUpdated {
+                            status: ForkchoiceStatus::from_payload_status(
+                                &updated.payload_status.status,
+                            ),
+                        })
+                }
+                StoredEngineApiMessage::NewPayload { payload } => {
+                    // don't wait for persistence or caches, so that the timing matches the
+                    // recorded run as closely as possible
+                    RethEngineApiClient::<ExecutionData<N>>::reth_new_payload(
+                        &client,
+                        RethNewPayloadInput::ExecutionData(payload),
+                        Some(false),
+                        Some(false),
+                    )
+                    .await
+                    .map(|status| StoredEngineApiResponse::NewPayload { status: status.status })
+                }
+            };
+            stats.replayed += 1;
+
+            let message = path.display();
+            match (replayed, store.response_for(&path)?) {
+                (Ok(replayed), Some(recorded)) if replayed == recorded => {
+                    debug!(target: "reth::cli", %message, ?replayed, "Engine response matches recorded response");
+                }
+                (Ok(replayed), Some(recorded)) => {
+                    stats.mismatches += 1;
+                    warn!(target: "reth::cli", %message, ?recorded, ?replayed, "Engine response differs from recorded response");
+                }
+                (Ok(replayed), None) => {
+                    stats.unrecorded += 1;
+                    debug!(target: "reth::cli", %message, ?replayed, "No recorded response to compare against");
+                }
+                (Err(error), recorded) => {
+                    stats.errors += 1;
+                    warn!(target: "reth::cli", %message, ?recorded, %error, "Failed to replay engine API message");
+                }
+            }
+        }
+
+        info!(
+            target: "reth::cli",
+            replayed = stats.replayed,
+            mismatches = stats.mismatches,
+            errors = stats.errors,
+            unrecorded = stats.unrecorded,
+            "Finished replaying engine API messages"
+        );
+
+        if stats.mismatches > 0 || stats.errors > 0 {
+            eyre::bail!(
+                "{} of {} replayed engine responses differ from the recorded ones, {} messages failed",
+                stats.mismatches,
+                stats.replayed,
+                stats.errors
+            )
+        }
+
+        Ok(())
+    }
+
+    /// Returns how long to wait before sending the message received at `timestamp`, given that
+    /// the previous message was received at `last_timestamp`.
+    fn delay(&self, last_timestamp: Option<u64>, timestamp: Option<u64>) -> Option<Duration> {
+        if let Some(interval) = self.interval {
+            return last_timestamp.is_some().then_some(interval)
+        }
+        let speed = self.speed?;
+        let elapsed = timestamp?.saturating_sub(last_timestamp?);
+        Some(Duration::from_millis(elapsed).div_f64(speed))
+    }
+}
+
+/// Counters of a replay run.
+#[derive(Debug, Default)]
+struct ReplayStats {
+    /// Number of messages sent to the node.
+    replayed: usize,
+    /// Number of engine responses that differ from the recorded ones.
+    mismatches: usize,
+    /// Number of messages that the node failed to answer.
+    errors: usize,
+    /// Number of messages without a recorded response.
+    unrecorded: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replay_delay() {
+        let args = ["reth", "--engine-api-store", "store", "--jwt-secret", "jwt.hex"];
+
+        let cmd = Command::parse_from(args);
+        assert_eq!(cmd.delay(Some(1_000), Some(3_000)), None);
+
+        let cmd = Command::parse_from(args.into_iter().chain(["--speed", "2"]));
+        assert_eq!(cmd.delay(None, Some(1_000)), None);
+        assert_eq!(cmd.delay(Some(1_000), Some(3_000)), Some(Duration::from_secs(1)));
+
+        let cmd = Command::parse_from(args.into_iter().chain(["--interval", "500ms"]));
+        assert_eq!(cmd.delay(None, Some(1_000)), None);
+        assert_eq!(cmd.delay(Some(1_000), Some(3_000)), Some(Duration::from_millis(500)));
+
+        assert!(Command::try_parse_from(args.into_iter().chain([
+            "--speed",
+            "2",
+            "--interval",
+            "500ms"
+        ]))
+        .is_err());
+    }
+}
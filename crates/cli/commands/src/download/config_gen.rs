@@ -63,6 +63,8 @@ where
         (PruneSegment::AccountHistory, segments.account_history),
         (PruneSegment::StorageHistory, segments.storage_history),
         (PruneSegment::Bodies, segments.bodies_history),
+        (PruneSegment::AddressTransactions, segments.address_transactions),
+        (PruneSegment::LogIndex, segments.log_index),
     ]
     .into_iter()
     .filter_map(|(segment, mode)| mode.map(|m| (segment, m)))
@@ -212,6 +214,8 @@ pub(crate) fn config_for_selections(
         prune.segments.sender_recovery = Some(PruneMode::Full);
     }
     prune.segments.transaction_lookup = Some(PruneMode::Full);
+    prune.segments.address_transactions = Some(PruneMode::Full);
+    prune.segments.log_index = Some(PruneMode::Full);
 
     if let Some(mode) = selection_to_prune_mode(tx_sel, Some(MINIMUM_HISTORY_DISTANCE)) {
         prune.segments.bodies_history = Some(mode);
@@ -264,6 +268,8 @@ pub(crate) fn describe_prune_config(config: &Config) -> Vec<String> {
         ("receipts", segments.receipts),
         ("account_history", segments.account_history),
         ("storage_history", segments.storage_history),
+        ("address_transactions", segments.address_transactions),
+        ("log_index", segments.log_index),
     ]
     .into_iter()
     .filter_map(|(name, mode)| mode.map(|m| format!("{name}={}", format_mode(&m))))
@@ -293,6 +299,7 @@ mod tests {
             timestamp: 0,
             base_url: None,
             reth_version: None,
+            signature: None,
             components: BTreeMap::new(),
         }
     }
@@ -1,7 +1,9 @@
+use alloy_primitives::{hex, keccak256, B256};
 use blake3::Hasher;
-use eyre::Result;
+use eyre::{Result, WrapErr};
 use rayon::prelude::*;
 use reqwest::Client;
+use secp256k1::{ecdsa::Signature, Message, PublicKey, SecretKey, SECP256K1};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::BTreeMap,
@@ -47,6 +49,11 @@ pub struct SnapshotManifest {
     pub reth_version: Option<String>,
     /// Available snapshot components.
     pub components: BTreeMap<String, ComponentManifest>,
+    /// Hex encoded secp256k1 signature of the manifest by its publisher.
+    ///
+    /// See [`SnapshotManifest::sign`] for the signed message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
 }
 
 /// Manifest entry for a single snapshot component.
@@ -259,6 +266,18 @@ impl SnapshotComponentType {
 }
 
 impl SnapshotManifest {
+    /// Signs the manifest with the publisher key, replacing any previous signature.
+    ///
+    /// The signed message is the keccak256 hash of the manifest JSON without the `signature`
+    /// field, serialized compactly with sorted keys.
+    pub fn sign(&mut self, key: &SecretKey) -> Result<()> {
+        self.signature = None;
+        let hash = manifest_signing_hash(serde_json::to_value(&*self)?)?;
+        let signature = SECP256K1.sign_ecdsa(&Message::from_digest(hash.0), key);
+        self.signature = Some(hex::encode_prefixed(signature.serialize_compact()));
+        Ok(())
+    }
+
     fn base_url_or_empty(&self) -> &str {
         self.base_url.as_deref().unwrap_or("")
     }
@@ -524,6 +543,60 @@ impl SingleArchive {
     }
 }
 
+/// Parses a manifest from its JSON, verifying its signature if a publisher key is given.
+///
+/// The signature is checked against the JSON as published rather than the parsed manifest, so
+/// fields unknown to this version are covered too.
+pub fn parse_manifest(json: &str, public_key: Option<&PublicKey>) -> Result<SnapshotManifest> {
+    let value: serde_json::Value = serde_json::from_str(json)?;
+    if let Some(public_key) = public_key {
+        verify_manifest_signature(value.clone(), public_key)?;
+    }
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Verifies that the manifest JSON `value` was signed by `public_key`.
+fn verify_manifest_signature(value: serde_json::Value, public_key: &PublicKey) -> Result<()> {
+    let signature = value
+        .get("signature")
+        .and_then(|signature| signature.as_str())
+        .ok_or_else(|| eyre::eyre!("Snapshot manifest is not signed"))?;
+    let signature = hex::decode(signature)
+        .ok()
+        .and_then(|bytes| Signature::from_compact(&bytes).ok())
+        .ok_or_else(|| eyre::eyre!("Invalid snapshot manifest signature: {signature}"))?;
+
+    let hash = manifest_signing_hash(value)?;
+    SECP256K1
+        .verify_ecdsa(&Message::from_digest(hash.0), &signature, public_key)
+        .wrap_err("Snapshot manifest signature does not match the publisher key")
+}
+
+/// Returns the hash signed by the publisher of the manifest JSON `value`.
+fn manifest_signing_hash(mut value: serde_json::Value) -> Result<B256> {
+    let serde_json::Value::Object(fields) = &mut value else {
+        eyre::bail!("Snapshot manifest must be a JSON object")
+    };
+    fields.remove("signature");
+    Ok(keccak256(serde_json::to_vec(&sorted_json(value))?))
+}
+
+/// Sorts the keys of all objects of `value`, so the serialization doesn't depend on the order of
+/// the fields in the published JSON.
+fn sorted_json(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(fields) => {
+            let fields: BTreeMap<_, _> =
+                fields.into_iter().map(|(key, value)| (key, sorted_json(value))).collect();
+            serde_json::Value::Object(fields.into_iter().collect())
+        }
+        serde_json::Value::Array(values) => {
+            serde_json::Value::Array(values.into_iter().map(sorted_json).collect())
+        }
+        value => value,
+    }
+}
+
 /// Fetch a snapshot manifest from a URL.
 pub async fn fetch_manifest(manifest_url: &str) -> Result<SnapshotManifest> {
     let client = Client::new();
@@ -533,6 +606,11 @@ pub async fn fetch_manifest(manifest_url: &str) -> Result<SnapshotManifest> {
 }
 
 /// Package chunk archives from a source datadir and generate a manifest.
+///
+/// With a `previous` manifest, the chunks it already published completely are not packaged again
+/// if their static files are unchanged, the new manifest points to the previous archives instead.
+/// Only the archives of the new chunks are written to `output_dir` then, and they must be published
+/// next to the previous ones.
 pub fn generate_manifest(
     source_datadir: &Path,
     output_dir: &Path,
@@ -540,6 +618,7 @@ pub fn generate_manifest(
     block: u64,
     chain_id: u64,
     blocks_per_file: u64,
+    previous: Option<&SnapshotManifest>,
 ) -> Result<SnapshotManifest> {
     std::fs::create_dir_all(output_dir)?;
 
@@ -557,6 +636,7 @@ pub fn generate_manifest(
         let key = ty.key();
         let num_chunks = block.div_ceil(blocks_per_file);
         let mut planned_chunks = Vec::with_capacity(num_chunks as usize);
+        let mut reused_chunks = Vec::new();
         let mut found_any = false;
 
         for i in 0..num_chunks {
@@ -572,6 +652,13 @@ pub fn generate_manifest(
             }
 
             found_any = true;
+            if let Some(previous) = previous &&
+                let Some(reused) =
+                    reusable_chunk(previous, *ty, blocks_per_file, i, &source_files)?
+            {
+                reused_chunks.push(reused);
+                continue;
+            }
             planned_chunks.push(PlannedChunk {
                 chunk_idx: i,
                 archive_path: output_dir.join(chunk_filename(key, start, end)),
@@ -592,6 +679,8 @@ pub fn generate_manifest(
                 .into_iter()
                 .collect::<Result<Vec<_>>>()?;
 
+            let reused = reused_chunks.len();
+            packaged_chunks.extend(reused_chunks);
             packaged_chunks.sort_unstable_by_key(|chunk| chunk.chunk_idx);
             let chunk_sizes = packaged_chunks.iter().map(|chunk| chunk.size).collect::<Vec<_>>();
             let chunk_output_files =
@@ -600,6 +689,7 @@ pub fn generate_manifest(
             info!(target: "reth::cli",
                 component = ty.display_name(),
                 chunks = chunk_sizes.len(),
+                reused,
                 total_blocks = block,
                 size = %super::DownloadProgress::format_size(total_size),
                 "Found chunked component"
@@ -661,6 +751,7 @@ pub fn generate_manifest(
         timestamp,
         base_url: base_url.map(str::to_owned),
         reth_version: Some(reth_node_core::version::version_metadata().short_version.to_string()),
+        signature: None,
         components,
     })
 }
@@ -670,6 +761,58 @@ pub fn chunk_filename(component_key: &str, start: u64, end: u64) -> String {
     format!("{component_key}-{start}-{end}.tar.zst")
 }
 
+/// Returns the chunk `chunk_idx` of `component` published by the `previous` manifest, if it was
+/// complete there and its static files still have the same sizes and checksums as the
+/// `source_files`.
+///
+/// The sizes are compared first so that a chunk that was produced differently, e.g. from a
+/// datadir that was pruned or resynced since, is usually rejected without hashing its files.
+fn reusable_chunk(
+    previous: &SnapshotManifest,
+    component: SnapshotComponentType,
+    blocks_per_file: u64,
+    chunk_idx: u64,
+    source_files: &[PathBuf],
+) -> Result<Option<PackagedChunk>> {
+    let Some(ComponentManifest::Chunked(chunked)) = previous.component(component) else {
+        return Ok(None)
+    };
+    if chunked.blocks_per_file != blocks_per_file ||
+        (chunk_idx + 1) * blocks_per_file > chunked.total_blocks
+    {
+        return Ok(None)
+    }
+    let (Some(&size), Some(output_files)) = (
+        chunked.chunk_sizes.get(chunk_idx as usize),
+        chunked.chunk_output_files.get(chunk_idx as usize),
+    ) else {
+        return Ok(None)
+    };
+    if output_files.len() != source_files.len() {
+        return Ok(None)
+    }
+
+    for source_path in source_files {
+        let Some(file_name) = source_path.file_name() else { return Ok(None) };
+        let path = PathBuf::from("static_files").join(file_name);
+        let path = path.to_string_lossy();
+        let source_size = std::fs::metadata(source_path)?.len();
+        let Some(recorded) =
+            output_files.iter().find(|file| file.path == path && file.size == source_size)
+        else {
+            return Ok(None)
+        };
+
+        let mut reader = HashingReader::new(std::fs::File::open(source_path)?);
+        std::io::copy(&mut reader, &mut std::io::sink())?;
+        if reader.finalize() != recorded.blake3 {
+            return Ok(None)
+        }
+    }
+
+    Ok(Some(PackagedChunk { chunk_idx, size, output_files: output_files.clone() }))
+}
+
 #[derive(Debug)]
 struct PlannedChunk {
     chunk_idx: u64,
@@ -946,6 +1089,7 @@ mod tests {
             timestamp: 0,
             base_url: Some("https://example.com".to_string()),
             reth_version: None,
+            signature: None,
             components,
         }
     }
@@ -998,6 +1142,7 @@ mod tests {
             timestamp: 0,
             base_url: Some("https://example.com".to_string()),
             reth_version: None,
+            signature: None,
             components,
         };
 
@@ -1070,6 +1215,7 @@ mod tests {
             timestamp: 0,
             base_url: Some("https://example.com".to_string()),
             reth_version: None,
+            signature: None,
             components,
         };
         let urls = m.archive_urls(SnapshotComponentType::StorageChangesets);
@@ -1150,6 +1296,7 @@ mod tests {
             timestamp: 0,
             base_url: Some("https://example.com".to_string()),
             reth_version: None,
+            signature: None,
             components,
         };
 
@@ -1210,6 +1357,7 @@ mod tests {
             timestamp: 0,
             base_url: Some("https://example.com".to_string()),
             reth_version: None,
+            signature: None,
             components,
         };
 
@@ -1235,7 +1383,7 @@ mod tests {
         std::fs::write(db_dir.join("mdbx.dat"), b"state-data").unwrap();
 
         let manifest =
-            generate_manifest(source.path(), output.path(), None, 0, 1, 500_000).unwrap();
+            generate_manifest(source.path(), output.path(), None, 0, 1, 500_000, None).unwrap();
 
         let state = manifest.component(SnapshotComponentType::State).unwrap();
         let ComponentManifest::Single(state) = state else {
@@ -1260,7 +1408,7 @@ mod tests {
         std::fs::write(rocksdb_dir.join("CURRENT"), b"MANIFEST-000001").unwrap();
 
         let manifest =
-            generate_manifest(source.path(), output.path(), None, 0, 1, 500_000).unwrap();
+            generate_manifest(source.path(), output.path(), None, 0, 1, 500_000, None).unwrap();
 
         let rocksdb = manifest.component(SnapshotComponentType::RocksdbIndices).unwrap();
         let ComponentManifest::Single(rocksdb) = rocksdb else {
@@ -1272,4 +1420,67 @@ mod tests {
         assert_eq!(rocksdb.output_files[0].path, "rocksdb/CURRENT");
         assert!(output.path().join("rocksdb_indices.tar.zst").exists());
     }
+
+    #[test]
+    fn generate_manifest_reuses_complete_chunks_of_previous_manifest() {
+        let source = tempdir().unwrap();
+        let db_dir = source.path().join("db");
+        std::fs::create_dir_all(&db_dir).unwrap();
+        std::fs::write(db_dir.join("mdbx.dat"), b"state-data").unwrap();
+        let static_files = source.path().join("static_files");
+        std::fs::create_dir_all(&static_files).unwrap();
+        std::fs::write(static_files.join("static_file_headers_0_9"), b"headers-0").unwrap();
+        std::fs::write(static_files.join("static_file_headers_10_19"), b"headers-10").unwrap();
+
+        let first = tempdir().unwrap();
+        let previous =
+            generate_manifest(source.path(), first.path(), None, 20, 1, 10, None).unwrap();
+
+        std::fs::write(static_files.join("static_file_headers_20_29"), b"headers-20").unwrap();
+        // The second chunk changed since the previous snapshot and must be packaged again, even
+        // though its size is unchanged.
+        std::fs::write(static_files.join("static_file_headers_10_19"), b"headers-11").unwrap();
+
+        let second = tempdir().unwrap();
+        let manifest =
+            generate_manifest(source.path(), second.path(), None, 25, 1, 10, Some(&previous))
+                .unwrap();
+
+        assert!(!second.path().join("headers-0-9.tar.zst").exists());
+        assert!(second.path().join("headers-10-19.tar.zst").exists());
+        assert!(second.path().join("headers-20-29.tar.zst").exists());
+
+        let (Some(ComponentManifest::Chunked(previous)), Some(ComponentManifest::Chunked(headers))) = (
+            previous.component(SnapshotComponentType::Headers),
+            manifest.component(SnapshotComponentType::Headers),
+        ) else {
+            panic!("headers should be chunked")
+        };
+        assert_eq!(headers.chunk_sizes.len(), 3);
+        assert_eq!(headers.chunk_sizes[0], previous.chunk_sizes[0]);
+        assert_eq!(headers.chunk_output_files[0], previous.chunk_output_files[0]);
+        assert_ne!(headers.chunk_output_files[1], previous.chunk_output_files[1]);
+    }
+
+    #[test]
+    fn signed_manifest_verifies_with_publisher_key() {
+        let key = SecretKey::from_byte_array(&[7; 32]).unwrap();
+        let public_key = key.public_key(SECP256K1);
+        let other_key = SecretKey::from_byte_array(&[8; 32]).unwrap().public_key(SECP256K1);
+
+        let mut manifest = test_manifest();
+        let unsigned = serde_json::to_string(&manifest).unwrap();
+        assert!(parse_manifest(&unsigned, None).is_ok());
+        assert!(parse_manifest(&unsigned, Some(&public_key)).is_err());
+
+        manifest.sign(&key).unwrap();
+        let signed = serde_json::to_string_pretty(&manifest).unwrap();
+        let parsed = parse_manifest(&signed, Some(&public_key)).unwrap();
+        assert_eq!(parsed.block, manifest.block);
+        assert!(parse_manifest(&signed, Some(&other_key)).is_err());
+
+        let tampered = signed.replace("\"block\": 1500000", "\"block\": 1500001");
+        assert_ne!(tampered, signed);
+        assert!(parse_manifest(&tampered, Some(&public_key)).is_err());
+    }
 }
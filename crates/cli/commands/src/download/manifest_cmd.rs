@@ -1,18 +1,33 @@
-use crate::download::manifest::generate_manifest;
+use crate::download::{
+    manifest::{generate_manifest, SnapshotManifest},
+    source::fetch_manifest_from_source,
+};
+use alloy_primitives::U64;
 use clap::Parser;
 use eyre::{Result, WrapErr};
+use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
+use reth_cli_util::get_secret_key;
 use reth_db::{mdbx::DatabaseArguments, open_db_read_only, tables, Database};
 use reth_db_api::transaction::DbTx;
 use reth_primitives_traits::FastInstant as Instant;
+use reth_rpc_api::clients::AdminApiClient;
 use reth_stages_types::StageId;
 use reth_static_file_types::DEFAULT_BLOCKS_PER_STATIC_FILE;
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
 use tracing::{info, warn};
 
+/// Interval in which the pause of the persistence of the node is renewed while packaging.
+///
+/// The node resumes its persistence if the pause isn't renewed within 5 minutes.
+const PAUSE_RENEW_INTERVAL: Duration = Duration::from_secs(60);
+
 /// Generate modular chunk archives and a snapshot manifest from a source datadir.
 ///
 /// Archive naming convention:
 ///   - Chunked: `{component}-{start}-{end}.tar.zst` (e.g. `transactions-0-499999.tar.zst`)
+///
+/// The source datadir can belong to a running node if its RPC is given with `--rpc-url`, the
+/// persistence of the node is paused while the archives are packaged.
 #[derive(Debug, Parser)]
 pub struct SnapshotManifestCommand {
     /// Source datadir containing static files.
@@ -20,6 +35,9 @@ pub struct SnapshotManifestCommand {
     source_datadir: PathBuf,
 
     /// Optional base URL where archives will be hosted.
+    ///
+    /// With `--previous-manifest`, this must be where the archives of the previous manifest are
+    /// hosted.
     #[arg(long)]
     base_url: Option<String>,
 
@@ -30,6 +48,9 @@ pub struct SnapshotManifestCommand {
     /// Block number this snapshot was taken at.
     ///
     /// If omitted, this is inferred from the source datadir's `Finish` stage checkpoint.
+    ///
+    /// With `--rpc-url`, the node persists the blocks up to this one before pausing its
+    /// persistence. If omitted, the persistence is paused at the last persisted block.
     #[arg(long)]
     block: Option<u64>,
 
@@ -42,12 +63,100 @@ pub struct SnapshotManifestCommand {
     /// If omitted, this is inferred from header static file ranges in the source datadir.
     #[arg(long)]
     blocks_per_file: Option<u64>,
+
+    /// HTTP RPC URL of the node running on the source datadir.
+    ///
+    /// The persistence of the node is paused with `admin_pausePersistence` while the archives are
+    /// packaged, so the snapshot is consistent, and resumed afterwards. The pause is renewed every
+    /// minute, so the node lifts it within 5 minutes if this command exits without resuming the
+    /// persistence.
+    #[arg(long, value_name = "URL")]
+    rpc_url: Option<String>,
+
+    /// Manifest of the previous snapshot, as an HTTP(S) URL, `file://` URL, or local path.
+    ///
+    /// Chunks that were complete in the previous snapshot and didn't change are not packaged
+    /// again, the manifest points to the previously published archives instead.
+    #[arg(long, value_name = "PATH|URL")]
+    previous_manifest: Option<String>,
+
+    /// Path to the secp256k1 secret key the manifest is signed with.
+    ///
+    /// The key is created if the file doesn't exist. Downloaders verify the signature with the
+    /// matching public key, see `reth download --manifest-public-key`.
+    #[arg(long, value_name = "PATH")]
+    signing_key: Option<PathBuf>,
 }
 
 impl SnapshotManifestCommand {
     /// Packages snapshot archives and writes the manifest file.
-    pub fn execute(self) -> Result<()> {
-        let block = match self.block {
+    pub async fn execute(self) -> Result<()> {
+        let signing_key = self.signing_key.as_deref().map(get_secret_key).transpose()?;
+        let previous = match &self.previous_manifest {
+            Some(source) => Some(
+                fetch_manifest_from_source(source, None)
+                    .await
+                    .wrap_err_with(|| format!("Failed to load previous manifest from {source}"))?,
+            ),
+            None => None,
+        };
+
+        let Some(rpc_url) = self.rpc_url.clone() else {
+            return tokio::task::spawn_blocking(move || self.package(None, previous, signing_key))
+                .await?
+        };
+
+        let client = HttpClientBuilder::default()
+            .build(&rpc_url)
+            .wrap_err_with(|| format!("Invalid RPC URL: {rpc_url}"))?;
+        let paused = AdminApiClient::pause_persistence(&client, self.block.map(U64::from))
+            .await
+            .wrap_err("Failed to pause persistence of the node")?;
+        info!(target: "reth::cli", block = paused.number, hash = %paused.hash, "Paused persistence of the node");
+
+        let mut package = tokio::task::spawn_blocking(move || {
+            self.package(Some(paused.number), previous, signing_key)
+        });
+        // the node lifts the pause if it isn't renewed in time
+        let mut renew = tokio::time::interval(PAUSE_RENEW_INTERVAL);
+        renew.tick().await;
+        let result = loop {
+            tokio::select! {
+                result = &mut package => {
+                    break result.map_err(Into::into).and_then(|result| result)
+                }
+                _ = tokio::signal::ctrl_c() => {
+                    break Err(eyre::eyre!("Interrupted while packaging the snapshot"))
+                }
+                _ = renew.tick() => {
+                    match AdminApiClient::pause_persistence(&client, None).await {
+                        Ok(persisted) if persisted.number != paused.number => {
+                            break Err(eyre::eyre!(
+                                "Persistence of the node resumed while packaging the snapshot"
+                            ))
+                        }
+                        Ok(_) => {}
+                        Err(err) => {
+                            warn!(target: "reth::cli", %err, "Failed to renew the pause of the persistence of the node");
+                        }
+                    }
+                }
+            }
+        };
+
+        resume_persistence(&client).await?;
+        result
+    }
+
+    /// Packages the snapshot archives at `block`, or the block inferred from the source datadir,
+    /// and writes the manifest file.
+    fn package(
+        self,
+        block: Option<u64>,
+        previous: Option<SnapshotManifest>,
+        signing_key: Option<secp256k1::SecretKey>,
+    ) -> Result<()> {
+        let block = match block.or(self.block) {
             Some(block) => block,
             None => infer_snapshot_block(&self.source_datadir)?,
         };
@@ -61,18 +170,28 @@ impl SnapshotManifestCommand {
             output = ?self.output_dir,
             block,
             blocks_per_file,
+            previous_block = previous.as_ref().map(|previous| previous.block),
             "Packaging modular snapshot archives"
         );
         let start = Instant::now();
-        let manifest = generate_manifest(
+        let mut manifest = generate_manifest(
             &self.source_datadir,
             &self.output_dir,
             self.base_url.as_deref(),
             block,
             self.chain_id,
             blocks_per_file,
+            previous.as_ref(),
         )?;
 
+        if let Some(key) = signing_key {
+            manifest.sign(&key)?;
+            info!(target: "reth::cli",
+                public_key = %key.public_key(secp256k1::SECP256K1),
+                "Signed manifest"
+            );
+        }
+
         let num_components = manifest.components.len();
         let json = serde_json::to_string_pretty(&manifest)?;
         let output = self.output_dir.join("manifest.json");
@@ -89,6 +208,15 @@ impl SnapshotManifestCommand {
     }
 }
 
+/// Resumes the persistence of the node paused for the snapshot.
+async fn resume_persistence(client: &HttpClient) -> Result<()> {
+    let persisted = AdminApiClient::resume_persistence(client).await.wrap_err(
+        "Failed to resume persistence of the node, call `admin_resumePersistence` to resume it",
+    )?;
+    info!(target: "reth::cli", block = persisted.number, "Resumed persistence of the node");
+    Ok(())
+}
+
 /// Infers the snapshot block from the source datadir.
 fn infer_snapshot_block(source_datadir: &std::path::Path) -> Result<u64> {
     if let Ok(block) = infer_snapshot_block_from_db(source_datadir) {
@@ -17,7 +17,8 @@
 //!   resulting config and database checkpoints.
 //!
 //! [`DownloadDefaults`] defines the discovery endpoints and default help text used when the command
-//! needs to discover a manifest instead of consuming an explicit source.
+//! needs to discover a manifest instead of consuming an explicit source, and the publisher key that
+//! manifests must be signed with, if any.
 //!
 //! ## Selection and planning
 //!
@@ -95,7 +96,7 @@ use archive::run_modular_downloads;
 use clap::{builder::RangedU64ValueParser, Parser};
 use config_gen::{config_for_selections, write_config};
 use extract::stream_and_extract;
-use eyre::Result;
+use eyre::{Result, WrapErr};
 use manifest::{ComponentSelection, SnapshotComponentType, SnapshotManifest};
 use planning::{collect_planned_archives, summarize_download_startup};
 use progress::{DownloadProgress, DownloadRequestLimiter};
@@ -107,6 +108,7 @@ use reth_db_api::transaction::DbTx;
 use reth_fs_util as fs;
 use reth_node_core::args::DefaultPruningValues;
 use reth_prune_types::PruneMode;
+use secp256k1::PublicKey;
 use source::{
     discover_manifest_url, fetch_manifest_from_source, fetch_snapshot_api_entries,
     print_snapshot_listing, resolve_manifest_base_url,
@@ -166,6 +168,10 @@ pub struct DownloadDefaults {
     ///
     /// Defaults to `https://snapshots.reth.rs/api/snapshots`.
     pub snapshot_api_url: Cow<'static, str>,
+    /// Hex encoded secp256k1 public key that snapshot manifests must be signed with.
+    ///
+    /// Manifests are not verified when `None`.
+    pub manifest_public_key: Option<Cow<'static, str>>,
     /// Optional custom long help text that overrides the generated help
     pub long_help: Option<String>,
 }
@@ -191,6 +197,7 @@ impl DownloadDefaults {
             default_base_url: Cow::Borrowed(RETH_SNAPSHOTS_BASE_URL),
             default_chain_aware_base_url: None,
             snapshot_api_url: Cow::Borrowed(RETH_SNAPSHOTS_API_URL),
+            manifest_public_key: None,
             long_help: None,
         }
     }
@@ -270,6 +277,12 @@ impl DownloadDefaults {
         self
     }
 
+    /// Set the public key that snapshot manifests must be signed with.
+    pub fn with_manifest_public_key(mut self, public_key: impl Into<Cow<'static, str>>) -> Self {
+        self.manifest_public_key = Some(public_key.into());
+        self
+    }
+
     /// Builder: Set custom long help text, overriding the generated help
     pub fn with_long_help(mut self, help: impl Into<String>) -> Self {
         self.long_help = Some(help.into());
@@ -308,6 +321,13 @@ pub struct DownloadCommand<C: ChainSpecParser> {
     #[arg(long, value_name = "PATH", conflicts_with_all = ["url", "manifest_url"])]
     manifest_path: Option<PathBuf>,
 
+    /// Hex encoded secp256k1 public key of the snapshot publisher.
+    ///
+    /// When set, the manifest must be signed with the matching key, see
+    /// `reth snapshot-manifest --signing-key`. Overrides the default key of the download defaults.
+    #[arg(long, value_name = "PUBLIC_KEY", conflicts_with = "url")]
+    manifest_public_key: Option<PublicKey>,
+
     /// Include all transaction static files.
     #[arg(long, conflicts_with_all = ["with_txs_since", "with_txs_distance", "minimal", "full", "archive"])]
     with_txs: bool,
@@ -480,8 +500,22 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> DownloadCo
     async fn load_manifest(&self, chain_id: u64) -> Result<SnapshotManifest> {
         let manifest_source = self.resolve_manifest_source(chain_id).await?;
 
-        info!(target: "reth::cli", source = %manifest_source, "Fetching snapshot manifest");
-        let mut manifest = fetch_manifest_from_source(&manifest_source).await?;
+        let public_key = match self.manifest_public_key {
+            Some(public_key) => Some(public_key),
+            None => DownloadDefaults::get_global()
+                .manifest_public_key
+                .as_deref()
+                .map(|public_key| {
+                    public_key.parse::<PublicKey>().wrap_err_with(|| {
+                        format!("Invalid default manifest public key: {public_key}")
+                    })
+                })
+                .transpose()?,
+        };
+
+        info!(target: "reth::cli", source = %manifest_source, verify_signature = public_key.is_some(), "Fetching snapshot manifest");
+        let mut manifest =
+            fetch_manifest_from_source(&manifest_source, public_key.as_ref()).await?;
         manifest.base_url = Some(resolve_manifest_base_url(&manifest, &manifest_source)?);
 
         info!(target: "reth::cli",
@@ -956,6 +990,7 @@ mod tests {
             timestamp: 0,
             base_url: Some("https://example.com".to_string()),
             reth_version: None,
+            signature: None,
             components,
         }
     }
@@ -305,6 +305,7 @@ mod tests {
             timestamp: 0,
             base_url: Some("https://example.com".to_string()),
             reth_version: None,
+            signature: None,
             components,
         };
 
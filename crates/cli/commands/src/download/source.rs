@@ -1,7 +1,12 @@
-use super::{manifest::SnapshotManifest, progress::DownloadProgress, DownloadDefaults};
+use super::{
+    manifest::{parse_manifest, SnapshotManifest},
+    progress::DownloadProgress,
+    DownloadDefaults,
+};
 use eyre::{Result, WrapErr};
 use reqwest::Client;
 use reth_fs_util as fs;
+use secp256k1::PublicKey;
 use std::path::{Path, PathBuf};
 use tracing::info;
 use url::Url;
@@ -135,7 +140,12 @@ pub(crate) fn print_snapshot_listing(entries: &[SnapshotApiEntry], chain_id: u64
 }
 
 /// Loads a manifest from an HTTP(S) URL, `file://` URL, or local path.
-pub(crate) async fn fetch_manifest_from_source(source: &str) -> Result<SnapshotManifest> {
+///
+/// If a publisher key is given, the manifest must be signed by it.
+pub(crate) async fn fetch_manifest_from_source(
+    source: &str,
+    public_key: Option<&PublicKey>,
+) -> Result<SnapshotManifest> {
     if let Ok(parsed) = Url::parse(source) {
         return match parsed.scheme() {
             "http" | "https" => {
@@ -159,21 +169,21 @@ pub(crate) async fn fetch_manifest_from_source(source: &str) -> Result<SnapshotM
                              Available snapshot sources:\n{sources}"
                         )
                     })?;
-                Ok(response.json().await?)
+                parse_manifest(&response.text().await?, public_key)
             }
             "file" => {
                 let path = parsed
                     .to_file_path()
                     .map_err(|_| eyre::eyre!("Invalid file:// manifest path: {source}"))?;
                 let content = fs::read_to_string(path)?;
-                Ok(serde_json::from_str(&content)?)
+                parse_manifest(&content, public_key)
             }
             _ => Err(eyre::eyre!("Unsupported manifest URL scheme: {}", parsed.scheme())),
         };
     }
 
     let content = fs::read_to_string(source)?;
-    Ok(serde_json::from_str(&content)?)
+    parse_manifest(&content, public_key)
 }
 
 /// Resolves the base URL used to join relative archive paths in a manifest.
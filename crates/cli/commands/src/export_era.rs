@@ -38,8 +38,18 @@ pub struct ExportArgs {
     max_blocks_per_file: Option<u64>,
     /// The directory path where to export era1 files.
     /// The block data are read from the database.
+    /// A `checksums.txt` file with the SHA-256 checksum and name of every exported file is written
+    /// to it.
     #[arg(long, value_name = "EXPORT_ERA1_PATH", verbatim_doc_comment)]
     path: Option<PathBuf>,
+    /// File with the known epoch accumulator roots, one hex encoded root per line.
+    /// Every exported pre-merge era is checked against it and the export fails on a mismatch,
+    /// or if an era isn't exported from its first to its last block.
+    #[arg(long, value_name = "EPOCH_ACCUMULATORS_FILE", verbatim_doc_comment)]
+    epoch_accumulators: Option<PathBuf>,
+    /// Export pre-merge eras without verifying them against the epoch accumulators.
+    #[arg(long, conflicts_with = "epoch_accumulators", verbatim_doc_comment)]
+    skip_epoch_verification: bool,
 }
 
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ExportEraCommand<C> {
@@ -61,6 +71,11 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ExportEraC
                 .join(ERA1_EXPORT_FOLDER_NAME),
         };
 
+        let epoch_accumulators = match &self.export.epoch_accumulators {
+            Some(path) => era1::read_epoch_accumulators(path)?,
+            None => Vec::new(),
+        };
+
         let export_config = era1::ExportConfig {
             network: self.env.chain.chain().to_string(),
             first_block_number: self.export.first_block_number.unwrap_or(0),
@@ -73,6 +88,8 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ExportEraC
                 .max_blocks_per_file
                 .unwrap_or(MAX_BLOCKS_PER_ERA1 as u64),
             dir: data_dir,
+            epoch_accumulators,
+            skip_epoch_verification: self.export.skip_epoch_verification,
         };
 
         export_config.validate()?;
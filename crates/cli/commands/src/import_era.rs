@@ -6,7 +6,7 @@ use eyre::eyre;
 use reqwest::{Client, Url};
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
-use reth_era_downloader::{read_dir, EraClient, EraStream, EraStreamConfig};
+use reth_era_downloader::{read_dir, EraClient, EraStream, EraStreamConfig, MergeBlock};
 use reth_era_utils as era;
 use reth_etl::Collector;
 use reth_fs_util as fs;
@@ -24,6 +24,14 @@ pub struct ImportEraCommand<C: ChainSpecParser> {
 
     #[clap(flatten)]
     import: ImportArgs,
+
+    /// File with the known historical roots of the beacon chain, one hex encoded root per line.
+    ///
+    /// The roots of the `historical_roots` followed by the ones of the `historical_summaries` of
+    /// a beacon state, the root of era `N` being on line `N - 1`. Required to import ERA files,
+    /// whose beacon blocks are verified against them.
+    #[arg(long, value_name = "HISTORICAL_ROOTS_FILE", verbatim_doc_comment)]
+    historical_roots: Option<PathBuf>,
 }
 
 #[derive(Debug, Args)]
@@ -32,13 +40,15 @@ pub struct ImportArgs {
     /// The path to a directory for import.
     ///
     /// The ERA1 files are read from the local directory parsing headers and bodies.
+    /// ERA files are read instead if the directory contains them, extracting headers and bodies
+    /// from the execution payloads of their beacon blocks.
     #[arg(long, value_name = "IMPORT_ERA_PATH", verbatim_doc_comment)]
     path: Option<PathBuf>,
 
     /// The URL to a remote host where the ERA1 files are hosted.
     ///
     /// The ERA1 files are read from the remote host using HTTP GET requests parsing headers
-    /// and bodies.
+    /// and bodies. ERA files are read instead if the URL does not point to ERA1 files.
     #[arg(long, value_name = "IMPORT_ERA_URL", verbatim_doc_comment)]
     url: Option<Url>,
 }
@@ -75,6 +85,11 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ImportEraC
 
         let mut hash_collector = Collector::new(config.stages.etl.file_size, config.stages.etl.dir);
 
+        let historical_roots = match &self.historical_roots {
+            Some(path) => era::read_historical_roots(path)?,
+            None => Vec::new(),
+        };
+
         let next_block = provider_factory
             .static_file_provider()
             .get_highest_static_file_block(StaticFileSegment::Headers)
@@ -84,7 +99,7 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ImportEraC
         if let Some(path) = self.import.path {
             let stream = read_dir(path, next_block)?;
 
-            era::import(stream, &provider_factory, &mut hash_collector)?;
+            era::import(stream, &historical_roots, &provider_factory, &mut hash_collector)?;
         } else {
             let url = match self.import.url {
                 Some(url) => url,
@@ -95,11 +110,13 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ImportEraC
 
             fs::create_dir_all(&folder)?;
 
-            let config = EraStreamConfig::default().start_from(next_block);
+            let config = EraStreamConfig::default()
+                .start_from(next_block)
+                .with_merge(MergeBlock::from_chain_id(self.env.chain.chain().id()));
             let client = EraClient::new(Client::new(), url, folder);
             let stream = EraStream::new(client, config);
 
-            era::import(stream, &provider_factory, &mut hash_collector)?;
+            era::import(stream, &historical_roots, &provider_factory, &mut hash_collector)?;
         }
 
         Ok(())
@@ -28,7 +28,7 @@ pub struct Command {
     #[arg(long, value_name = "PATH")]
     pub p2p_secret_key: Option<PathBuf>,
 
-    /// NAT resolution method (any|none|upnp|publicip|extip:\<IP\>)
+    /// NAT resolution method (any|none|upnp|natpmp|publicip|extip:\<IP\>)
     #[arg(long, default_value = "any")]
     pub nat: NatResolver,
 
@@ -18,7 +18,7 @@ use reth_db_common::{
 use reth_node_api::{HeaderTy, ReceiptTy, TxTy};
 use reth_node_core::args::StageEnum;
 use reth_provider::{
-    DBProvider, RocksDBProviderFactory, StaticFileProviderFactory, StaticFileWriter,
+    metadata, DBProvider, RocksDBProviderFactory, StaticFileProviderFactory, StaticFileWriter,
     StorageSettingsCache,
 };
 use reth_prune::PruneSegment;
@@ -231,6 +231,21 @@ impl<C: ChainSpecParser> Command<C> {
                 reset_stage_checkpoint(tx, StageId::TransactionLookup)?;
                 insert_genesis_header(&provider_rw, &self.env.chain)?;
             }
+            StageEnum::AddressTransactions => {
+                tx.clear::<tables::AddressTransactions>()?;
+                tx.delete::<tables::Metadata>(
+                    metadata::keys::ADDRESS_TRANSACTIONS_START.to_string(),
+                    None,
+                )?;
+                reset_prune_checkpoint(tx, PruneSegment::AddressTransactions)?;
+                reset_stage_checkpoint(tx, StageId::IndexAddressTransactions)?;
+            }
+            StageEnum::LogIndex => {
+                tx.clear::<tables::LogIndex>()?;
+                tx.delete::<tables::Metadata>(metadata::keys::LOG_INDEX_START.to_string(), None)?;
+                reset_prune_checkpoint(tx, PruneSegment::LogIndex)?;
+                reset_stage_checkpoint(tx, StageId::IndexLogs)?;
+            }
         }
 
         tx.put::<tables::StageCheckpoints>(StageId::Finish.to_string(), Default::default())?;
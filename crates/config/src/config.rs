@@ -15,6 +15,19 @@ const EXTENSION: &str = "toml";
 /// The default prune block interval
 pub const DEFAULT_BLOCK_INTERVAL: usize = 5;
 
+/// Settings of the [`Config`] that a running node applies when the config file is reloaded, by
+/// their path in the file.
+///
+/// Changes to any other setting only take effect after a restart.
+pub const RELOADABLE_SETTINGS: &[&str] = &[
+    "peers.connection_info",
+    "peers.trusted_nodes",
+    "peers.trusted_nodes_only",
+    "peers.banned_nodes",
+    "peers.banned_ips",
+    "prune.block_interval",
+];
+
 /// Configuration for the reth node.
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -69,6 +82,15 @@ impl Config {
         }
     }
 
+    /// Load a [`Config`] from a specified path.
+    ///
+    /// Unlike [`Self::from_path`], a missing file is an error and is not created.
+    pub fn from_existing_path(path: impl AsRef<Path>) -> eyre::Result<Self> {
+        let cfg_string = std::fs::read_to_string(path)
+            .map_err(|e| eyre::eyre!("Failed to load configuration: {e}"))?;
+        toml::from_str(&cfg_string).map_err(|e| eyre::eyre!("Failed to parse TOML: {e}"))
+    }
+
     /// Returns the [`PeersConfig`] for the node.
     ///
     /// If a peers file is provided, the basic nodes from the file are added to the configuration.
@@ -82,6 +104,33 @@ impl Config {
             .unwrap_or_else(|_| self.peers.clone())
     }
 
+    /// Replaces the [`RELOADABLE_SETTINGS`] with the ones of `new` and returns all settings that
+    /// differ between the two configs.
+    ///
+    /// The other settings are kept, so they're reported again by the next reload until the node is
+    /// restarted.
+    pub fn reload(&mut self, new: Self) -> eyre::Result<ConfigChanges> {
+        let old = toml::Value::try_from(&*self)
+            .map_err(|e| eyre::eyre!("Failed to serialize to TOML: {e}"))?;
+        let new_value = toml::Value::try_from(&new)
+            .map_err(|e| eyre::eyre!("Failed to serialize to TOML: {e}"))?;
+
+        let mut changed = Vec::new();
+        changed_settings(&old, &new_value, "", &mut changed);
+        let (applied, requires_restart) =
+            changed.into_iter().partition(|setting| is_reloadable_setting(setting));
+
+        let Self { prune, peers, .. } = new;
+        self.prune.block_interval = prune.block_interval;
+        self.peers.connection_info = peers.connection_info;
+        self.peers.trusted_nodes = peers.trusted_nodes;
+        self.peers.trusted_nodes_only = peers.trusted_nodes_only;
+        self.peers.banned_nodes = peers.banned_nodes;
+        self.peers.banned_ips = peers.banned_ips;
+
+        Ok(ConfigChanges { applied, requires_restart })
+    }
+
     /// Save the configuration to toml file.
     pub fn save(&self, path: &Path) -> Result<(), std::io::Error> {
         if path.extension() != Some(std::ffi::OsStr::new(EXTENSION)) {
@@ -99,6 +148,56 @@ impl Config {
     }
 }
 
+/// Settings that changed when reloading a [`Config`], see [`Config::reload`].
+///
+/// Settings are named by their path in the config file, e.g. `peers.connection_info.max_inbound`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigChanges {
+    /// Changed [`RELOADABLE_SETTINGS`].
+    pub applied: Vec<String>,
+    /// Changed settings that only take effect after a restart.
+    pub requires_restart: Vec<String>,
+}
+
+impl ConfigChanges {
+    /// Returns `true` if no setting changed.
+    pub const fn is_empty(&self) -> bool {
+        self.applied.is_empty() && self.requires_restart.is_empty()
+    }
+}
+
+/// Returns `true` if `setting` is one of the [`RELOADABLE_SETTINGS`] or nested in one of them.
+#[cfg(feature = "serde")]
+fn is_reloadable_setting(setting: &str) -> bool {
+    RELOADABLE_SETTINGS.iter().any(|reloadable| {
+        setting
+            .strip_prefix(reloadable)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+    })
+}
+
+/// Collects the paths of the values that differ between `old` and `new` into `changed`.
+///
+/// Tables are compared key by key, any other value as a whole.
+#[cfg(feature = "serde")]
+fn changed_settings(old: &toml::Value, new: &toml::Value, path: &str, changed: &mut Vec<String>) {
+    let (toml::Value::Table(old), toml::Value::Table(new)) = (old, new) else {
+        if old != new {
+            changed.push(path.to_string());
+        }
+        return
+    };
+
+    let keys = old.keys().chain(new.keys()).collect::<std::collections::BTreeSet<_>>();
+    for key in keys {
+        let path = if path.is_empty() { key.clone() } else { format!("{path}.{key}") };
+        match (old.get(key), new.get(key)) {
+            (Some(old), Some(new)) => changed_settings(old, new, &path, changed),
+            _ => changed.push(path),
+        }
+    }
+}
+
 /// Configuration for each stage in the pipeline.
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -110,6 +209,8 @@ pub struct StageConfig {
     pub headers: HeadersConfig,
     /// Body stage configuration.
     pub bodies: BodiesConfig,
+    /// Snap sync stage configuration.
+    pub snap_sync: SnapSyncConfig,
     /// Sender Recovery stage configuration.
     pub sender_recovery: SenderRecoveryConfig,
     /// Execution stage configuration.
@@ -128,6 +229,10 @@ pub struct StageConfig {
     pub index_account_history: IndexHistoryConfig,
     /// Index Storage History stage configuration.
     pub index_storage_history: IndexHistoryConfig,
+    /// Index Address Transactions stage configuration.
+    pub index_address_transactions: IndexHistoryConfig,
+    /// Index Logs stage configuration.
+    pub index_logs: IndexHistoryConfig,
     /// Common ETL related configuration.
     pub etl: EtlConfig,
 }
@@ -246,6 +351,42 @@ impl Default for BodiesConfig {
     }
 }
 
+/// Snap sync stage configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct SnapSyncConfig {
+    /// The number of account ranges the key space is split into, so that they can be downloaded
+    /// concurrently.
+    ///
+    /// Default: 16
+    pub account_tasks: usize,
+    /// The maximum number of requests to send concurrently.
+    ///
+    /// Default: 32
+    pub max_concurrent_requests: usize,
+    /// The soft limit for the size of a single response in bytes.
+    ///
+    /// Default: 512KB
+    pub response_bytes: u64,
+    /// The maximum number of accounts, storage slots and trie nodes to process before
+    /// committing the progress.
+    ///
+    /// Default: 500,000
+    pub commit_threshold: u64,
+}
+
+impl Default for SnapSyncConfig {
+    fn default() -> Self {
+        Self {
+            account_tasks: 16,
+            max_concurrent_requests: 32,
+            response_bytes: 512 * 1024,
+            commit_threshold: 500_000,
+        }
+    }
+}
+
 /// Sender recovery stage configuration.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -572,6 +713,16 @@ impl PruneConfig {
         self.segments.has_receipts_pruning()
     }
 
+    /// Validates the prune configuration.
+    ///
+    /// Returns an error if the block interval is zero or a prune mode leaves fewer blocks than its
+    /// segment requires.
+    pub fn validate(&self) -> eyre::Result<()> {
+        eyre::ensure!(self.block_interval > 0, "Prune block interval must be greater than 0");
+        self.segments.validate()?;
+        Ok(())
+    }
+
     /// Merges values from `other` into `self`.
     /// - `Option<PruneMode>` fields: set from `other` only if `self` is `None`.
     /// - `block_interval`: set from `other` only if `self.block_interval ==
@@ -588,6 +739,8 @@ impl PruneConfig {
                     account_history,
                     storage_history,
                     bodies_history,
+                    address_transactions,
+                    log_index,
                     receipts_log_filter,
                 },
             minimum_pruning_distance,
@@ -610,6 +763,9 @@ impl PruneConfig {
         self.segments.account_history = self.segments.account_history.or(account_history);
         self.segments.storage_history = self.segments.storage_history.or(storage_history);
         self.segments.bodies_history = self.segments.bodies_history.or(bodies_history);
+        self.segments.address_transactions =
+            self.segments.address_transactions.or(address_transactions);
+        self.segments.log_index = self.segments.log_index.or(log_index);
 
         if self.segments.receipts_log_filter.0.is_empty() && !receipts_log_filter.0.is_empty() {
             self.segments.receipts_log_filter = receipts_log_filter;
@@ -1122,6 +1278,8 @@ receipts = { distance = 16384 }
                 account_history: None,
                 storage_history: Some(PruneMode::Before(5000)),
                 bodies_history: None,
+                address_transactions: None,
+                log_index: Some(PruneMode::Distance(10_000)),
                 receipts_log_filter: ReceiptsLogPruneConfig(BTreeMap::from([(
                     Address::random(),
                     PruneMode::Full,
@@ -1139,6 +1297,8 @@ receipts = { distance = 16384 }
                 account_history: Some(PruneMode::Distance(2000)),
                 storage_history: Some(PruneMode::Distance(3000)),
                 bodies_history: None,
+                address_transactions: Some(PruneMode::Full),
+                log_index: Some(PruneMode::Full),
                 receipts_log_filter: ReceiptsLogPruneConfig(BTreeMap::from([
                     (Address::random(), PruneMode::Distance(1000)),
                     (Address::random(), PruneMode::Before(2000)),
@@ -1157,6 +1317,8 @@ receipts = { distance = 16384 }
         assert_eq!(config1.segments.receipts, Some(PruneMode::Distance(1000)));
         assert_eq!(config1.segments.account_history, Some(PruneMode::Distance(2000)));
         assert_eq!(config1.segments.storage_history, Some(PruneMode::Before(5000)));
+        assert_eq!(config1.segments.address_transactions, Some(PruneMode::Full));
+        assert_eq!(config1.segments.log_index, Some(PruneMode::Distance(10_000)));
         assert_eq!(config1.segments.receipts_log_filter, original_filter);
     }
 
@@ -1200,4 +1362,67 @@ connect_trusted_nodes_only = true
             assert!(conf.peers.trusted_nodes.contains(&node));
         }
     }
+
+    #[test]
+    fn test_reload_config() {
+        let mut config = Config::default();
+        assert!(config.reload(Config::default()).unwrap().is_empty());
+
+        let reth_toml = r#"
+    [stages.execution]
+    max_blocks = 1000
+
+    [peers]
+    trusted_nodes_only = true
+    banned_ips = ["10.0.0.1"]
+    ban_duration = "1h"
+
+    [peers.connection_info]
+    max_inbound = 10
+
+    [prune]
+    block_interval = 10
+
+    [prune.segments]
+    sender_recovery = "full"
+    "#;
+        let new: Config = toml::from_str(reth_toml).unwrap();
+
+        let changes = config.reload(new.clone()).unwrap();
+        assert_eq!(
+            changes.applied,
+            vec![
+                "peers.banned_ips",
+                "peers.connection_info.max_inbound",
+                "peers.trusted_nodes_only",
+                "prune.block_interval",
+            ]
+        );
+        assert_eq!(
+            changes.requires_restart,
+            vec![
+                "peers.ban_duration",
+                "prune.segments.sender_recovery",
+                "stages.execution.max_blocks"
+            ]
+        );
+
+        // only the reloadable settings were applied
+        assert_eq!(config.prune.block_interval, 10);
+        assert_eq!(config.prune.segments, PruneModes::default());
+        assert_eq!(config.peers.connection_info, new.peers.connection_info);
+        assert!(config.peers.trusted_nodes_only);
+        assert_eq!(config.stages, Config::default().stages);
+
+        let changes = config.reload(new).unwrap();
+        assert!(changes.applied.is_empty());
+        assert_eq!(
+            changes.requires_restart,
+            vec![
+                "peers.ban_duration",
+                "prune.segments.sender_recovery",
+                "stages.execution.max_blocks"
+            ]
+        );
+    }
 }
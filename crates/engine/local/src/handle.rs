@@ -0,0 +1,223 @@
+//! A handle to control a running [`LocalMiner`](crate::LocalMiner).
+
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{map::AddressMap, Address, B256, U256};
+use parking_lot::Mutex;
+use reth_evm::state_overrides::AccountStateOverride;
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+use tokio::sync::{mpsc, oneshot};
+
+/// Errors returned by a [`LocalMinerHandle`].
+#[derive(Debug, thiserror::Error)]
+pub enum LocalMinerError {
+    /// The miner stopped before handling the request.
+    #[error("local miner is not running")]
+    NotRunning,
+    /// The miner was not configured with a transaction pool.
+    #[error("automine requires a transaction pool")]
+    MissingPool,
+    /// The EVM configuration of the node doesn't support state overrides.
+    #[error("state overrides are not supported by the EVM configuration")]
+    StateOverridesUnsupported,
+    /// The payload attributes of the node can't be adjusted by the miner.
+    #[error("the payload attributes of the next block can't be adjusted")]
+    PayloadAttributesUnsupported,
+    /// The requested timestamp is not after the timestamp of the latest block.
+    #[error("timestamp {timestamp} is not greater than the latest block timestamp {latest}")]
+    TimestampTooLow {
+        /// The requested timestamp.
+        timestamp: u64,
+        /// The timestamp of the latest block.
+        latest: u64,
+    },
+    /// Advancing the chain failed.
+    #[error("failed to mine block: {0}")]
+    Mining(String),
+}
+
+/// Commands handled by the [`LocalMiner`](crate::LocalMiner).
+#[derive(Debug)]
+pub(crate) enum LocalMinerCommand {
+    /// Mines the given number of blocks, `interval` seconds apart.
+    Mine {
+        blocks: u64,
+        interval: Option<u64>,
+        tx: oneshot::Sender<Result<Vec<B256>, LocalMinerError>>,
+    },
+    /// Returns whether a block is mined for every new transaction.
+    GetAutomine(oneshot::Sender<bool>),
+    /// Enables or disables mining a block for every new transaction.
+    SetAutomine { enabled: bool, tx: oneshot::Sender<Result<(), LocalMinerError>> },
+    /// Mines blocks at a fixed interval, or disables interval mining.
+    SetIntervalMining { interval: Option<Duration>, tx: oneshot::Sender<()> },
+    /// Sets the exact timestamp of the next block.
+    SetNextBlockTimestamp { timestamp: u64, tx: oneshot::Sender<Result<(), LocalMinerError>> },
+    /// Moves the clock forward, returns the total offset.
+    IncreaseTime { seconds: u64, tx: oneshot::Sender<Result<i64, LocalMinerError>> },
+    /// Sets the clock to the given timestamp, returns the new offset.
+    SetTime { timestamp: u64, tx: oneshot::Sender<Result<i64, LocalMinerError>> },
+    /// Sets or removes a fixed timestamp interval between blocks, returns whether an interval was
+    /// set before.
+    SetBlockTimestampInterval {
+        interval: Option<u64>,
+        tx: oneshot::Sender<Result<bool, LocalMinerError>>,
+    },
+    /// Sets the fee recipient of all following blocks.
+    SetCoinbase { coinbase: Address, tx: oneshot::Sender<Result<(), LocalMinerError>> },
+    /// Sets the `PREVRANDAO` of the next block.
+    SetPrevRandao { prev_randao: B256, tx: oneshot::Sender<Result<(), LocalMinerError>> },
+    /// Mines a block on top of the latest block that applies the given account changes.
+    ModifyAccounts {
+        accounts: AddressMap<AccountStateOverride>,
+        tx: oneshot::Sender<Result<(), LocalMinerError>>,
+    },
+    /// Records the current head, returns the snapshot id.
+    Snapshot(oneshot::Sender<U256>),
+    /// Returns the recorded chain heads, by snapshot id.
+    Snapshots(oneshot::Sender<BTreeMap<U256, BlockNumHash>>),
+    /// Resets the chain to a snapshot, returns `false` if the snapshot doesn't exist.
+    Revert { id: U256, tx: oneshot::Sender<Result<bool, LocalMinerError>> },
+}
+
+/// A handle to control a [`LocalMiner`](crate::LocalMiner).
+///
+/// The handle can be created before the miner is spawned and is attached to it with
+/// [`LocalMiner::with_handle`](crate::LocalMiner::with_handle). Requests sent before that are
+/// handled once the miner runs.
+#[derive(Debug, Clone)]
+pub struct LocalMinerHandle {
+    to_miner: mpsc::UnboundedSender<LocalMinerCommand>,
+    from_handle: Arc<Mutex<Option<mpsc::UnboundedReceiver<LocalMinerCommand>>>>,
+}
+
+impl Default for LocalMinerHandle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LocalMinerHandle {
+    /// Creates a new handle that is not attached to a miner yet.
+    pub fn new() -> Self {
+        let (to_miner, from_handle) = mpsc::unbounded_channel();
+        Self { to_miner, from_handle: Arc::new(Mutex::new(Some(from_handle))) }
+    }
+
+    /// Attaches the handle to a miner.
+    ///
+    /// Returns `None` if the handle is already attached.
+    pub(crate) fn attach(&self) -> Option<mpsc::UnboundedReceiver<LocalMinerCommand>> {
+        self.from_handle.lock().take()
+    }
+
+    /// Sends a command to the miner and waits for the response.
+    async fn request<R>(
+        &self,
+        command: impl FnOnce(oneshot::Sender<R>) -> LocalMinerCommand,
+    ) -> Result<R, LocalMinerError> {
+        let (tx, rx) = oneshot::channel();
+        self.to_miner.send(command(tx)).map_err(|_| LocalMinerError::NotRunning)?;
+        rx.await.map_err(|_| LocalMinerError::NotRunning)
+    }
+
+    /// Mines `blocks` blocks, regardless of the mining mode.
+    ///
+    /// If `interval` is set, the timestamps of consecutive blocks are `interval` seconds apart.
+    /// Returns the hashes of the mined blocks.
+    pub async fn mine(
+        &self,
+        blocks: u64,
+        interval: Option<u64>,
+    ) -> Result<Vec<B256>, LocalMinerError> {
+        self.request(|tx| LocalMinerCommand::Mine { blocks, interval, tx }).await?
+    }
+
+    /// Returns `true` if a block is mined for every new transaction.
+    pub async fn automine(&self) -> Result<bool, LocalMinerError> {
+        self.request(LocalMinerCommand::GetAutomine).await
+    }
+
+    /// Enables or disables mining a block for every new transaction.
+    pub async fn set_automine(&self, enabled: bool) -> Result<(), LocalMinerError> {
+        self.request(|tx| LocalMinerCommand::SetAutomine { enabled, tx }).await?
+    }
+
+    /// Mines a block every `interval`, or stops mining on a timer if `interval` is `None`.
+    pub async fn set_interval_mining(
+        &self,
+        interval: Option<Duration>,
+    ) -> Result<(), LocalMinerError> {
+        self.request(|tx| LocalMinerCommand::SetIntervalMining { interval, tx }).await
+    }
+
+    /// Sets the exact timestamp of the next block.
+    pub async fn set_next_block_timestamp(&self, timestamp: u64) -> Result<(), LocalMinerError> {
+        self.request(|tx| LocalMinerCommand::SetNextBlockTimestamp { timestamp, tx }).await?
+    }
+
+    /// Moves the clock used for block timestamps forward by `seconds`.
+    ///
+    /// Returns the total offset to the wall clock in seconds.
+    pub async fn increase_time(&self, seconds: u64) -> Result<i64, LocalMinerError> {
+        self.request(|tx| LocalMinerCommand::IncreaseTime { seconds, tx }).await?
+    }
+
+    /// Sets the clock used for block timestamps to `timestamp`.
+    ///
+    /// Returns the offset to the wall clock in seconds.
+    pub async fn set_time(&self, timestamp: u64) -> Result<i64, LocalMinerError> {
+        self.request(|tx| LocalMinerCommand::SetTime { timestamp, tx }).await?
+    }
+
+    /// Sets or removes a fixed number of seconds between the timestamps of consecutive blocks.
+    ///
+    /// Returns `true` if an interval was set before.
+    pub async fn set_block_timestamp_interval(
+        &self,
+        interval: Option<u64>,
+    ) -> Result<bool, LocalMinerError> {
+        self.request(|tx| LocalMinerCommand::SetBlockTimestampInterval { interval, tx }).await?
+    }
+
+    /// Sets the fee recipient of all following blocks.
+    pub async fn set_coinbase(&self, coinbase: Address) -> Result<(), LocalMinerError> {
+        self.request(|tx| LocalMinerCommand::SetCoinbase { coinbase, tx }).await?
+    }
+
+    /// Sets the `PREVRANDAO` value of the next block.
+    pub async fn set_prev_randao(&self, prev_randao: B256) -> Result<(), LocalMinerError> {
+        self.request(|tx| LocalMinerCommand::SetPrevRandao { prev_randao, tx }).await?
+    }
+
+    /// Applies the given account changes on top of the latest block.
+    ///
+    /// The changes are committed by mining a new block, which also includes the pending
+    /// transactions of the pool.
+    pub async fn modify_accounts(
+        &self,
+        accounts: AddressMap<AccountStateOverride>,
+    ) -> Result<(), LocalMinerError> {
+        self.request(|tx| LocalMinerCommand::ModifyAccounts { accounts, tx }).await?
+    }
+
+    /// Records the current head of the chain, so it can be restored with
+    /// [`revert`](Self::revert).
+    ///
+    /// Returns the id of the snapshot.
+    pub async fn snapshot(&self) -> Result<U256, LocalMinerError> {
+        self.request(LocalMinerCommand::Snapshot).await
+    }
+
+    /// Returns the chain heads of all snapshots, by snapshot id.
+    pub async fn snapshots(&self) -> Result<BTreeMap<U256, BlockNumHash>, LocalMinerError> {
+        self.request(LocalMinerCommand::Snapshots).await
+    }
+
+    /// Unwinds the chain to the head recorded by the given snapshot.
+    ///
+    /// The snapshot and all snapshots taken after it are removed. Returns `false` if the snapshot
+    /// doesn't exist.
+    pub async fn revert(&self, id: U256) -> Result<bool, LocalMinerError> {
+        self.request(|tx| LocalMinerCommand::Revert { id, tx }).await?
+    }
+}
@@ -1,24 +1,33 @@
 //! Contains the implementation of the mining mode for the local engine.
 
-use alloy_primitives::{TxHash, B256};
+use crate::{
+    handle::{LocalMinerCommand, LocalMinerError, LocalMinerHandle},
+    payload::LocalPayloadAttributes,
+};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{map::AddressMap, Address, TxHash, B256, U256};
 use alloy_rpc_types_engine::ForkchoiceState;
 use eyre::OptionExt;
-use futures_util::{stream::Fuse, Stream, StreamExt};
+use futures_util::{
+    stream::{self, Fuse},
+    Stream, StreamExt,
+};
 use reth_engine_primitives::ConsensusEngineHandle;
+use reth_evm::state_overrides::{AccountStateOverride, BlockStateOverrides};
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_payload_primitives::{BuiltPayload, PayloadAttributesBuilder, PayloadKind, PayloadTypes};
-use reth_primitives_traits::{HeaderTy, SealedHeaderFor};
+use reth_primitives_traits::{HeaderTy, NodePrimitives, SealedHeaderFor};
 use reth_storage_api::BlockReader;
 use reth_transaction_pool::TransactionPool;
 use std::{
-    collections::VecDeque,
+    collections::{BTreeMap, VecDeque},
     fmt,
     future::Future,
     pin::Pin,
     task::{Context, Poll},
-    time::Duration,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
-use tokio::time::Interval;
+use tokio::{sync::mpsc, time::Interval};
 use tokio_stream::wrappers::ReceiverStream;
 use tracing::error;
 
@@ -82,6 +91,17 @@ impl<Pool: TransactionPool + Unpin> MiningMode<Pool> {
     pub fn trigger(trigger: impl Stream<Item = ()> + Send + Sync + 'static) -> Self {
         Self::Trigger(Box::pin(trigger))
     }
+
+    /// Constructor for a [`MiningMode::Trigger`] that never fires, so blocks are only mined on
+    /// request.
+    pub fn manual() -> Self {
+        Self::trigger(stream::pending())
+    }
+
+    /// Returns `true` if this is [`MiningMode::Instant`].
+    pub const fn is_instant(&self) -> bool {
+        matches!(self, Self::Instant { .. })
+    }
 }
 
 impl<Pool: TransactionPool + Unpin> Future for MiningMode<Pool> {
@@ -146,6 +166,73 @@ pub struct LocalMiner<T: PayloadTypes, B, Pool: TransactionPool + Unpin> {
     /// When set, the miner sleeps after `fork_choice_updated` before calling
     /// `resolve_kind`, giving the payload job time for multiple rebuild attempts.
     payload_wait_time: Option<Duration>,
+    /// The transaction pool, used to switch to [`MiningMode::Instant`] on request.
+    pool: Option<Pool>,
+    /// Commands sent by a [`LocalMinerHandle`].
+    commands: Option<mpsc::UnboundedReceiver<LocalMinerCommand>>,
+    /// State overrides of the EVM configuration, used to modify accounts.
+    state_overrides: Option<BlockStateOverrides>,
+    /// Settings applied to the payload attributes of the next blocks.
+    next_block: NextBlockSettings,
+    /// Applies [`NextBlockSettings`] to the payload attributes, only set if the payload
+    /// attributes implement [`LocalPayloadAttributes`].
+    apply_next_block: Option<ApplyNextBlockFn<T::PayloadAttributes>>,
+    /// Recorded chain heads and their latest block hashes, by snapshot id.
+    snapshots: BTreeMap<U256, ChainSnapshot<<T::BuiltPayload as BuiltPayload>::Primitives>>,
+    /// Id of the next snapshot.
+    next_snapshot_id: U256,
+}
+
+/// Settings for the payload attributes of the next blocks, configured through a
+/// [`LocalMinerHandle`].
+#[derive(Debug, Default)]
+struct NextBlockSettings {
+    /// Exact timestamp of the next block.
+    timestamp: Option<u64>,
+    /// Offset of the block timestamps to the wall clock, in seconds.
+    time_offset: i64,
+    /// Fixed number of seconds between the timestamps of consecutive blocks.
+    timestamp_interval: Option<u64>,
+    /// Fee recipient of all following blocks.
+    coinbase: Option<Address>,
+    /// `PREVRANDAO` of the next block.
+    prev_randao: Option<B256>,
+}
+
+/// Applies the [`NextBlockSettings`] to the payload attributes built on top of a parent with the
+/// given timestamp.
+type ApplyNextBlockFn<A> = fn(&mut NextBlockSettings, &mut A, u64);
+
+impl NextBlockSettings {
+    /// Applies the settings to the payload attributes of a block built on top of a parent with
+    /// the given timestamp.
+    ///
+    /// One-shot settings like the exact timestamp of the next block are consumed.
+    fn apply<A: LocalPayloadAttributes>(&mut self, attributes: &mut A, parent_timestamp: u64) {
+        if let Some(timestamp) = self.timestamp.take() {
+            attributes.set_timestamp(timestamp);
+        } else if let Some(interval) = self.timestamp_interval {
+            attributes.set_timestamp(parent_timestamp + interval.max(1));
+        } else if self.time_offset != 0 {
+            let timestamp = (unix_now() as i64).saturating_add(self.time_offset);
+            attributes.set_timestamp((timestamp.max(0) as u64).max(parent_timestamp + 1));
+        }
+        if let Some(coinbase) = self.coinbase {
+            attributes.set_suggested_fee_recipient(coinbase);
+        }
+        if let Some(prev_randao) = self.prev_randao.take() {
+            attributes.set_prev_randao(prev_randao);
+        }
+    }
+}
+
+/// Chain head recorded by a snapshot.
+#[derive(Debug)]
+struct ChainSnapshot<N: NodePrimitives> {
+    /// Latest block at the time of the snapshot.
+    header: SealedHeaderFor<N>,
+    /// Latest mined block hashes at the time of the snapshot.
+    block_hashes: VecDeque<B256>,
 }
 
 impl<T, B, Pool> LocalMiner<T, B, Pool>
@@ -176,6 +263,13 @@ where
             last_block_hashes: VecDeque::from([last_header.hash()]),
             last_header,
             payload_wait_time: None,
+            pool: None,
+            commands: None,
+            state_overrides: None,
+            next_block: NextBlockSettings::default(),
+            apply_next_block: None,
+            snapshots: BTreeMap::new(),
+            next_snapshot_id: U256::ZERO,
         }
     }
 
@@ -185,9 +279,33 @@ where
         self
     }
 
+    /// Sets the transaction pool, which allows switching to [`MiningMode::Instant`] through a
+    /// [`LocalMinerHandle`].
+    pub fn with_pool(mut self, pool: Pool) -> Self {
+        self.pool = Some(pool);
+        self
+    }
+
+    /// Sets the state overrides of the node's EVM configuration, if any.
+    ///
+    /// These are required to modify accounts through a [`LocalMinerHandle`].
+    pub fn with_state_overrides(mut self, state_overrides: Option<BlockStateOverrides>) -> Self {
+        self.state_overrides = state_overrides;
+        self
+    }
+
+    /// Attaches the given [`LocalMinerHandle`] to this miner.
+    ///
+    /// Does nothing if the handle is already attached to another miner.
+    pub fn with_handle(mut self, handle: &LocalMinerHandle) -> Self {
+        self.commands = handle.attach();
+        self
+    }
+
     /// Runs the [`LocalMiner`] in a loop, polling the miner and building payloads.
     pub async fn run(mut self) {
         let mut fcu_interval = tokio::time::interval(Duration::from_secs(1));
+        let mut commands = self.commands.take();
         loop {
             tokio::select! {
                 // Wait for the interval or the pool to receive a transaction
@@ -202,10 +320,196 @@ where
                         error!(target: "engine::local", "Error updating fork choice: {:?}", e);
                     }
                 }
+                Some(command) = next_command(&mut commands) => {
+                    self.on_command(command).await;
+                }
             }
         }
     }
 
+    /// Handles a command sent by a [`LocalMinerHandle`].
+    async fn on_command(&mut self, command: LocalMinerCommand) {
+        match command {
+            LocalMinerCommand::Mine { blocks, interval, tx } => {
+                let _ = tx.send(self.mine(blocks, interval).await);
+            }
+            LocalMinerCommand::GetAutomine(tx) => {
+                let _ = tx.send(self.mode.is_instant());
+            }
+            LocalMinerCommand::SetAutomine { enabled, tx } => {
+                let _ = tx.send(self.set_automine(enabled));
+            }
+            LocalMinerCommand::SetIntervalMining { interval, tx } => {
+                self.mode = interval.map(MiningMode::interval).unwrap_or_else(MiningMode::manual);
+                let _ = tx.send(());
+            }
+            LocalMinerCommand::SetNextBlockTimestamp { timestamp, tx } => {
+                let latest = self.last_header.timestamp();
+                let res = self.next_block_settings().and_then(|next_block| {
+                    if timestamp <= latest {
+                        return Err(LocalMinerError::TimestampTooLow { timestamp, latest })
+                    }
+                    next_block.timestamp = Some(timestamp);
+                    Ok(())
+                });
+                let _ = tx.send(res);
+            }
+            LocalMinerCommand::IncreaseTime { seconds, tx } => {
+                let seconds = i64::try_from(seconds).unwrap_or(i64::MAX);
+                let res = self.next_block_settings().map(|next_block| {
+                    next_block.time_offset = next_block.time_offset.saturating_add(seconds);
+                    next_block.time_offset
+                });
+                let _ = tx.send(res);
+            }
+            LocalMinerCommand::SetTime { timestamp, tx } => {
+                let res = self.next_block_settings().map(|next_block| {
+                    next_block.time_offset = (timestamp as i64).saturating_sub(unix_now() as i64);
+                    next_block.time_offset
+                });
+                let _ = tx.send(res);
+            }
+            LocalMinerCommand::SetBlockTimestampInterval { interval, tx } => {
+                let res = self.next_block_settings().map(|next_block| {
+                    std::mem::replace(&mut next_block.timestamp_interval, interval).is_some()
+                });
+                let _ = tx.send(res);
+            }
+            LocalMinerCommand::SetCoinbase { coinbase, tx } => {
+                let res = self
+                    .next_block_settings()
+                    .map(|next_block| next_block.coinbase = Some(coinbase));
+                let _ = tx.send(res);
+            }
+            LocalMinerCommand::SetPrevRandao { prev_randao, tx } => {
+                let res = self
+                    .next_block_settings()
+                    .map(|next_block| next_block.prev_randao = Some(prev_randao));
+                let _ = tx.send(res);
+            }
+            LocalMinerCommand::ModifyAccounts { accounts, tx } => {
+                let _ = tx.send(self.modify_accounts(accounts).await);
+            }
+            LocalMinerCommand::Snapshot(tx) => {
+                let id = self.next_snapshot_id;
+                self.next_snapshot_id += U256::from(1);
+                self.snapshots.insert(
+                    id,
+                    ChainSnapshot {
+                        header: self.last_header.clone(),
+                        block_hashes: self.last_block_hashes.clone(),
+                    },
+                );
+                let _ = tx.send(id);
+            }
+            LocalMinerCommand::Snapshots(tx) => {
+                let snapshots = self
+                    .snapshots
+                    .iter()
+                    .map(|(id, snapshot)| (*id, snapshot.header.num_hash()))
+                    .collect();
+                let _ = tx.send(snapshots);
+            }
+            LocalMinerCommand::Revert { id, tx } => {
+                let _ = tx.send(self.revert(id).await);
+            }
+        }
+    }
+
+    /// Mines `blocks` blocks, with timestamps `interval` seconds apart if set.
+    async fn mine(
+        &mut self,
+        blocks: u64,
+        interval: Option<u64>,
+    ) -> Result<Vec<B256>, LocalMinerError> {
+        let mut hashes = Vec::new();
+        for i in 0..blocks {
+            if let Some(interval) = interval.filter(|interval| i > 0 && *interval > 0) {
+                self.next_block.timestamp = Some(self.last_header.timestamp() + interval);
+            }
+            hashes.push(self.advance().await.map_err(mining_error)?);
+        }
+        // make the mined blocks canonical before responding
+        self.update_forkchoice_state().await.map_err(mining_error)?;
+        Ok(hashes)
+    }
+
+    /// Switches to or from [`MiningMode::Instant`].
+    fn set_automine(&mut self, enabled: bool) -> Result<(), LocalMinerError> {
+        if enabled && !self.mode.is_instant() {
+            let pool = self.pool.clone().ok_or(LocalMinerError::MissingPool)?;
+            self.mode = MiningMode::instant(pool, None);
+        } else if !enabled && self.mode.is_instant() {
+            self.mode = MiningMode::manual();
+        }
+        Ok(())
+    }
+
+    /// Returns the settings for the next blocks, if the payload attributes can be adjusted.
+    fn next_block_settings(&mut self) -> Result<&mut NextBlockSettings, LocalMinerError> {
+        if self.apply_next_block.is_none() {
+            return Err(LocalMinerError::PayloadAttributesUnsupported)
+        }
+        Ok(&mut self.next_block)
+    }
+
+    /// Mines a block on top of the latest block that applies the given account changes before
+    /// its transactions, so the changes are visible to `latest` reads and to the pool right away.
+    ///
+    /// The timestamp and `PREVRANDAO` configured for the next block are kept for the block after
+    /// it.
+    async fn modify_accounts(
+        &mut self,
+        accounts: AddressMap<AccountStateOverride>,
+    ) -> Result<(), LocalMinerError> {
+        let state_overrides =
+            self.state_overrides.clone().ok_or(LocalMinerError::StateOverridesUnsupported)?;
+        state_overrides.insert(self.last_header.num_hash(), accounts);
+
+        let timestamp = self.next_block.timestamp.take();
+        let prev_randao = self.next_block.prev_randao.take();
+        let res = self.mine(1, None).await;
+        self.next_block.timestamp = timestamp;
+        self.next_block.prev_randao = prev_randao;
+
+        if res.is_err() {
+            // don't let the changes leak into the next block if this one couldn't be mined
+            state_overrides.remove_from(self.last_header.number());
+        }
+        res.map(drop)
+    }
+
+    /// Unwinds the chain to the head recorded by the given snapshot.
+    ///
+    /// This relies on the engine tree unwinding the canonical chain if the forkchoice head is a
+    /// canonical ancestor of the current head.
+    async fn revert(&mut self, id: U256) -> Result<bool, LocalMinerError> {
+        let Some(ChainSnapshot { header, block_hashes }) = self.snapshots.remove(&id) else {
+            return Ok(false)
+        };
+        self.snapshots.retain(|snapshot_id, _| *snapshot_id < id);
+
+        self.last_header = header;
+        self.last_block_hashes = block_hashes;
+        self.update_forkchoice_state().await.map_err(mining_error)?;
+
+        // blocks built on top of the snapshot must not apply the overrides of the discarded ones
+        if let Some(state_overrides) = &self.state_overrides {
+            state_overrides.remove_from(self.last_header.number());
+        }
+
+        Ok(true)
+    }
+
+    /// Builds the payload attributes for the next block.
+    fn next_payload_attributes(&mut self) -> T::PayloadAttributes {
+        let mut attributes = self.payload_attributes_builder.build(&self.last_header);
+        if let Some(apply_next_block) = self.apply_next_block {
+            apply_next_block(&mut self.next_block, &mut attributes, self.last_header.timestamp());
+        }
+        attributes
+    }
+
     /// Returns current forkchoice state.
     fn forkchoice_state(&self) -> ForkchoiceState {
         ForkchoiceState {
@@ -235,14 +539,12 @@ where
 
     /// Generates payload attributes for a new block, passes them to FCU and inserts built payload
     /// through newPayload.
-    async fn advance(&mut self) -> eyre::Result<()> {
-        let res = self
-            .to_engine
-            .fork_choice_updated(
-                self.forkchoice_state(),
-                Some(self.payload_attributes_builder.build(&self.last_header)),
-            )
-            .await?;
+    ///
+    /// Returns the hash of the new block.
+    async fn advance(&mut self) -> eyre::Result<B256> {
+        let attributes = self.next_payload_attributes();
+        let res =
+            self.to_engine.fork_choice_updated(self.forkchoice_state(), Some(attributes)).await?;
 
         if !res.is_valid() {
             eyre::bail!("Invalid payload status")
@@ -268,13 +570,47 @@ where
             eyre::bail!("Invalid payload")
         }
 
-        self.last_block_hashes.push_back(header.hash());
+        let hash = header.hash();
+        self.last_block_hashes.push_back(hash);
         self.last_header = header;
         // ensure we keep at most 64 blocks
         if self.last_block_hashes.len() > 64 {
             self.last_block_hashes.pop_front();
         }
 
-        Ok(())
+        Ok(hash)
     }
 }
+
+impl<T, B, Pool> LocalMiner<T, B, Pool>
+where
+    T: PayloadTypes<PayloadAttributes: LocalPayloadAttributes>,
+    Pool: TransactionPool + Unpin,
+{
+    /// Allows a [`LocalMinerHandle`] to adjust the timestamp, fee recipient and `PREVRANDAO` of
+    /// the next blocks.
+    pub fn with_local_payload_attributes(mut self) -> Self {
+        self.apply_next_block = Some(NextBlockSettings::apply::<T::PayloadAttributes>);
+        self
+    }
+}
+
+/// Waits for the next command, or forever if there is no [`LocalMinerHandle`].
+async fn next_command(
+    commands: &mut Option<mpsc::UnboundedReceiver<LocalMinerCommand>>,
+) -> Option<LocalMinerCommand> {
+    match commands {
+        Some(commands) => commands.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Returns the current unix timestamp in seconds.
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+/// Converts an error of [`LocalMiner::advance`] into a [`LocalMinerError`].
+fn mining_error(err: eyre::Report) -> LocalMinerError {
+    LocalMinerError::Mining(format!("{err:#}"))
+}
@@ -9,6 +9,33 @@ use reth_payload_primitives::PayloadAttributesBuilder;
 use reth_primitives_traits::SealedHeader;
 use std::sync::Arc;
 
+/// Payload attributes that can be adjusted by the [`LocalMiner`](super::LocalMiner) before a block
+/// is built, for example to mine a block with a specific timestamp.
+pub trait LocalPayloadAttributes {
+    /// Sets the timestamp of the block.
+    fn set_timestamp(&mut self, timestamp: u64);
+
+    /// Sets the fee recipient of the block.
+    fn set_suggested_fee_recipient(&mut self, fee_recipient: Address);
+
+    /// Sets the `PREVRANDAO` value of the block.
+    fn set_prev_randao(&mut self, prev_randao: B256);
+}
+
+impl LocalPayloadAttributes for EthPayloadAttributes {
+    fn set_timestamp(&mut self, timestamp: u64) {
+        self.timestamp = timestamp;
+    }
+
+    fn set_suggested_fee_recipient(&mut self, fee_recipient: Address) {
+        self.suggested_fee_recipient = fee_recipient;
+    }
+
+    fn set_prev_randao(&mut self, prev_randao: B256) {
+        self.prev_randao = prev_randao;
+    }
+}
+
 /// The attributes builder for local Ethereum payload.
 #[derive(Debug)]
 #[non_exhaustive]
@@ -45,3 +45,85 @@ impl BeaconForkChoiceUpdateError {
         Self::Internal(Box::new(e))
     }
 }
+
+/// Represents error cases when resetting the canonical head to an earlier block.
+#[derive(Debug, thiserror::Error)]
+pub enum BeaconSetHeadError {
+    /// Thrown when the engine task is unavailable/stopped.
+    #[error("beacon consensus engine task stopped")]
+    EngineUnavailable,
+    /// Thrown when the head can't be reset while the engine is syncing.
+    #[error("cannot set head while backfill sync is in progress")]
+    BackfillInProgress,
+    /// Thrown when the target is not an ancestor of the current canonical head.
+    #[error("target block {target} is not below the canonical head {head}")]
+    InvalidTarget {
+        /// The requested block number.
+        target: u64,
+        /// The block number of the current canonical head.
+        head: u64,
+    },
+    /// Thrown when the target is more than [`MAX_SET_HEAD_DEPTH`](crate::MAX_SET_HEAD_DEPTH)
+    /// blocks below the canonical head.
+    #[error(
+        "target block {target} is more than {max_depth} blocks below the canonical head {head}"
+    )]
+    TooDeep {
+        /// The requested block number.
+        target: u64,
+        /// The block number of the current canonical head.
+        head: u64,
+        /// The maximum number of blocks that can be unwound.
+        max_depth: u64,
+    },
+    /// Thrown when the target is the genesis block, which can't be unwound to.
+    #[error("cannot set head to the genesis block")]
+    Genesis,
+    /// Thrown when the persistence of canonical blocks is paused, see
+    /// [`BeaconPersistenceError`].
+    #[error("cannot set head while persistence is paused")]
+    PersistencePaused,
+    /// An internal error occurred, e.g. the unwind target has already been pruned.
+    #[error(transparent)]
+    Internal(Box<dyn core::error::Error + Send + Sync>),
+}
+
+impl BeaconSetHeadError {
+    /// Create a new internal error.
+    pub fn internal<E: core::error::Error + Send + Sync + 'static>(e: E) -> Self {
+        Self::Internal(Box::new(e))
+    }
+}
+
+/// Represents error cases when pausing or resuming the persistence of canonical blocks.
+#[derive(Debug, thiserror::Error)]
+pub enum BeaconPersistenceError {
+    /// Thrown when the engine task is unavailable/stopped.
+    #[error("beacon consensus engine task stopped")]
+    EngineUnavailable,
+    /// Thrown when persistence can't be paused while the engine is syncing.
+    #[error("cannot pause persistence while backfill sync is in progress")]
+    BackfillInProgress,
+    /// Thrown when the target is not between the last persisted block and the canonical head.
+    #[error(
+        "target block {target} is not between the last persisted block {persisted} and the canonical head {head}"
+    )]
+    InvalidTarget {
+        /// The requested block number.
+        target: u64,
+        /// The block number of the last persisted block.
+        persisted: u64,
+        /// The block number of the current canonical head.
+        head: u64,
+    },
+    /// An internal error occurred while persisting the blocks up to the target.
+    #[error(transparent)]
+    Internal(Box<dyn core::error::Error + Send + Sync>),
+}
+
+impl BeaconPersistenceError {
+    /// Create a new internal error.
+    pub fn internal<E: core::error::Error + Send + Sync + 'static>(e: E) -> Self {
+        Self::Internal(Box::new(e))
+    }
+}
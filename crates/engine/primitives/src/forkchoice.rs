@@ -127,7 +127,8 @@ pub(crate) struct ReceivedForkchoiceState {
 }
 
 /// A simplified representation of [`PayloadStatusEnum`] specifically for FCU.
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
 pub enum ForkchoiceStatus {
     /// The forkchoice state is valid.
     Valid,
@@ -1,6 +1,9 @@
 use crate::{
-    error::BeaconForkChoiceUpdateError, BeaconOnNewPayloadError, ExecutionPayload, ForkchoiceStatus,
+    error::BeaconForkChoiceUpdateError, BeaconOnNewPayloadError, BeaconPersistenceError,
+    BeaconSetHeadError, ExecutionPayload, ForkchoiceStatus,
 };
+use alloy_eips::BlockNumHash;
+use alloy_primitives::BlockNumber;
 use alloy_rpc_types_engine::{
     ForkChoiceUpdateResult, ForkchoiceState, ForkchoiceUpdateError, ForkchoiceUpdated, PayloadId,
     PayloadStatus, PayloadStatusEnum,
@@ -15,9 +18,22 @@ use futures::{future::Either, FutureExt, TryFutureExt};
 use reth_errors::RethResult;
 use reth_payload_builder_primitives::PayloadBuilderError;
 use reth_payload_primitives::PayloadTypes;
-use std::time::{Duration, Instant};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tokio::sync::{mpsc::UnboundedSender, oneshot};
 
+/// Time after which a pause of the persistence is lifted, unless it's renewed by another pause
+/// request, see [`ConsensusEngineHandle::pause_persistence`].
+pub const PERSISTENCE_PAUSE_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// The maximum number of blocks a set head request can unwind, see
+/// [`ConsensusEngineHandle::set_head`].
+///
+/// The reverted blocks are loaded into memory for the canonical state notification.
+pub const MAX_SET_HEAD_DEPTH: u64 = 1024;
+
 /// Type alias for backwards compat
 #[deprecated(note = "Use ConsensusEngineHandle instead")]
 pub type BeaconConsensusEngineHandle<Payload> = ConsensusEngineHandle<Payload>;
@@ -224,6 +240,32 @@ pub enum BeaconEngineMessage<Payload: PayloadTypes> {
         /// The sender for returning forkchoice updated result.
         tx: oneshot::Sender<RethResult<OnForkChoiceUpdated>>,
     },
+    /// Message to reset the canonical head to an earlier block.
+    ///
+    /// All blocks above the target are unwound, including the ones already persisted. At most
+    /// [`MAX_SET_HEAD_DEPTH`] blocks can be unwound.
+    SetHead {
+        /// The number of the block that becomes the new canonical head.
+        block_number: BlockNumber,
+        /// The sender for returning the new canonical head.
+        tx: oneshot::Sender<Result<BlockNumHash, BeaconSetHeadError>>,
+    },
+    /// Message to stop persisting canonical blocks to disk, after persisting the canonical blocks
+    /// up to the given block, if any.
+    ///
+    /// Blocks keep being validated and kept in memory until persistence is resumed, or the pause
+    /// expires after [`PERSISTENCE_PAUSE_TIMEOUT`].
+    PausePersistence {
+        /// The number of the last block to persist before pausing.
+        block_number: Option<BlockNumber>,
+        /// The sender for returning the last persisted block.
+        tx: oneshot::Sender<Result<BlockNumHash, BeaconPersistenceError>>,
+    },
+    /// Message to resume persisting canonical blocks to disk.
+    ResumePersistence {
+        /// The sender for returning the last persisted block.
+        tx: oneshot::Sender<Result<BlockNumHash, BeaconPersistenceError>>,
+    },
 }
 
 impl<Payload: PayloadTypes> Display for BeaconEngineMessage<Payload> {
@@ -256,6 +298,13 @@ impl<Payload: PayloadTypes> Display for BeaconEngineMessage<Payload> {
                     payload_attrs.is_some()
                 )
             }
+            Self::SetHead { block_number, .. } => {
+                write!(f, "SetHead(number: {block_number})")
+            }
+            Self::PausePersistence { block_number, .. } => {
+                write!(f, "PausePersistence(number: {block_number:?})")
+            }
+            Self::ResumePersistence { .. } => write!(f, "ResumePersistence"),
         }
     }
 }
@@ -346,4 +395,165 @@ where
         });
         rx
     }
+
+    /// Resets the canonical head to the given block and waits for the unwind to complete.
+    ///
+    /// The target must be at most [`MAX_SET_HEAD_DEPTH`] blocks below the canonical head.
+    ///
+    /// Returns the new canonical head.
+    pub async fn set_head(
+        &self,
+        block_number: BlockNumber,
+    ) -> Result<BlockNumHash, BeaconSetHeadError> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.to_engine.send(BeaconEngineMessage::SetHead { block_number, tx });
+        rx.await.map_err(|_| BeaconSetHeadError::EngineUnavailable)?
+    }
+
+    /// Persists the canonical blocks up to the given block, if any, and stops persisting blocks
+    /// until [`Self::resume_persistence`] is called.
+    ///
+    /// The persistence is resumed automatically after [`PERSISTENCE_PAUSE_TIMEOUT`], calling this
+    /// again while the persistence is paused renews the pause.
+    ///
+    /// Returns the last persisted block, which stays the same until persistence is resumed.
+    pub async fn pause_persistence(
+        &self,
+        block_number: Option<BlockNumber>,
+    ) -> Result<BlockNumHash, BeaconPersistenceError> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.to_engine.send(BeaconEngineMessage::PausePersistence { block_number, tx });
+        rx.await.map_err(|_| BeaconPersistenceError::EngineUnavailable)?
+    }
+
+    /// Resumes persisting canonical blocks after [`Self::pause_persistence`].
+    ///
+    /// Returns the last persisted block.
+    pub async fn resume_persistence(&self) -> Result<BlockNumHash, BeaconPersistenceError> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.to_engine.send(BeaconEngineMessage::ResumePersistence { tx });
+        rx.await.map_err(|_| BeaconPersistenceError::EngineUnavailable)?
+    }
+}
+
+/// A handle to reset the canonical head of the engine, see [`ConsensusEngineHandle::set_head`].
+///
+/// Unlike the [`ConsensusEngineHandle`] this doesn't depend on the payload types, so it can be
+/// handed to components that are not aware of them, like the `debug_` RPC namespace.
+#[derive(Clone)]
+pub struct SetHeadHandle {
+    to_engine: Arc<SendSetHead>,
+}
+
+/// Sends a [`BeaconEngineMessage::SetHead`] request to the engine.
+type SendSetHead =
+    dyn Fn(BlockNumber, oneshot::Sender<Result<BlockNumHash, BeaconSetHeadError>>) + Send + Sync;
+
+impl SetHeadHandle {
+    /// Creates a new handle that sends its requests through the given [`ConsensusEngineHandle`].
+    pub fn new<Payload: PayloadTypes>(handle: ConsensusEngineHandle<Payload>) -> Self {
+        let ConsensusEngineHandle { to_engine } = handle;
+        Self {
+            to_engine: Arc::new(move |block_number, tx| {
+                let _ = to_engine.send(BeaconEngineMessage::SetHead { block_number, tx });
+            }),
+        }
+    }
+
+    /// Resets the canonical head to the given block and waits for the unwind to complete.
+    ///
+    /// The target must be at most [`MAX_SET_HEAD_DEPTH`] blocks below the canonical head.
+    ///
+    /// Returns the new canonical head.
+    pub async fn set_head(
+        &self,
+        block_number: BlockNumber,
+    ) -> Result<BlockNumHash, BeaconSetHeadError> {
+        let (tx, rx) = oneshot::channel();
+        (self.to_engine)(block_number, tx);
+        rx.await.map_err(|_| BeaconSetHeadError::EngineUnavailable)?
+    }
+}
+
+impl fmt::Debug for SetHeadHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SetHeadHandle").finish_non_exhaustive()
+    }
+}
+
+/// A handle to pause and resume the persistence of canonical blocks, see
+/// [`ConsensusEngineHandle::pause_persistence`].
+///
+/// Like the [`SetHeadHandle`] this doesn't depend on the payload types, so it can be handed to the
+/// `admin_` RPC namespace.
+#[derive(Clone)]
+pub struct PersistencePauseHandle {
+    to_engine: Arc<SendPersistenceRequest>,
+}
+
+/// A request to pause or resume persistence.
+enum PersistenceRequest {
+    /// See [`BeaconEngineMessage::PausePersistence`].
+    Pause(Option<BlockNumber>),
+    /// See [`BeaconEngineMessage::ResumePersistence`].
+    Resume,
+}
+
+/// Sends a [`PersistenceRequest`] to the engine.
+type SendPersistenceRequest = dyn Fn(PersistenceRequest, oneshot::Sender<Result<BlockNumHash, BeaconPersistenceError>>)
+    + Send
+    + Sync;
+
+impl PersistencePauseHandle {
+    /// Creates a new handle that sends its requests through the given [`ConsensusEngineHandle`].
+    pub fn new<Payload: PayloadTypes>(handle: ConsensusEngineHandle<Payload>) -> Self {
+        let ConsensusEngineHandle { to_engine } = handle;
+        Self {
+            to_engine: Arc::new(move |request, tx| {
+                let message = match request {
+                    PersistenceRequest::Pause(block_number) => {
+                        BeaconEngineMessage::PausePersistence { block_number, tx }
+                    }
+                    PersistenceRequest::Resume => BeaconEngineMessage::ResumePersistence { tx },
+                };
+                let _ = to_engine.send(message);
+            }),
+        }
+    }
+
+    /// Persists the canonical blocks up to the given block, if any, and stops persisting blocks
+    /// until [`Self::resume_persistence`] is called.
+    ///
+    /// The persistence is resumed automatically after [`PERSISTENCE_PAUSE_TIMEOUT`], calling this
+    /// again while the persistence is paused renews the pause.
+    ///
+    /// Returns the last persisted block.
+    pub async fn pause_persistence(
+        &self,
+        block_number: Option<BlockNumber>,
+    ) -> Result<BlockNumHash, BeaconPersistenceError> {
+        self.send(PersistenceRequest::Pause(block_number)).await
+    }
+
+    /// Resumes persisting canonical blocks.
+    ///
+    /// Returns the last persisted block.
+    pub async fn resume_persistence(&self) -> Result<BlockNumHash, BeaconPersistenceError> {
+        self.send(PersistenceRequest::Resume).await
+    }
+
+    async fn send(
+        &self,
+        request: PersistenceRequest,
+    ) -> Result<BlockNumHash, BeaconPersistenceError> {
+        let (tx, rx) = oneshot::channel();
+        (self.to_engine)(request, tx);
+        rx.await.map_err(|_| BeaconPersistenceError::EngineUnavailable)?
+    }
+}
+
+impl fmt::Debug for PersistencePauseHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PersistencePauseHandle").finish_non_exhaustive()
+    }
 }
@@ -7,7 +7,7 @@ use crate::{
 };
 use alloy_consensus::BlockHeader;
 use alloy_eips::{eip1898::BlockWithParent, merge::EPOCH_SLOTS, BlockNumHash, NumHash};
-use alloy_primitives::B256;
+use alloy_primitives::{BlockNumber, B256};
 use alloy_rpc_types_engine::{
     ForkchoiceState, PayloadStatus, PayloadStatusEnum, PayloadValidationError,
 };
@@ -18,8 +18,9 @@ use reth_chain_state::{
 };
 use reth_consensus::{Consensus, FullConsensus};
 use reth_engine_primitives::{
-    BeaconEngineMessage, BeaconOnNewPayloadError, ConsensusEngineEvent, ExecutionPayload,
-    ForkchoiceStateTracker, NewPayloadTimings, OnForkChoiceUpdated, SlowBlockInfo,
+    BeaconEngineMessage, BeaconOnNewPayloadError, BeaconPersistenceError, BeaconSetHeadError,
+    ConsensusEngineEvent, ExecutionPayload, ForkchoiceStateTracker, NewPayloadTimings,
+    OnForkChoiceUpdated, SlowBlockInfo, MAX_SET_HEAD_DEPTH, PERSISTENCE_PAUSE_TIMEOUT,
 };
 use reth_errors::{ConsensusError, ProviderResult};
 use reth_evm::ConfigureEvm;
@@ -29,13 +30,13 @@ use reth_primitives_traits::{
     FastInstant as Instant, NodePrimitives, RecoveredBlock, SealedBlock, SealedHeader,
 };
 use reth_provider::{
-    BlockExecutionOutput, BlockExecutionResult, BlockReader, ChangeSetReader,
-    DatabaseProviderFactory, HashedPostStateProvider, ProviderError, StageCheckpointReader,
-    StateProviderBox, StateProviderFactory, StateReader, StorageChangeSetReader,
-    StorageSettingsCache, TransactionVariant,
+    BlockExecutionOutput, BlockExecutionResult, BlockNumReader, BlockReader, ChangeSetReader,
+    DBProvider, DatabaseProviderFactory, HashedPostStateProvider, ProviderError,
+    PruneCheckpointReader, StageCheckpointReader, StateProviderBox, StateProviderFactory,
+    StateReader, StorageChangeSetReader, StorageSettingsCache, TransactionVariant,
 };
 use reth_revm::database::StateProviderDatabase;
-use reth_stages_api::ControlFlow;
+use reth_stages_api::{ControlFlow, PipelineTarget};
 use reth_tasks::{spawn_os_thread, utils::increase_thread_priority};
 use reth_trie_db::ChangesetCache;
 use revm::interpreter::debug_unreachable;
@@ -255,6 +256,31 @@ pub enum TreeAction {
     },
 }
 
+/// A set head request that is in progress.
+#[derive(Debug)]
+enum PendingSetHead<N: NodePrimitives> {
+    /// Waits for the canonical blocks to be persisted, so the pipeline can unwind them.
+    Persisting {
+        /// The chain update that reverts all blocks above the target.
+        chain_update: NewCanonicalChain<N>,
+        /// When the request was received.
+        started_at: Instant,
+        /// The sender for returning the new canonical head.
+        tx: oneshot::Sender<Result<BlockNumHash, BeaconSetHeadError>>,
+    },
+    /// Waits for the backfill pipeline to unwind the database.
+    Unwinding {
+        /// The block that becomes the new canonical head.
+        target: BlockNumHash,
+        /// The chain update that reverts all blocks above the target.
+        chain_update: NewCanonicalChain<N>,
+        /// When the request was received.
+        started_at: Instant,
+        /// The sender for returning the new canonical head.
+        tx: oneshot::Sender<Result<BlockNumHash, BeaconSetHeadError>>,
+    },
+}
+
 /// The engine API tree handler implementation.
 ///
 /// This type is responsible for processing engine API requests, maintaining the canonical state and
@@ -314,6 +340,15 @@ where
     building_payload: bool,
     /// Task runtime for spawning blocking work on named, reusable threads.
     runtime: reth_tasks::Runtime,
+    /// A set head request that waits for the canonical blocks to be persisted or for the
+    /// backfill pipeline to unwind the database.
+    pending_set_head: Option<PendingSetHead<N>>,
+    /// Deadline of the pause of the persistence of canonical blocks, so that the database and
+    /// static files don't change, e.g. while a snapshot of them is taken.
+    ///
+    /// The persistence is resumed once the deadline passed, unless the pause is renewed, see
+    /// [`PERSISTENCE_PAUSE_TIMEOUT`].
+    persistence_paused: Option<Instant>,
 }
 
 impl<N, P: Debug, T: PayloadTypes + Debug, V: Debug, C> std::fmt::Debug
@@ -341,6 +376,7 @@ where
             .field("changeset_cache", &self.changeset_cache)
             .field("execution_timing_stats", &self.execution_timing_stats.len())
             .field("runtime", &self.runtime)
+            .field("pending_set_head", &self.pending_set_head)
             .finish()
     }
 }
@@ -359,7 +395,8 @@ where
         + StageCheckpointReader
         + ChangeSetReader
         + StorageChangeSetReader
-        + StorageSettingsCache,
+        + StorageSettingsCache
+        + PruneCheckpointReader,
     C: ConfigureEvm<Primitives = N> + 'static,
     T: PayloadTypes<BuiltPayload: BuiltPayload<Primitives = N>>,
     V: EngineValidator<T> + WaitForCaches,
@@ -405,6 +442,8 @@ where
             execution_timing_stats: HashMap::new(),
             building_payload: false,
             runtime,
+            pending_set_head: None,
+            persistence_paused: None,
         }
     }
 
@@ -1049,11 +1088,16 @@ where
         let new_head_hash = canonical_header.hash();
         let new_head_number = canonical_header.number();
 
-        // Load the canonical ancestor's block
-        let executed_block = self.canonical_block_by_hash(new_head_hash)?;
+        // Load the canonical ancestor's block. The genesis block has no parent state that an
+        // in-memory block could be anchored on, so its state is served from the database.
+        let new = if new_head_number == 0 {
+            Vec::new()
+        } else {
+            vec![self.canonical_block_by_hash(new_head_hash)?]
+        };
         // Perform the reorg to properly handle the unwind
         self.canonical_in_memory_state
-            .update_chain(NewCanonicalChain::Reorg { new: vec![executed_block], old: old_blocks });
+            .update_chain(NewCanonicalChain::Reorg { new, old: old_blocks });
 
         // CRITICAL: Update the canonical head after the reorg
         // This ensures get_canonical_head() returns the correct block
@@ -1386,8 +1430,15 @@ where
     /// This checks if we need to remove blocks (disk reorg) or save new blocks to disk.
     /// Persistence completion is handled separately via the `wait_for_event` method.
     fn advance_persistence(&mut self) -> Result<(), AdvancePersistenceError> {
+        if self.is_persistence_paused() {
+            return Ok(())
+        }
+
         if !self.persistence_state.in_progress() {
-            if let Some(new_tip_num) = self.find_disk_reorg()? {
+            if matches!(self.pending_set_head, Some(PendingSetHead::Persisting { .. })) {
+                // the blocks of a set head request are persisted before anything else
+                self.advance_set_head();
+            } else if let Some(new_tip_num) = self.find_disk_reorg()? {
                 self.remove_blocks(new_tip_num)
             } else if self.should_persist() {
                 let blocks_to_persist =
@@ -1416,12 +1467,7 @@ where
     /// Persists all remaining blocks until none are left.
     fn persist_until_complete(&mut self) -> Result<(), AdvancePersistenceError> {
         loop {
-            // Wait for any in-progress persistence to complete (blocking)
-            if let Some((rx, start_time, action)) = self.persistence_state.rx.take() {
-                debug!(target: "engine::tree", ?action, "waiting for in-flight persistence");
-                let result = rx.recv().map_err(|_| AdvancePersistenceError::ChannelClosed)?;
-                self.on_persistence_complete(result, start_time)?;
-            }
+            self.wait_for_in_flight_persistence()?;
 
             let blocks_to_persist = self.get_canonical_blocks_to_persist(PersistTarget::Head)?;
 
@@ -1435,6 +1481,16 @@ where
         }
     }
 
+    /// Blocks until the in-flight persistence task, if any, completes and handles its result.
+    fn wait_for_in_flight_persistence(&mut self) -> Result<(), AdvancePersistenceError> {
+        if let Some((rx, start_time, action)) = self.persistence_state.rx.take() {
+            debug!(target: "engine::tree", ?action, "waiting for in-flight persistence");
+            let result = rx.recv().map_err(|_| AdvancePersistenceError::ChannelClosed)?;
+            self.on_persistence_complete(result, start_time)?;
+        }
+        Ok(())
+    }
+
     /// Tries to poll for a completed persistence task (non-blocking).
     ///
     /// Returns `true` if a persistence task was completed, `false` otherwise.
@@ -1537,6 +1593,17 @@ where
                 }
                 FromOrchestrator::BackfillSyncFinished(ctrl) => {
                     self.on_backfill_sync_finished(ctrl)?;
+                    if let Some(PendingSetHead::Unwinding {
+                        target,
+                        chain_update,
+                        started_at,
+                        tx,
+                    }) = self
+                        .pending_set_head
+                        .take_if(|set_head| matches!(set_head, PendingSetHead::Unwinding { .. }))
+                    {
+                        self.on_set_head_unwound(target, chain_update, started_at, tx);
+                    }
                 }
                 FromOrchestrator::Terminate { tx } => {
                     debug!(target: "engine::tree", "received terminate request");
@@ -1736,6 +1803,18 @@ where
 
                                 self.on_maybe_tree_event(maybe_event)?;
                             }
+                            BeaconEngineMessage::SetHead { block_number, tx } => {
+                                self.on_set_head(block_number, tx);
+                            }
+                            BeaconEngineMessage::PausePersistence { block_number, tx } => {
+                                let _ = tx.send(self.on_pause_persistence(block_number));
+                            }
+                            BeaconEngineMessage::ResumePersistence { tx } => {
+                                if self.persistence_paused.take().is_some() {
+                                    info!(target: "engine::tree", last_persisted_block = ?self.persistence_state.last_persisted_block, "Resuming persistence");
+                                }
+                                let _ = tx.send(Ok(self.persistence_state.last_persisted_block));
+                            }
                         }
                     }
                 }
@@ -1749,6 +1828,252 @@ where
         Ok(ops::ControlFlow::Continue(()))
     }
 
+    /// Handles a request to reset the canonical head to an earlier block.
+    ///
+    /// All canonical blocks are persisted first, so the unwind can be performed by the backfill
+    /// pipeline, the same way `reth stage unwind` does, which also truncates the static files. The
+    /// blocks are persisted in the background, see [`Self::advance_set_head`], and the response is
+    /// sent once the pipeline finished, see [`Self::on_set_head_unwound`].
+    fn on_set_head(
+        &mut self,
+        block_number: BlockNumber,
+        tx: oneshot::Sender<Result<BlockNumHash, BeaconSetHeadError>>,
+    ) {
+        debug!(target: "engine::tree", block_number, "received set head request");
+        let chain_update = match self
+            .validate_set_head(block_number)
+            .and_then(|()| self.set_head_chain_update(block_number))
+        {
+            Ok(chain_update) => chain_update,
+            Err(err) => {
+                warn!(target: "engine::tree", block_number, %err, "Failed to set head");
+                let _ = tx.send(Err(err));
+                return
+            }
+        };
+
+        self.pending_set_head =
+            Some(PendingSetHead::Persisting { chain_update, started_at: Instant::now(), tx });
+        self.advance_set_head();
+    }
+
+    /// Advances a set head request that waits for the canonical blocks to be persisted.
+    ///
+    /// Persists the remaining canonical blocks if no persistence task is in progress, or starts
+    /// the unwind once all of them are on disk.
+    fn advance_set_head(&mut self) {
+        if self.persistence_state.in_progress() {
+            return
+        }
+        let Some(PendingSetHead::Persisting { chain_update, started_at, tx }) = self
+            .pending_set_head
+            .take_if(|set_head| matches!(set_head, PendingSetHead::Persisting { .. }))
+        else {
+            return
+        };
+        let block_number = chain_update.tip().number();
+
+        match self.try_advance_set_head(chain_update) {
+            Ok((chain_update, true)) => {
+                self.pending_set_head =
+                    Some(PendingSetHead::Persisting { chain_update, started_at, tx });
+            }
+            Ok((chain_update, false)) => {
+                let target = chain_update.tip().num_hash();
+                self.pending_set_head =
+                    Some(PendingSetHead::Unwinding { target, chain_update, started_at, tx });
+                self.emit_event(EngineApiEvent::BackfillAction(BackfillAction::Start(
+                    PipelineTarget::Unwind(block_number),
+                )));
+            }
+            Err(err) => {
+                warn!(target: "engine::tree", block_number, %err, "Failed to set head");
+                let _ = tx.send(Err(err));
+            }
+        }
+    }
+
+    /// Persists the next canonical blocks of a set head request, or starts the unwind if all of
+    /// them are on disk.
+    ///
+    /// Returns the chain update of the request and whether it still waits for the persistence.
+    fn try_advance_set_head(
+        &mut self,
+        mut chain_update: NewCanonicalChain<N>,
+    ) -> Result<(NewCanonicalChain<N>, bool), BeaconSetHeadError> {
+        let block_number = chain_update.tip().number();
+
+        // the canonical chain may have changed while the blocks were persisted
+        self.validate_set_head(block_number)?;
+        let reverted_head = match &chain_update {
+            NewCanonicalChain::Reorg { old, .. } => {
+                old.last().map(|block| block.recovered_block().num_hash())
+            }
+            NewCanonicalChain::Commit { .. } => None,
+        };
+        if reverted_head != Some(self.state.tree_state.current_canonical_head) {
+            chain_update = self.set_head_chain_update(block_number)?;
+        }
+
+        // the pipeline can only unwind blocks that are on disk
+        let blocks_to_persist = self
+            .get_canonical_blocks_to_persist(PersistTarget::Head)
+            .map_err(BeaconSetHeadError::internal)?;
+        if !blocks_to_persist.is_empty() {
+            self.persist_blocks(blocks_to_persist);
+            return Ok((chain_update, true))
+        }
+
+        // refuse the request if the pipeline would fail to unwind, since that is a fatal error
+        let provider =
+            self.provider.database_provider_ro().map_err(BeaconSetHeadError::internal)?;
+        let latest_block = provider.last_block_number().map_err(BeaconSetHeadError::internal)?;
+        let checkpoints = provider.get_prune_checkpoints().map_err(BeaconSetHeadError::internal)?;
+        provider
+            .prune_modes_ref()
+            .ensure_unwind_target_unpruned(latest_block, block_number, &checkpoints)
+            .map_err(BeaconSetHeadError::internal)?;
+
+        Ok((chain_update, false))
+    }
+
+    /// Handles a request to pause the persistence of canonical blocks.
+    ///
+    /// Waits for the in-flight persistence task and persists the canonical blocks up to
+    /// `block_number`, if any, so that nothing is written to disk by the tree once the response is
+    /// sent. Returns the last persisted block.
+    ///
+    /// The pause expires after [`PERSISTENCE_PAUSE_TIMEOUT`], a request while the persistence is
+    /// paused renews it.
+    fn on_pause_persistence(
+        &mut self,
+        block_number: Option<BlockNumber>,
+    ) -> Result<BlockNumHash, BeaconPersistenceError> {
+        debug!(target: "engine::tree", ?block_number, "received pause persistence request");
+        if !self.backfill_sync_state.is_idle() || self.pending_set_head.is_some() {
+            return Err(BeaconPersistenceError::BackfillInProgress)
+        }
+
+        self.wait_for_in_flight_persistence().map_err(BeaconPersistenceError::internal)?;
+
+        if let Some(target) = block_number {
+            let persisted = self.persistence_state.last_persisted_block.number;
+            let head = self.state.tree_state.canonical_block_number();
+            if target < persisted || target > head {
+                return Err(BeaconPersistenceError::InvalidTarget { target, persisted, head })
+            }
+
+            let blocks_to_persist = self
+                .get_canonical_blocks_to_persist(PersistTarget::Block(target))
+                .map_err(BeaconPersistenceError::internal)?;
+            if !blocks_to_persist.is_empty() {
+                self.persist_blocks(blocks_to_persist);
+                self.wait_for_in_flight_persistence().map_err(BeaconPersistenceError::internal)?;
+            }
+        }
+
+        self.persistence_paused = Some(Instant::now() + PERSISTENCE_PAUSE_TIMEOUT);
+        info!(target: "engine::tree", last_persisted_block = ?self.persistence_state.last_persisted_block, timeout = ?PERSISTENCE_PAUSE_TIMEOUT, "Paused persistence");
+        Ok(self.persistence_state.last_persisted_block)
+    }
+
+    /// Returns whether the persistence of canonical blocks is paused.
+    ///
+    /// Resumes the persistence if the pause expired, e.g. because the process that paused it
+    /// exited without resuming it.
+    fn is_persistence_paused(&mut self) -> bool {
+        let Some(deadline) = self.persistence_paused else { return false };
+        if Instant::now() < deadline {
+            return true
+        }
+
+        warn!(target: "engine::tree", last_persisted_block = ?self.persistence_state.last_persisted_block, "Persistence pause expired, resuming persistence");
+        self.persistence_paused = None;
+        false
+    }
+
+    /// Validates the set head target against the current canonical head.
+    fn validate_set_head(&mut self, block_number: BlockNumber) -> Result<(), BeaconSetHeadError> {
+        if !self.backfill_sync_state.is_idle() || self.pending_set_head.is_some() {
+            return Err(BeaconSetHeadError::BackfillInProgress)
+        }
+        if self.is_persistence_paused() {
+            return Err(BeaconSetHeadError::PersistencePaused)
+        }
+
+        let head = self.state.tree_state.canonical_block_number();
+        if block_number == 0 {
+            return Err(BeaconSetHeadError::Genesis)
+        }
+        if block_number >= head {
+            return Err(BeaconSetHeadError::InvalidTarget { target: block_number, head })
+        }
+        if head - block_number > MAX_SET_HEAD_DEPTH {
+            return Err(BeaconSetHeadError::TooDeep {
+                target: block_number,
+                head,
+                max_depth: MAX_SET_HEAD_DEPTH,
+            })
+        }
+        Ok(())
+    }
+
+    /// Loads the blocks above the set head target, which are reverted by the unwind.
+    ///
+    /// The target block is loaded as well, because it becomes the tip of the new chain. Blocks
+    /// that are not persisted yet are taken from memory. The number of loaded blocks is bounded by
+    /// [`MAX_SET_HEAD_DEPTH`].
+    fn set_head_chain_update(
+        &self,
+        block_number: BlockNumber,
+    ) -> Result<NewCanonicalChain<N>, BeaconSetHeadError> {
+        let mut old = Vec::new();
+        let mut hash = self.state.tree_state.canonical_block_hash();
+        loop {
+            let block = self.canonical_block_by_hash(hash).map_err(BeaconSetHeadError::internal)?;
+            if block.recovered_block().number() <= block_number {
+                old.reverse();
+                return Ok(NewCanonicalChain::Reorg { new: vec![block], old })
+            }
+            hash = block.recovered_block().parent_hash();
+            old.push(block);
+        }
+    }
+
+    /// Invoked once the backfill pipeline unwound the database for a set head request.
+    ///
+    /// Clears the executed blocks, which are no longer connected to the canonical chain, and
+    /// notifies about the reverted blocks.
+    fn on_set_head_unwound(
+        &mut self,
+        target: BlockNumHash,
+        chain_update: NewCanonicalChain<N>,
+        started_at: Instant,
+        tx: oneshot::Sender<Result<BlockNumHash, BeaconSetHeadError>>,
+    ) {
+        let head = self.state.tree_state.canonical_block_number();
+        if head != target.number {
+            warn!(target: "engine::tree", ?target, head, "Set head unwind did not reach the target");
+            let _ = tx.send(Err(BeaconSetHeadError::internal(ProviderError::HeaderNotFound(
+                target.number.into(),
+            ))));
+            return
+        }
+
+        self.state.tree_state.reset(target);
+        self.metrics.engine.executed_blocks.set(self.state.tree_state.block_count() as f64);
+
+        let tip = chain_update.tip().clone_sealed_header();
+        self.canonical_in_memory_state.notify_canon_state(chain_update.to_chain_notification());
+        self.emit_event(ConsensusEngineEvent::CanonicalChainCommitted(
+            Box::new(tip),
+            started_at.elapsed(),
+        ));
+
+        info!(target: "engine::tree", ?target, elapsed = ?started_at.elapsed(), "Reset canonical head");
+        let _ = tx.send(Ok(target));
+    }
+
     /// Invoked if the backfill sync has finished to target.
     ///
     /// At this point we consider the block synced to the backfill target.
@@ -2005,6 +2330,12 @@ where
                 return
             }
 
+            if self.is_persistence_paused() {
+                // backfill sync writes to disk as well
+                debug!(target: "engine::tree", "skipping backfill while persistence is paused");
+                return
+            }
+
             self.backfill_sync_state = BackfillSyncState::Pending;
             self.metrics.engine.pipeline_runs.increment(1);
             debug!(target: "engine::tree", "emitting backfill action event");
@@ -2053,6 +2384,7 @@ where
             PersistTarget::Threshold => {
                 canonical_head_number.saturating_sub(self.config.memory_block_buffer_target())
             }
+            PersistTarget::Block(number) => number.min(canonical_head_number),
         };
 
         debug!(
@@ -3372,6 +3704,8 @@ enum PersistTarget {
     Threshold,
     /// Persist all blocks up to and including the canonical head.
     Head,
+    /// Persist all canonical blocks up to and including the given block.
+    Block(BlockNumber),
 }
 
 /// Result of waiting for caches to become available.
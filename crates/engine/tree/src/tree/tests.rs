@@ -619,6 +619,173 @@ async fn test_engine_request_during_backfill() {
     assert!(resp.payload_status.is_syncing());
 }
 
+#[test]
+fn test_set_head_rejects_invalid_targets() {
+    let blocks: Vec<_> = TestBlockBuilder::eth().get_executed_blocks(0..4).collect();
+    let mut test_harness = TestHarness::new(MAINNET.clone()).with_blocks(blocks);
+
+    let set_head = |test_harness: &mut TestHarness, block_number| {
+        let (tx, mut rx) = oneshot::channel();
+        test_harness
+            .tree
+            .on_engine_message(FromEngine::Request(
+                BeaconEngineMessage::SetHead { block_number, tx }.into(),
+            ))
+            .unwrap();
+        rx.try_recv().unwrap()
+    };
+
+    assert_matches!(
+        set_head(&mut test_harness, 3),
+        Err(BeaconSetHeadError::InvalidTarget { target: 3, head: 3 })
+    );
+    assert_matches!(
+        set_head(&mut test_harness, 10),
+        Err(BeaconSetHeadError::InvalidTarget { target: 10, head: 3 })
+    );
+    assert_matches!(set_head(&mut test_harness, 0), Err(BeaconSetHeadError::Genesis));
+
+    test_harness = test_harness.with_backfill_state(BackfillSyncState::Active);
+    assert_matches!(set_head(&mut test_harness, 1), Err(BeaconSetHeadError::BackfillInProgress));
+    assert!(test_harness.tree.pending_set_head.is_none());
+}
+
+#[tokio::test]
+async fn test_set_head_unwinds_to_target() {
+    let blocks: Vec<_> = TestBlockBuilder::eth().get_executed_blocks(0..4).collect();
+    let mut test_harness = TestHarness::new(MAINNET.clone()).with_blocks(blocks.clone());
+    let mut canon_notifications =
+        test_harness.tree.canonical_in_memory_state.subscribe_canon_state();
+
+    let (tx, mut rx) = oneshot::channel();
+    test_harness
+        .tree
+        .on_engine_message(FromEngine::Request(
+            BeaconEngineMessage::SetHead { block_number: 1, tx }.into(),
+        ))
+        .unwrap();
+
+    // the canonical blocks are persisted in the background before the unwind starts
+    let PersistenceAction::SaveBlocks(saved_blocks, sender) =
+        test_harness.action_rx.recv().unwrap()
+    else {
+        panic!("received wrong action");
+    };
+    assert_eq!(saved_blocks, blocks[1..].to_vec());
+    assert!(rx.try_recv().is_err());
+    assert_matches!(test_harness.tree.pending_set_head, Some(PendingSetHead::Persisting { .. }));
+
+    sender
+        .send(PersistenceResult {
+            last_block: Some(blocks[3].recovered_block().num_hash()),
+            commit_duration: Some(Duration::ZERO),
+        })
+        .unwrap();
+    assert!(test_harness.tree.try_poll_persistence().unwrap());
+    test_harness.tree.advance_persistence().unwrap();
+
+    // the pipeline is asked to unwind to the target
+    loop {
+        match test_harness.from_tree_rx.recv().await.unwrap() {
+            EngineApiEvent::BackfillAction(action) => {
+                assert_eq!(action, BackfillAction::Start(PipelineTarget::Unwind(1)));
+                break
+            }
+            _ => continue,
+        }
+    }
+    assert_matches!(test_harness.tree.pending_set_head, Some(PendingSetHead::Unwinding { .. }));
+
+    let backfill_finished =
+        FromOrchestrator::BackfillSyncFinished(ControlFlow::Continue { block_number: 1 });
+    test_harness.tree.on_engine_message(FromEngine::Event(backfill_finished)).unwrap();
+
+    let target = blocks[1].recovered_block().num_hash();
+    assert_eq!(rx.try_recv().unwrap().unwrap(), target);
+    assert!(test_harness.tree.pending_set_head.is_none());
+    assert_eq!(test_harness.tree.state.tree_state.current_canonical_head, target);
+
+    // the blocks above the target are reverted
+    let notification = canon_notifications.recv().await.unwrap();
+    assert_eq!(notification.tip().num_hash(), target);
+    let reverted = notification.reverted().unwrap();
+    assert_eq!(reverted.range(), 2..=3);
+    assert_eq!(reverted.tip().hash(), blocks[3].recovered_block().hash());
+}
+
+#[test]
+fn test_set_head_rejects_deep_unwind() {
+    let depth = MAX_SET_HEAD_DEPTH + 2;
+    let blocks: Vec<_> = TestBlockBuilder::eth().get_executed_blocks(0..depth + 1).collect();
+    let mut test_harness = TestHarness::new(MAINNET.clone()).with_blocks(blocks);
+
+    let (tx, mut rx) = oneshot::channel();
+    test_harness
+        .tree
+        .on_engine_message(FromEngine::Request(
+            BeaconEngineMessage::SetHead { block_number: 1, tx }.into(),
+        ))
+        .unwrap();
+    assert_matches!(
+        rx.try_recv().unwrap(),
+        Err(BeaconSetHeadError::TooDeep { target: 1, head, max_depth: MAX_SET_HEAD_DEPTH })
+            if head == depth
+    );
+    assert!(test_harness.tree.pending_set_head.is_none());
+}
+
+#[test]
+fn test_pause_persistence() {
+    let blocks: Vec<_> = TestBlockBuilder::eth().get_executed_blocks(0..4).collect();
+    let mut test_harness = TestHarness::new(MAINNET.clone()).with_blocks(blocks);
+
+    let send = |test_harness: &mut TestHarness, message| {
+        test_harness.tree.on_engine_message(FromEngine::Request(message)).unwrap();
+    };
+
+    let (tx, mut rx) = oneshot::channel();
+    send(
+        &mut test_harness,
+        BeaconEngineMessage::PausePersistence { block_number: Some(10), tx }.into(),
+    );
+    assert_matches!(
+        rx.try_recv().unwrap(),
+        Err(BeaconPersistenceError::InvalidTarget { target: 10, persisted: 0, head: 3 })
+    );
+    assert!(test_harness.tree.persistence_paused.is_none());
+
+    let (tx, mut rx) = oneshot::channel();
+    send(
+        &mut test_harness,
+        BeaconEngineMessage::PausePersistence { block_number: None, tx }.into(),
+    );
+    assert_eq!(rx.try_recv().unwrap().unwrap(), BlockNumHash::default());
+
+    // no blocks are persisted and the head can't be reset while paused
+    test_harness.tree.advance_persistence().unwrap();
+    assert_eq!(test_harness.tree.persistence_state.current_action(), None);
+    let (tx, mut rx) = oneshot::channel();
+    send(&mut test_harness, BeaconEngineMessage::SetHead { block_number: 1, tx }.into());
+    assert_matches!(rx.try_recv().unwrap(), Err(BeaconSetHeadError::PersistencePaused));
+
+    let (tx, mut rx) = oneshot::channel();
+    send(&mut test_harness, BeaconEngineMessage::ResumePersistence { tx }.into());
+    assert_eq!(rx.try_recv().unwrap().unwrap(), BlockNumHash::default());
+    assert!(test_harness.tree.persistence_paused.is_none());
+
+    // an expired pause is lifted
+    let (tx, mut rx) = oneshot::channel();
+    send(
+        &mut test_harness,
+        BeaconEngineMessage::PausePersistence { block_number: None, tx }.into(),
+    );
+    assert!(rx.try_recv().unwrap().is_ok());
+    assert!(test_harness.tree.is_persistence_paused());
+    test_harness.tree.persistence_paused = Some(Instant::now());
+    assert!(!test_harness.tree.is_persistence_paused());
+    assert!(test_harness.tree.persistence_paused.is_none());
+}
+
 #[test]
 fn test_disconnected_payload() {
     let s = include_str!("../../test-data/holesky/2.rlp");
@@ -1,20 +1,26 @@
 //! Stores engine API messages to disk for later inspection and replay.
 
-use alloy_rpc_types_engine::ForkchoiceState;
+use alloy_rpc_types_engine::{ForkchoiceState, PayloadStatus};
 use futures::{Stream, StreamExt};
-use reth_engine_primitives::{BeaconEngineMessage, ExecutionPayload};
+use reth_engine_primitives::{BeaconEngineMessage, ExecutionPayload, ForkchoiceStatus};
 use reth_fs_util as fs;
 use reth_payload_primitives::PayloadTypes;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::BTreeMap,
-    path::PathBuf,
+    path::{Path, PathBuf},
     pin::Pin,
     task::{ready, Context, Poll},
     time::SystemTime,
 };
+use tokio::sync::oneshot;
 use tracing::*;
 
+/// The subdirectory of the store that holds the engine responses to the stored messages.
+///
+/// Every response file has the same name as the file of the message it answers.
+pub const RESPONSES_DIR: &str = "responses";
+
 /// A message from the engine API that has been stored to disk.
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -34,8 +40,24 @@ pub enum StoredEngineApiMessage<T: PayloadTypes> {
     },
 }
 
+/// The response of the engine to a stored engine API message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StoredEngineApiResponse {
+    /// The on-disk representation of an `engine_forkchoiceUpdated` response.
+    ForkchoiceUpdated {
+        /// The [`ForkchoiceStatus`] the engine returned for the forkchoice state.
+        status: ForkchoiceStatus,
+    },
+    /// The on-disk representation of an `engine_newPayload` response.
+    NewPayload {
+        /// The [`PayloadStatus`] the engine returned for the payload.
+        status: PayloadStatus,
+    },
+}
+
 /// This can read and write engine API messages in a specific directory.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct EngineMessageStore {
     /// The path to the directory that stores the engine API messages.
     path: PathBuf,
@@ -60,10 +82,9 @@ impl EngineMessageStore {
         T: PayloadTypes,
     {
         fs::create_dir_all(&self.path)?; // ensure that store path had been created
-        let timestamp = received_at.duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis();
+        let Some(filename) = Self::message_filename(msg, received_at) else { return Ok(()) };
         match msg {
             BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, tx: _tx } => {
-                let filename = format!("{}-fcu-{}.json", timestamp, state.head_block_hash);
                 fs::write(
                     self.path.join(filename),
                     serde_json::to_vec(&StoredEngineApiMessage::<T>::ForkchoiceUpdated {
@@ -74,7 +95,6 @@ impl EngineMessageStore {
             }
             BeaconEngineMessage::NewPayload { payload, .. } |
             BeaconEngineMessage::RethNewPayload { payload, .. } => {
-                let filename = format!("{}-new_payload-{}.json", timestamp, payload.block_hash());
                 fs::write(
                     self.path.join(filename),
                     serde_json::to_vec(&StoredEngineApiMessage::<T>::NewPayload {
@@ -82,18 +102,78 @@ impl EngineMessageStore {
                     })?,
                 )?;
             }
+            BeaconEngineMessage::SetHead { .. } |
+            BeaconEngineMessage::PausePersistence { .. } |
+            BeaconEngineMessage::ResumePersistence { .. } => {}
         };
         Ok(())
     }
 
+    /// Stores the engine response to the message that was stored under `filename`.
+    pub fn on_response(
+        &self,
+        filename: &str,
+        response: &StoredEngineApiResponse,
+    ) -> eyre::Result<()> {
+        let responses_path = self.path.join(RESPONSES_DIR);
+        fs::create_dir_all(&responses_path)?;
+        fs::write(responses_path.join(filename), serde_json::to_vec(response)?)?;
+        Ok(())
+    }
+
+    /// Reads the stored engine response to the message stored at `message_path`, if any.
+    pub fn response_for(
+        &self,
+        message_path: &Path,
+    ) -> eyre::Result<Option<StoredEngineApiResponse>> {
+        let Some(filename) = message_path.file_name() else { return Ok(None) };
+        let response_path = self.path.join(RESPONSES_DIR).join(filename);
+        if !response_path.exists() {
+            return Ok(None)
+        }
+        Ok(Some(serde_json::from_slice(&fs::read(response_path)?)?))
+    }
+
+    /// Returns the time in milliseconds since the unix epoch at which the message stored at `path`
+    /// was received.
+    pub fn message_timestamp(path: &Path) -> Option<u64> {
+        path.file_name()?.to_str()?.split('-').next()?.parse().ok()
+    }
+
+    /// Returns the name of the file the message is stored in, or `None` if the message is not an
+    /// engine API message.
+    fn message_filename<T: PayloadTypes>(
+        msg: &BeaconEngineMessage<T>,
+        received_at: SystemTime,
+    ) -> Option<String> {
+        let timestamp = received_at.duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis();
+        match msg {
+            BeaconEngineMessage::ForkchoiceUpdated { state, .. } => {
+                Some(format!("{}-fcu-{}.json", timestamp, state.head_block_hash))
+            }
+            BeaconEngineMessage::NewPayload { payload, .. } |
+            BeaconEngineMessage::RethNewPayload { payload, .. } => {
+                Some(format!("{}-new_payload-{}.json", timestamp, payload.block_hash()))
+            }
+            // not engine API messages, so they can't be replayed
+            BeaconEngineMessage::SetHead { .. } |
+            BeaconEngineMessage::PausePersistence { .. } |
+            BeaconEngineMessage::ResumePersistence { .. } => None,
+        }
+    }
+
     /// Finds and iterates through any stored engine API message files, ordered by timestamp.
     pub fn engine_messages_iter(&self) -> eyre::Result<impl Iterator<Item = PathBuf>> {
         let mut filenames_by_ts = BTreeMap::<u64, Vec<PathBuf>>::default();
         for entry in fs::read_dir(&self.path)? {
             let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                // skip the responses directory
+                continue
+            }
             let filename = entry.file_name();
             if let Some(filename) = filename.to_str().filter(|n| n.ends_with(".json")) {
-                if let Some(Ok(timestamp)) = filename.split('-').next().map(|n| n.parse::<u64>()) {
+                if let Some(timestamp) = Self::message_timestamp(&entry.path()) {
                     filenames_by_ts.entry(timestamp).or_default().push(entry.path());
                     tracing::debug!(target: "engine::store", timestamp, filename, "Queued engine API message");
                 } else {
@@ -107,7 +187,7 @@ impl EngineMessageStore {
     }
 }
 
-/// A wrapper stream that stores Engine API messages in
+/// A wrapper stream that stores Engine API messages and the engine responses to them in
 /// the specified directory.
 #[derive(Debug)]
 #[pin_project::pin_project]
@@ -126,6 +206,78 @@ impl<S> EngineStoreStream<S> {
     }
 }
 
+/// Replaces the response channel of a message with one that is served by a spawned task.
+///
+/// The task forwards the engine response to the original channel as soon as it arrives and
+/// stores it afterwards, so the caller never waits on the store.
+fn forward_response<R: Send + 'static>(
+    tx: oneshot::Sender<R>,
+    store: EngineMessageStore,
+    filename: String,
+    to_stored: impl FnOnce(&R) -> Option<StoredEngineApiResponse> + Send + 'static,
+) -> oneshot::Sender<R> {
+    let (response_tx, response_rx) = oneshot::channel();
+    tokio::spawn(async move {
+        let Ok(response) = response_rx.await else { return };
+        let stored = to_stored(&response);
+        let _ = tx.send(response);
+        if let Some(stored) = stored &&
+            let Err(error) = store.on_response(&filename, &stored)
+        {
+            error!(target: "engine::stream::store", response = ?stored, %error, "Error handling Engine API response");
+        }
+    });
+    response_tx
+}
+
+/// Intercepts the response channel of the message, so that the engine response is stored under
+/// `filename` once it has been forwarded to the caller.
+fn intercept_response<T: PayloadTypes>(
+    msg: BeaconEngineMessage<T>,
+    store: &EngineMessageStore,
+    filename: String,
+) -> BeaconEngineMessage<T> {
+    let store = store.clone();
+    match msg {
+        BeaconEngineMessage::NewPayload { payload, tx } => {
+            let tx = forward_response(tx, store, filename, |response| {
+                let status = response.as_ref().ok()?.clone();
+                Some(StoredEngineApiResponse::NewPayload { status })
+            });
+            BeaconEngineMessage::NewPayload { payload, tx }
+        }
+        BeaconEngineMessage::RethNewPayload {
+            payload,
+            wait_for_persistence,
+            wait_for_caches,
+            tx,
+            enqueued_at,
+        } => {
+            let tx = forward_response(tx, store, filename, |response| {
+                let (status, _) = response.as_ref().ok()?;
+                Some(StoredEngineApiResponse::NewPayload { status: status.clone() })
+            });
+            BeaconEngineMessage::RethNewPayload {
+                payload,
+                wait_for_persistence,
+                wait_for_caches,
+                tx,
+                enqueued_at,
+            }
+        }
+        BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, tx } => {
+            let tx = forward_response(tx, store, filename, |response| {
+                let status = response.as_ref().ok()?.forkchoice_status();
+                Some(StoredEngineApiResponse::ForkchoiceUpdated { status })
+            });
+            BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, tx }
+        }
+        msg @ (BeaconEngineMessage::SetHead { .. } |
+        BeaconEngineMessage::PausePersistence { .. } |
+        BeaconEngineMessage::ResumePersistence { .. }) => msg,
+    }
+}
+
 impl<S, T> Stream for EngineStoreStream<S>
 where
     S: Stream<Item = BeaconEngineMessage<T>>,
@@ -135,12 +287,15 @@ where
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let mut this = self.project();
-        let next = ready!(this.stream.poll_next_unpin(cx));
-        if let Some(msg) = &next &&
-            let Err(error) = this.store.on_message(msg, SystemTime::now())
-        {
+        let Some(msg) = ready!(this.stream.poll_next_unpin(cx)) else { return Poll::Ready(None) };
+        let received_at = SystemTime::now();
+        if let Err(error) = this.store.on_message(&msg, received_at) {
             error!(target: "engine::stream::store", ?msg, %error, "Error handling Engine API message");
         }
-        Poll::Ready(next)
+
+        let Some(filename) = EngineMessageStore::message_filename(&msg, received_at) else {
+            return Poll::Ready(Some(msg))
+        };
+        Poll::Ready(Some(intercept_response(msg, this.store, filename)))
     }
 }
@@ -48,6 +48,13 @@ pub struct EraClient<Http> {
     era_type: EraFileType,
 }
 
+impl<Http> EraClient<Http> {
+    /// Returns the [`EraFileType`] downloaded by this client.
+    pub const fn era_type(&self) -> EraFileType {
+        self.era_type
+    }
+}
+
 impl<Http: HttpClient + Clone> EraClient<Http> {
     const CHECKSUMS: &'static str = "checksums.txt";
 
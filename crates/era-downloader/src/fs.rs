@@ -1,12 +1,22 @@
 use crate::{EraMeta, BLOCKS_PER_FILE};
 use alloy_primitives::{hex, hex::ToHexExt, BlockNumber};
-use eyre::{eyre, OptionExt};
+use eyre::{bail, eyre, OptionExt};
 use futures_util::{stream, Stream};
+use reth_era::common::file_ops::EraFileType;
 use reth_fs_util as fs;
 use sha2::{Digest, Sha256};
 use std::{fmt::Debug, io, io::BufRead, path::Path, str::FromStr};
 
-/// Creates a new ordered asynchronous [`Stream`] of ERA1 files read from `dir`.
+/// Creates a new ordered asynchronous [`Stream`] of ERA1 or ERA files read from `dir`.
+///
+/// ERA1 files are checked against `checksums.txt` in `dir` and those with blocks before
+/// `start_from` are skipped. Its lines either contain only the checksum of the file with the same
+/// position, or the checksum followed by the file name like `sha256sum` outputs, in which case the
+/// files are returned in the order they're listed.
+///
+/// ERA files have no checksums file and are verified against the historical roots they contain
+/// when read instead. Since they are numbered by slot, all of them are returned regardless of
+/// `start_from`. A `dir` must not contain both kinds of files.
 pub fn read_dir(
     dir: impl AsRef<Path> + Send + Sync + 'static,
     start_from: BlockNumber,
@@ -19,24 +29,23 @@ pub fn read_dir(
             (|| {
                 let path = entry?.path();
 
-                if path.extension() == Some("era1".as_ref()) &&
-                    let Some(last) = path.components().next_back()
-                {
+                if let Some(last) = path.components().next_back() {
                     let str = last.as_os_str().to_string_lossy().to_string();
                     let parts = str.split('-').collect::<Vec<_>>();
 
-                    if parts.len() == 3 {
+                    if let Some(file_type) = EraFileType::from_filename(&str) &&
+                        parts.len() == 3
+                    {
                         let number = usize::from_str(parts[1])?;
 
-                        return Ok(Some((number, path.into_boxed_path())));
+                        return Ok(Some((file_type, number, path.into_boxed_path())));
                     }
                 }
 
                 if path.file_name() == Some("checksums.txt".as_ref()) {
                     let file = fs::open(path)?;
                     let reader = io::BufReader::new(file);
-                    let lines = reader.lines();
-                    checksums = Some(lines);
+                    checksums = Some(reader.lines().collect::<Result<Vec<_>, _>>()?);
                 }
 
                 Ok(None)
@@ -44,39 +53,110 @@ pub fn read_dir(
             .transpose()
         })
         .collect::<eyre::Result<Vec<_>>>()?;
-    let mut checksums = checksums.ok_or_eyre("Missing file `checksums.txt` in the `dir`")?;
 
-    let start_index = start_from as usize / BLOCKS_PER_FILE;
-    for _ in 0..start_index {
-        // skip the first entries in the checksums iterator so that both iters align
-        checksums.next().transpose()?.ok_or_eyre("Got less checksums than ERA files")?;
+    let is_era = entries.iter().any(|(file_type, ..)| *file_type == EraFileType::Era);
+    if is_era && entries.iter().any(|(file_type, ..)| *file_type == EraFileType::Era1) {
+        bail!("Found both ERA and ERA1 files in the `dir`");
     }
 
-    entries.sort_by_key(|(left, _)| *left);
+    entries.sort_by_key(|(_, left, _)| *left);
 
-    Ok(stream::iter(entries.into_iter().skip_while(move |(n, _)| *n < start_index).map(
-        move |(_, path)| {
-            let expected_checksum =
-                checksums.next().transpose()?.ok_or_eyre("Got less checksums than ERA files")?;
-            let expected_checksum = hex::decode(expected_checksum)?;
+    let files = if is_era {
+        entries.into_iter().map(|(_, _, path)| (path, None)).collect::<Vec<_>>()
+    } else {
+        let checksums = checksums.ok_or_eyre("Missing file `checksums.txt` in the `dir`")?;
+        let start_index = start_from as usize / BLOCKS_PER_FILE;
 
-            let mut hasher = Sha256::new();
-            let mut reader = io::BufReader::new(fs::open(&path)?);
+        pair_with_checksums(entries, checksums, start_index)?
+    };
 
-            io::copy(&mut reader, &mut hasher)?;
-            let actual_checksum = hasher.finalize().to_vec();
+    Ok(stream::iter(files.into_iter().map(move |(path, expected_checksum)| {
+        let Some(expected_checksum) = expected_checksum else { return Ok(EraLocalMeta::new(path)) };
+        let expected_checksum = expected_checksum?;
+        let expected_checksum = hex::decode(expected_checksum)?;
 
-            if actual_checksum != expected_checksum {
-                return Err(eyre!(
-                    "Checksum mismatch, got: {}, expected: {}",
-                    actual_checksum.encode_hex(),
-                    expected_checksum.encode_hex()
-                ));
-            }
+        let mut hasher = Sha256::new();
+        let mut reader = io::BufReader::new(fs::open(&path)?);
 
-            Ok(EraLocalMeta::new(path))
-        },
-    )))
+        io::copy(&mut reader, &mut hasher)?;
+        let actual_checksum = hasher.finalize().to_vec();
+
+        if actual_checksum != expected_checksum {
+            return Err(eyre!(
+                "Checksum mismatch, got: {}, expected: {}",
+                actual_checksum.encode_hex(),
+                expected_checksum.encode_hex()
+            ));
+        }
+
+        Ok(EraLocalMeta::new(path))
+    })))
+}
+
+/// The path of an ERA file with its expected checksum, or the error of a missing one.
+type FileWithChecksum = (Box<Path>, Option<eyre::Result<String>>);
+
+/// Pairs the ERA1 `entries`, sorted by number, with the lines of `checksums.txt` and skips those
+/// numbered below `start_index`.
+///
+/// If every line names its file, the files are paired by name and ordered like the lines, since
+/// the files before and after the merge have the same number. Otherwise the line of the file
+/// numbered `N` is expected at position `N`.
+fn pair_with_checksums(
+    entries: Vec<(EraFileType, usize, Box<Path>)>,
+    checksums: Vec<String>,
+    start_index: usize,
+) -> eyre::Result<Vec<FileWithChecksum>> {
+    let mut lines =
+        checksums.iter().map(|line| line.trim()).filter(|line| !line.is_empty()).peekable();
+    let is_named = lines.peek().is_some() && lines.all(|line| line.contains(char::is_whitespace));
+
+    if !is_named {
+        if checksums.len() < start_index {
+            bail!("Got less checksums than ERA files");
+        }
+        // skip the first lines so that both iters align
+        let mut checksums = checksums.into_iter().skip(start_index);
+        return Ok(entries
+            .into_iter()
+            .skip_while(|(_, number, _)| *number < start_index)
+            .map(|(_, _, path)| {
+                let checksum = checksums.next().ok_or_eyre("Got less checksums than ERA files");
+                (path, Some(checksum))
+            })
+            .collect())
+    }
+
+    let mut entries = entries
+        .into_iter()
+        .map(|(_, number, path)| {
+            let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+            (name, (number, path))
+        })
+        .collect::<std::collections::HashMap<_, _>>();
+
+    let files = checksums
+        .iter()
+        .filter_map(|line| line.trim().split_once(char::is_whitespace))
+        .map(|(checksum, name)| {
+            // `sha256sum` marks files read in binary mode with `*`
+            let name = name.trim_start().trim_start_matches('*');
+            let (number, path) = entries
+                .remove(name)
+                .ok_or_else(|| eyre!("Missing file `{name}` listed in `checksums.txt`"))?;
+            Ok((number, path, checksum.to_string()))
+        })
+        .collect::<eyre::Result<Vec<_>>>()?;
+
+    if let Some(name) = entries.into_keys().min() {
+        bail!("File `{name}` is not listed in `checksums.txt`");
+    }
+
+    Ok(files
+        .into_iter()
+        .filter(|(number, ..)| *number >= start_index)
+        .map(|(_, path, checksum)| (path, Some(Ok(checksum))))
+        .collect())
 }
 
 /// Contains information about an ERA file that is on the local file-system and is read-only.
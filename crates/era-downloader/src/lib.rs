@@ -42,4 +42,61 @@ pub use client::{EraClient, HttpClient};
 pub use fs::read_dir;
 pub use stream::{EraMeta, EraStream, EraStreamConfig};
 
+use alloy_primitives::BlockNumber;
+use reth_era::era::types::group::SLOTS_PER_HISTORICAL_ROOT;
+
 pub(crate) const BLOCKS_PER_FILE: usize = 8192;
+
+/// The first block after the merge, used to find the ERA file that holds a block.
+///
+/// The `slot` must not be above the slot of the block `number`, so that no ERA file holding a
+/// requested block is ever skipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MergeBlock {
+    /// Number of the first post-merge block.
+    pub number: BlockNumber,
+    /// Beacon chain slot of the first post-merge block.
+    pub slot: u64,
+}
+
+impl MergeBlock {
+    /// The first post-merge block of mainnet.
+    pub const MAINNET: Self = Self { number: 15_537_394, slot: 4_700_013 };
+
+    /// The first post-merge block of sepolia, with the slot of the bellatrix fork.
+    pub const SEPOLIA: Self = Self { number: 1_450_409, slot: 3_200 };
+
+    /// Returns the first post-merge block of the chain with `chain_id`, if known.
+    pub const fn from_chain_id(chain_id: u64) -> Option<Self> {
+        match chain_id {
+            1 => Some(Self::MAINNET),
+            11155111 => Some(Self::SEPOLIA),
+            _ => None,
+        }
+    }
+
+    /// Returns the index of the first ERA file that can hold `block_number`.
+    ///
+    /// A slot holds at most one block, so a block is never at a slot below the merge slot plus
+    /// the number of blocks since the merge. Pre-merge blocks map to the file of the merge. The
+    /// ERA file `N` holds the blocks of the slots before the state at slot `N * 8192`.
+    pub const fn era_index(&self, block_number: BlockNumber) -> usize {
+        let slot = self.slot + block_number.saturating_sub(self.number);
+
+        (slot / SLOTS_PER_HISTORICAL_ROOT) as usize + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(0, 574; "pre-merge block")]
+    #[test_case(15_537_394, 574; "merge block")]
+    #[test_case(15_537_394 + 2_194, 574; "last block that can be in the merge file")]
+    #[test_case(15_537_394 + 2_195, 575; "first block that can be in the next file")]
+    fn test_era_index(block_number: BlockNumber, expected: usize) {
+        assert_eq!(MergeBlock::MAINNET.era_index(block_number), expected);
+    }
+}
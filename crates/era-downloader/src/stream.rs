@@ -1,7 +1,8 @@
-use crate::{client::HttpClient, EraClient, BLOCKS_PER_FILE};
+use crate::{client::HttpClient, EraClient, MergeBlock, BLOCKS_PER_FILE};
 use alloy_primitives::BlockNumber;
 use futures_util::{stream::FuturesOrdered, FutureExt, Stream, StreamExt};
 use reqwest::Url;
+use reth_era::common::file_ops::EraFileType;
 use reth_fs_util as fs;
 use std::{
     collections::VecDeque,
@@ -24,12 +25,13 @@ use std::{
 pub struct EraStreamConfig {
     max_files: usize,
     max_concurrent_downloads: usize,
-    start_from: Option<usize>,
+    start_from: Option<BlockNumber>,
+    merge: Option<MergeBlock>,
 }
 
 impl Default for EraStreamConfig {
     fn default() -> Self {
-        Self { max_files: 5, max_concurrent_downloads: 3, start_from: None }
+        Self { max_files: 5, max_concurrent_downloads: 3, start_from: None, merge: None }
     }
 }
 
@@ -47,8 +49,20 @@ impl EraStreamConfig {
     }
 
     /// Overrides the starting ERA file index to be the first one that contains `block_number`.
+    ///
+    /// ERA files are numbered by slot, so they need the [`merge`](Self::with_merge) to find it.
     pub const fn start_from(mut self, block_number: BlockNumber) -> Self {
-        self.start_from.replace(block_number as usize / BLOCKS_PER_FILE);
+        self.start_from = Some(block_number);
+        self
+    }
+
+    /// Sets the first post-merge block of the chain, which maps block numbers to ERA files.
+    ///
+    /// ERA streams start at the file holding the merge block or the
+    /// [`start_from`](Self::start_from) block, whichever is later. Without it, they are streamed
+    /// from the first file.
+    pub const fn with_merge(mut self, merge: Option<MergeBlock>) -> Self {
+        self.merge = merge;
         self
     }
 }
@@ -79,6 +93,13 @@ impl<Http> EraStream<Http> {
     /// Constructs a new [`EraStream`] that downloads concurrently up to `max_concurrent_downloads`
     /// ERA1 files to `client` `folder`, keeping their count up to `max_files`.
     pub fn new(client: EraClient<Http>, config: EraStreamConfig) -> Self {
+        let start_from = config.start_from.unwrap_or_default();
+        let index = match (client.era_type(), config.merge) {
+            (EraFileType::Era1, _) => start_from as usize / BLOCKS_PER_FILE,
+            (EraFileType::Era, Some(merge)) => merge.era_index(start_from),
+            (EraFileType::Era, None) => 0,
+        };
+
         Self {
             download_stream: DownloadStream {
                 downloads: Default::default(),
@@ -95,7 +116,7 @@ impl<Http> EraStream<Http> {
                 fetch_file_list: Box::pin(async move { Ok(()) }),
                 state: Default::default(),
                 max_files: config.max_files,
-                index: config.start_from.unwrap_or_default(),
+                index,
                 last: None,
                 downloading: 0,
             },
@@ -102,3 +102,75 @@ async fn test_streaming_from_local_directory(
         },
     }
 }
+
+#[tokio::test]
+async fn test_streaming_in_order_of_named_checksums() {
+    let folder = tempfile::tempdir().unwrap();
+    let folder = folder.path().to_owned();
+
+    // The files before and after the merge have the same number, the checksums file sets the order
+    let files = [
+        ("mainnet-01896-e6ebe562.era1", CONTENTS_1),
+        ("mainnet-01896-a5364e9a.era1", CONTENTS_0),
+        ("mainnet-01897-5ec1ffb8.era1", CONTENTS_0),
+    ];
+    let checksums = files
+        .iter()
+        .map(|(name, contents)| {
+            format!("{}  {name}\n", sha2::Sha256::digest(contents).encode_hex())
+        })
+        .collect::<String>();
+    fs::write(folder.join("checksums.txt"), checksums).await.unwrap();
+    for (name, contents) in files {
+        fs::write(folder.join(name), contents).await.unwrap();
+    }
+
+    let folder = folder.into_boxed_path();
+    let mut stream = read_dir(folder.clone(), 1896 * 8192).unwrap();
+    for (expected_file, _) in files {
+        let actual_file = stream.next().await.unwrap().unwrap();
+
+        assert_eq!(actual_file, folder.join(expected_file));
+    }
+    assert!(stream.next().await.is_none());
+
+    let mut stream = read_dir(folder.clone(), 1897 * 8192).unwrap();
+    assert_eq!(stream.next().await.unwrap().unwrap(), folder.join(files[2].0));
+    assert!(stream.next().await.is_none());
+
+    fs::write(folder.join("mainnet-01898-00000000.era1"), CONTENTS_0).await.unwrap();
+    let err = read_dir(folder, 0).err().expect("should be err").to_string();
+    assert_eq!(err, "File `mainnet-01898-00000000.era1` is not listed in `checksums.txt`");
+}
+
+#[tokio::test]
+async fn test_streaming_era_files_without_checksums() {
+    let folder = tempfile::tempdir().unwrap();
+    let folder = folder.path().to_owned();
+
+    fs::write(folder.join("mainnet-01001-f2d6e9a1.era"), CONTENTS_1).await.unwrap();
+    fs::write(folder.join("mainnet-01000-4b363db9.era"), CONTENTS_0).await.unwrap();
+
+    let folder = folder.into_boxed_path();
+    let mut stream = read_dir(folder.clone(), 1_000_000).unwrap();
+
+    for expected_file in ["mainnet-01000-4b363db9.era", "mainnet-01001-f2d6e9a1.era"] {
+        let actual_file = stream.next().await.unwrap().unwrap();
+
+        assert_eq!(actual_file, folder.join(expected_file));
+    }
+    assert!(stream.next().await.is_none());
+}
+
+#[tokio::test]
+async fn test_streaming_mixed_era_and_era1_files_fails() {
+    let folder = tempfile::tempdir().unwrap();
+    let folder = folder.path().to_owned();
+
+    fs::write(folder.join("mainnet-00000-5ec1ffb8.era1"), CONTENTS_0).await.unwrap();
+    fs::write(folder.join("mainnet-01000-4b363db9.era"), CONTENTS_1).await.unwrap();
+
+    let actual_err = read_dir(folder, 0).err().expect("should be err").to_string();
+
+    assert_eq!(actual_err, "Found both ERA and ERA1 files in the `dir`");
+}
@@ -1,9 +1,13 @@
 //! Logic to export from database era1 block history
 //! and injecting them into era1 files with `Era1Writer`.
+//!
+//! Pre-merge and post-merge blocks are never written to the same file. Pre-merge eras are verified
+//! against known epoch accumulators, and a `checksums.txt` manifest is written next to the exported
+//! files.
 
 use crate::calculate_td_by_number;
 use alloy_consensus::{BlockHeader, Sealable, TxReceipt};
-use alloy_primitives::{BlockNumber, U256};
+use alloy_primitives::{hex, BlockNumber, B256, U256};
 use eyre::{eyre, Result};
 use reth_era::{
     common::file_ops::{EraFileId, StreamWriter},
@@ -22,8 +26,11 @@ use reth_era::{
 use reth_fs_util as fs;
 use reth_primitives_traits::Block;
 use reth_storage_api::{BlockNumReader, BlockReader, HeaderProvider};
+use sha2::{Digest, Sha256};
 use std::{
-    path::PathBuf,
+    io::{self, BufRead, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
     time::{Duration, Instant},
 };
 use tracing::{debug, info, warn};
@@ -31,6 +38,7 @@ use tracing::{debug, info, warn};
 const REPORT_INTERVAL_SECS: u64 = 10;
 const ENTRY_HEADER_SIZE: usize = 8;
 const VERSION_ENTRY_SIZE: usize = ENTRY_HEADER_SIZE;
+const CHECKSUMS_FILE_NAME: &str = "checksums.txt";
 
 /// Configuration to export block history
 /// to era1 files
@@ -48,6 +56,17 @@ pub struct ExportConfig {
     pub max_blocks_per_file: u64,
     /// Network name.
     pub network: String,
+    /// Known accumulator roots of the pre-merge eras, indexed by era number.
+    ///
+    /// Every exported pre-merge era is checked against its root, and the export fails on a
+    /// mismatch or if an era can't be verified, see [`Self::skip_epoch_verification`].
+    pub epoch_accumulators: Vec<B256>,
+    /// Whether to export pre-merge eras without verifying them against
+    /// [`Self::epoch_accumulators`].
+    ///
+    /// Otherwise the export fails for pre-merge eras that aren't exported from their first to
+    /// their last block, or that have no known root.
+    pub skip_epoch_verification: bool,
 }
 
 impl Default for ExportConfig {
@@ -58,6 +77,8 @@ impl Default for ExportConfig {
             last_block_number: (MAX_BLOCKS_PER_ERA1 - 1) as u64,
             max_blocks_per_file: MAX_BLOCKS_PER_ERA1 as u64,
             network: "mainnet".to_string(),
+            epoch_accumulators: Vec::new(),
+            skip_epoch_verification: false,
         }
     }
 }
@@ -81,9 +102,30 @@ impl ExportConfig {
     }
 }
 
+/// Reads known epoch accumulator roots from the file at `path`.
+///
+/// The file contains one hex encoded root per line, the root of era `N` being on line `N`.
+pub fn read_epoch_accumulators(path: &Path) -> Result<Vec<B256>> {
+    read_roots(path, "epoch accumulator")
+}
+
+/// Reads one hex encoded `kind` of root per line from the file at `path`.
+pub(crate) fn read_roots(path: &Path, kind: &str) -> Result<Vec<B256>> {
+    io::BufReader::new(fs::open(path)?)
+        .lines()
+        .map(|line| {
+            let line = line?;
+            B256::from_str(line.trim()).map_err(|e| eyre!("Invalid {kind} `{}`: {e}", line.trim()))
+        })
+        .collect()
+}
+
 /// Fetches block history data from the provider
 /// and prepares it for export to era1 files
 /// for a given number of blocks then writes them to disk.
+///
+/// Returns the exported files, whose checksums and names are also written to `checksums.txt` in
+/// [`ExportConfig::dir`] in the same order.
 pub fn export<P>(provider: &P, config: &ExportConfig) -> Result<Vec<PathBuf>>
 where
     P: BlockReader,
@@ -125,11 +167,35 @@ where
         U256::ZERO
     };
 
+    let mut verifier = EpochVerifier::new(
+        (!config.skip_epoch_verification).then_some(config.epoch_accumulators.as_slice()),
+    );
+
     // Process blocks in chunks according to `max_blocks_per_file`
-    for start_block in
-        (config.first_block_number..=last_block_number).step_by(config.max_blocks_per_file as usize)
-    {
-        let end_block = (start_block + config.max_blocks_per_file - 1).min(last_block_number);
+    let mut start_block = config.first_block_number;
+    let mut after_merge = false;
+    while start_block <= last_block_number {
+        // The first post-merge file ends at the next multiple of `max_blocks_per_file` so that
+        // the following ones are aligned to eras again
+        let end_block = if after_merge {
+            (start_block / config.max_blocks_per_file + 1) * config.max_blocks_per_file - 1
+        } else {
+            start_block + config.max_blocks_per_file - 1
+        }
+        .min(last_block_number);
+        after_merge = false;
+
+        let mut headers = provider.headers_range(start_block..=end_block)?;
+
+        // Pre-merge and post-merge blocks are never written to the same file
+        let end_block = match headers.iter().position(|h| h.difficulty().is_zero()) {
+            Some(merge) if merge > 0 => {
+                headers.truncate(merge);
+                after_merge = true;
+                start_block + merge as u64 - 1
+            }
+            _ => end_block,
+        };
         let block_count = (end_block - start_block + 1) as usize;
 
         info!(
@@ -137,17 +203,16 @@ where
             "Processing blocks {start_block} to {end_block} ({block_count} blocks)"
         );
 
-        let headers = provider.headers_range(start_block..=end_block)?;
-
         // Pre-compute accumulator from headers to determine filename
         let mut precompute_td = total_difficulty;
-        let header_records: Vec<HeaderRecord> = headers
-            .iter()
-            .map(|h| {
-                precompute_td += h.difficulty();
-                HeaderRecord { block_hash: h.hash_slow(), total_difficulty: precompute_td }
-            })
-            .collect();
+        let mut header_records = Vec::with_capacity(headers.len());
+        for header in &headers {
+            precompute_td += header.difficulty();
+            let record =
+                HeaderRecord { block_hash: header.hash_slow(), total_difficulty: precompute_td };
+            verifier.push(header.number(), !header.difficulty().is_zero(), &record)?;
+            header_records.push(record);
+        }
         let accumulator = Accumulator::from_header_records(&header_records)
             .map_err(|e| eyre!("Failed to compute accumulator: {e}"))?;
         let file_hash: [u8; 4] = accumulator.root[..4].try_into().unwrap();
@@ -233,8 +298,18 @@ where
             );
             created_files.push(file_path);
         }
+
+        start_block = end_block + 1;
     }
 
+    // The era of the last exported block is complete if the next block is post-merge
+    let merged = provider
+        .header_by_number(last_block_number + 1)?
+        .is_some_and(|header| header.difficulty().is_zero());
+    verifier.finish(merged)?;
+
+    write_checksums(&config.dir, &created_files)?;
+
     info!(
         target: "era::history::export",
         "Successfully wrote {} ERA1 files in {:?}",
@@ -245,6 +320,120 @@ where
     Ok(created_files)
 }
 
+/// Verifies pre-merge eras against known epoch accumulators.
+///
+/// An era is complete once its last block, or the last pre-merge block, has been pushed. Eras that
+/// aren't pushed from their first block until they're complete can't be verified, which is an
+/// error.
+struct EpochVerifier<'a> {
+    /// Known accumulator roots, indexed by era number, or `None` to skip the verification.
+    known: Option<&'a [B256]>,
+    /// The era being collected.
+    era: u64,
+    /// Header records of the era being collected.
+    records: Vec<HeaderRecord>,
+}
+
+impl<'a> EpochVerifier<'a> {
+    const fn new(known: Option<&'a [B256]>) -> Self {
+        Self { known, era: 0, records: Vec::new() }
+    }
+
+    /// Adds the record of block `number` and verifies its era if it is complete.
+    fn push(&mut self, number: BlockNumber, pre_merge: bool, record: &HeaderRecord) -> Result<()> {
+        if self.known.is_none() {
+            return Ok(())
+        }
+
+        if !pre_merge {
+            // The era of the last pre-merge block ends at the merge
+            return self.verify()
+        }
+
+        if self.records.is_empty() {
+            self.era = number / MAX_BLOCKS_PER_ERA1 as u64;
+            if !number.is_multiple_of(MAX_BLOCKS_PER_ERA1 as u64) {
+                return Err(eyre!(
+                    "Cannot verify era {} against its epoch accumulator, the export must start at its first block {}",
+                    self.era,
+                    self.era * MAX_BLOCKS_PER_ERA1 as u64
+                ))
+            }
+        }
+
+        self.records.push(record.clone());
+        if self.records.len() == MAX_BLOCKS_PER_ERA1 {
+            return self.verify()
+        }
+
+        Ok(())
+    }
+
+    /// Verifies the era being collected once all blocks have been pushed, `merged` being whether
+    /// the block after the last pushed one is post-merge.
+    fn finish(&mut self, merged: bool) -> Result<()> {
+        if self.records.is_empty() {
+            return Ok(())
+        }
+        if !merged {
+            return Err(eyre!(
+                "Cannot verify era {} against its epoch accumulator, the export must end at its last block {}",
+                self.era,
+                (self.era + 1) * MAX_BLOCKS_PER_ERA1 as u64 - 1
+            ))
+        }
+
+        self.verify()
+    }
+
+    fn verify(&mut self) -> Result<()> {
+        let Some(known) = self.known else { return Ok(()) };
+        if self.records.is_empty() {
+            return Ok(())
+        }
+        let records = std::mem::take(&mut self.records);
+        let era = self.era;
+
+        let expected = known
+            .get(era as usize)
+            .ok_or_else(|| eyre!("No known epoch accumulator for era {era}"))?;
+        let accumulator = Accumulator::from_header_records(&records)
+            .map_err(|e| eyre!("Failed to compute accumulator: {e}"))?;
+
+        if accumulator.root != *expected {
+            return Err(eyre!(
+                "Epoch accumulator mismatch for era {era}, got: {}, expected: {expected}",
+                accumulator.root
+            ));
+        }
+
+        debug!(target: "era::history::export", era, root = %expected, "Verified epoch accumulator");
+
+        Ok(())
+    }
+}
+
+/// Writes the SHA-256 checksum of every file in `files` to `checksums.txt` in `dir`, one line per
+/// file in the same order.
+///
+/// Lines have the format of `sha256sum`, the hex encoded checksum followed by the file name, since
+/// the files before and after the merge of the same era have the same era number.
+fn write_checksums(dir: &Path, files: &[PathBuf]) -> Result<()> {
+    let mut checksums = io::BufWriter::new(fs::create_file(dir.join(CHECKSUMS_FILE_NAME))?);
+
+    for file in files {
+        let mut hasher = Sha256::new();
+        io::copy(&mut io::BufReader::new(fs::open(file)?), &mut hasher)?;
+
+        let file_name = file.file_name().unwrap_or_default().to_string_lossy();
+        writeln!(checksums, "{}  {file_name}", hex::encode(hasher.finalize()))?;
+    }
+
+    checksums.flush()?;
+
+    Ok(())
+}
+
 // Determines the actual last block number that can be exported,
 // Uses `headers_range` fallback when `best_block_number` is stale due to static file storage.
 fn determine_export_range<P>(provider: &P, config: &ExportConfig) -> Result<BlockNumber>
@@ -320,10 +509,102 @@ where
 
 #[cfg(test)]
 mod tests {
+    use super::EpochVerifier;
     use crate::ExportConfig;
-    use reth_era::era1::types::execution::MAX_BLOCKS_PER_ERA1;
+    use alloy_primitives::{B256, U256};
+    use reth_era::era1::types::execution::{Accumulator, HeaderRecord, MAX_BLOCKS_PER_ERA1};
     use tempfile::tempdir;
 
+    fn records(count: usize) -> Vec<HeaderRecord> {
+        (0..count)
+            .map(|i| HeaderRecord {
+                block_hash: B256::with_last_byte(i as u8),
+                total_difficulty: U256::from(i + 1),
+            })
+            .collect()
+    }
+
+    fn root(records: &[HeaderRecord]) -> B256 {
+        Accumulator::from_header_records(records).unwrap().root
+    }
+
+    #[test]
+    fn test_epoch_verifier_checks_complete_eras() {
+        let records = records(MAX_BLOCKS_PER_ERA1);
+        let known = [root(&records)];
+        let mut verifier = EpochVerifier::new(Some(&known));
+        for (number, record) in records.iter().enumerate() {
+            verifier.push(number as u64, true, record).unwrap();
+        }
+
+        let known = [B256::ZERO];
+        let mut verifier = EpochVerifier::new(Some(&known));
+        let (last, rest) = records.split_last().unwrap();
+        for (number, record) in rest.iter().enumerate() {
+            verifier.push(number as u64, true, record).unwrap();
+        }
+        let err = verifier.push(rest.len() as u64, true, last).unwrap_err();
+        assert!(err.to_string().starts_with("Epoch accumulator mismatch for era 0"));
+    }
+
+    #[test]
+    fn test_epoch_verifier_ends_era_at_merge() {
+        let records = records(100);
+        let known = [root(&records)];
+        let mut verifier = EpochVerifier::new(Some(&known));
+        for (number, record) in records.iter().enumerate() {
+            verifier.push(number as u64, true, record).unwrap();
+        }
+        assert_eq!(verifier.records.len(), 100);
+
+        verifier.push(100, false, &records[0]).unwrap();
+        assert!(verifier.records.is_empty());
+
+        let known = [B256::ZERO];
+        let mut verifier = EpochVerifier::new(Some(&known));
+        for (number, record) in records.iter().enumerate() {
+            verifier.push(number as u64, true, record).unwrap();
+        }
+        assert!(verifier.push(100, false, &records[0]).is_err());
+    }
+
+    #[test]
+    fn test_epoch_verifier_rejects_partial_eras() {
+        let records = records(100);
+        let known = [root(&records)];
+
+        // Eras can only be verified if collected from their first block
+        let mut verifier = EpochVerifier::new(Some(&known));
+        let err = verifier.push(1, true, &records[1]).unwrap_err();
+        assert!(err.to_string().starts_with("Cannot verify era 0"));
+
+        // and until their last block or the merge
+        let mut verifier = EpochVerifier::new(Some(&known));
+        for (number, record) in records.iter().enumerate() {
+            verifier.push(number as u64, true, record).unwrap();
+        }
+        let err = verifier.finish(false).unwrap_err();
+        assert!(err.to_string().starts_with("Cannot verify era 0"));
+        verifier.finish(true).unwrap();
+
+        // Nothing is verified if skipped
+        let mut verifier = EpochVerifier::new(None);
+        verifier.push(1, true, &records[1]).unwrap();
+        verifier.finish(false).unwrap();
+    }
+
+    #[test]
+    fn test_epoch_verifier_requires_known_accumulators() {
+        let records = records(MAX_BLOCKS_PER_ERA1);
+        let mut verifier = EpochVerifier::new(Some(&[]));
+        let (last, rest) = records.split_last().unwrap();
+        for (number, record) in rest.iter().enumerate() {
+            verifier.push(number as u64, true, record).unwrap();
+        }
+        let err = verifier.push(rest.len() as u64, true, last).unwrap_err();
+        assert_eq!(err.to_string(), "No known epoch accumulator for era 0");
+    }
+
     #[test]
     fn test_export_config_validation() {
         let temp_dir = tempdir().unwrap();
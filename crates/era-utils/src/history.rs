@@ -1,5 +1,6 @@
+use crate::payload::PayloadIter;
 use alloy_consensus::BlockHeader;
-use alloy_primitives::{BlockHash, BlockNumber, U256};
+use alloy_primitives::{BlockHash, BlockNumber, B256, U256};
 use futures_util::{Stream, StreamExt};
 use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW},
@@ -9,7 +10,10 @@ use reth_db_api::{
     RawKey, RawTable, RawValue,
 };
 use reth_era::{
-    common::{decode::DecodeCompressedRlp, file_ops::StreamReader},
+    common::{
+        decode::DecodeCompressedRlp,
+        file_ops::{EraFileType, StreamReader},
+    },
     e2s::error::E2sError,
     era1::{
         file::{BlockTupleIterator, Era1Reader},
@@ -44,9 +48,13 @@ use tracing::info;
 
 /// Imports blocks from `downloader` using `provider`.
 ///
+/// Era files are verified against the known `historical_roots` of the chain, see
+/// [`PayloadIter::open`].
+///
 /// Returns current block height.
 pub fn import<Downloader, Era, PF, B, BB, BH>(
     mut downloader: Downloader,
+    historical_roots: &[B256],
     provider_factory: &PF,
     hash_collector: &mut Collector<BlockHash, BlockNumber>,
 ) -> eyre::Result<BlockNumber>
@@ -90,6 +98,7 @@ where
 
         height = process(
             &meta?,
+            historical_roots,
             &mut static_file_provider.latest_writer(StaticFileSegment::Headers)?,
             &provider,
             hash_collector,
@@ -142,7 +151,8 @@ where
 
 /// Extracts block headers and bodies from `meta` and appends them using `writer` and `provider`.
 ///
-/// Collects hash to height using `hash_collector`.
+/// Era files are verified against the known `historical_roots` of the chain, see
+/// [`PayloadIter::open`]. Collects hash to height using `hash_collector`.
 ///
 /// Skips all blocks below the [`start_bound`] of `block_numbers` and stops when reaching past the
 /// [`end_bound`] or the end of the file.
@@ -153,6 +163,7 @@ where
 /// [`end_bound`]: RangeBounds::end_bound
 pub fn process<Era, P, B, BB, BH>(
     meta: &Era,
+    historical_roots: &[B256],
     writer: &mut StaticFileProviderRWRefMut<'_, <P as NodePrimitivesProvider>::Primitives>,
     provider: &P,
     hash_collector: &mut Collector<BlockHash, BlockNumber>,
@@ -169,15 +180,36 @@ where
     P: DBProvider<Tx: DbTxMut> + NodePrimitivesProvider + BlockWriter<Block = B>,
     <P as NodePrimitivesProvider>::Primitives: NodePrimitives<BlockHeader = BH, BlockBody = BB>,
 {
-    let reader = open(meta)?;
-    let iter = reader.iter().map(decode as fn(_) -> _);
+    let iter = open_blocks(meta, historical_roots)?;
     let iter = ProcessIter { iter, era: meta };
 
     process_iter(iter, writer, provider, hash_collector, block_numbers)
 }
 
-type ProcessInnerIter<R, BH, BB> =
-    Map<BlockTupleIterator<R>, fn(Result<BlockTuple, E2sError>) -> eyre::Result<(BH, BB)>>;
+/// An iterator of block headers and bodies read from either an era1 or an era file.
+#[derive(Debug)]
+pub enum BlockIter<R: Read, BH, BB> {
+    /// Pre-merge blocks stored in an era1 file.
+    Era1(Map<BlockTupleIterator<R>, fn(Result<BlockTuple, E2sError>) -> eyre::Result<(BH, BB)>>),
+    /// Post-merge blocks extracted from the beacon blocks of an era file.
+    Era(PayloadIter<R, BH, BB>),
+}
+
+impl<R, BH, BB> Iterator for BlockIter<R, BH, BB>
+where
+    R: Read + Seek,
+    BH: FullBlockHeader + Value,
+    BB: FullBlockBody<OmmerHeader = BH>,
+{
+    type Item = eyre::Result<(BH, BB)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Era1(iter) => iter.next(),
+            Self::Era(iter) => iter.next(),
+        }
+    }
+}
 
 /// An iterator that wraps era file extraction. After the final item [`EraMeta::mark_as_processed`]
 /// is called to ensure proper cleanup.
@@ -187,7 +219,7 @@ where
     BH: FullBlockHeader + Value,
     BB: FullBlockBody<OmmerHeader = BH>,
 {
-    iter: ProcessInnerIter<R, BH, BB>,
+    iter: BlockIter<R, BH, BB>,
     era: &'a Era,
 }
 
@@ -221,7 +253,29 @@ where
     }
 }
 
-/// Opens the era file described by `meta`.
+/// Opens the era1 or era file described by `meta` depending on its extension and returns an
+/// iterator of its block headers and bodies.
+///
+/// Era files are verified against the known `historical_roots` of the chain, see
+/// [`PayloadIter::open`].
+pub fn open_blocks<Era, BH, BB>(
+    meta: &Era,
+    historical_roots: &[B256],
+) -> eyre::Result<BlockIter<std::fs::File, BH, BB>>
+where
+    Era: EraMeta + ?Sized,
+    BH: FullBlockHeader + Value,
+    BB: FullBlockBody<OmmerHeader = BH>,
+{
+    let file_name = meta.path().file_name().unwrap_or_default().to_string_lossy();
+
+    Ok(match EraFileType::from_filename(&file_name) {
+        Some(EraFileType::Era) => BlockIter::Era(PayloadIter::open(meta.path(), historical_roots)?),
+        _ => BlockIter::Era1(open(meta)?.iter().map(decode as fn(_) -> _)),
+    })
+}
+
+/// Opens the era1 file described by `meta`.
 pub fn open<Era>(meta: &Era) -> eyre::Result<Era1Reader<std::fs::File>>
 where
     Era: EraMeta + ?Sized,
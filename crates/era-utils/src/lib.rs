@@ -1,19 +1,28 @@
 //! Utilities to store history from downloaded ERA files with storage-api
 //!  and export it to recreate era1 files.
 //!
+//! History is imported from era1 files up to the merge and from the execution payloads of the
+//! beacon blocks in era files afterward.
+//!
 //! The import is downloaded using [`reth_era_downloader`] and parsed using [`reth_era`].
 
 mod history;
 
+/// Extracts execution blocks from the beacon blocks of post-merge era files.
+mod payload;
+
 /// Export block history data from the database to recreate era1 files.
 mod export;
 
 /// Export history from storage-api between 2 blocks
 /// with parameters defined in [`ExportConfig`].
-pub use export::{export, ExportConfig};
+pub use export::{export, read_epoch_accumulators, ExportConfig};
 
 /// Imports history from ERA files.
 pub use history::{
-    build_index, calculate_td_by_number, decode, import, open, process, process_iter,
-    save_stage_checkpoints, ProcessIter,
+    build_index, calculate_td_by_number, decode, import, open, open_blocks, process, process_iter,
+    save_stage_checkpoints, BlockIter, ProcessIter,
 };
+
+/// Imports post-merge history from the execution payloads of ERA files.
+pub use payload::{decode_payload, read_historical_roots, PayloadIter};
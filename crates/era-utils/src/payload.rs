@@ -0,0 +1,149 @@
+use alloy_primitives::B256;
+use alloy_rlp::Decodable;
+use eyre::{bail, eyre};
+use reth_db_api::table::Value;
+use reth_era::{
+    common::file_ops::StreamReader,
+    era::{
+        file::{BeaconBlockIterator, EraReader},
+        types::{
+            beacon::HistoricalRoots, consensus::CompressedSignedBeaconBlock,
+            group::SLOTS_PER_HISTORICAL_ROOT,
+        },
+    },
+};
+use reth_fs_util as fs;
+use reth_primitives_traits::{BlockBody, FullBlockBody, FullBlockHeader};
+use std::{
+    fmt::{Debug, Formatter},
+    fs::File,
+    io::{Read, Seek},
+    marker::PhantomData,
+    path::Path,
+};
+
+/// Reads the known historical roots of the beacon chain from the file at `path`, i.e. its
+/// `historical_roots` followed by the roots of its `historical_summaries`.
+///
+/// The file contains one hex encoded root per line, the root of era `N` being on line `N - 1`.
+pub fn read_historical_roots(path: &Path) -> eyre::Result<Vec<B256>> {
+    crate::export::read_roots(path, "historical root")
+}
+
+/// An iterator of block headers and bodies extracted from the execution payloads of the beacon
+/// blocks in an era file.
+///
+/// The block roots recorded in the era state are checked against the known historical root of the
+/// era, and every beacon block is checked against them before its payload is converted. The
+/// resulting header must hash to the block hash of the payload. Beacon blocks without an execution
+/// payload, i.e. before the merge, are skipped.
+pub struct PayloadIter<R: Read, BH, BB> {
+    blocks: BeaconBlockIterator<R>,
+    roots: HistoricalRoots,
+    _types: PhantomData<fn() -> (BH, BB)>,
+}
+
+impl<R: Read, BH, BB> Debug for PayloadIter<R, BH, BB> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PayloadIter").field("roots", &self.roots).finish_non_exhaustive()
+    }
+}
+
+impl<BH, BB> PayloadIter<File, BH, BB> {
+    /// Opens the era file at `path`.
+    ///
+    /// Reads the historical roots from the era state first and checks their root against the
+    /// `known` historical roots of the chain, i.e. its `historical_roots` followed by the roots of
+    /// its `historical_summaries`, where era `N` has the root at index `N - 1`.
+    ///
+    /// The genesis era has no blocks and nothing to check.
+    pub fn open(path: &Path, known: &[B256]) -> eyre::Result<Self> {
+        let state = EraReader::new(fs::open(path)?).read_state()?;
+        let roots = state.historical_roots()?;
+
+        if roots.slot() > 0 {
+            if !roots.slot().is_multiple_of(SLOTS_PER_HISTORICAL_ROOT) {
+                bail!(
+                    "State of {} is not at the end of an era: slot {}",
+                    path.display(),
+                    roots.slot()
+                );
+            }
+            let era = roots.slot() / SLOTS_PER_HISTORICAL_ROOT;
+            let expected = known.get(era as usize - 1).ok_or_else(|| {
+                eyre!("No known historical root for era {era} of {}", path.display())
+            })?;
+
+            let historical_root = roots.historical_root()?;
+            if historical_root != *expected {
+                bail!(
+                    "Historical root mismatch for era {era} of {}: got {historical_root}, expected {expected}",
+                    path.display()
+                );
+            }
+        }
+
+        let blocks = EraReader::new(fs::open(path)?).iter();
+
+        Ok(Self { blocks, roots, _types: PhantomData })
+    }
+}
+
+impl<R, BH, BB> Iterator for PayloadIter<R, BH, BB>
+where
+    R: Read + Seek,
+    BH: FullBlockHeader + Value,
+    BB: FullBlockBody<OmmerHeader = BH>,
+{
+    type Item = eyre::Result<(BH, BB)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let block = match self.blocks.next()? {
+                Ok(block) => block,
+                Err(e) => return Some(Err(e.into())),
+            };
+
+            if let Some(item) = decode_payload(&block, &self.roots).transpose() {
+                return Some(item);
+            }
+        }
+    }
+}
+
+/// Extracts a pair of [`FullBlockHeader`] and [`FullBlockBody`] from the execution payload of
+/// `block` after verifying `block` against `roots`.
+///
+/// Returns `None` if `block` has no execution payload.
+pub fn decode_payload<BH, BB>(
+    block: &CompressedSignedBeaconBlock,
+    roots: &HistoricalRoots,
+) -> eyre::Result<Option<(BH, BB)>>
+where
+    BH: FullBlockHeader + Value,
+    BB: FullBlockBody<OmmerHeader = BH>,
+{
+    let beacon_block = block.decode()?;
+    roots.verify(&beacon_block)?;
+
+    let Some((payload, sidecar)) = beacon_block.execution_payload()? else { return Ok(None) };
+
+    let block_hash = payload.block_hash();
+    let block = payload
+        .try_into_block_with_sidecar::<<BB as BlockBody>::Transaction>(&sidecar)
+        .map_err(|e| eyre!("Invalid execution payload at slot {}: {e}", beacon_block.slot()))?;
+
+    let hash = block.header.hash_slow();
+    if hash != block_hash {
+        bail!(
+            "Block hash mismatch for block {}: got {hash}, expected {block_hash}",
+            block.header.number
+        );
+    }
+
+    // Era1 files store the same encoding, so the node types are decoded from it as well
+    let header = BH::decode(&mut alloy_rlp::encode(&block.header).as_slice())?;
+    let body = BB::decode(&mut alloy_rlp::encode(&block.body).as_slice())?;
+
+    Ok(Some((header, body)))
+}
@@ -13,7 +13,11 @@ fn test_export_with_genesis_only() {
     assert!(provider.block_by_number(1).unwrap().is_none(), "Block 1 should not exist");
 
     let export_dir = tempdir().unwrap();
-    let export_config = ExportConfig { dir: export_dir.path().to_owned(), ..Default::default() };
+    let export_config = ExportConfig {
+        dir: export_dir.path().to_owned(),
+        skip_epoch_verification: true,
+        ..Default::default()
+    };
 
     let exported_files =
         export(&provider_factory.provider_rw().unwrap().0, &export_config).unwrap();
@@ -1,12 +1,22 @@
 use crate::{ClientWithFakeIndex, ITHACA_ERA_INDEX_URL};
+use alloy_consensus::{Block, BlockBody, Header};
+use alloy_primitives::{hex, B256, U256};
 use reqwest::{Client, Url};
 use reth_db_common::init::init_genesis;
-use reth_era::era1::types::execution::MAX_BLOCKS_PER_ERA1;
-use reth_era_downloader::{EraClient, EraStream, EraStreamConfig};
+use reth_era::{
+    common::file_ops::FileReader,
+    era1::{file::Era1Reader, types::execution::MAX_BLOCKS_PER_ERA1},
+};
+use reth_era_downloader::{read_dir, EraClient, EraStream, EraStreamConfig};
 use reth_era_utils::{export, import, ExportConfig};
 use reth_etl::Collector;
 use reth_fs_util as fs;
-use reth_provider::{test_utils::create_test_provider_factory, BlockNumReader, BlockReader};
+use reth_primitives_traits::SealedBlock;
+use reth_provider::{
+    test_utils::create_test_provider_factory, BlockHashReader, BlockNumReader, BlockReader,
+    BlockWriter,
+};
+use sha2::{Digest, Sha256};
 use std::str::FromStr;
 use tempfile::tempdir;
 
@@ -38,7 +48,7 @@ async fn test_history_imports_from_fresh_state_successfully() {
     let mut hash_collector = Collector::new(4096, folder);
 
     let expected_block_number = 8191;
-    let actual_block_number = import(stream, &pf, &mut hash_collector).unwrap();
+    let actual_block_number = import(stream, &[], &pf, &mut hash_collector).unwrap();
 
     assert_eq!(actual_block_number, expected_block_number);
 }
@@ -67,7 +77,7 @@ async fn test_roundtrip_export_after_import() {
     let mut hash_collector = Collector::new(4096, folder);
 
     // Import blocks from one era1 file into database
-    let last_imported_block_height = import(stream, &pf, &mut hash_collector).unwrap();
+    let last_imported_block_height = import(stream, &[], &pf, &mut hash_collector).unwrap();
 
     assert_eq!(last_imported_block_height, 8191);
     let provider_ref = pf.provider_rw().unwrap().0;
@@ -92,6 +102,8 @@ async fn test_roundtrip_export_after_import() {
         last_block_number: EXPORT_LAST_BLOCK,        // 899
         max_blocks_per_file: EXPORT_BLOCKS_PER_FILE, // 250 blocks per file
         network: "mainnet".to_string(),
+        epoch_accumulators: Vec::new(),
+        skip_epoch_verification: true,
     };
 
     // Export blocks from database to era1 files
@@ -156,4 +168,94 @@ async fn test_roundtrip_export_after_import() {
             file_name
         );
     }
+
+    // The manifest lists the checksum and name of every exported file in order
+    let checksums = fs::read_to_string(export_folder.path().join("checksums.txt")).unwrap();
+    let checksums = checksums.lines().collect::<Vec<_>>();
+    assert_eq!(checksums.len(), exported_files.len());
+    for (file_path, line) in exported_files.iter().zip(checksums) {
+        let checksum = hex::encode(Sha256::digest(fs::read(file_path).unwrap()));
+        let file_name = file_path.file_name().unwrap().to_str().unwrap();
+        assert_eq!(line, format!("{checksum}  {file_name}"));
+    }
+
+    // Export the complete first era to verify it against its epoch accumulator
+    let era_folder = tempdir().unwrap();
+    let era_config = ExportConfig {
+        dir: era_folder.path().to_path_buf(),
+        last_block_number: MAX_BLOCKS_PER_ERA1 as u64 - 1,
+        skip_epoch_verification: true,
+        ..Default::default()
+    };
+    let era_files = export(&provider_ref, &era_config).expect("Export should succeed");
+    let accumulator = Era1Reader::open(&era_files[0], "mainnet").unwrap().group.accumulator.root;
+    assert_eq!(hex::encode(&accumulator[..4]), "5ec1ffb8", "Should match the imported file");
+
+    let verified_config = ExportConfig {
+        epoch_accumulators: vec![accumulator],
+        skip_epoch_verification: false,
+        ..era_config
+    };
+    export(&provider_ref, &verified_config).expect("Known epoch accumulator should match");
+
+    let invalid_config =
+        ExportConfig { epoch_accumulators: vec![B256::repeat_byte(0xaa)], ..verified_config };
+    let err = export(&provider_ref, &invalid_config).unwrap_err().to_string();
+    assert!(err.starts_with("Epoch accumulator mismatch for era 0"), "Unexpected error: {err}");
+
+    let unknown_config = ExportConfig { epoch_accumulators: Vec::new(), ..invalid_config };
+    let err = export(&provider_ref, &unknown_config).unwrap_err().to_string();
+    assert_eq!(err, "No known epoch accumulator for era 0");
+}
+
+/// Test that files exported across the merge are imported back in order, although the last
+/// pre-merge and the first post-merge file have the same era number.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_roundtrip_import_after_export_across_merge() {
+    const MERGE_BLOCK: u64 = 100;
+    const LAST_BLOCK: u64 = 199;
+
+    let pf = create_test_provider_factory();
+    let mut parent_hash = init_genesis(&pf).unwrap();
+    let provider_rw = pf.provider_rw().unwrap();
+    for number in 1..=LAST_BLOCK {
+        let difficulty = if number < MERGE_BLOCK { U256::from(1) } else { U256::ZERO };
+        let block = SealedBlock::seal_slow(Block {
+            header: Header { parent_hash, number, difficulty, ..Default::default() },
+            body: BlockBody::default(),
+        });
+        parent_hash = block.hash();
+        provider_rw.insert_block(&block.try_recover().unwrap()).unwrap();
+    }
+    provider_rw.commit().unwrap();
+
+    let export_folder = tempdir().unwrap();
+    let export_config = ExportConfig {
+        dir: export_folder.path().to_path_buf(),
+        last_block_number: LAST_BLOCK,
+        skip_epoch_verification: true,
+        ..Default::default()
+    };
+    let exported_files = export(&pf.provider().unwrap(), &export_config).unwrap();
+
+    let file_names = exported_files
+        .iter()
+        .map(|path| path.file_name().unwrap().to_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(file_names.len(), 2);
+    assert!(file_names.iter().all(|name| name.starts_with("mainnet-00000-")));
+
+    let imported = create_test_provider_factory();
+    init_genesis(&imported).unwrap();
+    let folder = tempdir().unwrap();
+    let mut hash_collector = Collector::new(4096, Some(folder.path().to_owned()));
+    let stream = read_dir(export_folder.path().to_owned(), 0).unwrap();
+
+    let height = import(stream, &[], &imported, &mut hash_collector).unwrap();
+    assert_eq!(height, LAST_BLOCK);
+
+    let (expected, actual) = (pf.provider().unwrap(), imported.provider().unwrap());
+    for number in [0, 1, MERGE_BLOCK - 1, MERGE_BLOCK, LAST_BLOCK] {
+        assert_eq!(actual.block_hash(number).unwrap(), expected.block_hash(number).unwrap());
+    }
 }
@@ -39,6 +39,11 @@ impl<R: Read + Seek> E2StoreReader<R> {
         Entry::read(&mut self.reader)
     }
 
+    /// Seek to `position` in the file, returning the new position from the start
+    pub fn seek(&mut self, position: SeekFrom) -> Result<u64, E2sError> {
+        Ok(self.reader.seek(position)?)
+    }
+
     /// Read all entries from the file, including the version entry
     pub fn entries(&mut self) -> Result<Vec<Entry>, E2sError> {
         // Reset reader to beginning
@@ -22,7 +22,7 @@ use crate::{
 };
 use std::{
     fs::File,
-    io::{Read, Seek, Write},
+    io::{Read, Seek, SeekFrom, Write},
 };
 
 /// Era file interface
@@ -193,6 +193,33 @@ impl<R: Read + Seek> EraReader<R> {
 
         Ok(EraFile::new(group, id))
     }
+
+    /// Reads the era state without reading any blocks, by following the state slot index at the
+    /// end of the file.
+    pub fn read_state(&mut self) -> Result<CompressedBeaconState, E2sError> {
+        // The state slot index covers a single slot: header | starting-slot | offset | count
+        let index_len = 8 + 3 * 8;
+        let index_position = self.reader.seek(SeekFrom::End(-index_len))?;
+
+        let entry = self
+            .reader
+            .read_next_entry()?
+            .ok_or_else(|| E2sError::Ssz("Era file missing state slot index".to_string()))?;
+        let offset = SlotIndex::from_entry(&entry)?
+            .get_offset(0)
+            .ok_or_else(|| E2sError::Ssz("State slot index is empty".to_string()))?;
+        let position = index_position
+            .checked_add_signed(offset)
+            .ok_or_else(|| E2sError::Ssz(format!("Invalid state offset {offset}")))?;
+
+        self.reader.seek(SeekFrom::Start(position))?;
+        let entry = self
+            .reader
+            .read_next_entry()?
+            .ok_or_else(|| E2sError::Ssz("Era file missing state entry".to_string()))?;
+
+        CompressedBeaconState::from_entry(&entry)
+    }
 }
 
 impl FileReader for EraReader<File> {}
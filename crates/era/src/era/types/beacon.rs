@@ -0,0 +1,423 @@
+//! Execution payloads of beacon blocks stored in era files
+//!
+//! Post-merge beacon blocks embed the execution payload that makes up the execution layer block.
+//! [`SignedBeaconBlock`] extracts it from the ssz-encoded block and computes the block root, so
+//! that the block can be checked against the [`HistoricalRoots`] recorded in the era state.
+//!
+//! Only the fields needed for this are decoded, the rest of the block is left as raw SSZ.
+
+use crate::{
+    e2s::error::E2sError,
+    era::types::{
+        group::SLOTS_PER_HISTORICAL_ROOT,
+        schema::{
+            self, read_offset, Ssz, BODY_ALTAIR, BODY_BELLATRIX, BODY_BLOB_KZG_COMMITMENTS,
+            BODY_CAPELLA, BODY_DENEB, BODY_ELECTRA, BODY_EXECUTION_PAYLOAD,
+            BODY_EXECUTION_REQUESTS, BODY_PHASE0, EXECUTION_REQUESTS,
+        },
+    },
+};
+use alloy_eips::{
+    eip4844::kzg_to_versioned_hash, eip6110::DEPOSIT_REQUEST_TYPE,
+    eip7002::WITHDRAWAL_REQUEST_TYPE, eip7251::CONSOLIDATION_REQUEST_TYPE, eip7685::Requests,
+};
+use alloy_primitives::B256;
+use alloy_rpc_types_engine::{
+    CancunPayloadFields, ExecutionPayload, ExecutionPayloadSidecar, ExecutionPayloadV1,
+    ExecutionPayloadV2, ExecutionPayloadV3, PraguePayloadFields,
+};
+use ssz::Decode;
+
+/// Length of the fixed part of `SignedBeaconBlock`: message offset and signature
+const SIGNED_BEACON_BLOCK_FIXED_LEN: usize = 4 + 96;
+
+/// Length of the fixed part of `BeaconBlock`: slot, proposer index, parent root, state root and
+/// body offset
+const BEACON_BLOCK_FIXED_LEN: usize = 8 + 8 + 32 + 32 + 4;
+
+/// Length of a `KZGCommitment`
+const KZG_COMMITMENT_LEN: usize = 48;
+
+/// Beacon chain fork of a block
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BeaconFork {
+    /// Phase 0, genesis of the beacon chain
+    Phase0,
+    /// Altair, adds sync committees
+    Altair,
+    /// Bellatrix, adds the execution payload
+    Bellatrix,
+    /// Capella, adds withdrawals
+    Capella,
+    /// Deneb, adds blobs
+    Deneb,
+    /// Electra, adds execution requests. Fulu blocks share the same layout.
+    Electra,
+}
+
+impl BeaconFork {
+    const ALL: [Self; 6] =
+        [Self::Phase0, Self::Altair, Self::Bellatrix, Self::Capella, Self::Deneb, Self::Electra];
+
+    const fn body(&self) -> &'static [Ssz] {
+        match self {
+            Self::Phase0 => BODY_PHASE0,
+            Self::Altair => BODY_ALTAIR,
+            Self::Bellatrix => BODY_BELLATRIX,
+            Self::Capella => BODY_CAPELLA,
+            Self::Deneb => BODY_DENEB,
+            Self::Electra => BODY_ELECTRA,
+        }
+    }
+
+    const fn block(&self) -> &'static Ssz {
+        match self {
+            Self::Phase0 => &schema::BEACON_BLOCK_PHASE0,
+            Self::Altair => &schema::BEACON_BLOCK_ALTAIR,
+            Self::Bellatrix => &schema::BEACON_BLOCK_BELLATRIX,
+            Self::Capella => &schema::BEACON_BLOCK_CAPELLA,
+            Self::Deneb => &schema::BEACON_BLOCK_DENEB,
+            Self::Electra => &schema::BEACON_BLOCK_ELECTRA,
+        }
+    }
+
+    /// Detects the fork from the ssz-encoded `BeaconBlockBody`.
+    ///
+    /// Every fork appends fields to the body, so the first offset, which points right past the
+    /// fixed part, is unique to each fork.
+    fn from_body(body: &[u8]) -> Result<Self, E2sError> {
+        // The proposer slashings offset follows the randao reveal, eth1 data and graffiti
+        let first_offset = read_offset(body, Ssz::fixed_part_len(&BODY_PHASE0[..3]))?;
+
+        Self::ALL
+            .into_iter()
+            .find(|fork| Ssz::fixed_part_len(fork.body()) == first_offset)
+            .ok_or_else(|| {
+                E2sError::Ssz(format!(
+                    "Unknown beacon block body layout, first offset {first_offset}"
+                ))
+            })
+    }
+}
+
+/// Ssz-encoded `SignedBeaconBlock`
+#[derive(Debug, Clone)]
+pub struct SignedBeaconBlock {
+    ssz: Vec<u8>,
+    fork: BeaconFork,
+}
+
+impl SignedBeaconBlock {
+    /// Create from ssz-encoded `SignedBeaconBlock` of any fork
+    pub fn from_ssz(ssz: Vec<u8>) -> Result<Self, E2sError> {
+        let message_offset = read_offset(&ssz, 0)?;
+        if message_offset != SIGNED_BEACON_BLOCK_FIXED_LEN || ssz.len() < message_offset {
+            return Err(E2sError::Ssz(format!(
+                "Invalid beacon block message offset {message_offset}"
+            )));
+        }
+
+        let message = &ssz[message_offset..];
+        let body_offset = read_offset(message, BEACON_BLOCK_FIXED_LEN - 4)?;
+        if body_offset != BEACON_BLOCK_FIXED_LEN || message.len() < body_offset {
+            return Err(E2sError::Ssz(format!("Invalid beacon block body offset {body_offset}")));
+        }
+
+        let fork = BeaconFork::from_body(&message[body_offset..])?;
+
+        Ok(Self { ssz, fork })
+    }
+
+    /// Returns the fork of this block
+    pub const fn fork(&self) -> BeaconFork {
+        self.fork
+    }
+
+    /// Returns the slot of this block
+    pub fn slot(&self) -> u64 {
+        u64::from_le_bytes(self.message()[..8].try_into().expect("slot is 8 bytes"))
+    }
+
+    /// Returns the root of the parent beacon block
+    pub fn parent_root(&self) -> B256 {
+        B256::from_slice(&self.message()[16..48])
+    }
+
+    /// Computes the block root, i.e. the `hash_tree_root` of the `BeaconBlock`
+    pub fn block_root(&self) -> Result<B256, E2sError> {
+        self.fork.block().hash_tree_root(self.message())
+    }
+
+    /// Extracts the execution payload and the fields of the execution block that are only part of
+    /// the beacon block.
+    ///
+    /// Returns `None` for blocks before the merge, which carry no or an empty payload.
+    pub fn execution_payload(
+        &self,
+    ) -> Result<Option<(ExecutionPayload, ExecutionPayloadSidecar)>, E2sError> {
+        if self.fork < BeaconFork::Bellatrix {
+            return Ok(None);
+        }
+
+        let fields = Ssz::fields(self.fork.body(), self.body())?;
+        let payload = fields[BODY_EXECUTION_PAYLOAD];
+        let payload: ExecutionPayload = match self.fork {
+            BeaconFork::Bellatrix => ExecutionPayloadV1::from_ssz_bytes(payload).map(Into::into),
+            BeaconFork::Capella => ExecutionPayloadV2::from_ssz_bytes(payload).map(Into::into),
+            _ => ExecutionPayloadV3::from_ssz_bytes(payload).map(Into::into),
+        }
+        .map_err(|e| E2sError::Ssz(format!("Failed to decode execution payload: {e:?}")))?;
+
+        // Bellatrix blocks before the merge transition carry a default payload
+        if payload.block_hash().is_zero() {
+            return Ok(None);
+        }
+
+        let sidecar = if self.fork >= BeaconFork::Deneb {
+            let versioned_hashes = fields[BODY_BLOB_KZG_COMMITMENTS]
+                .chunks_exact(KZG_COMMITMENT_LEN)
+                .map(kzg_to_versioned_hash)
+                .collect();
+            let cancun = CancunPayloadFields::new(self.parent_root(), versioned_hashes);
+
+            if self.fork >= BeaconFork::Electra {
+                let mut requests = Requests::default();
+                let request_types =
+                    [DEPOSIT_REQUEST_TYPE, WITHDRAWAL_REQUEST_TYPE, CONSOLIDATION_REQUEST_TYPE];
+
+                // Requests are fixed size, so each list is already the concatenation of its
+                // ssz-encoded requests
+                for (request_type, data) in request_types
+                    .into_iter()
+                    .zip(Ssz::fields(EXECUTION_REQUESTS, fields[BODY_EXECUTION_REQUESTS])?)
+                {
+                    requests.push_request_with_type(request_type, data.iter().copied());
+                }
+
+                ExecutionPayloadSidecar::v4(cancun, PraguePayloadFields::new(requests))
+            } else {
+                ExecutionPayloadSidecar::v3(cancun)
+            }
+        } else {
+            ExecutionPayloadSidecar::none()
+        };
+
+        Ok(Some((payload, sidecar)))
+    }
+
+    fn message(&self) -> &[u8] {
+        &self.ssz[SIGNED_BEACON_BLOCK_FIXED_LEN..]
+    }
+
+    fn body(&self) -> &[u8] {
+        &self.message()[BEACON_BLOCK_FIXED_LEN..]
+    }
+}
+
+/// Block and state roots of the slots of an era, as recorded in the era state
+///
+/// The state at the era transition slot holds the roots of the [`SLOTS_PER_HISTORICAL_ROOT`]
+/// preceding slots. Their combined root is what the beacon chain appends to `historical_roots`,
+/// or `historical_summaries` since Capella, and what era file names are derived from.
+#[derive(Debug, Clone)]
+pub struct HistoricalRoots {
+    slot: u64,
+    block_roots: Vec<B256>,
+    state_roots: Vec<B256>,
+}
+
+impl HistoricalRoots {
+    /// Length of the leading ssz-encoded `BeaconState` fields up to and including the state roots:
+    /// genesis time, genesis validators root, slot, fork, latest block header, block roots and
+    /// state roots. The layout of these fields is the same for all forks.
+    pub const STATE_PREFIX_LEN: usize = 8 + 32 + 8 + 16 + 112 + 2 * 32 * 8192;
+
+    /// Offset of the slot in the ssz-encoded `BeaconState`
+    const SLOT_OFFSET: usize = 8 + 32;
+
+    /// Offset of the block roots in the ssz-encoded `BeaconState`
+    const BLOCK_ROOTS_OFFSET: usize = 8 + 32 + 8 + 16 + 112;
+
+    /// Create from the first [`Self::STATE_PREFIX_LEN`] bytes of an ssz-encoded `BeaconState`
+    pub fn from_state_ssz(ssz: &[u8]) -> Result<Self, E2sError> {
+        if ssz.len() < Self::STATE_PREFIX_LEN {
+            return Err(E2sError::Ssz(format!(
+                "Beacon state of {} bytes is too short to contain historical roots",
+                ssz.len()
+            )));
+        }
+
+        let slot = u64::from_le_bytes(
+            ssz[Self::SLOT_OFFSET..Self::SLOT_OFFSET + 8].try_into().expect("slot is 8 bytes"),
+        );
+        let roots = ssz[Self::BLOCK_ROOTS_OFFSET..Self::STATE_PREFIX_LEN]
+            .chunks_exact(32)
+            .map(B256::from_slice)
+            .collect::<Vec<_>>();
+        let (block_roots, state_roots) = roots.split_at(SLOTS_PER_HISTORICAL_ROOT as usize);
+
+        Ok(Self { slot, block_roots: block_roots.to_vec(), state_roots: state_roots.to_vec() })
+    }
+
+    /// Returns the slot of the state the roots were read from
+    pub const fn slot(&self) -> u64 {
+        self.slot
+    }
+
+    /// Returns the root of the block at `slot`, or `None` if `slot` is not part of this era.
+    ///
+    /// For empty slots, this is the root of the latest block before `slot`.
+    pub fn block_root(&self, slot: u64) -> Option<B256> {
+        if slot >= self.slot || slot + SLOTS_PER_HISTORICAL_ROOT < self.slot {
+            return None;
+        }
+
+        self.block_roots.get((slot % SLOTS_PER_HISTORICAL_ROOT) as usize).copied()
+    }
+
+    /// Computes the historical root of this era, i.e. the `hash_tree_root` of the
+    /// `HistoricalBatch`, which equals the one of the `HistoricalSummary` since Capella.
+    pub fn historical_root(&self) -> Result<B256, E2sError> {
+        let limit = SLOTS_PER_HISTORICAL_ROOT as usize;
+        let block_summary_root = schema::merkleize(&self.block_roots, limit)?;
+        let state_summary_root = schema::merkleize(&self.state_roots, limit)?;
+
+        Ok(schema::hash_pair(&block_summary_root, &state_summary_root))
+    }
+
+    /// Checks that the root of `block` matches the block root recorded for its slot.
+    pub fn verify(&self, block: &SignedBeaconBlock) -> Result<(), E2sError> {
+        let slot = block.slot();
+        let expected = self.block_root(slot).ok_or_else(|| {
+            E2sError::Ssz(format!(
+                "Block at slot {slot} is outside of the era ending at slot {}",
+                self.slot
+            ))
+        })?;
+        let actual = block.block_root()?;
+
+        if actual != expected {
+            return Err(E2sError::Ssz(format!(
+                "Block root mismatch at slot {slot}: got {actual}, expected {expected}"
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Address, Bloom, Bytes, U256};
+    use ssz::Encode;
+
+    /// Builds an ssz-encoded Capella `SignedBeaconBlock` around `payload`, leaving all other
+    /// fields empty.
+    fn capella_block(slot: u64, parent_root: B256, payload: &ExecutionPayloadV2) -> Vec<u8> {
+        let payload = payload.as_ssz_bytes();
+        let fixed_part_len = Ssz::fixed_part_len(BODY_CAPELLA);
+
+        let mut body = Vec::new();
+        body.extend_from_slice(&[0; 96 + 72 + 32]);
+        // Five empty operation lists, then the payload and the empty BLS changes list
+        body.extend((0..5).flat_map(|_| (fixed_part_len as u32).to_le_bytes()));
+        body.extend_from_slice(&[0; 64 + 96]);
+        body.extend_from_slice(&(fixed_part_len as u32).to_le_bytes());
+        body.extend_from_slice(&((fixed_part_len + payload.len()) as u32).to_le_bytes());
+        body.extend_from_slice(&payload);
+
+        let mut message = Vec::new();
+        message.extend_from_slice(&slot.to_le_bytes());
+        message.extend_from_slice(&7u64.to_le_bytes());
+        message.extend_from_slice(parent_root.as_slice());
+        message.extend_from_slice(&[0x11; 32]);
+        message.extend_from_slice(&(BEACON_BLOCK_FIXED_LEN as u32).to_le_bytes());
+        message.extend_from_slice(&body);
+
+        let mut block = Vec::new();
+        block.extend_from_slice(&(SIGNED_BEACON_BLOCK_FIXED_LEN as u32).to_le_bytes());
+        block.extend_from_slice(&[0; 96]);
+        block.extend_from_slice(&message);
+        block
+    }
+
+    fn payload(block_hash: B256) -> ExecutionPayloadV2 {
+        ExecutionPayloadV2 {
+            payload_inner: ExecutionPayloadV1 {
+                parent_hash: B256::repeat_byte(1),
+                fee_recipient: Address::repeat_byte(2),
+                state_root: B256::repeat_byte(3),
+                receipts_root: B256::repeat_byte(4),
+                logs_bloom: Bloom::ZERO,
+                prev_randao: B256::repeat_byte(5),
+                block_number: 17_034_870,
+                gas_limit: 30_000_000,
+                gas_used: 21_000,
+                timestamp: 1_681_338_455,
+                extra_data: Bytes::from_static(b"reth"),
+                base_fee_per_gas: U256::from(7),
+                block_hash,
+                transactions: vec![Bytes::from_static(&[0x02, 0xc0])],
+            },
+            withdrawals: vec![],
+        }
+    }
+
+    #[test]
+    fn test_extracts_capella_payload() {
+        let parent_root = B256::repeat_byte(9);
+        let expected = payload(B256::repeat_byte(6));
+        let block = SignedBeaconBlock::from_ssz(capella_block(42, parent_root, &expected)).unwrap();
+
+        assert_eq!(block.fork(), BeaconFork::Capella);
+        assert_eq!(block.slot(), 42);
+        assert_eq!(block.parent_root(), parent_root);
+
+        let (payload, sidecar) = block.execution_payload().unwrap().unwrap();
+        assert_eq!(payload, ExecutionPayload::V2(expected));
+        assert!(sidecar.cancun().is_none());
+        assert!(sidecar.prague().is_none());
+    }
+
+    #[test]
+    fn test_skips_empty_payload() {
+        let block = capella_block(42, B256::ZERO, &payload(B256::ZERO));
+        let block = SignedBeaconBlock::from_ssz(block).unwrap();
+
+        assert!(block.execution_payload().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_verifies_block_root() {
+        let block = capella_block(8192 + 5, B256::ZERO, &payload(B256::repeat_byte(6)));
+        let block = SignedBeaconBlock::from_ssz(block).unwrap();
+        let root = block.block_root().unwrap();
+
+        let mut state = vec![0; HistoricalRoots::STATE_PREFIX_LEN];
+        state[HistoricalRoots::SLOT_OFFSET..HistoricalRoots::SLOT_OFFSET + 8]
+            .copy_from_slice(&(2 * 8192u64).to_le_bytes());
+        let position = HistoricalRoots::BLOCK_ROOTS_OFFSET + 5 * 32;
+        state[position..position + 32].copy_from_slice(root.as_slice());
+
+        let roots = HistoricalRoots::from_state_ssz(&state).unwrap();
+        assert_eq!(roots.slot(), 2 * 8192);
+        assert_eq!(roots.block_root(8192 + 5), Some(root));
+        assert_eq!(roots.block_root(2 * 8192), None);
+        assert!(roots.verify(&block).is_ok());
+
+        state[position] ^= 1;
+        let roots = HistoricalRoots::from_state_ssz(&state).unwrap();
+        assert!(roots.verify(&block).is_err());
+    }
+
+    #[test]
+    fn test_rejects_unknown_body_layout() {
+        let mut block = capella_block(42, B256::ZERO, &payload(B256::ZERO));
+        // Shift the first body offset to a length no fork uses
+        let position = SIGNED_BEACON_BLOCK_FIXED_LEN + BEACON_BLOCK_FIXED_LEN + 96 + 72 + 32;
+        block[position] += 1;
+
+        assert!(SignedBeaconBlock::from_ssz(block).is_err());
+    }
+}
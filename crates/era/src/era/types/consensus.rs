@@ -2,7 +2,8 @@
 //!
 //! # Decoding
 //!
-//! This crate only handles compression/decompression.
+//! This crate only handles compression/decompression, plus the minimal decoding needed to
+//! extract execution payloads, see [`SignedBeaconBlock`] and [`HistoricalRoots`].
 //! To decode the SSZ data into concrete beacon types, use the [Lighthouse `types`](https://github.com/sigp/lighthouse/tree/stable/consensus/types)
 //! crate or another SSZ-compatible library.
 //!
@@ -59,7 +60,10 @@
 //!     Ok(())
 //! }
 //! ```
-use crate::e2s::{error::E2sError, types::Entry};
+use crate::{
+    e2s::{error::E2sError, types::Entry},
+    era::types::beacon::{HistoricalRoots, SignedBeaconBlock},
+};
 use snap::{read::FrameDecoder, write::FrameEncoder};
 use std::io::{Read, Write};
 
@@ -136,6 +140,11 @@ impl CompressedSignedBeaconBlock {
         )
     }
 
+    /// Decompress and decode into a [`SignedBeaconBlock`]
+    pub fn decode(&self) -> Result<SignedBeaconBlock, E2sError> {
+        SignedBeaconBlock::from_ssz(self.decompress()?)
+    }
+
     /// Convert to an [`Entry`]
     pub fn to_entry(&self) -> Entry {
         Entry::new(COMPRESSED_SIGNED_BEACON_BLOCK, self.data.clone())
@@ -198,6 +207,17 @@ impl CompressedBeaconState {
         )
     }
 
+    /// Decompress only the leading fields of the state to read its [`HistoricalRoots`]
+    pub fn historical_roots(&self) -> Result<HistoricalRoots, E2sError> {
+        let mut prefix = vec![0; HistoricalRoots::STATE_PREFIX_LEN];
+
+        FrameDecoder::new(self.data.as_slice()).read_exact(&mut prefix).map_err(|e| {
+            E2sError::SnappyDecompression(format!("Failed to decompress beacon state: {e}"))
+        })?;
+
+        HistoricalRoots::from_state_ssz(&prefix)
+    }
+
     /// Convert to an [`Entry`]
     pub fn to_entry(&self) -> Entry {
         Entry::new(COMPRESSED_BEACON_STATE, self.data.clone())
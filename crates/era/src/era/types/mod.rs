@@ -2,5 +2,7 @@
 //!
 //! See also <https://github.com/eth-clients/e2store-format-specs/blob/main/formats/era.md>
 
+pub mod beacon;
 pub mod consensus;
 pub mod group;
+mod schema;
@@ -0,0 +1,624 @@
+//! Minimal SSZ schemas of the beacon types stored in era files
+//!
+//! The schemas are only used to slice containers into their fields and to compute
+//! `hash_tree_root` over the raw SSZ bytes, which is all that's needed to check a beacon block
+//! against the block roots committed to by the era state.
+//!
+//! All limits follow the mainnet preset.
+//!
+//! See also <https://github.com/ethereum/consensus-specs/blob/dev/ssz/simple-serialize.md>
+
+use crate::e2s::error::E2sError;
+use alloy_primitives::B256;
+use sha2::{Digest, Sha256};
+use std::sync::LazyLock;
+
+/// Number of bytes in an SSZ offset
+const BYTES_PER_OFFSET: usize = 4;
+
+/// Number of bytes in a merkle chunk
+const BYTES_PER_CHUNK: usize = 32;
+
+/// Maximum depth of a merkle tree supported by [`merkleize`]
+const MAX_TREE_DEPTH: usize = 64;
+
+/// Roots of empty subtrees indexed by depth
+static ZERO_HASHES: LazyLock<[B256; MAX_TREE_DEPTH + 1]> = LazyLock::new(|| {
+    let mut hashes = [B256::ZERO; MAX_TREE_DEPTH + 1];
+    for depth in 1..=MAX_TREE_DEPTH {
+        hashes[depth] = hash_pair(&hashes[depth - 1], &hashes[depth - 1]);
+    }
+    hashes
+});
+
+/// SSZ type description
+#[derive(Debug)]
+pub(crate) enum Ssz {
+    /// Little-endian unsigned integer of the given byte length
+    Uint(usize),
+    /// Fixed length byte vector
+    Bytes(usize),
+    /// Byte list with the given maximum length
+    ByteList(usize),
+    /// Bit vector of the given length
+    Bitvector(usize),
+    /// Bit list with the given maximum length
+    Bitlist(usize),
+    /// Vector of elements with the given length
+    Vector(&'static Self, usize),
+    /// List of elements with the given maximum length
+    List(&'static Self, usize),
+    /// Container of the given fields
+    Container(&'static [Self]),
+}
+
+impl Ssz {
+    /// Returns the serialized length, or `None` if the type has a variable length.
+    pub(crate) fn fixed_len(&self) -> Option<usize> {
+        match self {
+            Self::Uint(len) | Self::Bytes(len) => Some(*len),
+            Self::Bitvector(bits) => Some(bits.div_ceil(8)),
+            Self::Vector(element, len) => element.fixed_len().map(|size| size * len),
+            Self::Container(fields) => fields.iter().map(Self::fixed_len).sum(),
+            Self::ByteList(_) | Self::Bitlist(_) | Self::List(..) => None,
+        }
+    }
+
+    /// Returns the length of the fixed part of a container, where variable length fields only
+    /// take up an offset.
+    pub(crate) fn fixed_part_len(fields: &[Self]) -> usize {
+        fields.iter().map(|field| field.fixed_len().unwrap_or(BYTES_PER_OFFSET)).sum()
+    }
+
+    /// Splits the serialized container `bytes` into the serialized `fields`.
+    pub(crate) fn fields<'a>(fields: &[Self], bytes: &'a [u8]) -> Result<Vec<&'a [u8]>, E2sError> {
+        let fixed_part_len = Self::fixed_part_len(fields);
+        if bytes.len() < fixed_part_len {
+            return Err(E2sError::Ssz(format!(
+                "Container of {} bytes is shorter than its fixed part of {fixed_part_len} bytes",
+                bytes.len()
+            )));
+        }
+
+        // Fixed fields are sliced directly, variable fields are resolved once all offsets are known
+        let mut slices = Vec::with_capacity(fields.len());
+        let mut offsets = Vec::new();
+        let mut position = 0;
+
+        for field in fields {
+            match field.fixed_len() {
+                Some(len) => {
+                    slices.push(Some(&bytes[position..position + len]));
+                    position += len;
+                }
+                None => {
+                    offsets.push((slices.len(), read_offset(bytes, position)?));
+                    slices.push(None);
+                    position += BYTES_PER_OFFSET;
+                }
+            }
+        }
+
+        if let Some((_, first)) = offsets.first() &&
+            *first != fixed_part_len
+        {
+            return Err(E2sError::Ssz(format!(
+                "First offset {first} does not match the fixed part length {fixed_part_len}"
+            )));
+        }
+
+        for (i, (index, start)) in offsets.iter().enumerate() {
+            let end = offsets.get(i + 1).map_or(bytes.len(), |(_, next)| *next);
+            if *start > end || end > bytes.len() {
+                return Err(E2sError::Ssz(format!("Invalid offsets {start}..{end}")));
+            }
+            slices[*index] = Some(&bytes[*start..end]);
+        }
+
+        if offsets.is_empty() && bytes.len() != fixed_part_len {
+            return Err(E2sError::Ssz(format!(
+                "Expected {fixed_part_len} bytes for fixed length container, got {}",
+                bytes.len()
+            )));
+        }
+
+        Ok(slices.into_iter().flatten().collect())
+    }
+
+    /// Splits the serialized sequence `bytes` into serialized elements of type `element`.
+    fn elements<'a>(element: &Self, bytes: &'a [u8]) -> Result<Vec<&'a [u8]>, E2sError> {
+        if let Some(len) = element.fixed_len() {
+            if len == 0 || !bytes.len().is_multiple_of(len) {
+                return Err(E2sError::Ssz(format!(
+                    "Sequence of {} bytes is not a multiple of the element size {len}",
+                    bytes.len()
+                )));
+            }
+            return Ok(bytes.chunks_exact(len).collect());
+        }
+
+        if bytes.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let first = read_offset(bytes, 0)?;
+        if first == 0 || !first.is_multiple_of(BYTES_PER_OFFSET) || first > bytes.len() {
+            return Err(E2sError::Ssz(format!("Invalid first element offset {first}")));
+        }
+
+        let count = first / BYTES_PER_OFFSET;
+        let mut elements = Vec::with_capacity(count);
+        for i in 0..count {
+            let start = read_offset(bytes, i * BYTES_PER_OFFSET)?;
+            let end = if i + 1 < count {
+                read_offset(bytes, (i + 1) * BYTES_PER_OFFSET)?
+            } else {
+                bytes.len()
+            };
+            if start > end || end > bytes.len() {
+                return Err(E2sError::Ssz(format!("Invalid element offsets {start}..{end}")));
+            }
+            elements.push(&bytes[start..end]);
+        }
+
+        Ok(elements)
+    }
+
+    /// Computes the `hash_tree_root` of the serialized value `bytes` of this type.
+    pub(crate) fn hash_tree_root(&self, bytes: &[u8]) -> Result<B256, E2sError> {
+        if let Some(len) = self.fixed_len() &&
+            bytes.len() != len
+        {
+            return Err(E2sError::Ssz(format!(
+                "Expected {len} bytes for {self:?}, got {}",
+                bytes.len()
+            )));
+        }
+
+        match self {
+            Self::Uint(_) => merkleize(&pack(bytes), 1),
+            Self::Bytes(len) => merkleize(&pack(bytes), len.div_ceil(BYTES_PER_CHUNK)),
+            Self::ByteList(max) => {
+                check_limit(bytes.len(), *max)?;
+                let root = merkleize(&pack(bytes), max.div_ceil(BYTES_PER_CHUNK))?;
+                Ok(mix_in_length(&root, bytes.len()))
+            }
+            Self::Bitvector(bits) => merkleize(&pack(bytes), bits.div_ceil(256)),
+            Self::Bitlist(max) => {
+                let (bits, len) = strip_delimiter(bytes)?;
+                check_limit(len, *max)?;
+                let root = merkleize(&pack(&bits), max.div_ceil(256))?;
+                Ok(mix_in_length(&root, len))
+            }
+            Self::Vector(element, len) => match element {
+                Self::Uint(size) => merkleize(&pack(bytes), (size * len).div_ceil(BYTES_PER_CHUNK)),
+                _ => merkleize(&Self::element_roots(element, bytes)?, *len),
+            },
+            Self::List(element, max) => {
+                let (root, len) = match element {
+                    Self::Uint(size) => {
+                        if !bytes.len().is_multiple_of(*size) {
+                            return Err(E2sError::Ssz(format!(
+                                "List of {} bytes is not a multiple of the element size {size}",
+                                bytes.len()
+                            )));
+                        }
+                        let len = bytes.len() / size;
+                        check_limit(len, *max)?;
+                        (merkleize(&pack(bytes), (size * max).div_ceil(BYTES_PER_CHUNK))?, len)
+                    }
+                    _ => {
+                        let roots = Self::element_roots(element, bytes)?;
+                        check_limit(roots.len(), *max)?;
+                        (merkleize(&roots, *max)?, roots.len())
+                    }
+                };
+                Ok(mix_in_length(&root, len))
+            }
+            Self::Container(fields) => {
+                let roots = Self::fields(fields, bytes)?
+                    .into_iter()
+                    .zip(fields.iter())
+                    .map(|(bytes, field)| field.hash_tree_root(bytes))
+                    .collect::<Result<Vec<_>, _>>()?;
+                merkleize(&roots, fields.len())
+            }
+        }
+    }
+
+    fn element_roots(element: &Self, bytes: &[u8]) -> Result<Vec<B256>, E2sError> {
+        Self::elements(element, bytes)?
+            .into_iter()
+            .map(|bytes| element.hash_tree_root(bytes))
+            .collect()
+    }
+}
+
+/// Reads a little-endian `u32` offset at `position` of `bytes`.
+pub(crate) fn read_offset(bytes: &[u8], position: usize) -> Result<usize, E2sError> {
+    bytes
+        .get(position..position + BYTES_PER_OFFSET)
+        .map(|offset| u32::from_le_bytes(offset.try_into().expect("slice of 4 bytes")) as usize)
+        .ok_or_else(|| E2sError::Ssz(format!("Missing offset at position {position}")))
+}
+
+/// Merkleizes `chunks` as the leaves of a tree that can hold `limit` leaves.
+pub(crate) fn merkleize(chunks: &[B256], limit: usize) -> Result<B256, E2sError> {
+    check_limit(chunks.len(), limit)?;
+
+    let depth = limit.next_power_of_two().trailing_zeros() as usize;
+    let mut layer = chunks.to_vec();
+
+    for zero_hash in &ZERO_HASHES[..depth] {
+        if layer.is_empty() {
+            break;
+        }
+        if !layer.len().is_multiple_of(2) {
+            layer.push(*zero_hash);
+        }
+        layer = layer.chunks_exact(2).map(|pair| hash_pair(&pair[0], &pair[1])).collect();
+    }
+
+    Ok(layer.first().copied().unwrap_or(ZERO_HASHES[depth]))
+}
+
+/// Hashes the concatenation of `left` and `right`.
+pub(crate) fn hash_pair(left: &B256, right: &B256) -> B256 {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    B256::from_slice(&hasher.finalize())
+}
+
+fn mix_in_length(root: &B256, len: usize) -> B256 {
+    let mut length = B256::ZERO;
+    length[..8].copy_from_slice(&(len as u64).to_le_bytes());
+    hash_pair(root, &length)
+}
+
+/// Splits `bytes` into zero padded chunks.
+fn pack(bytes: &[u8]) -> Vec<B256> {
+    bytes
+        .chunks(BYTES_PER_CHUNK)
+        .map(|chunk| {
+            let mut padded = B256::ZERO;
+            padded[..chunk.len()].copy_from_slice(chunk);
+            padded
+        })
+        .collect()
+}
+
+/// Removes the delimiting bit of a serialized bit list, returning its bits and length.
+fn strip_delimiter(bytes: &[u8]) -> Result<(Vec<u8>, usize), E2sError> {
+    let Some(&last) = bytes.last().filter(|last| **last != 0) else {
+        return Err(E2sError::Ssz("Bit list is missing its delimiting bit".to_string()));
+    };
+
+    let delimiter = 7 - last.leading_zeros() as usize;
+    let mut bits = bytes.to_vec();
+    let len = (bits.len() - 1) * 8 + delimiter;
+
+    bits.pop();
+    if delimiter > 0 {
+        bits.push(last & !(1 << delimiter));
+    }
+
+    Ok((bits, len))
+}
+
+fn check_limit(len: usize, limit: usize) -> Result<(), E2sError> {
+    if len > limit.max(1) {
+        return Err(E2sError::Ssz(format!("Length {len} exceeds the limit of {limit}")));
+    }
+    Ok(())
+}
+
+/// `uint64`
+const UINT64: Ssz = Ssz::Uint(8);
+
+/// `uint256`
+const UINT256: Ssz = Ssz::Uint(32);
+
+/// `Root`, `Hash32` and `Bytes32`
+const ROOT: Ssz = Ssz::Bytes(32);
+
+/// `BLSSignature`
+const SIGNATURE: Ssz = Ssz::Bytes(96);
+
+/// `BLSPubkey` and `KZGCommitment`
+const PUBKEY: Ssz = Ssz::Bytes(48);
+
+/// `ExecutionAddress`
+const ADDRESS: Ssz = Ssz::Bytes(20);
+
+const CHECKPOINT: Ssz = Ssz::Container(&[UINT64, ROOT]);
+
+const ATTESTATION_DATA: Ssz = Ssz::Container(&[UINT64, UINT64, ROOT, CHECKPOINT, CHECKPOINT]);
+
+const ETH1_DATA: Ssz = Ssz::Container(&[ROOT, UINT64, ROOT]);
+
+const BEACON_BLOCK_HEADER: Ssz = Ssz::Container(&[UINT64, UINT64, ROOT, ROOT, ROOT]);
+
+const SIGNED_BEACON_BLOCK_HEADER: Ssz = Ssz::Container(&[BEACON_BLOCK_HEADER, SIGNATURE]);
+
+const PROPOSER_SLASHING: Ssz =
+    Ssz::Container(&[SIGNED_BEACON_BLOCK_HEADER, SIGNED_BEACON_BLOCK_HEADER]);
+
+const INDEXED_ATTESTATION: Ssz =
+    Ssz::Container(&[Ssz::List(&UINT64, 2048), ATTESTATION_DATA, SIGNATURE]);
+
+const INDEXED_ATTESTATION_ELECTRA: Ssz =
+    Ssz::Container(&[Ssz::List(&UINT64, 2048 * 64), ATTESTATION_DATA, SIGNATURE]);
+
+const ATTESTER_SLASHING: Ssz = Ssz::Container(&[INDEXED_ATTESTATION, INDEXED_ATTESTATION]);
+
+const ATTESTER_SLASHING_ELECTRA: Ssz =
+    Ssz::Container(&[INDEXED_ATTESTATION_ELECTRA, INDEXED_ATTESTATION_ELECTRA]);
+
+const ATTESTATION: Ssz = Ssz::Container(&[Ssz::Bitlist(2048), ATTESTATION_DATA, SIGNATURE]);
+
+const ATTESTATION_ELECTRA: Ssz =
+    Ssz::Container(&[Ssz::Bitlist(2048 * 64), ATTESTATION_DATA, SIGNATURE, Ssz::Bitvector(64)]);
+
+const DEPOSIT_DATA: Ssz = Ssz::Container(&[PUBKEY, ROOT, UINT64, SIGNATURE]);
+
+const DEPOSIT: Ssz = Ssz::Container(&[Ssz::Vector(&ROOT, 33), DEPOSIT_DATA]);
+
+const SIGNED_VOLUNTARY_EXIT: Ssz = Ssz::Container(&[Ssz::Container(&[UINT64, UINT64]), SIGNATURE]);
+
+const SYNC_AGGREGATE: Ssz = Ssz::Container(&[Ssz::Bitvector(512), SIGNATURE]);
+
+const SIGNED_BLS_TO_EXECUTION_CHANGE: Ssz =
+    Ssz::Container(&[Ssz::Container(&[UINT64, PUBKEY, ADDRESS]), SIGNATURE]);
+
+const WITHDRAWAL: Ssz = Ssz::Container(&[UINT64, UINT64, ADDRESS, UINT64]);
+
+const TRANSACTIONS: Ssz = Ssz::List(&Ssz::ByteList(1 << 30), 1 << 20);
+
+const EXECUTION_PAYLOAD_BELLATRIX: Ssz = Ssz::Container(&[
+    ROOT,
+    ADDRESS,
+    ROOT,
+    ROOT,
+    Ssz::Bytes(256),
+    ROOT,
+    UINT64,
+    UINT64,
+    UINT64,
+    UINT64,
+    Ssz::ByteList(32),
+    UINT256,
+    ROOT,
+    TRANSACTIONS,
+]);
+
+const EXECUTION_PAYLOAD_CAPELLA: Ssz = Ssz::Container(&[
+    ROOT,
+    ADDRESS,
+    ROOT,
+    ROOT,
+    Ssz::Bytes(256),
+    ROOT,
+    UINT64,
+    UINT64,
+    UINT64,
+    UINT64,
+    Ssz::ByteList(32),
+    UINT256,
+    ROOT,
+    TRANSACTIONS,
+    Ssz::List(&WITHDRAWAL, 16),
+]);
+
+const EXECUTION_PAYLOAD_DENEB: Ssz = Ssz::Container(&[
+    ROOT,
+    ADDRESS,
+    ROOT,
+    ROOT,
+    Ssz::Bytes(256),
+    ROOT,
+    UINT64,
+    UINT64,
+    UINT64,
+    UINT64,
+    Ssz::ByteList(32),
+    UINT256,
+    ROOT,
+    TRANSACTIONS,
+    Ssz::List(&WITHDRAWAL, 16),
+    UINT64,
+    UINT64,
+]);
+
+/// `ExecutionRequests` fields, holding deposit, withdrawal and consolidation requests
+pub(crate) const EXECUTION_REQUESTS: &[Ssz] = &[
+    Ssz::List(&Ssz::Container(&[PUBKEY, ROOT, UINT64, SIGNATURE, UINT64]), 8192),
+    Ssz::List(&Ssz::Container(&[ADDRESS, PUBKEY, UINT64]), 16),
+    Ssz::List(&Ssz::Container(&[ADDRESS, PUBKEY, PUBKEY]), 2),
+];
+
+/// `BeaconBlockBody` fields of phase 0
+pub(crate) const BODY_PHASE0: &[Ssz] = &[
+    SIGNATURE,
+    ETH1_DATA,
+    ROOT,
+    Ssz::List(&PROPOSER_SLASHING, 16),
+    Ssz::List(&ATTESTER_SLASHING, 2),
+    Ssz::List(&ATTESTATION, 128),
+    Ssz::List(&DEPOSIT, 16),
+    Ssz::List(&SIGNED_VOLUNTARY_EXIT, 16),
+];
+
+/// `BeaconBlockBody` fields of Altair
+pub(crate) const BODY_ALTAIR: &[Ssz] = &[
+    SIGNATURE,
+    ETH1_DATA,
+    ROOT,
+    Ssz::List(&PROPOSER_SLASHING, 16),
+    Ssz::List(&ATTESTER_SLASHING, 2),
+    Ssz::List(&ATTESTATION, 128),
+    Ssz::List(&DEPOSIT, 16),
+    Ssz::List(&SIGNED_VOLUNTARY_EXIT, 16),
+    SYNC_AGGREGATE,
+];
+
+/// `BeaconBlockBody` fields of Bellatrix
+pub(crate) const BODY_BELLATRIX: &[Ssz] = &[
+    SIGNATURE,
+    ETH1_DATA,
+    ROOT,
+    Ssz::List(&PROPOSER_SLASHING, 16),
+    Ssz::List(&ATTESTER_SLASHING, 2),
+    Ssz::List(&ATTESTATION, 128),
+    Ssz::List(&DEPOSIT, 16),
+    Ssz::List(&SIGNED_VOLUNTARY_EXIT, 16),
+    SYNC_AGGREGATE,
+    EXECUTION_PAYLOAD_BELLATRIX,
+];
+
+/// `BeaconBlockBody` fields of Capella
+pub(crate) const BODY_CAPELLA: &[Ssz] = &[
+    SIGNATURE,
+    ETH1_DATA,
+    ROOT,
+    Ssz::List(&PROPOSER_SLASHING, 16),
+    Ssz::List(&ATTESTER_SLASHING, 2),
+    Ssz::List(&ATTESTATION, 128),
+    Ssz::List(&DEPOSIT, 16),
+    Ssz::List(&SIGNED_VOLUNTARY_EXIT, 16),
+    SYNC_AGGREGATE,
+    EXECUTION_PAYLOAD_CAPELLA,
+    Ssz::List(&SIGNED_BLS_TO_EXECUTION_CHANGE, 16),
+];
+
+/// `BeaconBlockBody` fields of Deneb
+pub(crate) const BODY_DENEB: &[Ssz] = &[
+    SIGNATURE,
+    ETH1_DATA,
+    ROOT,
+    Ssz::List(&PROPOSER_SLASHING, 16),
+    Ssz::List(&ATTESTER_SLASHING, 2),
+    Ssz::List(&ATTESTATION, 128),
+    Ssz::List(&DEPOSIT, 16),
+    Ssz::List(&SIGNED_VOLUNTARY_EXIT, 16),
+    SYNC_AGGREGATE,
+    EXECUTION_PAYLOAD_DENEB,
+    Ssz::List(&SIGNED_BLS_TO_EXECUTION_CHANGE, 16),
+    Ssz::List(&PUBKEY, 4096),
+];
+
+/// `BeaconBlockBody` fields of Electra, unchanged in Fulu
+pub(crate) const BODY_ELECTRA: &[Ssz] = &[
+    SIGNATURE,
+    ETH1_DATA,
+    ROOT,
+    Ssz::List(&PROPOSER_SLASHING, 16),
+    Ssz::List(&ATTESTER_SLASHING_ELECTRA, 1),
+    Ssz::List(&ATTESTATION_ELECTRA, 8),
+    Ssz::List(&DEPOSIT, 16),
+    Ssz::List(&SIGNED_VOLUNTARY_EXIT, 16),
+    SYNC_AGGREGATE,
+    EXECUTION_PAYLOAD_DENEB,
+    Ssz::List(&SIGNED_BLS_TO_EXECUTION_CHANGE, 16),
+    Ssz::List(&PUBKEY, 4096),
+    Ssz::Container(EXECUTION_REQUESTS),
+];
+
+/// Index of the execution payload in the `BeaconBlockBody` fields
+pub(crate) const BODY_EXECUTION_PAYLOAD: usize = 9;
+
+/// Index of the blob KZG commitments in the `BeaconBlockBody` fields
+pub(crate) const BODY_BLOB_KZG_COMMITMENTS: usize = 11;
+
+/// Index of the execution requests in the `BeaconBlockBody` fields
+pub(crate) const BODY_EXECUTION_REQUESTS: usize = 12;
+
+/// `BeaconBlock` of phase 0
+pub(crate) const BEACON_BLOCK_PHASE0: Ssz =
+    Ssz::Container(&[UINT64, UINT64, ROOT, ROOT, Ssz::Container(BODY_PHASE0)]);
+
+/// `BeaconBlock` of Altair
+pub(crate) const BEACON_BLOCK_ALTAIR: Ssz =
+    Ssz::Container(&[UINT64, UINT64, ROOT, ROOT, Ssz::Container(BODY_ALTAIR)]);
+
+/// `BeaconBlock` of Bellatrix
+pub(crate) const BEACON_BLOCK_BELLATRIX: Ssz =
+    Ssz::Container(&[UINT64, UINT64, ROOT, ROOT, Ssz::Container(BODY_BELLATRIX)]);
+
+/// `BeaconBlock` of Capella
+pub(crate) const BEACON_BLOCK_CAPELLA: Ssz =
+    Ssz::Container(&[UINT64, UINT64, ROOT, ROOT, Ssz::Container(BODY_CAPELLA)]);
+
+/// `BeaconBlock` of Deneb
+pub(crate) const BEACON_BLOCK_DENEB: Ssz =
+    Ssz::Container(&[UINT64, UINT64, ROOT, ROOT, Ssz::Container(BODY_DENEB)]);
+
+/// `BeaconBlock` of Electra, unchanged in Fulu
+pub(crate) const BEACON_BLOCK_ELECTRA: Ssz =
+    Ssz::Container(&[UINT64, UINT64, ROOT, ROOT, Ssz::Container(BODY_ELECTRA)]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::b256;
+
+    #[test]
+    fn test_merkleize_pads_with_zero_hashes() {
+        let chunk = B256::repeat_byte(1);
+
+        assert_eq!(merkleize(&[], 4).unwrap(), ZERO_HASHES[2]);
+        assert_eq!(merkleize(&[chunk], 1).unwrap(), chunk);
+        assert_eq!(
+            merkleize(&[chunk], 4).unwrap(),
+            hash_pair(&hash_pair(&chunk, &B256::ZERO), &ZERO_HASHES[1])
+        );
+        assert!(merkleize(&[chunk, chunk], 1).is_err());
+    }
+
+    #[test]
+    fn test_bitlist_root() {
+        // `0b0000_1101` holds the bits `101` followed by the delimiting bit
+        let root = Ssz::Bitlist(8).hash_tree_root(&[0b0000_1101]).unwrap();
+        let mut chunk = B256::ZERO;
+        chunk[0] = 0b101;
+
+        assert_eq!(root, mix_in_length(&chunk, 3));
+        assert!(Ssz::Bitlist(8).hash_tree_root(&[0]).is_err());
+    }
+
+    #[test]
+    fn test_container_fields_and_root() {
+        // Checkpoint { epoch: 1, root: 0x22.. }
+        let mut checkpoint = 1u64.to_le_bytes().to_vec();
+        checkpoint.extend_from_slice(&[0x22; 32]);
+
+        let mut epoch = B256::ZERO;
+        epoch[0] = 1;
+
+        assert_eq!(
+            CHECKPOINT.hash_tree_root(&checkpoint).unwrap(),
+            hash_pair(&epoch, &B256::repeat_byte(0x22))
+        );
+
+        // A container with a variable length list of `uint64` values between fixed fields
+        const MIXED: &[Ssz] = &[UINT64, Ssz::List(&UINT64, 4), UINT64];
+        let mut mixed = 7u64.to_le_bytes().to_vec();
+        mixed.extend_from_slice(&20u32.to_le_bytes());
+        mixed.extend_from_slice(&9u64.to_le_bytes());
+        mixed.extend_from_slice(&3u64.to_le_bytes());
+
+        let fields = Ssz::fields(MIXED, &mixed).unwrap();
+        assert_eq!(fields, vec![&7u64.to_le_bytes()[..], &3u64.to_le_bytes(), &9u64.to_le_bytes()]);
+
+        mixed[8] = 21;
+        assert!(Ssz::fields(MIXED, &mixed).is_err());
+    }
+
+    #[test]
+    fn test_zero_hashes() {
+        assert_eq!(
+            ZERO_HASHES[1],
+            b256!("0xf5a5fd42d16a20302798ef6ed309979b43003d2320d9f0e8ea9831a92759fb4b")
+        );
+    }
+}
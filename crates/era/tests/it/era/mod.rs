@@ -1,2 +1,3 @@
 mod genesis;
+mod payload;
 mod roundtrip;
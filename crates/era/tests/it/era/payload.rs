@@ -0,0 +1,56 @@
+//! Execution payload extraction tests for `.era` files.
+//!
+//! These tests verify that every post-merge beacon block matches the block roots of the era state
+//! and that its execution payload converts into an execution block with the committed hash.
+
+use alloy_consensus::TxEnvelope;
+use reth_era::era::file::EraReader;
+use std::fs::File;
+
+use crate::{EraTestDownloader, MAINNET};
+
+async fn test_era_file_payloads(
+    downloader: &EraTestDownloader,
+    filename: &str,
+    network: &str,
+) -> eyre::Result<()> {
+    let file = downloader.open_era_file(filename, network).await?;
+    let roots = file.group.era_state.historical_roots()?;
+
+    // File names embed the first 4 bytes of the historical root of the era
+    let historical_root = roots.historical_root()?;
+    let short_root = filename.rsplit('-').next().unwrap().trim_end_matches(".era");
+    assert_eq!(alloy_primitives::hex::encode(&historical_root[..4]), short_root);
+
+    // The state can also be located through the slot index without reading the blocks
+    let path = downloader.download_file(filename, network).await?;
+    let state = EraReader::new(File::open(path)?).read_state()?;
+    assert_eq!(state.data, file.group.era_state.data);
+
+    let mut payloads = 0;
+    for compressed in &file.group.blocks {
+        let block = compressed.decode()?;
+        roots.verify(&block)?;
+
+        if let Some((payload, sidecar)) = block.execution_payload()? {
+            let block_hash = payload.block_hash();
+            let execution_block = payload.try_into_block_with_sidecar::<TxEnvelope>(&sidecar)?;
+
+            assert_eq!(execution_block.header.hash_slow(), block_hash);
+            payloads += 1;
+        }
+    }
+    println!("  Verified {payloads} execution payloads of {filename}");
+
+    Ok(())
+}
+
+#[test_case::test_case("mainnet-00780-bb546fec.era"; "era_payloads_mainnet_capella")]
+#[test_case::test_case("mainnet-01070-7616e3e2.era"; "era_payloads_mainnet_deneb")]
+#[test_case::test_case("mainnet-01581-82073d28.era"; "era_payloads_mainnet_electra")]
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "download intensive"]
+async fn test_mainnet_era_payloads(filename: &str) -> eyre::Result<()> {
+    let downloader = EraTestDownloader::new().await?;
+    test_era_file_payloads(&downloader, filename, MAINNET).await
+}
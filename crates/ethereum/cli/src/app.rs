@@ -194,7 +194,7 @@ where
             runner.run_blocking_command_until_exit(|ctx| command.execute::<N>(ctx))
         }
         Commands::Download(command) => runner.run_blocking_until_ctrl_c(command.execute::<N>()),
-        Commands::SnapshotManifest(command) => command.execute(),
+        Commands::SnapshotManifest(command) => runner.block_on(command.execute()),
         Commands::Stage(command) => {
             runner.run_command_until_exit(|ctx| command.execute::<N, _>(ctx, components))
         }
@@ -206,6 +206,7 @@ where
         Commands::ReExecute(command) => {
             runner.run_until_ctrl_c(command.execute::<N>(components, rt))
         }
+        Commands::Debug(command) => runner.run_until_ctrl_c(command.execute::<N>()),
         Commands::Ext(command) => command.execute(runner),
     }
 }
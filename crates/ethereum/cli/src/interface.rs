@@ -6,7 +6,7 @@ use reth_chainspec::{ChainSpec, Hardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_commands::{
     common::{CliComponentsBuilder, CliNodeTypes, HeaderMut},
-    config_cmd, db, download,
+    config_cmd, db, debug, download,
     download::manifest_cmd,
     dump_genesis, export_era, import, import_era, init_cmd, init_state,
     launcher::FnLauncher,
@@ -307,6 +307,9 @@ pub enum Commands<
     /// Re-execute blocks in parallel to verify historical sync correctness.
     #[command(name = "re-execute")]
     ReExecute(re_execute::Command<C>),
+    /// Debugging utilities that operate on a running node
+    #[command(name = "debug")]
+    Debug(debug::Command),
     /// Extension subcommands provided by consumers.
     #[command(flatten)]
     Ext(SubCmd),
@@ -348,6 +351,7 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug, SubCmd: Subcommand + fmt:
             Self::Config(_) => None,
             Self::Prune(cmd) => cmd.chain_spec(),
             Self::ReExecute(cmd) => cmd.chain_spec(),
+            Self::Debug(_) => None,
             Self::Ext(_) => None,
         }
     }
@@ -34,7 +34,13 @@ use reth_primitives_traits::{SealedBlock, SealedHeader};
 use revm::{context::BlockEnv, primitives::hardfork::SpecId};
 
 #[cfg(feature = "std")]
-use reth_evm::{ConfigureEngineEvm, ExecutableTxIterator};
+use reth_evm::{
+    block::{BlockExecutorFactory, BlockExecutorFor},
+    state_overrides::{BlockStateOverrides, StateOverridesExecutor},
+    ConfigureEngineEvm, Database, EvmFor, ExecutableTxIterator, InspectorFor,
+};
+#[cfg(feature = "std")]
+use revm::database::State;
 #[allow(unused_imports)]
 use {
     alloy_eips::Decodable2718,
@@ -83,6 +89,9 @@ pub struct EthEvmConfig<C = ChainSpec, EvmFactory = EthEvmFactory> {
     pub executor_factory: EthBlockExecutorFactory<RethReceiptBuilder, Arc<C>, EvmFactory>,
     /// Ethereum block assembler.
     pub block_assembler: EthBlockAssembler<C>,
+    /// State overrides applied before executing the children of a block, only set on dev chains.
+    #[cfg(feature = "std")]
+    state_overrides: Option<BlockStateOverrides>,
 }
 
 impl EthEvmConfig {
@@ -114,9 +123,20 @@ impl<ChainSpec, EvmFactory> EthEvmConfig<ChainSpec, EvmFactory> {
                 chain_spec,
                 evm_factory,
             ),
+            #[cfg(feature = "std")]
+            state_overrides: Default::default(),
         }
     }
 
+    /// Enables [`BlockStateOverrides`], which dev chains use to modify accounts between blocks.
+    ///
+    /// This must not be enabled on a node that follows a real network.
+    #[cfg(feature = "std")]
+    pub fn with_state_overrides(mut self) -> Self {
+        self.state_overrides = Some(Default::default());
+        self
+    }
+
     /// Returns the chain spec associated with this configuration.
     pub const fn chain_spec(&self) -> &Arc<ChainSpec> {
         self.executor_factory.spec()
@@ -214,6 +234,29 @@ where
             slot_number: attributes.slot_number,
         })
     }
+
+    #[cfg(feature = "std")]
+    fn state_overrides(&self) -> Option<&BlockStateOverrides> {
+        self.state_overrides.as_ref()
+    }
+
+    #[cfg(feature = "std")]
+    fn create_executor<'a, DB, I>(
+        &'a self,
+        evm: EvmFor<Self, &'a mut State<DB>, I>,
+        ctx: EthBlockExecutionCtx<'a>,
+    ) -> impl BlockExecutorFor<'a, Self::BlockExecutorFactory, &'a mut State<DB>, I>
+    where
+        DB: Database,
+        I: InspectorFor<Self, &'a mut State<DB>> + 'a,
+    {
+        let parent_hash = ctx.parent_hash;
+        StateOverridesExecutor::new(
+            self.executor_factory.create_executor(evm, ctx),
+            self.state_overrides.as_ref(),
+            parent_hash,
+        )
+    }
 }
 
 #[cfg(feature = "std")]
@@ -6,7 +6,7 @@ use alloy_eips::{eip7840::BlobParams, merge::EPOCH_SLOTS};
 use alloy_network::Ethereum;
 use alloy_rpc_types_engine::ExecutionData;
 use reth_chainspec::{ChainSpec, EthChainSpec, EthereumHardforks, Hardforks};
-use reth_engine_local::LocalPayloadAttributesBuilder;
+use reth_engine_local::{LocalMiner, LocalPayloadAttributesBuilder};
 use reth_engine_primitives::EngineTypes;
 use reth_ethereum_consensus::EthBeaconConsensus;
 use reth_ethereum_engine_primitives::{EthBuiltPayload, EthPayloadAttributes};
@@ -37,9 +37,11 @@ use reth_payload_primitives::PayloadTypes;
 use reth_provider::{providers::ProviderFactoryBuilder, EthStorage};
 use reth_rpc::{
     eth::core::{EthApiFor, EthRpcConverterFor},
-    TestingApi, ValidationApi,
+    AnvilApi, TestingApi, ValidationApi,
+};
+use reth_rpc_api::servers::{
+    AnvilApiServer, BlockSubmissionValidationApiServer, HardhatApiServer, TestingApiServer,
 };
-use reth_rpc_api::servers::{BlockSubmissionValidationApiServer, TestingApiServer};
 use reth_rpc_builder::config::RethRpcServerConfig;
 use reth_rpc_eth_api::{
     helpers::{
@@ -364,6 +366,19 @@ where
                     .modules
                     .merge_if_module_configured(RethRpcModule::Testing, testing_api.into_rpc())?;
 
+                // anvil_* and hardhat_*: only available in dev mode, where the local miner runs.
+                if let Some(miner) = container.registry.local_miner().cloned() {
+                    let anvil_api = AnvilApi::new(container.registry.eth_api().clone(), miner);
+                    container.modules.merge_if_module_configured(
+                        RethRpcModule::Anvil,
+                        AnvilApiServer::into_rpc(anvil_api.clone()),
+                    )?;
+                    container.modules.merge_if_module_configured(
+                        RethRpcModule::Hardhat,
+                        HardhatApiServer::into_rpc(anvil_api),
+                    )?;
+                }
+
                 Ok(())
             })
             .await
@@ -461,6 +476,15 @@ impl<N: FullNodeComponents<Types = Self>> DebugNode<N> for EthereumNode {
     ) -> impl PayloadAttributesBuilder<<Self::Payload as PayloadTypes>::PayloadAttributes> {
         LocalPayloadAttributesBuilder::new(Arc::new(chain_spec.clone()))
     }
+
+    fn configure_local_miner<B, Pool>(
+        miner: LocalMiner<Self::Payload, B, Pool>,
+    ) -> LocalMiner<Self::Payload, B, Pool>
+    where
+        Pool: TransactionPool + Unpin,
+    {
+        miner.with_local_payload_attributes()
+    }
 }
 
 /// A regular ethereum evm and executor builder.
@@ -479,7 +503,9 @@ where
     type EVM = EthEvmConfig<Types::ChainSpec>;
 
     async fn build_evm(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::EVM> {
-        Ok(EthEvmConfig::new(ctx.chain_spec()))
+        let evm_config = EthEvmConfig::new(ctx.chain_spec());
+        // Account overrides of the anvil namespace are only accepted on dev chains.
+        Ok(if ctx.is_dev() { evm_config.with_state_overrides() } else { evm_config })
     }
 }
 
@@ -534,6 +560,8 @@ where
         let blob_store =
             reth_node_builder::components::create_blob_store_with_cache(ctx, blob_cache_size)?;
 
+        let admission_policy = reth_node_builder::components::create_admission_policy(ctx)?;
+
         let validator =
             TransactionValidationTaskExecutor::eth_builder(ctx.provider().clone(), evm_config)
                 .set_eip4844(!blobs_disabled)
@@ -544,6 +572,7 @@ where
                 .with_max_tx_gas_limit(ctx.config().txpool.max_tx_gas_limit)
                 .with_minimum_priority_fee(ctx.config().txpool.minimum_priority_fee)
                 .with_additional_tasks(ctx.config().txpool.additional_validation_tasks)
+                .with_admission_policy(admission_policy.clone())
                 .build_with_tasks(ctx.task_executor().clone(), blob_store.clone());
 
         if validator.validator().eip4844() {
@@ -561,6 +590,20 @@ where
             .with_validator(validator)
             .build_and_spawn_maintenance_task(blob_store, pool_config)?;
 
+        if let Some(admission_policy) = admission_policy {
+            // bundles must not get around the rules of the validator
+            if let Some(bundle_pool) = transaction_pool.bundle_pool() {
+                bundle_pool.set_admission_policy(Some(admission_policy.clone()));
+            }
+            ctx.task_executor().spawn_critical_task(
+                "txpool admission policy task",
+                reth_transaction_pool::maintain::maintain_admission_policy(
+                    transaction_pool.clone(),
+                    admission_policy,
+                ),
+            );
+        }
+
         info!(target: "reth::cli", "Transaction pool initialized");
         debug!(target: "reth::cli", "Spawned txpool maintenance task");
 
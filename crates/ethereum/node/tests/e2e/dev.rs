@@ -1,6 +1,7 @@
 use alloy_eips::eip2718::Encodable2718;
 use alloy_genesis::Genesis;
-use alloy_primitives::{b256, hex, Address};
+use alloy_primitives::{b256, hex, Address, U256};
+use alloy_rpc_types_eth::TransactionRequest;
 use futures::StreamExt;
 use reth_chainspec::ChainSpec;
 use reth_node_api::{BlockBody, FullNodeComponents};
@@ -8,6 +9,8 @@ use reth_node_builder::{rpc::RethRpcAddOns, FullNode, NodeBuilder, NodeConfig, N
 use reth_node_core::args::DevArgs;
 use reth_node_ethereum::{node::EthereumAddOns, EthereumNode};
 use reth_provider::{providers::BlockchainProvider, CanonStateSubscriptions};
+use reth_rpc::AnvilApi;
+use reth_rpc_api::AnvilApiServer;
 use reth_rpc_eth_api::{helpers::EthTransactions, EthApiServer};
 use reth_tasks::Runtime;
 use std::sync::Arc;
@@ -75,6 +78,102 @@ async fn can_run_dev_node_custom_attributes() -> eyre::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn can_modify_and_revert_dev_chain() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let runtime = Runtime::test();
+
+    let node_config = NodeConfig::test()
+        .with_chain(custom_chain())
+        .with_dev(DevArgs { dev: true, ..Default::default() });
+    let NodeHandle { node, .. } = NodeBuilder::new(node_config.clone())
+        .testing_node(runtime.clone())
+        .with_types_and_provider::<EthereumNode, BlockchainProvider<_>>()
+        .with_components(EthereumNode::components())
+        .with_add_ons(EthereumAddOns::default())
+        .launch_with_debug_capabilities()
+        .await?;
+
+    let anvil_api = AnvilApi::new(
+        node.rpc_registry.eth_api().clone(),
+        node.rpc_registry.local_miner().cloned().expect("dev mode"),
+    );
+    let eth_api = node.rpc_registry.eth_api();
+    let address = Address::random();
+
+    let snapshot = anvil_api.anvil_snapshot().await?;
+
+    // account changes are visible right away, they are committed by a new block
+    anvil_api.anvil_set_balance(address, U256::from(1000)).await?;
+    assert_eq!(eth_api.balance(address, None).await?, U256::from(1000));
+    assert_eq!(eth_api.block_number()?, U256::from(1));
+
+    anvil_api.anvil_mine(Some(U256::from(2)), None).await?;
+    assert_eq!(eth_api.block_number()?, U256::from(3));
+
+    assert!(anvil_api.anvil_revert(snapshot).await?);
+    assert_eq!(eth_api.block_number()?, U256::ZERO);
+    assert_eq!(eth_api.balance(address, None).await?, U256::ZERO);
+
+    // the chain can be extended again after the revert
+    anvil_api.anvil_mine(None, None).await?;
+    assert_eq!(eth_api.block_number()?, U256::from(1));
+    assert_eq!(eth_api.balance(address, None).await?, U256::ZERO);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_send_impersonated_transactions() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let runtime = Runtime::test();
+
+    let node_config = NodeConfig::test()
+        .with_chain(custom_chain())
+        .with_dev(DevArgs { dev: true, ..Default::default() });
+    let NodeHandle { node, .. } = NodeBuilder::new(node_config.clone())
+        .testing_node(runtime.clone())
+        .with_types_and_provider::<EthereumNode, BlockchainProvider<_>>()
+        .with_components(EthereumNode::components())
+        .with_add_ons(EthereumAddOns::default())
+        .launch_with_debug_capabilities()
+        .await?;
+
+    let anvil_api = AnvilApi::new(
+        node.rpc_registry.eth_api().clone(),
+        node.rpc_registry.local_miner().cloned().expect("dev mode"),
+    );
+    let eth_api = node.rpc_registry.eth_api();
+    let sender = Address::random();
+    let recipient = Address::random();
+
+    anvil_api.anvil_set_automine(false).await?;
+    anvil_api.anvil_set_balance(sender, U256::from(10).pow(U256::from(18))).await?;
+
+    let request = TransactionRequest::default()
+        .from(sender)
+        .to(recipient)
+        .value(U256::from(1))
+        .max_fee_per_gas(10_000_000_000)
+        .max_priority_fee_per_gas(1_000_000_000);
+    assert!(eth_api.send_transaction_request(request.clone()).await.is_err());
+
+    anvil_api.anvil_impersonate_account(sender).await?;
+    let hash = eth_api.send_transaction_request(request.clone()).await?;
+    anvil_api.anvil_mine(None, None).await?;
+
+    let receipt =
+        EthTransactions::transaction_receipt(eth_api, hash).await?.expect("transaction is mined");
+    assert!(receipt.status());
+    assert_eq!(receipt.from, sender);
+    assert_eq!(eth_api.balance(recipient, None).await?, U256::from(1));
+
+    anvil_api.anvil_stop_impersonating_account(sender).await?;
+    assert!(eth_api.send_transaction_request(request).await.is_err());
+
+    Ok(())
+}
+
 async fn assert_chain_advances<N, AddOns>(node: &FullNode<N, AddOns>)
 where
     N: FullNodeComponents<Provider: CanonStateSubscriptions>,
@@ -5,7 +5,7 @@ use alloy_genesis::Genesis;
 use alloy_primitives::B256;
 use reth_chainspec::{ChainSpecBuilder, MAINNET};
 use reth_e2e_test_utils::{
-    node::NodeTestContext, transaction::TransactionTestContext, wallet::Wallet,
+    node::NodeTestContext, setup, transaction::TransactionTestContext, wallet::Wallet,
 };
 use reth_node_builder::{NodeBuilder, NodeHandle};
 use reth_node_core::{args::RpcServerArgs, node_config::NodeConfig};
@@ -14,9 +14,9 @@ use reth_primitives_traits::Recovered;
 use reth_provider::CanonStateSubscriptions;
 use reth_tasks::Runtime;
 use reth_transaction_pool::{
-    blobstore::InMemoryBlobStore, test_utils::OkValidator, BlockInfo, CoinbaseTipOrdering,
-    EthPooledTransaction, Pool, PoolTransaction, TransactionOrigin, TransactionPool,
-    TransactionPoolExt,
+    blobstore::InMemoryBlobStore, conditional::TransactionConditional, test_utils::OkValidator,
+    BlockInfo, CoinbaseTipOrdering, EthPooledTransaction, Pool, PoolTransaction, TransactionOrigin,
+    TransactionPool, TransactionPoolExt,
 };
 use std::{sync::Arc, time::Duration};
 
@@ -309,3 +309,50 @@ async fn maintain_txpool_commit() -> eyre::Result<()> {
 
     Ok(())
 }
+
+// Test that the payload builder skips conditional transactions until their block range is reached
+#[tokio::test]
+async fn payload_builder_skips_conditional_transactions() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let (mut nodes, wallet) = setup::<EthereumNode>(
+        1,
+        Arc::new(
+            ChainSpecBuilder::default()
+                .chain(MAINNET.chain)
+                .genesis(serde_json::from_str(include_str!("../assets/genesis.json")).unwrap())
+                .cancun_activated()
+                .build(),
+        ),
+        false,
+        eth_payload_attributes,
+    )
+    .await?;
+    let mut node = nodes.pop().unwrap();
+
+    let sender = wallet.inner.address();
+    let envelop = TransactionTestContext::transfer_tx(1, wallet.inner).await;
+    let tx = Recovered::new_unchecked(EthereumTxEnvelope::<TxEip4844>::from(envelop), sender);
+    let mut pooled_tx = EthPooledTransaction::new(tx, 200);
+    pooled_tx
+        .try_set_conditional(TransactionConditional {
+            block_number_min: Some(2),
+            ..Default::default()
+        })
+        .unwrap();
+    let tx_hash =
+        node.inner.pool.add_transaction(TransactionOrigin::External, pooled_tx).await?.hash;
+
+    // the conditional is not yet satisfied by the first block
+    let payload = node.advance_block().await?;
+    assert_eq!(payload.block().number, 1);
+    assert_eq!(payload.block().body().transactions().count(), 0);
+    assert!(node.inner.pool.contains(&tx_hash));
+
+    // the second block is within range
+    let payload = node.advance_block().await?;
+    assert_eq!(payload.block().number, 2);
+    assert!(payload.block().body().transactions().any(|tx| *tx.hash() == tx_hash));
+
+    Ok(())
+}
@@ -0,0 +1,244 @@
+//! Bundle simulation and execution for the Ethereum payload builder.
+
+use alloy_consensus::{Transaction, Typed2718};
+use alloy_primitives::{Address, B256, U256};
+use core::cell::RefCell;
+use reth_errors::BlockExecutionError;
+use reth_ethereum_primitives::{EthPrimitives, TransactionSigned};
+use reth_evm::{execute::BlockBuilder, ConfigureEvm, Evm, EvmEnvFor, EvmError};
+use reth_payload_builder_primitives::PayloadBuilderError;
+use reth_transaction_pool::bundle::PoolBundle;
+use revm::{
+    context_interface::result::ResultAndState,
+    database::CacheDB,
+    primitives::{StorageKey, StorageValue},
+    state::{AccountInfo, Bytecode},
+    Database, DatabaseCommit, DatabaseRef,
+};
+use std::sync::Arc;
+use tracing::{trace, warn};
+
+/// Maximum number of bundles that are simulated for a single payload.
+pub(crate) const MAX_SIMULATED_BUNDLES: usize = 64;
+
+/// A bundle that is eligible for the payload, together with the coinbase payment it made when it
+/// was last simulated.
+#[derive(Debug)]
+pub(crate) struct PayloadBundle {
+    /// The bundle.
+    pub(crate) bundle: Arc<PoolBundle<TransactionSigned>>,
+    /// Payment to the coinbase per unit of gas.
+    pub(crate) effective_gas_price: U256,
+}
+
+/// The outcome of a bundle that was executed on top of the block.
+#[derive(Debug)]
+pub(crate) struct ExecutedBundle {
+    /// Total gas used by the bundle's transactions.
+    pub(crate) gas_used: u64,
+    /// Total payment to the coinbase, including priority fees and direct transfers.
+    pub(crate) coinbase_payment: U256,
+}
+
+/// The outcome of a successful bundle simulation.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SimulatedBundle {
+    /// Total gas used by the bundle's transactions.
+    pub(crate) gas_used: u64,
+    /// Total payment to the coinbase, including priority fees and direct transfers.
+    pub(crate) coinbase_payment: U256,
+}
+
+impl SimulatedBundle {
+    /// Returns the payment to the coinbase per unit of gas.
+    pub(crate) fn effective_gas_price(&self) -> U256 {
+        self.coinbase_payment.checked_div(U256::from(self.gas_used)).unwrap_or_default()
+    }
+}
+
+/// Simulates all bundles on top of the current block state and orders them by their effective
+/// payment to the coinbase, best bundle last.
+///
+/// Bundles that fail to simulate are dropped.
+pub(crate) fn simulate_bundles<EvmConfig, Builder>(
+    evm_config: &EvmConfig,
+    evm_env: &EvmEnvFor<EvmConfig>,
+    coinbase: Address,
+    builder: &mut Builder,
+    bundles: Vec<Arc<PoolBundle<TransactionSigned>>>,
+) -> Result<Vec<PayloadBundle>, PayloadBuilderError>
+where
+    EvmConfig: ConfigureEvm<Primitives = EthPrimitives>,
+    Builder: BlockBuilder<Primitives = EthPrimitives>,
+{
+    let mut simulated = Vec::with_capacity(bundles.len());
+    for bundle in bundles {
+        let db = builder.evm_mut().db_mut();
+        if let Some(outcome) = simulate_bundle(evm_config, evm_env.clone(), coinbase, db, &bundle)?
+        {
+            simulated
+                .push(PayloadBundle { effective_gas_price: outcome.effective_gas_price(), bundle });
+        }
+    }
+    simulated.sort_by_key(|bundle| bundle.effective_gas_price);
+    Ok(simulated)
+}
+
+/// Executes the bundle on top of the block if it still succeeds against the current state and
+/// fits into the remaining block gas.
+///
+/// The bundle is simulated first, so that it is either included as a whole or not at all. If a
+/// transaction still fails to execute after earlier transactions of the bundle were applied, the
+/// block can't be built without splitting the bundle and an error is returned, so the build
+/// attempt is abandoned.
+///
+/// Returns `None` if the bundle was not included.
+pub(crate) fn execute_bundle<EvmConfig, Builder>(
+    evm_config: &EvmConfig,
+    evm_env: &EvmEnvFor<EvmConfig>,
+    coinbase: Address,
+    builder: &mut Builder,
+    bundle: &PoolBundle<TransactionSigned>,
+    gas_available: u64,
+) -> Result<Option<ExecutedBundle>, PayloadBuilderError>
+where
+    EvmConfig: ConfigureEvm<Primitives = EthPrimitives>,
+    Builder: BlockBuilder<Primitives = EthPrimitives>,
+{
+    let bundle_hash = bundle.hash();
+    if bundle.transactions.iter().any(|tx| tx.is_eip4844()) {
+        // blob sidecars are not tracked for bundles
+        trace!(target: "payload_builder", ?bundle_hash, "skipping bundle with blob transaction");
+        return Ok(None)
+    }
+
+    let gas_limit = bundle.transactions.iter().map(|tx| tx.gas_limit()).sum::<u64>();
+    if gas_limit > gas_available {
+        trace!(target: "payload_builder", ?bundle_hash, gas_limit, gas_available, "skipping bundle exceeding block gas limit");
+        return Ok(None)
+    }
+
+    let db = builder.evm_mut().db_mut();
+    if simulate_bundle(evm_config, evm_env.clone(), coinbase, db, bundle)?.is_none() {
+        return Ok(None)
+    }
+
+    let balance_before = balance_of(builder.evm_mut().db_mut(), coinbase)?;
+    let mut gas_used = 0;
+    for (idx, tx) in bundle.transactions.iter().enumerate() {
+        match builder.execute_transaction(tx.clone()) {
+            Ok(tx_gas_used) => gas_used += tx_gas_used,
+            Err(BlockExecutionError::Validation(err)) => {
+                // the bundle was simulated against the same state, so this is unexpected
+                warn!(target: "payload_builder", %err, ?bundle_hash, tx_hash = ?tx.tx_hash(), "failed to execute simulated bundle");
+                if idx == 0 {
+                    return Ok(None)
+                }
+                // the already executed transactions of the bundle can't be undone
+                return Err(PayloadBuilderError::evm(BlockExecutionError::Validation(err)))
+            }
+            Err(err) => return Err(PayloadBuilderError::evm(err)),
+        }
+    }
+    let balance_after = balance_of(builder.evm_mut().db_mut(), coinbase)?;
+
+    Ok(Some(ExecutedBundle {
+        gas_used,
+        coinbase_payment: balance_after.saturating_sub(balance_before),
+    }))
+}
+
+/// Simulates the bundle on top of the given database without modifying it.
+///
+/// Returns `None` if any transaction of the bundle is invalid or reverts without being allowed
+/// to.
+fn simulate_bundle<EvmConfig, DB>(
+    evm_config: &EvmConfig,
+    evm_env: EvmEnvFor<EvmConfig>,
+    coinbase: Address,
+    db: &mut DB,
+    bundle: &PoolBundle<TransactionSigned>,
+) -> Result<Option<SimulatedBundle>, PayloadBuilderError>
+where
+    EvmConfig: ConfigureEvm<Primitives = EthPrimitives>,
+    DB: Database,
+{
+    let bundle_hash = bundle.hash();
+    let mut evm = evm_config.evm_with_env(CacheDB::new(BundleSimulationDb::new(db)), evm_env);
+    let balance_before = balance_of(evm.db_mut(), coinbase)?;
+
+    let mut gas_used = 0;
+    for tx in &bundle.transactions {
+        let ResultAndState { result, state } = match evm.transact(tx.as_recovered_ref()) {
+            Ok(res) => res,
+            Err(err) if err.is_invalid_tx_err() => {
+                trace!(target: "payload_builder", %err, ?bundle_hash, tx_hash = ?tx.tx_hash(), "skipping bundle with invalid transaction");
+                return Ok(None)
+            }
+            Err(err) => return Err(PayloadBuilderError::other(err)),
+        };
+
+        if !result.is_success() && !bundle.can_revert(tx.tx_hash()) {
+            trace!(target: "payload_builder", ?bundle_hash, tx_hash = ?tx.tx_hash(), "skipping bundle with reverting transaction");
+            return Ok(None)
+        }
+
+        gas_used += result.tx_gas_used();
+        evm.db_mut().commit(state);
+    }
+
+    let balance_after = balance_of(evm.db_mut(), coinbase)?;
+
+    Ok(Some(SimulatedBundle {
+        gas_used,
+        coinbase_payment: balance_after.saturating_sub(balance_before),
+    }))
+}
+
+/// Returns the balance of the given account.
+fn balance_of<DB: Database>(db: &mut DB, address: Address) -> Result<U256, PayloadBuilderError> {
+    Ok(db
+        .basic(address)
+        .map_err(PayloadBuilderError::other)?
+        .map(|account| account.balance)
+        .unwrap_or_default())
+}
+
+/// Read-only view of the block state that is used as the base layer for bundle simulations.
+///
+/// All state changes of the simulation are kept in the [`CacheDB`] on top of this, so the block
+/// state itself is never modified.
+#[derive(Debug)]
+struct BundleSimulationDb<'a, DB> {
+    inner: RefCell<&'a mut DB>,
+}
+
+impl<'a, DB> BundleSimulationDb<'a, DB> {
+    const fn new(db: &'a mut DB) -> Self {
+        Self { inner: RefCell::new(db) }
+    }
+}
+
+impl<DB: Database> DatabaseRef for BundleSimulationDb<'_, DB> {
+    type Error = DB::Error;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.inner.borrow_mut().basic(address)
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.inner.borrow_mut().code_by_hash(code_hash)
+    }
+
+    fn storage_ref(
+        &self,
+        address: Address,
+        index: StorageKey,
+    ) -> Result<StorageValue, Self::Error> {
+        self.inner.borrow_mut().storage(address, index)
+    }
+
+    fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+        self.inner.borrow_mut().block_hash(number)
+    }
+}
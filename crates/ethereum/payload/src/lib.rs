@@ -31,16 +31,24 @@ use reth_payload_builder_primitives::PayloadBuilderError;
 use reth_payload_primitives::PayloadAttributes;
 use reth_primitives_traits::transaction::error::InvalidTransactionError;
 use reth_revm::{database::StateProviderDatabase, db::State};
-use reth_storage_api::StateProviderFactory;
+use reth_storage_api::{errors::provider::ProviderError, StateProvider, StateProviderFactory};
 use reth_transaction_pool::{
+    conditional::{
+        validate_block_attributes, AccountStorage, BlockConditionalAttributes,
+        TransactionConditional, TransactionConditionalError,
+    },
     error::{Eip4844PoolTransactionError, InvalidPoolTransactionError},
     BestTransactions, BestTransactionsAttributes, PoolTransaction, TransactionPool,
     ValidPoolTransaction,
 };
-use revm::context_interface::Block as _;
+use reth_trie_common::HashedStorage;
+use revm::{context_interface::Block as _, Database};
 use std::sync::Arc;
 use tracing::{debug, trace, warn};
 
+mod bundle;
+use bundle::{execute_bundle, simulate_bundles, PayloadBundle, MAX_SIMULATED_BUNDLES};
+
 mod config;
 pub use config::*;
 
@@ -186,27 +194,47 @@ where
         .with_bal_builder_if(is_amsterdam)
         .build();
 
-    let mut builder = evm_config
-        .builder_for_next_block(
-            &mut db,
-            &parent_header,
-            NextBlockEnvAttributes {
-                timestamp: attributes.timestamp(),
-                suggested_fee_recipient: attributes.suggested_fee_recipient,
-                prev_randao: attributes.prev_randao,
-                gas_limit: builder_config.gas_limit(parent_header.gas_limit),
-                parent_beacon_block_root: attributes.parent_beacon_block_root(),
-                withdrawals: attributes.withdrawals.clone().map(Into::into),
-                extra_data: builder_config.extra_data,
-                slot_number: attributes.slot_number(),
-            },
+    let next_block_attributes = NextBlockEnvAttributes {
+        timestamp: attributes.timestamp(),
+        suggested_fee_recipient: attributes.suggested_fee_recipient,
+        prev_randao: attributes.prev_randao,
+        gas_limit: builder_config.gas_limit(parent_header.gas_limit),
+        parent_beacon_block_root: attributes.parent_beacon_block_root(),
+        withdrawals: attributes.withdrawals.clone().map(Into::into),
+        extra_data: builder_config.extra_data,
+        slot_number: attributes.slot_number(),
+    };
+
+    // bundles are simulated in a separate evm with the same environment as the block
+    let bundles = pool
+        .bundle_pool()
+        .map(|bundles| {
+            bundles.bundles_for_block(
+                parent_header.number + 1,
+                attributes.timestamp(),
+                MAX_SIMULATED_BUNDLES,
+            )
+        })
+        .unwrap_or_default();
+    let bundle_evm_env = if bundles.is_empty() {
+        None
+    } else {
+        Some(
+            evm_config
+                .next_evm_env(&parent_header, &next_block_attributes)
+                .map_err(PayloadBuilderError::other)?,
         )
+    };
+
+    let mut builder = evm_config
+        .builder_for_next_block(&mut db, &parent_header, next_block_attributes)
         .map_err(PayloadBuilderError::other)?;
 
     debug!(target: "payload_builder", id=%payload_id, parent_header = ?parent_header.hash(), parent_number = parent_header.number, "building new payload");
     let mut cumulative_gas_used = 0;
     let block_gas_limit: u64 = builder.evm_mut().block().gas_limit();
     let base_fee = builder.evm_mut().block().basefee();
+    let coinbase = builder.evm_mut().block().beneficiary();
 
     let mut best_txs = best_txs(BestTransactionsAttributes::new(
         base_fee,
@@ -225,6 +253,12 @@ where
         PayloadBuilderError::Internal(err.into())
     })?;
 
+    // simulate all eligible bundles at the top of the block to determine their order
+    let mut bundles = match &bundle_evm_env {
+        Some(evm_env) => simulate_bundles(&evm_config, evm_env, coinbase, &mut builder, bundles)?,
+        None => Vec::new(),
+    };
+
     // initialize empty blob sidecars at first. If cancun is active then this will be populated by
     // blob sidecars if any.
     let mut blob_sidecars = BlobSidecars::Empty;
@@ -248,7 +282,65 @@ where
     let withdrawals_rlp_length =
         attributes.withdrawals.as_ref().map(|withdrawals| withdrawals.length()).unwrap_or(0);
 
-    while let Some(pool_tx) = best_txs.next() {
+    let conditional_block_attributes = BlockConditionalAttributes {
+        number: parent_header.number + 1,
+        timestamp: attributes.timestamp(),
+    };
+
+    loop {
+        let pool_tx = best_txs.next();
+
+        // include all bundles that pay more to the coinbase per unit of gas than the next best
+        // pool transaction, or all remaining bundles if the pool is exhausted
+        let pool_tx_tip = pool_tx
+            .as_ref()
+            .map(|tx| U256::from(tx.effective_tip_per_gas(base_fee).unwrap_or_default()));
+        while let Some(PayloadBundle { bundle, .. }) =
+            bundles.pop_if(|bundle| pool_tx_tip.is_none_or(|tip| bundle.effective_gas_price >= tip))
+        {
+            // check if the job was cancelled, if so we can exit early
+            if cancel.is_cancelled() {
+                return Ok(BuildOutcome::Cancelled)
+            }
+
+            let bundle_rlp_length =
+                bundle.transactions.iter().map(|tx| tx.inner().length()).sum::<usize>();
+            let estimated_block_size_with_bundle =
+                block_transactions_rlp_length + bundle_rlp_length + withdrawals_rlp_length + 1024;
+            if is_osaka && estimated_block_size_with_bundle > MAX_RLP_BLOCK_SIZE {
+                trace!(target: "payload_builder", bundle_hash=?bundle.hash(), "skipping oversized bundle");
+                continue
+            }
+
+            let Some(evm_env) = &bundle_evm_env else { break };
+            let executed = match execute_bundle(
+                &evm_config,
+                evm_env,
+                coinbase,
+                &mut builder,
+                &bundle,
+                block_gas_limit.saturating_sub(cumulative_gas_used),
+            ) {
+                Ok(Some(executed)) => executed,
+                Ok(None) => continue,
+                Err(err) => {
+                    // the bundle may be partially applied, so this attempt is abandoned and the
+                    // bundle is dropped to keep it out of the next one
+                    if let Some(bundles) = pool.bundle_pool() {
+                        bundles.remove_bundle(&bundle.hash());
+                    }
+                    return Err(err)
+                }
+            };
+
+            trace!(target: "payload_builder", bundle_hash=?bundle.hash(), gas_used=executed.gas_used, "included bundle");
+            block_transactions_rlp_length += bundle_rlp_length;
+            total_fees += executed.coinbase_payment;
+            cumulative_gas_used += executed.gas_used;
+        }
+
+        let Some(pool_tx) = pool_tx else { break };
+
         // ensure we still have capacity for this transaction
         if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
             // we can't fit this transaction into the block, so we need to mark it as invalid
@@ -266,6 +358,23 @@ where
             return Ok(BuildOutcome::Cancelled)
         }
 
+        // conditional transactions are only included within their block range and while the
+        // storage of their known accounts matches the state of the block so far
+        if let Some(conditional) = pool_tx.transaction.conditional() &&
+            let Err(err) = validate_block_attributes(conditional, &conditional_block_attributes)
+                .and_then(|()| {
+                    validate_known_accounts_in_block(
+                        conditional,
+                        builder.evm_mut().db_mut(),
+                        &*state_provider,
+                    )
+                })
+        {
+            trace!(target: "payload_builder", tx=?pool_tx.hash(), %err, "skipping conditional transaction");
+            best_txs.mark_invalid(&pool_tx, &InvalidPoolTransactionError::other(err));
+            continue
+        }
+
         // convert tx to a signed transaction
         let tx = pool_tx.to_consensus();
 
@@ -462,3 +571,49 @@ where
 
     Ok(BuildOutcome::Better { payload, cached_reads })
 }
+
+/// Checks the storage of the known accounts of a conditional against the state of the block that
+/// is being built, which includes the changes of the transactions that were already executed.
+fn validate_known_accounts_in_block<DB>(
+    conditional: &TransactionConditional,
+    db: &mut State<DB>,
+    parent: &dyn StateProvider,
+) -> Result<(), TransactionConditionalError>
+where
+    DB: Database<Error = ProviderError>,
+{
+    for (address, storage) in &conditional.known_accounts {
+        let matches = match storage {
+            AccountStorage::RootHash(root) => {
+                // the storage root of the parent with the storage of this block applied on top
+                let hashed_storage = db
+                    .cache
+                    .accounts
+                    .get(address)
+                    .map(|account| {
+                        HashedStorage::from_plain_storage(
+                            account.status,
+                            account.account.iter().flat_map(|account| &account.storage),
+                        )
+                    })
+                    .unwrap_or_else(|| HashedStorage::new(false));
+                parent.storage_root(*address, hashed_storage)? == *root
+            }
+            AccountStorage::Slots(slots) => {
+                let mut matches = true;
+                for (slot, value) in slots {
+                    let current = db.storage(*address, *slot)?;
+                    if current != U256::from_be_bytes(value.0) {
+                        matches = false;
+                        break
+                    }
+                }
+                matches
+            }
+        };
+        if !matches {
+            return Err(TransactionConditionalError::KnownAccountMismatch(*address))
+        }
+    }
+    Ok(())
+}
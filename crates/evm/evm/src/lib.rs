@@ -48,6 +48,8 @@ pub use engine::{ConfigureEngineEvm, ConvertTx, ExecutableTxIterator, Executable
 #[cfg(feature = "metrics")]
 pub mod metrics;
 pub mod noop;
+#[cfg(feature = "std")]
+pub mod state_overrides;
 #[cfg(any(test, feature = "test-utils"))]
 /// test helpers for mocking executor
 pub mod test_utils;
@@ -264,6 +266,15 @@ pub trait ConfigureEvm: Clone + Debug + Send + Sync + Unpin {
         self.block_executor_factory().evm_factory()
     }
 
+    /// Returns the state overrides that are applied before a child of an overridden block is
+    /// executed, if supported.
+    ///
+    /// Dev chains use these to modify accounts between blocks.
+    #[cfg(feature = "std")]
+    fn state_overrides(&self) -> Option<&state_overrides::BlockStateOverrides> {
+        None
+    }
+
     /// Returns a new EVM with the given database configured with the given environment settings,
     /// including the spec id and transaction environment.
     ///
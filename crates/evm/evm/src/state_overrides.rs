@@ -0,0 +1,263 @@
+//! State overrides applied on top of a parent block before its child is executed.
+//!
+//! Dev chains use these to change balances, nonces, code and storage between blocks. The
+//! overrides are keyed by the parent block and applied by every executor that builds or validates
+//! a child of that parent, so the payload builder and the engine tree agree on the resulting state.
+
+use alloc::{boxed::Box, sync::Arc};
+use alloy_eips::BlockNumHash;
+use alloy_evm::{
+    block::{
+        BlockExecutionError, BlockExecutionResult, BlockExecutor, ExecutableTx, GasOutput,
+        OnStateHook,
+    },
+    Evm,
+};
+use alloy_primitives::{
+    map::{AddressMap, B256Map},
+    BlockNumber, Bytes, B256, U256,
+};
+use parking_lot::RwLock;
+use revm::{
+    bytecode::Bytecode,
+    state::{Account, EvmStorageSlot},
+    Database, DatabaseCommit,
+};
+
+/// Changes to a single account.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccountStateOverride {
+    /// Replaces the balance of the account.
+    pub balance: Option<U256>,
+    /// Replaces the nonce of the account.
+    pub nonce: Option<u64>,
+    /// Replaces the code of the account.
+    pub code: Option<Bytes>,
+    /// Storage slots to overwrite. Slots that are not listed keep their value.
+    pub storage: B256Map<B256>,
+}
+
+impl AccountStateOverride {
+    /// Merges `other` into `self`, with the values of `other` taking precedence.
+    pub fn merge(&mut self, other: Self) {
+        let Self { balance, nonce, code, storage } = other;
+        if balance.is_some() {
+            self.balance = balance;
+        }
+        if nonce.is_some() {
+            self.nonce = nonce;
+        }
+        if code.is_some() {
+            self.code = code;
+        }
+        self.storage.extend(storage);
+    }
+}
+
+/// Overrides of a parent block, together with its number.
+type ParentOverrides = (BlockNumber, AddressMap<AccountStateOverride>);
+
+/// A shared set of state overrides, keyed by the hash of the parent block they are applied on top
+/// of.
+///
+/// Cloning this type is cheap, all clones share the same overrides.
+#[derive(Debug, Clone, Default)]
+pub struct BlockStateOverrides {
+    inner: Arc<RwLock<B256Map<ParentOverrides>>>,
+}
+
+impl BlockStateOverrides {
+    /// Adds overrides to apply before executing any child of `parent`.
+    ///
+    /// Overrides that were already registered for `parent` are merged with the new ones.
+    pub fn insert(&self, parent: BlockNumHash, overrides: AddressMap<AccountStateOverride>) {
+        let mut inner = self.inner.write();
+        let (_, accounts) =
+            inner.entry(parent.hash).or_insert_with(|| (parent.number, Default::default()));
+        for (address, account) in overrides {
+            accounts.entry(address).or_default().merge(account);
+        }
+    }
+
+    /// Drops the overrides of all parents at or above the given block number.
+    ///
+    /// This must be called when the chain is unwound to `number`, so blocks built on top of it
+    /// again don't pick up overrides that belonged to the discarded blocks.
+    pub fn remove_from(&self, number: BlockNumber) {
+        self.inner.write().retain(|_, (parent_number, _)| *parent_number < number);
+    }
+
+    /// Returns `true` if there are no overrides.
+    pub fn is_empty(&self) -> bool {
+        self.inner.read().is_empty()
+    }
+
+    /// Commits the overrides registered for `parent_hash` to the given database.
+    ///
+    /// The changes are committed as regular state transitions, so they end up in the bundle state
+    /// and in the state root of the executed block.
+    pub fn apply<DB: Database + DatabaseCommit>(
+        &self,
+        parent_hash: B256,
+        db: &mut DB,
+    ) -> Result<(), DB::Error> {
+        let inner = self.inner.read();
+        let Some((_, accounts)) = inner.get(&parent_hash) else { return Ok(()) };
+
+        let mut changes = AddressMap::default();
+        for (address, account_override) in accounts {
+            let mut info = db.basic(*address)?.unwrap_or_default();
+            if let Some(balance) = account_override.balance {
+                info.balance = balance;
+            }
+            if let Some(nonce) = account_override.nonce {
+                info.nonce = nonce;
+            }
+            if let Some(code) = &account_override.code {
+                info.set_code(Bytecode::new_raw(code.clone()));
+            }
+
+            let mut account = Account::from(info);
+            for (slot, value) in &account_override.storage {
+                let slot = U256::from_be_bytes(slot.0);
+                let original = db.storage(*address, slot)?;
+                account
+                    .storage
+                    .insert(slot, EvmStorageSlot::new_changed(original, (*value).into(), 0));
+            }
+            // Storage of an empty account would be discarded by the EIP-161 state clear, unless
+            // the account is marked as created.
+            if account.info.is_empty() && !account.storage.is_empty() {
+                account.mark_created();
+            }
+            account.mark_touch();
+            changes.insert(*address, account);
+        }
+        drop(inner);
+
+        db.commit(changes);
+        Ok(())
+    }
+}
+
+/// A [`BlockExecutor`] that commits the [`BlockStateOverrides`] of the parent block before the
+/// pre-execution changes of the wrapped executor.
+///
+/// Without overrides this behaves exactly like the wrapped executor.
+#[derive(Debug)]
+pub struct StateOverridesExecutor<'a, E> {
+    inner: E,
+    overrides: Option<&'a BlockStateOverrides>,
+    parent_hash: B256,
+}
+
+impl<'a, E> StateOverridesExecutor<'a, E> {
+    /// Wraps `inner`, which executes a child of `parent_hash`.
+    pub const fn new(
+        inner: E,
+        overrides: Option<&'a BlockStateOverrides>,
+        parent_hash: B256,
+    ) -> Self {
+        Self { inner, overrides, parent_hash }
+    }
+}
+
+impl<E> BlockExecutor for StateOverridesExecutor<'_, E>
+where
+    E: BlockExecutor,
+    <E::Evm as Evm>::DB: DatabaseCommit,
+{
+    type Transaction = E::Transaction;
+    type Receipt = E::Receipt;
+    type Evm = E::Evm;
+    type Result = E::Result;
+
+    fn apply_pre_execution_changes(&mut self) -> Result<(), BlockExecutionError> {
+        if let Some(overrides) = self.overrides {
+            overrides
+                .apply(self.parent_hash, self.inner.evm_mut().db_mut())
+                .map_err(BlockExecutionError::other)?;
+        }
+        self.inner.apply_pre_execution_changes()
+    }
+
+    fn execute_transaction_without_commit(
+        &mut self,
+        tx: impl ExecutableTx<Self>,
+    ) -> Result<Self::Result, BlockExecutionError> {
+        self.inner.execute_transaction_without_commit(tx)
+    }
+
+    fn commit_transaction(
+        &mut self,
+        output: Self::Result,
+    ) -> Result<GasOutput, BlockExecutionError> {
+        self.inner.commit_transaction(output)
+    }
+
+    fn finish(
+        self,
+    ) -> Result<(Self::Evm, BlockExecutionResult<Self::Receipt>), BlockExecutionError> {
+        self.inner.finish()
+    }
+
+    fn set_state_hook(&mut self, hook: Option<Box<dyn OnStateHook>>) {
+        self.inner.set_state_hook(hook)
+    }
+
+    fn evm_mut(&mut self) -> &mut Self::Evm {
+        self.inner.evm_mut()
+    }
+
+    fn evm(&self) -> &Self::Evm {
+        self.inner.evm()
+    }
+
+    fn receipts(&self) -> &[Self::Receipt] {
+        self.inner.receipts()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, bytes};
+    use revm::database::{CacheDB, EmptyDB, State};
+
+    #[test]
+    fn applies_overrides_of_parent() {
+        let parent = BlockNumHash::new(1, B256::with_last_byte(1));
+        let address = address!("0x2260FAC5E5542a773Aa44fBCfeDf7C193bc2C599");
+        let slot = b256!("0x0000000000000000000000000000000000000000000000000000000000000001");
+        let value = b256!("0x00000000000000000000000000000000000000000000000000000000000000ff");
+
+        let overrides = BlockStateOverrides::default();
+        overrides.insert(
+            parent,
+            AddressMap::from_iter([(
+                address,
+                AccountStateOverride {
+                    balance: Some(U256::from(10)),
+                    code: Some(bytes!("0x6000")),
+                    storage: B256Map::from_iter([(slot, value)]),
+                    ..Default::default()
+                },
+            )]),
+        );
+
+        let mut db = State::builder().with_database(CacheDB::new(EmptyDB::new())).build();
+
+        // overrides of other parents are not applied
+        overrides.apply(B256::ZERO, &mut db).unwrap();
+        assert_eq!(db.basic(address).unwrap(), None);
+
+        overrides.apply(parent.hash, &mut db).unwrap();
+        let account = db.basic(address).unwrap().unwrap();
+        assert_eq!(account.balance, U256::from(10));
+        assert!(account.code.is_some());
+        assert_eq!(db.storage(address, U256::from(1)).unwrap(), U256::from(0xff));
+
+        overrides.remove_from(parent.number);
+        assert!(overrides.is_empty());
+    }
+}
@@ -387,6 +387,22 @@ impl Discv4 {
         self.send_to_service(cmd);
     }
 
+    /// Sets the udp port advertised to other nodes
+    ///
+    /// This will update our [`NodeRecord`]'s udp port, the socket stays bound to its local port.
+    pub fn set_udp_port(&self, port: u16) {
+        let cmd = Discv4Command::SetUdpPort(port);
+        self.send_to_service(cmd);
+    }
+
+    /// Sets the external IP
+    ///
+    /// This will update our [`NodeRecord`]'s address and the IP of the EIP-868 [`Enr`].
+    pub fn set_external_ip_addr(&self, external_ip: IpAddr) {
+        let cmd = Discv4Command::SetExternalIp(external_ip);
+        self.send_to_service(cmd);
+    }
+
     /// Sets the pair in the EIP-868 [`Enr`] of the node.
     ///
     /// If the key already exists, this will update it.
@@ -1808,6 +1824,23 @@ impl Discv4Service {
                         }
                     }
 
+                    Discv4Command::SetUdpPort(port) => {
+                        if self.local_node_record.udp_port != port {
+                            debug!(target: "discv4", %port, "Update udp port");
+                            self.local_node_record.udp_port = port;
+                            if self.local_node_record.address.is_ipv4() {
+                                let _ = self.local_eip_868_enr.set_udp4(port, &self.secret_key);
+                            } else {
+                                let _ = self.local_eip_868_enr.set_udp6(port, &self.secret_key);
+                            }
+                            *self.shared_node_record.lock() = self.local_node_record;
+                        }
+                    }
+
+                    Discv4Command::SetExternalIp(external_ip) => {
+                        self.set_external_ip_addr(external_ip);
+                    }
+
                     Discv4Command::Terminated => {
                         // terminate the service
                         self.queued_events.push_back(Discv4Event::Terminated);
@@ -2089,6 +2122,8 @@ enum Discv4Command {
     Add(NodeRecord),
     AddBootNode(NodeRecord),
     SetTcpPort(u16),
+    SetUdpPort(u16),
+    SetExternalIp(IpAddr),
     SetEIP868RLPPair { key: Vec<u8>, rlp: Bytes },
     Ban(PeerId, IpAddr),
     BanPeer(PeerId),
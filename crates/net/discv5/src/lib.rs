@@ -93,6 +93,17 @@ impl Discv5 {
         self.discv5.add_enr(enr).map_err(Error::AddNodeFailed)
     }
 
+    /// Sets the external IP and ports of the node in the local [`Enr`].
+    ///
+    /// Ports that are `None` are advertised as the local UDP and TCP ports.
+    pub fn set_external_addr(&self, ip: IpAddr, udp_port: Option<u16>, tcp_port: Option<u16>) {
+        let udp_port = udp_port.unwrap_or(self.local_node_record.udp_port);
+        let tcp_port = tcp_port.unwrap_or(self.local_node_record.tcp_port);
+        let udp_updated = self.discv5.update_local_enr_socket((ip, udp_port).into(), false);
+        let tcp_updated = self.discv5.update_local_enr_socket((ip, tcp_port).into(), true);
+        debug!(target: "net::discv5", %ip, udp_port, tcp_port, udp_updated, tcp_updated, "updated external address in local enr");
+    }
+
     /// Sets the pair in the EIP-868 [`Enr`] of the node.
     ///
     /// If the key already exists, this will update it.
@@ -1,4 +1,4 @@
-//! Helpers for resolving the external IP.
+//! Helpers for resolving the external IP and mapping ports on the gateway of the local network.
 //!
 //! ## Feature Flags
 //!
@@ -12,9 +12,14 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+pub mod natpmp;
 pub mod net_if;
+pub mod port_mapping;
 
 pub use net_if::{NetInterfaceError, DEFAULT_NET_IF_NAME};
+pub use port_mapping::{
+    MappedPorts, PortMapper, PortMapping, PortMappingError, PortMappingProtocol,
+};
 
 use std::{
     fmt,
@@ -27,7 +32,7 @@ use std::{
 };
 use tracing::debug;
 
-use crate::net_if::resolve_net_if_ip;
+use crate::{net_if::resolve_net_if_ip, port_mapping::gateway_external_ip};
 #[cfg(feature = "serde")]
 use serde_with::{DeserializeFromStr, SerializeDisplay};
 
@@ -44,8 +49,14 @@ pub enum NatResolver {
     /// Resolve with any available resolver.
     #[default]
     Any,
-    /// Resolve external IP via `UPnP`.
+    /// Map ports on the gateway via `UPnP` IGD and use its external IP.
+    ///
+    /// Falls back to [`NatResolver::PublicIp`] if no gateway answers.
     Upnp,
+    /// Map ports on the gateway via NAT-PMP and use its external IP.
+    ///
+    /// Falls back to [`NatResolver::PublicIp`] if no gateway answers.
+    NatPmp,
     /// Resolve external IP via a network request.
     PublicIp,
     /// Use the given [`IpAddr`]
@@ -70,6 +81,12 @@ impl NatResolver {
         external_addr_with(self).await
     }
 
+    /// Returns `true` if this resolver maps ports on the gateway, see
+    /// [`PortMapper`](crate::PortMapper).
+    pub const fn supports_port_mapping(&self) -> bool {
+        matches!(self, Self::Upnp | Self::NatPmp)
+    }
+
     /// Returns the fixed ip, if it is [`NatResolver::ExternalIp`] or [`NatResolver::ExternalAddr`].
     ///
     /// In the case of [`NatResolver::ExternalAddr`], it will return the first IP address found for
@@ -91,6 +108,7 @@ impl fmt::Display for NatResolver {
         match self {
             Self::Any => f.write_str("any"),
             Self::Upnp => f.write_str("upnp"),
+            Self::NatPmp => f.write_str("natpmp"),
             Self::PublicIp => f.write_str("publicip"),
             Self::ExternalIp(ip) => write!(f, "extip:{ip}"),
             Self::ExternalAddr(domain) => write!(f, "extaddr:{domain}"),
@@ -118,6 +136,7 @@ impl FromStr for NatResolver {
         let r = match s {
             "any" => Self::Any,
             "upnp" => Self::Upnp,
+            "natpmp" | "nat-pmp" => Self::NatPmp,
             "none" => Self::None,
             "publicip" | "public-ip" => Self::PublicIp,
             "netif" => Self::NetIf,
@@ -221,7 +240,11 @@ pub async fn external_ip() -> Option<IpAddr> {
 /// Given a [`NatResolver`] attempts to produce an IP address (best effort).
 pub async fn external_addr_with(resolver: NatResolver) -> Option<IpAddr> {
     match resolver {
-        NatResolver::Any | NatResolver::Upnp | NatResolver::PublicIp => resolve_external_ip().await,
+        NatResolver::Any | NatResolver::PublicIp => resolve_external_ip().await,
+        NatResolver::Upnp | NatResolver::NatPmp => match gateway_external_ip(&resolver).await {
+            Some(ip) => Some(ip),
+            None => resolve_external_ip().await,
+        },
         NatResolver::ExternalIp(ip) => Some(ip),
         NatResolver::NetIf => resolve_net_if_ip(DEFAULT_NET_IF_NAME)
             .inspect_err(|err| {
@@ -310,6 +333,10 @@ mod tests {
         assert_eq!(NatResolver::Any, "any".parse().unwrap());
         assert_eq!(NatResolver::None, "none".parse().unwrap());
 
+        assert_eq!(NatResolver::Upnp, "upnp".parse().unwrap());
+        assert_eq!(NatResolver::NatPmp, "natpmp".parse().unwrap());
+        assert_eq!(NatResolver::NatPmp.to_string(), "natpmp");
+
         let ip = NatResolver::ExternalIp(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
         let s = "extip:0.0.0.0";
         assert_eq!(ip, s.parse().unwrap());
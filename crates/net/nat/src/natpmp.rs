@@ -0,0 +1,286 @@
+//! Minimal NAT-PMP client, see [RFC 6886](https://www.rfc-editor.org/rfc/rfc6886).
+
+use crate::port_mapping::PortMappingProtocol;
+use if_addrs::IfAddr;
+use std::{
+    io,
+    net::{Ipv4Addr, SocketAddrV4},
+    time::Duration,
+};
+use tokio::net::UdpSocket;
+
+/// The port NAT-PMP gateways listen on.
+pub const NATPMP_PORT: u16 = 5351;
+
+/// Opcode of the external address request.
+const OP_EXTERNAL_ADDRESS: u8 = 0;
+
+/// Added to the request opcode in responses.
+const OP_RESPONSE: u8 = 128;
+
+/// Time to wait for the first response, doubled on every retransmission.
+const INITIAL_TIMEOUT: Duration = Duration::from_millis(250);
+
+/// Number of times a request is sent before giving up.
+const MAX_ATTEMPTS: u32 = 5;
+
+/// Errors of NAT-PMP requests.
+#[derive(Debug, thiserror::Error)]
+pub enum NatPmpError {
+    /// Failed to talk to the gateway.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// The gateway did not answer.
+    #[error("no response from NAT-PMP gateway {0}")]
+    Timeout(Ipv4Addr),
+    /// The gateway answered with a non-zero result code.
+    #[error("NAT-PMP gateway returned result code {0}")]
+    ResultCode(u16),
+}
+
+/// A NAT-PMP gateway.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NatPmpGateway {
+    addr: Ipv4Addr,
+    port: u16,
+}
+
+impl NatPmpGateway {
+    /// Creates a new [`NatPmpGateway`] at the given address, listening on [`NATPMP_PORT`].
+    pub const fn new(addr: Ipv4Addr) -> Self {
+        Self { addr, port: NATPMP_PORT }
+    }
+
+    /// Sets the port the gateway listens on.
+    pub const fn with_port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Returns the address of the gateway.
+    pub const fn addr(&self) -> Ipv4Addr {
+        self.addr
+    }
+
+    /// Returns the socket address requests are sent to.
+    pub const fn socket_addr(&self) -> SocketAddrV4 {
+        SocketAddrV4::new(self.addr, self.port)
+    }
+
+    /// Requests the external address of the gateway.
+    pub async fn external_addr(&self) -> Result<Ipv4Addr, NatPmpError> {
+        let response = self.request(&[0, OP_EXTERNAL_ADDRESS], OP_EXTERNAL_ADDRESS, 12).await?;
+        Ok(Ipv4Addr::new(response[8], response[9], response[10], response[11]))
+    }
+
+    /// Requests a mapping of `internal_port` to `external_port` for `lifetime`.
+    ///
+    /// Returns the external port and the lifetime the gateway granted, which can differ from the
+    /// requested ones. A `lifetime` of zero removes the mapping.
+    pub async fn add_mapping(
+        &self,
+        protocol: PortMappingProtocol,
+        internal_port: u16,
+        external_port: u16,
+        lifetime: Duration,
+    ) -> Result<(u16, Duration), NatPmpError> {
+        let request = encode_mapping_request(protocol, internal_port, external_port, lifetime);
+        let response = self.request(&request, request[1], 16).await?;
+        Ok(decode_mapping_response(&response))
+    }
+
+    /// Removes the mapping of `internal_port`.
+    pub async fn remove_mapping(
+        &self,
+        protocol: PortMappingProtocol,
+        internal_port: u16,
+    ) -> Result<(), NatPmpError> {
+        self.add_mapping(protocol, internal_port, 0, Duration::ZERO).await.map(drop)
+    }
+
+    /// Sends the request until the gateway answers it, and returns the response.
+    async fn request(
+        &self,
+        request: &[u8],
+        op: u8,
+        response_len: usize,
+    ) -> Result<[u8; 16], NatPmpError> {
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).await?;
+        socket.connect(self.socket_addr()).await?;
+
+        let mut buf = [0u8; 16];
+        let mut timeout = INITIAL_TIMEOUT;
+        for _ in 0..MAX_ATTEMPTS {
+            socket.send(request).await?;
+
+            let recv = async {
+                loop {
+                    let len = socket.recv(&mut buf).await?;
+                    // skip anything that doesn't answer this request
+                    if len >= response_len && buf[0] == 0 && buf[1] == op + OP_RESPONSE {
+                        return Ok::<_, io::Error>(())
+                    }
+                }
+            };
+            let received = tokio::time::timeout(timeout, recv).await;
+            match received {
+                Ok(res) => {
+                    res?;
+                    let result_code = u16::from_be_bytes([buf[2], buf[3]]);
+                    if result_code != 0 {
+                        return Err(NatPmpError::ResultCode(result_code))
+                    }
+                    return Ok(buf)
+                }
+                Err(_) => timeout *= 2,
+            }
+        }
+
+        Err(NatPmpError::Timeout(self.addr))
+    }
+}
+
+/// Returns the opcode of mapping requests for the protocol.
+const fn mapping_opcode(protocol: PortMappingProtocol) -> u8 {
+    match protocol {
+        PortMappingProtocol::Udp => 1,
+        PortMappingProtocol::Tcp => 2,
+    }
+}
+
+/// Encodes a request to map `internal_port` to `external_port` for `lifetime`.
+fn encode_mapping_request(
+    protocol: PortMappingProtocol,
+    internal_port: u16,
+    external_port: u16,
+    lifetime: Duration,
+) -> [u8; 12] {
+    let lifetime = u32::try_from(lifetime.as_secs()).unwrap_or(u32::MAX);
+
+    let mut request = [0u8; 12];
+    request[1] = mapping_opcode(protocol);
+    request[4..6].copy_from_slice(&internal_port.to_be_bytes());
+    request[6..8].copy_from_slice(&external_port.to_be_bytes());
+    request[8..12].copy_from_slice(&lifetime.to_be_bytes());
+    request
+}
+
+/// Decodes the external port and the lifetime granted in a successful mapping response.
+fn decode_mapping_response(response: &[u8; 16]) -> (u16, Duration) {
+    let external_port = u16::from_be_bytes([response[10], response[11]]);
+    let lifetime = u32::from_be_bytes([response[12], response[13], response[14], response[15]]);
+    (external_port, Duration::from_secs(lifetime.into()))
+}
+
+/// Returns the likely NAT-PMP gateways of the host.
+///
+/// NAT-PMP has no discovery, the gateway is the default router. This guesses it as the first
+/// address of every private IPv4 network the host is attached to.
+pub fn guess_gateways() -> Vec<NatPmpGateway> {
+    let mut gateways = Vec::new();
+    for interface in if_addrs::get_if_addrs().unwrap_or_default() {
+        if let IfAddr::V4(addr) = interface.addr &&
+            !addr.is_loopback() &&
+            addr.ip.is_private()
+        {
+            let network = u32::from(addr.ip) & u32::from(addr.netmask);
+            let gateway = NatPmpGateway::new(Ipv4Addr::from(network | 1));
+            if gateway.addr != addr.ip && !gateways.contains(&gateway) {
+                gateways.push(gateway);
+            }
+        }
+    }
+    gateways
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    /// The longest lifetime granted by [`spawn_test_gateway`].
+    pub(crate) const TEST_GATEWAY_MAX_LIFETIME: u32 = 120;
+
+    /// Spawns a NAT-PMP gateway on the loopback interface that reports `external_ip` and assigns
+    /// `internal_port + port_offset` to every mapping, for at most
+    /// [`TEST_GATEWAY_MAX_LIFETIME`] seconds.
+    ///
+    /// Returns the gateway and the requests it received.
+    pub(crate) async fn spawn_test_gateway(
+        external_ip: Ipv4Addr,
+        port_offset: u16,
+    ) -> (NatPmpGateway, Arc<Mutex<Vec<Vec<u8>>>>) {
+        let socket = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        let gateway =
+            NatPmpGateway::new(Ipv4Addr::LOCALHOST).with_port(socket.local_addr().unwrap().port());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let received = requests.clone();
+        tokio::spawn(async move {
+            let mut buf = [0u8; 12];
+            loop {
+                let Ok((len, from)) = socket.recv_from(&mut buf).await else { return };
+                let request = &buf[..len];
+                received.lock().unwrap().push(request.to_vec());
+
+                let op = request[1];
+                let mut response = vec![0, op + OP_RESPONSE, 0, 0, 0, 0, 0, 1];
+                if op == OP_EXTERNAL_ADDRESS {
+                    response.extend_from_slice(&external_ip.octets());
+                } else {
+                    let internal_port = u16::from_be_bytes([request[4], request[5]]);
+                    let lifetime =
+                        u32::from_be_bytes([request[8], request[9], request[10], request[11]])
+                            .min(TEST_GATEWAY_MAX_LIFETIME);
+                    let external_port = if lifetime == 0 { 0 } else { internal_port + port_offset };
+                    response.extend_from_slice(&internal_port.to_be_bytes());
+                    response.extend_from_slice(&external_port.to_be_bytes());
+                    response.extend_from_slice(&lifetime.to_be_bytes());
+                }
+                let _ = socket.send_to(&response, from).await;
+            }
+        });
+
+        (gateway, requests)
+    }
+
+    #[test]
+    fn encodes_mapping_request() {
+        let request = encode_mapping_request(
+            PortMappingProtocol::Tcp,
+            30303,
+            30304,
+            Duration::from_secs(3600),
+        );
+        assert_eq!(request, [0, 2, 0, 0, 0x76, 0x5f, 0x76, 0x60, 0, 0, 0x0e, 0x10]);
+
+        let request = encode_mapping_request(PortMappingProtocol::Udp, 30303, 0, Duration::ZERO);
+        assert_eq!(request, [0, 1, 0, 0, 0x76, 0x5f, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn decodes_mapping_response() {
+        let response = [0, 130, 0, 0, 0, 0, 0, 1, 0x76, 0x5f, 0x76, 0x60, 0, 0, 0x1c, 0x20];
+        assert_eq!(decode_mapping_response(&response), (30304, Duration::from_secs(7200)));
+    }
+
+    #[tokio::test]
+    async fn requests_external_addr_and_mapping() {
+        let external_ip = Ipv4Addr::new(203, 0, 113, 7);
+        let (gateway, requests) = spawn_test_gateway(external_ip, 1).await;
+
+        assert_eq!(gateway.external_addr().await.unwrap(), external_ip);
+
+        let lifetime = Duration::from_secs(3600);
+        let mapped =
+            gateway.add_mapping(PortMappingProtocol::Udp, 30303, 30303, lifetime).await.unwrap();
+        assert_eq!(mapped, (30304, Duration::from_secs(TEST_GATEWAY_MAX_LIFETIME.into())),);
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0], [0, OP_EXTERNAL_ADDRESS]);
+        assert_eq!(
+            requests[1],
+            encode_mapping_request(PortMappingProtocol::Udp, 30303, 30303, lifetime)
+        );
+    }
+}
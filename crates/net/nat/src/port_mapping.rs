@@ -0,0 +1,506 @@
+//! Port mappings on the gateway of the local network, via `UPnP` IGD or NAT-PMP.
+
+use crate::{
+    natpmp::{guess_gateways, NatPmpError, NatPmpGateway},
+    NatResolver,
+};
+use futures_util::future::{select, Either};
+use igd_next::{
+    aio::{
+        tokio::{search_gateway, Tokio},
+        Gateway,
+    },
+    AddPortError, GetExternalIpError, RemovePortError, SearchError, SearchOptions,
+};
+use std::{
+    fmt,
+    future::Future,
+    io,
+    net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket},
+    pin::pin,
+    time::Duration,
+};
+use tracing::{debug, info, warn};
+
+/// The lifetime requested for port mappings.
+///
+/// Mappings are renewed after half of the lifetime granted by the gateway has passed.
+pub const DEFAULT_PORT_MAPPING_LEASE: Duration = Duration::from_secs(60 * 60);
+
+/// How long to wait before retrying after the ports could not be mapped.
+const RETRY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The description of the `UPnP` port mappings.
+const UPNP_DESCRIPTION: &str = "reth";
+
+/// The transport protocol of a port mapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PortMappingProtocol {
+    /// TCP, used by `RLPx`.
+    Tcp,
+    /// UDP, used by discovery.
+    Udp,
+}
+
+impl fmt::Display for PortMappingProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp => f.write_str("TCP"),
+            Self::Udp => f.write_str("UDP"),
+        }
+    }
+}
+
+impl From<PortMappingProtocol> for igd_next::PortMappingProtocol {
+    fn from(protocol: PortMappingProtocol) -> Self {
+        match protocol {
+            PortMappingProtocol::Tcp => Self::TCP,
+            PortMappingProtocol::Udp => Self::UDP,
+        }
+    }
+}
+
+/// A local port mapped on the gateway.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PortMapping {
+    /// The transport protocol of the mapping.
+    pub protocol: PortMappingProtocol,
+    /// The local port.
+    pub local_port: u16,
+    /// The external port assigned by the gateway.
+    pub external_port: u16,
+}
+
+/// The external endpoint of the node after its ports have been mapped on the gateway.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MappedPorts {
+    /// The external IP of the gateway.
+    pub external_ip: IpAddr,
+    /// The ports mapped on the gateway.
+    pub mappings: Vec<PortMapping>,
+}
+
+impl MappedPorts {
+    /// Returns the external port the given local port is mapped to, if it is mapped.
+    pub fn external_port(&self, protocol: PortMappingProtocol, local_port: u16) -> Option<u16> {
+        self.mappings
+            .iter()
+            .find(|mapping| mapping.protocol == protocol && mapping.local_port == local_port)
+            .map(|mapping| mapping.external_port)
+    }
+}
+
+/// Errors when mapping ports on the gateway.
+#[derive(Debug, thiserror::Error)]
+pub enum PortMappingError {
+    /// No `UPnP` gateway answered the search.
+    #[error("failed to find UPnP gateway: {0}")]
+    UpnpSearch(#[from] SearchError),
+    /// The `UPnP` gateway did not return its external IP.
+    #[error("failed to get external IP from UPnP gateway: {0}")]
+    UpnpExternalIp(#[from] GetExternalIpError),
+    /// The `UPnP` gateway refused the port mapping.
+    #[error("failed to add UPnP port mapping: {0}")]
+    UpnpAddPort(#[from] AddPortError),
+    /// The `UPnP` gateway did not remove the port mapping.
+    #[error("failed to remove UPnP port mapping: {0}")]
+    UpnpRemovePort(#[from] RemovePortError),
+    /// None of the guessed NAT-PMP gateways answered.
+    #[error("no NAT-PMP gateway found")]
+    NoNatPmpGateway,
+    /// The NAT-PMP request failed.
+    #[error(transparent)]
+    NatPmp(#[from] NatPmpError),
+    /// Failed to determine the local address facing the gateway.
+    #[error("failed to determine local address: {0}")]
+    LocalAddr(#[from] io::Error),
+}
+
+/// The protocol used to talk to the gateway.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GatewayKind {
+    Upnp,
+    NatPmp,
+}
+
+impl GatewayKind {
+    /// Returns the gateway protocol of the resolver, if it maps ports.
+    const fn from_resolver(resolver: &NatResolver) -> Option<Self> {
+        match resolver {
+            NatResolver::Upnp => Some(Self::Upnp),
+            NatResolver::NatPmp => Some(Self::NatPmp),
+            _ => None,
+        }
+    }
+}
+
+/// A gateway that supports port mappings.
+#[derive(Debug)]
+enum MappingGateway {
+    Upnp(Gateway<Tokio>),
+    NatPmp(NatPmpGateway),
+}
+
+impl MappingGateway {
+    /// Finds the gateway of the local network.
+    async fn discover(kind: GatewayKind) -> Result<Self, PortMappingError> {
+        match kind {
+            GatewayKind::Upnp => Ok(Self::Upnp(search_gateway(SearchOptions::default()).await?)),
+            GatewayKind::NatPmp => {
+                for gateway in guess_gateways() {
+                    match gateway.external_addr().await {
+                        Ok(_) => return Ok(Self::NatPmp(gateway)),
+                        Err(err) => {
+                            debug!(target: "net::nat", gateway=%gateway.addr(), %err, "No NAT-PMP gateway")
+                        }
+                    }
+                }
+                Err(PortMappingError::NoNatPmpGateway)
+            }
+        }
+    }
+
+    /// Returns the external IP of the gateway.
+    async fn external_ip(&self) -> Result<IpAddr, PortMappingError> {
+        match self {
+            Self::Upnp(gateway) => Ok(gateway.get_external_ip().await?),
+            Self::NatPmp(gateway) => Ok(gateway.external_addr().await?.into()),
+        }
+    }
+
+    /// Maps an external port to `local_addr`.
+    ///
+    /// `UPnP` gateways map the external port of the same number. NAT-PMP gateways are asked for
+    /// `external_port`, but may assign a different one.
+    ///
+    /// Returns the external port and the lifetime granted by the gateway.
+    async fn add_port(
+        &self,
+        protocol: PortMappingProtocol,
+        local_addr: SocketAddr,
+        external_port: u16,
+        lease: Duration,
+    ) -> Result<(u16, Duration), PortMappingError> {
+        let port = local_addr.port();
+        match self {
+            Self::Upnp(gateway) => {
+                let mut local_addr = local_addr;
+                if local_addr.ip().is_unspecified() {
+                    local_addr.set_ip(local_ip_towards(gateway.addr)?);
+                }
+                let lease_secs = u32::try_from(lease.as_secs()).unwrap_or(u32::MAX);
+                match gateway
+                    .add_port(protocol.into(), port, local_addr, lease_secs, UPNP_DESCRIPTION)
+                    .await
+                {
+                    Err(AddPortError::OnlyPermanentLeasesSupported) => {
+                        gateway
+                            .add_port(protocol.into(), port, local_addr, 0, UPNP_DESCRIPTION)
+                            .await?;
+                    }
+                    res => res?,
+                }
+                Ok((port, lease))
+            }
+            Self::NatPmp(gateway) => {
+                Ok(gateway.add_mapping(protocol, port, external_port, lease).await?)
+            }
+        }
+    }
+
+    /// Removes the mapping.
+    async fn remove_port(&self, mapping: PortMapping) -> Result<(), PortMappingError> {
+        let PortMapping { protocol, local_port, external_port } = mapping;
+        match self {
+            Self::Upnp(gateway) => Ok(gateway.remove_port(protocol.into(), external_port).await?),
+            Self::NatPmp(gateway) => Ok(gateway.remove_mapping(protocol, local_port).await?),
+        }
+    }
+}
+
+/// Returns the local IP the host uses to reach the gateway.
+fn local_ip_towards(gateway: SocketAddr) -> io::Result<IpAddr> {
+    // connecting a UDP socket sends nothing, it only selects the route
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+    socket.connect(gateway)?;
+    Ok(socket.local_addr()?.ip())
+}
+
+/// Maps local ports on the gateway of the local network and keeps the mappings alive.
+///
+/// Local ports are mapped to the external port of the same number if the gateway allows it.
+/// NAT-PMP gateways can assign a different external port, which is reported in [`MappedPorts`]
+/// and requested again when the mapping is renewed.
+#[derive(Debug)]
+pub struct PortMapper {
+    /// The protocol used to talk to the gateway.
+    kind: GatewayKind,
+    /// The local addresses to map.
+    ports: Vec<(PortMappingProtocol, SocketAddr)>,
+    /// The requested lifetime of the mappings.
+    lease: Duration,
+    /// The gateway, once discovered.
+    gateway: Option<MappingGateway>,
+    /// The ports currently mapped on the gateway.
+    mapped: Vec<PortMapping>,
+    /// When to renew the mappings after they have been created.
+    renew_after: Duration,
+}
+
+impl PortMapper {
+    /// Creates a new [`PortMapper`] for the given local addresses.
+    ///
+    /// Returns `None` if the resolver does not support port mappings, see
+    /// [`NatResolver::supports_port_mapping`].
+    pub fn new(
+        resolver: &NatResolver,
+        ports: impl IntoIterator<Item = (PortMappingProtocol, SocketAddr)>,
+    ) -> Option<Self> {
+        let kind = GatewayKind::from_resolver(resolver)?;
+        let mut unique_ports = Vec::new();
+        for port in ports {
+            if !unique_ports.contains(&port) {
+                unique_ports.push(port);
+            }
+        }
+        Some(Self {
+            kind,
+            ports: unique_ports,
+            lease: DEFAULT_PORT_MAPPING_LEASE,
+            gateway: None,
+            mapped: Vec::new(),
+            renew_after: DEFAULT_PORT_MAPPING_LEASE / 2,
+        })
+    }
+
+    /// Sets the lifetime requested for the mappings.
+    pub const fn with_lease(mut self, lease: Duration) -> Self {
+        self.lease = lease;
+        self
+    }
+
+    /// Creates or renews all mappings, discovering the gateway first if necessary.
+    ///
+    /// Returns the external IP of the gateway and the external ports it assigned.
+    pub async fn map_ports(&mut self) -> Result<MappedPorts, PortMappingError> {
+        let gateway = match self.gateway.take() {
+            Some(gateway) => gateway,
+            None => MappingGateway::discover(self.kind).await?,
+        };
+        let mapped = self.map_ports_on(&gateway).await?;
+        // only keep the gateway if it works, so that it's discovered again otherwise
+        self.gateway = Some(gateway);
+        Ok(mapped)
+    }
+
+    async fn map_ports_on(
+        &mut self,
+        gateway: &MappingGateway,
+    ) -> Result<MappedPorts, PortMappingError> {
+        let external_ip = gateway.external_ip().await?;
+        let mut renew_after = self.lease / 2;
+        for &(protocol, local_addr) in &self.ports {
+            let local_port = local_addr.port();
+            let existing = self.mapped.iter().position(|mapping| {
+                mapping.protocol == protocol && mapping.local_port == local_port
+            });
+            // renewals ask for the external port that was assigned before
+            let requested_port = existing.map_or(local_port, |idx| self.mapped[idx].external_port);
+            let (external_port, lifetime) =
+                gateway.add_port(protocol, local_addr, requested_port, self.lease).await?;
+
+            let mapping = PortMapping { protocol, local_port, external_port };
+            match existing {
+                Some(idx) => self.mapped[idx] = mapping,
+                None => {
+                    debug!(target: "net::nat", %protocol, %local_addr, external_port, ?lifetime, "Added port mapping");
+                    self.mapped.push(mapping);
+                }
+            }
+            renew_after = renew_after.min(lifetime / 2);
+        }
+        self.renew_after = renew_after;
+        Ok(MappedPorts { external_ip, mappings: self.mapped.clone() })
+    }
+
+    /// Removes all mappings created by this [`PortMapper`].
+    pub async fn remove_mappings(&mut self) {
+        let Some(gateway) = &self.gateway else { return };
+        for mapping in self.mapped.drain(..) {
+            let PortMapping { protocol, local_port, external_port } = mapping;
+            match gateway.remove_port(mapping).await {
+                Ok(()) => {
+                    debug!(target: "net::nat", %protocol, local_port, external_port, "Removed port mapping")
+                }
+                Err(err) => {
+                    debug!(target: "net::nat", %protocol, local_port, external_port, %err, "Failed to remove port mapping")
+                }
+            }
+        }
+    }
+
+    /// Maps the ports and renews the mappings until `shutdown` resolves, then removes them.
+    ///
+    /// `on_mapped` is called whenever the external IP of the gateway or one of the assigned
+    /// external ports changes. The output of `shutdown` is held until the mappings are removed.
+    pub async fn run_until_shutdown<S, G>(
+        mut self,
+        shutdown: S,
+        mut on_mapped: impl FnMut(&MappedPorts),
+    ) where
+        S: Future<Output = G>,
+    {
+        let mut shutdown = pin!(shutdown);
+        let mut last_mapped = None;
+        let mut failed = false;
+        let guard = loop {
+            let renew_after = match self.map_ports().await {
+                Ok(mapped) => {
+                    failed = false;
+                    if last_mapped.as_ref() != Some(&mapped) {
+                        info!(target: "net::nat", ip=%mapped.external_ip, mappings=?mapped.mappings, "Mapped ports on gateway");
+                        on_mapped(&mapped);
+                        last_mapped = Some(mapped);
+                    }
+                    self.renew_after
+                }
+                Err(err) => {
+                    if failed {
+                        debug!(target: "net::nat", %err, "Failed to map ports on gateway");
+                    } else {
+                        warn!(target: "net::nat", %err, "Failed to map ports on gateway");
+                    }
+                    failed = true;
+                    RETRY_INTERVAL
+                }
+            };
+
+            let sleep = pin!(tokio::time::sleep(renew_after));
+            if let Either::Right((guard, _)) = select(sleep, shutdown.as_mut()).await {
+                break guard
+            }
+        };
+
+        self.remove_mappings().await;
+        drop(guard);
+    }
+}
+
+/// Returns the external IP reported by the gateway of the local network.
+pub(crate) async fn gateway_external_ip(resolver: &NatResolver) -> Option<IpAddr> {
+    let kind = GatewayKind::from_resolver(resolver)?;
+    let res = match MappingGateway::discover(kind).await {
+        Ok(gateway) => gateway.external_ip().await,
+        Err(err) => Err(err),
+    };
+    res.inspect_err(|err| {
+        debug!(target: "net::nat", %err, "Failed to get external IP from gateway");
+    })
+    .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::natpmp::tests::{spawn_test_gateway, TEST_GATEWAY_MAX_LIFETIME};
+
+    /// Returns the internal port, suggested external port and lifetime of a mapping request.
+    fn mapping_request(request: &[u8]) -> (PortMappingProtocol, u16, u16, u32) {
+        let protocol =
+            if request[1] == 1 { PortMappingProtocol::Udp } else { PortMappingProtocol::Tcp };
+        (
+            protocol,
+            u16::from_be_bytes([request[4], request[5]]),
+            u16::from_be_bytes([request[6], request[7]]),
+            u32::from_be_bytes([request[8], request[9], request[10], request[11]]),
+        )
+    }
+
+    #[tokio::test]
+    async fn maps_renews_and_removes_natpmp_ports() {
+        let external_ip = Ipv4Addr::new(203, 0, 113, 7);
+        let (gateway, requests) = spawn_test_gateway(external_ip, 1).await;
+
+        let local_addr: SocketAddr = (Ipv4Addr::UNSPECIFIED, 30303).into();
+        let mut mapper = PortMapper::new(
+            &NatResolver::NatPmp,
+            [(PortMappingProtocol::Tcp, local_addr), (PortMappingProtocol::Udp, local_addr)],
+        )
+        .unwrap();
+        mapper.gateway = Some(MappingGateway::NatPmp(gateway));
+
+        // the gateway assigns different external ports, which are reported
+        let mapped = mapper.map_ports().await.unwrap();
+        assert_eq!(mapped.external_ip, IpAddr::from(external_ip));
+        assert_eq!(mapped.external_port(PortMappingProtocol::Tcp, 30303), Some(30304));
+        assert_eq!(mapped.external_port(PortMappingProtocol::Udp, 30303), Some(30304));
+        assert_eq!(mapped.external_port(PortMappingProtocol::Udp, 9200), None);
+        // mappings are renewed after half of the granted lifetime
+        assert_eq!(mapper.renew_after, Duration::from_secs((TEST_GATEWAY_MAX_LIFETIME / 2).into()));
+
+        let lease = u32::try_from(DEFAULT_PORT_MAPPING_LEASE.as_secs()).unwrap();
+        let mapping_requests = |requests: &[Vec<u8>]| {
+            requests
+                .iter()
+                .filter(|request| request.len() == 12)
+                .map(|request| mapping_request(request))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            mapping_requests(&requests.lock().unwrap()),
+            [
+                (PortMappingProtocol::Tcp, 30303, 30303, lease),
+                (PortMappingProtocol::Udp, 30303, 30303, lease),
+            ]
+        );
+        requests.lock().unwrap().clear();
+
+        // renewals ask for the assigned external ports
+        assert_eq!(mapper.map_ports().await.unwrap(), mapped);
+        assert_eq!(
+            mapping_requests(&requests.lock().unwrap()),
+            [
+                (PortMappingProtocol::Tcp, 30303, 30304, lease),
+                (PortMappingProtocol::Udp, 30303, 30304, lease),
+            ]
+        );
+        requests.lock().unwrap().clear();
+
+        // teardown deletes the mappings of the internal ports
+        mapper.remove_mappings().await;
+        assert!(mapper.mapped.is_empty());
+        assert_eq!(
+            mapping_requests(&requests.lock().unwrap()),
+            [(PortMappingProtocol::Tcp, 30303, 0, 0), (PortMappingProtocol::Udp, 30303, 0, 0)]
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_mapped_ports_until_shutdown() {
+        let external_ip = Ipv4Addr::new(203, 0, 113, 7);
+        let (gateway, requests) = spawn_test_gateway(external_ip, 0).await;
+
+        let local_addr: SocketAddr = (Ipv4Addr::UNSPECIFIED, 30303).into();
+        let mut mapper =
+            PortMapper::new(&NatResolver::NatPmp, [(PortMappingProtocol::Tcp, local_addr)])
+                .unwrap();
+        mapper.gateway = Some(MappingGateway::NatPmp(gateway));
+
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+        let (mapped_tx, mut mapped_rx) = tokio::sync::mpsc::unbounded_channel();
+        let task = tokio::spawn(mapper.run_until_shutdown(shutdown_rx, move |mapped| {
+            let _ = mapped_tx.send(mapped.clone());
+        }));
+
+        let mapped = mapped_rx.recv().await.unwrap();
+        assert_eq!(mapped.external_ip, IpAddr::from(external_ip));
+        assert_eq!(mapped.external_port(PortMappingProtocol::Tcp, 30303), Some(30303));
+
+        shutdown_tx.send(()).unwrap();
+        task.await.unwrap();
+
+        // the mapping is removed on shutdown
+        let last_request = requests.lock().unwrap().last().cloned().unwrap();
+        assert_eq!(mapping_request(&last_request), (PortMappingProtocol::Tcp, 30303, 0, 0));
+    }
+}
@@ -3,7 +3,7 @@
 use std::fmt::Debug;
 
 use futures::Future;
-use reth_network_p2p::BlockClient;
+use reth_network_p2p::{snap::client::SnapClient, BlockClient};
 use tokio::sync::oneshot;
 
 /// Provides client for downloading blocks.
@@ -19,3 +19,15 @@ pub trait BlockDownloaderProvider {
         &self,
     ) -> impl Future<Output = Result<Self::Client, oneshot::error::RecvError>> + Send;
 }
+
+/// Provides client for downloading state over the `snap` protocol.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait SnapDownloaderProvider {
+    /// The client this type can provide.
+    type SnapClient: SnapClient + Clone + 'static;
+
+    /// Returns a new [`SnapClient`], used for fetching state from peers.
+    ///
+    /// The client can only reach peers if the `snap` sub-protocol is enabled on the network.
+    fn snap_client(&self) -> Self::SnapClient;
+}
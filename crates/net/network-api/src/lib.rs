@@ -25,9 +25,9 @@ use test_utils::PeersHandleProvider;
 
 pub use alloy_rpc_types_admin::EthProtocolInfo;
 pub use reth_network_p2p::{BlockClient, HeadersClient};
-pub use reth_network_types::{PeerKind, Reputation, ReputationChangeKind};
+pub use reth_network_types::{PeerKind, PeersConfig, Reputation, ReputationChangeKind};
 
-pub use downloaders::BlockDownloaderProvider;
+pub use downloaders::{BlockDownloaderProvider, SnapDownloaderProvider};
 pub use error::NetworkError;
 pub use events::{
     DiscoveredEvent, DiscoveryEvent, NetworkEvent, NetworkEventListenerProvider, PeerRequest,
@@ -49,7 +49,8 @@ pub type PeerId = alloy_primitives::B512;
 pub trait FullNetwork:
     BlockDownloaderProvider<
         Client: BlockClient<Block = <Self::Primitives as NetworkPrimitives>::Block>,
-    > + NetworkSyncUpdater
+    > + SnapDownloaderProvider
+    + NetworkSyncUpdater
     + NetworkInfo
     + NetworkEventListenerProvider
     + Peers
@@ -63,7 +64,8 @@ pub trait FullNetwork:
 impl<T> FullNetwork for T where
     T: BlockDownloaderProvider<
             Client: BlockClient<Block = <Self::Primitives as NetworkPrimitives>::Block>,
-        > + NetworkSyncUpdater
+        > + SnapDownloaderProvider
+        + NetworkSyncUpdater
         + NetworkInfo
         + NetworkEventListenerProvider
         + Peers
@@ -221,6 +223,10 @@ pub trait Peers: PeersInfo {
         &self,
         peer_id: PeerId,
     ) -> impl Future<Output = Result<Option<Reputation>, NetworkError>> + Send;
+
+    /// Applies the settings of the given [`PeersConfig`] that can change at runtime: the
+    /// connection limits, the trusted nodes and the banned nodes and ips.
+    fn apply_peers_config(&self, config: PeersConfig);
 }
 
 /// Info about an active peer session.
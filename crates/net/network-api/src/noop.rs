@@ -11,16 +11,18 @@ use crate::{
     test_utils::{PeersHandle, PeersHandleProvider},
     BlockDownloaderProvider, DiscoveryEvent, NetworkError, NetworkEvent,
     NetworkEventListenerProvider, NetworkInfo, NetworkStatus, PeerId, PeerInfo, PeerRequest, Peers,
-    PeersInfo,
+    PeersInfo, SnapDownloaderProvider,
 };
 use alloy_rpc_types_admin::EthProtocolInfo;
 use enr::{secp256k1::SecretKey, Enr};
 use reth_eth_wire_types::{
     DisconnectReason, EthNetworkPrimitives, NetworkPrimitives, ProtocolVersion,
 };
-use reth_network_p2p::{sync::NetworkSyncUpdater, NoopFullBlockClient};
+use reth_network_p2p::{
+    snap::client::NoopSnapClient, sync::NetworkSyncUpdater, NoopFullBlockClient,
+};
 use reth_network_peers::NodeRecord;
-use reth_network_types::{PeerKind, Reputation, ReputationChangeKind};
+use reth_network_types::{PeerKind, PeersConfig, Reputation, ReputationChangeKind};
 use reth_tokio_util::{EventSender, EventStream};
 use tokio::sync::{mpsc, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
@@ -167,6 +169,8 @@ where
     async fn reputation_by_id(&self, _peer_id: PeerId) -> Result<Option<Reputation>, NetworkError> {
         Ok(None)
     }
+
+    fn apply_peers_config(&self, _config: PeersConfig) {}
 }
 
 impl<Net> BlockDownloaderProvider for NoopNetwork<Net>
@@ -180,6 +184,14 @@ where
     }
 }
 
+impl<Net> SnapDownloaderProvider for NoopNetwork<Net> {
+    type SnapClient = NoopSnapClient;
+
+    fn snap_client(&self) -> Self::SnapClient {
+        NoopSnapClient::default()
+    }
+}
+
 impl<Net> NetworkSyncUpdater for NoopNetwork<Net>
 where
     Net: fmt::Debug + Send + Sync + 'static,
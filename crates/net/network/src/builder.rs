@@ -2,6 +2,8 @@
 
 use crate::{
     eth_requests::EthRequestHandler,
+    snap_protocol::SnapProtocolHandler,
+    snap_requests::SnapRequestHandler,
     transactions::{
         config::{
             AnnouncementFilteringPolicy, StrictEthAnnouncementFilter, TransactionPropagationKind,
@@ -13,7 +15,7 @@ use crate::{
 };
 use reth_eth_wire::{EthNetworkPrimitives, NetworkPrimitives};
 use reth_network_api::test_utils::PeersHandleProvider;
-use reth_storage_api::BalProvider;
+use reth_storage_api::{BalProvider, BlockNumReader, HashedStateProviderFactory, HeaderProvider};
 use reth_transaction_pool::TransactionPool;
 use tokio::sync::mpsc;
 
@@ -21,6 +23,9 @@ use tokio::sync::mpsc;
 /// 256 requests with malicious 10MB body requests is 2.6GB which can be absorbed by the node.
 pub(crate) const ETH_REQUEST_CHANNEL_CAPACITY: usize = 256;
 
+/// The max channel capacity of the `SnapRequestHandler`.
+pub(crate) const SNAP_REQUEST_CHANNEL_CAPACITY: usize = 256;
+
 /// A builder that can configure all components of the network.
 #[expect(missing_debug_implementations)]
 pub struct NetworkBuilder<Tx, Eth, N: NetworkPrimitives = EthNetworkPrimitives> {
@@ -76,6 +81,29 @@ impl<Tx, Eth, N: NetworkPrimitives> NetworkBuilder<Tx, Eth, N> {
         NetworkBuilder { network, request_handler, transactions }
     }
 
+    /// Installs the `snap` sub-protocol on the network without serving the requests of peers.
+    ///
+    /// This is sufficient to download state with the
+    /// [`SnapFetchClient`](crate::SnapFetchClient). Must not be combined with
+    /// [`Self::snap_request_handler`].
+    pub fn snap(&mut self) {
+        let peers = self.network.handle().snap_peers().clone();
+        self.network.add_rlpx_sub_protocol(SnapProtocolHandler::new(peers, None));
+    }
+
+    /// Creates a new [`SnapRequestHandler`] and installs the `snap` sub-protocol on the network.
+    ///
+    /// The returned handler must be spawned to serve the requests of peers.
+    pub fn snap_request_handler<Client>(&mut self, client: Client) -> SnapRequestHandler<Client>
+    where
+        Client: HashedStateProviderFactory + HeaderProvider + BlockNumReader,
+    {
+        let (tx, rx) = mpsc::channel(SNAP_REQUEST_CHANNEL_CAPACITY);
+        let peers = self.network.handle().snap_peers().clone();
+        self.network.add_rlpx_sub_protocol(SnapProtocolHandler::new(peers, Some(tx)));
+        SnapRequestHandler::new(client, rx)
+    }
+
     /// Creates a new [`TransactionsManager`] and wires it to the network.
     pub fn transactions<Pool: TransactionPool>(
         self,
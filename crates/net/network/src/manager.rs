@@ -45,6 +45,7 @@ use reth_chainspec::EnrForkIdEntry;
 use reth_eth_wire::{DisconnectReason, EthNetworkPrimitives, NetworkPrimitives};
 use reth_fs_util::{self as fs, FsPathError};
 use reth_metrics::common::mpsc::UnboundedMeteredSender;
+use reth_net_nat::{PortMapper, PortMappingProtocol};
 use reth_network_api::{
     events::{PeerEvent, SessionInfo},
     test_utils::PeersHandle,
@@ -57,7 +58,7 @@ use reth_tasks::shutdown::GracefulShutdown;
 use reth_tokio_util::EventSender;
 use secp256k1::SecretKey;
 use std::{
-    net::SocketAddr,
+    net::{Ipv4Addr, SocketAddr},
     path::Path,
     pin::Pin,
     sync::{
@@ -306,6 +307,46 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
         let discv4 = discovery.discv4();
         let discv5 = discovery.discv5();
 
+        // map the listener and discovery ports on the gateway, if the resolver supports it
+        if let Some(port_mapper) = nat.as_ref().and_then(|nat| {
+            let mut ports = vec![(PortMappingProtocol::Tcp, listener_addr)];
+            if let Some(discv4) = &discv4 {
+                ports.push((PortMappingProtocol::Udp, discv4.local_addr()));
+            }
+            if let Some(discv5) = &discv5 {
+                ports.push((
+                    PortMappingProtocol::Udp,
+                    (Ipv4Addr::UNSPECIFIED, discv5.local_port()).into(),
+                ));
+            }
+            PortMapper::new(nat, ports)
+        }) {
+            let (discv4, discv5) = (discv4.clone(), discv5.clone());
+            let listener_port = listener_addr.port();
+            executor.spawn_with_graceful_shutdown_signal(|shutdown| {
+                port_mapper.run_until_shutdown(shutdown, move |mapped| {
+                    // the gateway may assign external ports that differ from the local ones
+                    let tcp_port = mapped.external_port(PortMappingProtocol::Tcp, listener_port);
+                    if let Some(discv4) = &discv4 {
+                        discv4.set_external_ip_addr(mapped.external_ip);
+                        if let Some(port) = tcp_port {
+                            discv4.set_tcp_port(port);
+                        }
+                        if let Some(port) = mapped
+                            .external_port(PortMappingProtocol::Udp, discv4.local_addr().port())
+                        {
+                            discv4.set_udp_port(port);
+                        }
+                    }
+                    if let Some(discv5) = &discv5 {
+                        let udp_port =
+                            mapped.external_port(PortMappingProtocol::Udp, discv5.local_port());
+                        discv5.set_external_addr(mapped.external_ip, udp_port, tcp_port);
+                    }
+                })
+            });
+        }
+
         let num_active_peers = Arc::new(AtomicUsize::new(0));
 
         let sessions = SessionManager::new(
@@ -720,6 +761,9 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             NetworkHandleMessage::ConnectPeer(peer_id, kind, addr) => {
                 self.swarm.state_mut().add_and_connect(peer_id, kind, addr);
             }
+            NetworkHandleMessage::ApplyPeersConfig(config) => {
+                self.swarm.state_mut().peers_mut().apply_config(*config);
+            }
             NetworkHandleMessage::SetNetworkState(net_state) => {
                 // Sets network connection state between Active and Hibernate.
                 // If hibernate stops the node to fill new outbound
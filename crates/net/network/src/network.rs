@@ -1,6 +1,11 @@
 use crate::{
-    config::NetworkMode, message::PeerMessage, protocol::RlpxSubProtocol,
-    swarm::NetworkConnectionState, transactions::TransactionsHandle, FetchClient,
+    config::NetworkMode,
+    message::PeerMessage,
+    protocol::RlpxSubProtocol,
+    snap_client::{SnapFetchClient, SnapPeers},
+    swarm::NetworkConnectionState,
+    transactions::TransactionsHandle,
+    FetchClient,
 };
 use alloy_primitives::B256;
 use enr::Enr;
@@ -18,11 +23,11 @@ use reth_network_api::{
     test_utils::{PeersHandle, PeersHandleProvider},
     BlockDownloaderProvider, DiscoveryEvent, NetworkError, NetworkEvent,
     NetworkEventListenerProvider, NetworkInfo, NetworkStatus, PeerInfo, PeerRequest, Peers,
-    PeersInfo,
+    PeersInfo, SnapDownloaderProvider,
 };
 use reth_network_p2p::sync::{NetworkSyncUpdater, SyncState, SyncStateProvider};
 use reth_network_peers::{NodeRecord, PeerId};
-use reth_network_types::{PeerAddr, PeerKind, Reputation, ReputationChangeKind};
+use reth_network_types::{PeerAddr, PeerKind, PeersConfig, Reputation, ReputationChangeKind};
 use reth_tokio_util::{EventSender, EventStream};
 use secp256k1::SecretKey;
 use std::{
@@ -83,6 +88,7 @@ impl<N: NetworkPrimitives> NetworkHandle<N> {
             discv5,
             event_sender,
             nat,
+            snap_peers: SnapPeers::default(),
         };
         Self { inner: Arc::new(inner) }
     }
@@ -92,6 +98,14 @@ impl<N: NetworkPrimitives> NetworkHandle<N> {
         &self.inner.local_peer_id
     }
 
+    /// Returns the set of peers with an established `snap` connection.
+    ///
+    /// The set is only populated if the `snap` sub-protocol is installed, see
+    /// [`NetworkBuilder::snap`](crate::NetworkBuilder::snap).
+    pub fn snap_peers(&self) -> &SnapPeers {
+        &self.inner.snap_peers
+    }
+
     fn manager(&self) -> &UnboundedSender<NetworkHandleMessage<N>> {
         &self.inner.to_manager_tx
     }
@@ -404,6 +418,12 @@ impl<N: NetworkPrimitives> Peers for NetworkHandle<N> {
         let _ = self.manager().send(NetworkHandleMessage::GetReputationById(peer_id, tx));
         Ok(rx.await?)
     }
+
+    /// Sends a message to the [`NetworkManager`](crate::NetworkManager) to apply the runtime
+    /// settings of the given [`PeersConfig`].
+    fn apply_peers_config(&self, config: PeersConfig) {
+        self.send_message(NetworkHandleMessage::ApplyPeersConfig(Box::new(config)))
+    }
 }
 
 impl<N: NetworkPrimitives> PeersHandleProvider for NetworkHandle<N> {
@@ -480,6 +500,14 @@ impl<N: NetworkPrimitives> BlockDownloaderProvider for NetworkHandle<N> {
     }
 }
 
+impl<N: NetworkPrimitives> SnapDownloaderProvider for NetworkHandle<N> {
+    type SnapClient = SnapFetchClient;
+
+    fn snap_client(&self) -> Self::SnapClient {
+        SnapFetchClient::new(self.inner.snap_peers.clone(), self.inner.peers.clone())
+    }
+}
+
 #[derive(Debug)]
 struct NetworkInner<N: NetworkPrimitives = EthNetworkPrimitives> {
     /// Number of active peer sessions the node's currently handling.
@@ -512,6 +540,8 @@ struct NetworkInner<N: NetworkPrimitives = EthNetworkPrimitives> {
     event_sender: EventSender<NetworkEvent<PeerRequest<N>>>,
     /// The NAT resolver
     nat: Option<NatResolver>,
+    /// The peers with an established `snap` connection.
+    snap_peers: SnapPeers,
 }
 
 /// Provides access to modify the network's additional protocol handlers.
@@ -594,6 +624,8 @@ pub(crate) enum NetworkHandleMessage<N: NetworkPrimitives = EthNetworkPrimitives
     AddRlpxSubProtocol(RlpxSubProtocol),
     /// Connect to the given peer.
     ConnectPeer(PeerId, PeerKind, PeerAddr),
+    /// Applies the runtime settings of a reloaded [`PeersConfig`].
+    ApplyPeersConfig(Box<PeersConfig>),
     /// Message to update the node's advertised block range information.
     InternalBlockRangeUpdate(BlockRangeUpdate),
 }
@@ -70,6 +70,10 @@ pub struct PeersManager {
     connection_info: ConnectionInfo,
     /// Tracks unwanted ips/peer ids.
     ban_list: BanList,
+    /// Peer ids banned by the [`PeersConfig`], see [`Self::apply_config`].
+    banned_nodes: HashSet<PeerId>,
+    /// Ips banned by the [`PeersConfig`], see [`Self::apply_config`].
+    banned_ips: HashSet<IpAddr>,
     /// Tracks currently backed off peers.
     backed_off_peers: HashMap<PeerId, std::time::Instant>,
     /// Interval at which to check for peers to unban and release from the backoff map.
@@ -104,7 +108,9 @@ impl PeersManager {
             refill_slots_interval,
             connection_info,
             reputation_weights,
-            ban_list,
+            mut ban_list,
+            banned_nodes,
+            banned_ips,
             ban_duration,
             backoff_durations,
             trusted_nodes,
@@ -166,6 +172,15 @@ impl PeersManager {
             });
         }
 
+        let banned_nodes = banned_nodes.into_iter().collect::<HashSet<_>>();
+        let banned_ips = banned_ips.into_iter().collect::<HashSet<_>>();
+        for peer_id in &banned_nodes {
+            ban_list.ban_peer(*peer_id);
+        }
+        for ip in &banned_ips {
+            ban_list.ban_ip(*ip);
+        }
+
         trace!(target: "net::peers", trusted_peers=?trusted_peer_ids, "Initialized peers manager");
 
         Self {
@@ -183,6 +198,8 @@ impl PeersManager {
             release_interval: tokio::time::interval_at(now + unban_interval, unban_interval),
             connection_info: ConnectionInfo::new(connection_info),
             ban_list,
+            banned_nodes,
+            banned_ips,
             backed_off_peers: Default::default(),
             ban_duration,
             backoff_durations,
@@ -980,6 +997,76 @@ impl PeersManager {
         self.trusted_peer_ids.remove(&peer_id);
     }
 
+    /// Applies the settings of the given [`PeersConfig`] that can change at runtime: the connection
+    /// limits, the trusted nodes and the banned nodes and ips.
+    ///
+    /// Lowered connection limits and `trusted_nodes_only` only apply to new connections. New
+    /// trusted nodes are resolved in the background and connected peers that are banned now are
+    /// disconnected.
+    pub(crate) fn apply_config(&mut self, config: PeersConfig) {
+        let PeersConfig {
+            connection_info,
+            trusted_nodes,
+            trusted_nodes_only,
+            banned_nodes,
+            banned_ips,
+            ..
+        } = config;
+
+        self.connection_info.config = connection_info;
+        self.trusted_nodes_only = trusted_nodes_only;
+
+        // trusted nodes that were removed from the config are demoted to basic peers
+        let trusted_ids = trusted_nodes.iter().map(|peer| peer.id).collect::<HashSet<_>>();
+        let removed_trusted = self
+            .trusted_peers_resolver
+            .trusted_peers
+            .iter()
+            .map(|peer| peer.id)
+            .filter(|peer_id| !trusted_ids.contains(peer_id))
+            .collect::<Vec<_>>();
+        for peer_id in removed_trusted {
+            self.remove_peer_from_trusted_set(peer_id);
+            self.trusted_peer_ids.remove(&peer_id);
+        }
+        for peer_id in trusted_ids {
+            self.add_trusted_peer_id(peer_id);
+        }
+        self.trusted_peers_resolver.trusted_peers = trusted_nodes;
+        self.trusted_peers_resolver.interval.reset_immediately();
+
+        let banned_nodes = banned_nodes.into_iter().collect::<HashSet<_>>();
+        for peer_id in self.banned_nodes.difference(&banned_nodes).copied().collect::<Vec<_>>() {
+            self.unban_peer(peer_id);
+        }
+        for peer_id in banned_nodes.difference(&self.banned_nodes).copied().collect::<Vec<_>>() {
+            self.ban_list.ban_peer(peer_id);
+            self.queued_actions.push_back(PeerAction::BanPeer { peer_id });
+        }
+        self.banned_nodes = banned_nodes;
+
+        let banned_ips = banned_ips.into_iter().collect::<HashSet<_>>();
+        for ip in self.banned_ips.difference(&banned_ips) {
+            self.ban_list.unban_ip(ip);
+        }
+        for ip in banned_ips.difference(&self.banned_ips) {
+            self.ban_list.ban_ip(*ip);
+        }
+        self.banned_ips = banned_ips;
+
+        for (peer_id, peer) in &mut self.peers {
+            if peer.state.is_connected() && self.ban_list.is_banned(peer_id, &peer.addr.tcp().ip())
+            {
+                trace!(target: "net::peers", ?peer_id, "disconnecting banned peer");
+                peer.state.disconnect();
+                self.queued_actions.push_back(PeerAction::Disconnect {
+                    peer_id: *peer_id,
+                    reason: Some(DisconnectReason::DisconnectRequested),
+                });
+            }
+        }
+    }
+
     /// Returns the best idle peer to connect to.
     ///
     /// Peers that are `trusted` or `static`, see [`PeerKind`], are prioritized as long as they're
@@ -1072,6 +1159,14 @@ impl PeersManager {
                 peer.addr = new_addr;
                 trace!(target: "net::peers", ?peer_id, addr=?peer.addr, "Updated resolved trusted peer address");
             }
+        } else if self.trusted_peer_ids.contains(&peer_id) {
+            // trusted node added by a config reload
+            let addr = PeerAddr::new_with_ports(
+                new_record.address,
+                new_record.tcp_port,
+                Some(new_record.udp_port),
+            );
+            self.add_peer_kind(peer_id, Some(PeerKind::Trusted), addr, None);
         }
     }
 
@@ -3188,6 +3283,75 @@ mod tests {
         assert_eq!(updated_peer.addr.tcp().ip(), updated_ip);
     }
 
+    #[tokio::test]
+    async fn test_apply_config() {
+        let removed_trusted = PeerId::random();
+        let config = PeersConfig::test().with_trusted_nodes(vec![TrustedPeer {
+            host: url::Host::Ipv4("1.1.1.1".parse().unwrap()),
+            tcp_port: 8008,
+            udp_port: 8008,
+            id: removed_trusted,
+        }]);
+        let mut peers = PeersManager::new(config.clone());
+        assert!(peers.peers.get(&removed_trusted).unwrap().is_trusted());
+
+        let connected = PeerId::random();
+        let mut peer = Peer::new(PeerAddr::from_tcp(SocketAddr::new(
+            "2.2.2.2".parse::<IpAddr>().unwrap(),
+            8008,
+        )));
+        peer.state = PeerConnectionState::Out;
+        peers.peers.insert(connected, peer);
+
+        let added_trusted = PeerId::random();
+        let mut new_config = config
+            .with_max_inbound(5)
+            .with_trusted_nodes_only(true)
+            .with_trusted_nodes(vec![TrustedPeer {
+                host: url::Host::Ipv4("3.3.3.3".parse().unwrap()),
+                tcp_port: 8008,
+                udp_port: 8008,
+                id: added_trusted,
+            }]);
+        new_config.banned_nodes = vec![connected];
+        peers.apply_config(new_config.clone());
+
+        assert_eq!(peers.connection_info.config.max_inbound, 5);
+        assert!(peers.trusted_nodes_only);
+        assert!(!peers.peers.get(&removed_trusted).unwrap().is_trusted());
+        assert!(!peers.trusted_peer_ids.contains(&removed_trusted));
+        assert!(peers.trusted_peer_ids.contains(&added_trusted));
+
+        // the connected peer is banned and disconnected
+        assert!(peers.ban_list.is_banned_peer(&connected));
+        assert!(matches!(
+            peers.queued_actions.pop_front(),
+            Some(PeerAction::BanPeer { peer_id }) if peer_id == connected
+        ));
+        assert!(matches!(
+            peers.queued_actions.pop_front(),
+            Some(PeerAction::Disconnect { peer_id, .. }) if peer_id == connected
+        ));
+
+        // the trusted node added by the config is tracked once it's resolved
+        for _ in 0..100 {
+            let _ = event!(peers);
+            if peers.peers.contains_key(&added_trusted) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(peers.peers.get(&added_trusted).unwrap().is_trusted());
+
+        new_config.banned_nodes.clear();
+        peers.apply_config(new_config);
+        assert!(!peers.ban_list.is_banned_peer(&connected));
+        assert!(matches!(
+            peers.queued_actions.pop_front(),
+            Some(PeerAction::UnBanPeer { peer_id }) if peer_id == connected
+        ));
+    }
+
     #[tokio::test]
     async fn test_ip_filter_blocks_inbound_connection() {
         use reth_net_banlist::IpFilter;
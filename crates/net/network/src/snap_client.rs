@@ -0,0 +1,199 @@
+//! Client side of the `snap` sub-protocol.
+//!
+//! Every established `snap` connection registers itself in the shared [`SnapPeers`] set. The
+//! [`SnapFetchClient`] dispatches requests to the least busy of these peers.
+
+use futures::{future, FutureExt};
+use parking_lot::Mutex;
+use reth_eth_wire_types::snap::{
+    GetAccountRangeMessage, GetByteCodesMessage, GetStorageRangesMessage, GetTrieNodesMessage,
+    SnapProtocolMessage,
+};
+use reth_network_api::test_utils::PeersHandle;
+use reth_network_p2p::{
+    download::DownloadClient,
+    error::{PeerRequestResult, RequestError, RequestResult},
+    priority::Priority,
+    snap::client::{SnapClient, SnapResponse},
+};
+use reth_network_peers::{PeerId, WithPeerId};
+use reth_network_types::ReputationChangeKind;
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::sync::{mpsc, oneshot};
+
+/// Timeout after which a `snap` request is considered failed.
+pub const SNAP_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The future returned by the [`SnapFetchClient`].
+pub type SnapFut = Pin<Box<dyn Future<Output = PeerRequestResult<SnapResponse>> + Send + Sync>>;
+
+/// A request that is sent to a peer over its `snap` connection.
+#[derive(Debug)]
+pub(crate) struct SnapPeerRequest {
+    /// The request message, the request id is assigned by the connection.
+    pub(crate) message: SnapProtocolMessage,
+    /// Sender half of the channel for the response.
+    pub(crate) response: oneshot::Sender<RequestResult<SnapResponse>>,
+}
+
+/// Handle to an established `snap` connection.
+#[derive(Debug, Clone)]
+pub(crate) struct SnapPeerHandle {
+    /// Sender half of the request channel of the connection.
+    pub(crate) to_connection: mpsc::UnboundedSender<SnapPeerRequest>,
+    /// Number of requests that are awaiting a response from the peer.
+    pub(crate) inflight: Arc<AtomicUsize>,
+    /// Total number of requests sent to the peer, used to rotate between idle peers.
+    pub(crate) requests: Arc<AtomicUsize>,
+}
+
+/// The set of peers with an established `snap` connection.
+#[derive(Debug, Clone, Default)]
+pub struct SnapPeers {
+    peers: Arc<Mutex<HashMap<PeerId, SnapPeerHandle>>>,
+}
+
+impl SnapPeers {
+    /// Returns the number of connected `snap` peers.
+    pub fn len(&self) -> usize {
+        self.peers.lock().len()
+    }
+
+    /// Returns `true` if there are no connected `snap` peers.
+    pub fn is_empty(&self) -> bool {
+        self.peers.lock().is_empty()
+    }
+
+    /// Registers the connection of the peer, replacing a previous connection.
+    pub(crate) fn insert(&self, peer_id: PeerId, handle: SnapPeerHandle) {
+        self.peers.lock().insert(peer_id, handle);
+    }
+
+    /// Removes the connection of the peer, if it is still the registered one.
+    pub(crate) fn remove(
+        &self,
+        peer_id: &PeerId,
+        to_connection: &mpsc::UnboundedSender<SnapPeerRequest>,
+    ) {
+        let mut peers = self.peers.lock();
+        if peers.get(peer_id).is_some_and(|handle| handle.to_connection.same_channel(to_connection))
+        {
+            peers.remove(peer_id);
+        }
+    }
+
+    /// Returns the least busy peer.
+    fn next_peer(&self) -> Option<(PeerId, SnapPeerHandle)> {
+        self.peers
+            .lock()
+            .iter()
+            .min_by_key(|(_, handle)| {
+                (handle.inflight.load(Ordering::Relaxed), handle.requests.load(Ordering::Relaxed))
+            })
+            .map(|(peer_id, handle)| (*peer_id, handle.clone()))
+    }
+}
+
+/// Front-end API for downloading state over the `snap` protocol.
+#[derive(Debug, Clone)]
+pub struct SnapFetchClient {
+    /// The peers with an established `snap` connection.
+    peers: SnapPeers,
+    /// The handle to the peers
+    peers_handle: PeersHandle,
+}
+
+impl SnapFetchClient {
+    /// Creates a new client that sends requests to the given peers.
+    pub const fn new(peers: SnapPeers, peers_handle: PeersHandle) -> Self {
+        Self { peers, peers_handle }
+    }
+
+    /// Sends the request to the least busy peer.
+    fn send_request(&self, message: SnapProtocolMessage) -> SnapFut {
+        let Some((peer_id, peer)) = self.peers.next_peer() else {
+            return Box::pin(future::err(RequestError::ConnectionDropped))
+        };
+
+        let (response, rx) = oneshot::channel();
+        peer.requests.fetch_add(1, Ordering::Relaxed);
+        if peer.to_connection.send(SnapPeerRequest { message, response }).is_err() {
+            return Box::pin(future::err(RequestError::ConnectionDropped))
+        }
+
+        Box::pin(tokio::time::timeout(SNAP_REQUEST_TIMEOUT, rx).map(move |result| {
+            let response = match result {
+                Ok(Ok(response)) => response?,
+                Ok(Err(_)) => return Err(RequestError::ConnectionDropped),
+                Err(_) => return Err(RequestError::Timeout),
+            };
+            Ok(WithPeerId::new(peer_id, response))
+        }))
+    }
+}
+
+impl DownloadClient for SnapFetchClient {
+    fn report_bad_message(&self, peer_id: PeerId) {
+        self.peers_handle.reputation_change(peer_id, ReputationChangeKind::BadMessage);
+    }
+
+    fn num_connected_peers(&self) -> usize {
+        self.peers.len()
+    }
+}
+
+impl SnapClient for SnapFetchClient {
+    type Output = SnapFut;
+
+    fn get_account_range_with_priority(
+        &self,
+        request: GetAccountRangeMessage,
+        _priority: Priority,
+    ) -> Self::Output {
+        self.send_request(SnapProtocolMessage::GetAccountRange(request))
+    }
+
+    fn get_storage_ranges(&self, request: GetStorageRangesMessage) -> Self::Output {
+        self.get_storage_ranges_with_priority(request, Priority::Normal)
+    }
+
+    fn get_storage_ranges_with_priority(
+        &self,
+        request: GetStorageRangesMessage,
+        _priority: Priority,
+    ) -> Self::Output {
+        self.send_request(SnapProtocolMessage::GetStorageRanges(request))
+    }
+
+    fn get_byte_codes(&self, request: GetByteCodesMessage) -> Self::Output {
+        self.get_byte_codes_with_priority(request, Priority::Normal)
+    }
+
+    fn get_byte_codes_with_priority(
+        &self,
+        request: GetByteCodesMessage,
+        _priority: Priority,
+    ) -> Self::Output {
+        self.send_request(SnapProtocolMessage::GetByteCodes(request))
+    }
+
+    fn get_trie_nodes(&self, request: GetTrieNodesMessage) -> Self::Output {
+        self.get_trie_nodes_with_priority(request, Priority::Normal)
+    }
+
+    fn get_trie_nodes_with_priority(
+        &self,
+        request: GetTrieNodesMessage,
+        _priority: Priority,
+    ) -> Self::Output {
+        self.send_request(SnapProtocolMessage::GetTrieNodes(request))
+    }
+}
@@ -0,0 +1,389 @@
+//! `snap` sub-protocol support for serving and downloading state.
+//!
+//! The `snap` protocol runs side-by-side with `eth` on the same `RLPx` connection. Incoming
+//! requests are delegated to the [`SnapRequestHandler`](crate::snap_requests::SnapRequestHandler),
+//! outgoing requests are issued by the [`SnapFetchClient`](crate::snap_client::SnapFetchClient).
+
+use crate::{
+    protocol::{ConnectionHandler, OnNotSupported, ProtocolHandler},
+    snap_client::{SnapPeerHandle, SnapPeerRequest, SnapPeers},
+    snap_requests::IncomingSnapRequest,
+};
+use alloy_primitives::bytes::BytesMut;
+use futures::{stream::FuturesUnordered, Stream, StreamExt};
+use reth_eth_wire::{
+    capability::SharedCapabilities, multiplex::ProtocolConnection, protocol::Protocol,
+};
+use reth_eth_wire_types::snap::{SnapMessageId, SnapProtocolMessage, SnapVersion};
+use reth_network_api::{Direction, PeerId};
+use reth_network_p2p::{
+    error::{RequestError, RequestResult},
+    snap::client::SnapResponse,
+};
+use std::{
+    collections::HashMap,
+    fmt,
+    future::Future,
+    net::SocketAddr,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+use tokio::sync::{
+    mpsc::{self, error::TrySendError},
+    oneshot,
+};
+use tracing::{debug, trace};
+
+/// Maximum number of requests of a single peer that are served concurrently.
+///
+/// Additional requests are dropped until responses have been sent.
+pub const MAX_CONCURRENT_SNAP_REQUESTS_PER_PEER: usize = 16;
+
+/// A [`ProtocolHandler`] that announces `snap/1` on every connection.
+///
+/// Established connections are registered in the [`SnapPeers`] set, so that state can be
+/// downloaded from them. Requests of peers are served through the
+/// [`SnapRequestHandler`](crate::snap_requests::SnapRequestHandler), if one is configured.
+#[derive(Debug, Clone)]
+pub struct SnapProtocolHandler {
+    /// The set of peers with an established `snap` connection.
+    peers: SnapPeers,
+    /// Sender half of the channel to the request handler.
+    to_request_handler: Option<mpsc::Sender<IncomingSnapRequest>>,
+}
+
+impl SnapProtocolHandler {
+    /// Creates a new handler that registers connections in the given set of peers and delegates
+    /// all requests of peers to the given channel.
+    ///
+    /// Requests of peers are ignored if no request handler channel is provided.
+    pub const fn new(
+        peers: SnapPeers,
+        to_request_handler: Option<mpsc::Sender<IncomingSnapRequest>>,
+    ) -> Self {
+        Self { peers, to_request_handler }
+    }
+
+    fn connection_handler(&self) -> SnapConnectionHandler {
+        SnapConnectionHandler {
+            peers: self.peers.clone(),
+            to_request_handler: self.to_request_handler.clone(),
+        }
+    }
+}
+
+impl ProtocolHandler for SnapProtocolHandler {
+    type ConnectionHandler = SnapConnectionHandler;
+
+    fn on_incoming(&self, _socket_addr: SocketAddr) -> Option<Self::ConnectionHandler> {
+        Some(self.connection_handler())
+    }
+
+    fn on_outgoing(
+        &self,
+        _socket_addr: SocketAddr,
+        _peer_id: PeerId,
+    ) -> Option<Self::ConnectionHandler> {
+        Some(self.connection_handler())
+    }
+}
+
+/// The [`ConnectionHandler`] for a single `snap` connection.
+#[derive(Debug)]
+pub struct SnapConnectionHandler {
+    /// The set of peers with an established `snap` connection.
+    peers: SnapPeers,
+    /// Sender half of the channel to the request handler.
+    to_request_handler: Option<mpsc::Sender<IncomingSnapRequest>>,
+}
+
+impl ConnectionHandler for SnapConnectionHandler {
+    type Connection = SnapConnection;
+
+    fn protocol(&self) -> Protocol {
+        Protocol::snap(SnapVersion::V1)
+    }
+
+    fn on_unsupported_by_peer(
+        self,
+        _supported: &SharedCapabilities,
+        _direction: Direction,
+        _peer_id: PeerId,
+    ) -> OnNotSupported {
+        OnNotSupported::KeepAlive
+    }
+
+    fn into_connection(
+        self,
+        _direction: Direction,
+        peer_id: PeerId,
+        conn: ProtocolConnection,
+    ) -> Self::Connection {
+        let (to_connection, from_client) = mpsc::unbounded_channel();
+        let inflight = Arc::new(AtomicUsize::new(0));
+        self.peers.insert(
+            peer_id,
+            SnapPeerHandle {
+                to_connection: to_connection.clone(),
+                inflight: inflight.clone(),
+                requests: Arc::default(),
+            },
+        );
+
+        SnapConnection {
+            peer_id,
+            conn,
+            to_request_handler: self.to_request_handler,
+            pending_responses: FuturesUnordered::new(),
+            peers: self.peers,
+            to_connection,
+            from_client,
+            inflight_requests: HashMap::new(),
+            inflight,
+            next_request_id: 0,
+        }
+    }
+}
+
+/// A request to the peer that is awaiting a response.
+#[derive(Debug)]
+struct InflightSnapRequest {
+    /// The message id of the expected response.
+    response_id: SnapMessageId,
+    /// Sender half of the channel for the response.
+    response: oneshot::Sender<RequestResult<SnapResponse>>,
+}
+
+/// A response that is being served by the request handler.
+type PendingSnapResponse = Pin<Box<dyn Future<Output = Option<SnapProtocolMessage>> + Send>>;
+
+/// An established `snap` connection with a peer.
+///
+/// Yields the encoded responses to the peer's requests and the encoded requests of the
+/// [`SnapFetchClient`](crate::snap_client::SnapFetchClient). The connection is closed if the peer
+/// sends a malformed message.
+pub struct SnapConnection {
+    /// The remote peer.
+    peer_id: PeerId,
+    /// Incoming raw messages of the peer.
+    conn: ProtocolConnection,
+    /// Sender half of the channel to the request handler.
+    to_request_handler: Option<mpsc::Sender<IncomingSnapRequest>>,
+    /// Responses that are currently being served.
+    pending_responses: FuturesUnordered<PendingSnapResponse>,
+    /// The set of peers with an established `snap` connection.
+    peers: SnapPeers,
+    /// Sender half of the request channel, used to unregister the connection.
+    to_connection: mpsc::UnboundedSender<SnapPeerRequest>,
+    /// Requests that should be sent to the peer.
+    from_client: mpsc::UnboundedReceiver<SnapPeerRequest>,
+    /// Requests to the peer that are awaiting a response, by request id.
+    inflight_requests: HashMap<u64, InflightSnapRequest>,
+    /// Number of requests that are awaiting a response, shared with the [`SnapPeers`] set.
+    inflight: Arc<AtomicUsize>,
+    /// The id of the next request to the peer.
+    next_request_id: u64,
+}
+
+impl SnapConnection {
+    /// Delegates the request to the request handler and tracks its response.
+    fn on_request<T: Send + 'static>(
+        &self,
+        request: IncomingSnapRequest,
+        rx: oneshot::Receiver<RequestResult<T>>,
+        into_message: fn(T) -> SnapProtocolMessage,
+    ) {
+        let Some(to_request_handler) = &self.to_request_handler else {
+            trace!(target: "net::snap", peer_id=%self.peer_id, "Not serving snap requests, dropping request");
+            return
+        };
+
+        if self.pending_responses.len() >= MAX_CONCURRENT_SNAP_REQUESTS_PER_PEER {
+            trace!(target: "net::snap", peer_id=%self.peer_id, "Too many concurrent snap requests, dropping request");
+            return
+        }
+
+        if let Err(err) = to_request_handler.try_send(request) {
+            if let TrySendError::Full(_) = err {
+                debug!(target: "net::snap", "SnapRequestHandler channel is full!");
+            }
+            return
+        }
+
+        self.pending_responses.push(Box::pin(async move { rx.await.ok()?.ok().map(into_message) }));
+    }
+
+    /// Handles a message received from the peer.
+    fn on_message(&mut self, message: SnapProtocolMessage) {
+        let peer_id = self.peer_id;
+        match message {
+            SnapProtocolMessage::GetAccountRange(request) => {
+                let (response, rx) = oneshot::channel();
+                self.on_request(
+                    IncomingSnapRequest::GetAccountRange { peer_id, request, response },
+                    rx,
+                    SnapProtocolMessage::AccountRange,
+                )
+            }
+            SnapProtocolMessage::GetStorageRanges(request) => {
+                let (response, rx) = oneshot::channel();
+                self.on_request(
+                    IncomingSnapRequest::GetStorageRanges { peer_id, request, response },
+                    rx,
+                    SnapProtocolMessage::StorageRanges,
+                )
+            }
+            SnapProtocolMessage::GetByteCodes(request) => {
+                let (response, rx) = oneshot::channel();
+                self.on_request(
+                    IncomingSnapRequest::GetByteCodes { peer_id, request, response },
+                    rx,
+                    SnapProtocolMessage::ByteCodes,
+                )
+            }
+            SnapProtocolMessage::GetTrieNodes(request) => {
+                let (response, rx) = oneshot::channel();
+                self.on_request(
+                    IncomingSnapRequest::GetTrieNodes { peer_id, request, response },
+                    rx,
+                    SnapProtocolMessage::TrieNodes,
+                )
+            }
+            SnapProtocolMessage::AccountRange(response) => {
+                let request_id = response.request_id;
+                self.on_response(request_id, SnapResponse::AccountRange(response))
+            }
+            SnapProtocolMessage::StorageRanges(response) => {
+                let request_id = response.request_id;
+                self.on_response(request_id, SnapResponse::StorageRanges(response))
+            }
+            SnapProtocolMessage::ByteCodes(response) => {
+                let request_id = response.request_id;
+                self.on_response(request_id, SnapResponse::ByteCodes(response))
+            }
+            SnapProtocolMessage::TrieNodes(response) => {
+                let request_id = response.request_id;
+                self.on_response(request_id, SnapResponse::TrieNodes(response))
+            }
+            message => {
+                trace!(target: "net::snap", %peer_id, id=?message.message_id(), "Ignoring unsolicited snap message");
+            }
+        }
+    }
+
+    /// Assigns a request id to the client's request and returns the message to send.
+    fn on_client_request(&mut self, request: SnapPeerRequest) -> Option<SnapProtocolMessage> {
+        let SnapPeerRequest { mut message, response } = request;
+        let request_id = self.next_request_id;
+        self.next_request_id += 1;
+
+        let response_id = match &mut message {
+            SnapProtocolMessage::GetAccountRange(request) => {
+                request.request_id = request_id;
+                SnapMessageId::AccountRange
+            }
+            SnapProtocolMessage::GetStorageRanges(request) => {
+                request.request_id = request_id;
+                SnapMessageId::StorageRanges
+            }
+            SnapProtocolMessage::GetByteCodes(request) => {
+                request.request_id = request_id;
+                SnapMessageId::ByteCodes
+            }
+            SnapProtocolMessage::GetTrieNodes(request) => {
+                request.request_id = request_id;
+                SnapMessageId::TrieNodes
+            }
+            _ => {
+                let _ = response.send(Err(RequestError::BadResponse));
+                return None
+            }
+        };
+
+        // drop requests whose caller is no longer waiting for the response
+        self.inflight_requests.retain(|_, request| !request.response.is_closed());
+        self.inflight_requests.insert(request_id, InflightSnapRequest { response_id, response });
+        self.inflight.store(self.inflight_requests.len(), Ordering::Relaxed);
+
+        Some(message)
+    }
+
+    /// Resolves the request the peer responded to.
+    fn on_response(&mut self, request_id: u64, response: SnapResponse) {
+        let Some(request) = self.inflight_requests.remove(&request_id) else {
+            trace!(target: "net::snap", peer_id=%self.peer_id, request_id, "Ignoring unsolicited snap response");
+            return
+        };
+        self.inflight.store(self.inflight_requests.len(), Ordering::Relaxed);
+
+        let response_id = match &response {
+            SnapResponse::AccountRange(_) => SnapMessageId::AccountRange,
+            SnapResponse::StorageRanges(_) => SnapMessageId::StorageRanges,
+            SnapResponse::ByteCodes(_) => SnapMessageId::ByteCodes,
+            SnapResponse::TrieNodes(_) => SnapMessageId::TrieNodes,
+        };
+        let response = if response_id == request.response_id {
+            Ok(response)
+        } else {
+            Err(RequestError::BadResponse)
+        };
+        let _ = request.response.send(response);
+    }
+}
+
+impl Drop for SnapConnection {
+    fn drop(&mut self) {
+        self.peers.remove(&self.peer_id, &self.to_connection);
+    }
+}
+
+impl fmt::Debug for SnapConnection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SnapConnection")
+            .field("peer_id", &self.peer_id)
+            .field("pending_responses", &self.pending_responses.len())
+            .field("inflight_requests", &self.inflight_requests.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Stream for SnapConnection {
+    type Item = BytesMut;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            // send out finished responses first
+            while let Poll::Ready(Some(response)) = this.pending_responses.poll_next_unpin(cx) {
+                if let Some(message) = response {
+                    return Poll::Ready(Some(BytesMut::from(&message.encode()[..])))
+                }
+            }
+
+            // send out requests of the client
+            while let Poll::Ready(Some(request)) = this.from_client.poll_recv(cx) {
+                if let Some(message) = this.on_client_request(request) {
+                    return Poll::Ready(Some(BytesMut::from(&message.encode()[..])))
+                }
+            }
+
+            let Some(raw) = std::task::ready!(this.conn.poll_next_unpin(cx)) else {
+                return Poll::Ready(None)
+            };
+
+            let Some((&id, mut buf)) = raw.split_first() else { continue };
+            match SnapProtocolMessage::decode(id, &mut buf) {
+                Ok(message) => this.on_message(message),
+                Err(err) => {
+                    debug!(target: "net::snap", peer_id=%this.peer_id, %err, "Failed to decode snap message, closing connection");
+                    return Poll::Ready(None)
+                }
+            }
+        }
+    }
+}
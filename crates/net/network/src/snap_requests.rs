@@ -0,0 +1,713 @@
+//! State snapshot serving for the `snap` protocol.
+//!
+//! See also <https://github.com/ethereum/devp2p/blob/master/caps/snap.md>
+
+use crate::{
+    budget::DEFAULT_BUDGET_TRY_DRAIN_DOWNLOADERS, eth_requests::SOFT_RESPONSE_LIMIT,
+    metered_poll_nested_stream_with_budget, metrics::SnapRequestHandlerMetrics,
+};
+use alloy_consensus::{
+    constants::{EMPTY_ROOT_HASH, KECCAK_EMPTY},
+    BlockHeader,
+};
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{map::B256Map, Bytes, B256, U256};
+use alloy_rlp::Encodable;
+use futures::StreamExt;
+use parking_lot::Mutex;
+use reth_eth_wire_types::snap::{
+    AccountData, AccountRangeMessage, ByteCodesMessage, GetAccountRangeMessage,
+    GetByteCodesMessage, GetStorageRangesMessage, GetTrieNodesMessage, StorageData,
+    StorageRangesMessage, TrieNodesMessage, TriePath,
+};
+use reth_network_p2p::error::RequestResult;
+use reth_network_peers::PeerId;
+use reth_primitives_traits::Account;
+use reth_storage_api::{
+    errors::provider::ProviderResult, BlockNumReader, HashedStateProviderFactory,
+    HashedStateRangeReaderBox, HeaderProvider,
+};
+use reth_trie_common::{proof::ProofNodes, MultiProofTargets, Nibbles};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::sync::{mpsc::Receiver, oneshot};
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::{debug, trace};
+
+// Limits: <https://github.com/ethereum/go-ethereum/blob/master/eth/protocols/snap/handler.go>
+
+/// Maximum number of contract codes to serve.
+///
+/// Used to limit lookups.
+pub const MAX_BYTECODES_SERVE: usize = 1024;
+
+/// Maximum number of trie nodes to serve.
+///
+/// Used to limit lookups.
+pub const MAX_TRIE_NODES_SERVE: usize = 1024;
+
+/// Number of recent canonical blocks whose state is served.
+///
+/// Peers sync to a recent pivot block that rarely is the tip of this node, so the states of the
+/// latest blocks are served, like geth does.
+pub const SNAP_SERVE_RECENT_BLOCKS: u64 = 128;
+
+/// Number of entries read from the hashed state tables at once.
+const HASHED_STATE_READ_BATCH: usize = 256;
+
+/// Manages `snap` related requests on top of the p2p network.
+///
+/// Requests are served from the states of the latest [`SNAP_SERVE_RECENT_BLOCKS`] canonical
+/// blocks. Requests for any other state root are answered with empty responses, as mandated by the
+/// protocol.
+///
+/// This can be spawned to another task and is supposed to be run as background service.
+#[derive(Debug)]
+#[must_use = "Manager does nothing unless polled."]
+pub struct SnapRequestHandler<C> {
+    /// The client type that can access the state.
+    client: C,
+    /// Incoming requests from the `snap` sub-protocol connections.
+    incoming_requests: ReceiverStream<IncomingSnapRequest>,
+    /// State roots of the recent canonical blocks.
+    recent_roots: Mutex<RecentStateRoots>,
+    /// Metrics for the snap request handler.
+    metrics: SnapRequestHandlerMetrics,
+}
+
+// === impl SnapRequestHandler ===
+
+impl<C> SnapRequestHandler<C> {
+    /// Create a new instance
+    pub fn new(client: C, incoming: Receiver<IncomingSnapRequest>) -> Self {
+        Self {
+            client,
+            incoming_requests: ReceiverStream::new(incoming),
+            recent_roots: Default::default(),
+            metrics: Default::default(),
+        }
+    }
+}
+
+impl<C> SnapRequestHandler<C>
+where
+    C: HashedStateProviderFactory + HeaderProvider + BlockNumReader,
+{
+    /// Returns the state with the requested root, if it's the state of one of the recent
+    /// canonical blocks.
+    fn state_for_root(&self, root_hash: B256) -> ProviderResult<Option<HashedStateRangeReaderBox>> {
+        let persisted = self.client.persisted_hashed_state()?;
+        if persisted.state_root == root_hash {
+            let state: HashedStateRangeReaderBox = persisted.provider;
+            return Ok(Some(state))
+        }
+
+        let Some(block_hash) = self.recent_block_with_root(root_hash)? else {
+            trace!(target: "net::snap", requested=?root_hash, "Requested state root is not served");
+            self.metrics.snap_unavailable_root_requests_total.increment(1);
+            return Ok(None)
+        };
+        self.client.hashed_state_by_block_hash(block_hash).map(Some)
+    }
+
+    /// Returns the hash of the block with the given state root among the latest
+    /// [`SNAP_SERVE_RECENT_BLOCKS`] canonical blocks.
+    fn recent_block_with_root(&self, root_hash: B256) -> ProviderResult<Option<B256>> {
+        let tip_number = self.client.best_block_number()?;
+        let Some(tip_hash) = self.client.block_hash(tip_number)? else { return Ok(None) };
+
+        let mut recent = self.recent_roots.lock();
+        if recent.tip.hash != tip_hash {
+            let start = tip_number.saturating_sub(SNAP_SERVE_RECENT_BLOCKS - 1);
+            recent.blocks = self
+                .client
+                .sealed_headers_range(start..=tip_number)?
+                .into_iter()
+                .map(|header| (header.state_root(), header.hash()))
+                .collect();
+            recent.tip = BlockNumHash::new(tip_number, tip_hash);
+        }
+
+        Ok(recent.blocks.get(&root_hash).copied())
+    }
+
+    /// Returns the accounts starting at the requested origin, together with the boundary proofs
+    /// of the returned range.
+    fn get_account_range_response(
+        &self,
+        request: GetAccountRangeMessage,
+    ) -> ProviderResult<AccountRangeMessage> {
+        let GetAccountRangeMessage {
+            request_id,
+            root_hash,
+            starting_hash,
+            limit_hash,
+            response_bytes,
+        } = request;
+        let mut response =
+            AccountRangeMessage { request_id, accounts: Vec::new(), proof: Vec::new() };
+
+        let Some(state) = self.state_for_root(root_hash)? else { return Ok(response) };
+        let limit = response_limit(response_bytes);
+        let mut total_bytes = 0;
+
+        let mut next = Some(starting_hash);
+        'outer: while let Some(start) = next {
+            let accounts = state.hashed_accounts_from(start, HASHED_STATE_READ_BATCH)?;
+            next = if accounts.len() < HASHED_STATE_READ_BATCH {
+                None
+            } else {
+                accounts.last().and_then(|(hash, _)| next_key(*hash))
+            };
+
+            for (hash, account) in accounts {
+                let storage_root = state.hashed_storage_root(hash)?;
+                let body = slim_account_rlp(&account, storage_root);
+                total_bytes += B256::len_bytes() + body.len();
+                response.accounts.push(AccountData { hash, body });
+
+                if hash >= limit_hash || total_bytes >= limit {
+                    break 'outer
+                }
+            }
+        }
+
+        let mut targets = MultiProofTargets::account(starting_hash);
+        if let Some(last) = response.accounts.last() {
+            targets.extend(MultiProofTargets::account(last.hash));
+        }
+        let multiproof = state.hashed_multiproof(targets)?;
+        response.proof = proof_nodes(multiproof.account_subtree);
+
+        Ok(response)
+    }
+
+    /// Returns the storage slots of the requested accounts.
+    ///
+    /// Only the last account's slots may be incomplete, in which case a boundary proof is attached.
+    fn get_storage_ranges_response(
+        &self,
+        request: GetStorageRangesMessage,
+    ) -> ProviderResult<StorageRangesMessage> {
+        let GetStorageRangesMessage {
+            request_id,
+            root_hash,
+            account_hashes,
+            starting_hash,
+            limit_hash,
+            response_bytes,
+        } = request;
+        let mut response =
+            StorageRangesMessage { request_id, slots: Vec::new(), proof: Vec::new() };
+
+        let Some(state) = self.state_for_root(root_hash)? else { return Ok(response) };
+        let limit = response_limit(response_bytes);
+        let mut total_bytes = 0;
+
+        for (idx, hashed_address) in account_hashes.into_iter().enumerate() {
+            if total_bytes >= limit {
+                break
+            }
+
+            // the origin and limit only apply to the first requested account
+            let (origin, last) = if idx == 0 {
+                (starting_hash, limit_hash)
+            } else {
+                (B256::ZERO, B256::repeat_byte(0xff))
+            };
+
+            let mut slots = Vec::new();
+            let mut aborted = false;
+            let mut next = Some(origin);
+            'outer: while let Some(start) = next {
+                let entries =
+                    state.hashed_storages_from(hashed_address, start, HASHED_STATE_READ_BATCH)?;
+                next = if entries.len() < HASHED_STATE_READ_BATCH {
+                    None
+                } else {
+                    entries.last().and_then(|(hash, _)| next_key(*hash))
+                };
+
+                for (hash, value) in entries {
+                    if total_bytes >= limit {
+                        aborted = true;
+                        break 'outer
+                    }
+
+                    let data = Bytes::from(alloy_rlp::encode(value));
+                    total_bytes += B256::len_bytes() + data.len();
+                    slots.push(StorageData { hash, data });
+
+                    if hash >= last {
+                        break 'outer
+                    }
+                }
+            }
+
+            let last_slot = slots.last().map(|slot| slot.hash);
+            if !slots.is_empty() {
+                response.slots.push(slots);
+            }
+
+            // a partial range must be proven, and no further accounts are served
+            if origin != B256::ZERO || (aborted && last_slot.is_some()) {
+                let targets = MultiProofTargets::account_with_slots(
+                    hashed_address,
+                    core::iter::once(origin).chain(last_slot),
+                );
+                let mut multiproof = state.hashed_multiproof(targets)?;
+                if let Some(storage) = multiproof.storages.remove(&hashed_address) {
+                    response.proof = proof_nodes(storage.subtree);
+                }
+                break
+            }
+        }
+
+        Ok(response)
+    }
+
+    /// Returns the requested contract codes, skipping unknown hashes.
+    fn get_bytecodes_response(
+        &self,
+        request: GetByteCodesMessage,
+    ) -> ProviderResult<ByteCodesMessage> {
+        let GetByteCodesMessage { request_id, hashes, response_bytes } = request;
+        let mut response = ByteCodesMessage { request_id, codes: Vec::new() };
+
+        let state = self.client.persisted_hashed_state()?;
+        let limit = response_limit(response_bytes);
+        let mut total_bytes = 0;
+
+        for hash in hashes.into_iter().take(MAX_BYTECODES_SERVE) {
+            let code = if hash == KECCAK_EMPTY {
+                Bytes::new()
+            } else if let Some(code) = state.provider.bytecode_by_hash(&hash)? {
+                code.original_bytes()
+            } else {
+                continue
+            };
+
+            total_bytes += code.len();
+            response.codes.push(code);
+
+            if total_bytes >= limit {
+                break
+            }
+        }
+
+        Ok(response)
+    }
+
+    /// Returns the requested account and storage trie nodes.
+    ///
+    /// Nodes that don't exist in the served state are returned as empty entries.
+    fn get_trie_nodes_response(
+        &self,
+        request: GetTrieNodesMessage,
+    ) -> ProviderResult<TrieNodesMessage> {
+        let GetTrieNodesMessage { request_id, root_hash, paths, response_bytes } = request;
+        let mut response = TrieNodesMessage { request_id, nodes: Vec::new() };
+
+        let Some(state) = self.state_for_root(root_hash)? else { return Ok(response) };
+        let limit = response_limit(response_bytes);
+
+        // resolve all requested paths first, so that all nodes can be retrieved in a single proof
+        let mut requested = Vec::new();
+        let mut targets = MultiProofTargets::default();
+        for TriePath { account_path, slot_paths } in paths {
+            if requested.len() >= MAX_TRIE_NODES_SERVE {
+                break
+            }
+
+            if slot_paths.is_empty() {
+                let Some(path) = decode_compact_path(&account_path) else { break };
+                targets.extend(MultiProofTargets::account(path_to_key(&path)));
+                requested.push((None, path));
+                continue
+            }
+
+            if account_path.len() != B256::len_bytes() {
+                break
+            }
+            let hashed_address = B256::from_slice(&account_path);
+            for slot_path in slot_paths {
+                let Some(path) = decode_compact_path(&slot_path) else { break };
+                targets.extend(MultiProofTargets::account_with_slots(
+                    hashed_address,
+                    [path_to_key(&path)],
+                ));
+                requested.push((Some(hashed_address), path));
+            }
+        }
+
+        if requested.is_empty() {
+            return Ok(response)
+        }
+
+        let multiproof = state.hashed_multiproof(targets)?;
+        let mut total_bytes = 0;
+        for (hashed_address, path) in requested.into_iter().take(MAX_TRIE_NODES_SERVE) {
+            let node = match hashed_address {
+                None => multiproof.account_subtree.get(&path),
+                Some(hashed_address) => multiproof
+                    .storages
+                    .get(&hashed_address)
+                    .and_then(|storage| storage.subtree.get(&path)),
+            };
+            let node = node.cloned().unwrap_or_default();
+
+            total_bytes += node.len();
+            response.nodes.push(node);
+
+            if total_bytes >= limit {
+                break
+            }
+        }
+
+        Ok(response)
+    }
+
+    fn on_account_range_request(
+        &self,
+        peer_id: PeerId,
+        request: GetAccountRangeMessage,
+        response: oneshot::Sender<RequestResult<AccountRangeMessage>>,
+    ) {
+        self.metrics.snap_account_range_requests_received_total.increment(1);
+        let request_id = request.request_id;
+        let accounts = self.get_account_range_response(request).unwrap_or_else(|err| {
+            debug!(target: "net::snap", %peer_id, %err, "Failed to serve account range");
+            AccountRangeMessage { request_id, accounts: Vec::new(), proof: Vec::new() }
+        });
+        let _ = response.send(Ok(accounts));
+    }
+
+    fn on_storage_ranges_request(
+        &self,
+        peer_id: PeerId,
+        request: GetStorageRangesMessage,
+        response: oneshot::Sender<RequestResult<StorageRangesMessage>>,
+    ) {
+        self.metrics.snap_storage_ranges_requests_received_total.increment(1);
+        let request_id = request.request_id;
+        let slots = self.get_storage_ranges_response(request).unwrap_or_else(|err| {
+            debug!(target: "net::snap", %peer_id, %err, "Failed to serve storage ranges");
+            StorageRangesMessage { request_id, slots: Vec::new(), proof: Vec::new() }
+        });
+        let _ = response.send(Ok(slots));
+    }
+
+    fn on_bytecodes_request(
+        &self,
+        peer_id: PeerId,
+        request: GetByteCodesMessage,
+        response: oneshot::Sender<RequestResult<ByteCodesMessage>>,
+    ) {
+        self.metrics.snap_bytecodes_requests_received_total.increment(1);
+        let request_id = request.request_id;
+        let codes = self.get_bytecodes_response(request).unwrap_or_else(|err| {
+            debug!(target: "net::snap", %peer_id, %err, "Failed to serve bytecodes");
+            ByteCodesMessage { request_id, codes: Vec::new() }
+        });
+        let _ = response.send(Ok(codes));
+    }
+
+    fn on_trie_nodes_request(
+        &self,
+        peer_id: PeerId,
+        request: GetTrieNodesMessage,
+        response: oneshot::Sender<RequestResult<TrieNodesMessage>>,
+    ) {
+        self.metrics.snap_trie_nodes_requests_received_total.increment(1);
+        let request_id = request.request_id;
+        let nodes = self.get_trie_nodes_response(request).unwrap_or_else(|err| {
+            debug!(target: "net::snap", %peer_id, %err, "Failed to serve trie nodes");
+            TrieNodesMessage { request_id, nodes: Vec::new() }
+        });
+        let _ = response.send(Ok(nodes));
+    }
+}
+
+/// An endless future.
+///
+/// This should be spawned or used as part of `tokio::select!`.
+impl<C> Future for SnapRequestHandler<C>
+where
+    C: HashedStateProviderFactory + HeaderProvider + BlockNumReader + Unpin,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        let mut acc = Duration::ZERO;
+        let maybe_more_incoming_requests = metered_poll_nested_stream_with_budget!(
+            acc,
+            "net::snap",
+            "Incoming snap requests stream",
+            DEFAULT_BUDGET_TRY_DRAIN_DOWNLOADERS,
+            this.incoming_requests.poll_next_unpin(cx),
+            |incoming| {
+                match incoming {
+                    IncomingSnapRequest::GetAccountRange { peer_id, request, response } => {
+                        this.on_account_range_request(peer_id, request, response)
+                    }
+                    IncomingSnapRequest::GetStorageRanges { peer_id, request, response } => {
+                        this.on_storage_ranges_request(peer_id, request, response)
+                    }
+                    IncomingSnapRequest::GetByteCodes { peer_id, request, response } => {
+                        this.on_bytecodes_request(peer_id, request, response)
+                    }
+                    IncomingSnapRequest::GetTrieNodes { peer_id, request, response } => {
+                        this.on_trie_nodes_request(peer_id, request, response)
+                    }
+                }
+            },
+        );
+
+        this.metrics.acc_duration_poll_snap_req_handler.set(acc.as_secs_f64());
+
+        // stream is fully drained and import futures pending
+        if maybe_more_incoming_requests {
+            // make sure we're woken up again
+            cx.waker().wake_by_ref();
+        }
+
+        Poll::Pending
+    }
+}
+
+/// The state roots of the recent canonical blocks, used to look up the requested state.
+#[derive(Debug, Default)]
+struct RecentStateRoots {
+    /// The canonical tip the state roots were collected for.
+    tip: BlockNumHash,
+    /// Block hashes by state root.
+    blocks: B256Map<B256>,
+}
+
+/// All `snap` requests delegated by the `snap` sub-protocol connections.
+#[derive(Debug)]
+pub enum IncomingSnapRequest {
+    /// Request a range of accounts from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetAccountRange {
+        /// The ID of the peer that requested the accounts.
+        peer_id: PeerId,
+        /// The requested account range.
+        request: GetAccountRangeMessage,
+        /// The channel sender for the response containing the accounts.
+        response: oneshot::Sender<RequestResult<AccountRangeMessage>>,
+    },
+    /// Request storage ranges of multiple accounts from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetStorageRanges {
+        /// The ID of the peer that requested the storage slots.
+        peer_id: PeerId,
+        /// The requested storage ranges.
+        request: GetStorageRangesMessage,
+        /// The channel sender for the response containing the storage slots.
+        response: oneshot::Sender<RequestResult<StorageRangesMessage>>,
+    },
+    /// Request contract codes from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetByteCodes {
+        /// The ID of the peer that requested the codes.
+        peer_id: PeerId,
+        /// The requested code hashes.
+        request: GetByteCodesMessage,
+        /// The channel sender for the response containing the codes.
+        response: oneshot::Sender<RequestResult<ByteCodesMessage>>,
+    },
+    /// Request trie nodes from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetTrieNodes {
+        /// The ID of the peer that requested the trie nodes.
+        peer_id: PeerId,
+        /// The requested trie node paths.
+        request: GetTrieNodesMessage,
+        /// The channel sender for the response containing the trie nodes.
+        response: oneshot::Sender<RequestResult<TrieNodesMessage>>,
+    },
+}
+
+/// Returns the effective response size limit for the requested soft limit.
+fn response_limit(response_bytes: u64) -> usize {
+    usize::try_from(response_bytes).unwrap_or(usize::MAX).min(SOFT_RESPONSE_LIMIT)
+}
+
+/// Returns the key that directly follows the given key, if any.
+fn next_key(key: B256) -> Option<B256> {
+    U256::from_be_bytes(key.0).checked_add(U256::from(1)).map(B256::from)
+}
+
+/// Encodes the account in the slim format used by the `snap` protocol.
+///
+/// The storage root and code hash are encoded as empty strings if the account has no storage or
+/// code respectively.
+fn slim_account_rlp(account: &Account, storage_root: B256) -> Bytes {
+    let storage_root: &[u8] =
+        if storage_root == EMPTY_ROOT_HASH { &[] } else { storage_root.as_slice() };
+    let code_hash = account.get_bytecode_hash();
+    let code_hash: &[u8] = if code_hash == KECCAK_EMPTY { &[] } else { code_hash.as_slice() };
+
+    let header = alloy_rlp::Header {
+        list: true,
+        payload_length: account.nonce.length() +
+            account.balance.length() +
+            storage_root.length() +
+            code_hash.length(),
+    };
+    let mut out = Vec::with_capacity(header.length_with_payload());
+    header.encode(&mut out);
+    account.nonce.encode(&mut out);
+    account.balance.encode(&mut out);
+    storage_root.encode(&mut out);
+    code_hash.encode(&mut out);
+    out.into()
+}
+
+/// Returns the proof nodes ordered by path.
+///
+/// Nodes that are embedded into their parent are omitted, except for the root node.
+fn proof_nodes(nodes: ProofNodes) -> Vec<Bytes> {
+    nodes
+        .into_nodes_sorted()
+        .into_iter()
+        .filter(|(path, node)| path.is_empty() || node.len() >= B256::len_bytes())
+        .map(|(_, node)| node)
+        .collect()
+}
+
+/// Decodes a hex-prefix (compact) encoded trie path.
+fn decode_compact_path(compact: &[u8]) -> Option<Nibbles> {
+    let Some((&first, rest)) = compact.split_first() else { return Some(Nibbles::default()) };
+
+    let flag = first >> 4;
+    if flag > 3 {
+        return None
+    }
+
+    let mut nibbles = Vec::with_capacity(rest.len() * 2 + 1);
+    if flag & 1 == 1 {
+        nibbles.push(first & 0x0f);
+    }
+    for byte in rest {
+        nibbles.push(byte >> 4);
+        nibbles.push(byte & 0x0f);
+    }
+
+    (nibbles.len() <= 64).then(|| Nibbles::from_nibbles_unchecked(nibbles))
+}
+
+/// Returns the key of the left-most leaf below the given path.
+fn path_to_key(path: &Nibbles) -> B256 {
+    let mut key = B256::ZERO;
+    path.pack_to(key.as_mut_slice());
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_primitives::b256;
+    use alloy_rlp::Decodable;
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_trie_common::{encode_path_leaf, TrieAccount};
+    use tokio::sync::mpsc;
+
+    #[test]
+    fn serves_recent_state_roots() {
+        let client = MockEthProvider::default();
+        let tip = SNAP_SERVE_RECENT_BLOCKS + 1;
+        let state_root = |number: u64| B256::from(U256::from(number + 1));
+        for number in 0..=tip {
+            let header = Header { number, state_root: state_root(number), ..Default::default() };
+            client.add_header(header.hash_slow(), header);
+        }
+        let handler = SnapRequestHandler::new(client, mpsc::channel(1).1);
+
+        // the tip and the blocks below it within the window are served
+        assert!(handler.state_for_root(state_root(tip)).unwrap().is_some());
+        assert!(handler.state_for_root(state_root(tip - 10)).unwrap().is_some());
+        assert!(handler
+            .state_for_root(state_root(tip + 1 - SNAP_SERVE_RECENT_BLOCKS))
+            .unwrap()
+            .is_some());
+
+        // older and unknown roots are not
+        assert!(handler
+            .state_for_root(state_root(tip - SNAP_SERVE_RECENT_BLOCKS))
+            .unwrap()
+            .is_none());
+        assert!(handler.state_for_root(B256::repeat_byte(0xaa)).unwrap().is_none());
+
+        let response = handler
+            .get_account_range_response(GetAccountRangeMessage {
+                request_id: 1,
+                root_hash: state_root(tip - 10),
+                starting_hash: B256::ZERO,
+                limit_hash: B256::repeat_byte(0xff),
+                response_bytes: 1024,
+            })
+            .unwrap();
+        assert_eq!(response.request_id, 1);
+    }
+
+    #[test]
+    fn decode_compact_paths() {
+        assert_eq!(decode_compact_path(&[]), Some(Nibbles::default()));
+        // even extension path
+        assert_eq!(decode_compact_path(&[0x00, 0x12]), Some(Nibbles::from_nibbles([1, 2])));
+        // odd extension path
+        assert_eq!(decode_compact_path(&[0x11, 0x23]), Some(Nibbles::from_nibbles([1, 2, 3])));
+        // odd leaf path
+        assert_eq!(decode_compact_path(&[0x3a]), Some(Nibbles::from_nibbles([0xa])));
+        // invalid flag
+        assert_eq!(decode_compact_path(&[0x40]), None);
+        // too long
+        assert_eq!(decode_compact_path(&[0x00; 34]), None);
+    }
+
+    #[test]
+    fn compact_path_roundtrip() {
+        let key = b256!("0x4c2f7a8e61ab4dfd2a6c2bb22e6b4d6b2e0d1a8f77c5f3b1f8ea91c3aa7b1230");
+        let path = Nibbles::unpack(key);
+        for len in [0, 1, 2, 7, 64] {
+            let prefix = path.slice(..len);
+            assert_eq!(decode_compact_path(&encode_path_leaf(&prefix, false)), Some(prefix));
+            assert!(path_to_key(&prefix) <= key);
+        }
+        assert_eq!(path_to_key(&path), key);
+    }
+
+    #[test]
+    fn slim_account_encoding() {
+        let account = Account { nonce: 1, balance: U256::from(2), bytecode_hash: None };
+        let slim = slim_account_rlp(&account, EMPTY_ROOT_HASH);
+        // list of nonce, balance and two empty strings
+        assert_eq!(slim.as_ref(), &[0xc4, 0x01, 0x02, 0x80, 0x80]);
+
+        let code_hash = B256::repeat_byte(0xcc);
+        let storage_root = B256::repeat_byte(0xaa);
+        let account = Account { nonce: 1, balance: U256::from(2), bytecode_hash: Some(code_hash) };
+        let slim = slim_account_rlp(&account, storage_root);
+        let full = TrieAccount::decode(&mut slim.as_ref()).unwrap();
+        assert_eq!(full, account.into_trie_account(storage_root));
+    }
+
+    #[test]
+    fn next_key_overflow() {
+        assert_eq!(next_key(B256::ZERO), Some(B256::with_last_byte(1)));
+        assert_eq!(next_key(B256::repeat_byte(0xff)), None);
+    }
+}
@@ -1,9 +1,14 @@
-use crate::{download::DownloadClient, error::PeerRequestResult, priority::Priority};
+use crate::{
+    download::DownloadClient,
+    error::{PeerRequestResult, RequestError},
+    priority::Priority,
+};
 use futures::Future;
 use reth_eth_wire_types::snap::{
     AccountRangeMessage, ByteCodesMessage, GetAccountRangeMessage, GetByteCodesMessage,
     GetStorageRangesMessage, GetTrieNodesMessage, StorageRangesMessage, TrieNodesMessage,
 };
+use reth_network_peers::PeerId;
 
 /// Response types for snap sync requests
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -74,3 +79,66 @@ pub trait SnapClient: DownloadClient {
         priority: Priority,
     ) -> Self::Output;
 }
+
+/// A snap client implementation that does nothing.
+///
+/// All requests fail, since there are no peers to send them to.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct NoopSnapClient;
+
+impl DownloadClient for NoopSnapClient {
+    fn report_bad_message(&self, _peer_id: PeerId) {}
+
+    fn num_connected_peers(&self) -> usize {
+        0
+    }
+}
+
+impl SnapClient for NoopSnapClient {
+    type Output = futures::future::Ready<PeerRequestResult<SnapResponse>>;
+
+    fn get_account_range_with_priority(
+        &self,
+        _request: GetAccountRangeMessage,
+        _priority: Priority,
+    ) -> Self::Output {
+        futures::future::err(RequestError::ChannelClosed)
+    }
+
+    fn get_storage_ranges(&self, request: GetStorageRangesMessage) -> Self::Output {
+        self.get_storage_ranges_with_priority(request, Priority::Normal)
+    }
+
+    fn get_storage_ranges_with_priority(
+        &self,
+        _request: GetStorageRangesMessage,
+        _priority: Priority,
+    ) -> Self::Output {
+        futures::future::err(RequestError::ChannelClosed)
+    }
+
+    fn get_byte_codes(&self, request: GetByteCodesMessage) -> Self::Output {
+        self.get_byte_codes_with_priority(request, Priority::Normal)
+    }
+
+    fn get_byte_codes_with_priority(
+        &self,
+        _request: GetByteCodesMessage,
+        _priority: Priority,
+    ) -> Self::Output {
+        futures::future::err(RequestError::ChannelClosed)
+    }
+
+    fn get_trie_nodes(&self, request: GetTrieNodesMessage) -> Self::Output {
+        self.get_trie_nodes_with_priority(request, Priority::Normal)
+    }
+
+    fn get_trie_nodes_with_priority(
+        &self,
+        _request: GetTrieNodesMessage,
+        _priority: Priority,
+    ) -> Self::Output {
+        futures::future::err(RequestError::ChannelClosed)
+    }
+}
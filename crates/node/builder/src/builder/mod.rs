@@ -37,7 +37,9 @@ use reth_provider::{
     ChainSpecProvider, FullProvider,
 };
 use reth_tasks::TaskExecutor;
-use reth_transaction_pool::{PoolConfig, PoolTransaction, TransactionPool};
+use reth_transaction_pool::{
+    history::TransactionHistoryConfig, PoolConfig, PoolTransaction, TransactionPool,
+};
 use secp256k1::SecretKey;
 use std::sync::Arc;
 use tracing::{info, trace, warn};
@@ -808,7 +810,14 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
 
     /// Returns the transaction pool config of the node.
     pub fn pool_config(&self) -> PoolConfig {
-        self.config().txpool.pool_config()
+        let pool_config = self.config().txpool.pool_config();
+        if self.config().txpool.history {
+            let history_config =
+                TransactionHistoryConfig::new(self.config().datadir().txpool_history())
+                    .with_max_events(self.config().txpool.history_max_events);
+            return pool_config.with_transaction_history(history_config)
+        }
+        pool_config
     }
 
     /// Loads `EnvKzgSettings::Default`.
@@ -913,10 +922,18 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         PropPolicy: TransactionPropagationPolicy<N>,
         AnnPolicy: AnnouncementFilteringPolicy<N>,
     {
-        let (handle, network, txpool, eth) = builder
+        let mut builder = builder
             .transactions_with_policies(pool, tx_config, propagation_policy, announcement_policy)
-            .request_handler(self.provider().clone())
-            .split_with_handle();
+            .request_handler(self.provider().clone());
+
+        if self.config().network.serve_snap {
+            let snap = builder.snap_request_handler(self.provider().clone());
+            self.executor.spawn_critical_blocking_task("p2p snap request handler", snap);
+        } else if self.config().network.snap_sync {
+            builder.snap();
+        }
+
+        let (handle, network, txpool, eth) = builder.split_with_handle();
 
         self.executor.spawn_critical_blocking_task("p2p txpool", txpool);
         self.executor.spawn_critical_blocking_task("p2p eth request handler", eth);
@@ -1,16 +1,22 @@
 //! Pool component for the node builder.
 
-use crate::{BuilderContext, FullNodeTypes};
+use crate::{
+    launch::config_reload::{watch_file, FILE_EVENTS_DEBOUNCE},
+    BuilderContext, FullNodeTypes,
+};
 use alloy_primitives::map::AddressSet;
 use reth_chain_state::CanonStateSubscriptions;
 use reth_chainspec::EthereumHardforks;
 use reth_node_api::{BlockTy, NodeTypes, TxTy};
+use reth_tracing::tracing::{info, warn};
 use reth_transaction_pool::{
-    blobstore::DiskFileBlobStore, BlobStore, CoinbaseTipOrdering, PoolConfig, PoolTransaction,
-    SubPoolLimit, TransactionOrdering, TransactionPool, TransactionValidationTaskExecutor,
-    TransactionValidator,
+    blobstore::DiskFileBlobStore,
+    validate::{AdmissionPolicy, AdmissionPolicyConfig},
+    BlobStore, CoinbaseTipOrdering, PoolConfig, PoolTransaction, SubPoolLimit, TransactionOrdering,
+    TransactionPool, TransactionValidationTaskExecutor, TransactionValidator,
 };
-use std::future::Future;
+use std::{future::Future, path::Path};
+use tokio::sync::mpsc;
 
 /// A type that knows how to build the transaction pool.
 pub trait PoolBuilder<Node: FullNodeTypes, Evm>: Send {
@@ -200,6 +206,8 @@ where
             reth_transaction_pool::Pool::new(validator, ordering, blob_store, pool_config.clone());
 
         spawn_maintenance_tasks(ctx, transaction_pool.clone(), &pool_config)?;
+        spawn_pool_journal_task(ctx, transaction_pool.clone());
+        spawn_transaction_history_task(ctx, transaction_pool.clone());
 
         Ok(transaction_pool)
     }
@@ -230,6 +238,50 @@ pub fn create_blob_store_with_cache<Node: FullNodeTypes>(
     Ok(reth_transaction_pool::blobstore::DiskFileBlobStore::open(data_dir.blobstore(), config)?)
 }
 
+/// Loads the admission rules of the file configured with `--txpool.admission-policy`, if any.
+///
+/// A task is spawned that reloads the rules whenever the file changes. If the changed file is
+/// invalid, the rules in effect are kept.
+pub fn create_admission_policy<Node: FullNodeTypes>(
+    ctx: &BuilderContext<Node>,
+) -> eyre::Result<Option<AdmissionPolicy>> {
+    let Some(path) = ctx.config().txpool.admission_policy.clone() else { return Ok(None) };
+
+    let policy = AdmissionPolicy::new(load_admission_policy(&path)?);
+    info!(target: "reth::cli", ?path, "Loaded transaction admission policy");
+
+    let (file_events_tx, mut file_events) = mpsc::unbounded_channel();
+    let watcher = watch_file(&path, file_events_tx)?;
+    let reloaded = policy.clone();
+    ctx.task_executor().spawn_task(async move {
+        // The watcher stops watching when dropped, so it's kept until the task exits.
+        let _watcher = watcher;
+        while file_events.recv().await.is_some() {
+            tokio::time::sleep(FILE_EVENTS_DEBOUNCE).await;
+            while file_events.try_recv().is_ok() {}
+
+            match load_admission_policy(&path) {
+                Ok(config) => {
+                    reloaded.update(config);
+                    info!(target: "reth::cli", ?path, "Reloaded transaction admission policy");
+                }
+                Err(err) => {
+                    warn!(target: "reth::cli", ?path, %err, "Failed to reload transaction admission policy, keeping the current one");
+                }
+            }
+        }
+    });
+
+    Ok(Some(policy))
+}
+
+/// Loads the admission rules from the TOML file at `path`.
+fn load_admission_policy(path: &Path) -> eyre::Result<AdmissionPolicyConfig> {
+    let contents = reth_fs_util::read_to_string(path)?;
+    toml::from_str(&contents)
+        .map_err(|e| eyre::eyre!("Failed to parse admission policy {}: {e}", path.display()))
+}
+
 /// Spawn local transaction backup task if enabled.
 fn spawn_local_backup_task<Node, Pool>(ctx: &BuilderContext<Node>, pool: Pool) -> eyre::Result<()>
 where
@@ -264,6 +316,41 @@ where
     Ok(())
 }
 
+/// Spawn the task that persists all pool transactions to the journal if enabled.
+pub fn spawn_pool_journal_task<Node, Pool>(ctx: &BuilderContext<Node>, pool: Pool)
+where
+    Node: FullNodeTypes,
+    Pool: TransactionPool<Transaction: reth_transaction_pool::EthPoolTransaction> + Clone + 'static,
+{
+    if !ctx.config().txpool.persist_pool {
+        return
+    }
+
+    let config = reth_transaction_pool::journal::PoolJournalConfig::new(
+        ctx.config().datadir().txpool_journal(),
+    )
+    .with_interval(ctx.config().txpool.persist_pool_interval);
+
+    ctx.task_executor()
+        .spawn_critical_with_graceful_shutdown_signal("txpool journal task", |shutdown| {
+            reth_transaction_pool::journal::pool_journal_task(shutdown, pool, config)
+        });
+}
+
+/// Spawn the task that records the events of pool transactions if the history is enabled.
+pub fn spawn_transaction_history_task<Node, Pool>(ctx: &BuilderContext<Node>, pool: Pool)
+where
+    Node: FullNodeTypes,
+    Pool: TransactionPool + 'static,
+{
+    if pool.transaction_history().is_none() {
+        return
+    }
+
+    ctx.task_executor()
+        .spawn_task(reth_transaction_pool::history::record_transaction_history(pool));
+}
+
 /// Spawn the main maintenance task for transaction pool.
 fn spawn_pool_maintenance_task<Node, Pool>(
     ctx: &BuilderContext<Node>,
@@ -45,6 +45,7 @@ use reth_db_api::{database::Database, database_metrics::DatabaseMetrics};
 use reth_db_common::init::{init_genesis_with_settings, InitStorageError};
 use reth_downloaders::{bodies::noop::NoopBodiesDownloader, headers::noop::NoopHeaderDownloader};
 use reth_engine_local::MiningMode;
+use reth_era_downloader::MergeBlock;
 use reth_evm::{noop::NoopEvmConfig, ConfigureEvm};
 use reth_exex::ExExManagerHandle;
 use reth_fs_util as fs;
@@ -950,10 +951,13 @@ where
     ///
     /// A target block hash if the pipeline is inconsistent, otherwise `None`.
     pub fn check_pipeline_consistency(&self) -> ProviderResult<Option<B256>> {
-        // We skip the era stage if it's not enabled
+        // We skip the era and snap sync stages if they're not enabled
         let era_enabled = self.era_import_source().is_some();
-        let mut all_stages =
-            StageId::ALL.into_iter().filter(|id| era_enabled || id != &StageId::Era);
+        let snap_sync_enabled = self.node_config().network.snap_sync;
+        let mut all_stages = StageId::ALL
+            .into_iter()
+            .filter(|id| era_enabled || id != &StageId::Era)
+            .filter(|id| snap_sync_enabled || id != &StageId::SnapSync);
 
         // Get the expected first stage based on config.
         let first_stage = all_stages.next().expect("there must be at least one stage");
@@ -1066,6 +1070,11 @@ where
             || node_config.chain.chain().kind().default_era_host(),
             || node_config.datadir().data_dir().join("era").into(),
         )
+        .map(|source| {
+            source
+                .with_historical_roots(node_config.era.historical_roots.clone())
+                .with_merge(MergeBlock::from_chain_id(node_config.chain.chain().id()))
+        })
     }
 
     /// Creates consensus layer health events stream based on node configuration.
@@ -1309,6 +1318,12 @@ mod tests {
                     storage_history_full: false,
                     storage_history_distance: None,
                     storage_history_before: None,
+                    address_transactions_full: false,
+                    address_transactions_distance: None,
+                    address_transactions_before: None,
+                    log_index_full: false,
+                    log_index_distance: None,
+                    log_index_before: None,
                     bodies_pre_merge: false,
                     bodies_distance: None,
                     receipts_log_filter: None,
@@ -0,0 +1,200 @@
+//! Reloading of the config file of a running node.
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use reth_chainspec::EthereumHardforks;
+use reth_config::{config::EtlConfig, Config, ConfigChanges, PruneConfig};
+use reth_network_api::{Peers, PeersConfig};
+use reth_node_core::{
+    config_reload::{ConfigReloadError, ConfigReloadRequest},
+    dirs::{ChainPath, DataDirPath},
+    node_config::NodeConfig,
+};
+use reth_tracing::tracing::{info, warn};
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tokio::sync::mpsc;
+
+/// Time to wait for more file events before reloading, editors usually write a file in several
+/// steps.
+pub(crate) const FILE_EVENTS_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// Reloads the config file on changes and on requests of a
+/// [`ConfigReloadHandle`](reth_node_core::config_reload::ConfigReloadHandle), and applies the
+/// settings that can change at runtime.
+///
+/// The CLI arguments take precedence over the config file like on launch.
+pub(crate) struct ConfigReloader<ChainSpec, N> {
+    /// Path of the config file.
+    path: PathBuf,
+    /// The node configuration, for the CLI arguments overriding the config file.
+    node_config: NodeConfig<ChainSpec>,
+    /// The data directory of the node.
+    data_dir: ChainPath<DataDirPath>,
+    /// The config currently in effect.
+    current: Config,
+    /// The network to apply the peers config to.
+    network: N,
+    /// Applies a new prune config to the pruner.
+    update_pruner: Box<dyn Fn(PruneConfig) + Send>,
+}
+
+impl<ChainSpec, N> std::fmt::Debug for ConfigReloader<ChainSpec, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConfigReloader").field("path", &self.path).finish_non_exhaustive()
+    }
+}
+
+impl<ChainSpec, N> ConfigReloader<ChainSpec, N>
+where
+    ChainSpec: EthereumHardforks + Send + Sync + 'static,
+    N: Peers + 'static,
+{
+    /// Creates a new reloader for the node that was launched with the given configs.
+    pub(crate) fn new(
+        node_config: NodeConfig<ChainSpec>,
+        data_dir: ChainPath<DataDirPath>,
+        toml_config: Config,
+        network: N,
+        update_pruner: Box<dyn Fn(PruneConfig) + Send>,
+    ) -> Self {
+        let path = node_config.config.clone().unwrap_or_else(|| data_dir.config());
+        let mut reloader =
+            Self { path, node_config, data_dir, current: toml_config, network, update_pruner };
+        reloader.current.peers = reloader.peers_with_cli_limits(reloader.current.peers.clone());
+        reloader.current.prune = reloader.prune_with_cli_args(reloader.current.prune.clone());
+        reloader
+    }
+
+    /// Reloads the config file and applies the settings that changed and can change at runtime.
+    pub(crate) fn reload(&mut self) -> Result<ConfigChanges, ConfigReloadError> {
+        let new = self.load().map_err(|err| ConfigReloadError::InvalidConfig(err.to_string()))?;
+        let changes = self
+            .current
+            .reload(new)
+            .map_err(|err| ConfigReloadError::InvalidConfig(err.to_string()))?;
+
+        if changes.applied.iter().any(|setting| setting.starts_with("peers")) {
+            self.network.apply_peers_config(self.current.peers.clone());
+        }
+        if changes.applied.iter().any(|setting| setting.starts_with("prune")) {
+            (self.update_pruner)(self.current.prune.clone());
+        }
+
+        if !changes.applied.is_empty() {
+            info!(target: "reth::cli", path = ?self.path, applied = ?changes.applied, "Configuration reloaded");
+        }
+        if !changes.requires_restart.is_empty() {
+            warn!(target: "reth::cli", path = ?self.path, settings = ?changes.requires_restart, "Changed settings require a restart to take effect");
+        }
+
+        Ok(changes)
+    }
+
+    /// Loads the config file and overrides settings according to the CLI arguments, like on
+    /// launch.
+    ///
+    /// Unlike on launch, a missing config file is an error and the file is never written.
+    fn load(&self) -> eyre::Result<Config> {
+        let mut config = Config::from_existing_path(&self.path)?;
+
+        config.prune.segments.migrate();
+        config.prune = self.prune_with_cli_args(config.prune);
+        config.prune.validate()?;
+        config.peers = self.peers_with_cli_limits(config.peers);
+        config.peers.trusted_nodes_only |= self.node_config.network.trusted_only;
+        config.peers.trusted_nodes.extend(self.node_config.network.trusted_peers.clone());
+        config.static_files = self
+            .node_config
+            .static_files
+            .merge_with_config(config.static_files, self.node_config.pruning.minimal);
+        config.static_files.validate()?;
+        if config.stages.etl.dir.is_none() {
+            config.stages.etl.dir = Some(EtlConfig::from_datadir(self.data_dir.data_dir()));
+        }
+
+        Ok(config)
+    }
+
+    /// Merges the prune CLI arguments, if any, with `prune`.
+    fn prune_with_cli_args(&self, prune: PruneConfig) -> PruneConfig {
+        let Some(mut node_prune_config) = self.node_config.prune_config() else { return prune };
+        node_prune_config.merge(prune);
+        node_prune_config
+    }
+
+    /// Overrides the peer limits of `peers` with the CLI arguments, if any.
+    fn peers_with_cli_limits(&self, peers: PeersConfig) -> PeersConfig {
+        peers
+            .with_max_inbound_opt(self.node_config.network.resolved_max_inbound_peers())
+            .with_max_outbound_opt(self.node_config.network.resolved_max_outbound_peers())
+    }
+
+    /// Reloads the config file whenever it changes or a reload is requested.
+    pub(crate) async fn run(
+        mut self,
+        mut requests: Option<mpsc::UnboundedReceiver<ConfigReloadRequest>>,
+    ) {
+        let (file_events_tx, mut file_events) = mpsc::unbounded_channel();
+        // The watcher stops watching when dropped, so it's kept until the loop exits.
+        let _watcher = match watch_file(&self.path, file_events_tx) {
+            Ok(watcher) => {
+                info!(target: "reth::cli", path = ?self.path, "Watching configuration file for changes");
+                Some(watcher)
+            }
+            Err(err) => {
+                warn!(target: "reth::cli", path = ?self.path, %err, "Failed to watch configuration file");
+                None
+            }
+        };
+
+        loop {
+            tokio::select! {
+                Some(()) = file_events.recv() => {
+                    tokio::time::sleep(FILE_EVENTS_DEBOUNCE).await;
+                    while file_events.try_recv().is_ok() {}
+
+                    if let Err(err) = self.reload() {
+                        warn!(target: "reth::cli", %err, "Failed to reload configuration, keeping the current one");
+                    }
+                }
+                Some(request) = async {
+                    match &mut requests {
+                        Some(requests) => requests.recv().await,
+                        None => std::future::pending().await,
+                    }
+                } => {
+                    let _ = request.send(self.reload());
+                }
+                else => break,
+            }
+        }
+    }
+}
+
+/// Watches the file at `path`, sending an event to `events` whenever it's written.
+///
+/// The parent directory is watched, so the file is still watched after being replaced.
+pub(crate) fn watch_file(
+    path: &Path,
+    events: mpsc::UnboundedSender<()>,
+) -> notify::Result<RecommendedWatcher> {
+    let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    let file_name = path.file_name().map(ToOwned::to_owned);
+
+    let mut watcher = RecommendedWatcher::new(
+        move |res: notify::Result<notify::Event>| {
+            if let Ok(event) = res &&
+                matches!(event.kind, notify::EventKind::Modify(_) | notify::EventKind::Create(_)) &&
+                event.paths.iter().any(|path| path.file_name() == file_name.as_deref())
+            {
+                let _ = events.send(());
+            }
+        },
+        notify::Config::default(),
+    )?;
+    watcher.watch(dir, RecursiveMode::NonRecursive)?;
+
+    Ok(watcher)
+}
@@ -9,9 +9,10 @@ use reth_consensus_debug_client::{
 };
 use reth_engine_local::{LocalMiner, MiningMode};
 use reth_node_api::{
-    BlockTy, FullNodeComponents, FullNodeTypes, HeaderTy, PayloadAttrTy, PayloadAttributesBuilder,
-    PayloadTypes,
+    BlockTy, ConfigureEvm, FullNodeComponents, FullNodeTypes, HeaderTy, PayloadAttrTy,
+    PayloadAttributesBuilder, PayloadTypes,
 };
+use reth_transaction_pool::TransactionPool;
 use std::{
     future::{Future, IntoFuture},
     pin::Pin,
@@ -77,6 +78,21 @@ pub trait DebugNode<N: FullNodeComponents>: Node<N> {
     fn local_payload_attributes_builder(
         chain_spec: &Self::ChainSpec,
     ) -> impl PayloadAttributesBuilder<<Self::Payload as PayloadTypes>::PayloadAttributes, HeaderTy<Self>>;
+
+    /// Configures the `LocalMiner` before it is spawned in dev mode.
+    ///
+    /// Nodes whose payload attributes implement
+    /// [`LocalPayloadAttributes`](reth_engine_local::LocalPayloadAttributes) can enable
+    /// [`LocalMiner::with_local_payload_attributes`] here, which allows the `anvil` namespace to
+    /// adjust the timestamp, fee recipient and `PREVRANDAO` of the next blocks.
+    fn configure_local_miner<B, Pool>(
+        miner: LocalMiner<Self::Payload, B, Pool>,
+    ) -> LocalMiner<Self::Payload, B, Pool>
+    where
+        Pool: TransactionPool + Unpin,
+    {
+        miner
+    }
 }
 
 /// Node launcher with support for launching various debugging utilities.
@@ -306,7 +322,9 @@ where
             };
 
             let dev_mining_mode =
-                mining_mode.unwrap_or_else(|| handle.node.config.dev_mining_mode(pool));
+                mining_mode.unwrap_or_else(|| handle.node.config.dev_mining_mode(pool.clone()));
+            let state_overrides = handle.node.evm_config.state_overrides().cloned();
+            let miner_handle = handle.node.add_ons_handle.local_miner().cloned();
             let payload_wait_time = config.dev.payload_wait_time;
             if let (Some(wait_time), Some(block_time)) = (payload_wait_time, config.dev.block_time)
             {
@@ -316,7 +334,7 @@ where
                 );
             }
             handle.node.task_executor.spawn_critical_task("local engine", async move {
-                LocalMiner::new(
+                let miner = LocalMiner::new(
                     blockchain_db,
                     builder,
                     beacon_engine_handle,
@@ -324,8 +342,13 @@ where
                     payload_builder_handle,
                 )
                 .with_payload_wait_time_opt(payload_wait_time)
-                .run()
-                .await
+                .with_pool(pool)
+                .with_state_overrides(state_overrides);
+                let mut miner = <N::Types as DebugNode<N>>::configure_local_miner(miner);
+                if let Some(miner_handle) = &miner_handle {
+                    miner = miner.with_handle(miner_handle);
+                }
+                miner.run().await
             });
         }
 
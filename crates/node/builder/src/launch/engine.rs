@@ -1,5 +1,6 @@
 //! Engine node related functionality.
 
+use super::config_reload::ConfigReloader;
 use crate::{
     common::{Attached, LaunchContextWith, WithConfigs},
     hooks::NodeHooks,
@@ -20,7 +21,7 @@ use reth_engine_tree::{
 use reth_engine_util::EngineMessageStreamExt;
 use reth_exex::ExExManagerHandle;
 use reth_network::{types::BlockRangeUpdate, NetworkSyncUpdater, SyncState};
-use reth_network_api::BlockDownloaderProvider;
+use reth_network_api::{BlockDownloaderProvider, SnapDownloaderProvider};
 use reth_node_api::{
     BuiltPayload, ConsensusEngineHandle, FullNodeTypes, NodeTypes, NodeTypesWithDBAdapter,
 };
@@ -34,6 +35,7 @@ use reth_provider::{
     providers::{BlockchainProvider, NodeTypesForProvider},
     BlockNumReader, StorageSettingsCache,
 };
+use reth_prune::PrunerBuilder;
 use reth_tasks::TaskExecutor;
 use reth_tokio_util::EventSender;
 use reth_tracing::tracing::{debug, error, info};
@@ -78,7 +80,7 @@ impl EngineNodeLauncher {
         AO: RethRpcAddOns<NodeAdapter<T, CB::Components>>
             + EngineValidatorAddOn<NodeAdapter<T, CB::Components>>,
     {
-        let Self { ctx, engine_tree_config } = self;
+        let Self { ctx, mut engine_tree_config } = self;
         let NodeBuilderWithComponents {
             adapter: NodeTypesAdapter { database },
             rocksdb_provider,
@@ -88,6 +90,14 @@ impl EngineNodeLauncher {
         } = target;
         let NodeHooks { on_component_initialized, on_node_started, .. } = hooks;
 
+        if config.dev.dev {
+            // reverting a dev chain to a snapshot unwinds the canonical chain to an ancestor of
+            // the current head
+            engine_tree_config = engine_tree_config
+                .with_always_process_payload_attributes_on_canonical_head(true)
+                .with_unwind_canonical_header(true);
+        }
+
         // Create changeset cache that will be shared across the engine
         let changeset_cache = ChangesetCache::new();
 
@@ -159,6 +169,7 @@ impl EngineNodeLauncher {
             ctx.components().evm_config().clone(),
             maybe_exex_manager_handle.clone().unwrap_or_else(ExExManagerHandle::empty),
             ctx.era_import_source(),
+            ctx.node_config().network.snap_sync.then(|| network_handle.snap_client()),
         )?;
 
         // The new engine writes directly to static files. This ensures that they're up to the tip.
@@ -171,7 +182,10 @@ impl EngineNodeLauncher {
             pruner_builder =
                 pruner_builder.finished_exex_height(exex_manager_handle.finished_height());
         }
-        let pruner = pruner_builder.build_with_provider_factory(ctx.provider_factory().clone());
+        let (pruner_config_tx, pruner_config_rx) = unbounded_channel();
+        let pruner = pruner_builder
+            .build_with_provider_factory(ctx.provider_factory().clone())
+            .with_config_updates(pruner_config_rx);
         let pruner_events = pruner.events();
         info!(target: "reth::cli", prune_config=?ctx.prune_config(), "Pruner initialized");
 
@@ -275,6 +289,23 @@ impl EngineNodeLauncher {
             engine_shutdown: _,
         } = add_ons.launch_add_ons(add_ons_ctx).await?;
 
+        // Reload the config file on changes and on `admin_reloadConfig` requests
+        let provider_factory = ctx.provider_factory().clone();
+        let config_reloader = ConfigReloader::new(
+            ctx.node_config().clone(),
+            ctx.data_dir().clone(),
+            ctx.toml_config().clone(),
+            ctx.components().network().clone(),
+            Box::new(move |prune_config| {
+                let update =
+                    PrunerBuilder::new(prune_config).build_config_update(&provider_factory);
+                let _ = pruner_config_tx.send(update);
+            }),
+        );
+        let config_reload_requests =
+            rpc_registry.config_reload_handle().and_then(|handle| handle.attach());
+        ctx.task_executor().spawn_task(config_reloader.run(config_reload_requests));
+
         // Create engine shutdown handle
         let (engine_shutdown, shutdown_rx) = EngineShutdown::new();
 
@@ -1,6 +1,7 @@
 //! Abstraction for launching a node.
 
 pub mod common;
+pub(crate) mod config_reload;
 mod exex;
 pub mod invalid_block_hook;
 
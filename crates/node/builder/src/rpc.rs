@@ -22,6 +22,7 @@ use jsonrpsee::RpcModule;
 use parking_lot::Mutex;
 use reth_chain_state::CanonStateSubscriptions;
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks, Hardforks};
+use reth_engine_local::LocalMinerHandle;
 use reth_node_api::{
     AddOnsContext, BlockTy, EngineApiValidator, EngineTypes, FullNodeComponents, FullNodeTypes,
     NodeAddOns, NodeTypes, PayloadTypes, PayloadValidator, PrimitivesTy, TreeConfig,
@@ -43,7 +44,7 @@ use reth_rpc_builder::{
     RpcModuleBuilder, RpcRegistryInner, RpcServerConfig, RpcServerHandle, TransportRpcModules,
 };
 use reth_rpc_engine_api::{capabilities::EngineCapabilities, EngineApi};
-use reth_rpc_eth_types::{cache::cache_new_blocks_task, EthConfig, EthStateCache};
+use reth_rpc_eth_types::{cache::cache_new_blocks_task, EraHistory, EthConfig, EthStateCache};
 use reth_tokio_util::EventSender;
 use reth_tracing::tracing::{debug, info};
 use std::{
@@ -218,6 +219,21 @@ pub struct RpcRegistry<Node: FullNodeComponents, EthApi: EthApiTypes> {
         Node::Evm,
         Node::Consensus,
     >,
+    /// Handle to the local miner, only set in dev mode.
+    pub(crate) local_miner: Option<LocalMinerHandle>,
+}
+
+impl<Node, EthApi> RpcRegistry<Node, EthApi>
+where
+    Node: FullNodeComponents,
+    EthApi: EthApiTypes,
+{
+    /// Returns the handle to the local miner that drives the chain in dev mode.
+    ///
+    /// This is `None` if the node is not running in dev mode.
+    pub const fn local_miner(&self) -> Option<&LocalMinerHandle> {
+        self.local_miner.as_ref()
+    }
 }
 
 impl<Node, EthApi> Deref for RpcRegistry<Node, EthApi>
@@ -1103,9 +1119,14 @@ where
 
         info!(target: "reth::cli", "Engine API handler initialized");
 
-        let cache = EthStateCache::spawn_with(
+        let EthConfig { cache, mut era_history, .. } = config.rpc.eth_config();
+        if era_history.dir.is_none() && era_history.url.is_some() {
+            era_history.dir = Some(config.datadir().era_history());
+        }
+        let cache = EthStateCache::spawn_with_era_history(
             node.provider().clone(),
-            config.rpc.eth_config().cache,
+            cache,
+            EraHistory::new(era_history),
             node.task_executor().clone(),
         );
 
@@ -1125,7 +1146,12 @@ where
         let eth_api = eth_api_builder.build_eth_api(ctx).await?;
 
         let auth_config = config.rpc.auth_server_config(jwt_secret)?;
-        let module_config = config.rpc.transport_rpc_module_config();
+        let mut module_config = config.rpc.transport_rpc_module_config();
+        if let Some(eth_config) = module_config.config_mut().as_mut().map(|c| c.eth_mut()) &&
+            eth_config.debug_trace_dir.is_none()
+        {
+            eth_config.debug_trace_dir = Some(config.datadir().debug_traces());
+        }
         debug!(target: "reth::cli", http=?module_config.http(), ws=?module_config.ws(), "Using RPC module config");
 
         let (mut modules, mut auth_module, registry) = RpcModuleBuilder::default()
@@ -1149,7 +1175,11 @@ where
             registry.eth_api().signers().write().extend(signers);
         }
 
-        let mut registry = RpcRegistry { registry };
+        // in dev mode the local miner is controlled through this handle, it is attached once the
+        // miner is spawned
+        let local_miner = config.dev.dev.then(LocalMinerHandle::new);
+
+        let mut registry = RpcRegistry { registry, local_miner };
         let ctx = RpcContext {
             node: node.clone(),
             config,
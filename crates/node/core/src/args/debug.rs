@@ -67,7 +67,7 @@ pub struct DebugArgs {
     pub reorg_depth: Option<usize>,
 
     /// The path to store engine API messages at.
-    /// If specified, all of the intercepted engine API messages
+    /// If specified, all of the intercepted engine API messages and the engine responses to them
     /// will be written to specified location.
     #[arg(long = "debug.engine-api-store", help_heading = "Debug", value_name = "PATH")]
     pub engine_api_store: Option<PathBuf>,
@@ -18,6 +18,13 @@ pub struct EraArgs {
     /// Describes where to get the ERA files to import from.
     #[clap(flatten)]
     pub source: EraSourceArgs,
+
+    /// The path to a file with the known historical roots of the beacon chain.
+    ///
+    /// One hex encoded root per line, the root of era `N` being on line `N - 1`. The ERA files
+    /// past the merge are verified against these roots and the import fails without them.
+    #[arg(long = "era.historical-roots", value_name = "PATH", verbatim_doc_comment)]
+    pub historical_roots: Option<Box<Path>>,
 }
 
 /// Arguments for the block history import based on ERA1 encoded files.
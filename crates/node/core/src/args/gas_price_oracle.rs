@@ -1,9 +1,10 @@
 use alloy_primitives::U256;
 use clap::Args;
-use reth_rpc_eth_types::GasPriceOracleConfig;
+use reth_rpc_eth_types::{FeeEstimatePercentiles, GasPriceOracleConfig};
 use reth_rpc_server_types::constants::gas_oracle::{
-    DEFAULT_GAS_PRICE_BLOCKS, DEFAULT_GAS_PRICE_PERCENTILE, DEFAULT_IGNORE_GAS_PRICE,
-    DEFAULT_MAX_GAS_PRICE,
+    DEFAULT_FAST_FEE_PERCENTILE, DEFAULT_GAS_PRICE_BLOCKS, DEFAULT_GAS_PRICE_PERCENTILE,
+    DEFAULT_IGNORE_GAS_PRICE, DEFAULT_MAX_GAS_PRICE, DEFAULT_SLOW_FEE_PERCENTILE,
+    DEFAULT_STANDARD_FEE_PERCENTILE,
 };
 
 /// Parameters to configure Gas Price Oracle
@@ -29,18 +30,44 @@ pub struct GasPriceOracleArgs {
     /// The default gas price to use if there are no blocks to use
     #[arg(long = "gpo.default-suggested-fee")]
     pub default_suggested_fee: Option<U256>,
+
+    /// The percentile of fees to use for the slow estimate of `reth_feeEstimates`
+    #[arg(long = "gpo.slow-percentile", default_value_t = DEFAULT_SLOW_FEE_PERCENTILE)]
+    pub slow_percentile: u32,
+
+    /// The percentile of fees to use for the standard estimate of `reth_feeEstimates`
+    #[arg(long = "gpo.standard-percentile", default_value_t = DEFAULT_STANDARD_FEE_PERCENTILE)]
+    pub standard_percentile: u32,
+
+    /// The percentile of fees to use for the fast estimate of `reth_feeEstimates`
+    #[arg(long = "gpo.fast-percentile", default_value_t = DEFAULT_FAST_FEE_PERCENTILE)]
+    pub fast_percentile: u32,
 }
 
 impl GasPriceOracleArgs {
     /// Returns a [`GasPriceOracleConfig`] from the arguments.
     pub fn gas_price_oracle_config(&self) -> GasPriceOracleConfig {
-        let Self { blocks, ignore_price, max_price, percentile, default_suggested_fee } = self;
+        let Self {
+            blocks,
+            ignore_price,
+            max_price,
+            percentile,
+            default_suggested_fee,
+            slow_percentile,
+            standard_percentile,
+            fast_percentile,
+        } = self;
         GasPriceOracleConfig {
             max_price: Some(U256::from(*max_price)),
             ignore_price: Some(U256::from(*ignore_price)),
             percentile: *percentile,
             blocks: *blocks,
             default_suggested_fee: *default_suggested_fee,
+            fee_estimate_percentiles: FeeEstimatePercentiles {
+                slow: *slow_percentile,
+                standard: *standard_percentile,
+                fast: *fast_percentile,
+            },
             ..Default::default()
         }
     }
@@ -54,6 +81,9 @@ impl Default for GasPriceOracleArgs {
             max_price: DEFAULT_MAX_GAS_PRICE.to(),
             percentile: DEFAULT_GAS_PRICE_PERCENTILE,
             default_suggested_fee: None,
+            slow_percentile: DEFAULT_SLOW_FEE_PERCENTILE,
+            standard_percentile: DEFAULT_STANDARD_FEE_PERCENTILE,
+            fast_percentile: DEFAULT_FAST_FEE_PERCENTILE,
         }
     }
 }
@@ -80,6 +110,9 @@ mod tests {
                 max_price: DEFAULT_MAX_GAS_PRICE.to(),
                 percentile: DEFAULT_GAS_PRICE_PERCENTILE,
                 default_suggested_fee: None,
+                slow_percentile: DEFAULT_SLOW_FEE_PERCENTILE,
+                standard_percentile: DEFAULT_STANDARD_FEE_PERCENTILE,
+                fast_percentile: DEFAULT_FAST_FEE_PERCENTILE,
             }
         );
     }
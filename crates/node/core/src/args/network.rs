@@ -274,7 +274,7 @@ pub struct NetworkArgs {
     #[arg(long, verbatim_doc_comment)]
     pub no_persist_peers: bool,
 
-    /// NAT resolution method (any|none|upnp|publicip|extip:\<IP\>)
+    /// NAT resolution method (any|none|upnp|natpmp|publicip|extip:\<IP\>)
     #[arg(long, default_value_t = DefaultNetworkArgs::get_global().nat.clone())]
     pub nat: NatResolver,
 
@@ -414,6 +414,21 @@ pub struct NetworkArgs {
     /// networks that pollute the discovery table.
     #[arg(long, default_value_t = DefaultNetworkArgs::get_global().enforce_enr_fork_id)]
     pub enforce_enr_fork_id: bool,
+
+    /// Serve the `snap/1` protocol to peers.
+    ///
+    /// Requests are answered from the states of the latest 128 blocks, which allows other clients
+    /// to snap sync from this node.
+    #[arg(long = "serve-snap")]
+    pub serve_snap: bool,
+
+    /// Sync the state of a recent block over the `snap/1` protocol instead of executing all
+    /// historical blocks.
+    ///
+    /// Only takes effect on a node that has not executed any blocks yet, and requires the v2
+    /// storage layout.
+    #[arg(long = "snap-sync")]
+    pub snap_sync: bool,
 }
 
 impl NetworkArgs {
@@ -699,6 +714,8 @@ impl Default for NetworkArgs {
             eth_max_message_size: None,
             netrestrict: None,
             enforce_enr_fork_id,
+            serve_snap: false,
+            snap_sync: false,
         }
     }
 }
@@ -75,6 +75,8 @@ impl Default for DefaultPruningValues {
                 storage_history: Some(PruneMode::Distance(MINIMUM_UNWIND_SAFE_DISTANCE)),
                 // This field is ignored when full_bodies_history_use_pre_merge is true
                 bodies_history: None,
+                address_transactions: Some(PruneMode::Full),
+                log_index: Some(PruneMode::Full),
                 receipts_log_filter: Default::default(),
             },
             full_bodies_history_use_pre_merge: true,
@@ -85,6 +87,8 @@ impl Default for DefaultPruningValues {
                 account_history: Some(PruneMode::Distance(MINIMUM_UNWIND_SAFE_DISTANCE)),
                 storage_history: Some(PruneMode::Distance(MINIMUM_UNWIND_SAFE_DISTANCE)),
                 bodies_history: Some(PruneMode::Distance(MINIMUM_UNWIND_SAFE_DISTANCE)),
+                address_transactions: Some(PruneMode::Full),
+                log_index: Some(PruneMode::Full),
                 receipts_log_filter: Default::default(),
             },
         }
@@ -103,7 +107,8 @@ pub struct PruningArgs {
     /// Run minimal storage mode with maximum pruning and smaller static files.
     ///
     /// This mode configures the node to use minimal disk space by:
-    /// - Fully pruning sender recovery, transaction lookup, receipts
+    /// - Fully pruning sender recovery, transaction lookup, address transactions, log index,
+    ///   receipts
     /// - Leaving 10,064 blocks for account, storage history and block bodies
     /// - Using 10,000 blocks per static file segment
     #[arg(long, default_value_t = false, conflicts_with = "full")]
@@ -197,6 +202,32 @@ pub struct PruningArgs {
     #[arg(long = "prune.bodies.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["bodies_distance", "bodies_pre_merge"])]
     pub bodies_before: Option<BlockNumber>,
 
+    // Address Transactions
+    /// Prunes all address transactions index data.
+    #[arg(long = "prune.address-transactions.full", conflicts_with_all = &["address_transactions_distance", "address_transactions_before"])]
+    pub address_transactions_full: bool,
+    /// Prune address transactions index data before the `head-N` block number. In other words,
+    /// keep last N + 1 blocks.
+    #[arg(long = "prune.address-transactions.distance", value_name = "BLOCKS", conflicts_with_all = &["address_transactions_full", "address_transactions_before"])]
+    pub address_transactions_distance: Option<u64>,
+    /// Prune address transactions index data before the specified block number. The specified
+    /// block number is not pruned.
+    #[arg(long = "prune.address-transactions.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["address_transactions_full", "address_transactions_distance"])]
+    pub address_transactions_before: Option<BlockNumber>,
+
+    // Log Index
+    /// Prunes all log index data.
+    #[arg(long = "prune.log-index.full", conflicts_with_all = &["log_index_distance", "log_index_before"])]
+    pub log_index_full: bool,
+    /// Prune log index data before the `head-N` block number. In other words, keep last N + 1
+    /// blocks.
+    #[arg(long = "prune.log-index.distance", value_name = "BLOCKS", conflicts_with_all = &["log_index_full", "log_index_before"])]
+    pub log_index_distance: Option<u64>,
+    /// Prune log index data before the specified block number. The specified block number is not
+    /// pruned.
+    #[arg(long = "prune.log-index.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["log_index_full", "log_index_distance"])]
+    pub log_index_before: Option<BlockNumber>,
+
     /// Minimum pruning distance from the tip. This controls the safety margin for reorgs and
     /// manual unwinds.
     #[arg(long = "prune.minimum-distance", value_name = "BLOCKS")]
@@ -266,6 +297,12 @@ impl PruningArgs {
         if let Some(mode) = self.storage_history_prune_mode() {
             config.segments.storage_history = Some(mode);
         }
+        if let Some(mode) = self.address_transactions_prune_mode() {
+            config.segments.address_transactions = Some(mode);
+        }
+        if let Some(mode) = self.log_index_prune_mode() {
+            config.segments.log_index = Some(mode);
+        }
         if let Some(receipt_logs) =
             self.receipts_log_filter.as_ref().filter(|c| !c.is_empty()).cloned()
         {
@@ -359,6 +396,30 @@ impl PruningArgs {
             None
         }
     }
+
+    const fn address_transactions_prune_mode(&self) -> Option<PruneMode> {
+        if self.address_transactions_full {
+            Some(PruneMode::Full)
+        } else if let Some(distance) = self.address_transactions_distance {
+            Some(PruneMode::Distance(distance))
+        } else if let Some(block_number) = self.address_transactions_before {
+            Some(PruneMode::Before(block_number))
+        } else {
+            None
+        }
+    }
+
+    const fn log_index_prune_mode(&self) -> Option<PruneMode> {
+        if self.log_index_full {
+            Some(PruneMode::Full)
+        } else if let Some(distance) = self.log_index_distance {
+            Some(PruneMode::Distance(distance))
+        } else if let Some(block_number) = self.log_index_before {
+            Some(PruneMode::Before(block_number))
+        } else {
+            None
+        }
+    }
 }
 
 /// Parses `,` separated pruning info into [`ReceiptsLogPruneConfig`].
@@ -663,6 +663,13 @@ pub struct RpcServerArgs {
     /// By default this is disabled, meaning transactions are submitted as-is.
     #[arg(long = "rpc.force-blob-sidecar-upcasting", default_value_t = false)]
     pub rpc_force_blob_sidecar_upcasting: bool,
+
+    /// Directory `debug_standardTraceBlockToFile` and `debug_standardTraceBadBlockToFile` write
+    /// their trace files to.
+    ///
+    /// Defaults to `<DIR>/<CHAIN_ID>/debug_traces`.
+    #[arg(long = "rpc.debug-trace-dir", value_name = "PATH")]
+    pub rpc_debug_trace_dir: Option<PathBuf>,
 }
 
 impl RpcServerArgs {
@@ -896,6 +903,7 @@ impl Default for RpcServerArgs {
             testing_skip_invalid_transactions: true,
             testing_gas_limit: None,
             rpc_force_blob_sidecar_upcasting: false,
+            rpc_debug_trace_dir: None,
         }
     }
 }
@@ -1062,6 +1070,10 @@ mod tests {
                 max_headers: 1000,
                 max_concurrent_db_requests: 512,
                 max_cached_tx_hashes: 30_000,
+                era_dir: None,
+                era_url: None,
+                max_era_files: 4,
+                max_era_downloads_per_hour: 16,
             },
             gas_price_oracle: GasPriceOracleArgs {
                 blocks: 20,
@@ -1069,11 +1081,15 @@ mod tests {
                 max_price: 500_000_000_000,
                 percentile: 60,
                 default_suggested_fee: None,
+                slow_percentile: 25,
+                standard_percentile: 50,
+                fast_percentile: 90,
             },
             rpc_send_raw_transaction_sync_timeout: std::time::Duration::from_secs(30),
             testing_skip_invalid_transactions: true,
             testing_gas_limit: None,
             rpc_force_blob_sidecar_upcasting: false,
+            rpc_debug_trace_dir: None,
         };
 
         let parsed_args = CommandParser::<RpcServerArgs>::parse_from([
@@ -1,8 +1,11 @@
 use clap::Args;
 use reth_rpc_server_types::constants::cache::{
     DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_CONCURRENT_DB_REQUESTS, DEFAULT_HEADER_CACHE_MAX_LEN,
-    DEFAULT_MAX_CACHED_TX_HASHES, DEFAULT_RECEIPT_CACHE_MAX_LEN,
+    DEFAULT_MAX_CACHED_ERA_FILES, DEFAULT_MAX_CACHED_TX_HASHES, DEFAULT_MAX_ERA_DOWNLOADS_PER_HOUR,
+    DEFAULT_RECEIPT_CACHE_MAX_LEN,
 };
+use std::path::PathBuf;
+use url::Url;
 
 /// Parameters to configure RPC state cache.
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
@@ -43,6 +46,35 @@ pub struct RpcStateCacheArgs {
         default_value_t = DEFAULT_MAX_CACHED_TX_HASHES,
     )]
     pub max_cached_tx_hashes: u32,
+
+    /// Directory of ERA1 files that blocks expired from the database are served from.
+    ///
+    /// Files downloaded from `--rpc-cache.era-url` are stored in its `downloads` subdirectory,
+    /// which is cleared on startup. Defaults to `<DIR>/<CHAIN_ID>/era1` if only the URL is set.
+    #[arg(long = "rpc-cache.era-dir", value_name = "PATH")]
+    pub era_dir: Option<PathBuf>,
+
+    /// The URL to a remote host that ERA1 files are downloaded from on demand.
+    #[arg(long = "rpc-cache.era-url", value_name = "URL")]
+    pub era_url: Option<Url>,
+
+    /// Max number of ERA1 files in use at the same time.
+    ///
+    /// Downloaded files are deleted once they are no longer in use.
+    #[arg(
+        long = "rpc-cache.max-era-files",
+        default_value_t = DEFAULT_MAX_CACHED_ERA_FILES,
+    )]
+    pub max_era_files: u32,
+
+    /// Max number of ERA1 files downloaded from `--rpc-cache.era-url` per hour.
+    ///
+    /// Requests for expired blocks in other files fail once the limit is reached.
+    #[arg(
+        long = "rpc-cache.max-era-downloads-per-hour",
+        default_value_t = DEFAULT_MAX_ERA_DOWNLOADS_PER_HOUR,
+    )]
+    pub max_era_downloads_per_hour: u32,
 }
 
 impl RpcStateCacheArgs {
@@ -62,6 +94,10 @@ impl Default for RpcStateCacheArgs {
             max_headers: DEFAULT_HEADER_CACHE_MAX_LEN,
             max_concurrent_db_requests: DEFAULT_CONCURRENT_DB_REQUESTS,
             max_cached_tx_hashes: DEFAULT_MAX_CACHED_TX_HASHES,
+            era_dir: None,
+            era_url: None,
+            max_era_files: DEFAULT_MAX_CACHED_ERA_FILES,
+            max_era_downloads_per_hour: DEFAULT_MAX_ERA_DOWNLOADS_PER_HOUR,
         }
     }
 }
@@ -50,4 +50,12 @@ pub enum StageEnum {
     ///
     /// Manages historical data related to storage.
     StorageHistory,
+    /// The address transactions stage within the pipeline.
+    ///
+    /// Indexes the transactions that touched each address.
+    AddressTransactions,
+    /// The log index stage within the pipeline.
+    ///
+    /// Indexes the logs of each block by emitter and topics.
+    LogIndex,
 }
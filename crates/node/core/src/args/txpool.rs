@@ -7,6 +7,9 @@ use clap::{builder::Resettable, Args};
 use reth_cli_util::{parse_duration_from_secs_or_ms, parsers::format_duration_as_secs_or_ms};
 use reth_transaction_pool::{
     blobstore::disk::DEFAULT_MAX_CACHED_BLOBS,
+    bundle::{BundlePoolConfig, DEFAULT_MAX_BUNDLES},
+    history::DEFAULT_MAX_HISTORY_EVENTS,
+    journal::DEFAULT_JOURNAL_INTERVAL,
     maintain::MAX_QUEUED_TRANSACTION_LIFETIME,
     pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
     validate::DEFAULT_MAX_TX_INPUT_BYTES,
@@ -55,6 +58,13 @@ pub struct DefaultTxPoolValues {
     transactions_backup_path: Option<PathBuf>,
     disable_transactions_backup: bool,
     max_batch_size: usize,
+    enable_bundles: bool,
+    max_bundles: usize,
+    admission_policy: Option<PathBuf>,
+    persist_pool: bool,
+    persist_pool_interval: Duration,
+    history: bool,
+    history_max_events: usize,
 }
 
 impl DefaultTxPoolValues {
@@ -247,6 +257,48 @@ impl DefaultTxPoolValues {
         self.max_batch_size = v;
         self
     }
+
+    /// Set whether to enable the bundle pool by default
+    pub const fn with_enable_bundles(mut self, v: bool) -> Self {
+        self.enable_bundles = v;
+        self
+    }
+
+    /// Set the default max number of bundles
+    pub const fn with_max_bundles(mut self, v: usize) -> Self {
+        self.max_bundles = v;
+        self
+    }
+
+    /// Set the default admission policy file path
+    pub fn with_admission_policy(mut self, v: Option<PathBuf>) -> Self {
+        self.admission_policy = v;
+        self
+    }
+
+    /// Set whether to persist the whole pool by default
+    pub const fn with_persist_pool(mut self, v: bool) -> Self {
+        self.persist_pool = v;
+        self
+    }
+
+    /// Set the default interval at which the pool journal is written
+    pub const fn with_persist_pool_interval(mut self, v: Duration) -> Self {
+        self.persist_pool_interval = v;
+        self
+    }
+
+    /// Set whether to record the history of transaction events by default
+    pub const fn with_history(mut self, v: bool) -> Self {
+        self.history = v;
+        self
+    }
+
+    /// Set the default maximum number of events kept in the transaction history
+    pub const fn with_history_max_events(mut self, v: usize) -> Self {
+        self.history_max_events = v;
+        self
+    }
 }
 
 impl Default for DefaultTxPoolValues {
@@ -282,6 +334,13 @@ impl Default for DefaultTxPoolValues {
             transactions_backup_path: None,
             disable_transactions_backup: false,
             max_batch_size: 1,
+            enable_bundles: false,
+            max_bundles: DEFAULT_MAX_BUNDLES,
+            admission_policy: None,
+            persist_pool: false,
+            persist_pool_interval: DEFAULT_JOURNAL_INTERVAL,
+            history: false,
+            history_max_events: DEFAULT_MAX_HISTORY_EVENTS,
         }
     }
 }
@@ -411,6 +470,47 @@ pub struct TxPoolArgs {
     /// Max batch size for transaction pool insertions
     #[arg(long = "txpool.max-batch-size", default_value_t = DefaultTxPoolValues::get_global().max_batch_size)]
     pub max_batch_size: usize,
+
+    /// Enables the bundle pool for bundles submitted via `eth_sendBundle` and `mev_sendBundle`.
+    ///
+    /// Bundles are included by the payload builder if they pay more to the coinbase than the
+    /// pool transactions they compete with.
+    #[arg(long = "txpool.enable-bundles", default_value_t = DefaultTxPoolValues::get_global().enable_bundles)]
+    pub enable_bundles: bool,
+
+    /// Max number of bundles in the bundle pool
+    #[arg(long = "txpool.max-bundles", requires = "enable_bundles", default_value_t = DefaultTxPoolValues::get_global().max_bundles)]
+    pub max_bundles: usize,
+
+    /// Path to a TOML file with admission rules for transactions entering the pool.
+    ///
+    /// The rules, such as denied addresses or a minimum priority fee per origin, are applied on
+    /// top of the built-in validation. The file is reloaded when it changes.
+    #[arg(long = "txpool.admission-policy", value_name = "PATH", default_value = Resettable::from(DefaultTxPoolValues::get_global().admission_policy.as_ref().map(|v| v.to_string_lossy().into())))]
+    pub admission_policy: Option<PathBuf>,
+
+    /// Periodically persists all transactions of the pool, including blob transactions, and
+    /// restores them on startup.
+    ///
+    /// Unlike the local transactions backup, the journal is also written while the node is
+    /// running, so the pool survives crashes. Restored transactions are revalidated against the
+    /// current state.
+    #[arg(long = "txpool.persist-pool", default_value_t = DefaultTxPoolValues::get_global().persist_pool)]
+    pub persist_pool: bool,
+
+    /// Interval at which the pool journal is written.
+    #[arg(long = "txpool.persist-pool-interval", requires = "persist_pool", value_parser = parse_duration_from_secs_or_ms, value_name = "DURATION", default_value = format_duration_as_secs_or_ms(DefaultTxPoolValues::get_global().persist_pool_interval))]
+    pub persist_pool_interval: Duration,
+
+    /// Records the lifecycle events of pool transactions to disk so they can be queried with
+    /// `txpool_history` and `txpool_senderHistory`.
+    #[arg(long = "txpool.history", default_value_t = DefaultTxPoolValues::get_global().history)]
+    pub history: bool,
+
+    /// Maximum number of events kept in the transaction history, the oldest events are
+    /// overwritten first.
+    #[arg(long = "txpool.history-max-events", requires = "history", default_value_t = DefaultTxPoolValues::get_global().history_max_events)]
+    pub history_max_events: usize,
 }
 
 impl TxPoolArgs {
@@ -464,6 +564,13 @@ impl Default for TxPoolArgs {
             transactions_backup_path,
             disable_transactions_backup,
             max_batch_size,
+            enable_bundles,
+            max_bundles,
+            admission_policy,
+            persist_pool,
+            persist_pool_interval,
+            history,
+            history_max_events,
         } = DefaultTxPoolValues::get_global().clone();
         Self {
             pending_max_count,
@@ -496,6 +603,13 @@ impl Default for TxPoolArgs {
             transactions_backup_path,
             disable_transactions_backup,
             max_batch_size,
+            enable_bundles,
+            max_bundles,
+            admission_policy,
+            persist_pool,
+            persist_pool_interval,
+            history,
+            history_max_events,
         }
     }
 }
@@ -540,6 +654,11 @@ impl RethTransactionPoolConfig for TxPoolArgs {
             max_new_pending_txs_notifications: self.max_new_pending_txs_notifications,
             max_queued_lifetime: self.max_queued_lifetime,
             max_inflight_delegated_slot_limit: default_config.max_inflight_delegated_slot_limit,
+            bundle_pool: self
+                .enable_bundles
+                .then_some(BundlePoolConfig { max_bundles: self.max_bundles }),
+            // the path depends on the data dir, see `BuilderContext::pool_config`
+            transaction_history: None,
         }
     }
 
@@ -625,6 +744,13 @@ mod tests {
             transactions_backup_path: Some(PathBuf::from("/tmp/txpool-backup")),
             disable_transactions_backup: false,
             max_batch_size: 10,
+            enable_bundles: true,
+            max_bundles: 64,
+            admission_policy: Some(PathBuf::from("/tmp/admission-policy.toml")),
+            persist_pool: true,
+            persist_pool_interval: Duration::from_secs(30),
+            history: true,
+            history_max_events: 5000,
         };
 
         let parsed_args = CommandParser::<TxPoolArgs>::parse_from([
@@ -685,6 +811,17 @@ mod tests {
             "/tmp/txpool-backup",
             "--txpool.max-batch-size",
             "10",
+            "--txpool.enable-bundles",
+            "--txpool.max-bundles",
+            "64",
+            "--txpool.admission-policy",
+            "/tmp/admission-policy.toml",
+            "--txpool.persist-pool",
+            "--txpool.persist-pool-interval",
+            "30",
+            "--txpool.history",
+            "--txpool.history-max-events",
+            "5000",
         ])
         .args;
 
@@ -0,0 +1,60 @@
+//! A handle to reload the config file of a running node.
+
+use reth_config::ConfigChanges;
+use std::sync::{Arc, Mutex};
+use tokio::sync::{mpsc, oneshot};
+
+/// Errors returned by a [`ConfigReloadHandle`].
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigReloadError {
+    /// No config reloader is attached to the handle.
+    #[error("config reloader is not running")]
+    NotRunning,
+    /// The config file could not be loaded or is invalid.
+    #[error("failed to reload config: {0}")]
+    InvalidConfig(String),
+}
+
+/// A request to reload the config file, answered with the settings that changed.
+pub type ConfigReloadRequest = oneshot::Sender<Result<ConfigChanges, ConfigReloadError>>;
+
+/// A handle to request a reload of the config file of a running node.
+///
+/// The handle can be created before the reloader is spawned, the reloader takes the requests with
+/// [`ConfigReloadHandle::attach`]. Requests sent before that are handled once the reloader runs.
+#[derive(Debug, Clone)]
+pub struct ConfigReloadHandle {
+    to_reloader: mpsc::UnboundedSender<ConfigReloadRequest>,
+    from_handle: Arc<Mutex<Option<mpsc::UnboundedReceiver<ConfigReloadRequest>>>>,
+}
+
+impl Default for ConfigReloadHandle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConfigReloadHandle {
+    /// Creates a new handle that is not attached to a reloader yet.
+    pub fn new() -> Self {
+        let (to_reloader, from_handle) = mpsc::unbounded_channel();
+        Self { to_reloader, from_handle: Arc::new(Mutex::new(Some(from_handle))) }
+    }
+
+    /// Attaches the handle to a reloader, returning the receiver of the reload requests.
+    ///
+    /// Returns `None` if the handle is already attached.
+    pub fn attach(&self) -> Option<mpsc::UnboundedReceiver<ConfigReloadRequest>> {
+        self.from_handle.lock().unwrap_or_else(|e| e.into_inner()).take()
+    }
+
+    /// Reloads the config file and applies the settings that can change at runtime.
+    ///
+    /// Returns the settings that changed, split into the applied ones and the ones that require a
+    /// restart.
+    pub async fn reload(&self) -> Result<ConfigChanges, ConfigReloadError> {
+        let (tx, rx) = oneshot::channel();
+        self.to_reloader.send(tx).map_err(|_| ConfigReloadError::NotRunning)?;
+        rx.await.map_err(|_| ConfigReloadError::NotRunning)?
+    }
+}
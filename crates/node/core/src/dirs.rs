@@ -353,6 +353,20 @@ impl<D> ChainPath<D> {
         self.data_dir().join("txpool-transactions-backup.rlp")
     }
 
+    /// Returns the path to the journal of all pool transactions
+    ///
+    /// `<DIR>/<CHAIN_ID>/txpool-journal.bin`
+    pub fn txpool_journal(&self) -> PathBuf {
+        self.data_dir().join("txpool-journal.bin")
+    }
+
+    /// Returns the path to the history of transaction pool events
+    ///
+    /// `<DIR>/<CHAIN_ID>/txpool-history.bin`
+    pub fn txpool_history(&self) -> PathBuf {
+        self.data_dir().join("txpool-history.bin")
+    }
+
     /// Returns the path to the config file for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/reth.toml`
@@ -374,6 +388,20 @@ impl<D> ChainPath<D> {
         self.data_dir().join("invalid_block_hooks")
     }
 
+    /// Returns the path to the directory the `debug` namespace writes trace files to.
+    ///
+    /// `<DIR>/<CHAIN_ID>/debug_traces`
+    pub fn debug_traces(&self) -> PathBuf {
+        self.data_dir().join("debug_traces")
+    }
+
+    /// Returns the path to the directory ERA1 files are downloaded to for serving expired blocks.
+    ///
+    /// `<DIR>/<CHAIN_ID>/era1`
+    pub fn era_history(&self) -> PathBuf {
+        self.data_dir().join("era1")
+    }
+
     /// Returns the path to the ExEx WAL directory for this chain.
     pub fn exex_wal(&self) -> PathBuf {
         self.data_dir().join("exex/wal")
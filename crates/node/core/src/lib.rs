@@ -10,6 +10,7 @@
 
 pub mod args;
 pub mod cli;
+pub mod config_reload;
 pub mod dirs;
 pub mod exit;
 pub mod node_config;
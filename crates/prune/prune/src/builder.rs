@@ -1,4 +1,4 @@
-use crate::{segments::SegmentSet, Pruner};
+use crate::{segments::SegmentSet, Pruner, PrunerConfigUpdate};
 use alloy_eips::eip2718::Encodable2718;
 use reth_config::PruneConfig;
 use reth_db_api::{table::Value, transaction::DbTxMut};
@@ -117,6 +117,43 @@ impl PrunerBuilder {
         pruner
     }
 
+    /// Builds a [`PrunerConfigUpdate`] from the current configuration with the given provider
+    /// factory, to reconfigure a running [Pruner] built with
+    /// [`Self::build_with_provider_factory`].
+    pub fn build_config_update<PF>(
+        &self,
+        provider_factory: &PF,
+    ) -> PrunerConfigUpdate<PF::ProviderRW>
+    where
+        PF: DatabaseProviderFactory<
+                ProviderRW: PruneCheckpointWriter
+                                + PruneCheckpointReader
+                                + BlockReader<Transaction: Encodable2718>
+                                + ChainStateBlockReader
+                                + StorageSettingsCache
+                                + StageCheckpointReader
+                                + ChangeSetReader
+                                + StorageChangeSetReader
+                                + RocksDBProviderFactory
+                                + StaticFileProviderFactory<
+                    Primitives: NodePrimitives<SignedTx: Value, Receipt: Value, BlockHeader: Value>,
+                >,
+            > + StaticFileProviderFactory<
+                Primitives = <PF::ProviderRW as NodePrimitivesProvider>::Primitives,
+            >,
+    {
+        let segments = SegmentSet::from_components(
+            provider_factory.static_file_provider(),
+            self.segments.clone(),
+        );
+
+        PrunerConfigUpdate {
+            segments: segments.into_vec(),
+            min_block_interval: self.block_interval,
+            minimum_pruning_distance: self.minimum_pruning_distance,
+        }
+    }
+
     /// Builds a [Pruner] from the current configuration with the given static file provider.
     pub fn build<Provider>(
         self,
@@ -21,7 +21,7 @@ use crate::metrics::Metrics;
 pub use builder::PrunerBuilder;
 pub use error::PrunerError;
 pub use limiter::PruneLimiter;
-pub use pruner::{Pruner, PrunerResult, PrunerWithFactory, PrunerWithResult};
+pub use pruner::{Pruner, PrunerConfigUpdate, PrunerResult, PrunerWithFactory, PrunerWithResult};
 
 // Re-export prune types
 #[doc(inline)]
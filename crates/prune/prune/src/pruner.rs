@@ -15,7 +15,7 @@ use reth_prune_types::{PruneProgress, PrunedSegmentInfo, PrunerOutput};
 use reth_stages_types::StageId;
 use reth_tokio_util::{EventSender, EventStream};
 use std::time::Duration;
-use tokio::sync::watch;
+use tokio::sync::{mpsc, watch};
 use tracing::{debug, instrument};
 
 /// Result of [`Pruner::run`] execution.
@@ -27,6 +27,18 @@ pub type PrunerWithResult<S, DB> = (Pruner<S, DB>, PrunerResult);
 /// Pruner with preset provider factory.
 pub type PrunerWithFactory<PF> = Pruner<<PF as DatabaseProviderFactory>::ProviderRW, PF>;
 
+/// Settings of a running [`Pruner`] that are replaced by a config reload, see
+/// [`Pruner::with_config_updates`].
+#[derive(Debug)]
+pub struct PrunerConfigUpdate<Provider> {
+    /// Segments to prune.
+    pub segments: Vec<Box<dyn Segment<Provider>>>,
+    /// Minimum pruning interval measured in blocks.
+    pub min_block_interval: usize,
+    /// Optional override for the minimum pruning distance.
+    pub minimum_pruning_distance: Option<u64>,
+}
+
 /// Pruning routine. Main pruning logic happens in [`Pruner::run`].
 #[derive(Debug)]
 pub struct Pruner<Provider, PF> {
@@ -49,6 +61,8 @@ pub struct Pruner<Provider, PF> {
     minimum_pruning_distance: Option<u64>,
     /// The finished height of all `ExEx`'s.
     finished_exex_height: watch::Receiver<FinishedExExHeight>,
+    /// Receiver of settings that replace the current ones before the next run.
+    config_updates: Option<mpsc::UnboundedReceiver<PrunerConfigUpdate<Provider>>>,
     #[doc(hidden)]
     metrics: Metrics,
     event_sender: EventSender<PrunerEvent>,
@@ -72,6 +86,7 @@ impl<Provider> Pruner<Provider, ()> {
             timeout,
             minimum_pruning_distance: None,
             finished_exex_height,
+            config_updates: None,
             metrics: Metrics::default(),
             event_sender: Default::default(),
         }
@@ -100,6 +115,7 @@ where
             timeout,
             minimum_pruning_distance: None,
             finished_exex_height,
+            config_updates: None,
             metrics: Metrics::default(),
             event_sender: Default::default(),
         }
@@ -112,6 +128,37 @@ impl<Provider, S> Pruner<Provider, S> {
         self.minimum_pruning_distance = Some(distance);
         self
     }
+
+    /// Sets the receiver of settings that replace the current ones, e.g. when the config file is
+    /// reloaded.
+    ///
+    /// Updates are applied before the next run, see [`Self::apply_config_updates`].
+    pub fn with_config_updates(
+        mut self,
+        config_updates: mpsc::UnboundedReceiver<PrunerConfigUpdate<Provider>>,
+    ) -> Self {
+        self.config_updates = Some(config_updates);
+        self
+    }
+
+    /// Replaces the current settings with the latest received [`PrunerConfigUpdate`], if any.
+    pub fn apply_config_updates(&mut self) {
+        let Some(config_updates) = &mut self.config_updates else { return };
+
+        let mut latest = None;
+        while let Ok(update) = config_updates.try_recv() {
+            latest = Some(update);
+        }
+
+        if let Some(PrunerConfigUpdate { segments, min_block_interval, minimum_pruning_distance }) =
+            latest
+        {
+            debug!(target: "pruner", segments = segments.len(), %min_block_interval, ?minimum_pruning_distance, "Applying config update");
+            self.segments = segments;
+            self.min_block_interval = min_block_interval;
+            self.minimum_pruning_distance = minimum_pruning_distance;
+        }
+    }
 }
 
 impl<Provider, S> Pruner<Provider, S>
@@ -139,6 +186,8 @@ where
         provider: &Provider,
         tip_block_number: BlockNumber,
     ) -> PrunerResult {
+        self.apply_config_updates();
+
         let Some(tip_block_number) =
             self.adjust_tip_block_number_to_finished_exex_height(tip_block_number)
         else {
@@ -380,6 +429,7 @@ fn is_stage_finished<Provider: StageCheckpointReader>(
 
 #[cfg(test)]
 mod tests {
+    use super::PrunerConfigUpdate;
     use crate::Pruner;
     use reth_exex_types::FinishedExExHeight;
     use reth_provider::test_utils::create_test_provider_factory;
@@ -423,4 +473,40 @@ mod tests {
         finished_exex_height_tx.send(FinishedExExHeight::Height(third_block_number)).unwrap();
         assert!(pruner.is_pruning_needed(third_block_number));
     }
+
+    #[test]
+    fn apply_config_updates() {
+        let provider_factory = create_test_provider_factory();
+        let (config_updates_tx, config_updates_rx) = tokio::sync::mpsc::unbounded_channel();
+
+        let mut pruner = Pruner::new_with_factory(
+            provider_factory,
+            vec![],
+            5,
+            0,
+            None,
+            tokio::sync::watch::channel(FinishedExExHeight::NoExExs).1,
+        )
+        .with_config_updates(config_updates_rx);
+
+        // Nothing to apply
+        pruner.apply_config_updates();
+        assert_eq!(pruner.min_block_interval, 5);
+
+        // Only the latest update is applied
+        for min_block_interval in [10, 20] {
+            config_updates_tx
+                .send(PrunerConfigUpdate {
+                    segments: vec![],
+                    min_block_interval,
+                    minimum_pruning_distance: Some(min_block_interval as u64 * 100),
+                })
+                .unwrap();
+        }
+        pruner.apply_config_updates();
+        assert_eq!(pruner.min_block_interval, 20);
+        assert_eq!(pruner.minimum_pruning_distance, Some(2000));
+        assert!(!pruner.is_pruning_needed(19));
+        assert!(pruner.is_pruning_needed(20));
+    }
 }
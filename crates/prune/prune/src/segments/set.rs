@@ -1,6 +1,6 @@
 use crate::segments::{
-    user::ReceiptsByLogs, AccountHistory, Bodies, Segment, SenderRecovery, StorageHistory,
-    TransactionLookup, UserReceipts,
+    user::ReceiptsByLogs, AccountHistory, AddressTransactions, Bodies, LogIndex, Segment,
+    SenderRecovery, StorageHistory, TransactionLookup, UserReceipts,
 };
 use alloy_eips::eip2718::Encodable2718;
 use reth_db_api::{table::Value, transaction::DbTxMut};
@@ -72,6 +72,8 @@ where
             account_history,
             storage_history,
             bodies_history,
+            address_transactions,
+            log_index,
             receipts_log_filter,
         } = prune_modes;
 
@@ -79,12 +81,17 @@ where
             // Transaction lookup must run before bodies because it needs to read transaction
             // data from static files before bodies deletes them.
             .segment_opt(transaction_lookup.map(TransactionLookup::new))
+            // Address transactions derives the addresses to prune from the transactions and
+            // receipts, so it also runs before bodies and receipts.
+            .segment_opt(address_transactions.map(AddressTransactions::new))
             // Bodies
             .segment_opt(bodies_history.map(|mode| Bodies::new(mode, transaction_lookup)))
             // Account history
             .segment_opt(account_history.map(AccountHistory::new))
             // Storage history
             .segment_opt(storage_history.map(StorageHistory::new))
+            // Log index
+            .segment_opt(log_index.map(LogIndex::new))
             // User receipts
             .segment_opt(receipts.map(UserReceipts::new))
             // Receipts by logs
@@ -0,0 +1,267 @@
+use crate::{
+    db_ext::DbTxPruneExt,
+    segments::{PruneInput, Segment, SegmentOutput},
+    PrunerError,
+};
+use alloy_consensus::TxReceipt;
+use alloy_primitives::{Address, TxNumber};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW},
+    models::ShardedKey,
+    tables,
+    transaction::DbTxMut,
+    BlockNumberList,
+};
+use reth_primitives_traits::SignerRecoverable;
+use reth_provider::{touched_addresses, BlockReader, DBProvider};
+use reth_prune_types::{
+    PruneMode, PruneProgress, PrunePurpose, PruneSegment, SegmentOutputCheckpoint,
+};
+use reth_stages_types::StageId;
+use std::ops::Range;
+use tracing::{instrument, trace};
+
+/// Segment responsible for pruning the [`tables::AddressTransactions`] index.
+///
+/// The addresses to prune are derived from the pruned transactions, whose numbers are then
+/// dropped from the shards of each address. It must run before the transactions are pruned.
+#[derive(Debug)]
+pub struct AddressTransactions {
+    mode: PruneMode,
+}
+
+impl AddressTransactions {
+    pub const fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<Provider> Segment<Provider> for AddressTransactions
+where
+    Provider: DBProvider<Tx: DbTxMut> + BlockReader,
+{
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::AddressTransactions
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    fn required_stage(&self) -> Option<StageId> {
+        Some(StageId::IndexAddressTransactions)
+    }
+
+    #[instrument(
+        name = "AddressTransactions::prune",
+        target = "pruner",
+        skip(self, provider),
+        ret(level = "trace")
+    )]
+    fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+        let tx_range = match input.get_next_tx_num_range(provider)? {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No address transactions to prune");
+                return Ok(SegmentOutput::done())
+            }
+        };
+        let end = *tx_range.end();
+
+        // For PruneMode::Full, clear the entire table in one operation
+        if self.mode.is_full() {
+            let pruned = provider.tx_ref().clear_table::<tables::AddressTransactions>()?;
+            trace!(target: "pruner", %pruned, "Cleared address transactions table");
+
+            return Ok(SegmentOutput {
+                progress: PruneProgress::Finished,
+                pruned,
+                checkpoint: Some(SegmentOutputCheckpoint {
+                    block_number: Some(input.to_block),
+                    tx_number: Some(end),
+                }),
+            })
+        }
+
+        // The affected addresses are derived from the transactions of the range, so the range
+        // is capped to one transaction per entry left to delete.
+        let mut limiter = input.limiter;
+        let start = *tx_range.start();
+        let tx_range_end = limiter
+            .deleted_entries_limit_left()
+            .map_or(end, |left| end.min(start + left.max(1) as u64 - 1));
+
+        let addresses = touched_addresses_in_range(provider, start..tx_range_end + 1)?;
+
+        let mut cursor = provider.tx_ref().cursor_write::<tables::AddressTransactions>()?;
+        let mut pruned = 0;
+        for address in addresses {
+            let mut entry = cursor.seek(ShardedKey::new(address, 0))?;
+            while let Some((key, list)) = entry {
+                if key.key != address {
+                    break
+                }
+
+                if key.highest_block_number <= tx_range_end {
+                    // Every transaction of the shard is below the target.
+                    cursor.delete_current()?;
+                    limiter.increment_deleted_entries_count();
+                    pruned += 1;
+                    entry = cursor.next()?;
+                    continue
+                }
+
+                if list.iter().next().is_some_and(|tx| tx <= tx_range_end) {
+                    let remaining =
+                        list.iter().skip_while(|tx| *tx <= tx_range_end).collect::<Vec<_>>();
+                    cursor.upsert(key, &BlockNumberList::new_pre_sorted(remaining))?;
+                }
+                break
+            }
+        }
+        let done = tx_range_end == end;
+
+        trace!(target: "pruner", %pruned, %done, "Pruned address transactions");
+
+        let last_pruned_block = provider
+            .block_by_transaction_id(tx_range_end)?
+            .ok_or(PrunerError::InconsistentData("Block for transaction is not found"))?
+            // If there's more transactions to prune, set the checkpoint block number to previous,
+            // so we could finish pruning its transactions on the next run.
+            .checked_sub(if done { 0 } else { 1 });
+
+        Ok(SegmentOutput {
+            progress: limiter.progress(done),
+            pruned,
+            checkpoint: Some(SegmentOutputCheckpoint {
+                block_number: last_pruned_block,
+                tx_number: Some(tx_range_end),
+            }),
+        })
+    }
+}
+
+/// Returns the addresses touched by the transactions in `tx_range`, sorted and deduplicated.
+///
+/// Senders that were pruned are recovered from the transaction signature, and log emitters are
+/// skipped if the receipts of the range were pruned.
+fn touched_addresses_in_range<Provider: BlockReader>(
+    provider: &Provider,
+    tx_range: Range<TxNumber>,
+) -> Result<Vec<Address>, PrunerError> {
+    let transactions = provider.transactions_by_tx_range(tx_range.clone())?;
+    let senders = provider.senders_by_tx_range(tx_range.clone())?;
+    let receipts = provider.receipts_by_tx_range(tx_range)?;
+    let has_senders = senders.len() == transactions.len();
+    let has_receipts = receipts.len() == transactions.len();
+
+    let mut addresses = Vec::new();
+    for (idx, transaction) in transactions.iter().enumerate() {
+        let sender = if has_senders {
+            senders[idx]
+        } else {
+            transaction.recover_signer_unchecked().map_err(|_| {
+                PrunerError::InconsistentData("Transaction sender is not recoverable")
+            })?
+        };
+        let logs = if has_receipts { receipts[idx].logs() } else { &[] };
+        addresses.extend(touched_addresses(sender, transaction, logs));
+    }
+    addresses.sort_unstable();
+    addresses.dedup();
+
+    Ok(addresses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PruneLimiter;
+    use alloy_primitives::{Address, B256};
+    use reth_db_api::models::ShardedKey;
+    use reth_provider::{DatabaseProviderFactory, PruneCheckpointReader};
+    use reth_stages::test_utils::{StorageKind, TestStageDB};
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+
+    #[test]
+    fn prune() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(
+            &mut rng,
+            0..=10,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 2..3, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Static).unwrap();
+
+        // Index every transaction, with a first shard per address holding its first transaction
+        let mut index = std::collections::BTreeMap::<Address, Vec<TxNumber>>::new();
+        let transactions = blocks.iter().flat_map(|block| block.body().transactions.iter());
+        for (tx_number, transaction) in transactions.enumerate() {
+            let sender = transaction.recover_signer().unwrap();
+            for address in touched_addresses(sender, transaction, std::iter::empty()) {
+                index.entry(address).or_default().push(tx_number as TxNumber);
+            }
+        }
+        db.commit(|tx| {
+            for (address, tx_numbers) in &index {
+                let (first, rest) = tx_numbers.split_first().unwrap();
+                tx.put::<tables::AddressTransactions>(
+                    ShardedKey::new(*address, *first),
+                    BlockNumberList::new_pre_sorted([*first]),
+                )?;
+                if !rest.is_empty() {
+                    tx.put::<tables::AddressTransactions>(
+                        ShardedKey::last(*address),
+                        BlockNumberList::new_pre_sorted(rest.iter().copied()),
+                    )?;
+                }
+            }
+            Ok(())
+        })
+        .unwrap();
+
+        let segment = AddressTransactions::new(PruneMode::Before(6));
+        let input =
+            PruneInput { previous_checkpoint: None, to_block: 5, limiter: PruneLimiter::default() };
+        let provider = db.factory.database_provider_rw().unwrap();
+        let output = segment.prune(&provider, input).unwrap();
+        segment
+            .save_checkpoint(
+                &provider,
+                output.checkpoint.unwrap().as_prune_checkpoint(PruneMode::Before(6)),
+            )
+            .unwrap();
+        provider.commit().unwrap();
+
+        // Blocks 0 to 5 hold the first 12 transactions
+        let mut actual = std::collections::BTreeMap::<Address, Vec<TxNumber>>::new();
+        for (key, list) in db.table::<tables::AddressTransactions>().unwrap() {
+            actual.entry(key.key).or_default().extend(list.iter());
+        }
+        let remaining = index
+            .into_iter()
+            .map(|(address, tx_numbers)| {
+                (address, tx_numbers.into_iter().filter(|tx| *tx > 11).collect::<Vec<_>>())
+            })
+            .filter(|(_, tx_numbers)| !tx_numbers.is_empty())
+            .collect::<std::collections::BTreeMap<_, _>>();
+
+        assert_eq!(output.progress, PruneProgress::Finished);
+        assert_eq!(actual, remaining);
+        assert_eq!(
+            db.factory
+                .provider()
+                .unwrap()
+                .get_prune_checkpoint(PruneSegment::AddressTransactions)
+                .unwrap()
+                .and_then(|checkpoint| checkpoint.tx_number),
+            Some(11)
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use crate::{
+    db_ext::DbTxPruneExt,
+    segments::{PruneInput, Segment, SegmentOutput},
+    PrunerError,
+};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW},
+    tables,
+    transaction::DbTxMut,
+    BlockNumberList,
+};
+use reth_provider::DBProvider;
+use reth_prune_types::{
+    PruneMode, PruneProgress, PrunePurpose, PruneSegment, SegmentOutputCheckpoint,
+};
+use reth_stages_types::StageId;
+use reth_storage_api::log_position;
+use tracing::{instrument, trace};
+
+/// Segment responsible for pruning the [`tables::LogIndex`].
+///
+/// The index is keyed by address and topic hashes, so pruning walks the whole table and drops the
+/// log positions of the pruned blocks from every shard.
+#[derive(Debug)]
+pub struct LogIndex {
+    mode: PruneMode,
+}
+
+impl LogIndex {
+    pub const fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<Provider> Segment<Provider> for LogIndex
+where
+    Provider: DBProvider<Tx: DbTxMut>,
+{
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::LogIndex
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    fn required_stage(&self) -> Option<StageId> {
+        Some(StageId::IndexLogs)
+    }
+
+    #[instrument(
+        name = "LogIndex::prune",
+        target = "pruner",
+        skip(self, provider),
+        ret(level = "trace")
+    )]
+    fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+        if input.get_next_block_range().is_none() {
+            trace!(target: "pruner", "No log index to prune");
+            return Ok(SegmentOutput::done())
+        }
+        let checkpoint =
+            SegmentOutputCheckpoint { block_number: Some(input.to_block), tx_number: None };
+
+        // For PruneMode::Full, clear the entire table in one operation
+        if self.mode.is_full() {
+            let pruned = provider.tx_ref().clear_table::<tables::LogIndex>()?;
+            trace!(target: "pruner", %pruned, "Cleared log index table");
+
+            return Ok(SegmentOutput {
+                progress: PruneProgress::Finished,
+                pruned,
+                checkpoint: Some(checkpoint),
+            })
+        }
+
+        // Every position below the first log of the next block belongs to a pruned block.
+        let end = log_position(input.to_block + 1, 0);
+
+        let mut limiter = input.limiter;
+        let mut cursor = provider.tx_ref().cursor_write::<tables::LogIndex>()?;
+        let mut pruned = 0;
+        let mut done = true;
+
+        let mut entry = cursor.first()?;
+        while let Some((key, list)) = entry {
+            if limiter.is_limit_reached() {
+                done = false;
+                break
+            }
+
+            if key.highest_block_number < end {
+                // Every log of the shard is below the target.
+                cursor.delete_current()?;
+                limiter.increment_deleted_entries_count();
+                pruned += 1;
+            } else if list.iter().next().is_some_and(|position| position < end) {
+                let remaining =
+                    list.iter().skip_while(|position| *position < end).collect::<Vec<_>>();
+                if remaining.is_empty() {
+                    cursor.delete_current()?;
+                    limiter.increment_deleted_entries_count();
+                    pruned += 1;
+                } else {
+                    cursor.upsert(key, &BlockNumberList::new_pre_sorted(remaining))?;
+                }
+            }
+
+            entry = cursor.next()?;
+        }
+
+        trace!(target: "pruner", %pruned, %done, "Pruned log index");
+
+        Ok(SegmentOutput {
+            progress: limiter.progress(done),
+            pruned,
+            // The table is walked from the start on every run, so an interrupted run keeps the
+            // previous checkpoint.
+            checkpoint: if done {
+                Some(checkpoint)
+            } else {
+                input.previous_checkpoint.map(SegmentOutputCheckpoint::from_prune_checkpoint)
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PruneLimiter;
+    use alloy_primitives::B256;
+    use reth_db_api::models::ShardedKey;
+    use reth_provider::{DatabaseProviderFactory, PruneCheckpointReader};
+    use reth_stages::test_utils::TestStageDB;
+
+    #[test]
+    fn prune() {
+        let db = TestStageDB::default();
+
+        // Key one has a log in every block, split over two shards, and key two only in the last
+        // block.
+        let one = B256::with_last_byte(1);
+        let two = B256::with_last_byte(2);
+        db.commit(|tx| {
+            tx.put::<tables::LogIndex>(
+                ShardedKey::new(one, log_position(4, 0)),
+                BlockNumberList::new_pre_sorted((0..=4).map(|block| log_position(block, 0))),
+            )?;
+            tx.put::<tables::LogIndex>(
+                ShardedKey::last(one),
+                BlockNumberList::new_pre_sorted((5..=10).map(|block| log_position(block, 0))),
+            )?;
+            tx.put::<tables::LogIndex>(
+                ShardedKey::last(two),
+                BlockNumberList::new_pre_sorted([log_position(10, 1)]),
+            )?;
+            Ok(())
+        })
+        .unwrap();
+
+        let segment = LogIndex::new(PruneMode::Before(6));
+        let input =
+            PruneInput { previous_checkpoint: None, to_block: 5, limiter: PruneLimiter::default() };
+        let provider = db.factory.database_provider_rw().unwrap();
+        let output = segment.prune(&provider, input).unwrap();
+        segment
+            .save_checkpoint(
+                &provider,
+                output.checkpoint.unwrap().as_prune_checkpoint(PruneMode::Before(6)),
+            )
+            .unwrap();
+        provider.commit().unwrap();
+
+        assert_eq!(output.progress, PruneProgress::Finished);
+        assert_eq!(output.pruned, 1);
+        assert_eq!(
+            db.table::<tables::LogIndex>().unwrap(),
+            vec![
+                (
+                    ShardedKey::last(one),
+                    BlockNumberList::new_pre_sorted((6..=10).map(|block| log_position(block, 0)))
+                ),
+                (ShardedKey::last(two), BlockNumberList::new_pre_sorted([log_position(10, 1)])),
+            ]
+        );
+        assert_eq!(
+            db.factory
+                .provider()
+                .unwrap()
+                .get_prune_checkpoint(PruneSegment::LogIndex)
+                .unwrap()
+                .and_then(|checkpoint| checkpoint.block_number),
+            Some(5)
+        );
+    }
+}
@@ -1,6 +1,8 @@
 mod account_history;
+mod address_transactions;
 mod bodies;
 mod history;
+mod log_index;
 mod receipts;
 mod receipts_by_logs;
 mod sender_recovery;
@@ -8,7 +10,9 @@ mod storage_history;
 mod transaction_lookup;
 
 pub use account_history::AccountHistory;
+pub use address_transactions::AddressTransactions;
 pub use bodies::Bodies;
+pub use log_index::LogIndex;
 pub use receipts::Receipts;
 pub use receipts_by_logs::ReceiptsByLogs;
 pub use sender_recovery::SenderRecovery;
@@ -43,6 +43,10 @@ pub enum PruneSegment {
     MerkleChangeSets,
     /// Prune segment responsible for bodies (transactions in static files).
     Bodies,
+    /// Prune segment responsible for the `AddressTransactions` table.
+    AddressTransactions,
+    /// Prune segment responsible for the `LogIndex` table.
+    LogIndex,
 }
 
 #[cfg(test)]
@@ -65,7 +69,10 @@ impl PruneSegment {
     /// Returns minimum number of blocks to keep in the database for this segment.
     pub const fn min_blocks(&self) -> u64 {
         match self {
-            Self::SenderRecovery | Self::TransactionLookup => 0,
+            Self::SenderRecovery |
+            Self::TransactionLookup |
+            Self::AddressTransactions |
+            Self::LogIndex => 0,
             Self::Receipts | Self::Bodies => MINIMUM_DISTANCE,
             Self::ContractLogs | Self::AccountHistory | Self::StorageHistory => {
                 MINIMUM_UNWIND_SAFE_DISTANCE
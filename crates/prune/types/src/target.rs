@@ -2,7 +2,7 @@ use alloy_primitives::BlockNumber;
 use derive_more::Display;
 use thiserror::Error;
 
-use crate::{PruneCheckpoint, PruneMode, PruneSegment, ReceiptsLogPruneConfig};
+use crate::{PruneCheckpoint, PruneMode, PruneSegment, PruneSegmentError, ReceiptsLogPruneConfig};
 
 /// Minimum distance from the tip necessary for the node to work correctly:
 /// 1. Minimum 2 epochs (32 blocks per epoch) required to handle any reorg according to the
@@ -77,6 +77,12 @@ pub struct PruneModes {
     /// Bodies History pruning configuration.
     #[cfg_attr(any(test, feature = "serde"), serde(skip_serializing_if = "Option::is_none"))]
     pub bodies_history: Option<PruneMode>,
+    /// Address transactions index pruning configuration.
+    #[cfg_attr(any(test, feature = "serde"), serde(skip_serializing_if = "Option::is_none"))]
+    pub address_transactions: Option<PruneMode>,
+    /// Log index pruning configuration.
+    #[cfg_attr(any(test, feature = "serde"), serde(skip_serializing_if = "Option::is_none"))]
+    pub log_index: Option<PruneMode>,
     /// Receipts pruning configuration by retaining only those receipts that contain logs emitted
     /// by the specified addresses, discarding others. This setting is overridden by `receipts`.
     ///
@@ -99,6 +105,8 @@ impl PruneModes {
             account_history: Some(PruneMode::Full),
             storage_history: Some(PruneMode::Full),
             bodies_history: Some(PruneMode::Full),
+            address_transactions: Some(PruneMode::Full),
+            log_index: Some(PruneMode::Full),
             receipts_log_filter: Default::default(),
         }
     }
@@ -121,6 +129,30 @@ impl PruneModes {
         }
     }
 
+    /// Returns an error if a [`PruneMode::Distance`] leaves fewer blocks than its segment requires,
+    /// see [`PruneSegment::min_blocks`].
+    ///
+    /// [`PruneMode::Full`] is always valid, it keeps the minimum number of blocks instead.
+    pub fn validate(&self) -> Result<(), PruneSegmentError> {
+        for (segment, mode) in [
+            (PruneSegment::SenderRecovery, self.sender_recovery),
+            (PruneSegment::TransactionLookup, self.transaction_lookup),
+            (PruneSegment::Receipts, self.receipts),
+            (PruneSegment::AccountHistory, self.account_history),
+            (PruneSegment::StorageHistory, self.storage_history),
+            (PruneSegment::Bodies, self.bodies_history),
+            (PruneSegment::AddressTransactions, self.address_transactions),
+            (PruneSegment::LogIndex, self.log_index),
+        ] {
+            if let Some(PruneMode::Distance(distance)) = mode &&
+                distance < segment.min_blocks()
+            {
+                return Err(PruneSegmentError::Configuration(segment))
+            }
+        }
+        Ok(())
+    }
+
     /// Returns an error if we can't unwind to the targeted block because the target block is
     /// outside the range.
     ///
@@ -246,6 +278,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_validate_prune_modes() {
+        assert!(PruneModes::default().validate().is_ok());
+        assert!(PruneModes::all().validate().is_ok());
+
+        let prune_modes = PruneModes {
+            bodies_history: Some(PruneMode::Distance(MINIMUM_DISTANCE)),
+            ..Default::default()
+        };
+        assert!(prune_modes.validate().is_ok());
+
+        let prune_modes = PruneModes {
+            bodies_history: Some(PruneMode::Distance(MINIMUM_DISTANCE - 1)),
+            ..Default::default()
+        };
+        assert_eq!(
+            prune_modes.validate(),
+            Err(PruneSegmentError::Configuration(PruneSegment::Bodies))
+        );
+    }
+
     #[test]
     fn test_unwind_target_unpruned() {
         // Test case 1: No pruning configured - should always succeed
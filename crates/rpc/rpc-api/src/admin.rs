@@ -1,6 +1,21 @@
+use alloy_eips::BlockNumHash;
+use alloy_primitives::U64;
 use alloy_rpc_types_admin::{NodeInfo, PeerInfo};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_network_peers::{AnyNode, NodeRecord};
+use serde::{Deserialize, Serialize};
+
+/// Settings that changed when reloading the config file with `admin_reloadConfig`.
+///
+/// Settings are named by their path in the config file, e.g. `peers.connection_info.max_inbound`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReloadConfigResult {
+    /// Changed settings that were applied to the running node.
+    pub applied: Vec<String>,
+    /// Changed settings that only take effect after a restart.
+    pub requires_restart: Vec<String>,
+}
 
 /// Admin namespace rpc interface that gives access to several non-standard RPC methods.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
@@ -50,4 +65,25 @@ pub trait AdminApi {
     /// Returns the number of transactions that were removed from the pool.
     #[method(name = "clearTxpool")]
     async fn clear_txpool(&self) -> RpcResult<u64>;
+
+    /// Reloads the config file, applies the settings that can change at runtime and reports the
+    /// changed settings that require a restart.
+    #[method(name = "reloadConfig")]
+    async fn reload_config(&self) -> RpcResult<ReloadConfigResult>;
+
+    /// Persists the canonical blocks up to the given block, if any, and stops persisting blocks
+    /// to disk until `admin_resumePersistence` is called.
+    ///
+    /// Returns the last persisted block. The database and static files don't change while
+    /// persistence is paused, so a consistent snapshot of them can be taken.
+    ///
+    /// The pause expires after 5 minutes, calling this again while paused renews it.
+    #[method(name = "pausePersistence")]
+    async fn pause_persistence(&self, block_number: Option<U64>) -> RpcResult<BlockNumHash>;
+
+    /// Resumes persisting canonical blocks after `admin_pausePersistence`.
+    ///
+    /// Returns the last persisted block.
+    #[method(name = "resumePersistence")]
+    async fn resume_persistence(&self) -> RpcResult<BlockNumHash>;
 }
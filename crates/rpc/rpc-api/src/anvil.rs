@@ -1,14 +1,14 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 
+use alloy_json_rpc::RpcObject;
 use alloy_primitives::{Address, Bytes, B256, U256};
 use alloy_rpc_types_anvil::{Forking, Metadata, MineOptions, NodeInfo};
-use alloy_rpc_types_eth::Block;
 
 /// Anvil rpc interface.
 /// https://book.getfoundry.sh/reference/anvil/#custom-methods
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "anvil"))]
 #[cfg_attr(feature = "client", rpc(server, client, namespace = "anvil"))]
-pub trait AnvilApi {
+pub trait AnvilApi<B: RpcObject> {
     /// Sends transactions impersonating specific account and contract addresses.
     #[method(name = "impersonateAccount")]
     async fn anvil_impersonate_account(&self, address: Address) -> RpcResult<()>;
@@ -39,7 +39,7 @@ pub trait AnvilApi {
     async fn anvil_set_interval_mining(&self, interval: u64) -> RpcResult<()>;
 
     /// Removes transactions from the pool.
-    #[method(name = "anvil_dropTransaction")]
+    #[method(name = "dropTransaction")]
     async fn anvil_drop_transaction(&self, tx_hash: B256) -> RpcResult<Option<B256>>;
 
     /// Resets the fork to a fresh forked state, and optionally update the fork config.
@@ -152,7 +152,7 @@ pub trait AnvilApi {
     /// compatibility reasons, this is a separate call since `evm_mine` is not an anvil original.
     /// and `ganache` may change the `0x0` placeholder.
     #[method(name = "mine_detailed")] // This method requires using `snake_case`.
-    async fn anvil_mine_detailed(&self, opts: Option<MineOptions>) -> RpcResult<Vec<Block>>;
+    async fn anvil_mine_detailed(&self, opts: Option<MineOptions>) -> RpcResult<Vec<B>>;
 
     /// Turn on call traces for transactions that are returned to the user when they execute a
     /// transaction (instead of just txhash/receipt).
@@ -2,13 +2,81 @@ use alloy_eips::{BlockId, BlockNumberOrTag};
 use alloy_genesis::ChainConfig;
 use alloy_json_rpc::RpcObject;
 use alloy_primitives::{Address, Bytes, B256, U64};
-use alloy_rpc_types_debug::ExecutionWitness;
+use alloy_rpc_types_debug::{AccountState, ExecutionWitness};
 use alloy_rpc_types_eth::{Bundle, StateContext};
 use alloy_rpc_types_trace::geth::{
     BlockTraceResult, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, TraceResult,
 };
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_trie_common::{updates::TrieUpdates, ExecutionWitnessMode, HashedPostState};
+use serde::{Deserialize, Serialize};
+use serde_with::{base64::Base64, serde_as};
+use std::collections::BTreeMap;
+
+/// The kinds of subscriptions served by `debug_subscribe`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DebugSubscriptionKind {
+    /// Traces of every block in a range, one notification per block.
+    TraceChain,
+}
+
+/// A page of accounts returned by `debug_accountRange`, in geth's dump format.
+///
+/// Accounts are keyed by address. Accounts whose address preimage is unknown are keyed by
+/// `pre(<hashed address>)`.
+#[serde_as]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountRangeResult {
+    /// State root of the block the accounts were read from.
+    pub root: B256,
+    /// Accounts of this page, ordered by hashed address.
+    pub accounts: BTreeMap<String, AccountState>,
+    /// Hashed address to continue the iteration from, if there are more accounts.
+    ///
+    /// Serialized as base64, like geth does.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "Option<Base64>")]
+    pub next: Option<Bytes>,
+    /// Whether the storage of an account of this page was truncated.
+    ///
+    /// Only the first slots of such accounts are returned, ordered by hashed slot.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub incomplete: bool,
+}
+
+/// A storage slot returned by `debug_storageRangeAt`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageRangeEntry {
+    /// The storage key, if its preimage is known.
+    pub key: Option<B256>,
+    /// The storage value.
+    pub value: B256,
+}
+
+/// A page of storage slots returned by `debug_storageRangeAt`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageRangeResult {
+    /// Storage slots of this page, keyed by hashed storage key.
+    pub storage: BTreeMap<B256, StorageRangeEntry>,
+    /// Hashed storage key to continue the iteration from, if there are more slots.
+    pub next_key: Option<B256>,
+}
+
+/// Options for `debug_standardTraceBlockToFile` and `debug_standardTraceBadBlockToFile`.
+///
+/// The remaining options of geth's `StdTraceConfig` are accepted but ignored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StdTraceConfig {
+    /// Whether to include the memory in every step.
+    #[serde(default)]
+    pub enable_memory: bool,
+    /// Only trace the transaction with this hash instead of all transactions of the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<B256>,
+}
 
 /// Debug rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "debug"))]
@@ -42,13 +110,35 @@ pub trait DebugApi<TxReq: RpcObject> {
 
     /// Returns the structured logs created during the execution of EVM between two blocks
     /// (excluding start) as a JSON object.
+    ///
+    /// The whole range is traced into a single response, so the range is capped. Larger ranges
+    /// should be traced with the `traceChain` subscription, see [`DebugSubscriptionKind`].
     #[method(name = "traceChain")]
     async fn debug_trace_chain(
         &self,
         start_exclusive: BlockNumberOrTag,
         end_inclusive: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
     ) -> RpcResult<Vec<BlockTraceResult>>;
 
+    /// Creates a debug subscription of the given kind.
+    ///
+    /// The only supported kind is [`DebugSubscriptionKind::TraceChain`], which traces every block
+    /// between two blocks (excluding start) and emits one [`BlockTraceResult`] per block, in
+    /// block order. The subscription ends after the last block has been emitted.
+    #[subscription(
+        name = "subscribe" => "subscription",
+        unsubscribe = "unsubscribe",
+        item = BlockTraceResult
+    )]
+    async fn debug_subscribe(
+        &self,
+        kind: DebugSubscriptionKind,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> jsonrpsee::core::SubscriptionResult;
+
     /// The `debug_traceBlock` method will return a full stack trace of all invoked opcodes of all
     /// transaction that were included in this block.
     ///
@@ -172,17 +262,21 @@ pub trait DebugApi<TxReq: RpcObject> {
     /// in the page and the items have keys that come after the `start` key (hashed address).
     ///
     /// If incompletes is false, then accounts for which the key preimage (i.e: the address) doesn't
-    /// exist in db are skipped. NB: geth by default does not store preimages.
+    /// exist in db are skipped. NB: geth by default does not store preimages, reth never does, so
+    /// reth rejects requests unless incompletes is true.
+    ///
+    /// The storage of accounts is truncated to a limited number of slots, in which case the result
+    /// is marked as incomplete.
     #[method(name = "accountRange")]
     async fn debug_account_range(
         &self,
-        block_number: BlockNumberOrTag,
+        block_id: BlockId,
         start: Bytes,
         max_results: u64,
         nocode: bool,
         nostorage: bool,
         incompletes: bool,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<AccountRangeResult>;
 
     /// Turns on block profiling for the given duration and writes profile data to disk. It uses a
     /// profile rate of 1 for most accurate information. If a different rate is desired, set the
@@ -266,17 +360,18 @@ pub trait DebugApi<TxReq: RpcObject> {
     async fn debug_get_modified_accounts_by_hash(
         &self,
         start_hash: B256,
-        end_hash: B256,
-    ) -> RpcResult<()>;
+        end_hash: Option<B256>,
+    ) -> RpcResult<Vec<Address>>;
 
     /// Returns all accounts that have changed between the two blocks specified. A change is defined
-    /// as a difference in nonce, balance, code hash or storage hash.
+    /// as a difference in nonce, balance, code hash or storage hash. With one parameter, returns
+    /// the list of accounts modified in the specified block.
     #[method(name = "getModifiedAccountsByNumber")]
     async fn debug_get_modified_accounts_by_number(
         &self,
         start_number: u64,
-        end_number: u64,
-    ) -> RpcResult<()>;
+        end_number: Option<u64>,
+    ) -> RpcResult<Vec<Address>>;
 
     /// Turns on Go runtime tracing for the given duration and writes trace data to disk.
     #[method(name = "goTrace")]
@@ -342,22 +437,23 @@ pub trait DebugApi<TxReq: RpcObject> {
     #[method(name = "stacks")]
     async fn debug_stacks(&self) -> RpcResult<()>;
 
-    /// Used to obtain info about a block.
+    /// This method is similar to `debug_standardTraceBlockToFile`, but can be used to obtain info
+    /// about a block which has been rejected as invalid (for some reason).
     #[method(name = "standardTraceBadBlockToFile")]
     async fn debug_standard_trace_bad_block_to_file(
         &self,
-        block: BlockNumberOrTag,
-        opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+        block_hash: B256,
+        opts: Option<StdTraceConfig>,
+    ) -> RpcResult<Vec<String>>;
 
-    /// This method is similar to `debug_standardTraceBlockToFile`, but can be used to obtain info
-    /// about a block which has been rejected as invalid (for some reason).
+    /// Replays the block and writes an EIP-3155 trace of every transaction to its own file.
+    /// Returns the paths of the written files.
     #[method(name = "standardTraceBlockToFile")]
     async fn debug_standard_trace_block_to_file(
         &self,
-        block: BlockNumberOrTag,
-        opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+        block_hash: B256,
+        opts: Option<StdTraceConfig>,
+    ) -> RpcResult<Vec<String>>;
 
     /// Turns on CPU profiling indefinitely, writing to the given file.
     #[method(name = "startCPUProfile")]
@@ -387,6 +483,8 @@ pub trait DebugApi<TxReq: RpcObject> {
     /// Returns the storage at the given block height and transaction index. The result can be
     /// paged by providing a `maxResult` to cap the number of storage slots returned as well as
     /// specifying the offset via `keyStart` (hash of storage key).
+    ///
+    /// The storage is read after executing the first `txIndex` transactions of the block.
     #[method(name = "storageRangeAt")]
     async fn debug_storage_range_at(
         &self,
@@ -395,7 +493,7 @@ pub trait DebugApi<TxReq: RpcObject> {
         contract_address: Address,
         key_start: B256,
         max_result: u64,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<StorageRangeResult>;
 
     /// Returns the structured logs created during the execution of EVM against a block pulled
     /// from the pool of bad ones and returns them as a JSON object. For the second parameter see
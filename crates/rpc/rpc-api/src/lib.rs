@@ -32,7 +32,13 @@ mod txpool;
 mod validation;
 mod web3;
 
+pub use admin::ReloadConfigResult;
+pub use debug::{
+    AccountRangeResult, DebugSubscriptionKind, StdTraceConfig, StorageRangeEntry,
+    StorageRangeResult,
+};
 pub use testing::{TestingBuildBlockRequestV1, TESTING_BUILD_BLOCK_V1};
+pub use txpool::TxpoolHistoryEntry;
 
 /// re-export of all server traits
 pub use servers::*;
@@ -41,13 +47,15 @@ pub use servers::*;
 pub mod servers {
     pub use crate::{
         admin::AdminApiServer,
+        anvil::AnvilApiServer,
         debug::{DebugApiServer, DebugExecutionWitnessApiServer},
         engine::{EngineApiServer, EngineEthApiServer, IntoEngineApiRpcModule},
+        hardhat::HardhatApiServer,
         mev::{MevFullApiServer, MevSimApiServer},
         miner::MinerApiServer,
         net::NetApiServer,
         otterscan::OtterscanServer,
-        reth::RethApiServer,
+        reth::{RethApiServer, RethFeesApiServer},
         reth_engine::{RethEngineApiServer, RethNewPayloadInput, RethPayloadStatus},
         rpc::RpcApiServer,
         testing::TestingApiServer,
@@ -79,7 +87,7 @@ pub mod clients {
         miner::MinerApiClient,
         net::NetApiClient,
         otterscan::OtterscanClient,
-        reth::RethApiClient,
+        reth::{RethApiClient, RethFeesApiClient},
         reth_engine::RethEngineApiClient,
         rpc::RpcApiClient,
         testing::TestingApiClient,
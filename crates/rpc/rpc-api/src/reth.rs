@@ -1,6 +1,7 @@
 use alloy_eips::BlockId;
 use alloy_primitives::{map::AddressMap, U256, U64};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_rpc_eth_types::FeeEstimates;
 
 // Required for the subscription attributes below
 use reth_chain_state as _;
@@ -59,3 +60,13 @@ pub trait RethApi {
         &self,
     ) -> jsonrpsee::core::SubscriptionResult;
 }
+
+/// Reth API namespace for fee estimation.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethFeesApi {
+    /// Returns slow, standard and fast estimates for the priority fee and the blob fee, based on
+    /// recent blocks and the pending transaction pool.
+    #[method(name = "feeEstimates")]
+    async fn reth_fee_estimates(&self) -> RpcResult<FeeEstimates>;
+}
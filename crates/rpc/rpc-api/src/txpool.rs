@@ -1,7 +1,28 @@
 use alloy_json_rpc::RpcObject;
-use alloy_primitives::Address;
+use alloy_primitives::{Address, TxHash};
 use alloy_rpc_types_txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolStatus};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
+
+/// A recorded transition of a pool transaction returned by `txpool_history` and
+/// `txpool_senderHistory`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxpoolHistoryEntry {
+    /// Time of the transition in milliseconds since the unix epoch.
+    pub timestamp: u64,
+    /// Hash of the transaction.
+    pub hash: TxHash,
+    /// Sender of the transaction, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sender: Option<Address>,
+    /// The new state of the transaction, one of `pending`, `queued`, `mined`, `replaced`,
+    /// `discarded` or `invalid`.
+    pub event: String,
+    /// Why the transaction transitioned, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
 
 /// Txpool rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "txpool"))]
@@ -34,4 +55,16 @@ pub trait TxPoolApi<T: RpcObject> {
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content) for more details
     #[method(name = "content")]
     async fn txpool_content(&self) -> RpcResult<TxpoolContent<T>>;
+
+    /// Returns the recorded transitions of the transaction with the given hash, oldest first.
+    ///
+    /// Requires the transaction history to be enabled with `--txpool.history`.
+    #[method(name = "history")]
+    async fn txpool_history(&self, hash: TxHash) -> RpcResult<Vec<TxpoolHistoryEntry>>;
+
+    /// Returns the recorded transitions of all transactions of the given sender, oldest first.
+    ///
+    /// Requires the transaction history to be enabled with `--txpool.history`.
+    #[method(name = "senderHistory")]
+    async fn txpool_sender_history(&self, address: Address) -> RpcResult<Vec<TxpoolHistoryEntry>>;
 }
@@ -1,7 +1,7 @@
 use jsonrpsee::server::ServerConfigBuilder;
 use reth_node_core::{args::RpcServerArgs, utils::get_or_create_jwt_secret_from_path};
 use reth_rpc::ValidationApiConfig;
-use reth_rpc_eth_types::{EthConfig, EthStateCacheConfig, GasPriceOracleConfig};
+use reth_rpc_eth_types::{EraHistoryConfig, EthConfig, EthStateCacheConfig, GasPriceOracleConfig};
 use reth_rpc_layer::{JwtError, JwtSecret};
 use reth_rpc_server_types::RpcModuleSelection;
 use std::{net::SocketAddr, path::PathBuf};
@@ -33,6 +33,9 @@ pub trait RethRpcServerConfig {
     /// Returns state cache configuration.
     fn state_cache_config(&self) -> EthStateCacheConfig;
 
+    /// Returns the configuration of the ERA1 files expired blocks are served from.
+    fn era_history_config(&self) -> EraHistoryConfig;
+
     /// Returns the max request size in bytes.
     fn rpc_max_request_size_bytes(&self) -> u32;
 
@@ -102,12 +105,14 @@ impl RethRpcServerConfig for RpcServerArgs {
             .rpc_gas_cap(self.rpc_gas_cap)
             .rpc_max_simulate_blocks(self.rpc_max_simulate_blocks)
             .state_cache(self.state_cache_config())
+            .era_history(self.era_history_config())
             .gpo_config(self.gas_price_oracle_config())
             .proof_permits(self.rpc_proof_permits)
             .pending_block_kind(self.rpc_pending_block)
             .raw_tx_forwarder(self.rpc_forwarder.clone())
             .rpc_evm_memory_limit(self.rpc_evm_memory_limit)
             .force_blob_sidecar_upcasting(self.rpc_force_blob_sidecar_upcasting)
+            .debug_trace_dir(self.rpc_debug_trace_dir.clone())
     }
 
     fn flashbots_config(&self) -> ValidationApiConfig {
@@ -127,6 +132,15 @@ impl RethRpcServerConfig for RpcServerArgs {
         }
     }
 
+    fn era_history_config(&self) -> EraHistoryConfig {
+        EraHistoryConfig {
+            dir: self.rpc_state_cache.era_dir.clone(),
+            url: self.rpc_state_cache.era_url.clone(),
+            max_cached_files: self.rpc_state_cache.max_era_files,
+            max_downloads_per_hour: self.rpc_state_cache.max_era_downloads_per_hour,
+        }
+    }
+
     fn rpc_max_request_size_bytes(&self) -> u32 {
         self.rpc_max_request_size.get().saturating_mul(1024 * 1024)
     }
@@ -32,14 +32,17 @@ use jsonrpsee::{
 };
 use reth_chainspec::{ChainSpecProvider, EthereumHardforks};
 use reth_consensus::FullConsensus;
-use reth_engine_primitives::{ConsensusEngineEvent, ConsensusEngineHandle};
+use reth_engine_primitives::{
+    ConsensusEngineEvent, ConsensusEngineHandle, PersistencePauseHandle, SetHeadHandle,
+};
 use reth_evm::ConfigureEvm;
 use reth_network_api::{noop::NoopNetwork, NetworkInfo, Peers};
+use reth_node_core::config_reload::ConfigReloadHandle;
 use reth_payload_primitives::PayloadTypes;
 use reth_primitives_traits::{NodePrimitives, TxTy};
 use reth_rpc::{
-    AdminApi, DebugApi, EngineEthApi, EthApi, EthApiBuilder, EthBundle, MinerApi, NetApi,
-    OtterscanApi, RPCApi, RethApi, TraceApi, TxPoolApi, Web3Api,
+    AdminApi, DebugApi, EngineEthApi, EthApi, EthApiBuilder, EthBundle, EthConditional, MinerApi,
+    NetApi, OtterscanApi, RPCApi, RethApi, RethFeesApi, TraceApi, TxPoolApi, Web3Api,
 };
 use reth_rpc_api::servers::*;
 use reth_rpc_engine_api::RethEngineApi;
@@ -347,7 +350,13 @@ where
     {
         let config = module_config.config.clone().unwrap_or_default();
 
-        let mut registry = self.into_registry(config, eth, engine_events);
+        let mut registry = self
+            .into_registry(config, eth, engine_events)
+            .with_set_head_handle(SetHeadHandle::new(beacon_engine_handle.clone()))
+            .with_persistence_pause_handle(PersistencePauseHandle::new(
+                beacon_engine_handle.clone(),
+            ))
+            .with_config_reload_handle(ConfigReloadHandle::new());
         let modules = registry.create_transport_rpc_modules(module_config);
         let auth_module = registry.create_auth_module(engine, beacon_engine_handle);
 
@@ -506,6 +515,12 @@ pub struct RpcRegistryInner<Provider, Pool, Network, EthApi: EthApiTypes, EvmCon
     /// Notification channel for engine API events
     engine_events:
         EventSender<ConsensusEngineEvent<<EthApi::RpcConvert as RpcConvert>::Primitives>>,
+    /// Handle to reset the canonical head of the engine, used by `debug_setHead`
+    set_head: Option<SetHeadHandle>,
+    /// Handle to reload the config file of the node, used by `admin_reloadConfig`
+    config_reload: Option<ConfigReloadHandle>,
+    /// Handle to pause the persistence of the engine, used by `admin_pausePersistence`
+    persistence: Option<PersistencePauseHandle>,
 }
 
 // === impl RpcRegistryInner ===
@@ -559,8 +574,32 @@ where
             eth_config: config.eth,
             evm_config,
             engine_events,
+            set_head: None,
+            config_reload: None,
+            persistence: None,
         }
     }
+
+    /// Configures the handle used by `debug_setHead` to reset the canonical head of the engine.
+    pub fn with_set_head_handle(mut self, set_head: SetHeadHandle) -> Self {
+        self.set_head = Some(set_head);
+        self
+    }
+
+    /// Configures the handle used by `admin_reloadConfig` to reload the config file.
+    ///
+    /// A config reloader must be attached to the handle, see [`ConfigReloadHandle::attach`].
+    pub fn with_config_reload_handle(mut self, config_reload: ConfigReloadHandle) -> Self {
+        self.config_reload = Some(config_reload);
+        self
+    }
+
+    /// Configures the handle used by `admin_pausePersistence` and `admin_resumePersistence` to
+    /// pause the persistence of the engine.
+    pub fn with_persistence_pause_handle(mut self, persistence: PersistencePauseHandle) -> Self {
+        self.persistence = Some(persistence);
+        self
+    }
 }
 
 impl<Provider, Pool, Network, EthApi, Evm, Consensus>
@@ -598,6 +637,11 @@ where
         &self.evm_config
     }
 
+    /// Returns the handle used by `admin_reloadConfig`, if configured.
+    pub const fn config_reload_handle(&self) -> Option<&ConfigReloadHandle> {
+        self.config_reload.as_ref()
+    }
+
     /// Returns all installed methods
     pub fn methods(&self) -> Vec<Methods> {
         self.modules.values().cloned().collect()
@@ -628,6 +672,8 @@ where
         Pool: TransactionPool + Clone + 'static,
     {
         AdminApi::new(self.network.clone(), self.provider.chain_spec(), self.pool.clone())
+            .with_config_reload_handle(self.config_reload.clone())
+            .with_persistence_pause_handle(self.persistence.clone())
     }
 
     /// Instantiates `Web3Api`
@@ -827,6 +873,8 @@ where
             self.blocking_pool_guard.clone(),
             self.tasks(),
             self.engine_events.new_listener(),
+            self.eth_config.debug_trace_dir.clone(),
+            self.set_head.clone(),
         )
     }
 
@@ -964,6 +1012,8 @@ where
                             self.provider.chain_spec(),
                             self.pool.clone(),
                         )
+                        .with_config_reload_handle(self.config_reload.clone())
+                        .with_persistence_pause_handle(self.persistence.clone())
                         .into_rpc()
                         .into(),
                         RethRpcModule::Debug => DebugApi::new(
@@ -971,6 +1021,8 @@ where
                             self.blocking_pool_guard.clone(),
                             &self.executor,
                             self.engine_events.new_listener(),
+                            self.eth_config.debug_trace_dir.clone(),
+                            self.set_head.clone(),
                         )
                         .into_rpc()
                         .into(),
@@ -979,14 +1031,20 @@ where
                             let mut module = eth_api.clone().into_rpc();
                             module.merge(eth_filter.clone().into_rpc()).expect("No conflicts");
                             module.merge(eth_pubsub.clone().into_rpc()).expect("No conflicts");
+                            // the full bundle api is only available if the pool accepts bundles
+                            let bundle_api =
+                                EthBundle::new(eth_api.clone(), self.blocking_pool_guard.clone());
+                            if self.pool.bundle_pool().is_some() {
+                                module
+                                    .merge(EthBundleApiServer::into_rpc(bundle_api))
+                                    .expect("No conflicts");
+                            } else {
+                                module
+                                    .merge(EthCallBundleApiServer::into_rpc(bundle_api))
+                                    .expect("No conflicts");
+                            }
                             module
-                                .merge(
-                                    EthBundle::new(
-                                        eth_api.clone(),
-                                        self.blocking_pool_guard.clone(),
-                                    )
-                                    .into_rpc(),
-                                )
+                                .merge(EthConditional::new(eth_api.clone()).into_rpc())
                                 .expect("No conflicts");
 
                             module.into()
@@ -1017,25 +1075,38 @@ where
                         .into_rpc()
                         .into(),
                         RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
-                        RethRpcModule::Reth => RethApi::new(
-                            self.provider.clone(),
-                            self.evm_config.clone(),
-                            self.blocking_pool_guard.clone(),
-                            self.executor.clone(),
-                        )
-                        .into_rpc()
-                        .into(),
+                        RethRpcModule::Reth => {
+                            let mut module = RethApi::new(
+                                self.provider.clone(),
+                                self.evm_config.clone(),
+                                self.blocking_pool_guard.clone(),
+                                self.executor.clone(),
+                            )
+                            .into_rpc();
+                            module
+                                .merge(RethFeesApi::new(eth_api.clone()).into_rpc())
+                                .expect("No conflicts");
+                            module.into()
+                        }
                         RethRpcModule::Miner => MinerApi::default().into_rpc().into(),
                         RethRpcModule::Mev => {
-                            EthSimBundle::new(eth_api.clone(), self.blocking_pool_guard.clone())
-                                .into_rpc()
-                                .into()
+                            let mev_api = EthSimBundle::new(
+                                eth_api.clone(),
+                                self.blocking_pool_guard.clone(),
+                            );
+                            if self.pool.bundle_pool().is_some() {
+                                MevFullApiServer::into_rpc(mev_api).into()
+                            } else {
+                                MevSimApiServer::into_rpc(mev_api).into()
+                            }
                         }
                         // these are implementation specific and need to be handled during
                         // initialization and should be registered via extend_rpc_modules in the
                         // nodebuilder rpc addon stack
                         RethRpcModule::Flashbots |
                         RethRpcModule::Testing |
+                        RethRpcModule::Anvil |
+                        RethRpcModule::Hardhat |
                         RethRpcModule::Other(_) => Default::default(),
                     })
                     .clone()
@@ -1067,6 +1138,9 @@ where
             modules: self.modules.clone(),
             eth_config: self.eth_config.clone(),
             engine_events: self.engine_events.clone(),
+            set_head: self.set_head.clone(),
+            config_reload: self.config_reload.clone(),
+            persistence: self.persistence.clone(),
         }
     }
 }
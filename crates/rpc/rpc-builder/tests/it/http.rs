@@ -446,6 +446,62 @@ where
     DebugApiClient::<TransactionRequest>::raw_transaction(client, B256::default()).await.unwrap();
     DebugApiClient::<TransactionRequest>::raw_receipts(client, block_id).await.unwrap_err();
     DebugApiClient::<TransactionRequest>::bad_blocks(client).await.unwrap();
+    DebugApiClient::<TransactionRequest>::debug_account_range(
+        client,
+        block_id,
+        Bytes::default(),
+        10,
+        true,
+        true,
+        true,
+    )
+    .await
+    .unwrap_err();
+    DebugApiClient::<TransactionRequest>::debug_storage_range_at(
+        client,
+        B256::default(),
+        0,
+        Address::default(),
+        B256::default(),
+        10,
+    )
+    .await
+    .unwrap_err();
+    DebugApiClient::<TransactionRequest>::debug_get_modified_accounts_by_number(client, 1, None)
+        .await
+        .unwrap_err();
+    DebugApiClient::<TransactionRequest>::debug_get_modified_accounts_by_number(client, 1, Some(1))
+        .await
+        .unwrap_err();
+    DebugApiClient::<TransactionRequest>::debug_get_modified_accounts_by_hash(
+        client,
+        B256::default(),
+        None,
+    )
+    .await
+    .unwrap_err();
+    DebugApiClient::<TransactionRequest>::debug_trace_chain(
+        client,
+        BlockNumberOrTag::Number(0),
+        BlockNumberOrTag::Number(1000),
+        None,
+    )
+    .await
+    .unwrap_err();
+    DebugApiClient::<TransactionRequest>::debug_standard_trace_block_to_file(
+        client,
+        B256::default(),
+        None,
+    )
+    .await
+    .unwrap_err();
+    DebugApiClient::<TransactionRequest>::debug_standard_trace_bad_block_to_file(
+        client,
+        B256::default(),
+        None,
+    )
+    .await
+    .unwrap_err();
 }
 
 async fn test_basic_net_calls<C>(client: &C)
@@ -573,28 +629,24 @@ where
     .err()
     .unwrap();
 
-    assert!(is_unimplemented(
-        OtterscanClient::<Transaction, Header>::search_transactions_before(
-            client,
-            address,
-            LenientBlockNumberOrTag::new(BlockNumberOrTag::Number(block_number)),
-            page_size,
-        )
-        .await
-        .err()
-        .unwrap()
-    ));
-    assert!(is_unimplemented(
-        OtterscanClient::<Transaction, Header>::search_transactions_after(
-            client,
-            address,
-            LenientBlockNumberOrTag::new(BlockNumberOrTag::Number(block_number)),
-            page_size,
-        )
-        .await
-        .err()
-        .unwrap()
-    ));
+    OtterscanClient::<Transaction, Header>::search_transactions_before(
+        client,
+        address,
+        LenientBlockNumberOrTag::new(BlockNumberOrTag::Number(block_number)),
+        page_size,
+    )
+    .await
+    .err()
+    .unwrap();
+    OtterscanClient::<Transaction, Header>::search_transactions_after(
+        client,
+        address,
+        LenientBlockNumberOrTag::new(BlockNumberOrTag::Number(block_number)),
+        page_size,
+    )
+    .await
+    .err()
+    .unwrap();
     assert!(OtterscanClient::<Transaction, Header>::get_transaction_by_sender_and_nonce(
         client, sender, nonce
     )
@@ -2,7 +2,7 @@
 
 use super::LoadBlock;
 use crate::FromEthApiError;
-use alloy_consensus::BlockHeader;
+use alloy_consensus::{BlockHeader, Transaction as _};
 use alloy_eips::eip7840::BlobParams;
 use alloy_primitives::U256;
 use alloy_rpc_types_eth::{BlockNumberOrTag, FeeHistory};
@@ -10,12 +10,17 @@ use futures::{Future, StreamExt};
 use reth_chainspec::{ChainSpecProvider, EthChainSpec};
 use reth_primitives_traits::BlockBody;
 use reth_rpc_eth_types::{
-    fee_history::calculate_reward_percentiles_for_block, utils::checked_blob_gas_used_ratio,
-    EthApiError, FeeHistoryCache, FeeHistoryEntry, GasPriceOracle, RpcInvalidTransactionError,
+    fee_estimates::{estimate_fees, PendingFees},
+    fee_history::calculate_reward_percentiles_for_block,
+    utils::checked_blob_gas_used_ratio,
+    EthApiError, FeeEstimates, FeeHistoryCache, FeeHistoryEntry, GasPriceOracle,
+    RpcInvalidTransactionError,
 };
+use reth_rpc_server_types::constants::gas_oracle::MAX_FEE_ESTIMATE_PENDING_TRANSACTIONS;
 use reth_storage_api::{
     BlockIdReader, BlockNumReader, BlockReaderIdExt, HeaderProvider, ProviderHeader,
 };
+use reth_transaction_pool::TransactionPool;
 use tracing::debug;
 
 /// Fee related functions for the [`EthApiServer`](crate::EthApiServer) trait in the
@@ -268,6 +273,65 @@ pub trait EthFees:
         }
     }
 
+    /// Returns slow, standard and fast estimates for the priority fee and the blob fee.
+    ///
+    /// The estimates are computed from the fee history of the configured number of recent blocks
+    /// and the fees of the best transactions in the pending pool, see [`estimate_fees`]. They are
+    /// computed once per block and served from the [`GasPriceOracle`] until the next block.
+    fn fee_estimates(&self) -> impl Future<Output = Result<FeeEstimates, Self::Error>> + Send {
+        async move {
+            let latest = self
+                .provider()
+                .latest_header()
+                .map_err(Self::Error::from_eth_err)?
+                .ok_or(EthApiError::HeaderNotFound(BlockNumberOrTag::Latest.into()))?;
+            if let Some(estimates) = self.gas_oracle().cached_fee_estimates(latest.hash()).await {
+                return Ok(estimates)
+            }
+
+            let config = self.gas_oracle().config();
+            let percentiles = config.fee_estimate_percentiles;
+            let max_price = config.max_price.map(|price| price.saturating_to());
+            let history = self
+                .fee_history(
+                    config.blocks as u64,
+                    latest.number().into(),
+                    Some(percentiles.reward_percentiles()),
+                )
+                .await?;
+
+            let blob_params = self
+                .provider()
+                .chain_spec()
+                .blob_params_at_timestamp(latest.timestamp())
+                .unwrap_or_else(BlobParams::cancun);
+
+            let base_fee = history.base_fee_per_gas.last().copied().unwrap_or_default() as u64;
+            let mut pending = PendingFees {
+                block_gas_limit: latest.gas_limit(),
+                max_blob_count: blob_params.max_blob_count,
+                ..Default::default()
+            };
+            // the best transactions are the ones competing for the next block, so sampling them is
+            // enough to tell whether the block is congested
+            for tx in self.pool().pending_transactions_max(MAX_FEE_ESTIMATE_PENDING_TRANSACTIONS) {
+                let tx = &tx.transaction;
+                if let Some(tip) = tx.effective_tip_per_gas(base_fee) {
+                    pending.tips.push((tip, tx.gas_limit()));
+                }
+                if let (Some(blob_fee), Some(blob_count)) =
+                    (tx.max_fee_per_blob_gas(), tx.blob_count())
+                {
+                    pending.blob_fees.push((blob_fee, blob_count));
+                }
+            }
+
+            let estimates = estimate_fees(&history, percentiles, pending, max_price);
+            self.gas_oracle().cache_fee_estimates(latest.hash(), estimates.clone()).await;
+            Ok(estimates)
+        }
+    }
+
     /// Approximates reward at a given percentile for a specific block
     /// Based on the configured resolution
     fn approximate_percentile(
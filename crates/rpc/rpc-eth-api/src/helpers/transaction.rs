@@ -290,6 +290,20 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
                 }
             }
 
+            // Transactions of blocks that expired from the database are read from ERA1 files,
+            // if configured.
+            if let Some(cached) = self
+                .cache()
+                .get_expired_transaction(hash)
+                .await
+                .map_err(Self::Error::from_eth_err)? &&
+                let Some(tx) = cached.recovered_transaction().map(|tx| tx.cloned()) &&
+                let Some(all_receipts) = cached.receipts.clone() &&
+                let Some(receipt) = all_receipts.get(cached.tx_index).cloned()
+            {
+                return Ok(Some((tx, cached.transaction_meta(hash), receipt, Some(all_receipts))));
+            }
+
             // Full cache miss — fetch both from provider.
             self.spawn_blocking_io(move |this| {
                 let provider = this.provider();
@@ -662,6 +676,18 @@ pub trait LoadTransaction: SpawnBlocking + FullEthApiTypes + RpcNodeCoreExt {
                 return Ok(Some(source));
             }
 
+            // Transactions of blocks that expired from the database are read from ERA1 files, if
+            // configured
+            if let Some(cached) = self
+                .cache()
+                .get_expired_transaction(hash)
+                .await
+                .map_err(Self::Error::from_eth_err)? &&
+                let Some(source) = cached.to_transaction_source()
+            {
+                return Ok(Some(source));
+            }
+
             // Cache miss - try to find the transaction on disk
             if let Some((tx, meta)) = self
                 .spawn_blocking_io(move |this| {
@@ -8,7 +8,8 @@ use reth_node_api::{FullNodeComponents, NodePrimitives, PrimitivesTy};
 use reth_primitives_traits::{BlockTy, HeaderTy, ReceiptTy, TxTy};
 use reth_rpc_eth_types::EthStateCache;
 use reth_storage_api::{
-    BlockReader, BlockReaderIdExt, StageCheckpointReader, StateProviderFactory,
+    AddressTransactionsReader, BlockReader, BlockReaderIdExt, ChangeSetReader,
+    HashedStateProviderFactory, LogIndexReader, StageCheckpointReader, StateProviderFactory,
 };
 use reth_transaction_pool::{PoolTransaction, TransactionPool};
 
@@ -37,6 +38,10 @@ pub trait RpcNodeCore: Clone + Send + Sync + Unpin + 'static {
         > + StateProviderFactory
         + CanonStateSubscriptions<Primitives = Self::Primitives>
         + StageCheckpointReader
+        + AddressTransactionsReader
+        + LogIndexReader
+        + HashedStateProviderFactory
+        + ChangeSetReader
         + Send
         + Sync
         + Clone
@@ -130,6 +135,10 @@ where
         > + StateProviderFactory
         + CanonStateSubscriptions<Primitives = Evm::Primitives>
         + StageCheckpointReader
+        + AddressTransactionsReader
+        + LogIndexReader
+        + HashedStateProviderFactory
+        + ChangeSetReader
         + Send
         + Sync
         + Unpin
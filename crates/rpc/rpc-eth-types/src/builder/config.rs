@@ -1,10 +1,10 @@
 //! Configuration for `eth` namespace APIs.
 
-use std::time::Duration;
+use std::{path::PathBuf, time::Duration};
 
 use crate::{
-    EthStateCacheConfig, FeeHistoryCacheConfig, ForwardConfig, GasPriceOracleConfig,
-    RPC_DEFAULT_GAS_CAP,
+    EraHistoryConfig, EthStateCacheConfig, FeeHistoryCacheConfig, ForwardConfig,
+    GasPriceOracleConfig, RPC_DEFAULT_GAS_CAP,
 };
 use reqwest::Url;
 use reth_rpc_server_types::constants::{
@@ -63,6 +63,8 @@ impl PendingBlockKind {
 pub struct EthConfig {
     /// Settings for the caching layer
     pub cache: EthStateCacheConfig,
+    /// Settings for the ERA1 files blocks that expired from the database are served from
+    pub era_history: EraHistoryConfig,
     /// Settings for the gas price oracle
     pub gas_oracle: GasPriceOracleConfig,
     /// The maximum number of blocks into the past for generating state proofs.
@@ -112,6 +114,11 @@ pub struct EthConfig {
     /// This is disabled by default, allowing blob transactions with EIP-4844 sidecars to be
     /// submitted without automatic conversion.
     pub force_blob_sidecar_upcasting: bool,
+    /// Directory `debug_standardTraceBlockToFile` and `debug_standardTraceBadBlockToFile` write
+    /// their trace files to.
+    ///
+    /// Falls back to the system's temporary directory if not set.
+    pub debug_trace_dir: Option<PathBuf>,
 }
 
 impl EthConfig {
@@ -128,6 +135,7 @@ impl Default for EthConfig {
     fn default() -> Self {
         Self {
             cache: EthStateCacheConfig::default(),
+            era_history: EraHistoryConfig::default(),
             gas_oracle: GasPriceOracleConfig::default(),
             eth_proof_window: DEFAULT_ETH_PROOF_WINDOW,
             max_tracing_requests: default_max_tracing_requests(),
@@ -146,6 +154,7 @@ impl Default for EthConfig {
             send_raw_transaction_sync_timeout: RPC_DEFAULT_SEND_RAW_TX_SYNC_TIMEOUT_SECS,
             rpc_evm_memory_limit: (1 << 32) - 1,
             force_blob_sidecar_upcasting: false,
+            debug_trace_dir: None,
         }
     }
 }
@@ -254,6 +263,18 @@ impl EthConfig {
         self.force_blob_sidecar_upcasting = force;
         self
     }
+
+    /// Configures the directory the `debug` namespace writes trace files to.
+    pub fn debug_trace_dir(mut self, dir: Option<PathBuf>) -> Self {
+        self.debug_trace_dir = dir;
+        self
+    }
+
+    /// Configures the ERA1 files blocks that expired from the database are served from.
+    pub fn era_history(mut self, era_history: EraHistoryConfig) -> Self {
+        self.era_history = era_history;
+        self
+    }
 }
 
 /// Config for the filter
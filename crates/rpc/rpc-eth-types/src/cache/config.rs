@@ -1,10 +1,14 @@
 //! Configuration for RPC cache.
 
+use std::path::PathBuf;
+
+use reqwest::Url;
 use serde::{Deserialize, Serialize};
 
 use reth_rpc_server_types::constants::cache::{
     DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_CONCURRENT_DB_REQUESTS, DEFAULT_HEADER_CACHE_MAX_LEN,
-    DEFAULT_MAX_CACHED_TX_HASHES, DEFAULT_RECEIPT_CACHE_MAX_LEN,
+    DEFAULT_MAX_CACHED_ERA_FILES, DEFAULT_MAX_CACHED_TX_HASHES, DEFAULT_MAX_ERA_DOWNLOADS_PER_HOUR,
+    DEFAULT_RECEIPT_CACHE_MAX_LEN,
 };
 
 /// Settings for the [`EthStateCache`](super::EthStateCache).
@@ -42,3 +46,33 @@ impl Default for EthStateCacheConfig {
         }
     }
 }
+
+/// Settings for the [`EraHistory`](super::era::EraHistory) expired blocks are served from.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EraHistoryConfig {
+    /// Directory of the ERA1 files.
+    ///
+    /// Files downloaded from the [`url`](Self::url) are stored in its `downloads` subdirectory,
+    /// which is cleared on startup.
+    pub dir: Option<PathBuf>,
+    /// Remote host ERA1 files that are missing in the directory are downloaded from.
+    pub url: Option<Url>,
+    /// Max number of ERA1 files that are indexed at the same time.
+    ///
+    /// Downloaded files are deleted once they are evicted.
+    pub max_cached_files: u32,
+    /// Max number of ERA1 files downloaded from the [`url`](Self::url) per hour.
+    pub max_downloads_per_hour: u32,
+}
+
+impl Default for EraHistoryConfig {
+    fn default() -> Self {
+        Self {
+            dir: None,
+            url: None,
+            max_cached_files: DEFAULT_MAX_CACHED_ERA_FILES,
+            max_downloads_per_hour: DEFAULT_MAX_ERA_DOWNLOADS_PER_HOUR,
+        }
+    }
+}
@@ -0,0 +1,569 @@
+//! Serves blocks that expired from the database from ERA1 files.
+//!
+//! Pruned nodes drop the bodies and receipts of old blocks, but keep their headers and the
+//! transaction lookup table. This allows to resolve the number of an expired block and read it from
+//! the ERA1 file that contains it on demand.
+//!
+//! Files are downloaded into the `downloads` subdirectory, which is cleared on startup so that
+//! files left behind by an unclean shutdown are not mistaken for local ones.
+
+use super::config::EraHistoryConfig;
+use alloy_consensus::{
+    proofs::calculate_receipt_root, BlockHeader, ReceiptEnvelope, RlpDecodableReceipt,
+};
+use alloy_primitives::{BlockNumber, TxHash, B256};
+use parking_lot::Mutex;
+use reth_era::{
+    common::decode::DecodeCompressedRlp,
+    e2s::{
+        error::E2sError,
+        types::{Entry, IndexEntry},
+    },
+    era1::types::{
+        execution::{CompressedBody, CompressedHeader, CompressedReceipts, MAX_BLOCKS_PER_ERA1},
+        group::BlockIndex,
+    },
+};
+use reth_era_downloader::EraClient;
+use reth_errors::{ProviderError, ProviderResult};
+use reth_primitives_traits::{Block, BlockBody, RecoveredBlock, SealedBlock};
+use reth_storage_api::BlockReader;
+use schnellru::{ByLength, LruMap};
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    fs::File,
+    io::{self, BufReader, Read, Seek, SeekFrom},
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::OnceCell;
+use tracing::{debug, trace};
+
+/// Name of the subdirectory ERA1 files are downloaded into.
+const DOWNLOADS_DIR: &str = "downloads";
+
+/// The window that [`EraHistoryConfig::max_downloads_per_hour`] applies to.
+const DOWNLOAD_WINDOW: Duration = Duration::from_secs(60 * 60);
+
+/// Returns the number of the block with the given hash, if the block expired from the database.
+pub fn expired_block_number<P: BlockReader>(
+    provider: &P,
+    block_hash: B256,
+) -> ProviderResult<Option<BlockNumber>> {
+    let Some(number) = provider.block_number(block_hash)? else { return Ok(None) };
+    Ok((number < provider.earliest_block_number()?).then_some(number))
+}
+
+/// Returns the hash of the expired block that contains the transaction with the given hash.
+///
+/// Returns `None` if the transaction is unknown or its block has not expired.
+pub fn expired_transaction_block<P: BlockReader>(
+    provider: &P,
+    tx_hash: TxHash,
+) -> ProviderResult<Option<B256>> {
+    let Some(tx_number) = provider.transaction_id(tx_hash)? else { return Ok(None) };
+    let Some(number) = provider.block_by_transaction_id(tx_number)? else { return Ok(None) };
+    if number >= provider.earliest_block_number()? {
+        return Ok(None)
+    }
+    provider.block_hash(number)
+}
+
+/// Errors that can occur when reading blocks from ERA1 files.
+#[derive(Debug, thiserror::Error)]
+pub enum EraHistoryError {
+    /// Failed to access an ERA1 file.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// Failed to decode an ERA1 file.
+    #[error(transparent)]
+    E2s(#[from] E2sError),
+    /// Failed to decode the receipts of a block.
+    #[error(transparent)]
+    Rlp(#[from] alloy_rlp::Error),
+    /// Failed to download an ERA1 file.
+    #[error("failed to download ERA1 file: {0}")]
+    Download(String),
+    /// Too many ERA1 files were downloaded recently.
+    #[error("ERA1 download limit of {0} files per hour reached")]
+    DownloadLimit(u32),
+    /// The block index of an ERA1 file is malformed.
+    #[error("invalid block index in ERA1 file {}", .0.display())]
+    InvalidIndex(PathBuf),
+    /// The ERA1 file contains a different block at the requested number.
+    #[error("ERA1 block {number} does not match hash {hash}")]
+    HashMismatch {
+        /// The requested block number.
+        number: BlockNumber,
+        /// The requested block hash.
+        hash: B256,
+    },
+    /// The body or receipts of the block do not match its header.
+    #[error("ERA1 block {0} does not match its header")]
+    InvalidBlock(BlockNumber),
+    /// Failed to recover the senders of the block.
+    #[error("failed to recover senders of ERA1 block {0}")]
+    SenderRecovery(BlockNumber),
+}
+
+impl From<EraHistoryError> for ProviderError {
+    fn from(err: EraHistoryError) -> Self {
+        Self::other(err)
+    }
+}
+
+/// Reads blocks that expired from the database from ERA1 files.
+///
+/// Files are looked up in a local directory and, if a remote host is configured, downloaded into
+/// it on demand, up to a limit of files per hour. The block indices of the most recently used
+/// files are kept in an LRU cache.
+///
+/// Reading a block performs blocking file IO, so this is expected to be used from blocking tasks.
+#[derive(Clone)]
+pub struct EraHistory {
+    inner: Arc<EraHistoryInner>,
+}
+
+struct EraHistoryInner {
+    /// Directory of the ERA1 files.
+    dir: PathBuf,
+    /// Client for the remote host, if configured.
+    client: Option<EraClient<reqwest::Client>>,
+    /// Whether the list of remote files was fetched.
+    file_list: OnceCell<()>,
+    /// Serializes the lookups of files that are not cached by era number.
+    lookups: Mutex<HashMap<u64, Arc<tokio::sync::Mutex<()>>>>,
+    /// Max number of files downloaded per hour.
+    max_downloads_per_hour: u32,
+    /// Start times of the downloads of the last hour.
+    downloads: Mutex<VecDeque<Instant>>,
+    /// The most recently used files by era number.
+    files: Mutex<LruMap<u64, Arc<IndexedFile>, ByLength>>,
+}
+
+impl EraHistory {
+    /// Creates a new [`EraHistory`] from the given config.
+    ///
+    /// Returns `None` if no directory is configured.
+    pub fn new(config: EraHistoryConfig) -> Option<Self> {
+        let EraHistoryConfig { dir, url, max_cached_files, max_downloads_per_hour } = config;
+        let dir = dir?;
+        let client = url.map(|url| {
+            let downloads = dir.join(DOWNLOADS_DIR);
+            // downloaded files are only in use while the node is running
+            if let Err(err) = std::fs::remove_dir_all(&downloads) &&
+                err.kind() != io::ErrorKind::NotFound
+            {
+                debug!(target: "rpc::eth::cache", %err, "Failed to remove downloaded ERA1 files");
+            }
+            EraClient::new(reqwest::Client::new(), url, downloads)
+        });
+
+        Some(Self {
+            inner: Arc::new(EraHistoryInner {
+                dir,
+                client,
+                file_list: OnceCell::new(),
+                lookups: Default::default(),
+                max_downloads_per_hour,
+                downloads: Default::default(),
+                files: Mutex::new(LruMap::new(ByLength::new(max_cached_files.max(1)))),
+            }),
+        })
+    }
+
+    /// Returns the block with the given number and hash.
+    ///
+    /// Returns `None` if no ERA1 file contains the block.
+    pub async fn recovered_block<B: Block>(
+        &self,
+        number: BlockNumber,
+        hash: B256,
+    ) -> Result<Option<RecoveredBlock<B>>, EraHistoryError> {
+        let Some((header, body, _)) = self.read_block(number).await? else { return Ok(None) };
+
+        let block = SealedBlock::seal_slow(B::new(header.decode()?, body.decode()?));
+        if block.hash() != hash {
+            return Err(EraHistoryError::HashMismatch { number, hash })
+        }
+        if block.body().calculate_tx_root() != block.header().transactions_root() {
+            return Err(EraHistoryError::InvalidBlock(number))
+        }
+
+        // ERA1 files only contain mined transactions, which can be pre EIP-2
+        block.try_recover_unchecked().map(Some).map_err(|_| EraHistoryError::SenderRecovery(number))
+    }
+
+    /// Returns the receipts of the block with the given number and hash.
+    ///
+    /// Pre-Byzantium receipts contain the intermediate state root instead of a status, they are
+    /// returned as successful.
+    ///
+    /// Returns `None` if no ERA1 file contains the block.
+    pub async fn receipts<R: RlpDecodableReceipt>(
+        &self,
+        number: BlockNumber,
+        hash: B256,
+    ) -> Result<Option<Vec<R>>, EraHistoryError> {
+        let Some((header, _, receipts)) = self.read_block(number).await? else { return Ok(None) };
+
+        let header: alloy_consensus::Header = header.decode()?;
+        if header.hash_slow() != hash {
+            return Err(EraHistoryError::HashMismatch { number, hash })
+        }
+        let receipts: Vec<ReceiptEnvelope> = receipts.decode()?;
+        if calculate_receipt_root(&receipts) != header.receipts_root {
+            return Err(EraHistoryError::InvalidBlock(number))
+        }
+
+        receipts
+            .into_iter()
+            .map(|mut receipt| {
+                if let Some(receipt) = receipt.as_receipt_with_bloom_mut() {
+                    receipt.receipt.status.coerced_eip658();
+                }
+                let encoded = alloy_rlp::encode(&receipt);
+                Ok(R::rlp_decode_with_bloom(&mut encoded.as_slice())?.receipt)
+            })
+            .collect::<Result<_, EraHistoryError>>()
+            .map(Some)
+    }
+
+    /// Reads the compressed header, body and receipts of the block with the given number.
+    async fn read_block(
+        &self,
+        number: BlockNumber,
+    ) -> Result<Option<(CompressedHeader, CompressedBody, CompressedReceipts)>, EraHistoryError>
+    {
+        let era = number / MAX_BLOCKS_PER_ERA1 as u64;
+        let Some(file) = self.file(era).await? else {
+            trace!(target: "rpc::eth::cache", number, era, "No ERA1 file for expired block");
+            return Ok(None)
+        };
+        file.read_block(number)
+    }
+
+    /// Returns the ERA1 file of the given era, downloading it if necessary.
+    async fn file(&self, era: u64) -> Result<Option<Arc<IndexedFile>>, EraHistoryError> {
+        if let Some(file) = self.inner.files.lock().get(&era).cloned() {
+            return Ok(Some(file))
+        }
+
+        let lookup = self.inner.lookups.lock().entry(era).or_default().clone();
+        let file = self.lookup_file(era, &lookup).await;
+
+        // nobody else is waiting for the lookup of this era
+        let mut lookups = self.inner.lookups.lock();
+        if Arc::strong_count(&lookup) == 2 {
+            lookups.remove(&era);
+        }
+
+        file
+    }
+
+    /// Opens the ERA1 file of the given era while holding the `lookup` lock of the era.
+    async fn lookup_file(
+        &self,
+        era: u64,
+        lookup: &tokio::sync::Mutex<()>,
+    ) -> Result<Option<Arc<IndexedFile>>, EraHistoryError> {
+        let _lookup = lookup.lock().await;
+        // the file may have been opened while waiting for the lock
+        if let Some(file) = self.inner.files.lock().get(&era).cloned() {
+            return Ok(Some(file))
+        }
+
+        let file = if let Some(path) = self.local_file(era)? {
+            IndexedFile::open(path, false)?
+        } else if let Some(path) = self.download(era).await? {
+            IndexedFile::open(path, true)?
+        } else {
+            return Ok(None)
+        };
+
+        let file = Arc::new(file);
+        self.inner.files.lock().insert(era, file.clone());
+        Ok(Some(file))
+    }
+
+    /// Returns the path of the ERA1 file of the given era in the local directory.
+    ///
+    /// ERA1 files are named `<network>-<era>-<short-root>.era1`.
+    fn local_file(&self, era: u64) -> Result<Option<PathBuf>, EraHistoryError> {
+        let entries = match std::fs::read_dir(&self.inner.dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension() == Some("era1".as_ref()) &&
+                path.file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .and_then(|stem| stem.split('-').nth(1))
+                    .and_then(|number| number.parse::<u64>().ok()) ==
+                    Some(era)
+            {
+                return Ok(Some(path))
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Downloads the ERA1 file of the given era from the remote host, if configured.
+    async fn download(&self, era: u64) -> Result<Option<PathBuf>, EraHistoryError> {
+        let Some(client) = &self.inner.client else { return Ok(None) };
+
+        std::fs::create_dir_all(self.inner.dir.join(DOWNLOADS_DIR))?;
+        self.inner
+            .file_list
+            .get_or_try_init(|| client.fetch_file_list())
+            .await
+            .map_err(|err| EraHistoryError::Download(err.to_string()))?;
+
+        let Some(url) = client
+            .url(era as usize)
+            .await
+            .map_err(|err| EraHistoryError::Download(err.to_string()))?
+        else {
+            return Ok(None)
+        };
+
+        self.reserve_download()?;
+        debug!(target: "rpc::eth::cache", era, %url, "Downloading ERA1 file");
+        let path = client
+            .clone()
+            .download_to_file(url)
+            .await
+            .map_err(|err| EraHistoryError::Download(err.to_string()))?;
+
+        Ok(Some(path.into_path_buf()))
+    }
+
+    /// Records a download, unless the max number of downloads of the last hour is reached.
+    fn reserve_download(&self) -> Result<(), EraHistoryError> {
+        let now = Instant::now();
+        let mut downloads = self.inner.downloads.lock();
+        while downloads.front().is_some_and(|start| now.duration_since(*start) >= DOWNLOAD_WINDOW) {
+            downloads.pop_front();
+        }
+
+        if downloads.len() >= self.inner.max_downloads_per_hour as usize {
+            return Err(EraHistoryError::DownloadLimit(self.inner.max_downloads_per_hour))
+        }
+        downloads.push_back(now);
+        Ok(())
+    }
+}
+
+impl fmt::Debug for EraHistory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EraHistory")
+            .field("dir", &self.inner.dir)
+            .field("remote", &self.inner.client.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+/// An ERA1 file and its block index.
+#[derive(Debug)]
+struct IndexedFile {
+    path: PathBuf,
+    index: BlockIndex,
+    /// Position of the block index record, the block offsets are relative to it.
+    index_position: u64,
+    /// Whether the file was downloaded and is deleted once it is no longer used.
+    downloaded: bool,
+}
+
+impl IndexedFile {
+    /// Opens the ERA1 file at the given path and reads its block index.
+    ///
+    /// The block index is the last record of the file:
+    /// `header | starting-number | offset* | count`
+    fn open(path: PathBuf, downloaded: bool) -> Result<Self, EraHistoryError> {
+        let mut file = File::open(&path)?;
+        let len = file.seek(SeekFrom::End(-8))? + 8;
+
+        let mut count = [0; 8];
+        file.read_exact(&mut count)?;
+        let count = u64::from_le_bytes(count);
+        if count > MAX_BLOCKS_PER_ERA1 as u64 {
+            return Err(EraHistoryError::InvalidIndex(path))
+        }
+
+        let Some(index_position) = len.checked_sub(8 + 16 + count * 8) else {
+            return Err(EraHistoryError::InvalidIndex(path))
+        };
+        file.seek(SeekFrom::Start(index_position))?;
+        let Some(entry) = Entry::read(&mut file)? else {
+            return Err(EraHistoryError::InvalidIndex(path))
+        };
+        let index = BlockIndex::from_entry(&entry)?;
+
+        Ok(Self { path, index, index_position, downloaded })
+    }
+
+    /// Reads the compressed header, body and receipts of the block with the given number.
+    ///
+    /// Returns `None` if the file does not contain the block.
+    fn read_block(
+        &self,
+        number: BlockNumber,
+    ) -> Result<Option<(CompressedHeader, CompressedBody, CompressedReceipts)>, EraHistoryError>
+    {
+        let Some(offset) = self.index.offset_for_block(number) else { return Ok(None) };
+        let Some(position) = self.index_position.checked_add_signed(offset) else {
+            return Err(EraHistoryError::InvalidIndex(self.path.clone()))
+        };
+
+        let mut reader = BufReader::new(File::open(&self.path)?);
+        reader.seek(SeekFrom::Start(position))?;
+
+        let header = CompressedHeader::from_entry(&self.next_entry(&mut reader)?)?;
+        let body = CompressedBody::from_entry(&self.next_entry(&mut reader)?)?;
+        let receipts = CompressedReceipts::from_entry(&self.next_entry(&mut reader)?)?;
+
+        Ok(Some((header, body, receipts)))
+    }
+
+    fn next_entry(&self, reader: &mut impl Read) -> Result<Entry, EraHistoryError> {
+        Entry::read(reader)?.ok_or_else(|| EraHistoryError::InvalidIndex(self.path.clone()))
+    }
+}
+
+impl Drop for IndexedFile {
+    fn drop(&mut self) {
+        if self.downloaded {
+            trace!(target: "rpc::eth::cache", path = %self.path.display(), "Removing ERA1 file");
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_primitives::U256;
+    use reth_era::{e2s::file::E2StoreWriter, era1::types::execution::BlockTuple};
+    use reth_ethereum_primitives::{Block, Receipt};
+    use std::path::Path;
+
+    /// Writes the blocks into an ERA1 file of era 0.
+    fn write_era1_file(dir: &Path, blocks: &[Block]) -> PathBuf {
+        let path = dir.join("mainnet-00000-00000000.era1");
+        let mut writer = E2StoreWriter::new(File::create(&path).unwrap());
+        writer.write_version().unwrap();
+
+        // the version record is a header without data
+        let mut position = 8u64;
+        let mut positions = Vec::new();
+        for block in blocks {
+            positions.push(position);
+            let tuple =
+                BlockTuple::from_alloy_block(block, &Vec::<ReceiptEnvelope>::new(), U256::ZERO)
+                    .unwrap();
+            for entry in [
+                tuple.header.to_entry(),
+                tuple.body.to_entry(),
+                tuple.receipts.to_entry(),
+                tuple.total_difficulty.to_entry(),
+            ] {
+                position += 8 + entry.data.len() as u64;
+                writer.write_entry(&entry).unwrap();
+            }
+        }
+
+        let offsets = positions.iter().map(|p| *p as i64 - position as i64).collect();
+        writer.write_entry(&BlockIndex::new(0, offsets).to_entry()).unwrap();
+        writer.flush().unwrap();
+        path
+    }
+
+    fn blocks() -> Vec<Block> {
+        let genesis = Block { header: Header::default(), body: Default::default() };
+        let child = Block {
+            header: Header {
+                number: 1,
+                parent_hash: genesis.header.hash_slow(),
+                ..Default::default()
+            },
+            body: Default::default(),
+        };
+        vec![genesis, child]
+    }
+
+    fn history(dir: &Path) -> EraHistory {
+        EraHistory::new(EraHistoryConfig { dir: Some(dir.to_path_buf()), ..Default::default() })
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn reads_blocks_from_local_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let blocks = blocks();
+        let path = write_era1_file(dir.path(), &blocks);
+        let history = history(dir.path());
+
+        for block in &blocks {
+            let hash = block.header.hash_slow();
+            let recovered =
+                history.recovered_block::<Block>(block.header.number, hash).await.unwrap().unwrap();
+            assert_eq!(recovered.hash(), hash);
+
+            let receipts =
+                history.receipts::<Receipt>(block.header.number, hash).await.unwrap().unwrap();
+            assert!(receipts.is_empty());
+        }
+
+        // the file only contains two blocks and there is no file for the next era
+        assert!(history.recovered_block::<Block>(2, B256::ZERO).await.unwrap().is_none());
+        assert!(history
+            .recovered_block::<Block>(MAX_BLOCKS_PER_ERA1 as u64, B256::ZERO)
+            .await
+            .unwrap()
+            .is_none());
+        // local files are kept
+        drop(history);
+        assert!(path.exists());
+    }
+
+    #[tokio::test]
+    async fn rejects_hash_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        write_era1_file(dir.path(), &blocks());
+        let history = history(dir.path());
+
+        let err = history.recovered_block::<Block>(1, B256::ZERO).await.unwrap_err();
+        assert!(matches!(err, EraHistoryError::HashMismatch { number: 1, .. }));
+    }
+
+    #[test]
+    fn clears_downloads_and_limits_them() {
+        let dir = tempfile::tempdir().unwrap();
+        let local = write_era1_file(dir.path(), &blocks());
+        let leftover = dir.path().join(DOWNLOADS_DIR).join("mainnet-00001-00000000.era1");
+        std::fs::create_dir_all(leftover.parent().unwrap()).unwrap();
+        std::fs::write(&leftover, b"partial").unwrap();
+
+        let history = EraHistory::new(EraHistoryConfig {
+            dir: Some(dir.path().to_path_buf()),
+            url: Some("http://localhost".parse().unwrap()),
+            max_downloads_per_hour: 1,
+            ..Default::default()
+        })
+        .unwrap();
+
+        // files left behind by a previous run are removed, local files are kept
+        assert!(!leftover.exists());
+        assert!(local.exists());
+
+        history.reserve_download().unwrap();
+        assert!(matches!(history.reserve_download(), Err(EraHistoryError::DownloadLimit(1))));
+    }
+}
@@ -4,7 +4,8 @@ use super::{EthStateCacheConfig, MultiConsumerLruCache};
 use crate::block::CachedTransaction;
 use alloy_consensus::{transaction::TxHashRef, BlockHeader};
 use alloy_eips::BlockHashOrNumber;
-use alloy_primitives::{TxHash, B256};
+use alloy_primitives::{BlockNumber, TxHash, B256};
+use era::EraHistory;
 use futures::{stream::FuturesOrdered, Stream, StreamExt};
 use reth_chain_state::CanonStateNotification;
 use reth_errors::{ProviderError, ProviderResult};
@@ -27,6 +28,7 @@ use tokio_stream::wrappers::UnboundedReceiverStream;
 
 pub mod config;
 pub mod db;
+pub mod era;
 pub mod metrics;
 pub mod multi_consumer;
 
@@ -51,6 +53,9 @@ type CachedParentBlocksResponseSender<B> = oneshot::Sender<Vec<Arc<RecoveredBloc
 /// The type that can send the response for a transaction hash lookup
 type TransactionHashResponseSender<B, R> = oneshot::Sender<Option<CachedTransaction<B, R>>>;
 
+/// The type that can send the hash of the expired block that contains a transaction
+type ExpiredTransactionBlockResponseSender = oneshot::Sender<ProviderResult<Option<B256>>>;
+
 type BlockLruCache<B, L> =
     MultiConsumerLruCache<B256, Arc<RecoveredBlock<B>>, L, BlockWithSendersResponseSender<B>>;
 
@@ -84,6 +89,7 @@ impl<N: NodePrimitives> EthStateCache<N> {
         max_headers: u32,
         max_concurrent_db_operations: usize,
         max_cached_tx_hashes: u32,
+        era_history: Option<EraHistory>,
     ) -> (Self, EthStateCacheService<Provider, Runtime>)
     where
         Provider: BlockReader<Block = N::Block, Receipt = N::Receipt>,
@@ -100,6 +106,7 @@ impl<N: NodePrimitives> EthStateCache<N> {
             action_task_spawner,
             rate_limiter: Arc::new(Semaphore::new(max_concurrent_db_operations)),
             tx_hash_index: LruMap::new(ByLength::new(max_cached_tx_hashes)),
+            era_history,
         };
         let cache = Self { to_service };
         (cache, service)
@@ -114,6 +121,20 @@ impl<N: NodePrimitives> EthStateCache<N> {
         config: EthStateCacheConfig,
         executor: Runtime,
     ) -> Self
+    where
+        Provider: BlockReader<Block = N::Block, Receipt = N::Receipt> + Clone + Unpin + 'static,
+    {
+        Self::spawn_with_era_history(provider, config, None, executor)
+    }
+
+    /// Creates a new async LRU backed cache service task like [`Self::spawn_with`] that serves
+    /// blocks which expired from the database from the given [`EraHistory`].
+    pub fn spawn_with_era_history<Provider>(
+        provider: Provider,
+        config: EthStateCacheConfig,
+        era_history: Option<EraHistory>,
+        executor: Runtime,
+    ) -> Self
     where
         Provider: BlockReader<Block = N::Block, Receipt = N::Receipt> + Clone + Unpin + 'static,
     {
@@ -132,6 +153,7 @@ impl<N: NodePrimitives> EthStateCache<N> {
             max_headers,
             max_concurrent_db_requests,
             max_cached_tx_hashes,
+            era_history,
         );
         executor.spawn_critical_task("eth state cache", service);
         this
@@ -265,6 +287,36 @@ impl<N: NodePrimitives> EthStateCache<N> {
         let _ = self.to_service.send(CacheAction::GetTransactionByHash { tx_hash, response_tx });
         rx.await.ok()?
     }
+
+    /// Looks up a transaction of a block that expired from the database.
+    ///
+    /// The block and its receipts are read from the [`EraHistory`] and cached like any other
+    /// block.
+    ///
+    /// Returns `None` if no [`EraHistory`] is configured or the transaction is not part of an
+    /// expired block.
+    pub async fn get_expired_transaction(
+        &self,
+        tx_hash: TxHash,
+    ) -> ProviderResult<Option<CachedTransaction<N::Block, N::Receipt>>> {
+        let (response_tx, rx) = oneshot::channel();
+        let _ =
+            self.to_service.send(CacheAction::GetExpiredTransactionBlock { tx_hash, response_tx });
+        let Some(block_hash) = rx.await.map_err(|_| CacheServiceUnavailable)?? else {
+            return Ok(None)
+        };
+
+        let Some((block, receipts)) = self.get_block_and_receipts(block_hash).await? else {
+            return Ok(None)
+        };
+        let Some(tx_index) =
+            block.body().transactions().iter().position(|tx| *tx.tx_hash() == tx_hash)
+        else {
+            return Ok(None)
+        };
+
+        Ok(Some(CachedTransaction::new(block, tx_index, Some(receipts))))
+    }
 }
 /// Thrown when the cache service task dropped.
 #[derive(Debug, thiserror::Error)]
@@ -329,6 +381,8 @@ pub(crate) struct EthStateCacheService<
     rate_limiter: Arc<Semaphore>,
     /// LRU index mapping transaction hashes to their block hash and index within the block.
     tx_hash_index: LruMap<TxHash, (B256, usize), ByLength>,
+    /// ERA1 files blocks that expired from the database are read from.
+    era_history: Option<EraHistory>,
 }
 
 impl<Provider> EthStateCacheService<Provider, Runtime>
@@ -479,19 +533,36 @@ where
                                 let provider = this.provider.clone();
                                 let action_tx = this.action_tx.clone();
                                 let rate_limiter = this.rate_limiter.clone();
+                                let era_history = this.era_history.clone();
                                 let mut action_sender =
                                     ActionSender::new(CacheKind::Block, block_hash, action_tx);
                                 this.action_task_spawner.spawn_blocking_task(async move {
-                                    // Acquire permit
-                                    let _permit = rate_limiter.acquire().await;
+                                    let expired_block = match era_history {
+                                        Some(era_history) => {
+                                            expired_block(
+                                                &era_history,
+                                                &provider,
+                                                &rate_limiter,
+                                                block_hash,
+                                            )
+                                            .await
+                                        }
+                                        None => Ok(None),
+                                    };
                                     // Only look in the database to prevent situations where we
                                     // looking up the tree is blocking
-                                    let block_sender = provider
-                                        .sealed_block_with_senders(
-                                            BlockHashOrNumber::Hash(block_hash),
-                                            TransactionVariant::WithHash,
-                                        )
-                                        .map(|maybe_block| maybe_block.map(Arc::new));
+                                    let block_sender = match expired_block {
+                                        Ok(None) => {
+                                            // Acquire permit
+                                            let _permit = rate_limiter.acquire().await;
+                                            provider.sealed_block_with_senders(
+                                                BlockHashOrNumber::Hash(block_hash),
+                                                TransactionVariant::WithHash,
+                                            )
+                                        }
+                                        res => res,
+                                    }
+                                    .map(|maybe_block| maybe_block.map(Arc::new));
                                     action_sender.send_block(block_sender);
                                 });
                             }
@@ -508,14 +579,31 @@ where
                                 let provider = this.provider.clone();
                                 let action_tx = this.action_tx.clone();
                                 let rate_limiter = this.rate_limiter.clone();
+                                let era_history = this.era_history.clone();
                                 let mut action_sender =
                                     ActionSender::new(CacheKind::Receipt, block_hash, action_tx);
                                 this.action_task_spawner.spawn_blocking_task(async move {
-                                    // Acquire permit
-                                    let _permit = rate_limiter.acquire().await;
-                                    let res = provider
-                                        .receipts_by_block(block_hash.into())
-                                        .map(|maybe_receipts| maybe_receipts.map(Arc::new));
+                                    let expired_receipts = match era_history {
+                                        Some(era_history) => {
+                                            expired_receipts(
+                                                &era_history,
+                                                &provider,
+                                                &rate_limiter,
+                                                block_hash,
+                                            )
+                                            .await
+                                        }
+                                        None => Ok(None),
+                                    };
+                                    let res = match expired_receipts {
+                                        Ok(None) => {
+                                            // Acquire permit
+                                            let _permit = rate_limiter.acquire().await;
+                                            provider.receipts_by_block(block_hash.into())
+                                        }
+                                        res => res,
+                                    }
+                                    .map(|maybe_receipts| maybe_receipts.map(Arc::new));
 
                                     action_sender.send_receipts(res);
                                 });
@@ -646,6 +734,21 @@ where
                                 });
                             let _ = response_tx.send(result);
                         }
+                        CacheAction::GetExpiredTransactionBlock { tx_hash, response_tx } => {
+                            if this.era_history.is_none() {
+                                let _ = response_tx.send(Ok(None));
+                                continue
+                            }
+
+                            let provider = this.provider.clone();
+                            let rate_limiter = this.rate_limiter.clone();
+                            this.action_task_spawner.spawn_blocking_task(async move {
+                                // Acquire permit
+                                let _permit = rate_limiter.acquire().await;
+                                let _ = response_tx
+                                    .send(era::expired_transaction_block(&provider, tx_hash));
+                            });
+                        }
                     };
                     this.update_cached_metrics();
                 }
@@ -704,6 +807,11 @@ enum CacheAction<B: Block, R> {
         tx_hash: TxHash,
         response_tx: TransactionHashResponseSender<B, R>,
     },
+    /// Look up the expired block that contains a transaction
+    GetExpiredTransactionBlock {
+        tx_hash: TxHash,
+        response_tx: ExpiredTransactionBlockResponseSender,
+    },
 }
 
 struct BlockReceipts<R> {
@@ -805,6 +913,51 @@ impl<R: Send + Sync, B: Block> Drop for ActionSender<B, R> {
     }
 }
 
+/// Reads the block with the given hash from the [`EraHistory`], if it expired from the database.
+///
+/// A `rate_limiter` permit is only held while looking up the block number, not while the ERA1 file
+/// is downloaded or read.
+///
+/// Returns `None` if the block has not expired or no ERA1 file contains it.
+async fn expired_block<Provider: BlockReader>(
+    era_history: &EraHistory,
+    provider: &Provider,
+    rate_limiter: &Semaphore,
+    block_hash: B256,
+) -> ProviderResult<Option<RecoveredBlock<Provider::Block>>> {
+    let Some(number) = expired_block_number(provider, rate_limiter, block_hash).await? else {
+        return Ok(None)
+    };
+    Ok(era_history.recovered_block(number, block_hash).await?)
+}
+
+/// Reads the receipts of the block with the given hash from the [`EraHistory`], if the block
+/// expired from the database.
+///
+/// Returns `None` if the block has not expired or no ERA1 file contains it.
+async fn expired_receipts<Provider: BlockReader>(
+    era_history: &EraHistory,
+    provider: &Provider,
+    rate_limiter: &Semaphore,
+    block_hash: B256,
+) -> ProviderResult<Option<Vec<Provider::Receipt>>> {
+    let Some(number) = expired_block_number(provider, rate_limiter, block_hash).await? else {
+        return Ok(None)
+    };
+    Ok(era_history.receipts(number, block_hash).await?)
+}
+
+/// Returns the number of the block with the given hash while holding a `rate_limiter` permit, if
+/// the block expired from the database.
+async fn expired_block_number<Provider: BlockReader>(
+    provider: &Provider,
+    rate_limiter: &Semaphore,
+    block_hash: B256,
+) -> ProviderResult<Option<BlockNumber>> {
+    let _permit = rate_limiter.acquire().await;
+    era::expired_block_number(provider, block_hash)
+}
+
 /// Awaits for new chain events and directly inserts them into the cache so they're available
 /// immediately before they need to be fetched from disk.
 ///
@@ -850,6 +1003,7 @@ mod tests {
             4,
             1,
             16,
+            None,
         );
         service
     }
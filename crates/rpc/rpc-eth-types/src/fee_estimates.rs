@@ -0,0 +1,272 @@
+//! Fee estimates for the `reth_feeEstimates` endpoint.
+//!
+//! The estimates combine the rewards and blob base fees of recent blocks, as reported by
+//! `eth_feeHistory`, with the fees offered by the transactions in the pending pool.
+
+use alloy_rpc_types_eth::FeeHistory;
+use reth_rpc_server_types::constants::gas_oracle::{
+    DEFAULT_FAST_FEE_PERCENTILE, DEFAULT_SLOW_FEE_PERCENTILE, DEFAULT_STANDARD_FEE_PERCENTILE,
+};
+use serde::{Deserialize, Serialize};
+
+/// The percentiles used for the slow, standard and fast [`FeeEstimates`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FeeEstimatePercentiles {
+    /// Percentile of the slow estimate.
+    pub slow: u32,
+    /// Percentile of the standard estimate.
+    pub standard: u32,
+    /// Percentile of the fast estimate.
+    pub fast: u32,
+}
+
+impl FeeEstimatePercentiles {
+    /// Returns the percentiles capped at 100 and in ascending order.
+    pub fn sanitized(self) -> Self {
+        let mut percentiles = [self.slow, self.standard, self.fast].map(|p| p.min(100));
+        percentiles.sort_unstable();
+        let [slow, standard, fast] = percentiles;
+        Self { slow, standard, fast }
+    }
+
+    /// Returns the percentiles as reward percentiles for `eth_feeHistory`.
+    pub fn reward_percentiles(&self) -> Vec<f64> {
+        vec![self.slow as f64, self.standard as f64, self.fast as f64]
+    }
+}
+
+impl Default for FeeEstimatePercentiles {
+    fn default() -> Self {
+        Self {
+            slow: DEFAULT_SLOW_FEE_PERCENTILE,
+            standard: DEFAULT_STANDARD_FEE_PERCENTILE,
+            fast: DEFAULT_FAST_FEE_PERCENTILE,
+        }
+    }
+}
+
+/// A slow, standard and fast fee estimate.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeEstimateLevels {
+    /// Fee that is expected to be included eventually.
+    #[serde(with = "alloy_serde::quantity")]
+    pub slow: u128,
+    /// Fee that is expected to be included within a few blocks.
+    #[serde(with = "alloy_serde::quantity")]
+    pub standard: u128,
+    /// Fee that is expected to be included in the next block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub fast: u128,
+}
+
+/// Response of `reth_feeEstimates`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeEstimates {
+    /// The latest block the estimates are based on.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: u64,
+    /// The base fee of the next block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub base_fee_per_gas: u128,
+    /// The blob base fee of the next block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub base_fee_per_blob_gas: u128,
+    /// Estimates for the priority fee per gas.
+    pub max_priority_fee_per_gas: FeeEstimateLevels,
+    /// Estimates for the max fee per blob gas.
+    pub max_fee_per_blob_gas: FeeEstimateLevels,
+}
+
+/// Fees offered by the transactions in the pending pool.
+#[derive(Debug, Clone, Default)]
+pub struct PendingFees {
+    /// Effective tip at the next base fee and gas limit of every pending transaction.
+    pub tips: Vec<(u128, u64)>,
+    /// Max fee per blob gas and blob count of every pending blob transaction.
+    pub blob_fees: Vec<(u128, u64)>,
+    /// Gas limit of the next block.
+    pub block_gas_limit: u64,
+    /// Maximum number of blobs in the next block.
+    pub max_blob_count: u64,
+}
+
+impl PendingFees {
+    /// Returns `true` if the pending transactions don't fit into the next block.
+    pub fn is_gas_congested(&self) -> bool {
+        self.tips.iter().map(|(_, gas)| *gas).sum::<u64>() > self.block_gas_limit
+    }
+
+    /// Returns `true` if the pending blobs don't fit into the next block.
+    pub fn is_blob_congested(&self) -> bool {
+        self.blob_fees.iter().map(|(_, count)| *count).sum::<u64>() > self.max_blob_count
+    }
+}
+
+/// Computes the [`FeeEstimates`] from the fee history of recent blocks and the pending pool.
+///
+/// The `history` must contain the rewards at the given percentiles. Each estimate is the
+/// percentile of the recent blocks. If the pending transactions don't fit into the next block, the
+/// estimate is raised to the gas weighted percentile of the pending fees, so that it keeps up with
+/// the current pool pressure. Blob fee estimates are never lower than the next blob base fee.
+///
+/// Priority fee estimates are capped at `max_price`, if set.
+pub fn estimate_fees(
+    history: &FeeHistory,
+    percentiles: FeeEstimatePercentiles,
+    mut pending: PendingFees,
+    max_price: Option<u128>,
+) -> FeeEstimates {
+    let block_number =
+        (history.oldest_block + history.gas_used_ratio.len() as u64).saturating_sub(1);
+    let base_fee_per_gas = history.base_fee_per_gas.last().copied().unwrap_or_default();
+    let base_fee_per_blob_gas = history.base_fee_per_blob_gas.last().copied().unwrap_or_default();
+
+    // only blocks that contain transactions report meaningful rewards
+    let rewards = history
+        .reward
+        .iter()
+        .flatten()
+        .zip(&history.gas_used_ratio)
+        .filter(|(_, ratio)| **ratio > 0.0)
+        .map(|(rewards, _)| rewards)
+        .collect::<Vec<_>>();
+    let mut blob_fees = history
+        .base_fee_per_blob_gas
+        .split_last()
+        .map(|(_, fees)| fees.to_vec())
+        .unwrap_or_default();
+    blob_fees.sort_unstable();
+
+    let gas_congested = pending.is_gas_congested();
+    let blob_congested = pending.is_blob_congested();
+    pending.tips.sort_unstable();
+    pending.blob_fees.sort_unstable();
+
+    let levels = [percentiles.slow, percentiles.standard, percentiles.fast];
+    let [slow, standard, fast] = [0, 1, 2].map(|idx| {
+        let percentile = levels[idx];
+        let mut tip = average(rewards.iter().filter_map(|rewards| rewards.get(idx).copied()));
+        if gas_congested {
+            tip = tip.max(weighted_percentile(&pending.tips, percentile).unwrap_or_default());
+        }
+        if let Some(max_price) = max_price {
+            tip = tip.min(max_price);
+        }
+
+        let mut blob_fee =
+            base_fee_per_blob_gas.max(percentile_of(&blob_fees, percentile).unwrap_or_default());
+        if blob_congested {
+            blob_fee = blob_fee
+                .max(weighted_percentile(&pending.blob_fees, percentile).unwrap_or_default());
+        }
+
+        (tip, blob_fee)
+    });
+
+    FeeEstimates {
+        block_number,
+        base_fee_per_gas,
+        base_fee_per_blob_gas,
+        max_priority_fee_per_gas: FeeEstimateLevels {
+            slow: slow.0,
+            standard: standard.0,
+            fast: fast.0,
+        },
+        max_fee_per_blob_gas: FeeEstimateLevels {
+            slow: slow.1,
+            standard: standard.1,
+            fast: fast.1,
+        },
+    }
+}
+
+/// Returns the average of the values, or zero if there are none.
+fn average(values: impl Iterator<Item = u128>) -> u128 {
+    let (sum, count) =
+        values.fold((0u128, 0u128), |(sum, count), value| (sum.saturating_add(value), count + 1));
+    sum.checked_div(count).unwrap_or_default()
+}
+
+/// Returns the value at the percentile of the sorted values.
+fn percentile_of(sorted: &[u128], percentile: u32) -> Option<u128> {
+    if sorted.is_empty() {
+        return None
+    }
+    sorted.get((sorted.len() - 1) * percentile as usize / 100).copied()
+}
+
+/// Returns the value at the percentile of the sorted values, weighted by the second element.
+fn weighted_percentile(sorted: &[(u128, u64)], percentile: u32) -> Option<u128> {
+    let total = sorted.iter().map(|(_, weight)| *weight as u128).sum::<u128>();
+    let threshold = total * percentile as u128 / 100;
+    let mut cumulative = 0u128;
+    for (value, weight) in sorted {
+        cumulative += *weight as u128;
+        if cumulative >= threshold {
+            return Some(*value)
+        }
+    }
+    sorted.last().map(|(value, _)| *value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history() -> FeeHistory {
+        FeeHistory {
+            base_fee_per_gas: vec![10, 10, 10, 11],
+            gas_used_ratio: vec![0.5, 0.0, 0.5],
+            base_fee_per_blob_gas: vec![1, 3, 2, 2],
+            blob_gas_used_ratio: vec![0.0, 0.0, 0.0],
+            oldest_block: 10,
+            reward: Some(vec![vec![1, 2, 4], vec![0, 0, 0], vec![3, 4, 6]]),
+        }
+    }
+
+    #[test]
+    fn estimates_from_history() {
+        let pending = PendingFees {
+            tips: vec![(100, 21_000)],
+            block_gas_limit: 30_000_000,
+            max_blob_count: 6,
+            ..Default::default()
+        };
+        let estimates =
+            estimate_fees(&history(), FeeEstimatePercentiles::default(), pending, Some(5));
+
+        assert_eq!(estimates.block_number, 12);
+        assert_eq!(estimates.base_fee_per_gas, 11);
+        assert_eq!(estimates.base_fee_per_blob_gas, 2);
+        // the empty block is ignored and the fast estimate is capped
+        assert_eq!(
+            estimates.max_priority_fee_per_gas,
+            FeeEstimateLevels { slow: 2, standard: 3, fast: 5 }
+        );
+        assert_eq!(
+            estimates.max_fee_per_blob_gas,
+            FeeEstimateLevels { slow: 2, standard: 2, fast: 2 }
+        );
+    }
+
+    #[test]
+    fn estimates_follow_congested_pool() {
+        let pending = PendingFees {
+            tips: vec![(10, 10), (20, 10), (30, 10), (40, 10)],
+            blob_fees: vec![(7, 2), (9, 2)],
+            block_gas_limit: 20,
+            max_blob_count: 3,
+        };
+        let estimates = estimate_fees(&history(), FeeEstimatePercentiles::default(), pending, None);
+
+        assert_eq!(
+            estimates.max_priority_fee_per_gas,
+            FeeEstimateLevels { slow: 10, standard: 20, fast: 40 }
+        );
+        assert_eq!(
+            estimates.max_fee_per_blob_gas,
+            FeeEstimateLevels { slow: 7, standard: 7, fast: 9 }
+        );
+    }
+}
@@ -1,7 +1,10 @@
 //! An implementation of the eth gas price oracle, used for providing gas price estimates based on
 //! previous blocks.
 
-use super::{EthApiError, EthResult, EthStateCache, RpcInvalidTransactionError};
+use super::{
+    EthApiError, EthResult, EthStateCache, FeeEstimatePercentiles, FeeEstimates,
+    RpcInvalidTransactionError,
+};
 use alloy_consensus::{constants::GWEI_TO_WEI, BlockHeader, Transaction, TxReceipt};
 use alloy_eips::BlockNumberOrTag;
 use alloy_primitives::{B256, U256};
@@ -56,6 +59,10 @@ pub struct GasPriceOracleConfig {
 
     /// The minimum gas price, under which the sample will be ignored
     pub ignore_price: Option<U256>,
+
+    /// The percentiles of the slow, standard and fast fee estimates
+    #[serde(default)]
+    pub fee_estimate_percentiles: FeeEstimatePercentiles,
 }
 
 impl Default for GasPriceOracleConfig {
@@ -69,6 +76,7 @@ impl Default for GasPriceOracleConfig {
             default_suggested_fee: None,
             max_price: Some(DEFAULT_MAX_GAS_PRICE),
             ignore_price: Some(DEFAULT_IGNORE_GAS_PRICE),
+            fee_estimate_percentiles: FeeEstimatePercentiles::default(),
         }
     }
 }
@@ -107,6 +115,7 @@ where
             warn!(prev_percentile = ?oracle_config.percentile, "Invalid configured gas price percentile, assuming 100.");
             oracle_config.percentile = 100;
         }
+        oracle_config.fee_estimate_percentiles = oracle_config.fee_estimate_percentiles.sanitized();
         let ignore_price = oracle_config.ignore_price.map(|price| price.saturating_to());
 
         // this is the number of blocks that we will cache the values for
@@ -121,6 +130,7 @@ where
             lowest_effective_tip_cache: EffectiveTipLruCache(LruMap::new(ByLength::new(
                 cached_values,
             ))),
+            last_fee_estimates: None,
         });
 
         Self { provider, oracle_config, cache, ignore_price, inner }
@@ -131,6 +141,21 @@ where
         &self.oracle_config
     }
 
+    /// Returns the last fee estimates if they were computed for the given block.
+    pub async fn cached_fee_estimates(&self, block_hash: B256) -> Option<FeeEstimates> {
+        let inner = self.inner.lock().await;
+        inner
+            .last_fee_estimates
+            .as_ref()
+            .filter(|(hash, _)| *hash == block_hash)
+            .map(|(_, estimates)| estimates.clone())
+    }
+
+    /// Stores the fee estimates computed for the given block.
+    pub async fn cache_fee_estimates(&self, block_hash: B256, estimates: FeeEstimates) {
+        self.inner.lock().await.last_fee_estimates = Some((block_hash, estimates));
+    }
+
     /// Suggests a gas price estimate based on recent blocks, using the configured percentile.
     pub async fn suggest_tip_cap(&self) -> EthResult<U256> {
         let header = self
@@ -388,6 +413,8 @@ where
 struct GasPriceOracleInner {
     last_price: GasPriceOracleResult,
     lowest_effective_tip_cache: EffectiveTipLruCache,
+    /// The last fee estimates and the hash of the block they were computed for
+    last_fee_estimates: Option<(B256, FeeEstimates)>,
 }
 
 /// Wrapper struct for `LruMap`
@@ -15,6 +15,7 @@ pub mod block;
 pub mod builder;
 pub mod cache;
 pub mod error;
+pub mod fee_estimates;
 pub mod fee_history;
 pub mod gas_oracle;
 pub mod id_provider;
@@ -30,10 +31,14 @@ pub use alloy_rpc_types_eth::FillTransaction;
 pub use block::CachedTransaction;
 pub use builder::config::{EthConfig, EthFilterConfig};
 pub use cache::{
-    config::EthStateCacheConfig, db::StateCacheDb, multi_consumer::MultiConsumerLruCache,
+    config::{EraHistoryConfig, EthStateCacheConfig},
+    db::StateCacheDb,
+    era::EraHistory,
+    multi_consumer::MultiConsumerLruCache,
     EthStateCache,
 };
 pub use error::{EthApiError, EthResult, RevertError, RpcInvalidTransactionError, SignError};
+pub use fee_estimates::{FeeEstimateLevels, FeeEstimatePercentiles, FeeEstimates};
 pub use fee_history::{FeeHistoryCache, FeeHistoryCacheConfig, FeeHistoryEntry};
 pub use gas_oracle::{
     GasCap, GasPriceOracle, GasPriceOracleConfig, GasPriceOracleResult, RPC_DEFAULT_GAS_CAP,
@@ -90,6 +90,18 @@ pub mod gas_oracle {
     /// The percentile of gas prices to use for the estimate
     pub const DEFAULT_GAS_PRICE_PERCENTILE: u32 = 60;
 
+    /// The percentile of fees to use for the slow fee estimate
+    pub const DEFAULT_SLOW_FEE_PERCENTILE: u32 = 25;
+
+    /// The percentile of fees to use for the standard fee estimate
+    pub const DEFAULT_STANDARD_FEE_PERCENTILE: u32 = 50;
+
+    /// The percentile of fees to use for the fast fee estimate
+    pub const DEFAULT_FAST_FEE_PERCENTILE: u32 = 90;
+
+    /// The maximum number of best pending transactions sampled for the fee estimates
+    pub const MAX_FEE_ESTIMATE_PENDING_TRANSACTIONS: usize = 4096;
+
     /// Maximum transaction priority fee (or gas price before London Fork) to be recommended by the
     /// gas price oracle
     pub const DEFAULT_MAX_GAS_PRICE: U256 = U256::from_limbs([500_000_000_000u64, 0, 0, 0]);
@@ -128,4 +140,10 @@ pub mod cache {
 
     /// Default maximum number of transaction hashes to cache for lookups.
     pub const DEFAULT_MAX_CACHED_TX_HASHES: u32 = 30_000;
+
+    /// Default number of ERA1 files expired blocks are served from at the same time.
+    pub const DEFAULT_MAX_CACHED_ERA_FILES: u32 = 4;
+
+    /// Default max number of ERA1 files downloaded per hour for expired blocks.
+    pub const DEFAULT_MAX_ERA_DOWNLOADS_PER_HOUR: u32 = 16;
 }
@@ -325,6 +325,10 @@ pub enum RethRpcModule {
     Mev,
     /// `testing_` module
     Testing,
+    /// `anvil_` module
+    Anvil,
+    /// `hardhat_` module
+    Hardhat,
     /// Custom RPC module not part of the standard set
     #[strum(default)]
     #[serde(untagged)]
@@ -350,6 +354,8 @@ impl RethRpcModule {
         Self::Miner,
         Self::Mev,
         Self::Testing,
+        Self::Anvil,
+        Self::Hardhat,
     ];
 
     /// Returns the number of standard variants (excludes Other)
@@ -410,6 +416,8 @@ impl AsRef<str> for RethRpcModule {
             Self::Miner => "miner",
             Self::Mev => "mev",
             Self::Testing => "testing",
+            Self::Anvil => "anvil",
+            Self::Hardhat => "hardhat",
         }
     }
 }
@@ -433,6 +441,8 @@ impl FromStr for RethRpcModule {
             "miner" => Self::Miner,
             "mev" => Self::Mev,
             "testing" => Self::Testing,
+            "anvil" => Self::Anvil,
+            "hardhat" => Self::Hardhat,
             // Any unknown module becomes Other
             other => Self::Other(other.to_string()),
         })
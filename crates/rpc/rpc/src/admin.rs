@@ -1,6 +1,8 @@
 use std::sync::Arc;
 
+use alloy_eips::BlockNumHash;
 use alloy_genesis::ChainConfig;
+use alloy_primitives::U64;
 use alloy_rpc_types_admin::{
     EthInfo, EthPeerInfo, EthProtocolInfo, NodeInfo, PeerInfo, PeerNetworkInfo, PeerProtocolInfo,
     Ports, ProtocolInfo,
@@ -8,11 +10,16 @@ use alloy_rpc_types_admin::{
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use reth_chainspec::{EthChainSpec, EthereumHardfork, EthereumHardforks, ForkCondition};
+use reth_engine_primitives::{BeaconPersistenceError, PersistencePauseHandle};
 use reth_network_api::{NetworkInfo, Peers};
 use reth_network_peers::{AnyNode, NodeRecord};
 use reth_network_types::PeerKind;
-use reth_rpc_api::AdminApiServer;
-use reth_rpc_server_types::ToRpcResult;
+use reth_node_core::config_reload::ConfigReloadHandle;
+use reth_rpc_api::{AdminApiServer, ReloadConfigResult};
+use reth_rpc_server_types::{
+    result::{internal_rpc_err, invalid_params_rpc_err},
+    ToRpcResult,
+};
 use reth_transaction_pool::TransactionPool;
 use revm_primitives::keccak256;
 
@@ -26,12 +33,38 @@ pub struct AdminApi<N, ChainSpec, Pool> {
     chain_spec: Arc<ChainSpec>,
     /// The transaction pool
     pool: Pool,
+    /// Handle to reload the config file of the node, used by `admin_reloadConfig`
+    config_reload: Option<ConfigReloadHandle>,
+    /// Handle to pause the persistence of the engine, used by `admin_pausePersistence`
+    persistence: Option<PersistencePauseHandle>,
 }
 
 impl<N, ChainSpec, Pool> AdminApi<N, ChainSpec, Pool> {
     /// Creates a new instance of `AdminApi`.
     pub const fn new(network: N, chain_spec: Arc<ChainSpec>, pool: Pool) -> Self {
-        Self { network, chain_spec, pool }
+        Self { network, chain_spec, pool, config_reload: None, persistence: None }
+    }
+
+    /// Configures the handle used by `admin_reloadConfig` to reload the config file.
+    pub fn with_config_reload_handle(mut self, config_reload: Option<ConfigReloadHandle>) -> Self {
+        self.config_reload = config_reload;
+        self
+    }
+
+    /// Configures the handle used by `admin_pausePersistence` and `admin_resumePersistence`.
+    pub fn with_persistence_pause_handle(
+        mut self,
+        persistence: Option<PersistencePauseHandle>,
+    ) -> Self {
+        self.persistence = persistence;
+        self
+    }
+
+    /// Returns the persistence handle, or an error if there is no consensus engine.
+    fn persistence(&self) -> RpcResult<&PersistencePauseHandle> {
+        self.persistence.as_ref().ok_or_else(|| {
+            internal_rpc_err("persistence can't be paused without a consensus engine")
+        })
     }
 }
 
@@ -191,6 +224,40 @@ where
         let _ = self.pool.remove_transactions(all_hashes);
         Ok(count)
     }
+
+    /// Handler for `admin_reloadConfig`
+    async fn reload_config(&self) -> RpcResult<ReloadConfigResult> {
+        let Some(config_reload) = &self.config_reload else {
+            return Err(internal_rpc_err("reloadConfig is not supported without a config file"))
+        };
+
+        let changes =
+            config_reload.reload().await.map_err(|err| internal_rpc_err(err.to_string()))?;
+        Ok(ReloadConfigResult {
+            applied: changes.applied,
+            requires_restart: changes.requires_restart,
+        })
+    }
+
+    /// Handler for `admin_pausePersistence`
+    async fn pause_persistence(&self, block_number: Option<U64>) -> RpcResult<BlockNumHash> {
+        self.persistence()?.pause_persistence(block_number.map(|number| number.to())).await.map_err(
+            |err| match err {
+                BeaconPersistenceError::InvalidTarget { .. } => {
+                    invalid_params_rpc_err(err.to_string())
+                }
+                err => internal_rpc_err(err.to_string()),
+            },
+        )
+    }
+
+    /// Handler for `admin_resumePersistence`
+    async fn resume_persistence(&self) -> RpcResult<BlockNumHash> {
+        self.persistence()?
+            .resume_persistence()
+            .await
+            .map_err(|err| internal_rpc_err(err.to_string()))
+    }
 }
 
 impl<N, ChainSpec, Pool> std::fmt::Debug for AdminApi<N, ChainSpec, Pool> {
@@ -0,0 +1,756 @@
+//! Implementation of the `anvil` and `hardhat` namespaces on top of a dev chain.
+//!
+//! Blocks are mined and the chain is modified through the [`LocalMinerHandle`] of the node's local
+//! miner. Account changes like `anvil_setBalance` take effect right away: the miner mines a block
+//! that applies them before its transactions. Snapshots are reverted by unwinding the engine tree
+//! to the recorded head.
+//!
+//! Impersonated accounts are served by an [`EthSigner`] that attaches a placeholder signature to
+//! their transactions. `eth_sendTransaction` submits them to the pool with the impersonated sender,
+//! and the locally built blocks that include them are inserted into the engine tree without
+//! recovering their senders again.
+//!
+//! Forking is not supported, the dev chain always starts from its genesis.
+
+use alloy_consensus::SignableTransaction;
+use alloy_dyn_abi::TypedData;
+use alloy_eips::BlockId;
+use alloy_network::TxSigner;
+use alloy_primitives::{
+    map::{AddressMap, AddressSet, B256Map},
+    Address, Bytes, Signature, B256, U256,
+};
+use alloy_rpc_types_anvil::{
+    Forking, Metadata, MineOptions, NodeEnvironment, NodeForkConfig, NodeInfo,
+};
+use async_trait::async_trait;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use jsonrpsee::core::RpcResult;
+use jsonrpsee_types::ErrorObject;
+use parking_lot::RwLock;
+use reth_chainspec::{ChainSpecProvider, EthChainSpec, Hardforks};
+use reth_engine_local::{LocalMinerError, LocalMinerHandle};
+use reth_evm::state_overrides::AccountStateOverride;
+use reth_network_api::NetworkInfo;
+use reth_primitives_traits::AlloyBlockHeader as _;
+use reth_rpc_api::{AnvilApiServer, HardhatApiServer};
+use reth_rpc_convert::{RpcTxReq, SignableTxRequest};
+use reth_rpc_eth_api::{
+    helpers::{signer, EthBlocks, EthSigner, EthTransactions, LoadState, SpawnBlocking},
+    FullEthApiTypes, RpcBlock,
+};
+use reth_rpc_eth_types::{EthApiError, SignError};
+use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
+use reth_storage_api::{
+    BlockHashReader, BlockNumReader, BlockReaderIdExt, ChangeSetReader, ProviderTx,
+    StateProviderFactory, StorageChangeSetReader,
+};
+use reth_transaction_pool::TransactionPool;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{Read, Write},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+/// Error returned by methods that require forking another chain.
+const FORKING_UNSUPPORTED: &str = "forking is not supported";
+
+/// `anvil` and `hardhat` API implementation.
+#[derive(Clone)]
+pub struct AnvilApi<Eth> {
+    inner: Arc<AnvilApiInner<Eth>>,
+}
+
+impl<Eth> AnvilApi<Eth>
+where
+    Eth: EthTransactions,
+    RpcTxReq<Eth::NetworkTypes>: SignableTxRequest<ProviderTx<Eth::Provider>>,
+{
+    /// Creates a new instance of `AnvilApi` that controls the dev chain through the given miner
+    /// handle.
+    ///
+    /// This registers the signer of impersonated accounts with the `eth` API.
+    pub fn new(eth_api: Eth, miner: LocalMinerHandle) -> Self {
+        let impersonation = ImpersonationSigner::default();
+        eth_api.signers().write().push(Box::new(impersonation.clone()));
+        Self {
+            inner: Arc::new(AnvilApiInner {
+                eth_api,
+                miner,
+                impersonation,
+                instance_id: B256::random(),
+            }),
+        }
+    }
+}
+
+impl<Eth> AnvilApi<Eth>
+where
+    Eth: FullEthApiTypes + EthBlocks + LoadState + SpawnBlocking + 'static,
+    Eth::Provider: ChangeSetReader + StorageChangeSetReader,
+{
+    /// Applies the given account change on top of the latest block.
+    async fn modify_account(
+        &self,
+        address: Address,
+        account: AccountStateOverride,
+    ) -> RpcResult<()> {
+        self.inner
+            .miner
+            .modify_accounts(AddressMap::from_iter([(address, account)]))
+            .await
+            .map_err(miner_err)?;
+        Ok(())
+    }
+
+    /// Mines `blocks` blocks, `interval` seconds apart.
+    async fn mine(&self, blocks: Option<U256>, interval: Option<U256>) -> RpcResult<Vec<B256>> {
+        let blocks = blocks.map(|blocks| blocks.saturating_to()).unwrap_or(1);
+        let interval = interval.map(|interval| interval.saturating_to());
+        self.inner.miner.mine(blocks, interval).await.map_err(miner_err)
+    }
+
+    /// Returns metadata about the dev chain.
+    async fn metadata(&self) -> RpcResult<Metadata> {
+        let provider = self.inner.eth_api.provider();
+        let latest = provider.best_block_number().map_err(EthApiError::from)?;
+        let latest_hash = provider
+            .block_hash(latest)
+            .map_err(EthApiError::from)?
+            .ok_or(EthApiError::HeaderNotFound(latest.into()))?;
+        let client_version = self
+            .inner
+            .eth_api
+            .network()
+            .network_status()
+            .await
+            .map(|status| status.client_version)
+            .unwrap_or_default();
+        let snapshots = self
+            .inner
+            .miner
+            .snapshots()
+            .await
+            .map_err(miner_err)?
+            .into_iter()
+            .map(|(id, block)| (id, (block.number, block.hash)))
+            .collect();
+
+        Ok(Metadata {
+            client_version,
+            client_semver: Some(env!("CARGO_PKG_VERSION").to_string()),
+            client_commit_sha: None,
+            chain_id: provider.chain_spec().chain_id(),
+            instance_id: self.inner.instance_id,
+            latest_block_number: latest,
+            latest_block_hash: latest_hash,
+            forked_network: None,
+            snapshots,
+        })
+    }
+
+    /// Returns the configuration of the dev chain.
+    async fn node_info(&self) -> RpcResult<NodeInfo> {
+        let provider = self.inner.eth_api.provider();
+        let header = provider
+            .latest_header()
+            .map_err(EthApiError::from)?
+            .ok_or(EthApiError::HeaderNotFound(BlockId::latest()))?;
+        let chain_spec = provider.chain_spec();
+
+        // the latest hardfork that is active at the head of the chain
+        let hard_fork = chain_spec
+            .forks_iter()
+            .filter(|(_, condition)| {
+                condition.active_at_timestamp_or_number(header.timestamp(), header.number())
+            })
+            .last()
+            .map(|(fork, _)| fork.name().to_lowercase())
+            .unwrap_or_default();
+        let base_fee = header.base_fee_per_gas().unwrap_or_default() as u128;
+
+        Ok(NodeInfo {
+            current_block_number: header.number(),
+            current_block_timestamp: header.timestamp(),
+            current_block_hash: header.hash(),
+            hard_fork,
+            transaction_order: "fees".to_string(),
+            environment: NodeEnvironment {
+                base_fee,
+                chain_id: chain_spec.chain_id(),
+                gas_limit: header.gas_limit(),
+                gas_price: base_fee,
+            },
+            fork_config: NodeForkConfig::default(),
+            network: None,
+        })
+    }
+
+    /// Collects the state of all accounts that were touched since genesis.
+    async fn dump_state(&self) -> RpcResult<SerializableState> {
+        self.inner
+            .eth_api
+            .spawn_blocking_io(|eth_api| {
+                let provider = eth_api.provider();
+                let tip = provider.best_block_number().map_err(EthApiError::from)?;
+
+                // every account is either part of the genesis alloc or has changed since
+                let mut slots = BTreeMap::<Address, BTreeSet<B256>>::new();
+                for (address, account) in &provider.chain_spec().genesis().alloc {
+                    slots
+                        .entry(*address)
+                        .or_default()
+                        .extend(account.storage.iter().flatten().map(|(slot, _)| *slot));
+                }
+                for (_, changeset) in
+                    provider.account_changesets_range(1..=tip).map_err(EthApiError::from)?
+                {
+                    slots.entry(changeset.address).or_default();
+                }
+                for (block_address, entry) in
+                    provider.storage_changesets_range(1..=tip).map_err(EthApiError::from)?
+                {
+                    slots.entry(block_address.address()).or_default().insert(entry.key);
+                }
+
+                let state = provider.latest().map_err(EthApiError::from)?;
+                let mut accounts = BTreeMap::new();
+                for (address, slots) in slots {
+                    let Some(account) = state.basic_account(&address).map_err(EthApiError::from)?
+                    else {
+                        continue
+                    };
+                    let code = match account.bytecode_hash {
+                        Some(hash) => state
+                            .bytecode_by_hash(&hash)
+                            .map_err(EthApiError::from)?
+                            .map(|code| code.original_bytes())
+                            .unwrap_or_default(),
+                        None => Bytes::new(),
+                    };
+                    let mut storage = BTreeMap::new();
+                    for slot in slots {
+                        let value = state
+                            .storage(address, slot)
+                            .map_err(EthApiError::from)?
+                            .unwrap_or_default();
+                        if !value.is_zero() {
+                            storage.insert(U256::from_be_bytes(slot.0), value);
+                        }
+                    }
+                    accounts.insert(
+                        address,
+                        SerializableAccountRecord {
+                            nonce: account.nonce,
+                            balance: account.balance,
+                            code,
+                            storage,
+                        },
+                    );
+                }
+
+                Ok(SerializableState { best_block_number: Some(tip), accounts })
+            })
+            .await
+            .map_err(Into::into)
+    }
+}
+
+#[async_trait]
+impl<Eth> AnvilApiServer<RpcBlock<Eth::NetworkTypes>> for AnvilApi<Eth>
+where
+    Eth: FullEthApiTypes + EthBlocks + LoadState + SpawnBlocking + 'static,
+    Eth::Provider: ChangeSetReader + StorageChangeSetReader,
+{
+    /// Handler for `anvil_impersonateAccount`
+    async fn anvil_impersonate_account(&self, address: Address) -> RpcResult<()> {
+        self.inner.impersonation.accounts.write().insert(address);
+        Ok(())
+    }
+
+    /// Handler for `anvil_stopImpersonatingAccount`
+    async fn anvil_stop_impersonating_account(&self, address: Address) -> RpcResult<()> {
+        self.inner.impersonation.accounts.write().remove(&address);
+        Ok(())
+    }
+
+    /// Handler for `anvil_autoImpersonateAccount`
+    async fn anvil_auto_impersonate_account(&self, enabled: bool) -> RpcResult<()> {
+        self.inner.impersonation.auto_impersonate.store(enabled, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Handler for `anvil_getAutomine`
+    async fn anvil_get_automine(&self) -> RpcResult<bool> {
+        self.inner.miner.automine().await.map_err(miner_err)
+    }
+
+    /// Handler for `anvil_mine`
+    async fn anvil_mine(&self, blocks: Option<U256>, interval: Option<U256>) -> RpcResult<()> {
+        self.mine(blocks, interval).await?;
+        Ok(())
+    }
+
+    /// Handler for `anvil_setAutomine`
+    async fn anvil_set_automine(&self, enabled: bool) -> RpcResult<()> {
+        self.inner.miner.set_automine(enabled).await.map_err(miner_err)
+    }
+
+    /// Handler for `anvil_setIntervalMining`
+    async fn anvil_set_interval_mining(&self, interval: u64) -> RpcResult<()> {
+        let interval = (interval > 0).then(|| Duration::from_secs(interval));
+        self.inner.miner.set_interval_mining(interval).await.map_err(miner_err)
+    }
+
+    /// Handler for `anvil_dropTransaction`
+    async fn anvil_drop_transaction(&self, tx_hash: B256) -> RpcResult<Option<B256>> {
+        let removed = self.inner.eth_api.pool().remove_transactions(vec![tx_hash]);
+        Ok((!removed.is_empty()).then_some(tx_hash))
+    }
+
+    /// Handler for `anvil_reset`
+    async fn anvil_reset(&self, _fork: Option<Forking>) -> RpcResult<()> {
+        Err(EthApiError::Unsupported(FORKING_UNSUPPORTED).into())
+    }
+
+    /// Handler for `anvil_setRpcUrl`
+    async fn anvil_set_rpc_url(&self, _url: String) -> RpcResult<()> {
+        Err(EthApiError::Unsupported(FORKING_UNSUPPORTED).into())
+    }
+
+    /// Handler for `anvil_setBalance`
+    async fn anvil_set_balance(&self, address: Address, balance: U256) -> RpcResult<()> {
+        self.modify_account(
+            address,
+            AccountStateOverride { balance: Some(balance), ..Default::default() },
+        )
+        .await
+    }
+
+    /// Handler for `anvil_setCode`
+    async fn anvil_set_code(&self, address: Address, code: Bytes) -> RpcResult<()> {
+        self.modify_account(
+            address,
+            AccountStateOverride { code: Some(code), ..Default::default() },
+        )
+        .await
+    }
+
+    /// Handler for `anvil_setNonce`
+    async fn anvil_set_nonce(&self, address: Address, nonce: U256) -> RpcResult<()> {
+        let nonce = nonce.try_into().map_err(|_| invalid_params_rpc_err("nonce exceeds u64"))?;
+        self.modify_account(
+            address,
+            AccountStateOverride { nonce: Some(nonce), ..Default::default() },
+        )
+        .await
+    }
+
+    /// Handler for `anvil_setStorageAt`
+    async fn anvil_set_storage_at(
+        &self,
+        address: Address,
+        slot: U256,
+        value: B256,
+    ) -> RpcResult<bool> {
+        self.modify_account(
+            address,
+            AccountStateOverride {
+                storage: B256Map::from_iter([(B256::from(slot), value)]),
+                ..Default::default()
+            },
+        )
+        .await?;
+        Ok(true)
+    }
+
+    /// Handler for `anvil_setCoinbase`
+    async fn anvil_set_coinbase(&self, address: Address) -> RpcResult<()> {
+        self.inner.miner.set_coinbase(address).await.map_err(miner_err)
+    }
+
+    /// Handler for `anvil_setChainId`
+    async fn anvil_set_chain_id(&self, _chain_id: u64) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("the chain id is fixed by the chain spec").into())
+    }
+
+    /// Handler for `anvil_setLoggingEnabled`
+    async fn anvil_set_logging_enabled(&self, _enabled: bool) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("logging is configured with the node's log filters").into())
+    }
+
+    /// Handler for `anvil_setMinGasPrice`
+    async fn anvil_set_min_gas_price(&self, _gas_price: U256) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("the minimum gas price is set by the pool config").into())
+    }
+
+    /// Handler for `anvil_setNextBlockBaseFeePerGas`
+    async fn anvil_set_next_block_base_fee_per_gas(&self, _base_fee: U256) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("the base fee is derived from the parent block").into())
+    }
+
+    /// Handler for `anvil_setTime`
+    async fn anvil_set_time(&self, timestamp: u64) -> RpcResult<u64> {
+        let offset = self.inner.miner.set_time(timestamp).await.map_err(miner_err)?;
+        Ok(offset.unsigned_abs())
+    }
+
+    /// Handler for `anvil_dumpState`
+    async fn anvil_dump_state(&self) -> RpcResult<Bytes> {
+        let state = self.dump_state().await?;
+        let json = serde_json::to_vec(&state).map_err(|err| internal_rpc_err(err.to_string()))?;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&json).map_err(|err| internal_rpc_err(err.to_string()))?;
+        let compressed = encoder.finish().map_err(|err| internal_rpc_err(err.to_string()))?;
+
+        Ok(compressed.into())
+    }
+
+    /// Handler for `anvil_loadState`
+    async fn anvil_load_state(&self, state: Bytes) -> RpcResult<bool> {
+        let state = SerializableState::decode(&state)
+            .map_err(|err| invalid_params_rpc_err(format!("invalid state: {err}")))?;
+        if state.accounts.is_empty() {
+            return Ok(true)
+        }
+
+        let accounts = state
+            .accounts
+            .into_iter()
+            .map(|(address, account)| {
+                let account = AccountStateOverride {
+                    balance: Some(account.balance),
+                    nonce: Some(account.nonce),
+                    code: Some(account.code),
+                    storage: account
+                        .storage
+                        .into_iter()
+                        .map(|(slot, value)| (B256::from(slot), B256::from(value)))
+                        .collect(),
+                };
+                (address, account)
+            })
+            .collect();
+        self.inner.miner.modify_accounts(accounts).await.map_err(miner_err)?;
+
+        Ok(true)
+    }
+
+    /// Handler for `anvil_nodeInfo`
+    async fn anvil_node_info(&self) -> RpcResult<NodeInfo> {
+        self.node_info().await
+    }
+
+    /// Handler for `anvil_metadata`
+    async fn anvil_metadata(&self) -> RpcResult<Metadata> {
+        self.metadata().await
+    }
+
+    /// Handler for `anvil_snapshot`
+    async fn anvil_snapshot(&self) -> RpcResult<U256> {
+        self.inner.miner.snapshot().await.map_err(miner_err)
+    }
+
+    /// Handler for `anvil_revert`
+    async fn anvil_revert(&self, id: U256) -> RpcResult<bool> {
+        self.inner.miner.revert(id).await.map_err(miner_err)
+    }
+
+    /// Handler for `anvil_increaseTime`
+    async fn anvil_increase_time(&self, seconds: U256) -> RpcResult<i64> {
+        self.inner.miner.increase_time(seconds.saturating_to()).await.map_err(miner_err)
+    }
+
+    /// Handler for `anvil_setNextBlockTimestamp`
+    async fn anvil_set_next_block_timestamp(&self, seconds: u64) -> RpcResult<()> {
+        self.inner.miner.set_next_block_timestamp(seconds).await.map_err(miner_err)
+    }
+
+    /// Handler for `anvil_setBlockGasLimit`
+    async fn anvil_set_block_gas_limit(&self, _gas_limit: U256) -> RpcResult<bool> {
+        Err(EthApiError::Unsupported("the block gas limit is set by the payload builder config")
+            .into())
+    }
+
+    /// Handler for `anvil_setBlockTimestampInterval`
+    async fn anvil_set_block_timestamp_interval(&self, seconds: u64) -> RpcResult<()> {
+        self.inner.miner.set_block_timestamp_interval(Some(seconds)).await.map_err(miner_err)?;
+        Ok(())
+    }
+
+    /// Handler for `anvil_removeBlockTimestampInterval`
+    async fn anvil_remove_block_timestamp_interval(&self) -> RpcResult<bool> {
+        self.inner.miner.set_block_timestamp_interval(None).await.map_err(miner_err)
+    }
+
+    /// Handler for `anvil_mine_detailed`
+    async fn anvil_mine_detailed(
+        &self,
+        opts: Option<MineOptions>,
+    ) -> RpcResult<Vec<RpcBlock<Eth::NetworkTypes>>> {
+        let (timestamp, blocks) = match opts.unwrap_or_default() {
+            MineOptions::Options { timestamp, blocks } => (timestamp, blocks),
+            MineOptions::Timestamp(timestamp) => (timestamp, None),
+        };
+        if let Some(timestamp) = timestamp {
+            self.inner.miner.set_next_block_timestamp(timestamp).await.map_err(miner_err)?;
+        }
+
+        let hashes = self.mine(blocks.map(U256::from), None).await?;
+        let mut mined = Vec::with_capacity(hashes.len());
+        for hash in hashes {
+            let block = self
+                .inner
+                .eth_api
+                .rpc_block(hash.into(), true)
+                .await
+                .map_err(Into::into)?
+                .ok_or(EthApiError::HeaderNotFound(hash.into()))?;
+            mined.push(block);
+        }
+
+        Ok(mined)
+    }
+
+    /// Handler for `anvil_enableTraces`
+    async fn anvil_enable_traces(&self) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("use the debug and trace namespaces instead").into())
+    }
+
+    /// Handler for `anvil_removePoolTransactions`
+    async fn anvil_remove_pool_transactions(&self, address: Address) -> RpcResult<()> {
+        self.inner.eth_api.pool().remove_transactions_by_sender(address);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Eth> HardhatApiServer for AnvilApi<Eth>
+where
+    Eth: FullEthApiTypes + EthBlocks + LoadState + SpawnBlocking + 'static,
+    Eth::Provider: ChangeSetReader + StorageChangeSetReader,
+{
+    /// Handler for `hardhat_dropTransaction`
+    async fn hardhat_drop_transaction(&self, tx_hash: B256) -> RpcResult<bool> {
+        Ok(self.anvil_drop_transaction(tx_hash).await?.is_some())
+    }
+
+    /// Handler for `hardhat_impersonateAccount`
+    async fn hardhat_impersonate_account(&self, address: Address) -> RpcResult<()> {
+        self.anvil_impersonate_account(address).await
+    }
+
+    /// Handler for `hardhat_getAutomine`
+    async fn hardhat_get_automine(&self) -> RpcResult<bool> {
+        self.anvil_get_automine().await
+    }
+
+    /// Handler for `hardhat_metadata`
+    async fn hardhat_metadata(&self) -> RpcResult<Metadata> {
+        self.metadata().await
+    }
+
+    /// Handler for `hardhat_mine`
+    async fn hardhat_mine(&self, blocks: Option<U256>, interval: Option<U256>) -> RpcResult<()> {
+        self.anvil_mine(blocks, interval).await
+    }
+
+    /// Handler for `hardhat_reset`
+    async fn hardhat_reset(&self, fork: Option<Forking>) -> RpcResult<()> {
+        self.anvil_reset(fork).await
+    }
+
+    /// Handler for `hardhat_setBalance`
+    async fn hardhat_set_balance(&self, address: Address, balance: U256) -> RpcResult<()> {
+        self.anvil_set_balance(address, balance).await
+    }
+
+    /// Handler for `hardhat_setCode`
+    async fn hardhat_set_code(&self, address: Address, code: Bytes) -> RpcResult<()> {
+        self.anvil_set_code(address, code).await
+    }
+
+    /// Handler for `hardhat_setCoinbase`
+    async fn hardhat_set_coinbase(&self, address: Address) -> RpcResult<()> {
+        self.anvil_set_coinbase(address).await
+    }
+
+    /// Handler for `hardhat_setLoggingEnabled`
+    async fn hardhat_set_logging_enabled(&self, enabled: bool) -> RpcResult<()> {
+        self.anvil_set_logging_enabled(enabled).await
+    }
+
+    /// Handler for `hardhat_setMinGasPrice`
+    async fn hardhat_set_min_gas_price(&self, gas_price: U256) -> RpcResult<()> {
+        self.anvil_set_min_gas_price(gas_price).await
+    }
+
+    /// Handler for `hardhat_setNextBlockBaseFeePerGas`
+    async fn hardhat_set_next_block_base_fee_per_gas(
+        &self,
+        base_fee_per_gas: U256,
+    ) -> RpcResult<()> {
+        self.anvil_set_next_block_base_fee_per_gas(base_fee_per_gas).await
+    }
+
+    /// Handler for `hardhat_setPrevRandao`
+    async fn hardhat_set_prev_randao(&self, prev_randao: B256) -> RpcResult<()> {
+        self.inner.miner.set_prev_randao(prev_randao).await.map_err(miner_err)
+    }
+
+    /// Handler for `hardhat_setNonce`
+    async fn hardhat_set_nonce(&self, address: Address, nonce: U256) -> RpcResult<()> {
+        self.anvil_set_nonce(address, nonce).await
+    }
+
+    /// Handler for `hardhat_setStorageAt`
+    async fn hardhat_set_storage_at(
+        &self,
+        address: Address,
+        slot: U256,
+        value: B256,
+    ) -> RpcResult<()> {
+        self.anvil_set_storage_at(address, slot, value).await?;
+        Ok(())
+    }
+
+    /// Handler for `hardhat_stopImpersonatingAccount`
+    async fn hardhat_stop_impersonating_account(&self, address: Address) -> RpcResult<()> {
+        self.anvil_stop_impersonating_account(address).await
+    }
+}
+
+impl<Eth> std::fmt::Debug for AnvilApi<Eth> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnvilApi").finish_non_exhaustive()
+    }
+}
+
+struct AnvilApiInner<Eth> {
+    /// The `eth` API of the node.
+    eth_api: Eth,
+    /// Handle to the local miner that drives the dev chain.
+    miner: LocalMinerHandle,
+    /// Signer of the impersonated accounts, registered with the `eth` API.
+    impersonation: ImpersonationSigner,
+    /// Random id of this instance, reported by `anvil_metadata`.
+    instance_id: B256,
+}
+
+/// Signature attached to the transactions of impersonated accounts.
+///
+/// The sender of these transactions is never recovered from it.
+const IMPERSONATED_SIGNATURE: Signature =
+    Signature::new(U256::from_limbs([1, 0, 0, 0]), U256::from_limbs([1, 0, 0, 0]), false);
+
+/// [`EthSigner`] for impersonated accounts.
+///
+/// `eth_sendTransaction` attributes the transaction to the requested sender, so the transactions
+/// only carry a placeholder signature.
+#[derive(Debug, Clone, Default)]
+struct ImpersonationSigner {
+    /// Accounts impersonated with `anvil_impersonateAccount`.
+    accounts: Arc<RwLock<AddressSet>>,
+    /// Whether all accounts are impersonated.
+    auto_impersonate: Arc<AtomicBool>,
+}
+
+#[async_trait]
+impl<T, TxReq> EthSigner<T, TxReq> for ImpersonationSigner
+where
+    TxReq: SignableTxRequest<T>,
+{
+    fn accounts(&self) -> Vec<Address> {
+        // impersonated accounts are not owned by the node
+        Vec::new()
+    }
+
+    fn is_signer_for(&self, addr: &Address) -> bool {
+        self.auto_impersonate.load(Ordering::Relaxed) || self.accounts.read().contains(addr)
+    }
+
+    async fn sign(&self, _address: Address, _message: &[u8]) -> signer::Result<Signature> {
+        Err(SignError::CouldNotSign)
+    }
+
+    async fn sign_transaction(&self, request: TxReq, address: &Address) -> signer::Result<T> {
+        request
+            .try_build_and_sign(ImpersonatedTxSigner(*address))
+            .await
+            .map_err(|_| SignError::InvalidTransactionRequest)
+    }
+
+    fn sign_typed_data(
+        &self,
+        _address: Address,
+        _payload: &TypedData,
+    ) -> signer::Result<Signature> {
+        Err(SignError::CouldNotSign)
+    }
+}
+
+/// [`TxSigner`] that attaches the [`IMPERSONATED_SIGNATURE`] to the transactions of an
+/// impersonated account.
+struct ImpersonatedTxSigner(Address);
+
+#[async_trait]
+impl TxSigner<Signature> for ImpersonatedTxSigner {
+    fn address(&self) -> Address {
+        self.0
+    }
+
+    async fn sign_transaction(
+        &self,
+        _tx: &mut dyn SignableTransaction<Signature>,
+    ) -> alloy_signer::Result<Signature> {
+        Ok(IMPERSONATED_SIGNATURE)
+    }
+}
+
+/// State of the dev chain as produced by `anvil_dumpState`.
+///
+/// This is compatible with the accounts of Anvil's state dumps, other fields of an Anvil dump are
+/// ignored when the state is loaded.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SerializableState {
+    /// The latest block number when the state was dumped.
+    #[serde(default)]
+    best_block_number: Option<u64>,
+    /// All accounts of the chain.
+    accounts: BTreeMap<Address, SerializableAccountRecord>,
+}
+
+impl SerializableState {
+    /// Decodes a state from gzip compressed or plain JSON.
+    fn decode(bytes: &[u8]) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        if bytes.starts_with(&[0x1f, 0x8b]) {
+            let mut json = Vec::new();
+            GzDecoder::new(bytes).read_to_end(&mut json)?;
+            return Ok(serde_json::from_slice(&json)?)
+        }
+        Ok(serde_json::from_slice(bytes)?)
+    }
+}
+
+/// A single account of a [`SerializableState`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SerializableAccountRecord {
+    nonce: u64,
+    balance: U256,
+    code: Bytes,
+    storage: BTreeMap<U256, U256>,
+}
+
+/// Converts a [`LocalMinerError`] into an RPC error.
+fn miner_err(err: LocalMinerError) -> ErrorObject<'static> {
+    match err {
+        LocalMinerError::TimestampTooLow { .. } => invalid_params_rpc_err(err.to_string()),
+        err => internal_rpc_err(err.to_string()),
+    }
+}
@@ -1,47 +1,78 @@
-use alloy_consensus::{transaction::TxHashRef, BlockHeader};
-use alloy_eips::{eip2718::Encodable2718, BlockId, BlockNumberOrTag};
+use alloy_consensus::{constants::KECCAK_EMPTY, transaction::TxHashRef, BlockHeader};
+use alloy_eips::{eip2718::Encodable2718, BlockHashOrNumber, BlockId, BlockNumberOrTag};
 use alloy_evm::{env::BlockEnvironment, Evm};
 use alloy_genesis::ChainConfig;
-use alloy_primitives::{hex::decode, uint, Address, Bytes, B256, U64};
+use alloy_primitives::{hex, hex::decode, keccak256, uint, Address, Bytes, B256, U256, U64};
 use alloy_rlp::{Decodable, Encodable};
 use alloy_rpc_types::BlockTransactionsKind;
-use alloy_rpc_types_debug::ExecutionWitness;
+use alloy_rpc_types_debug::{AccountState, ExecutionWitness};
 use alloy_rpc_types_eth::{state::EvmOverrides, BlockError, Bundle, StateContext};
 use alloy_rpc_types_trace::geth::{
     BlockTraceResult, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, TraceResult,
 };
 use async_trait::async_trait;
-use futures::Stream;
-use jsonrpsee::core::RpcResult;
+use futures::{Stream, StreamExt, TryStreamExt};
+use jsonrpsee::{core::RpcResult, PendingSubscriptionSink, SubscriptionMessage, SubscriptionSink};
 use parking_lot::RwLock;
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
-use reth_engine_primitives::ConsensusEngineEvent;
+use reth_engine_primitives::{BeaconSetHeadError, ConsensusEngineEvent, SetHeadHandle};
 use reth_errors::RethError;
 use reth_evm::{execute::Executor, ConfigureEvm, EvmEnvFor};
 use reth_primitives_traits::{
     Block as BlockTrait, BlockBody, BlockTy, ReceiptWithBloom, RecoveredBlock,
 };
 use reth_revm::{db::State, witness::ExecutionWitnessRecord};
-use reth_rpc_api::DebugApiServer;
+use reth_rpc_api::{
+    AccountRangeResult, DebugApiServer, DebugSubscriptionKind, StdTraceConfig, StorageRangeEntry,
+    StorageRangeResult,
+};
 use reth_rpc_convert::RpcTxReq;
 use reth_rpc_eth_api::{
     helpers::{EthTransactions, TraceExt},
     FromEthApiError, FromEvmError, RpcConvert, RpcNodeCore,
 };
 use reth_rpc_eth_types::EthApiError;
-use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
+use reth_rpc_server_types::{
+    result::{internal_rpc_err, invalid_params_rpc_err},
+    ToRpcResult,
+};
 use reth_storage_api::{
-    BlockIdReader, BlockReaderIdExt, HashedPostStateProvider, HeaderProvider, ProviderBlock,
+    BlockIdReader, BlockNumReader, BlockReaderIdExt, ChangeSetReader, HashedPostStateProvider,
+    HashedStateProviderFactory, HashedStateRangeReader, HeaderProvider, ProviderBlock,
     ReceiptProviderIdExt, StateProviderFactory, StateRootProvider, TransactionVariant,
 };
 use reth_tasks::{pool::BlockingTaskGuard, Runtime};
 use reth_trie_common::{updates::TrieUpdates, ExecutionWitnessMode, HashedPostState};
-use revm::{database::states::bundle_state::BundleRetention, DatabaseCommit};
+use revm::{
+    database::states::bundle_state::BundleRetention, inspector::inspectors::TracerEip3155,
+    DatabaseCommit,
+};
 use revm_inspectors::tracing::{DebugInspector, TransactionContext};
 use serde::{Deserialize, Serialize};
-use std::{collections::VecDeque, sync::Arc};
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    fs::File,
+    path::PathBuf,
+    sync::Arc,
+};
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
-use tokio_stream::StreamExt;
+
+/// The maximum number of blocks `debug_traceChain` traces into a single response.
+///
+/// Larger ranges must be traced with the `traceChain` subscription.
+const MAX_TRACE_CHAIN_RESPONSE_BLOCKS: u64 = 100;
+
+/// The maximum number of blocks of a `traceChain` request that are traced concurrently.
+const TRACE_CHAIN_CONCURRENCY: usize = 8;
+
+/// The maximum number of blocks `debug_getModifiedAccountsBy{Number,Hash}` reads the changes of.
+const MODIFIED_ACCOUNTS_MAX_BLOCKS: u64 = 1024;
+
+/// The maximum number of accounts `debug_accountRange` returns in a single page, same as geth.
+const ACCOUNT_RANGE_MAX_RESULTS: u64 = 256;
+
+/// The maximum number of storage slots `debug_accountRange` returns per account.
+const ACCOUNT_RANGE_MAX_STORAGE_SLOTS: usize = 1024;
 
 /// `debug` API implementation.
 ///
@@ -55,17 +86,24 @@ where
     Eth: RpcNodeCore,
 {
     /// Create a new instance of the [`DebugApi`]
+    ///
+    /// Trace files are written to `trace_dir`, or to the system's temporary directory if it is not
+    /// set. `debug_setHead` is only supported if a `set_head` handle to the engine is given.
     pub fn new(
         eth_api: Eth,
         blocking_task_guard: BlockingTaskGuard,
         executor: &Runtime,
         mut stream: impl Stream<Item = ConsensusEngineEvent<Eth::Primitives>> + Send + Unpin + 'static,
+        trace_dir: Option<PathBuf>,
+        set_head: Option<SetHeadHandle>,
     ) -> Self {
         let bad_block_store = BadBlockStore::default();
         let inner = Arc::new(DebugApiInner {
             eth_api,
             blocking_task_guard,
             bad_block_store: bad_block_store.clone(),
+            trace_dir: trace_dir.unwrap_or_else(std::env::temp_dir),
+            set_head,
         });
 
         // Spawn a task caching bad blocks
@@ -206,6 +244,77 @@ where
         self.trace_block(block, evm_env, opts).await
     }
 
+    /// Returns a stream of the traces of every block in `(start_exclusive, end_inclusive]`, in
+    /// block order.
+    ///
+    /// Up to [`TRACE_CHAIN_CONCURRENCY`] blocks are re-executed at a time, each holding a trace
+    /// permit. The stream ends after the first error.
+    pub fn debug_trace_chain_stream(
+        &self,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+        opts: GethDebugTracingOptions,
+    ) -> Result<impl Stream<Item = Result<BlockTraceResult, Eth::Error>> + Send + 'static, Eth::Error>
+    {
+        let (start, end) = self.trace_chain_range(start_exclusive, end_inclusive)?;
+
+        let this = self.clone();
+        let stream = futures::stream::iter(start + 1..=end)
+            .map(move |number| {
+                let this = this.clone();
+                let opts = opts.clone();
+                async move {
+                    let _permit = this.acquire_trace_permit().await;
+                    let block_id = BlockId::from(number);
+                    let block = this
+                        .eth_api()
+                        .recovered_block(block_id)
+                        .await?
+                        .ok_or(EthApiError::HeaderNotFound(block_id))?;
+                    let evm_env =
+                        this.eth_api().evm_env_for_header(block.sealed_block().sealed_header())?;
+                    let hash = block.hash();
+                    let traces = this.trace_block(block, evm_env, opts).await?;
+
+                    Ok(BlockTraceResult { block: U256::from(number), hash, traces })
+                }
+            })
+            .buffered(TRACE_CHAIN_CONCURRENCY)
+            .scan(false, |failed, res: Result<_, Eth::Error>| {
+                if *failed {
+                    return futures::future::ready(None)
+                }
+                *failed = res.is_err();
+                futures::future::ready(Some(res))
+            });
+
+        Ok(stream)
+    }
+
+    /// Resolves the bounds of a `traceChain` request into block numbers.
+    fn trace_chain_range(
+        &self,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+    ) -> Result<(u64, u64), Eth::Error> {
+        let resolve = |number_or_tag: BlockNumberOrTag| -> Result<u64, Eth::Error> {
+            self.provider()
+                .convert_block_number(number_or_tag)
+                .map_err(Eth::Error::from_eth_err)?
+                .ok_or_else(|| EthApiError::HeaderNotFound(number_or_tag.into()).into())
+        };
+        let start = resolve(start_exclusive)?;
+        let end = resolve(end_inclusive)?;
+        if start >= end {
+            return Err(EthApiError::InvalidParams(format!(
+                "end block ({end}) must be greater than start block ({start})"
+            ))
+            .into())
+        }
+
+        Ok((start, end))
+    }
+
     /// Trace the transaction according to the provided options.
     ///
     /// Ref: <https://geth.ethereum.org/docs/developers/evm-tracing/built-in-tracers>
@@ -589,12 +698,17 @@ where
     }
 
     /// Executes a block and returns the state root after each transaction.
+    ///
+    /// The block can be canonical, a sidechain block or a cached bad block.
     pub async fn intermediate_roots(&self, block_hash: B256) -> Result<Vec<B256>, Eth::Error> {
-        let block = self
-            .eth_api()
-            .recovered_block(block_hash.into())
-            .await?
-            .ok_or(EthApiError::HeaderNotFound(block_hash.into()))?;
+        let block = match self.eth_api().recovered_block(block_hash.into()).await? {
+            Some(block) => block,
+            None => self
+                .inner
+                .bad_block_store
+                .get(block_hash)
+                .ok_or(EthApiError::HeaderNotFound(block_hash.into()))?,
+        };
         let evm_env = self.eth_api().evm_env_for_header(block.sealed_block().sealed_header())?;
 
         self.eth_api()
@@ -624,6 +738,362 @@ where
             })
             .await
     }
+
+    /// Replays a block and writes an EIP-3155 trace of each transaction to its own file in the
+    /// trace directory. Returns the paths of the written files.
+    pub async fn debug_standard_trace_block_to_file(
+        &self,
+        block_hash: B256,
+        config: StdTraceConfig,
+    ) -> Result<Vec<String>, Eth::Error> {
+        let block = self
+            .eth_api()
+            .recovered_block(block_hash.into())
+            .await?
+            .ok_or(EthApiError::HeaderNotFound(block_hash.into()))?;
+        let evm_env = self.eth_api().evm_env_for_header(block.sealed_block().sealed_header())?;
+
+        self.write_standard_traces(block, evm_env, config, "block").await
+    }
+
+    /// Replays the block and writes an EIP-3155 trace of each transaction to its own file in the
+    /// trace directory. Returns the paths of the written files.
+    ///
+    /// If the config selects a transaction, only that transaction is traced.
+    async fn write_standard_traces(
+        &self,
+        block: Arc<RecoveredBlock<ProviderBlock<Eth::Provider>>>,
+        evm_env: EvmEnvFor<Eth::Evm>,
+        config: StdTraceConfig,
+        file_prefix: &'static str,
+    ) -> Result<Vec<String>, Eth::Error> {
+        if let Some(tx_hash) = config.tx_hash &&
+            !block.body().contains_transaction(&tx_hash)
+        {
+            return Err(EthApiError::InvalidParams(format!(
+                "transaction {tx_hash} not found in block {}",
+                block.hash()
+            ))
+            .into())
+        }
+
+        let trace_dir = self.inner.trace_dir.clone();
+        std::fs::create_dir_all(&trace_dir)
+            .map_err(RethError::other)
+            .map_err(Eth::Error::from_eth_err)?;
+
+        self.eth_api()
+            .spawn_with_state_at_block(block.parent_hash(), move |eth_api, mut db| {
+                eth_api.apply_pre_execution_changes(&block, &mut db)?;
+
+                let mut files = Vec::new();
+                for (index, tx) in block.transactions_recovered().enumerate() {
+                    let tx_hash = *tx.tx_hash();
+                    let tx_env = eth_api.evm_config().tx_env(tx);
+
+                    if config.tx_hash.is_some_and(|hash| hash != tx_hash) {
+                        let mut evm = eth_api.evm_config().evm_with_env(&mut db, evm_env.clone());
+                        evm.transact_commit(tx_env).map_err(Eth::Error::from_evm_err)?;
+                        continue
+                    }
+
+                    let path = trace_dir.join(format!(
+                        "{file_prefix}_0x{}-{index}-0x{}.jsonl",
+                        hex::encode(&block.hash()[..4]),
+                        hex::encode(&tx_hash[..4]),
+                    ));
+                    let file = File::create(&path)
+                        .map_err(RethError::other)
+                        .map_err(Eth::Error::from_eth_err)?;
+                    let mut inspector = TracerEip3155::buffered(file);
+                    if config.enable_memory {
+                        inspector = inspector.with_memory();
+                    }
+
+                    let res = eth_api.inspect(&mut db, evm_env.clone(), tx_env, &mut inspector)?;
+                    files.push(path.display().to_string());
+
+                    if config.tx_hash.is_some() {
+                        break
+                    }
+                    db.commit(res.state);
+                }
+
+                Ok(files)
+            })
+            .await
+    }
+
+    /// Returns the accounts modified in the blocks after `start`, up to and including `end`.
+    ///
+    /// If no `end` block is given, the accounts modified in the `start` block are returned.
+    /// Accounts are read from the account changesets of each block, so an account whose changes
+    /// cancel out over the range is still included. At most [`MODIFIED_ACCOUNTS_MAX_BLOCKS`] blocks
+    /// are read.
+    pub async fn modified_accounts(
+        &self,
+        start: BlockHashOrNumber,
+        end: Option<BlockHashOrNumber>,
+    ) -> Result<Vec<Address>, Eth::Error> {
+        let block_number = |block: BlockHashOrNumber| -> Result<u64, Eth::Error> {
+            self.provider()
+                .convert_hash_or_number(block)
+                .map_err(Eth::Error::from_eth_err)?
+                .ok_or_else(|| EthApiError::HeaderNotFound(block.into()).into())
+        };
+
+        let (start, end) = match end {
+            Some(end) => (block_number(start)?, block_number(end)?),
+            None => {
+                let end = block_number(start)?;
+                let start = end.checked_sub(1).ok_or_else(|| {
+                    EthApiError::InvalidParams("genesis block has no parent".to_string())
+                })?;
+                (start, end)
+            }
+        };
+        if start >= end {
+            return Err(EthApiError::InvalidParams(format!(
+                "start block height ({start}) must be less than end block height ({end})"
+            ))
+            .into())
+        }
+        if end - start > MODIFIED_ACCOUNTS_MAX_BLOCKS {
+            return Err(EthApiError::InvalidParams(format!(
+                "block range exceeds {MODIFIED_ACCOUNTS_MAX_BLOCKS} blocks"
+            ))
+            .into())
+        }
+        if end > self.provider().best_block_number().map_err(Eth::Error::from_eth_err)? {
+            return Err(EthApiError::HeaderNotFound(end.into()).into())
+        }
+
+        self.eth_api()
+            .spawn_blocking_io(move |this| {
+                let mut accounts = BTreeSet::new();
+                for block in start + 1..=end {
+                    let changeset = this
+                        .provider()
+                        .account_block_changeset(block)
+                        .map_err(Eth::Error::from_eth_err)?;
+                    accounts.extend(changeset.into_iter().map(|change| change.address));
+                }
+                Ok(accounts.into_iter().collect())
+            })
+            .await
+    }
+
+    /// Returns a page of the accounts after the given block, ordered by hashed address.
+    ///
+    /// `start` is a prefix of the hashed address to start from. Since reth does not store address
+    /// preimages, accounts are keyed by their hashed address and requests without `incompletes`
+    /// are rejected. The storage of each account is truncated to
+    /// [`ACCOUNT_RANGE_MAX_STORAGE_SLOTS`] slots, which marks the result as incomplete.
+    pub async fn debug_account_range(
+        &self,
+        block_id: BlockId,
+        start: Bytes,
+        max_results: u64,
+        nocode: bool,
+        nostorage: bool,
+        incompletes: bool,
+    ) -> Result<AccountRangeResult, Eth::Error> {
+        if !incompletes {
+            // every account would be skipped for its missing preimage
+            return Err(EthApiError::InvalidParams(
+                "incompletes must be set, address preimages are not stored".to_string(),
+            )
+            .into())
+        }
+        if start.len() > B256::len_bytes() {
+            return Err(EthApiError::InvalidParams(format!(
+                "start key is longer than {} bytes",
+                B256::len_bytes()
+            ))
+            .into())
+        }
+        let mut start_key = B256::ZERO;
+        start_key[..start.len()].copy_from_slice(&start);
+
+        let max_results = if max_results == 0 || max_results > ACCOUNT_RANGE_MAX_RESULTS {
+            ACCOUNT_RANGE_MAX_RESULTS
+        } else {
+            max_results
+        } as usize;
+
+        let header = self
+            .provider()
+            .sealed_header_by_id(block_id)
+            .map_err(Eth::Error::from_eth_err)?
+            .ok_or(EthApiError::HeaderNotFound(block_id))?;
+        let root = header.state_root();
+
+        self.eth_api()
+            .spawn_blocking_io(move |this| {
+                let provider = this.provider();
+                let hashed_state = provider
+                    .hashed_state_by_block_hash(header.hash())
+                    .map_err(Eth::Error::from_eth_err)?;
+                let state = provider
+                    .history_by_block_hash(header.hash())
+                    .map_err(Eth::Error::from_eth_err)?;
+
+                let mut accounts = hashed_state
+                    .hashed_accounts_from(start_key, max_results + 1)
+                    .map_err(Eth::Error::from_eth_err)?;
+                let next = (accounts.len() > max_results)
+                    .then(|| accounts.pop())
+                    .flatten()
+                    .map(|(hashed_address, _)| Bytes::copy_from_slice(hashed_address.as_slice()));
+
+                let mut result = AccountRangeResult { root, next, ..Default::default() };
+                for (hashed_address, account) in accounts {
+                    let code_hash = account.bytecode_hash.unwrap_or(KECCAK_EMPTY);
+                    let code = if nocode || code_hash == KECCAK_EMPTY {
+                        None
+                    } else {
+                        state
+                            .bytecode_by_hash(&code_hash)
+                            .map_err(Eth::Error::from_eth_err)?
+                            .map(|code| code.original_bytes())
+                    };
+                    let storage = if nostorage {
+                        None
+                    } else {
+                        let mut storage = hashed_state
+                            .hashed_storages_from(
+                                hashed_address,
+                                B256::ZERO,
+                                ACCOUNT_RANGE_MAX_STORAGE_SLOTS + 1,
+                            )
+                            .map_err(Eth::Error::from_eth_err)?;
+                        if storage.len() > ACCOUNT_RANGE_MAX_STORAGE_SLOTS {
+                            storage.truncate(ACCOUNT_RANGE_MAX_STORAGE_SLOTS);
+                            result.incomplete = true;
+                        }
+                        Some(storage.into_iter().collect())
+                    };
+                    let account = AccountState {
+                        balance: account.balance,
+                        nonce: account.nonce,
+                        root: hashed_state
+                            .hashed_storage_root(hashed_address)
+                            .map_err(Eth::Error::from_eth_err)?,
+                        code_hash,
+                        code,
+                        storage,
+                        address: None,
+                        address_hash: Some(hashed_address),
+                    };
+                    result.accounts.insert(format!("pre({hashed_address})"), account);
+                }
+
+                Ok(result)
+            })
+            .await
+    }
+
+    /// Returns a page of the storage of the given contract, ordered by hashed storage key, as it is
+    /// before the transaction at `tx_idx` of the block is executed.
+    pub async fn debug_storage_range_at(
+        &self,
+        block_hash: B256,
+        tx_idx: usize,
+        contract_address: Address,
+        key_start: B256,
+        max_result: u64,
+    ) -> Result<StorageRangeResult, Eth::Error> {
+        let block = self
+            .eth_api()
+            .recovered_block(block_hash.into())
+            .await?
+            .ok_or(EthApiError::HeaderNotFound(block_hash.into()))?;
+        let tx_count = block.body().transactions().len();
+        if tx_idx >= tx_count && !(tx_idx == 0 && tx_count == 0) {
+            return Err(EthApiError::InvalidParams(format!(
+                "transaction index {tx_idx} out of range for block {block_hash}"
+            ))
+            .into())
+        }
+        let evm_env = self.eth_api().evm_env_for_header(block.sealed_block().sealed_header())?;
+        let max_result = usize::try_from(max_result).unwrap_or(usize::MAX);
+
+        self.eth_api()
+            .spawn_with_state_at_block(block.parent_hash(), move |eth_api, mut db| {
+                eth_api.apply_pre_execution_changes(&block, &mut db)?;
+                if let Some(tx) = block.transactions_recovered().nth(tx_idx) {
+                    eth_api.replay_transactions_until(
+                        &mut db,
+                        evm_env,
+                        block.transactions_recovered(),
+                        *tx.tx_hash(),
+                    )?;
+                }
+
+                // slots touched so far are cached with their plain key, the rest is read from the
+                // hashed state of the parent block
+                let (overlay, wiped) = match db.cache.accounts.get(&contract_address) {
+                    Some(cached) => (
+                        cached
+                            .account
+                            .as_ref()
+                            .map(|account| account.storage.clone())
+                            .unwrap_or_default(),
+                        cached.account.is_none() || cached.status.was_destroyed(),
+                    ),
+                    None => (Default::default(), false),
+                };
+
+                let mut storage = BTreeMap::new();
+                if !wiped {
+                    let limit = max_result.saturating_add(1).saturating_add(overlay.len());
+                    let base = eth_api
+                        .provider()
+                        .hashed_state_by_block_hash(block.parent_hash())
+                        .map_err(Eth::Error::from_eth_err)?
+                        .hashed_storages_from(keccak256(contract_address), key_start, limit)
+                        .map_err(Eth::Error::from_eth_err)?;
+                    // if the page was cut off, cached slots past its end can't be merged yet
+                    let last_key =
+                        if base.len() == limit { base.last().map(|(key, _)| *key) } else { None };
+                    storage.extend(base.into_iter().map(|(key, value)| (key, (None, value))));
+                    for (slot, value) in overlay {
+                        let key = B256::from(slot);
+                        let hashed_key = keccak256(key);
+                        if hashed_key < key_start || last_key.is_some_and(|last| hashed_key > last)
+                        {
+                            continue
+                        }
+                        if value.is_zero() {
+                            storage.remove(&hashed_key);
+                        } else {
+                            storage.insert(hashed_key, (Some(key), value));
+                        }
+                    }
+                } else {
+                    storage.extend(overlay.into_iter().filter(|(_, value)| !value.is_zero()).map(
+                        |(slot, value)| {
+                            let key = B256::from(slot);
+                            (keccak256(key), (Some(key), value))
+                        },
+                    ));
+                    storage.retain(|hashed_key, _| *hashed_key >= key_start);
+                }
+
+                let mut entries = storage.into_iter();
+                let storage = entries
+                    .by_ref()
+                    .take(max_result)
+                    .map(|(hashed_key, (key, value))| {
+                        (hashed_key, StorageRangeEntry { key, value: value.into() })
+                    })
+                    .collect();
+                let next_key = entries.next().map(|(hashed_key, _)| hashed_key);
+
+                Ok(StorageRangeResult { storage, next_key })
+            })
+            .await
+    }
 }
 
 #[async_trait]
@@ -731,10 +1201,53 @@ where
     /// Handler for `debug_traceChain`
     async fn debug_trace_chain(
         &self,
-        _start_exclusive: BlockNumberOrTag,
-        _end_inclusive: BlockNumberOrTag,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
     ) -> RpcResult<Vec<BlockTraceResult>> {
-        Err(internal_rpc_err("unimplemented"))
+        let (start, end) =
+            self.trace_chain_range(start_exclusive, end_inclusive).map_err(Into::into)?;
+        if end - start > MAX_TRACE_CHAIN_RESPONSE_BLOCKS {
+            return Err(invalid_params_rpc_err(format!(
+                "block range exceeds {MAX_TRACE_CHAIN_RESPONSE_BLOCKS} blocks, use the traceChain \
+                 subscription instead"
+            )))
+        }
+
+        self.debug_trace_chain_stream(start.into(), end.into(), opts.unwrap_or_default())
+            .map_err(Into::into)?
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Handler for `debug_subscribe`
+    async fn debug_subscribe(
+        &self,
+        pending: PendingSubscriptionSink,
+        kind: DebugSubscriptionKind,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        match kind {
+            DebugSubscriptionKind::TraceChain => {
+                let stream = match self.debug_trace_chain_stream(
+                    start_exclusive,
+                    end_inclusive,
+                    opts.unwrap_or_default(),
+                ) {
+                    Ok(stream) => stream,
+                    Err(err) => {
+                        pending.reject(err).await;
+                        return Ok(())
+                    }
+                };
+                let sink = pending.accept().await?;
+                // the subscription is closed with an error notification if tracing fails
+                pipe_trace_chain(sink, stream).await
+            }
+        }
     }
 
     /// Handler for `debug_traceBlock`
@@ -834,14 +1347,25 @@ where
 
     async fn debug_account_range(
         &self,
-        _block_number: BlockNumberOrTag,
-        _start: Bytes,
-        _max_results: u64,
-        _nocode: bool,
-        _nostorage: bool,
-        _incompletes: bool,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_id: BlockId,
+        start: Bytes,
+        max_results: u64,
+        nocode: bool,
+        nostorage: bool,
+        incompletes: bool,
+    ) -> RpcResult<AccountRangeResult> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_account_range(
+            self,
+            block_id,
+            start,
+            max_results,
+            nocode,
+            nostorage,
+            incompletes,
+        )
+        .await
+        .map_err(Into::into)
     }
 
     async fn debug_block_profile(&self, _file: String, _seconds: u64) -> RpcResult<()> {
@@ -940,18 +1464,22 @@ where
 
     async fn debug_get_modified_accounts_by_hash(
         &self,
-        _start_hash: B256,
-        _end_hash: B256,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_hash: B256,
+        end_hash: Option<B256>,
+    ) -> RpcResult<Vec<Address>> {
+        self.modified_accounts(start_hash.into(), end_hash.map(Into::into))
+            .await
+            .map_err(Into::into)
     }
 
     async fn debug_get_modified_accounts_by_number(
         &self,
-        _start_number: u64,
-        _end_number: u64,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_number: u64,
+        end_number: Option<u64>,
+    ) -> RpcResult<Vec<Address>> {
+        self.modified_accounts(start_number.into(), end_number.map(Into::into))
+            .await
+            .map_err(Into::into)
     }
 
     async fn debug_go_trace(&self, _file: String, _seconds: u64) -> RpcResult<()> {
@@ -995,7 +1523,17 @@ where
         Ok(())
     }
 
-    async fn debug_set_head(&self, _number: U64) -> RpcResult<()> {
+    async fn debug_set_head(&self, number: U64) -> RpcResult<()> {
+        let Some(set_head) = &self.inner.set_head else {
+            return Err(internal_rpc_err("setHead is not supported without a consensus engine"))
+        };
+
+        set_head.set_head(number.to()).await.map_err(|err| match err {
+            BeaconSetHeadError::InvalidTarget { .. } |
+            BeaconSetHeadError::TooDeep { .. } |
+            BeaconSetHeadError::Genesis => invalid_params_rpc_err(err.to_string()),
+            err => internal_rpc_err(err.to_string()),
+        })?;
         Ok(())
     }
 
@@ -1013,18 +1551,37 @@ where
 
     async fn debug_standard_trace_bad_block_to_file(
         &self,
-        _block: BlockNumberOrTag,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<StdTraceConfig>,
+    ) -> RpcResult<Vec<String>> {
+        let _permit = self.acquire_trace_permit().await;
+        let block = self
+            .inner
+            .bad_block_store
+            .get(block_hash)
+            .ok_or_else(|| internal_rpc_err("bad block not found in cache"))?;
+
+        let evm_env = self
+            .eth_api()
+            .evm_config()
+            .evm_env(block.header())
+            .map_err(RethError::other)
+            .to_rpc_result()?;
+
+        self.write_standard_traces(block, evm_env, opts.unwrap_or_default(), "badblock")
+            .await
+            .map_err(Into::into)
     }
 
     async fn debug_standard_trace_block_to_file(
         &self,
-        _block: BlockNumberOrTag,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<StdTraceConfig>,
+    ) -> RpcResult<Vec<String>> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_standard_trace_block_to_file(self, block_hash, opts.unwrap_or_default())
+            .await
+            .map_err(Into::into)
     }
 
     async fn debug_start_cpu_profile(&self, _file: String) -> RpcResult<()> {
@@ -1053,13 +1610,23 @@ where
 
     async fn debug_storage_range_at(
         &self,
-        _block_hash: B256,
-        _tx_idx: usize,
-        _contract_address: Address,
-        _key_start: B256,
-        _max_result: u64,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        tx_idx: usize,
+        contract_address: Address,
+        key_start: B256,
+        max_result: u64,
+    ) -> RpcResult<StorageRangeResult> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_storage_range_at(
+            self,
+            block_hash,
+            tx_idx,
+            contract_address,
+            key_start,
+            max_result,
+        )
+        .await
+        .map_err(Into::into)
     }
 
     async fn debug_trace_bad_block(
@@ -1106,6 +1673,51 @@ where
     }
 }
 
+/// Pipes the block traces of a `traceChain` subscription to the sink until the stream ends, fails
+/// or the subscription is closed.
+///
+/// Returns an error if tracing fails, which closes the subscription with that error.
+async fn pipe_trace_chain<S, E>(
+    sink: SubscriptionSink,
+    stream: S,
+) -> jsonrpsee::core::SubscriptionResult
+where
+    S: Stream<Item = Result<BlockTraceResult, E>>,
+    E: std::fmt::Display,
+{
+    let mut stream = std::pin::pin!(stream);
+    loop {
+        tokio::select! {
+            _ = sink.closed() => break,
+            maybe_item = stream.next() => {
+                let trace = match maybe_item {
+                    Some(Ok(trace)) => trace,
+                    Some(Err(err)) => {
+                        tracing::debug!(target: "rpc::debug", %err, "Failed to trace chain");
+                        return Err(err.into())
+                    }
+                    None => break,
+                };
+                let msg = match SubscriptionMessage::new(
+                    sink.method_name(),
+                    sink.subscription_id(),
+                    &trace,
+                ) {
+                    Ok(msg) => msg,
+                    Err(err) => {
+                        tracing::error!(target: "rpc::debug", %err, "Failed to serialize subscription message");
+                        return Err(err.into())
+                    }
+                };
+                if sink.send(msg).await.is_err() {
+                    break
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
 impl<Eth: RpcNodeCore> std::fmt::Debug for DebugApi<Eth> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("DebugApi").finish_non_exhaustive()
@@ -1125,6 +1737,10 @@ struct DebugApiInner<Eth: RpcNodeCore> {
     blocking_task_guard: BlockingTaskGuard,
     /// Cache for bad blocks.
     bad_block_store: BadBlockStore<BlockTy<Eth::Primitives>>,
+    /// Directory the `standardTrace*ToFile` calls write their trace files to.
+    trace_dir: PathBuf,
+    /// Handle to the engine used by `debug_setHead`.
+    set_head: Option<SetHeadHandle>,
 }
 
 /// A bounded, deduplicating store of recently observed bad blocks.
@@ -1175,3 +1791,309 @@ impl<B: BlockTrait> Default for BadBlockStore<B> {
         Self::new(64)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{eth::EthApi, EthApiBuilder};
+    use alloy_consensus::{Header, SignableTransaction, TxLegacy};
+    use alloy_network::Ethereum;
+    use alloy_primitives::TxKind;
+    use alloy_signer::SignerSync;
+    use alloy_signer_local::PrivateKeySigner;
+    use jsonrpsee::Methods;
+    use reth_chainspec::{ChainSpec, ChainSpecProvider};
+    use reth_db_api::models::AccountBeforeTx;
+    use reth_ethereum_primitives::{Block, BlockBody, TransactionSigned};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_rpc_convert::RpcConverter;
+    use reth_rpc_eth_api::node::RpcNodeCoreAdapter;
+    use reth_rpc_eth_types::receipt::EthReceiptConverter;
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+    use serde_json::value::RawValue;
+    use tokio::sync::mpsc::Receiver;
+
+    type TestDebugApi = DebugApi<
+        EthApi<
+            RpcNodeCoreAdapter<MockEthProvider, TestPool, NoopNetwork, EthEvmConfig>,
+            RpcConverter<Ethereum, EthEvmConfig, EthReceiptConverter<ChainSpec>>,
+        >,
+    >;
+
+    /// Builds a [`DebugApi`] over the given provider that writes trace files to `trace_dir`.
+    fn build_debug_api(provider: MockEthProvider, trace_dir: Option<PathBuf>) -> TestDebugApi {
+        let eth_api = EthApiBuilder::new(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork::default(),
+            EthEvmConfig::new(provider.chain_spec()),
+        )
+        .build();
+        DebugApi::new(
+            eth_api,
+            BlockingTaskGuard::new(1),
+            &Runtime::test(),
+            futures::stream::empty(),
+            trace_dir,
+            None,
+        )
+    }
+
+    /// Builds a [`DebugApi`] over blocks `0..=tip` where block `n` modifies the accounts
+    /// `changes[n]`.
+    fn build_test_debug_api(tip: u64, changes: &[(u64, &[Address])]) -> TestDebugApi {
+        let provider = MockEthProvider::default();
+        for number in 0..=tip {
+            provider.add_header(
+                B256::left_padding_from(&number.to_be_bytes()),
+                Header { number, ..Default::default() },
+            );
+        }
+        for (number, addresses) in changes {
+            provider.add_account_changeset(
+                *number,
+                addresses
+                    .iter()
+                    .map(|address| AccountBeforeTx { address: *address, info: None })
+                    .collect(),
+            );
+        }
+
+        build_debug_api(provider, None)
+    }
+
+    /// Contract called by the transactions of [`add_block_with_calls`].
+    const CALLED_CONTRACT: Address = Address::with_last_byte(0xaa);
+
+    /// Code of [`CALLED_CONTRACT`]: `PUSH1 0x2a POP STOP`.
+    const CALLED_CODE: [u8; 4] = [0x60, 0x2a, 0x50, 0x00];
+
+    /// Returns a provider with the genesis header, [`CALLED_CONTRACT`] and a funded account of the
+    /// signer.
+    fn build_call_provider(signer: &PrivateKeySigner) -> (MockEthProvider, Header) {
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            signer.address(),
+            ExtendedAccount::new(0, U256::from(1_000_000_000_000_000_000u128)),
+        );
+        provider.add_account(
+            CALLED_CONTRACT,
+            ExtendedAccount::new(0, U256::ZERO).with_bytecode(Bytes::from_static(&CALLED_CODE)),
+        );
+        let genesis = Header { gas_limit: 30_000_000, ..Default::default() };
+        provider.add_header(genesis.hash_slow(), genesis.clone());
+        (provider, genesis)
+    }
+
+    /// Adds a block on top of `parent` with a call to [`CALLED_CONTRACT`] for each nonce of the
+    /// `signer` and returns it.
+    fn add_block_with_calls(
+        provider: &MockEthProvider,
+        signer: &PrivateKeySigner,
+        parent: &Header,
+        nonces: std::ops::Range<u64>,
+    ) -> Block {
+        let transactions = nonces
+            .map(|nonce| {
+                let tx = TxLegacy {
+                    nonce,
+                    gas_price: 1,
+                    gas_limit: 100_000,
+                    to: TxKind::Call(CALLED_CONTRACT),
+                    ..Default::default()
+                };
+                let signature = signer.sign_hash_sync(&tx.signature_hash()).unwrap();
+                TransactionSigned::new_unhashed(tx.into(), signature)
+            })
+            .collect();
+        let block = Block {
+            header: Header {
+                number: parent.number + 1,
+                parent_hash: parent.hash_slow(),
+                gas_limit: 30_000_000,
+                ..Default::default()
+            },
+            body: BlockBody { transactions, ..Default::default() },
+        };
+        provider.add_block(block.header.hash_slow(), block.clone());
+        block
+    }
+
+    /// Subscribes to the traces of the blocks `(0, end]`.
+    async fn subscribe_trace_chain(methods: &Methods, end: u64) -> Receiver<Box<RawValue>> {
+        let request = format!(
+            r#"{{"jsonrpc":"2.0","id":1,"method":"debug_subscribe","params":["traceChain","0x0","{end:#x}"]}}"#
+        );
+        let (_, notifications) = methods.raw_json_request(&request, 8).await.unwrap();
+        notifications
+    }
+
+    /// Returns the next notification of a subscription, or `None` once it is closed.
+    async fn next_notification(
+        notifications: &mut Receiver<Box<RawValue>>,
+    ) -> Option<serde_json::Value> {
+        let notification = notifications.recv().await?;
+        Some(serde_json::from_str(notification.get()).unwrap())
+    }
+
+    /// Asserts that the next notifications are the traces of the blocks with the given hashes,
+    /// starting at block 1.
+    async fn assert_block_traces(notifications: &mut Receiver<Box<RawValue>>, hashes: &[B256]) {
+        for (number, hash) in (1u64..).zip(hashes) {
+            let notification = next_notification(notifications).await.unwrap();
+            assert_eq!(notification["method"], "debug_subscription");
+            let trace: BlockTraceResult =
+                serde_json::from_value(notification["params"]["result"].clone()).unwrap();
+            assert_eq!(trace.block, U256::from(number));
+            assert_eq!(trace.hash, *hash);
+            assert_eq!(trace.traces.len(), 1);
+            assert!(matches!(trace.traces[0], TraceResult::Success { .. }));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_trace_chain_subscription() {
+        let signer = PrivateKeySigner::random();
+        let (provider, mut parent) = build_call_provider(&signer);
+        let mut hashes = Vec::new();
+        for _ in 0..3 {
+            // the mock state is the same for every block, so each block starts at nonce 0
+            let block = add_block_with_calls(&provider, &signer, &parent, 0..1);
+            hashes.push(block.header.hash_slow());
+            parent = block.header;
+        }
+        let module = build_debug_api(provider, None).into_rpc();
+
+        // the traces are sent in block order and the subscription ends after the last block
+        let mut notifications = subscribe_trace_chain(&module, 3).await;
+        assert_block_traces(&mut notifications, &hashes).await;
+        assert!(next_notification(&mut notifications).await.is_none());
+
+        // tracing fails at the first missing block, which closes the subscription with an error
+        let mut notifications = subscribe_trace_chain(&module, 5).await;
+        assert_block_traces(&mut notifications, &hashes).await;
+        let notification = next_notification(&mut notifications).await.unwrap();
+        assert!(notification["params"].get("result").is_none());
+        assert!(notification["params"]["error"].to_string().contains("header not found"));
+        assert!(next_notification(&mut notifications).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_standard_trace_block_to_file() {
+        let signer = PrivateKeySigner::random();
+        let (provider, genesis) = build_call_provider(&signer);
+        let block = add_block_with_calls(&provider, &signer, &genesis, 0..2);
+        let block_hash = block.header.hash_slow();
+
+        let trace_dir = tempfile::tempdir().unwrap();
+        let api = build_debug_api(provider, Some(trace_dir.path().to_path_buf()));
+
+        let file_name = |index: usize| {
+            let tx_hash = block.body.transactions[index].tx_hash();
+            format!(
+                "block_0x{}-{index}-0x{}.jsonl",
+                hex::encode(&block_hash[..4]),
+                hex::encode(&tx_hash[..4])
+            )
+        };
+
+        // every transaction is written to its own file
+        let files = api
+            .debug_standard_trace_block_to_file(block_hash, StdTraceConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(files.len(), 2);
+        for (index, file) in files.iter().enumerate() {
+            let path = PathBuf::from(file);
+            assert_eq!(path, trace_dir.path().join(file_name(index)));
+
+            // one line per executed opcode, followed by the summary
+            let lines = std::fs::read_to_string(&path)
+                .unwrap()
+                .lines()
+                .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(lines.len(), 4);
+
+            let steps = &lines[..3];
+            let op_names = steps.iter().map(|step| step["opName"].as_str()).collect::<Vec<_>>();
+            assert_eq!(op_names, [Some("PUSH1"), Some("POP"), Some("STOP")]);
+            let pcs = steps.iter().map(|step| step["pc"].as_u64()).collect::<Vec<_>>();
+            assert_eq!(pcs, [Some(0), Some(2), Some(3)]);
+            assert_eq!(steps[0]["op"], 0x60);
+            assert_eq!(steps[0]["depth"], 1);
+            assert_eq!(steps[0]["gasCost"], "0x3");
+            assert!(steps[0]["gas"].as_str().unwrap().starts_with("0x"));
+            assert_eq!(steps[0]["stack"], serde_json::json!([]));
+            assert_eq!(steps[1]["stack"], serde_json::json!(["0x2a"]));
+            assert!(steps[0].get("memory").is_none());
+
+            let summary = &lines[3];
+            assert_eq!(summary["pass"], true);
+            assert!(summary["gasUsed"].is_string());
+            assert!(summary.get("stateRoot").is_some());
+        }
+
+        // only the selected transaction is traced, on top of the state of the ones before it
+        let tx_hash = *block.body.transactions[1].tx_hash();
+        let config = StdTraceConfig { enable_memory: true, tx_hash: Some(tx_hash) };
+        let files = api.debug_standard_trace_block_to_file(block_hash, config).await.unwrap();
+        assert_eq!(files, vec![trace_dir.path().join(file_name(1)).display().to_string()]);
+        let trace = std::fs::read_to_string(&files[0]).unwrap();
+        let first_step: serde_json::Value =
+            serde_json::from_str(trace.lines().next().unwrap()).unwrap();
+        assert_eq!(first_step["memory"], "0x");
+        let summary: serde_json::Value =
+            serde_json::from_str(trace.lines().last().unwrap()).unwrap();
+        assert_eq!(summary["pass"], true);
+
+        // transactions of other blocks are rejected
+        let config = StdTraceConfig { tx_hash: Some(B256::ZERO), ..Default::default() };
+        api.debug_standard_trace_block_to_file(block_hash, config).await.unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn test_modified_accounts() {
+        let (a, b, c) =
+            (Address::with_last_byte(1), Address::with_last_byte(2), Address::with_last_byte(3));
+        let api = build_test_debug_api(3, &[(1, &[b, a]), (2, &[c]), (3, &[a])]);
+
+        // a single block
+        let accounts = api.modified_accounts(2.into(), None).await.unwrap();
+        assert_eq!(accounts, vec![c]);
+
+        // the blocks after the start block, up to and including the end block
+        let accounts = api.modified_accounts(0.into(), Some(3.into())).await.unwrap();
+        assert_eq!(accounts, vec![a, b, c]);
+        let accounts = api.modified_accounts(1.into(), Some(3.into())).await.unwrap();
+        assert_eq!(accounts, vec![a, c]);
+
+        // blocks beyond the tip are rejected
+        api.modified_accounts(2.into(), Some(4.into())).await.unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn test_account_range_requires_incompletes() {
+        let api = build_test_debug_api(1, &[]);
+
+        let err = api
+            .debug_account_range(BlockId::latest(), Bytes::new(), 10, true, true, false)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("incompletes must be set"));
+    }
+
+    #[tokio::test]
+    async fn test_modified_accounts_range_is_capped() {
+        let api = build_test_debug_api(MODIFIED_ACCOUNTS_MAX_BLOCKS + 1, &[]);
+
+        api.modified_accounts(1.into(), Some((MODIFIED_ACCOUNTS_MAX_BLOCKS + 1).into()))
+            .await
+            .unwrap();
+        api.modified_accounts(0.into(), Some((MODIFIED_ACCOUNTS_MAX_BLOCKS + 1).into()))
+            .await
+            .unwrap_err();
+    }
+}
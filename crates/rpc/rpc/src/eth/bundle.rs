@@ -1,21 +1,28 @@
 //! `Eth` bundle implementation and helpers.
 
-use alloy_consensus::{transaction::TxHashRef, EnvKzgSettings, Transaction as _};
+use alloy_consensus::{transaction::TxHashRef, EnvKzgSettings, Transaction as _, Typed2718 as _};
 use alloy_eips::eip7840::BlobParams;
 use alloy_evm::env::BlockEnvironment;
-use alloy_primitives::{uint, Keccak256, U256};
-use alloy_rpc_types_mev::{EthCallBundle, EthCallBundleResponse, EthCallBundleTransactionResult};
+use alloy_primitives::{map::AddressMap, uint, Bytes, Keccak256, B256, U256};
+use alloy_rpc_types_mev::{
+    EthBundleHash, EthCallBundle, EthCallBundleResponse, EthCallBundleTransactionResult,
+    EthCancelBundle, EthCancelPrivateTransaction, EthSendBundle, EthSendPrivateTransaction,
+};
 use jsonrpsee::core::RpcResult;
 use reth_chainspec::{ChainSpecProvider, EthChainSpec};
 use reth_evm::{ConfigureEvm, Evm};
+use reth_primitives_traits::{SignedTransaction, WithEncoded};
 use reth_rpc_eth_api::{
-    helpers::{Call, EthTransactions, LoadPendingBlock},
-    EthCallBundleApiServer, FromEthApiError, FromEvmError,
+    helpers::{Call, EthTransactions, LoadPendingBlock, LoadState},
+    EthBundleApiServer, EthCallBundleApiServer, FromEthApiError, FromEvmError,
 };
 use reth_rpc_eth_types::{utils::recover_raw_transaction, EthApiError, RpcInvalidTransactionError};
+use reth_storage_api::StateProvider;
 use reth_tasks::pool::BlockingTaskGuard;
 use reth_transaction_pool::{
-    EthBlobTransactionSidecar, EthPoolTransaction, PoolPooledTx, PoolTransaction, TransactionPool,
+    bundle::{BundlePool, PoolBundle},
+    EthBlobTransactionSidecar, EthPoolTransaction, PoolConsensusTx, PoolPooledTx, PoolTransaction,
+    TransactionOrigin, TransactionPool,
 };
 use revm::{
     context::Block, context_interface::result::ResultAndState, DatabaseCommit, DatabaseRef,
@@ -257,6 +264,97 @@ where
             })
             .await
     }
+
+    /// Adds a bundle to the bundle pool of the transaction pool.
+    ///
+    /// The bundle is considered by the payload builder for the requested block, and replaces any
+    /// bundle previously submitted with the same replacement UUID.
+    pub fn send_bundle(&self, bundle: EthSendBundle) -> Result<EthBundleHash, Eth::Error> {
+        let EthSendBundle {
+            txs,
+            block_number,
+            min_timestamp,
+            max_timestamp,
+            reverting_tx_hashes,
+            replacement_uuid,
+            ..
+        } = bundle;
+        let bundle_pool = self.bundle_pool()?;
+        if txs.is_empty() {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::EmptyBundleTransactions.to_string(),
+            )
+            .into())
+        }
+        if block_number == 0 {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::BundleMissingBlockNumber.to_string(),
+            )
+            .into())
+        }
+
+        let transactions = txs
+            .iter()
+            .map(|tx| {
+                let tx = recover_raw_transaction::<PoolPooledTx<Eth::Pool>>(tx)?;
+                if tx.is_eip4844() {
+                    return Err(EthApiError::InvalidParams(
+                        EthBundleError::BlobTransactionsNotSupported.to_string(),
+                    ))
+                }
+                Ok(tx.map(<Eth::Pool as TransactionPool>::Transaction::pooled_into_consensus))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let bundle = PoolBundle {
+            transactions,
+            block_number,
+            max_block_number: block_number,
+            min_timestamp,
+            max_timestamp,
+            reverting_tx_hashes: reverting_tx_hashes.into_iter().collect(),
+            replacement_uuid,
+        };
+        ensure_bundle_nonces(self.eth_api(), &bundle)?;
+        let bundle_hash = bundle_pool
+            .add_bundle(bundle)
+            .map_err(|err| EthApiError::InvalidParams(err.to_string()))?;
+
+        Ok(EthBundleHash { bundle_hash })
+    }
+
+    /// Removes the bundle with the given replacement UUID from the bundle pool.
+    pub fn cancel_bundle(&self, request: EthCancelBundle) -> Result<(), Eth::Error> {
+        self.bundle_pool()?.cancel_bundle(&request.replacement_uuid);
+        Ok(())
+    }
+
+    /// Submits a transaction to the pool as a private transaction, which is not propagated to
+    /// peers.
+    pub async fn send_private_raw_transaction(&self, tx: Bytes) -> Result<B256, Eth::Error> {
+        let recovered = recover_raw_transaction::<PoolPooledTx<Eth::Pool>>(&tx)?;
+        self.eth_api()
+            .send_transaction(TransactionOrigin::Private, WithEncoded::new(tx, recovered))
+            .await
+    }
+
+    /// Removes a private transaction from the pool.
+    ///
+    /// Returns `false` if the pool does not contain a private transaction with the given hash.
+    pub fn cancel_private_transaction(&self, tx_hash: B256) -> bool {
+        let pool = self.eth_api().pool();
+        if !pool.get(&tx_hash).is_some_and(|tx| tx.origin.is_private()) {
+            return false
+        }
+        pool.remove_transaction(tx_hash).is_some()
+    }
+
+    /// Returns the bundle pool of the transaction pool.
+    fn bundle_pool(&self) -> Result<&BundlePool<PoolConsensusTx<Eth::Pool>>, Eth::Error> {
+        self.eth_api().pool().bundle_pool().ok_or_else(|| {
+            EthApiError::InvalidParams(EthBundleError::BundlePoolDisabled.to_string()).into()
+        })
+    }
 }
 
 #[async_trait::async_trait]
@@ -269,6 +367,65 @@ where
     }
 }
 
+#[async_trait::async_trait]
+impl<Eth> EthBundleApiServer for EthBundle<Eth>
+where
+    Eth: EthTransactions + LoadPendingBlock + Call + 'static,
+{
+    async fn send_bundle(&self, bundle: EthSendBundle) -> RpcResult<EthBundleHash> {
+        Self::send_bundle(self, bundle).map_err(Into::into)
+    }
+
+    async fn call_bundle(&self, request: EthCallBundle) -> RpcResult<EthCallBundleResponse> {
+        Self::call_bundle(self, request).await.map_err(Into::into)
+    }
+
+    async fn cancel_bundle(&self, request: EthCancelBundle) -> RpcResult<()> {
+        Self::cancel_bundle(self, request).map_err(Into::into)
+    }
+
+    async fn send_private_transaction(
+        &self,
+        request: EthSendPrivateTransaction,
+    ) -> RpcResult<B256> {
+        Self::send_private_raw_transaction(self, request.tx).await.map_err(Into::into)
+    }
+
+    async fn send_private_raw_transaction(&self, bytes: Bytes) -> RpcResult<B256> {
+        Self::send_private_raw_transaction(self, bytes).await.map_err(Into::into)
+    }
+
+    async fn cancel_private_transaction(
+        &self,
+        request: EthCancelPrivateTransaction,
+    ) -> RpcResult<bool> {
+        Ok(Self::cancel_private_transaction(self, request.tx_hash))
+    }
+}
+
+/// Checks the nonces of the bundle's transactions against the latest state.
+pub(crate) fn ensure_bundle_nonces<Eth, T>(
+    eth_api: &Eth,
+    bundle: &PoolBundle<T>,
+) -> Result<(), Eth::Error>
+where
+    Eth: LoadState,
+    T: SignedTransaction,
+{
+    let state = eth_api.latest_state()?;
+    let mut account_nonces = AddressMap::default();
+    for tx in &bundle.transactions {
+        let sender = tx.signer();
+        if !account_nonces.contains_key(&sender) {
+            let nonce = state.account_nonce(&sender).map_err(Eth::Error::from_eth_err)?;
+            account_nonces.insert(sender, nonce.unwrap_or_default());
+        }
+    }
+    bundle
+        .ensure_nonces(&account_nonces)
+        .map_err(|err| EthApiError::InvalidParams(err.to_string()).into())
+}
+
 /// Container type for `EthBundle` internals
 #[derive(Debug)]
 struct EthBundleInner<Eth> {
@@ -303,4 +460,10 @@ pub enum EthBundleError {
     /// Thrown when the blob gas usage of the blob transactions in a bundle exceed the maximum.
     #[error("blob gas usage exceeds the limit of {0} gas per block.")]
     Eip4844BlobGasExceeded(u64),
+    /// Thrown if a bundle submitted to the bundle pool contains a blob transaction.
+    #[error("blob transactions are not supported in bundles")]
+    BlobTransactionsNotSupported,
+    /// Thrown if a bundle is submitted but the transaction pool has no bundle pool.
+    #[error("bundles are not enabled, see --txpool.enable-bundles")]
+    BundlePoolDisabled,
 }
@@ -0,0 +1,113 @@
+//! `eth_sendRawTransactionConditional` implementation.
+
+use alloy_consensus::BlockHeader;
+use alloy_eips::BlockId;
+use alloy_primitives::{Bytes, B256};
+use jsonrpsee::core::RpcResult;
+use reth_rpc_eth_api::{
+    helpers::{EthTransactions, LoadState, SpawnBlocking},
+    FromEthApiError, L2EthApiExtServer, RpcNodeCore,
+};
+use reth_rpc_eth_types::{utils::recover_raw_transaction, EthApiError};
+use reth_storage_api::{BlockReaderIdExt, StateProviderFactory};
+use reth_transaction_pool::{
+    conditional::{
+        next_block_attributes, validate_conditional, validate_conditional_cost,
+        TransactionConditional, TransactionConditionalError,
+    },
+    AddedTransactionOutcome, PoolPooledTx, PoolTransaction, TransactionOrigin, TransactionPool,
+};
+
+/// `Eth` API for conditional transactions.
+///
+/// Conditional transactions are submitted as [`TransactionOrigin::Private`] transactions, because
+/// peers would not be aware of the conditions.
+#[derive(Debug, Clone)]
+pub struct EthConditional<Eth> {
+    eth_api: Eth,
+}
+
+impl<Eth> EthConditional<Eth> {
+    /// Create a new `EthConditional` instance.
+    pub const fn new(eth_api: Eth) -> Self {
+        Self { eth_api }
+    }
+
+    /// Access the underlying `Eth` API.
+    pub const fn eth_api(&self) -> &Eth {
+        &self.eth_api
+    }
+}
+
+impl<Eth> EthConditional<Eth>
+where
+    Eth: EthTransactions + LoadState + SpawnBlocking + 'static,
+{
+    /// Submits the transaction to the pool, if the conditional is satisfied by the latest state.
+    ///
+    /// The conditional is checked again on every canonical state change and when the transaction
+    /// is picked for a payload.
+    pub async fn send_raw_transaction_conditional(
+        &self,
+        tx: Bytes,
+        conditional: TransactionConditional,
+    ) -> Result<B256, Eth::Error> {
+        validate_conditional_cost(&conditional)
+            .map_err(|err| EthApiError::InvalidParams(err.to_string()))?;
+
+        let recovered = recover_raw_transaction::<PoolPooledTx<Eth::Pool>>(&tx)?;
+        let mut pool_transaction =
+            <Eth::Pool as TransactionPool>::Transaction::from_pooled(recovered);
+
+        let conditional = self
+            .eth_api
+            .spawn_blocking_io(move |this| {
+                let latest = this
+                    .provider()
+                    .latest_header()
+                    .map_err(Eth::Error::from_eth_err)?
+                    .ok_or_else(|| EthApiError::HeaderNotFound(BlockId::latest()))?;
+                let state = this
+                    .provider()
+                    .state_by_block_hash(latest.hash())
+                    .map_err(Eth::Error::from_eth_err)?;
+                let block = next_block_attributes(latest.number(), latest.timestamp());
+                match validate_conditional(&conditional, &block, &*state) {
+                    // the transaction is kept in the pool until the block range is reached
+                    Ok(()) | Err(TransactionConditionalError::NotYetValid) => Ok(conditional),
+                    Err(TransactionConditionalError::Provider(err)) => {
+                        Err(Eth::Error::from_eth_err(err))
+                    }
+                    Err(err) => Err(EthApiError::InvalidParams(err.to_string()).into()),
+                }
+            })
+            .await?;
+
+        pool_transaction.try_set_conditional(conditional).map_err(|_| {
+            EthApiError::InvalidParams("conditional transactions are not supported".to_string())
+        })?;
+
+        let AddedTransactionOutcome { hash, .. } = self
+            .eth_api
+            .pool()
+            .add_transaction(TransactionOrigin::Private, pool_transaction)
+            .await
+            .map_err(EthApiError::from)?;
+
+        Ok(hash)
+    }
+}
+
+#[async_trait::async_trait]
+impl<Eth> L2EthApiExtServer for EthConditional<Eth>
+where
+    Eth: EthTransactions + LoadState + SpawnBlocking + 'static,
+{
+    async fn send_raw_transaction_conditional(
+        &self,
+        bytes: Bytes,
+        condition: TransactionConditional,
+    ) -> RpcResult<B256> {
+        Self::send_raw_transaction_conditional(self, bytes, condition).await.map_err(Into::into)
+    }
+}
@@ -579,7 +579,10 @@ mod tests {
         StageCheckpointReader,
     };
     use reth_rpc_eth_api::{node::RpcNodeCoreAdapter, EthApiServer};
-    use reth_storage_api::{BlockReader, BlockReaderIdExt, StateProviderFactory};
+    use reth_storage_api::{
+        AddressTransactionsReader, BlockReader, BlockReaderIdExt, ChangeSetReader,
+        HashedStateProviderFactory, LogIndexReader, StateProviderFactory,
+    };
     use reth_testing_utils::generators;
     use reth_transaction_pool::test_utils::{testing_pool, TestPool};
 
@@ -599,6 +602,10 @@ mod tests {
             + StateProviderFactory
             + CanonStateSubscriptions<Primitives = reth_ethereum_primitives::EthPrimitives>
             + StageCheckpointReader
+            + AddressTransactionsReader
+            + LogIndexReader
+            + HashedStateProviderFactory
+            + ChangeSetReader
             + Unpin
             + Clone
             + 'static,
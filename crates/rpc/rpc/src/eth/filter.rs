@@ -28,7 +28,8 @@ use reth_rpc_eth_types::{
 };
 use reth_rpc_server_types::{result::rpc_error_with_code, ToRpcResult};
 use reth_storage_api::{
-    BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, HeaderProvider, ProviderBlock,
+    log_index_address_key, log_index_topic_key, log_position_block, BlockHashReader, BlockIdReader,
+    BlockNumReader, BlockReader, HeaderProvider, LogIndexQuery, LogIndexReader, ProviderBlock,
     ProviderReceipt, ReceiptProvider,
 };
 use reth_tasks::Runtime;
@@ -635,8 +636,22 @@ where
             return Err(EthFilterError::InvalidBlockRangeParams)
         }
 
-        if let Some(max_blocks_per_filter) =
-            limits.max_blocks_per_filter.filter(|limit| to_block - from_block > *limit)
+        // Blocks covered by the log index are looked up directly, only the blocks above it are
+        // scanned and count towards the block limit.
+        let query = log_index_query(&filter);
+        let indexed_to = if query.is_empty() {
+            None
+        } else {
+            self.provider()
+                .log_index_block_range()?
+                .filter(|range| range.contains(&from_block))
+                .map(|range| (*range.end()).min(to_block))
+        };
+        let scanned_from = indexed_to.map_or(from_block, |indexed_to| indexed_to + 1);
+
+        if let Some(max_blocks_per_filter) = limits
+            .max_blocks_per_filter
+            .filter(|limit| to_block.saturating_sub(scanned_from) > *limit)
         {
             return Err(EthFilterError::QueryExceedsMaxBlocks(max_blocks_per_filter))
         }
@@ -644,8 +659,10 @@ where
         let (tx, rx) = oneshot::channel();
         let this = self.clone();
         self.task_spawner.spawn_blocking_task(async move {
-            let res =
-                this.get_logs_in_block_range_inner(&filter, from_block, to_block, limits).await;
+            let indexed = indexed_to.map(|indexed_to| (query, indexed_to));
+            let res = this
+                .get_logs_in_block_range_inner(&filter, from_block, to_block, indexed, limits)
+                .await;
             let _ = tx.send(res);
         });
 
@@ -658,24 +675,68 @@ where
     /// ranges and utilizes the rpc cache for optimistically fetching receipts and blocks.
     /// This function is considered blocking and should thus be spawned on a blocking task.
     ///
+    /// If `indexed` is set, the blocks up to the given block number are looked up in the log
+    /// index with the given query instead of being scanned.
+    ///
     /// Returns an error if:
     ///  - underlying database error
+    ///  - amount of matches in the log index exceeds configured limit
     async fn get_logs_in_block_range_inner(
         self: Arc<Self>,
         filter: &Filter,
         from_block: u64,
         to_block: u64,
+        indexed: Option<(LogIndexQuery, u64)>,
         limits: QueryLimits,
     ) -> Result<Vec<Log>, EthFilterError> {
         let mut all_logs = Vec::new();
         let mut matching_headers = Vec::new();
+        let is_multi_block_range = from_block != to_block;
 
         // get current chain tip to determine processing mode
         let chain_tip = self.provider().best_block_number()?;
 
-        // first collect all headers that match the bloom filter for cached mode decision
-        for (from, to) in
-            BlockRangeInclusiveIter::new(from_block..=to_block, self.max_headers_range)
+        let mut scan_from = from_block;
+        if let Some((query, indexed_to)) = indexed {
+            // one extra position is enough to know whether the limit is exceeded
+            let limit = limits
+                .max_logs_per_response
+                .filter(|_| is_multi_block_range)
+                .map_or(usize::MAX, |max_logs| max_logs.saturating_add(1));
+            let positions =
+                self.provider().log_index_positions(&query, from_block..=indexed_to, limit)?;
+
+            if let Some(max_logs_per_response) = limits.max_logs_per_response &&
+                is_multi_block_range &&
+                positions.len() > max_logs_per_response
+            {
+                let to_block = positions.last().copied().map_or(indexed_to, log_position_block);
+                debug!(
+                    target: "rpc::eth::filter",
+                    max_logs_per_response,
+                    from_block,
+                    to_block,
+                    "Log index query exceeded max logs per response limit"
+                );
+                return Err(EthFilterError::QueryExceedsMaxResults {
+                    max_logs: max_logs_per_response,
+                    from_block,
+                    to_block,
+                });
+            }
+
+            for block_number in positions.into_iter().map(log_position_block).dedup() {
+                let header = self
+                    .provider()
+                    .sealed_header(block_number)?
+                    .ok_or_else(|| ProviderError::HeaderNotFound(block_number.into()))?;
+                matching_headers.push(header);
+            }
+            scan_from = indexed_to + 1;
+        }
+
+        // collect all remaining headers that match the bloom filter for cached mode decision
+        for (from, to) in BlockRangeInclusiveIter::new(scan_from..=to_block, self.max_headers_range)
         {
             let headers = self.provider().headers_range(from..=to)?;
 
@@ -732,7 +793,6 @@ where
 
             // size check but only if range is multiple blocks, so we always return all
             // logs of a single block
-            let is_multi_block_range = from_block != to_block;
             if let Some(max_logs_per_response) = limits.max_logs_per_response &&
                 is_multi_block_range &&
                 all_logs.len() > max_logs_per_response
@@ -908,6 +968,23 @@ enum FilterKind<T> {
     PendingTransaction(PendingTransactionKind<T>),
 }
 
+/// Returns the [`LogIndexQuery`] for the address and topics of `filter`.
+///
+/// Every non-empty set of the filter becomes one condition of the query, so a filter without
+/// address and topics results in an empty query.
+fn log_index_query(filter: &Filter) -> LogIndexQuery {
+    let mut keys = Vec::new();
+    if !filter.address.is_empty() {
+        keys.push(filter.address.iter().map(|address| log_index_address_key(*address)).collect());
+    }
+    for (index, topics) in filter.topics.iter().enumerate() {
+        if !topics.is_empty() {
+            keys.push(topics.iter().map(|topic| log_index_topic_key(index, *topic)).collect());
+        }
+    }
+    LogIndexQuery { keys }
+}
+
 /// An iterator that yields _inclusive_ block ranges of a given step size
 #[derive(Debug)]
 struct BlockRangeInclusiveIter {
@@ -1383,6 +1460,24 @@ mod tests {
         assert_eq!(end, *range.end());
     }
 
+    #[test]
+    fn test_log_index_query() {
+        assert!(log_index_query(&Filter::new()).is_empty());
+
+        let address = alloy_primitives::Address::with_last_byte(1);
+        let topic = alloy_primitives::B256::with_last_byte(2);
+        let filter = Filter::new().address(address).topic2(topic);
+        assert_eq!(
+            log_index_query(&filter),
+            LogIndexQuery {
+                keys: vec![
+                    vec![log_index_address_key(address)],
+                    vec![log_index_topic_key(2, topic)]
+                ]
+            }
+        );
+    }
+
     // Helper function to create a test EthApi instance
     #[expect(clippy::type_complexity)]
     fn build_test_eth_api(
@@ -1,18 +1,19 @@
 //! `Eth` Sim bundle implementation and helpers.
 
-use alloy_consensus::{transaction::TxHashRef, BlockHeader};
+use crate::eth::bundle::ensure_bundle_nonces;
+use alloy_consensus::{transaction::TxHashRef, BlockHeader, Typed2718 as _};
 use alloy_eips::BlockNumberOrTag;
 use alloy_evm::{env::BlockEnvironment, overrides::apply_block_overrides};
 use alloy_primitives::U256;
 use alloy_rpc_types_eth::{BlockId, Log};
 use alloy_rpc_types_mev::{
-    BundleItem, Inclusion, MevSendBundle, Privacy, RefundConfig, SimBundleLogs, SimBundleOverrides,
-    SimBundleResponse, Validity,
+    BundleItem, EthBundleHash, Inclusion, MevSendBundle, Privacy, RefundConfig, SimBundleLogs,
+    SimBundleOverrides, SimBundleResponse, Validity,
 };
 use jsonrpsee::core::RpcResult;
 use reth_evm::{ConfigureEvm, Evm};
 use reth_primitives_traits::Recovered;
-use reth_rpc_api::MevSimApiServer;
+use reth_rpc_api::{MevFullApiServer, MevSimApiServer};
 use reth_rpc_eth_api::{
     helpers::{block::LoadBlock, Call, EthTransactions},
     FromEthApiError, FromEvmError,
@@ -20,7 +21,7 @@ use reth_rpc_eth_api::{
 use reth_rpc_eth_types::{utils::recover_raw_transaction, EthApiError};
 use reth_storage_api::ProviderTx;
 use reth_tasks::pool::BlockingTaskGuard;
-use reth_transaction_pool::{PoolPooledTx, PoolTransaction, TransactionPool};
+use reth_transaction_pool::{bundle::PoolBundle, PoolPooledTx, PoolTransaction, TransactionPool};
 use revm::{
     context::Block, context_interface::result::ResultAndState, DatabaseCommit, DatabaseRef,
 };
@@ -277,6 +278,43 @@ where
         Ok(items)
     }
 
+    /// Adds a bundle to the bundle pool of the transaction pool.
+    ///
+    /// Nested bundles are flattened into a single bundle that targets the inclusion range of the
+    /// outer bundle.
+    pub fn send_bundle(&self, request: MevSendBundle) -> Result<EthBundleHash, Eth::Error> {
+        let Some(bundle_pool) = self.eth_api().pool().bundle_pool() else {
+            return Err(EthApiError::InvalidParams(
+                EthSimBundleError::BundlePoolDisabled.to_string(),
+            )
+            .into())
+        };
+
+        let items = self.parse_and_flatten_bundle(&request)?;
+        if items.iter().any(|item| item.tx.is_eip4844()) {
+            return Err(
+                EthApiError::InvalidParams(EthSimBundleError::InvalidBundle.to_string()).into()
+            )
+        }
+
+        let block_number = request.inclusion.block_number();
+        let mut bundle = PoolBundle::new(Vec::with_capacity(items.len()), block_number);
+        bundle.max_block_number = request.inclusion.max_block_number().unwrap_or(block_number);
+        for item in items {
+            if item.can_revert {
+                bundle.reverting_tx_hashes.insert(*item.tx.tx_hash());
+            }
+            bundle.transactions.push(item.tx);
+        }
+        ensure_bundle_nonces(self.eth_api(), &bundle)?;
+
+        let bundle_hash = bundle_pool
+            .add_bundle(bundle)
+            .map_err(|err| EthApiError::InvalidParams(err.to_string()))?;
+
+        Ok(EthBundleHash { bundle_hash })
+    }
+
     async fn sim_bundle_inner(
         &self,
         request: MevSendBundle,
@@ -500,6 +538,26 @@ where
     }
 }
 
+#[async_trait::async_trait]
+impl<Eth> MevFullApiServer for EthSimBundle<Eth>
+where
+    Eth: EthTransactions + LoadBlock + Call + 'static,
+{
+    async fn send_bundle(&self, request: MevSendBundle) -> RpcResult<EthBundleHash> {
+        trace!("mev_sendBundle called, request: {:?}", request);
+
+        Self::send_bundle(self, request).map_err(Into::into)
+    }
+
+    async fn sim_bundle(
+        &self,
+        request: MevSendBundle,
+        overrides: SimBundleOverrides,
+    ) -> RpcResult<SimBundleResponse> {
+        MevSimApiServer::sim_bundle(self, request, overrides).await
+    }
+}
+
 /// Container type for `EthSimBundle` internals
 #[derive(Debug)]
 struct EthSimBundleInner<Eth> {
@@ -552,6 +610,9 @@ pub enum EthSimBundleError {
     /// Thrown when a bundle simulation returns negative profit
     #[error("bundle simulation returned negative profit")]
     NegativeProfit,
+    /// Thrown if a bundle is submitted but the transaction pool has no bundle pool.
+    #[error("bundles are not enabled, see --txpool.enable-bundles")]
+    BundlePoolDisabled,
 }
 
 #[cfg(test)]
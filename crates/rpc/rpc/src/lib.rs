@@ -27,6 +27,7 @@
 
 mod admin;
 mod aliases;
+mod anvil;
 mod debug;
 mod engine;
 pub mod eth;
@@ -43,13 +44,16 @@ mod web3;
 
 pub use admin::AdminApi;
 pub use aliases::*;
+pub use anvil::AnvilApi;
 pub use debug::DebugApi;
 pub use engine::{EngineApi, EngineEthApi};
-pub use eth::{helpers::SyncListener, EthApi, EthApiBuilder, EthBundle, EthFilter, EthPubSub};
+pub use eth::{
+    helpers::SyncListener, EthApi, EthApiBuilder, EthBundle, EthConditional, EthFilter, EthPubSub,
+};
 pub use miner::MinerApi;
 pub use net::NetApi;
 pub use otterscan::OtterscanApi;
-pub use reth::RethApi;
+pub use reth::{RethApi, RethFeesApi};
 pub use reth_rpc_convert::RpcTypes;
 pub use rpc::RPCApi;
 pub use testing::TestingApi;
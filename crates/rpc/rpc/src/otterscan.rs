@@ -1,7 +1,7 @@
 use alloy_consensus::{BlockHeader, Typed2718};
-use alloy_eips::{eip1898::LenientBlockNumberOrTag, BlockId};
+use alloy_eips::{eip1898::LenientBlockNumberOrTag, BlockId, BlockNumberOrTag};
 use alloy_network::{ReceiptResponse, TransactionResponse};
-use alloy_primitives::{Address, Bytes, TxHash, B256, U256};
+use alloy_primitives::{Address, BlockNumber, Bytes, TxHash, TxNumber, B256, U256};
 use alloy_rpc_types_eth::{BlockTransactions, TransactionReceipt};
 use alloy_rpc_types_trace::{
     otterscan::{
@@ -12,20 +12,23 @@ use alloy_rpc_types_trace::{
 };
 use async_trait::async_trait;
 use jsonrpsee::{core::RpcResult, types::ErrorObjectOwned};
+use reth_errors::{ProviderError, ProviderResult};
 use reth_primitives_traits::TxTy;
 use reth_rpc_api::{EthApiServer, OtterscanServer};
 use reth_rpc_convert::RpcTxReq;
 use reth_rpc_eth_api::{
     helpers::{EthTransactions, TraceExt},
-    FullEthApiTypes, RpcBlock, RpcHeader, RpcReceipt, RpcTransaction,
+    FromEthApiError, FullEthApiTypes, RpcBlock, RpcHeader, RpcReceipt, RpcTransaction,
 };
 use reth_rpc_eth_types::{utils::binary_search, EthApiError};
-use reth_rpc_server_types::result::internal_rpc_err;
+use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
+use reth_storage_api::{AddressTransactionsReader, BlockIdReader, BlockReader};
 use revm::context_interface::result::ExecutionResult;
 use revm_inspectors::{
     tracing::{types::CallTraceNode, TracingInspectorConfig},
     transfer::{TransferInspector, TransferKind},
 };
+use std::ops::RangeInclusive;
 
 const API_LEVEL: u64 = 8;
 
@@ -64,6 +67,60 @@ where
     }
 }
 
+impl<Eth> OtterscanApi<Eth>
+where
+    Eth: EthApiServer<
+            RpcTxReq<Eth::NetworkTypes>,
+            RpcTransaction<Eth::NetworkTypes>,
+            RpcBlock<Eth::NetworkTypes>,
+            RpcReceipt<Eth::NetworkTypes>,
+            RpcHeader<Eth::NetworkTypes>,
+            TxTy<Eth::Primitives>,
+        > + FullEthApiTypes,
+{
+    /// Fetches the transactions and receipts of a search page.
+    async fn transactions_with_receipts(
+        &self,
+        page: AddressTransactionsPage,
+    ) -> RpcResult<TransactionsWithReceipts<RpcTransaction<Eth::NetworkTypes>>> {
+        let mut txs = Vec::new();
+        let mut receipts = Vec::new();
+        for (block_number, indices) in page.blocks {
+            let block_id = block_number.into();
+            let block = self.eth.block_by_number(block_number.into(), true);
+            let block_receipts = self.eth.block_receipts(block_id);
+            let (block, block_receipts) = futures::try_join!(block, block_receipts)?;
+
+            let block = block.ok_or(EthApiError::HeaderNotFound(block_id))?;
+            let mut block_receipts =
+                block_receipts.ok_or(EthApiError::ReceiptsNotFound(block_id))?;
+            let timestamp = Some(block.header.timestamp());
+            let BlockTransactions::Full(mut transactions) = block.transactions else {
+                return Err(internal_rpc_err("block is not full"));
+            };
+
+            // Indices are in descending order, so each one is the last remaining item once the
+            // higher ones are cut off.
+            for index in indices {
+                transactions.truncate(index + 1);
+                block_receipts.truncate(index + 1);
+                let (Some(tx), Some(receipt)) = (transactions.pop(), block_receipts.pop()) else {
+                    return Err(internal_rpc_err("transaction index out of bounds"));
+                };
+                receipts.push(ots_transaction_receipt(receipt, tx.ty(), timestamp));
+                txs.push(tx);
+            }
+        }
+
+        Ok(TransactionsWithReceipts {
+            txs,
+            receipts,
+            first_page: page.first_page,
+            last_page: page.last_page,
+        })
+    }
+}
+
 #[async_trait]
 impl<Eth> OtterscanServer<RpcTransaction<Eth::NetworkTypes>, RpcHeader<Eth::NetworkTypes>>
     for OtterscanApi<Eth>
@@ -247,32 +304,7 @@ where
         let receipts = receipts
             .drain(page)
             .zip(transactions.iter().map(Typed2718::ty))
-            .map(|(receipt, tx_ty)| {
-                let inner = OtsReceipt {
-                    status: receipt.status(),
-                    cumulative_gas_used: receipt.cumulative_gas_used(),
-                    logs: None,
-                    logs_bloom: None,
-                    r#type: tx_ty,
-                };
-
-                let receipt = TransactionReceipt {
-                    inner,
-                    transaction_hash: receipt.transaction_hash(),
-                    transaction_index: receipt.transaction_index(),
-                    block_hash: receipt.block_hash(),
-                    block_number: receipt.block_number(),
-                    gas_used: receipt.gas_used(),
-                    effective_gas_price: receipt.effective_gas_price(),
-                    blob_gas_used: receipt.blob_gas_used(),
-                    blob_gas_price: receipt.blob_gas_price(),
-                    from: receipt.from(),
-                    to: receipt.to(),
-                    contract_address: receipt.contract_address(),
-                };
-
-                OtsTransactionReceipt { receipt, timestamp }
-            })
+            .map(|(receipt, tx_ty)| ots_transaction_receipt(receipt, tx_ty, timestamp))
             .collect();
 
         // use `transaction_count` to indicate the paginate information
@@ -284,21 +316,70 @@ where
     /// Handler for `ots_searchTransactionsBefore`
     async fn search_transactions_before(
         &self,
-        _address: Address,
-        _block_number: LenientBlockNumberOrTag,
-        _page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        address: Address,
+        block_number: LenientBlockNumberOrTag,
+        page_size: usize,
+    ) -> RpcResult<TransactionsWithReceipts<RpcTransaction<Eth::NetworkTypes>>> {
+        if page_size == 0 {
+            return Err(invalid_params_rpc_err("page size must be greater than zero"))
+        }
+        let block_number = block_number.into_inner();
+        let page = self
+            .eth
+            .spawn_blocking_io(move |this| {
+                let indexed_from =
+                    address_transactions_indexed_from::<_, Eth::Error>(this.provider())?;
+                let block_number = match block_number {
+                    // Searching before the latest block starts from the newest transactions
+                    BlockNumberOrTag::Latest | BlockNumberOrTag::Pending => 0,
+                    tag => resolve_block_number::<_, Eth::Error>(this.provider(), tag)?,
+                };
+                let page = address_transactions_before(
+                    this.provider(),
+                    address,
+                    block_number,
+                    indexed_from,
+                    page_size,
+                )
+                .map_err(Eth::Error::from_eth_err)?;
+                // nothing left to return, but older transactions may not have been indexed
+                if page.blocks.is_empty() && !page.last_page {
+                    return Err(Eth::Error::from_eth_err(not_indexed_error(indexed_from)))
+                }
+                Ok(page)
+            })
+            .await
+            .map_err(Into::into)?;
+        self.transactions_with_receipts(page).await
     }
 
     /// Handler for `ots_searchTransactionsAfter`
     async fn search_transactions_after(
         &self,
-        _address: Address,
-        _block_number: LenientBlockNumberOrTag,
-        _page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        address: Address,
+        block_number: LenientBlockNumberOrTag,
+        page_size: usize,
+    ) -> RpcResult<TransactionsWithReceipts<RpcTransaction<Eth::NetworkTypes>>> {
+        if page_size == 0 {
+            return Err(invalid_params_rpc_err("page size must be greater than zero"))
+        }
+        let block_number = block_number.into_inner();
+        let page = self
+            .eth
+            .spawn_blocking_io(move |this| {
+                let indexed_from =
+                    address_transactions_indexed_from::<_, Eth::Error>(this.provider())?;
+                let block_number =
+                    resolve_block_number::<_, Eth::Error>(this.provider(), block_number)?;
+                if block_number + 1 < indexed_from {
+                    return Err(Eth::Error::from_eth_err(not_indexed_error(indexed_from)))
+                }
+                address_transactions_after(this.provider(), address, block_number, page_size)
+                    .map_err(Eth::Error::from_eth_err)
+            })
+            .await
+            .map_err(Into::into)?;
+        self.transactions_with_receipts(page).await
     }
 
     /// Handler for `ots_getTransactionBySenderAndNonce`
@@ -383,6 +464,207 @@ where
     }
 }
 
+/// A page of the transactions touching an address.
+#[derive(Debug)]
+struct AddressTransactionsPage {
+    /// Block numbers with the indices of the transactions in each block, both in descending
+    /// order.
+    blocks: Vec<(BlockNumber, Vec<usize>)>,
+    /// Whether there are no newer transactions.
+    first_page: bool,
+    /// Whether there are no older transactions.
+    last_page: bool,
+}
+
+/// Resolves `block` to the number of the block it refers to.
+fn resolve_block_number<Provider, E>(provider: &Provider, block: BlockNumberOrTag) -> Result<u64, E>
+where
+    Provider: BlockIdReader,
+    E: FromEthApiError,
+{
+    provider
+        .convert_block_number(block)
+        .map_err(E::from_eth_err)?
+        .ok_or_else(|| E::from_eth_err(EthApiError::HeaderNotFound(block.into())))
+}
+
+/// Returns the first block of the address transactions index.
+fn address_transactions_indexed_from<Provider, E>(provider: &Provider) -> Result<BlockNumber, E>
+where
+    Provider: AddressTransactionsReader,
+    E: FromEthApiError,
+{
+    provider
+        .address_transactions_block_range()
+        .map_err(E::from_eth_err)?
+        .map(|range| *range.start())
+        .ok_or_else(|| {
+            E::from_eth_err(EthApiError::Unsupported("address transactions index is not available"))
+        })
+}
+
+/// Returns the error for a search reaching below the first indexed block.
+fn not_indexed_error(indexed_from: BlockNumber) -> EthApiError {
+    EthApiError::InvalidParams(format!("transactions before block {indexed_from} are not indexed"))
+}
+
+/// Returns the newest `page_size` transactions touching `address` in the blocks before
+/// `block_number`, or in all indexed blocks if it's zero.
+///
+/// The block of the oldest returned transaction is always included in full, so a page may hold
+/// more than `page_size` transactions but a block never spans two pages. The page is only the
+/// last one if the index starts at genesis, see [`index_covers_genesis`].
+fn address_transactions_before<Provider>(
+    provider: &Provider,
+    address: Address,
+    block_number: BlockNumber,
+    indexed_from: BlockNumber,
+    page_size: usize,
+) -> ProviderResult<AddressTransactionsPage>
+where
+    Provider: AddressTransactionsReader + BlockReader,
+{
+    let complete = index_covers_genesis(indexed_from);
+    let first_page = block_number == 0;
+    let before = if first_page {
+        TxNumber::MAX
+    } else {
+        provider
+            .block_body_indices(block_number)?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(block_number))?
+            .first_tx_num()
+    };
+
+    let mut txs = provider.address_transactions_before(address, before, page_size)?;
+    let Some(&oldest) = txs.last() else {
+        return Ok(AddressTransactionsPage { blocks: Vec::new(), first_page, last_page: complete })
+    };
+
+    let (_, block_txs) = block_transactions_of(provider, oldest)?;
+    let first_tx = *block_txs.start();
+    txs.extend(
+        provider
+            .address_transactions_before(address, oldest, (oldest - first_tx) as usize)?
+            .into_iter()
+            .filter(|tx| *tx >= first_tx),
+    );
+    let last_page =
+        complete && provider.address_transactions_before(address, first_tx, 1)?.is_empty();
+
+    Ok(AddressTransactionsPage { blocks: group_by_block(provider, txs)?, first_page, last_page })
+}
+
+/// Returns the oldest `page_size` transactions touching `address` in the blocks after
+/// `block_number`, newest first.
+///
+/// The block of the newest returned transaction is always included in full, so a page may hold
+/// more than `page_size` transactions but a block never spans two pages.
+fn address_transactions_after<Provider>(
+    provider: &Provider,
+    address: Address,
+    block_number: BlockNumber,
+    page_size: usize,
+) -> ProviderResult<AddressTransactionsPage>
+where
+    Provider: AddressTransactionsReader + BlockReader,
+{
+    let last_page = block_number == 0;
+    let after = provider
+        .block_body_indices(block_number)?
+        .ok_or(ProviderError::BlockBodyIndicesNotFound(block_number))?
+        .next_tx_num();
+
+    let mut txs = provider.address_transactions_after(address, after, page_size)?;
+    let Some(&newest) = txs.last() else {
+        return Ok(AddressTransactionsPage { blocks: Vec::new(), first_page: true, last_page })
+    };
+
+    let (_, block_txs) = block_transactions_of(provider, newest)?;
+    let last_tx = *block_txs.end();
+    txs.extend(
+        provider
+            .address_transactions_after(address, newest + 1, (last_tx - newest) as usize)?
+            .into_iter()
+            .filter(|tx| *tx <= last_tx),
+    );
+    let first_page = provider.address_transactions_after(address, last_tx + 1, 1)?.is_empty();
+
+    txs.reverse();
+    Ok(AddressTransactionsPage { blocks: group_by_block(provider, txs)?, first_page, last_page })
+}
+
+/// Returns `true` if an index starting at `indexed_from` covers all transactions, since the
+/// genesis block has none.
+const fn index_covers_genesis(indexed_from: BlockNumber) -> bool {
+    indexed_from <= 1
+}
+
+/// Returns the number and the range of transaction numbers of the block containing the given
+/// transaction.
+fn block_transactions_of<Provider: BlockReader>(
+    provider: &Provider,
+    tx_number: TxNumber,
+) -> ProviderResult<(BlockNumber, RangeInclusive<TxNumber>)> {
+    let block_number = provider
+        .block_by_transaction_id(tx_number)?
+        .ok_or(ProviderError::TransactionNotFound(tx_number.into()))?;
+    let body = provider
+        .block_body_indices(block_number)?
+        .ok_or(ProviderError::BlockBodyIndicesNotFound(block_number))?;
+    Ok((block_number, body.first_tx_num()..=body.last_tx_num()))
+}
+
+/// Groups transaction numbers in descending order by block, converting them to indices within
+/// their block.
+fn group_by_block<Provider: BlockReader>(
+    provider: &Provider,
+    txs: Vec<TxNumber>,
+) -> ProviderResult<Vec<(BlockNumber, Vec<usize>)>> {
+    let mut blocks = Vec::<(BlockNumber, Vec<usize>)>::new();
+    let mut first_tx = TxNumber::MAX;
+    for tx in txs {
+        if tx < first_tx {
+            let (block_number, block_txs) = block_transactions_of(provider, tx)?;
+            first_tx = *block_txs.start();
+            blocks.push((block_number, Vec::new()));
+        }
+        blocks.last_mut().expect("block was pushed").1.push((tx - first_tx) as usize);
+    }
+    Ok(blocks)
+}
+
+/// Converts an RPC receipt into the reduced receipt returned by Otterscan methods.
+fn ots_transaction_receipt<R: ReceiptResponse>(
+    receipt: R,
+    tx_ty: u8,
+    timestamp: Option<u64>,
+) -> OtsTransactionReceipt {
+    let inner = OtsReceipt {
+        status: receipt.status(),
+        cumulative_gas_used: receipt.cumulative_gas_used(),
+        logs: None,
+        logs_bloom: None,
+        r#type: tx_ty,
+    };
+
+    let receipt = TransactionReceipt {
+        inner,
+        transaction_hash: receipt.transaction_hash(),
+        transaction_index: receipt.transaction_index(),
+        block_hash: receipt.block_hash(),
+        block_number: receipt.block_number(),
+        gas_used: receipt.gas_used(),
+        effective_gas_price: receipt.effective_gas_price(),
+        blob_gas_used: receipt.blob_gas_used(),
+        blob_gas_price: receipt.blob_gas_price(),
+        from: receipt.from(),
+        to: receipt.to(),
+        contract_address: receipt.contract_address(),
+    };
+
+    OtsTransactionReceipt { receipt, timestamp }
+}
+
 /// Returns the transaction slice for an Otterscan block page.
 ///
 /// Otterscan paginates in block order, so page `0` corresponds to the first transactions in the
@@ -399,7 +681,14 @@ fn block_transaction_page_range(
 
 #[cfg(test)]
 mod tests {
-    use super::block_transaction_page_range;
+    use super::*;
+    use reth_db_api::{
+        models::{ShardedKey, StoredBlockBodyIndices},
+        tables,
+        transaction::DbTxMut,
+        BlockNumberList,
+    };
+    use reth_provider::test_utils::create_test_provider_factory;
 
     #[test]
     fn block_transaction_page_range_returns_first_pages_in_block_order() {
@@ -412,4 +701,83 @@ mod tests {
         assert_eq!(block_transaction_page_range(25, 2, 10), 20..25);
         assert_eq!(block_transaction_page_range(25, 3, 10), 25..25);
     }
+
+    #[test]
+    fn address_transactions_pages_never_split_blocks() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let address = Address::with_last_byte(1);
+
+        // Five blocks with two transactions each, the address is touched by transactions 1, 2, 3,
+        // 6 and 9.
+        for block in 0..5u64 {
+            let body = StoredBlockBodyIndices { first_tx_num: block * 2, tx_count: 2 };
+            provider.tx_ref().put::<tables::BlockBodyIndices>(block, body).unwrap();
+            provider.tx_ref().put::<tables::TransactionBlocks>(block * 2 + 1, block).unwrap();
+        }
+        provider
+            .tx_ref()
+            .put::<tables::AddressTransactions>(
+                ShardedKey::last(address),
+                BlockNumberList::new_pre_sorted([1, 2, 3, 6, 9]),
+            )
+            .unwrap();
+
+        let page = address_transactions_before(&*provider, address, 0, 0, 2).unwrap();
+        assert_eq!(page.blocks, vec![(4, vec![1]), (3, vec![0])]);
+        assert!(page.first_page);
+        assert!(!page.last_page);
+
+        let page = address_transactions_before(&*provider, address, 3, 0, 1).unwrap();
+        assert_eq!(page.blocks, vec![(1, vec![1, 0])]);
+        assert!(!page.first_page);
+        assert!(!page.last_page);
+
+        let page = address_transactions_after(&*provider, address, 1, 1).unwrap();
+        assert_eq!(page.blocks, vec![(3, vec![0])]);
+        assert!(!page.first_page);
+        assert!(!page.last_page);
+
+        let page = address_transactions_after(&*provider, address, 0, 5).unwrap();
+        assert_eq!(page.blocks, vec![(4, vec![1]), (3, vec![0]), (1, vec![1, 0])]);
+        assert!(page.first_page);
+        assert!(page.last_page);
+
+        let page = address_transactions_before(&*provider, address, 2, 0, 5).unwrap();
+        assert_eq!(page.blocks, vec![(1, vec![1, 0]), (0, vec![1])]);
+        assert!(page.last_page);
+    }
+
+    #[test]
+    fn address_transactions_pages_are_incomplete_below_index_start() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let address = Address::with_last_byte(1);
+
+        // Three blocks with one transaction each touching the address, indexed from block 1.
+        for block in 0..3u64 {
+            let body = StoredBlockBodyIndices { first_tx_num: block, tx_count: 1 };
+            provider.tx_ref().put::<tables::BlockBodyIndices>(block, body).unwrap();
+            provider.tx_ref().put::<tables::TransactionBlocks>(block, block).unwrap();
+        }
+        provider
+            .tx_ref()
+            .put::<tables::AddressTransactions>(
+                ShardedKey::last(address),
+                BlockNumberList::new_pre_sorted([1, 2]),
+            )
+            .unwrap();
+
+        let page = address_transactions_before(&*provider, address, 0, 1, 5).unwrap();
+        assert_eq!(page.blocks, vec![(2, vec![0]), (1, vec![0])]);
+        assert!(page.last_page);
+
+        let page = address_transactions_before(&*provider, address, 0, 2, 5).unwrap();
+        assert_eq!(page.blocks, vec![(2, vec![0]), (1, vec![0])]);
+        assert!(!page.last_page);
+
+        let page = address_transactions_before(&*provider, address, 1, 2, 5).unwrap();
+        assert!(page.blocks.is_empty());
+        assert!(!page.last_page);
+    }
 }
@@ -14,8 +14,9 @@ use reth_errors::RethResult;
 use reth_evm::{execute::Executor, ConfigureEvm};
 use reth_execution_types::ExecutionOutcome;
 use reth_primitives_traits::{NodePrimitives, SealedHeader};
-use reth_rpc_api::RethApiServer;
-use reth_rpc_eth_types::{EthApiError, EthResult};
+use reth_rpc_api::{RethApiServer, RethFeesApiServer};
+use reth_rpc_eth_api::helpers::EthFees;
+use reth_rpc_eth_types::{EthApiError, EthResult, FeeEstimates};
 use reth_storage_api::{
     BlockReader, BlockReaderIdExt, ChangeSetReader, StateProviderFactory, TransactionVariant,
 };
@@ -383,3 +384,29 @@ struct RethApiInner<Provider, EvmConfig> {
     /// The type that can spawn tasks which would otherwise block.
     task_spawner: Runtime,
 }
+
+/// `reth` fee estimation API implementation.
+///
+/// Serves `reth_feeEstimates` from the fee history and the transaction pool of the `eth` API.
+#[derive(Debug, Clone)]
+pub struct RethFeesApi<Eth> {
+    eth_api: Eth,
+}
+
+impl<Eth> RethFeesApi<Eth> {
+    /// Create a new instance of the [`RethFeesApi`]
+    pub const fn new(eth_api: Eth) -> Self {
+        Self { eth_api }
+    }
+}
+
+#[async_trait]
+impl<Eth> RethFeesApiServer for RethFeesApi<Eth>
+where
+    Eth: EthFees + 'static,
+{
+    /// Handler for `reth_feeEstimates`
+    async fn reth_fee_estimates(&self) -> RpcResult<FeeEstimates> {
+        self.eth_api.fee_estimates().await.map_err(Into::into)
+    }
+}
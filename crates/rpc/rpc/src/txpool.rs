@@ -2,17 +2,19 @@ use core::fmt;
 use std::collections::BTreeMap;
 
 use alloy_consensus::Transaction;
-use alloy_primitives::Address;
+use alloy_primitives::{Address, TxHash};
 use alloy_rpc_types_txpool::{
     TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolInspectSummary, TxpoolStatus,
 };
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use reth_primitives_traits::NodePrimitives;
-use reth_rpc_api::TxPoolApiServer;
+use reth_rpc_api::{TxPoolApiServer, TxpoolHistoryEntry};
 use reth_rpc_convert::{RpcConvert, RpcTypes};
 use reth_rpc_eth_api::RpcTransaction;
+use reth_rpc_server_types::result::internal_rpc_err;
 use reth_transaction_pool::{
+    history::{TransactionHistory, TransactionHistoryEntry},
     AllPoolTransactions, PoolConsensusTx, PoolTransaction, TransactionPool,
 };
 use tracing::trace;
@@ -73,6 +75,24 @@ where
 
         Ok(content)
     }
+
+    fn history(&self) -> RpcResult<&TransactionHistory> {
+        self.pool.transaction_history().ok_or_else(|| {
+            internal_rpc_err("transaction history is not enabled, see --txpool.history")
+        })
+    }
+}
+
+/// Converts a recorded pool event into its rpc representation.
+fn history_entry(entry: TransactionHistoryEntry) -> TxpoolHistoryEntry {
+    let TransactionHistoryEntry { timestamp, hash, sender, event } = entry;
+    TxpoolHistoryEntry {
+        timestamp,
+        hash,
+        sender,
+        event: event.name().to_string(),
+        reason: event.reason(),
+    }
 }
 
 #[async_trait]
@@ -147,6 +167,18 @@ where
         trace!(target: "rpc::eth", "Serving txpool_content");
         Ok(self.content().map_err(Into::into)?)
     }
+
+    /// Handler for `txpool_history`
+    async fn txpool_history(&self, hash: TxHash) -> RpcResult<Vec<TxpoolHistoryEntry>> {
+        trace!(target: "rpc::eth", ?hash, "Serving txpool_history");
+        Ok(self.history()?.transaction_history(&hash).into_iter().map(history_entry).collect())
+    }
+
+    /// Handler for `txpool_senderHistory`
+    async fn txpool_sender_history(&self, address: Address) -> RpcResult<Vec<TxpoolHistoryEntry>> {
+        trace!(target: "rpc::eth", ?address, "Serving txpool_senderHistory");
+        Ok(self.history()?.sender_history(&address).into_iter().map(history_entry).collect())
+    }
 }
 
 impl<Pool, Eth> fmt::Debug for TxPoolApi<Pool, Eth> {
@@ -1,11 +1,10 @@
 use crate::{StageCheckpoint, StageId};
-use alloy_primitives::{BlockHash, BlockNumber};
+use alloy_primitives::{BlockHash, BlockNumber, B256};
 use futures_util::{Stream, StreamExt};
 use reqwest::{Client, Url};
 use reth_config::config::EtlConfig;
 use reth_db_api::{table::Value, transaction::DbTxMut};
-use reth_era::{common::file_ops::StreamReader, era1::file::Era1Reader};
-use reth_era_downloader::{read_dir, EraClient, EraMeta, EraStream, EraStreamConfig};
+use reth_era_downloader::{read_dir, EraClient, EraMeta, EraStream, EraStreamConfig, MergeBlock};
 use reth_era_utils as era;
 use reth_etl::Collector;
 use reth_primitives_traits::{FullBlockBody, FullBlockHeader, NodePrimitives};
@@ -19,6 +18,7 @@ use std::{
     fmt::{Debug, Formatter},
     iter,
     path::Path,
+    sync::Arc,
     task::{ready, Context, Poll},
 };
 
@@ -28,9 +28,11 @@ type ThreadSafeEraStream<Header, Body> =
     Box<dyn Stream<Item = eyre::Result<Item<Header, Body>>> + Send + Sync + Unpin>;
 
 /// The [ERA1](https://github.com/eth-clients/e2store-format-specs/blob/main/formats/era1.md)
-/// pre-merge history stage.
+/// and [ERA](https://github.com/eth-clients/e2store-format-specs/blob/main/formats/era.md)
+/// history stage.
 ///
-/// Imports block headers and bodies from genesis up to the last pre-merge block. Receipts are
+/// Imports block headers and bodies from genesis up to the last pre-merge block from era1 files,
+/// and past the merge from the execution payloads of the beacon blocks in era files. Receipts are
 /// generated by execution. Execution is not done in this stage.
 pub struct EraStage<Header, Body, StreamFactory> {
     /// The `source` creates `stream`.
@@ -54,18 +56,31 @@ where
     Body: FullBlockBody<OmmerHeader = Header>,
 {
     fn create(self, input: ExecInput) -> Result<ThreadSafeEraStream<Header, Body>, StageError> {
-        match self {
-            Self::Path(path) => Self::convert(
+        let historical_roots: Arc<[B256]> = match &self.historical_roots {
+            Some(path) => {
+                era::read_historical_roots(path).map_err(|e| StageError::Fatal(e.into()))?.into()
+            }
+            None => Arc::new([]),
+        };
+
+        match self.location {
+            EraImportLocation::Path(path) => Self::convert(
                 read_dir(path, input.next_block()).map_err(|e| StageError::Fatal(e.into()))?,
+                historical_roots,
             ),
-            Self::Url(url, folder) => {
+            EraImportLocation::Url(url, folder) => {
                 let _ = reth_fs_util::create_dir_all(&folder);
                 let client = EraClient::new(Client::new(), url, folder);
 
-                Self::convert(EraStream::new(
-                    client,
-                    EraStreamConfig::default().start_from(input.next_block()),
-                ))
+                Self::convert(
+                    EraStream::new(
+                        client,
+                        EraStreamConfig::default()
+                            .start_from(input.next_block())
+                            .with_merge(self.merge),
+                    ),
+                    historical_roots,
+                )
             }
         }
     }
@@ -78,17 +93,15 @@ impl EraImportSource {
             + Sync
             + 'static
             + Unpin,
+        historical_roots: Arc<[B256]>,
     ) -> Result<ThreadSafeEraStream<Header, Body>, StageError>
     where
         Header: FullBlockHeader + Value,
         Body: FullBlockBody<OmmerHeader = Header>,
     {
-        Ok(Box::new(Box::pin(stream.map(|meta| {
+        Ok(Box::new(Box::pin(stream.map(move |meta| {
             meta.and_then(|meta| {
-                let file = reth_fs_util::open(meta.path())?;
-                let reader = Era1Reader::new(file);
-                let iter = reader.iter();
-                let iter = iter.map(era::decode);
+                let iter = era::open_blocks(&meta, &historical_roots)?;
                 let iter = iter.chain(
                     iter::once_with(move || match meta.mark_as_processed() {
                         Ok(..) => None,
@@ -236,9 +249,21 @@ where
     }
 }
 
+/// Describes where to get the era files from and what to verify them against.
+#[derive(Debug, Clone)]
+pub struct EraImportSource {
+    /// Where to get the era files from.
+    location: EraImportLocation,
+    /// Path to a file with the known historical roots of the beacon chain, see
+    /// [`era::read_historical_roots`].
+    historical_roots: Option<Box<Path>>,
+    /// The first post-merge block of the chain, used to skip the ERA files before it.
+    merge: Option<MergeBlock>,
+}
+
 /// Describes where to get the era files from.
 #[derive(Debug, Clone)]
-pub enum EraImportSource {
+pub enum EraImportLocation {
     /// Remote HTTP accessible host.
     Url(Url, Box<Path>),
     /// Local directory.
@@ -263,7 +288,27 @@ impl EraImportSource {
         default: impl FnOnce() -> Option<Url>,
         folder: impl FnOnce() -> Box<Path>,
     ) -> Option<Self> {
-        path.map(Self::Path).or_else(|| url.or_else(default).map(|url| Self::Url(url, folder())))
+        path.map(EraImportLocation::Path)
+            .or_else(|| url.or_else(default).map(|url| EraImportLocation::Url(url, folder())))
+            .map(|location| Self { location, historical_roots: None, merge: None })
+    }
+
+    /// Sets the path to a file with the known historical roots of the beacon chain.
+    ///
+    /// Era files are verified against these roots, so importing blocks past the merge fails
+    /// without them.
+    pub fn with_historical_roots(mut self, historical_roots: Option<Box<Path>>) -> Self {
+        self.historical_roots = historical_roots;
+        self
+    }
+
+    /// Sets the first post-merge block of the chain.
+    ///
+    /// Remote ERA files are then downloaded starting at the one holding the next block to import,
+    /// see [`EraStreamConfig::with_merge`].
+    pub const fn with_merge(mut self, merge: Option<MergeBlock>) -> Self {
+        self.merge = merge;
+        self
     }
 }
 
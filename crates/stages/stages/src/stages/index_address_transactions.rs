@@ -0,0 +1,373 @@
+use super::load_sharded_indices;
+use alloy_consensus::TxReceipt;
+use alloy_primitives::{map::AddressMap, Address, TxNumber};
+use reth_config::config::{EtlConfig, IndexHistoryConfig};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW},
+    models::ShardedKey,
+    tables,
+    transaction::DbTxMut,
+    BlockNumberList,
+};
+use reth_etl::Collector;
+use reth_primitives_traits::SignerRecoverable;
+use reth_provider::{
+    touched_addresses, BlockReader, DBProvider, MetadataProvider, MetadataWriter,
+    PruneCheckpointReader, PruneCheckpointWriter, StaticFileProviderFactory,
+};
+use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use reth_storage_errors::provider::ProviderError;
+use std::ops::Range;
+use tracing::info;
+
+/// Maximum number of transactions to read from the database before pushing the collected indices
+/// to the [`Collector`].
+const TRANSACTIONS_PER_CHUNK: u64 = 500_000;
+
+/// Stage indexing the transactions that touched each address into
+/// [`tables::AddressTransactions`].
+///
+/// A transaction touches an address if the address is its sender, its recipient, the contract it
+/// created or the emitter of one of its logs. The index is sharded like
+/// [`tables::AccountsHistory`], with transaction numbers instead of block numbers.
+#[derive(Debug)]
+pub struct IndexAddressTransactionsStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+    /// Pruning configuration.
+    pub prune_mode: Option<PruneMode>,
+    /// ETL configuration
+    pub etl_config: EtlConfig,
+}
+
+impl IndexAddressTransactionsStage {
+    /// Create new instance of [`IndexAddressTransactionsStage`].
+    pub const fn new(
+        config: IndexHistoryConfig,
+        etl_config: EtlConfig,
+        prune_mode: Option<PruneMode>,
+    ) -> Self {
+        Self { commit_threshold: config.commit_threshold, etl_config, prune_mode }
+    }
+}
+
+impl Default for IndexAddressTransactionsStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000, prune_mode: None, etl_config: EtlConfig::default() }
+    }
+}
+
+impl<Provider> Stage<Provider> for IndexAddressTransactionsStage
+where
+    Provider: DBProvider<Tx: DbTxMut>
+        + BlockReader
+        + MetadataProvider
+        + MetadataWriter
+        + PruneCheckpointReader
+        + PruneCheckpointWriter
+        + StaticFileProviderFactory,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexAddressTransactions
+    }
+
+    /// Execute the stage.
+    fn execute(
+        &mut self,
+        provider: &Provider,
+        mut input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        if let Some((target_prunable_block, prune_mode)) = self
+            .prune_mode
+            .map(|mode| {
+                mode.prune_target_block(
+                    input.target(),
+                    PruneSegment::AddressTransactions,
+                    PrunePurpose::User,
+                )
+            })
+            .transpose()?
+            .flatten() &&
+            target_prunable_block > input.checkpoint().block_number
+        {
+            input.checkpoint = Some(StageCheckpoint::new(target_prunable_block));
+
+            // Save prune checkpoint only if we don't have one already.
+            // Otherwise, pruner may skip the unpruned range of blocks.
+            if provider.get_prune_checkpoint(PruneSegment::AddressTransactions)?.is_none() {
+                let target_prunable_tx_number = provider
+                    .block_body_indices(target_prunable_block)?
+                    .ok_or(ProviderError::BlockBodyIndicesNotFound(target_prunable_block))?
+                    .last_tx_num();
+
+                provider.save_prune_checkpoint(
+                    PruneSegment::AddressTransactions,
+                    PruneCheckpoint {
+                        block_number: Some(target_prunable_block),
+                        tx_number: Some(target_prunable_tx_number),
+                        prune_mode,
+                    },
+                )?;
+            }
+        }
+
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        // On first sync we clear the table since it's faster to rebuild from scratch.
+        let first_sync = input.checkpoint().block_number == 0;
+        if first_sync {
+            provider.tx_ref().clear::<tables::AddressTransactions>()?;
+            provider.write_address_transactions_start(0)?;
+        } else if provider.address_transactions_start()?.is_none() {
+            // The checkpoint wasn't produced by indexing, so the index only starts here.
+            provider.write_address_transactions_start(input.next_block())?;
+        }
+
+        info!(target: "sync::stages::index_address_transactions::exec", ?first_sync, "Collecting indices");
+
+        let mut collector = Collector::new(self.etl_config.file_size, self.etl_config.dir.clone());
+        while let Some(range_output) =
+            input.next_block_range_with_transaction_threshold(provider, TRANSACTIONS_PER_CHUNK)?
+        {
+            info!(target: "sync::stages::index_address_transactions::exec", tx_range = ?range_output.tx_range, "Collecting indices");
+
+            let mut cache = AddressMap::<Vec<TxNumber>>::default();
+            for_each_touched_address(provider, range_output.tx_range, |tx_number, address| {
+                cache.entry(address).or_default().push(tx_number)
+            })?;
+            for (address, indices) in cache {
+                let last = *indices.last().expect("indices is non-empty");
+                collector.insert(
+                    ShardedKey::new(address, last),
+                    BlockNumberList::new_pre_sorted(indices),
+                )?;
+            }
+
+            input.checkpoint = Some(StageCheckpoint::new(*range_output.block_range.end()));
+            if range_output.is_final_range {
+                break
+            }
+        }
+
+        info!(target: "sync::stages::index_address_transactions::exec", "Loading indices into database");
+
+        let mut cursor = provider.tx_ref().cursor_write::<tables::AddressTransactions>()?;
+        load_sharded_indices::<tables::AddressTransactions, _, _>(
+            collector,
+            first_sync,
+            &mut cursor,
+        )?;
+
+        Ok(ExecOutput::done(StageCheckpoint::new(input.target())))
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        let bodies = provider.block_body_indices_range(range)?;
+        let (Some(first), Some(last)) = (bodies.first(), bodies.last()) else {
+            return Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+        };
+        let first_tx = first.first_tx_num();
+
+        let mut addresses = Vec::new();
+        for_each_touched_address(provider, first_tx..last.next_tx_num(), |_, address| {
+            addresses.push(address)
+        })?;
+        addresses.sort_unstable();
+        addresses.dedup();
+
+        let mut cursor = provider.tx_ref().cursor_write::<tables::AddressTransactions>()?;
+        for address in addresses {
+            unwind_shards(&mut cursor, address, first_tx)?;
+        }
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+/// Calls `f` once for every address touched by each transaction in the range, in ascending order
+/// of transaction number.
+///
+/// Senders that were pruned are recovered from the transaction signature, and log emitters are
+/// skipped if the receipts of the range were pruned.
+fn for_each_touched_address<Provider>(
+    provider: &Provider,
+    tx_range: Range<TxNumber>,
+    mut f: impl FnMut(TxNumber, Address),
+) -> Result<(), StageError>
+where
+    Provider: BlockReader,
+{
+    let transactions = provider.transactions_by_tx_range(tx_range.clone())?;
+    let senders = provider.senders_by_tx_range(tx_range.clone())?;
+    let receipts = provider.receipts_by_tx_range(tx_range.clone())?;
+    let has_senders = senders.len() == transactions.len();
+    let has_receipts = receipts.len() == transactions.len();
+
+    for (idx, (tx_number, transaction)) in tx_range.zip(&transactions).enumerate() {
+        let sender = if has_senders {
+            senders[idx]
+        } else {
+            transaction.recover_signer_unchecked().map_err(|err| StageError::Fatal(err.into()))?
+        };
+
+        let logs = if has_receipts { receipts[idx].logs() } else { &[] };
+        for address in touched_addresses(sender, transaction, logs) {
+            f(tx_number, address);
+        }
+    }
+
+    Ok(())
+}
+
+/// Removes all transaction numbers greater than or equal to `first_tx` from the shards of
+/// `address`, moving the remaining ones of the boundary shard to the `u64::MAX` key.
+fn unwind_shards<C>(cursor: &mut C, address: Address, first_tx: TxNumber) -> Result<(), StageError>
+where
+    C: DbCursorRO<tables::AddressTransactions> + DbCursorRW<tables::AddressTransactions>,
+{
+    let mut remaining = Vec::new();
+    let mut item = cursor.seek_exact(ShardedKey::last(address))?;
+    while let Some((sharded_key, list)) = item {
+        if sharded_key.key != address {
+            break
+        }
+
+        cursor.delete_current()?;
+        if list.iter().next().is_some_and(|first| first >= first_tx) {
+            item = cursor.prev()?;
+            continue
+        }
+
+        remaining = list.iter().take_while(|tx| *tx < first_tx).collect();
+        break
+    }
+
+    if !remaining.is_empty() {
+        cursor.upsert(ShardedKey::last(address), &BlockNumberList::new_pre_sorted(remaining))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{StorageKind, TestStageDB};
+    use alloy_primitives::B256;
+    use reth_db_api::transaction::DbTx;
+    use reth_provider::{
+        AddressTransactionsReader, BlockBodyIndicesProvider, DatabaseProviderFactory,
+    };
+    use reth_testing_utils::generators::{
+        self, random_block_range, random_receipt, BlockRangeParams,
+    };
+    use std::collections::BTreeMap;
+
+    /// Builds the expected index from the transactions and receipts stored in `db`.
+    fn expected_index(db: &TestStageDB, tx_range: Range<TxNumber>) -> BTreeMap<Address, Vec<u64>> {
+        let provider = db.factory.provider().unwrap();
+        let mut index = BTreeMap::<Address, Vec<u64>>::new();
+        for_each_touched_address(&provider, tx_range, |tx_number, address| {
+            index.entry(address).or_default().push(tx_number)
+        })
+        .unwrap();
+        index
+    }
+
+    fn table_index(db: &TestStageDB) -> BTreeMap<Address, Vec<u64>> {
+        let mut index = BTreeMap::<Address, Vec<u64>>::new();
+        db.query(|tx| {
+            let mut cursor = tx.cursor_read::<tables::AddressTransactions>()?;
+            for entry in cursor.walk(None)? {
+                let (key, list) = entry?;
+                index.entry(key.key).or_default().extend(list.iter());
+            }
+            Ok(())
+        })
+        .unwrap();
+        index
+    }
+
+    #[test]
+    fn execute_and_unwind() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(
+            &mut rng,
+            0..=20,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 1..4, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Static).unwrap();
+
+        let mut tx_number = 0;
+        let mut receipts = Vec::new();
+        for block in &blocks {
+            for transaction in &block.body().transactions {
+                receipts.push((tx_number, random_receipt(&mut rng, transaction, Some(2), None)));
+                tx_number += 1;
+            }
+        }
+        db.insert_receipts(receipts).unwrap();
+
+        let mut stage = IndexAddressTransactionsStage::default();
+        let provider = db.factory.database_provider_rw().unwrap();
+        let output =
+            stage.execute(&provider, ExecInput { target: Some(20), checkpoint: None }).unwrap();
+        assert_eq!(output, ExecOutput::done(StageCheckpoint::new(20)));
+        provider.commit().unwrap();
+
+        // Transactions of the genesis block are not indexed.
+        let first_tx = blocks[0].body().transactions.len() as u64;
+        let expected = expected_index(&db, first_tx..tx_number);
+        assert_eq!(table_index(&db), expected);
+
+        // Every address can be searched in both directions.
+        let provider = db.factory.provider().unwrap();
+        for (address, indices) in &expected {
+            let mut descending = indices.clone();
+            descending.reverse();
+            assert_eq!(
+                provider.address_transactions_before(*address, u64::MAX, usize::MAX).unwrap(),
+                descending
+            );
+            assert_eq!(
+                provider.address_transactions_after(*address, 0, usize::MAX).unwrap(),
+                *indices
+            );
+        }
+        drop(provider);
+
+        let unwind_to = 10;
+        let first_unwound_tx =
+            db.factory.provider().unwrap().block_body_indices(unwind_to + 1).unwrap().unwrap();
+        let provider = db.factory.database_provider_rw().unwrap();
+        stage
+            .unwind(
+                &provider,
+                UnwindInput { checkpoint: StageCheckpoint::new(20), unwind_to, bad_block: None },
+            )
+            .unwrap();
+        provider.commit().unwrap();
+
+        assert_eq!(
+            table_index(&db),
+            expected_index(&db, first_tx..first_unwound_tx.first_tx_num())
+        );
+    }
+}
@@ -0,0 +1,289 @@
+use super::load_sharded_indices;
+use alloy_consensus::TxReceipt;
+use alloy_primitives::{map::B256Map, B256};
+use reth_config::config::{EtlConfig, IndexHistoryConfig};
+use reth_db_api::{models::ShardedKey, tables, transaction::DbTxMut, BlockNumberList};
+use reth_etl::Collector;
+use reth_provider::{
+    BlockReader, DBProvider, LogIndexWriter, MetadataProvider, MetadataWriter,
+    PruneCheckpointReader, PruneCheckpointWriter,
+};
+use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use reth_storage_api::{log_index_keys, log_position};
+use tracing::info;
+
+/// Maximum number of transactions to read the receipts of before pushing the collected indices to
+/// the [`Collector`].
+const TRANSACTIONS_PER_CHUNK: u64 = 100_000;
+
+/// Stage indexing the logs of every block by emitter address and topics into
+/// [`tables::LogIndex`].
+///
+/// Every log gets one entry for its address and one for each of its topics and their position,
+/// pointing to the position of the log in the chain. Blocks whose receipts were pruned have no
+/// logs to index.
+#[derive(Debug)]
+pub struct IndexLogsStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+    /// Pruning configuration.
+    pub prune_mode: Option<PruneMode>,
+    /// ETL configuration
+    pub etl_config: EtlConfig,
+}
+
+impl IndexLogsStage {
+    /// Create new instance of [`IndexLogsStage`].
+    pub const fn new(
+        config: IndexHistoryConfig,
+        etl_config: EtlConfig,
+        prune_mode: Option<PruneMode>,
+    ) -> Self {
+        Self { commit_threshold: config.commit_threshold, etl_config, prune_mode }
+    }
+}
+
+impl Default for IndexLogsStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000, prune_mode: None, etl_config: EtlConfig::default() }
+    }
+}
+
+impl<Provider> Stage<Provider> for IndexLogsStage
+where
+    Provider: DBProvider<Tx: DbTxMut>
+        + BlockReader
+        + LogIndexWriter
+        + MetadataProvider
+        + MetadataWriter
+        + PruneCheckpointReader
+        + PruneCheckpointWriter,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexLogs
+    }
+
+    /// Execute the stage.
+    fn execute(
+        &mut self,
+        provider: &Provider,
+        mut input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        if let Some((target_prunable_block, prune_mode)) = self
+            .prune_mode
+            .map(|mode| {
+                mode.prune_target_block(input.target(), PruneSegment::LogIndex, PrunePurpose::User)
+            })
+            .transpose()?
+            .flatten() &&
+            target_prunable_block > input.checkpoint().block_number
+        {
+            input.checkpoint = Some(StageCheckpoint::new(target_prunable_block));
+
+            // Save prune checkpoint only if we don't have one already.
+            // Otherwise, pruner may skip the unpruned range of blocks.
+            if provider.get_prune_checkpoint(PruneSegment::LogIndex)?.is_none() {
+                provider.save_prune_checkpoint(
+                    PruneSegment::LogIndex,
+                    PruneCheckpoint {
+                        block_number: Some(target_prunable_block),
+                        tx_number: None,
+                        prune_mode,
+                    },
+                )?;
+            }
+        }
+
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        // On first sync we clear the table since it's faster to rebuild from scratch.
+        let first_sync = input.checkpoint().block_number == 0;
+        if first_sync {
+            provider.tx_ref().clear::<tables::LogIndex>()?;
+            provider.write_log_index_start(0)?;
+        } else if provider.log_index_start()?.is_none() {
+            // The checkpoint wasn't produced by indexing, so the index only starts here.
+            provider.write_log_index_start(input.next_block())?;
+        }
+
+        info!(target: "sync::stages::index_logs::exec", ?first_sync, "Collecting indices");
+
+        let mut collector = Collector::new(self.etl_config.file_size, self.etl_config.dir.clone());
+        while let Some(range_output) =
+            input.next_block_range_with_transaction_threshold(provider, TRANSACTIONS_PER_CHUNK)?
+        {
+            info!(target: "sync::stages::index_logs::exec", block_range = ?range_output.block_range, "Collecting indices");
+
+            let mut cache = B256Map::<Vec<u64>>::default();
+            let receipts = provider.receipts_by_block_range(range_output.block_range.clone())?;
+            for (block_number, receipts) in range_output.block_range.clone().zip(receipts) {
+                let logs = receipts.iter().flat_map(|receipt| receipt.logs());
+                for (log_index, log) in logs.enumerate() {
+                    let position = log_position(block_number, log_index as u64);
+                    for key in log_index_keys(log) {
+                        cache.entry(key).or_default().push(position);
+                    }
+                }
+            }
+            for (key, positions) in cache {
+                let last = *positions.last().expect("positions is non-empty");
+                collector.insert(
+                    ShardedKey::new(key, last),
+                    BlockNumberList::new_pre_sorted(positions),
+                )?;
+            }
+
+            input.checkpoint = Some(StageCheckpoint::new(*range_output.block_range.end()));
+            if range_output.is_final_range {
+                break
+            }
+        }
+
+        info!(target: "sync::stages::index_logs::exec", "Loading indices into database");
+
+        let mut cursor = provider.tx_ref().cursor_write::<tables::LogIndex>()?;
+        load_sharded_indices::<tables::LogIndex, B256, _>(collector, first_sync, &mut cursor)?;
+
+        Ok(ExecOutput::done(StageCheckpoint::new(input.target())))
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        provider.unwind_log_index(range)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{StorageKind, TestStageDB};
+    use alloy_primitives::{Address, Log};
+    use reth_db_api::{cursor::DbCursorRO, transaction::DbTx};
+    use reth_provider::{DatabaseProviderFactory, LogIndexReader, ReceiptProvider};
+    use reth_stages_api::StageCheckpointWriter;
+    use reth_storage_api::{log_index_address_key, log_index_topic_key, LogIndexQuery};
+    use reth_testing_utils::generators::{
+        self, random_block_range, random_receipt, BlockRangeParams,
+    };
+    use std::collections::BTreeMap;
+
+    /// Builds the expected index from the receipts of `blocks` stored in `db`.
+    fn expected_index(
+        db: &TestStageDB,
+        blocks: std::ops::RangeInclusive<u64>,
+    ) -> BTreeMap<B256, Vec<u64>> {
+        let provider = db.factory.provider().unwrap();
+        let mut index = BTreeMap::<B256, Vec<u64>>::new();
+        for (block_number, receipts) in
+            blocks.clone().zip(provider.receipts_by_block_range(blocks).unwrap())
+        {
+            for (log_index, log) in receipts.iter().flat_map(|r| r.logs()).enumerate() {
+                for key in log_index_keys(log) {
+                    index.entry(key).or_default().push(log_position(block_number, log_index as _));
+                }
+            }
+        }
+        index
+    }
+
+    fn table_index(db: &TestStageDB) -> BTreeMap<B256, Vec<u64>> {
+        let mut index = BTreeMap::<B256, Vec<u64>>::new();
+        db.query(|tx| {
+            let mut cursor = tx.cursor_read::<tables::LogIndex>()?;
+            for entry in cursor.walk(None)? {
+                let (key, list) = entry?;
+                index.entry(key.key).or_default().extend(list.iter());
+            }
+            Ok(())
+        })
+        .unwrap();
+        index
+    }
+
+    #[test]
+    fn execute_and_unwind() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(
+            &mut rng,
+            0..=20,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 1..4, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Static).unwrap();
+
+        // One emitter with a fixed first topic in every receipt, on top of random logs.
+        let emitter = Address::with_last_byte(1);
+        let topic = B256::with_last_byte(2);
+        let mut tx_number = 0;
+        let mut receipts = Vec::new();
+        for block in &blocks {
+            for transaction in &block.body().transactions {
+                let mut receipt = random_receipt(&mut rng, transaction, Some(2), None);
+                receipt.logs.push(Log::new_unchecked(emitter, vec![topic], Default::default()));
+                receipts.push((tx_number, receipt));
+                tx_number += 1;
+            }
+        }
+        db.insert_receipts(receipts).unwrap();
+
+        let mut stage = IndexLogsStage::default();
+        let provider = db.factory.database_provider_rw().unwrap();
+        let output =
+            stage.execute(&provider, ExecInput { target: Some(20), checkpoint: None }).unwrap();
+        assert_eq!(output, ExecOutput::done(StageCheckpoint::new(20)));
+        provider.save_stage_checkpoint(StageId::IndexLogs, output.checkpoint).unwrap();
+        provider.commit().unwrap();
+
+        // Logs of the genesis block are not indexed.
+        let expected = expected_index(&db, 1..=20);
+        assert_eq!(table_index(&db), expected);
+
+        let provider = db.factory.provider().unwrap();
+        assert_eq!(provider.log_index_block_range().unwrap(), Some(0..=20));
+
+        // The emitter and topic conditions only agree on the logs pushed above.
+        let query = LogIndexQuery {
+            keys: vec![vec![log_index_address_key(emitter)], vec![log_index_topic_key(0, topic)]],
+        };
+        let positions = provider.log_index_positions(&query, 5..=10, usize::MAX).unwrap();
+        assert_eq!(
+            positions,
+            expected[&log_index_address_key(emitter)]
+                .iter()
+                .copied()
+                .filter(|position| (log_position(5, 0)..log_position(11, 0)).contains(position))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(provider.log_index_positions(&query, 5..=10, 2).unwrap(), positions[..2]);
+        drop(provider);
+
+        let unwind_to = 10;
+        let provider = db.factory.database_provider_rw().unwrap();
+        stage
+            .unwind(
+                &provider,
+                UnwindInput { checkpoint: StageCheckpoint::new(20), unwind_to, bad_block: None },
+            )
+            .unwrap();
+        provider.commit().unwrap();
+
+        assert_eq!(table_index(&db), expected_index(&db, 1..=unwind_to));
+    }
+}
@@ -0,0 +1,714 @@
+//! Download of the account and storage ranges of the pivot state.
+
+use super::{SnapRequest, MAX_EMPTY_RESPONSES};
+use alloy_consensus::constants::KECCAK_EMPTY;
+use alloy_primitives::{keccak256, map::B256Set, Bytes, B256, U256};
+use alloy_rlp::Decodable;
+use futures_util::{stream::FuturesUnordered, StreamExt};
+use reth_config::config::SnapSyncConfig;
+use reth_db_api::{tables, transaction::DbTxMut};
+use reth_eth_wire_types::snap::{
+    AccountRangeMessage, ByteCodesMessage, GetAccountRangeMessage, GetByteCodesMessage,
+    GetStorageRangesMessage, StorageRangesMessage,
+};
+use reth_network_p2p::{
+    error::PeerRequestResult,
+    snap::client::{SnapClient, SnapResponse},
+};
+use reth_primitives_traits::{Account, Bytecode};
+use reth_provider::{DBProvider, StateWriter};
+use reth_stages_api::StageError;
+use reth_trie::{verify_range_proof, HashedPostState, TrieAccount, EMPTY_ROOT_HASH};
+use std::{
+    collections::VecDeque,
+    fmt, mem,
+    task::{Context, Poll},
+};
+use tracing::*;
+
+/// The maximum number of downloaded accounts per range that are waiting for their storage or
+/// bytecode, before no more accounts are requested for the range.
+const MAX_PENDING_ACCOUNTS: usize = 4096;
+
+/// The maximum number of accounts whose storage is requested at once.
+const MAX_STORAGE_ACCOUNTS: usize = 64;
+
+/// The maximum number of bytecodes that are requested at once.
+const MAX_BYTECODES: usize = 128;
+
+/// Downloads the account ranges of the pivot state, along with their storage and bytecodes.
+///
+/// The key space is split into ranges that are downloaded concurrently. Downloaded accounts are
+/// kept pending until their storage and bytecode are downloaded as well, so that every account
+/// before the cursor of a range is complete once written.
+pub(super) struct StateDownload<F> {
+    /// The account ranges.
+    tasks: Vec<AccountTask>,
+    /// The storage ranges that need to be requested.
+    storage_queue: VecDeque<StorageJob>,
+    /// The bytecodes that need to be requested.
+    code_queue: VecDeque<B256>,
+    /// The bytecodes that were queued during this download.
+    known_codes: B256Set,
+    /// The bytecodes that are queued, but not downloaded yet.
+    pending_codes: B256Set,
+    /// The requests in flight.
+    requests: FuturesUnordered<SnapRequest<DownloadRequest, F>>,
+    /// The number of consecutive responses without any data.
+    empty_responses: usize,
+    /// Whether peers stopped serving the state of the pivot block.
+    stale: bool,
+    /// The downloaded state that is not written yet.
+    state: HashedPostState,
+    /// The downloaded bytecodes that are not written yet.
+    codes: Vec<(B256, Bytes)>,
+    /// The number of entries in the buffers.
+    buffered: u64,
+    /// Statistics of the download.
+    stats: DownloadStats,
+}
+
+impl<F> StateDownload<F> {
+    /// Creates a new download of the given account ranges, as pairs of the next and the last key.
+    pub(super) fn new(ranges: Vec<(B256, B256)>) -> Self {
+        Self {
+            tasks: ranges
+                .into_iter()
+                .map(|(next, limit)| AccountTask {
+                    next,
+                    limit,
+                    complete: next > limit,
+                    inflight: false,
+                    pending: VecDeque::new(),
+                })
+                .collect(),
+            storage_queue: VecDeque::new(),
+            code_queue: VecDeque::new(),
+            known_codes: B256Set::default(),
+            pending_codes: B256Set::default(),
+            requests: FuturesUnordered::new(),
+            empty_responses: 0,
+            stale: false,
+            state: HashedPostState::default(),
+            codes: Vec::new(),
+            buffered: 0,
+            stats: DownloadStats::default(),
+        }
+    }
+
+    /// Returns the account ranges that are not downloaded and written yet.
+    pub(super) fn ranges(&self) -> Vec<(B256, B256)> {
+        self.tasks
+            .iter()
+            .filter(|task| !task.is_done())
+            .map(|task| (task.cursor(), task.limit))
+            .collect()
+    }
+
+    /// Returns the downloaded share of the key space.
+    pub(super) fn progress(&self) -> f64 {
+        // the top 64 bits of the keys are precise enough
+        let top = |key: B256| u64::from_be_bytes(key[..8].try_into().unwrap()) as f64;
+        let remaining = self
+            .tasks
+            .iter()
+            .filter(|task| !task.is_done())
+            .map(|task| top(task.limit) - top(task.cursor()) + 1.0)
+            .sum::<f64>();
+        1.0 - remaining / (u64::MAX as f64 + 1.0)
+    }
+
+    /// Returns the statistics of the download.
+    pub(super) const fn stats(&self) -> &DownloadStats {
+        &self.stats
+    }
+
+    /// Returns `true` if there are no requests in flight.
+    pub(super) fn is_idle(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    /// Returns `true` if the whole state is downloaded and written.
+    pub(super) fn is_finished(&self) -> bool {
+        self.buffered == 0 &&
+            self.requests.is_empty() &&
+            self.storage_queue.is_empty() &&
+            self.code_queue.is_empty() &&
+            self.tasks.iter().all(AccountTask::is_done)
+    }
+
+    /// Returns whether peers stopped serving the state of the pivot block, and resets the flag.
+    pub(super) fn take_stale(&mut self) -> bool {
+        self.empty_responses = 0;
+        mem::take(&mut self.stale)
+    }
+
+    /// Writes the downloaded state.
+    pub(super) fn write<Provider>(&mut self, provider: &Provider) -> Result<(), StageError>
+    where
+        Provider: DBProvider<Tx: DbTxMut> + StateWriter,
+    {
+        let state = mem::take(&mut self.state).into_sorted();
+        provider.write_hashed_state(&state)?;
+        for (hash, code) in self.codes.drain(..) {
+            provider.tx_ref().put::<tables::Bytecodes>(hash, Bytecode::new_raw(code))?;
+        }
+        self.buffered = 0;
+        Ok(())
+    }
+
+    /// Moves the accounts at the front of the ranges, whose storage and bytecode are downloaded,
+    /// to the buffer.
+    fn advance(&mut self) {
+        for task in &mut self.tasks {
+            while let Some(account) = task.pending.front() {
+                if account.storage ||
+                    account.code.is_some_and(|code| self.pending_codes.contains(&code))
+                {
+                    break
+                }
+
+                let account = task.pending.pop_front().expect("not empty");
+                self.state.accounts.insert(account.hash, Some(account.account));
+                self.buffered += 1;
+            }
+        }
+    }
+
+    /// Returns `true` if the task at the given index is ready to request the next range.
+    fn is_task_ready(task: &AccountTask) -> bool {
+        !task.complete && !task.inflight && task.pending.len() < MAX_PENDING_ACCOUNTS
+    }
+}
+
+impl<F> StateDownload<F>
+where
+    F: std::future::Future<Output = PeerRequestResult<SnapResponse>> + Unpin,
+{
+    /// Sends requests and processes responses until there is enough data to be written, or the
+    /// download is finished.
+    pub(super) fn poll<C>(
+        &mut self,
+        cx: &mut Context<'_>,
+        client: &C,
+        root: B256,
+        config: &SnapSyncConfig,
+    ) -> Poll<()>
+    where
+        C: SnapClient<Output = F>,
+    {
+        loop {
+            if client.num_connected_peers() > 0 {
+                self.send_requests(client, root, config);
+            }
+
+            match self.requests.poll_next_unpin(cx) {
+                Poll::Ready(Some((request, response))) => {
+                    self.on_response(client, request, response);
+                    self.advance();
+
+                    if self.stale || self.buffered >= config.commit_threshold {
+                        return Poll::Ready(())
+                    }
+                }
+                Poll::Ready(None) | Poll::Pending => {
+                    if self.buffered > 0 && self.requests.is_empty() ||
+                        self.tasks.iter().all(AccountTask::is_done) && self.requests.is_empty()
+                    {
+                        return Poll::Ready(())
+                    }
+                    return Poll::Pending
+                }
+            }
+        }
+    }
+
+    /// Sends requests until the limit of concurrent requests is reached.
+    fn send_requests<C>(&mut self, client: &C, root: B256, config: &SnapSyncConfig)
+    where
+        C: SnapClient<Output = F>,
+    {
+        while self.requests.len() < config.max_concurrent_requests {
+            let request = if !self.code_queue.is_empty() {
+                let count = self.code_queue.len().min(MAX_BYTECODES);
+                DownloadRequest::ByteCodes { hashes: self.code_queue.drain(..count).collect() }
+            } else if let Some(job) = self.storage_queue.pop_front() {
+                // a partially downloaded storage is requested on its own, since the origin
+                // applies to all accounts of the request
+                let mut jobs = vec![job];
+                if jobs[0].origin.is_zero() {
+                    while jobs.len() < MAX_STORAGE_ACCOUNTS &&
+                        self.storage_queue.front().is_some_and(|job| job.origin.is_zero())
+                    {
+                        jobs.extend(self.storage_queue.pop_front());
+                    }
+                }
+                DownloadRequest::StorageRanges { root, jobs }
+            } else if let Some(index) = self.tasks.iter().position(Self::is_task_ready) {
+                let task = &mut self.tasks[index];
+                task.inflight = true;
+                DownloadRequest::AccountRange { task: index, root, origin: task.next }
+            } else {
+                break
+            };
+
+            let fut = match &request {
+                DownloadRequest::AccountRange { task, root, origin } => {
+                    client.get_account_range(GetAccountRangeMessage {
+                        request_id: 0,
+                        root_hash: *root,
+                        starting_hash: *origin,
+                        limit_hash: self.tasks[*task].limit,
+                        response_bytes: config.response_bytes,
+                    })
+                }
+                DownloadRequest::StorageRanges { root, jobs } => {
+                    client.get_storage_ranges(GetStorageRangesMessage {
+                        request_id: 0,
+                        root_hash: *root,
+                        account_hashes: jobs.iter().map(|job| job.account).collect(),
+                        starting_hash: jobs[0].origin,
+                        limit_hash: B256::repeat_byte(0xff),
+                        response_bytes: config.response_bytes,
+                    })
+                }
+                DownloadRequest::ByteCodes { hashes } => {
+                    client.get_byte_codes(GetByteCodesMessage {
+                        request_id: 0,
+                        hashes: hashes.clone(),
+                        response_bytes: config.response_bytes,
+                    })
+                }
+            };
+            self.requests.push(SnapRequest::new(request, fut));
+        }
+    }
+
+    /// Processes the response to the given request.
+    fn on_response<C: SnapClient>(
+        &mut self,
+        client: &C,
+        request: DownloadRequest,
+        response: PeerRequestResult<SnapResponse>,
+    ) {
+        let response = match response {
+            Ok(response) => response,
+            Err(error) => {
+                trace!(target: "sync::stages::snap", %error, "Snap request failed");
+                self.retry(request);
+                return
+            }
+        };
+        let (peer_id, response) = response.split();
+
+        let result = match (request, response) {
+            (request @ DownloadRequest::AccountRange { .. }, SnapResponse::AccountRange(msg)) => {
+                self.on_account_range(request, msg)
+            }
+            (request @ DownloadRequest::StorageRanges { .. }, SnapResponse::StorageRanges(msg)) => {
+                self.on_storage_ranges(request, msg)
+            }
+            (request @ DownloadRequest::ByteCodes { .. }, SnapResponse::ByteCodes(msg)) => {
+                self.on_byte_codes(request, msg)
+            }
+            (request, _) => Err(request),
+        };
+
+        if let Err(request) = result {
+            debug!(target: "sync::stages::snap", %peer_id, "Received invalid snap response");
+            client.report_bad_message(peer_id);
+            self.retry(request);
+        }
+    }
+
+    /// Records a response without any data, which indicates that the peer does not serve the
+    /// state anymore.
+    fn on_empty_response(&mut self, request: DownloadRequest) {
+        self.empty_responses += 1;
+        if self.empty_responses >= MAX_EMPTY_RESPONSES {
+            self.stale = true;
+        }
+        self.retry(request);
+    }
+
+    /// Queues the request again.
+    fn retry(&mut self, request: DownloadRequest) {
+        match request {
+            DownloadRequest::AccountRange { task, .. } => self.tasks[task].inflight = false,
+            DownloadRequest::StorageRanges { jobs, .. } => {
+                for job in jobs.into_iter().rev() {
+                    self.storage_queue.push_front(job);
+                }
+            }
+            DownloadRequest::ByteCodes { hashes } => {
+                for hash in hashes.into_iter().rev() {
+                    self.code_queue.push_front(hash);
+                }
+            }
+        }
+    }
+
+    /// Processes an account range, returning the request if the response is invalid.
+    fn on_account_range(
+        &mut self,
+        request: DownloadRequest,
+        msg: AccountRangeMessage,
+    ) -> Result<(), DownloadRequest> {
+        let DownloadRequest::AccountRange { task: index, root, origin } = &request else {
+            unreachable!("matched by the caller")
+        };
+        let (index, root, origin) = (*index, *root, *origin);
+        if msg.accounts.is_empty() && msg.proof.is_empty() {
+            self.on_empty_response(request);
+            return Ok(())
+        }
+
+        let Ok(accounts) = msg
+            .accounts
+            .iter()
+            .map(|account| Ok((account.hash, decode_slim_account(&account.body)?)))
+            .collect::<alloy_rlp::Result<Vec<_>>>()
+        else {
+            return Err(request)
+        };
+        let leaves = accounts
+            .iter()
+            .map(|(hash, account)| (*hash, alloy_rlp::encode(account)))
+            .collect::<Vec<_>>();
+        let has_more = match verify_range_proof(root, origin, &leaves, &msg.proof) {
+            Ok(has_more) => has_more,
+            Err(error) => {
+                trace!(target: "sync::stages::snap", %error, "Invalid account range");
+                return Err(request)
+            }
+        };
+        self.empty_responses = 0;
+
+        let task = &mut self.tasks[index];
+        task.inflight = false;
+        // the range may contain accounts beyond the limit, which belong to the next task
+        let total = accounts.len();
+        let (mut in_range, mut last) = (0, None);
+        for (hash, account) in accounts.into_iter().take_while(|(hash, _)| *hash <= task.limit) {
+            in_range += 1;
+            last = Some(hash);
+            let storage = account.storage_root != EMPTY_ROOT_HASH;
+            if storage {
+                self.storage_queue.push_back(StorageJob {
+                    task: index,
+                    account: hash,
+                    root: account.storage_root,
+                    origin: B256::ZERO,
+                });
+            }
+            let code = (account.code_hash != KECCAK_EMPTY).then_some(account.code_hash);
+            if let Some(code) = code.filter(|code| self.known_codes.insert(*code)) {
+                self.code_queue.push_back(code);
+                self.pending_codes.insert(code);
+            }
+            task.pending.push_back(PendingAccount {
+                hash,
+                account: Account::from(account),
+                storage,
+                code,
+            });
+            self.stats.accounts += 1;
+        }
+
+        let next = last.filter(|_| has_more && in_range == total).and_then(next_key);
+        match next.filter(|next| *next <= task.limit) {
+            Some(next) => task.next = next,
+            None => task.complete = true,
+        }
+
+        Ok(())
+    }
+
+    /// Processes storage ranges, returning the request if the response is invalid.
+    fn on_storage_ranges(
+        &mut self,
+        request: DownloadRequest,
+        msg: StorageRangesMessage,
+    ) -> Result<(), DownloadRequest> {
+        let DownloadRequest::StorageRanges { jobs, .. } = &request else {
+            unreachable!("matched by the caller")
+        };
+        if msg.slots.is_empty() {
+            if msg.proof.is_empty() {
+                self.on_empty_response(request);
+                return Ok(())
+            }
+            return Err(request)
+        }
+        if msg.slots.len() > jobs.len() {
+            return Err(request)
+        }
+
+        // verify all ranges before applying any of them
+        let last = msg.slots.len() - 1;
+        let mut results = Vec::with_capacity(msg.slots.len());
+        for (index, (job, slots)) in jobs.iter().zip(&msg.slots).enumerate() {
+            let Ok(values) = slots
+                .iter()
+                .map(|slot| {
+                    let value = U256::decode(&mut slot.data.as_ref())?;
+                    if value.is_zero() {
+                        return Err(alloy_rlp::Error::Custom("zero storage value"))
+                    }
+                    Ok((slot.hash, value))
+                })
+                .collect::<alloy_rlp::Result<Vec<_>>>()
+            else {
+                return Err(request)
+            };
+
+            let leaves =
+                slots.iter().map(|slot| (slot.hash, slot.data.as_ref())).collect::<Vec<_>>();
+            // only the last range may be partial and have a proof
+            let proof = if index == last { msg.proof.as_slice() } else { &[] };
+            match verify_range_proof(job.root, job.origin, &leaves, proof) {
+                Ok(has_more) => results.push((values, has_more)),
+                Err(error) => {
+                    trace!(target: "sync::stages::snap", %error, "Invalid storage range");
+                    return Err(request)
+                }
+            }
+        }
+        self.empty_responses = 0;
+
+        let DownloadRequest::StorageRanges { jobs, .. } = request else { unreachable!() };
+        let mut jobs = jobs.into_iter();
+        let mut requeue = Vec::new();
+        for (job, (values, has_more)) in jobs.by_ref().zip(results) {
+            self.stats.storage_slots += values.len() as u64;
+            self.buffered += values.len() as u64;
+            let next = values.last().and_then(|(hash, _)| next_key(*hash));
+            self.state.storages.entry(job.account).or_default().storage.extend(values);
+
+            match next.filter(|_| has_more) {
+                Some(origin) => requeue.push(StorageJob { origin, ..job }),
+                None => self.complete_storage(job),
+            }
+        }
+        requeue.extend(jobs);
+        for job in requeue.into_iter().rev() {
+            self.storage_queue.push_front(job);
+        }
+
+        Ok(())
+    }
+
+    /// Marks the storage of the account of the job as downloaded.
+    fn complete_storage(&mut self, job: StorageJob) {
+        let task = &mut self.tasks[job.task];
+        if let Ok(index) = task.pending.binary_search_by_key(&job.account, |account| account.hash) {
+            task.pending[index].storage = false;
+        }
+    }
+
+    /// Processes bytecodes, returning the request if the response is invalid.
+    fn on_byte_codes(
+        &mut self,
+        request: DownloadRequest,
+        msg: ByteCodesMessage,
+    ) -> Result<(), DownloadRequest> {
+        let DownloadRequest::ByteCodes { hashes } = &request else {
+            unreachable!("matched by the caller")
+        };
+        if msg.codes.is_empty() {
+            self.on_empty_response(request);
+            return Ok(())
+        }
+
+        // the codes are returned in the order of the request, but may skip some of them
+        let mut requested = hashes.iter();
+        let mut codes = Vec::with_capacity(msg.codes.len());
+        for code in msg.codes {
+            let hash = keccak256(&code);
+            if !requested.any(|requested| *requested == hash) {
+                return Err(request)
+            }
+            codes.push((hash, code));
+        }
+        self.empty_responses = 0;
+
+        let DownloadRequest::ByteCodes { hashes } = request else { unreachable!() };
+        for hash in hashes.into_iter().rev() {
+            if !codes.iter().any(|(code, _)| *code == hash) {
+                self.code_queue.push_front(hash);
+            }
+        }
+        self.stats.bytecodes += codes.len() as u64;
+        self.buffered += codes.len() as u64;
+        for (hash, _) in &codes {
+            self.pending_codes.remove(hash);
+        }
+        self.codes.extend(codes);
+
+        Ok(())
+    }
+}
+
+impl<F> fmt::Debug for StateDownload<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StateDownload")
+            .field("tasks", &self.tasks)
+            .field("storage_queue", &self.storage_queue.len())
+            .field("code_queue", &self.code_queue.len())
+            .field("requests", &self.requests.len())
+            .field("empty_responses", &self.empty_responses)
+            .field("stale", &self.stale)
+            .field("buffered", &self.buffered)
+            .field("stats", &self.stats)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Statistics of the state download.
+#[derive(Debug, Default, Clone, Copy)]
+pub(super) struct DownloadStats {
+    /// The number of downloaded accounts.
+    pub(super) accounts: u64,
+    /// The number of downloaded storage slots.
+    pub(super) storage_slots: u64,
+    /// The number of downloaded bytecodes.
+    pub(super) bytecodes: u64,
+}
+
+/// A range of accounts that is downloaded sequentially.
+#[derive(Debug)]
+struct AccountTask {
+    /// The next key to request.
+    next: B256,
+    /// The last key of the range.
+    limit: B256,
+    /// Whether all accounts of the range are downloaded.
+    complete: bool,
+    /// Whether a request for the range is in flight.
+    inflight: bool,
+    /// The downloaded accounts whose storage or bytecode is not downloaded yet.
+    pending: VecDeque<PendingAccount>,
+}
+
+impl AccountTask {
+    /// Returns the key from which the range needs to be downloaded after a restart.
+    fn cursor(&self) -> B256 {
+        self.pending.front().map_or(self.next, |account| account.hash)
+    }
+
+    /// Returns `true` if all accounts of the range are downloaded and complete.
+    fn is_done(&self) -> bool {
+        self.complete && self.pending.is_empty()
+    }
+}
+
+/// A downloaded account, waiting for its storage and bytecode.
+#[derive(Debug)]
+struct PendingAccount {
+    /// The hashed address of the account.
+    hash: B256,
+    /// The account.
+    account: Account,
+    /// Whether the storage of the account is still being downloaded.
+    storage: bool,
+    /// The bytecode hash of the account, if it has code.
+    code: Option<B256>,
+}
+
+/// The storage of an account that needs to be downloaded.
+#[derive(Debug)]
+struct StorageJob {
+    /// The index of the account task the account belongs to.
+    task: usize,
+    /// The hashed address of the account.
+    account: B256,
+    /// The storage root of the account.
+    root: B256,
+    /// The next storage key to request.
+    origin: B256,
+}
+
+/// A request of the state download.
+#[derive(Debug)]
+enum DownloadRequest {
+    /// Request for a range of accounts of the task at the given index.
+    AccountRange { task: usize, root: B256, origin: B256 },
+    /// Request for the storage of the given accounts.
+    StorageRanges { root: B256, jobs: Vec<StorageJob> },
+    /// Request for the given bytecodes.
+    ByteCodes { hashes: Vec<B256> },
+}
+
+/// Returns the key that directly follows the given key, if any.
+fn next_key(key: B256) -> Option<B256> {
+    U256::from_be_bytes(key.0).checked_add(U256::from(1)).map(B256::from)
+}
+
+/// Decodes an account in the slim format used by the `snap` protocol.
+///
+/// The storage root and code hash are empty strings if the account has no storage or code
+/// respectively.
+fn decode_slim_account(mut buf: &[u8]) -> alloy_rlp::Result<TrieAccount> {
+    let header = alloy_rlp::Header::decode(&mut buf)?;
+    if !header.list {
+        return Err(alloy_rlp::Error::UnexpectedString)
+    }
+    if buf.len() != header.payload_length {
+        return Err(alloy_rlp::Error::ListLengthMismatch {
+            expected: header.payload_length,
+            got: buf.len(),
+        })
+    }
+
+    let nonce = u64::decode(&mut buf)?;
+    let balance = U256::decode(&mut buf)?;
+    let storage_root = decode_optional_hash(&mut buf)?.unwrap_or(EMPTY_ROOT_HASH);
+    let code_hash = decode_optional_hash(&mut buf)?.unwrap_or(KECCAK_EMPTY);
+    if !buf.is_empty() {
+        return Err(alloy_rlp::Error::UnexpectedLength)
+    }
+
+    Ok(TrieAccount { nonce, balance, storage_root, code_hash })
+}
+
+/// Decodes a hash that may be encoded as an empty string.
+fn decode_optional_hash(buf: &mut &[u8]) -> alloy_rlp::Result<Option<B256>> {
+    let bytes = Bytes::decode(buf)?;
+    match bytes.len() {
+        0 => Ok(None),
+        32 => Ok(Some(B256::from_slice(&bytes))),
+        _ => Err(alloy_rlp::Error::UnexpectedLength),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_slim_accounts() {
+        // list of nonce, balance and two empty strings
+        let account = decode_slim_account(&[0xc4, 0x01, 0x02, 0x80, 0x80]).unwrap();
+        assert_eq!(
+            account,
+            TrieAccount {
+                nonce: 1,
+                balance: U256::from(2),
+                storage_root: EMPTY_ROOT_HASH,
+                code_hash: KECCAK_EMPTY
+            }
+        );
+
+        // the full encoding is a valid slim encoding
+        let account = TrieAccount {
+            nonce: 1,
+            balance: U256::from(2),
+            storage_root: B256::repeat_byte(0xaa),
+            code_hash: B256::repeat_byte(0xcc),
+        };
+        assert_eq!(decode_slim_account(&alloy_rlp::encode(account)).unwrap(), account);
+
+        assert!(decode_slim_account(&[0xc3, 0x01, 0x02, 0x80]).is_err());
+        assert!(decode_slim_account(&[0xc5, 0x01, 0x02, 0x80, 0x81, 0x00]).is_err());
+    }
+}
@@ -0,0 +1,704 @@
+//! Healing of the state trie with the trie nodes of the pivot state.
+
+use super::{DbStateRoot, SnapRequest, MAX_EMPTY_RESPONSES};
+use alloy_consensus::constants::KECCAK_EMPTY;
+use alloy_primitives::{keccak256, map::B256Set, Bytes, B256, U256};
+use alloy_rlp::Decodable;
+use futures_util::{stream::FuturesUnordered, StreamExt};
+use reth_config::config::SnapSyncConfig;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_eth_wire_types::snap::{
+    ByteCodesMessage, GetByteCodesMessage, GetTrieNodesMessage, TrieNodesMessage, TriePath,
+};
+use reth_network_p2p::{
+    error::PeerRequestResult,
+    snap::client::{SnapClient, SnapResponse},
+};
+use reth_primitives_traits::{Account, Bytecode};
+use reth_provider::{DBProvider, StateWriter, StorageSettingsCache, TrieWriter};
+use reth_stages_api::StageError;
+use reth_trie::{
+    encode_path_leaf,
+    prefix_set::{PrefixSetMut, TriePrefixSetsMut},
+    trie_cursor::{TrieCursor, TrieCursorFactory},
+    BranchNodeCompact, HashedPostState, HashedStorage, Nibbles, TrieAccount, TrieNode,
+    EMPTY_ROOT_HASH,
+};
+use reth_trie_db::{DatabaseStateRoot, DatabaseTrieCursorFactory};
+use std::{
+    collections::VecDeque,
+    fmt, mem,
+    task::{Context, Poll},
+};
+use tracing::*;
+
+/// The maximum number of trie nodes that are requested at once.
+const MAX_TRIE_NODES: usize = 128;
+
+/// The maximum number of bytecodes that are requested at once.
+const MAX_BYTECODES: usize = 128;
+
+/// The number of received trie nodes after which they are processed.
+const PROCESS_BATCH: usize = 1024;
+
+/// Heals the state trie by downloading the trie nodes of the pivot state that differ from the
+/// local trie.
+///
+/// Starting at the state root, the children of every downloaded branch node are compared with the
+/// local trie, and only the differing subtries are descended into. The flat state is updated with
+/// the downloaded leaves and entries that are not part of the pivot state are deleted. After
+/// every round, the local trie is updated from the changed state, and another round is started if
+/// the state root still differs.
+///
+/// Since processing the nodes requires database access, downloaded nodes are buffered until they
+/// are written by the stage.
+pub(super) struct StateHeal<F> {
+    /// The trie nodes that need to be requested.
+    queue: VecDeque<NodeRequest>,
+    /// The bytecodes that need to be requested.
+    code_queue: VecDeque<B256>,
+    /// The bytecodes that were queued during this round.
+    known_codes: B256Set,
+    /// The requests in flight.
+    requests: FuturesUnordered<SnapRequest<HealRequest, F>>,
+    /// The downloaded trie nodes that are not processed yet.
+    nodes: Vec<(NodeRequest, Bytes)>,
+    /// The downloaded bytecodes that are not written yet.
+    codes: Vec<(B256, Bytes)>,
+    /// The number of consecutive responses without any data.
+    empty_responses: usize,
+    /// Whether peers stopped serving the state of the pivot block.
+    stale: bool,
+    /// The changes to the flat state since the last trie update.
+    prefix_sets: TriePrefixSetsMut,
+    /// Statistics of the healing.
+    stats: HealStats,
+}
+
+impl<F> StateHeal<F> {
+    /// Creates a new healing of the trie with the given root.
+    pub(super) fn new(root: B256) -> Self {
+        let mut heal = Self {
+            queue: VecDeque::new(),
+            code_queue: VecDeque::new(),
+            known_codes: B256Set::default(),
+            requests: FuturesUnordered::new(),
+            nodes: Vec::new(),
+            codes: Vec::new(),
+            empty_responses: 0,
+            stale: false,
+            prefix_sets: TriePrefixSetsMut::default(),
+            stats: HealStats::default(),
+        };
+        heal.start_round(root);
+        heal
+    }
+
+    /// Starts a new round of healing the trie with the given root.
+    pub(super) fn start_round(&mut self, root: B256) {
+        self.reset();
+        self.known_codes.clear();
+        self.queue.push_back(NodeRequest { account: None, path: Nibbles::default(), hash: root });
+    }
+
+    /// Drops all requested and downloaded trie nodes, since they belong to a previous pivot.
+    ///
+    /// The changes to the flat state are kept, so that the next trie update includes them.
+    pub(super) fn reset(&mut self) {
+        self.queue.clear();
+        self.requests = FuturesUnordered::new();
+        self.nodes.clear();
+        self.empty_responses = 0;
+    }
+
+    /// Returns the statistics of the healing.
+    pub(super) const fn stats(&self) -> &HealStats {
+        &self.stats
+    }
+
+    /// Returns `true` if there are no requests in flight.
+    pub(super) fn is_idle(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    /// Returns `true` if all trie nodes of the round are downloaded and written.
+    pub(super) fn is_round_finished(&self) -> bool {
+        self.queue.is_empty() &&
+            self.code_queue.is_empty() &&
+            self.requests.is_empty() &&
+            self.nodes.is_empty() &&
+            self.codes.is_empty()
+    }
+
+    /// Returns whether peers stopped serving the state of the pivot block, and resets the flag.
+    pub(super) fn take_stale(&mut self) -> bool {
+        self.empty_responses = 0;
+        mem::take(&mut self.stale)
+    }
+
+    /// Processes the downloaded trie nodes and writes the changes to the flat state.
+    pub(super) fn write<Provider>(&mut self, provider: &Provider) -> Result<(), StageError>
+    where
+        Provider: DBProvider<Tx: DbTxMut> + StorageSettingsCache + StateWriter,
+    {
+        let mut state = HashedPostState::default();
+        for (request, node) in mem::take(&mut self.nodes) {
+            let node = TrieNode::decode(&mut node.as_ref())
+                .map_err(|error| StageError::Fatal(Box::new(error)))?;
+            self.process_node(provider, &mut state, request.account, request.path, node)?;
+        }
+        provider.write_hashed_state(&state.into_sorted())?;
+
+        for (hash, code) in self.codes.drain(..) {
+            provider.tx_ref().put::<tables::Bytecodes>(hash, Bytecode::new_raw(code))?;
+        }
+        Ok(())
+    }
+
+    /// Updates the trie with the changes to the flat state and returns the new state root.
+    pub(super) fn update_trie<Provider>(&mut self, provider: &Provider) -> Result<B256, StageError>
+    where
+        Provider: DBProvider<Tx: DbTxMut> + StorageSettingsCache + TrieWriter,
+    {
+        let prefix_sets = mem::take(&mut self.prefix_sets).freeze();
+        let tx = provider.tx_ref();
+        let (root, updates) = reth_trie_db::with_adapter!(provider, |A| {
+            DbStateRoot::<_, A>::from_tx(tx).with_prefix_sets(prefix_sets).root_with_updates()
+        })
+        .map_err(|error| StageError::Fatal(Box::new(error)))?;
+        provider.write_trie_updates(updates)?;
+        Ok(root)
+    }
+
+    /// Processes a trie node of the pivot state at the given path.
+    fn process_node<Provider>(
+        &mut self,
+        provider: &Provider,
+        state: &mut HashedPostState,
+        account: Option<B256>,
+        path: Nibbles,
+        node: TrieNode,
+    ) -> Result<(), StageError>
+    where
+        Provider: DBProvider + StorageSettingsCache,
+    {
+        self.stats.trie_nodes += 1;
+
+        match node {
+            TrieNode::EmptyRoot => {
+                self.delete_under(provider, state, account, path, None)?;
+            }
+            TrieNode::Branch(branch) => {
+                let local = local_branch(provider, account, path)?;
+                for (nibble, child) in branch.as_ref().children() {
+                    let mut child_path = path;
+                    child_path.push(nibble);
+                    let Some(child) = child else {
+                        self.delete_under(provider, state, account, child_path, None)?;
+                        continue
+                    };
+
+                    match child.as_hash() {
+                        Some(hash) => {
+                            let unchanged = local.as_ref().is_some_and(|local| {
+                                local.hash_mask.is_bit_set(nibble) &&
+                                    local.hash_for_nibble(nibble) == hash
+                            });
+                            if !unchanged {
+                                self.queue.push_back(NodeRequest {
+                                    account,
+                                    path: child_path,
+                                    hash,
+                                });
+                            }
+                        }
+                        None => {
+                            let child = TrieNode::decode(&mut child.as_ref())
+                                .map_err(|error| StageError::Fatal(Box::new(error)))?;
+                            self.process_node(provider, state, account, child_path, child)?;
+                        }
+                    }
+                }
+            }
+            TrieNode::Extension(extension) => {
+                let child_path = path.join(&extension.key);
+                self.delete_under(provider, state, account, path, Some(child_path))?;
+
+                match extension.child.as_hash() {
+                    Some(hash) => {
+                        self.queue.push_back(NodeRequest { account, path: child_path, hash })
+                    }
+                    None => {
+                        let child = TrieNode::decode(&mut extension.child.as_ref())
+                            .map_err(|error| StageError::Fatal(Box::new(error)))?;
+                        self.process_node(provider, state, account, child_path, child)?;
+                    }
+                }
+            }
+            TrieNode::Leaf(leaf) => {
+                let key_path = path.join(&leaf.key);
+                self.delete_under(provider, state, account, path, Some(key_path))?;
+
+                let mut key = B256::ZERO;
+                key_path.pack_to(key.as_mut_slice());
+                let mut value = leaf.value.as_slice();
+                match account {
+                    None => {
+                        let account = TrieAccount::decode(&mut value)
+                            .map_err(|error| StageError::Fatal(Box::new(error)))?;
+                        self.process_account(provider, state, key, account)?;
+                    }
+                    Some(account) => {
+                        let value = U256::decode(&mut value)
+                            .map_err(|error| StageError::Fatal(Box::new(error)))?;
+                        self.stats.storage_slots += 1;
+                        state.storages.entry(account).or_default().storage.insert(key, value);
+                        self.prefix_sets.account_prefix_set.insert(Nibbles::unpack(account));
+                        self.prefix_sets
+                            .storage_prefix_sets
+                            .entry(account)
+                            .or_default()
+                            .insert(key_path);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Processes an account leaf of the pivot state.
+    fn process_account<Provider: DBProvider>(
+        &mut self,
+        provider: &Provider,
+        state: &mut HashedPostState,
+        hashed_address: B256,
+        account: TrieAccount,
+    ) -> Result<(), StageError> {
+        self.stats.accounts += 1;
+        state.accounts.insert(hashed_address, Some(Account::from(account)));
+        self.prefix_sets.account_prefix_set.insert(Nibbles::unpack(hashed_address));
+
+        if account.storage_root == EMPTY_ROOT_HASH {
+            self.wipe_storage(state, hashed_address);
+        } else {
+            // the root node is never stored locally, but its children are compared
+            self.queue.push_back(NodeRequest {
+                account: Some(hashed_address),
+                path: Nibbles::default(),
+                hash: account.storage_root,
+            });
+        }
+
+        if account.code_hash != KECCAK_EMPTY &&
+            self.known_codes.insert(account.code_hash) &&
+            provider.tx_ref().get::<tables::Bytecodes>(account.code_hash)?.is_none()
+        {
+            self.code_queue.push_back(account.code_hash);
+        }
+
+        Ok(())
+    }
+
+    /// Deletes the flat state under the given path that is not under the path to keep.
+    fn delete_under<Provider: DBProvider>(
+        &mut self,
+        provider: &Provider,
+        state: &mut HashedPostState,
+        account: Option<B256>,
+        prefix: Nibbles,
+        keep: Option<Nibbles>,
+    ) -> Result<(), StageError> {
+        let (lower, upper) = key_range(&prefix);
+        let deleted = |key: &B256| keep.is_none_or(|keep| !Nibbles::unpack(key).starts_with(&keep));
+
+        match account {
+            None => {
+                let mut cursor = provider.tx_ref().cursor_read::<tables::HashedAccounts>()?;
+                for entry in cursor.walk_range(lower..=upper)? {
+                    let (hashed_address, _) = entry?;
+                    if deleted(&hashed_address) {
+                        state.accounts.insert(hashed_address, None);
+                        self.prefix_sets.account_prefix_set.insert(Nibbles::unpack(hashed_address));
+                        self.wipe_storage(state, hashed_address);
+                    }
+                }
+            }
+            Some(account) => {
+                let mut cursor = provider.tx_ref().cursor_dup_read::<tables::HashedStorages>()?;
+                let storage = state.storages.entry(account).or_default();
+                for entry in cursor.walk_dup(Some(account), Some(lower))? {
+                    let (_, entry) = entry?;
+                    if entry.key > upper {
+                        break
+                    }
+                    if deleted(&entry.key) {
+                        storage.storage.insert(entry.key, U256::ZERO);
+                        self.prefix_sets
+                            .storage_prefix_sets
+                            .entry(account)
+                            .or_default()
+                            .insert(Nibbles::unpack(entry.key));
+                        self.prefix_sets.account_prefix_set.insert(Nibbles::unpack(account));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Deletes the storage of the account, and marks its storage trie for removal.
+    fn wipe_storage(&mut self, state: &mut HashedPostState, hashed_address: B256) {
+        state.storages.insert(hashed_address, HashedStorage::new(true));
+        self.prefix_sets.storage_prefix_sets.insert(hashed_address, PrefixSetMut::all());
+        self.prefix_sets.destroyed_accounts.insert(hashed_address);
+    }
+}
+
+impl<F> StateHeal<F>
+where
+    F: std::future::Future<Output = PeerRequestResult<SnapResponse>> + Unpin,
+{
+    /// Sends requests and receives trie nodes until there is a batch of nodes to be processed,
+    /// or the round is finished.
+    pub(super) fn poll<C>(
+        &mut self,
+        cx: &mut Context<'_>,
+        client: &C,
+        root: B256,
+        config: &SnapSyncConfig,
+    ) -> Poll<()>
+    where
+        C: SnapClient<Output = F>,
+    {
+        loop {
+            if client.num_connected_peers() > 0 {
+                self.send_requests(client, root, config);
+            }
+
+            match self.requests.poll_next_unpin(cx) {
+                Poll::Ready(Some((request, response))) => {
+                    self.on_response(client, request, response);
+                    if self.stale || self.nodes.len() + self.codes.len() >= PROCESS_BATCH {
+                        return Poll::Ready(())
+                    }
+                }
+                Poll::Ready(None) | Poll::Pending => {
+                    // nodes need to be processed to discover more nodes to request
+                    if self.requests.is_empty() &&
+                        (!self.nodes.is_empty() ||
+                            !self.codes.is_empty() ||
+                            self.is_round_finished())
+                    {
+                        return Poll::Ready(())
+                    }
+                    return Poll::Pending
+                }
+            }
+        }
+    }
+
+    /// Sends requests until the limit of concurrent requests is reached.
+    fn send_requests<C>(&mut self, client: &C, root: B256, config: &SnapSyncConfig)
+    where
+        C: SnapClient<Output = F>,
+    {
+        while self.requests.len() < config.max_concurrent_requests {
+            let request = if !self.code_queue.is_empty() {
+                let count = self.code_queue.len().min(MAX_BYTECODES);
+                HealRequest::ByteCodes { hashes: self.code_queue.drain(..count).collect() }
+            } else if !self.queue.is_empty() {
+                let count = self.queue.len().min(MAX_TRIE_NODES);
+                HealRequest::TrieNodes { nodes: self.queue.drain(..count).collect() }
+            } else {
+                break
+            };
+
+            let fut = match &request {
+                HealRequest::TrieNodes { nodes } => client.get_trie_nodes(GetTrieNodesMessage {
+                    request_id: 0,
+                    root_hash: root,
+                    paths: trie_paths(nodes),
+                    response_bytes: config.response_bytes,
+                }),
+                HealRequest::ByteCodes { hashes } => client.get_byte_codes(GetByteCodesMessage {
+                    request_id: 0,
+                    hashes: hashes.clone(),
+                    response_bytes: config.response_bytes,
+                }),
+            };
+            self.requests.push(SnapRequest::new(request, fut));
+        }
+    }
+
+    /// Processes the response to the given request.
+    fn on_response<C: SnapClient>(
+        &mut self,
+        client: &C,
+        request: HealRequest,
+        response: PeerRequestResult<SnapResponse>,
+    ) {
+        let response = match response {
+            Ok(response) => response,
+            Err(error) => {
+                trace!(target: "sync::stages::snap", %error, "Snap request failed");
+                self.retry(request);
+                return
+            }
+        };
+        let (peer_id, response) = response.split();
+
+        let result = match (request, response) {
+            (HealRequest::TrieNodes { nodes }, SnapResponse::TrieNodes(msg)) => {
+                self.on_trie_nodes(nodes, msg)
+            }
+            (HealRequest::ByteCodes { hashes }, SnapResponse::ByteCodes(msg)) => {
+                self.on_byte_codes(hashes, msg)
+            }
+            (request, _) => Err(request),
+        };
+
+        if let Err(request) = result {
+            debug!(target: "sync::stages::snap", %peer_id, "Received invalid snap response");
+            client.report_bad_message(peer_id);
+            self.retry(request);
+        }
+    }
+
+    /// Queues the request again.
+    fn retry(&mut self, request: HealRequest) {
+        match request {
+            HealRequest::TrieNodes { nodes } => {
+                for node in nodes.into_iter().rev() {
+                    self.queue.push_front(node);
+                }
+            }
+            HealRequest::ByteCodes { hashes } => {
+                for hash in hashes.into_iter().rev() {
+                    self.code_queue.push_front(hash);
+                }
+            }
+        }
+    }
+
+    /// Records a response without any data, which indicates that the peer does not serve the
+    /// state anymore.
+    fn on_empty_response(&mut self, request: HealRequest) {
+        self.empty_responses += 1;
+        if self.empty_responses >= MAX_EMPTY_RESPONSES {
+            self.stale = true;
+        }
+        self.retry(request);
+    }
+
+    /// Processes trie nodes, returning the request if the response is invalid.
+    fn on_trie_nodes(
+        &mut self,
+        requested: Vec<NodeRequest>,
+        msg: TrieNodesMessage,
+    ) -> Result<(), HealRequest> {
+        // missing nodes are returned as empty bytes
+        if msg.nodes.iter().all(|node| node.is_empty()) {
+            self.on_empty_response(HealRequest::TrieNodes { nodes: requested });
+            return Ok(())
+        }
+        if msg.nodes.len() > requested.len() ||
+            msg.nodes
+                .iter()
+                .zip(&requested)
+                .any(|(node, request)| !node.is_empty() && keccak256(node) != request.hash)
+        {
+            return Err(HealRequest::TrieNodes { nodes: requested })
+        }
+        self.empty_responses = 0;
+
+        let mut requested = requested.into_iter();
+        let mut missing = Vec::new();
+        for (request, node) in requested.by_ref().zip(msg.nodes) {
+            if node.is_empty() {
+                missing.push(request);
+            } else {
+                self.nodes.push((request, node));
+            }
+        }
+        missing.extend(requested);
+        self.retry(HealRequest::TrieNodes { nodes: missing });
+
+        Ok(())
+    }
+
+    /// Processes bytecodes, returning the request if the response is invalid.
+    fn on_byte_codes(
+        &mut self,
+        hashes: Vec<B256>,
+        msg: ByteCodesMessage,
+    ) -> Result<(), HealRequest> {
+        if msg.codes.is_empty() {
+            self.on_empty_response(HealRequest::ByteCodes { hashes });
+            return Ok(())
+        }
+
+        // the codes are returned in the order of the request, but may skip some of them
+        let mut requested = hashes.iter();
+        let mut codes = Vec::with_capacity(msg.codes.len());
+        for code in msg.codes {
+            let hash = keccak256(&code);
+            if !requested.any(|requested| *requested == hash) {
+                return Err(HealRequest::ByteCodes { hashes })
+            }
+            codes.push((hash, code));
+        }
+        self.empty_responses = 0;
+
+        let missing =
+            hashes.into_iter().filter(|hash| !codes.iter().any(|(code, _)| code == hash)).collect();
+        self.retry(HealRequest::ByteCodes { hashes: missing });
+        self.stats.bytecodes += codes.len() as u64;
+        self.codes.extend(codes);
+
+        Ok(())
+    }
+}
+
+impl<F> fmt::Debug for StateHeal<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StateHeal")
+            .field("queue", &self.queue.len())
+            .field("code_queue", &self.code_queue.len())
+            .field("requests", &self.requests.len())
+            .field("nodes", &self.nodes.len())
+            .field("empty_responses", &self.empty_responses)
+            .field("stale", &self.stale)
+            .field("stats", &self.stats)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Statistics of the trie healing.
+#[derive(Debug, Default, Clone, Copy)]
+pub(super) struct HealStats {
+    /// The number of healed accounts.
+    pub(super) accounts: u64,
+    /// The number of healed storage slots.
+    pub(super) storage_slots: u64,
+    /// The number of downloaded bytecodes.
+    pub(super) bytecodes: u64,
+    /// The number of processed trie nodes.
+    pub(super) trie_nodes: u64,
+}
+
+/// A trie node that needs to be downloaded.
+#[derive(Debug, Clone, Copy)]
+struct NodeRequest {
+    /// The hashed address of the account for storage trie nodes.
+    account: Option<B256>,
+    /// The path of the node.
+    path: Nibbles,
+    /// The hash of the node.
+    hash: B256,
+}
+
+/// A request of the trie healing.
+#[derive(Debug)]
+enum HealRequest {
+    /// Request for the given trie nodes.
+    TrieNodes { nodes: Vec<NodeRequest> },
+    /// Request for the given bytecodes.
+    ByteCodes { hashes: Vec<B256> },
+}
+
+/// Returns the local branch node at the given path of the account or storage trie.
+fn local_branch<Provider>(
+    provider: &Provider,
+    account: Option<B256>,
+    path: Nibbles,
+) -> Result<Option<BranchNodeCompact>, StageError>
+where
+    Provider: DBProvider + StorageSettingsCache,
+{
+    let tx = provider.tx_ref();
+    let node = reth_trie_db::with_adapter!(provider, |A| {
+        let factory = DatabaseTrieCursorFactory::<_, A>::new(tx);
+        match account {
+            None => factory.account_trie_cursor()?.seek_exact(path)?,
+            Some(account) => factory.storage_trie_cursor(account)?.seek_exact(path)?,
+        }
+    });
+    Ok(node.map(|(_, node)| node))
+}
+
+/// Returns the paths of the given trie nodes, grouping consecutive storage trie nodes of the same
+/// account.
+fn trie_paths(nodes: &[NodeRequest]) -> Vec<TriePath> {
+    let mut paths: Vec<TriePath> = Vec::new();
+    for node in nodes {
+        let path = Bytes::from(encode_path_leaf(&node.path, false).to_vec());
+        match node.account {
+            None => paths.push(TriePath { account_path: path, slot_paths: Vec::new() }),
+            Some(account) => match paths.last_mut() {
+                Some(last)
+                    if !last.slot_paths.is_empty() && last.account_path[..] == account[..] =>
+                {
+                    last.slot_paths.push(path)
+                }
+                _ => paths.push(TriePath {
+                    account_path: Bytes::copy_from_slice(account.as_slice()),
+                    slot_paths: vec![path],
+                }),
+            },
+        }
+    }
+    paths
+}
+
+/// Returns the range of keys under the given path.
+fn key_range(prefix: &Nibbles) -> (B256, B256) {
+    let mut lower = B256::ZERO;
+    prefix.pack_to(lower.as_mut_slice());
+    let mut upper = lower;
+    for index in prefix.len()..64 {
+        upper[index / 2] |= if index % 2 == 0 { 0xf0 } else { 0x0f };
+    }
+    (lower, upper)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_ranges() {
+        assert_eq!(key_range(&Nibbles::default()), (B256::ZERO, B256::repeat_byte(0xff)));
+
+        let (lower, upper) = key_range(&Nibbles::from_nibbles([0xa, 0xb, 0xc]));
+        assert_eq!(lower[..2], [0xab, 0xc0]);
+        assert!(lower[2..].iter().all(|byte| *byte == 0));
+        assert_eq!(upper[..2], [0xab, 0xcf]);
+        assert!(upper[2..].iter().all(|byte| *byte == 0xff));
+    }
+
+    #[test]
+    fn group_storage_paths() {
+        let account = B256::repeat_byte(0x11);
+        let nodes = [
+            NodeRequest { account: None, path: Nibbles::default(), hash: B256::ZERO },
+            NodeRequest { account: Some(account), path: Nibbles::default(), hash: B256::ZERO },
+            NodeRequest {
+                account: Some(account),
+                path: Nibbles::from_nibbles([1]),
+                hash: B256::ZERO,
+            },
+        ];
+        let paths = trie_paths(&nodes);
+        assert_eq!(paths.len(), 2);
+        assert_eq!(paths[0].account_path.as_ref(), &[0x00]);
+        assert!(paths[0].slot_paths.is_empty());
+        assert_eq!(paths[1].account_path.as_ref(), account.as_slice());
+        assert_eq!(paths[1].slot_paths.len(), 2);
+    }
+}
@@ -0,0 +1,608 @@
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{BlockNumber, B256};
+use futures_util::FutureExt;
+use reth_config::config::SnapSyncConfig;
+use reth_db_api::{table::Value, tables, transaction::DbTxMut};
+use reth_network_p2p::{
+    error::PeerRequestResult,
+    snap::client::{SnapClient, SnapResponse},
+};
+use reth_primitives_traits::NodePrimitives;
+use reth_provider::{
+    BlockReader, DBProvider, EitherWriter, HeaderProvider, PruneCheckpointWriter,
+    StageCheckpointReader, StageCheckpointWriter, StateWriter, StaticFileProviderFactory,
+    StorageSettingsCache, TrieWriter,
+};
+use reth_prune_types::{PruneCheckpoint, PruneMode, PruneSegment};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use reth_static_file_types::StaticFileSegment;
+use reth_storage_errors::provider::ProviderError;
+use reth_trie::{IntermediateStateRootState, StateRoot, StateRootProgress};
+use reth_trie_db::DatabaseStateRoot;
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+use tokio::time::Sleep;
+use tracing::*;
+
+mod download;
+mod heal;
+
+use download::StateDownload;
+use heal::StateHeal;
+
+type DbStateRoot<'a, TX, A> = StateRoot<
+    reth_trie_db::DatabaseTrieCursorFactory<&'a TX, A>,
+    reth_trie_db::DatabaseHashedCursorFactory<&'a TX>,
+>;
+
+/// The number of consecutive responses without any data after which the state of the pivot block
+/// is considered to be no longer available from peers.
+const MAX_EMPTY_RESPONSES: usize = 32;
+
+/// The delay after which requests are retried if there are no connected `snap` peers.
+const NO_PEERS_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// The stages whose work is covered by the snap sync.
+///
+/// Their checkpoints are moved to the pivot block once the state is synced.
+const SYNCED_STAGES: [StageId; 9] = [
+    StageId::SenderRecovery,
+    StageId::Execution,
+    StageId::PruneSenderRecovery,
+    StageId::MerkleUnwind,
+    StageId::AccountHashing,
+    StageId::StorageHashing,
+    StageId::MerkleExecute,
+    StageId::IndexStorageHistory,
+    StageId::IndexAccountHistory,
+];
+
+/// The snap sync stage downloads the state of the target block over the `snap` protocol instead of
+/// executing all blocks up to it.
+///
+/// The target block of the first pipeline run on a node without executed blocks becomes the
+/// pivot. The stage then:
+///
+/// - downloads the account ranges of the pivot state, along with their storage ranges and
+///   bytecodes, verifying every range against the state root of the pivot block,
+/// - computes the state trie from the downloaded state, and
+/// - heals the trie by downloading the trie nodes that differ from the pivot state, since ranges
+///   may have been downloaded from older pivots.
+///
+/// If peers stop serving the state of the pivot block, the progress is committed and the stage
+/// finishes without advancing its checkpoint. The next pipeline run continues with its target
+/// block as the new pivot.
+///
+/// Once the state of the pivot block is complete, the checkpoints of the stages that would have
+/// built it are moved to the pivot block, so that the pipeline continues executing from there.
+/// History before the pivot block is not available.
+///
+/// On nodes that have already executed blocks, the stage does nothing.
+///
+/// # Tables
+///
+/// The state is written to the [`HashedAccounts`][tables::HashedAccounts],
+/// [`HashedStorages`][tables::HashedStorages] and [`Bytecodes`][tables::Bytecodes] tables, the trie
+/// to the [`AccountsTrie`][tables::AccountsTrie] and [`StoragesTrie`][tables::StoragesTrie] tables.
+/// Therefore, the stage requires the v2 storage layout.
+pub struct SnapSyncStage<C: SnapClient> {
+    /// The client to download the state with.
+    client: C,
+    /// The configuration of the stage.
+    config: SnapSyncConfig,
+    /// The block whose state is synced.
+    pivot: Option<Pivot>,
+    /// The current phase of the sync, restored from the stage checkpoint on the first execution.
+    phase: Option<SyncPhase<C::Output>>,
+    /// Timer to retry sending requests if no peers are connected.
+    retry: Option<Pin<Box<Sleep>>>,
+}
+
+impl<C: SnapClient> SnapSyncStage<C> {
+    /// Create a new snap sync stage with the given client.
+    pub const fn new(client: C, config: SnapSyncConfig) -> Self {
+        Self { client, config, pivot: None, phase: None, retry: None }
+    }
+
+    /// Updates the pivot to the given block.
+    fn update_pivot<Provider: HeaderProvider>(
+        &mut self,
+        provider: &Provider,
+        number: BlockNumber,
+    ) -> Result<Pivot, StageError> {
+        if let Some(pivot) = self.pivot.filter(|pivot| pivot.number == number) {
+            return Ok(pivot)
+        }
+
+        let state_root = provider
+            .header_by_number(number)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?
+            .state_root();
+        let pivot = Pivot { number, state_root };
+        if let Some(previous) = self.pivot.replace(pivot) {
+            debug!(target: "sync::stages::snap", from = previous.number, to = number, "Moving pivot");
+        }
+
+        // nodes of the previous pivot can not be used to heal the trie
+        if let Some(SyncPhase::Heal(heal)) = &mut self.phase {
+            heal.reset();
+        }
+
+        Ok(pivot)
+    }
+
+    /// Restores the phase of the sync from the stage checkpoint.
+    fn restore_phase<Provider>(
+        &self,
+        provider: &Provider,
+    ) -> Result<SyncPhase<C::Output>, StageError>
+    where
+        Provider: DBProvider<Tx: DbTxMut> + StageCheckpointReader,
+    {
+        let progress = provider
+            .get_stage_checkpoint_progress(StageId::SnapSync)?
+            .and_then(|progress| SnapSyncProgress::decode(&progress));
+
+        Ok(match progress {
+            None => {
+                debug!(target: "sync::stages::snap", "Starting state download");
+                SyncPhase::Download(StateDownload::new(account_ranges(self.config.account_tasks)))
+            }
+            Some(SnapSyncProgress::Download(ranges)) => {
+                debug!(target: "sync::stages::snap", ranges = ranges.len(), "Resuming state download");
+                SyncPhase::Download(StateDownload::new(ranges))
+            }
+            Some(SnapSyncProgress::Trie) => {
+                debug!(target: "sync::stages::snap", "Rebuilding state trie");
+                clear_trie(provider)?;
+                SyncPhase::Trie(None)
+            }
+        })
+    }
+
+    /// Moves the pipeline to the pivot block, once its state is complete.
+    fn finish<Provider>(
+        &mut self,
+        provider: &Provider,
+        pivot: Pivot,
+    ) -> Result<ExecOutput, StageError>
+    where
+        Provider: DBProvider<Tx: DbTxMut>
+            + BlockReader
+            + StaticFileProviderFactory<Primitives: NodePrimitives<Receipt: Value>>
+            + StorageSettingsCache
+            + StageCheckpointWriter
+            + PruneCheckpointWriter,
+    {
+        info!(target: "sync::stages::snap", number = pivot.number, state_root = %pivot.state_root, "Snap sync finished");
+
+        for stage_id in SYNCED_STAGES {
+            provider.save_stage_checkpoint(stage_id, StageCheckpoint::new(pivot.number))?;
+        }
+
+        // Static files of the skipped stages need to be at the pivot block, so that the stages can
+        // append to them.
+        let static_file_provider = provider.static_file_provider();
+        let highest_block = |segment| {
+            static_file_provider.get_highest_static_file_block(segment).unwrap_or_default()
+        };
+        EitherWriter::new_senders(provider, highest_block(StaticFileSegment::TransactionSenders))?
+            .ensure_at_block(pivot.number)?;
+        EitherWriter::new_receipts(provider, highest_block(StaticFileSegment::Receipts))?
+            .ensure_at_block(pivot.number)?;
+        EitherWriter::new_account_changesets(
+            provider,
+            highest_block(StaticFileSegment::AccountChangeSets),
+        )?
+        .ensure_at_block(pivot.number)?;
+        EitherWriter::new_storage_changesets(
+            provider,
+            highest_block(StaticFileSegment::StorageChangeSets),
+        )?
+        .ensure_at_block(pivot.number)?;
+
+        // History before the pivot block was never written, which is the same as it being pruned.
+        let tx_number = provider
+            .block_body_indices(pivot.number)?
+            .and_then(|indices| indices.next_tx_num().checked_sub(1));
+        for (segment, tx_number) in [
+            (PruneSegment::SenderRecovery, tx_number),
+            (PruneSegment::Receipts, tx_number),
+            (PruneSegment::AccountHistory, None),
+            (PruneSegment::StorageHistory, None),
+        ] {
+            provider.save_prune_checkpoint(
+                segment,
+                PruneCheckpoint {
+                    block_number: Some(pivot.number),
+                    tx_number,
+                    prune_mode: PruneMode::Before(pivot.number + 1),
+                },
+            )?;
+        }
+
+        provider.save_stage_checkpoint_progress(StageId::SnapSync, Vec::new())?;
+        self.phase = None;
+
+        Ok(ExecOutput::done(StageCheckpoint::new(pivot.number)))
+    }
+}
+
+impl<C: SnapClient> fmt::Debug for SnapSyncStage<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SnapSyncStage")
+            .field("client", &self.client)
+            .field("config", &self.config)
+            .field("pivot", &self.pivot)
+            .field("phase", &self.phase)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Provider, C> Stage<Provider> for SnapSyncStage<C>
+where
+    Provider: DBProvider<Tx: DbTxMut>
+        + BlockReader
+        + HeaderProvider
+        + StaticFileProviderFactory<Primitives: NodePrimitives<Receipt: Value>>
+        + StorageSettingsCache
+        + StateWriter
+        + TrieWriter
+        + StageCheckpointReader
+        + StageCheckpointWriter
+        + PruneCheckpointWriter,
+    C: SnapClient + 'static,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::SnapSync
+    }
+
+    fn poll_execute_ready(
+        &mut self,
+        cx: &mut Context<'_>,
+        input: ExecInput,
+    ) -> Poll<Result<(), StageError>> {
+        // the pivot and the phase are set up on execution
+        let (Some(pivot), Some(phase)) = (self.pivot, self.phase.as_mut()) else {
+            return Poll::Ready(Ok(()))
+        };
+        if input.target_reached() || input.target() != pivot.number {
+            return Poll::Ready(Ok(()))
+        }
+
+        loop {
+            let poll = match phase {
+                SyncPhase::Download(download) => {
+                    download.poll(cx, &self.client, pivot.state_root, &self.config)
+                }
+                SyncPhase::Trie(_) => return Poll::Ready(Ok(())),
+                SyncPhase::Heal(heal) => {
+                    heal.poll(cx, &self.client, pivot.state_root, &self.config)
+                }
+            };
+            if poll.is_ready() {
+                return Poll::Ready(Ok(()))
+            }
+
+            // Requests are only sent if there are connected peers, so retry periodically until
+            // peers are available.
+            if !phase.is_idle() || self.client.num_connected_peers() > 0 {
+                return Poll::Pending
+            }
+            let retry = self
+                .retry
+                .get_or_insert_with(|| Box::pin(tokio::time::sleep(NO_PEERS_RETRY_DELAY)));
+            ready!(retry.as_mut().poll(cx));
+            self.retry = None;
+        }
+    }
+
+    /// Download the state of the target block, if the node has not executed any blocks yet.
+    fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        // Once blocks are executed, the state is maintained by the other stages.
+        let genesis_block = provider.static_file_provider().genesis_block_number();
+        let executed_block =
+            provider.get_stage_checkpoint(StageId::Execution)?.unwrap_or_default().block_number;
+        if executed_block > genesis_block {
+            self.phase = None;
+            return Ok(ExecOutput::done(StageCheckpoint::new(input.target())))
+        }
+
+        if !provider.cached_storage_settings().use_hashed_state() {
+            return Err(StageError::Fatal("snap sync requires the v2 storage layout".into()))
+        }
+
+        let pivot = self.update_pivot(provider, input.target())?;
+        if self.phase.is_none() {
+            self.phase = Some(self.restore_phase(provider)?);
+        }
+        let Some(phase) = self.phase.as_mut() else { unreachable!("phase is restored above") };
+
+        match phase {
+            SyncPhase::Download(download) => {
+                download.write(provider)?;
+
+                if download.is_finished() {
+                    info!(target: "sync::stages::snap", number = pivot.number, "State download finished, computing state trie");
+                    clear_trie(provider)?;
+                    provider.save_stage_checkpoint_progress(
+                        StageId::SnapSync,
+                        SnapSyncProgress::Trie.encode(),
+                    )?;
+                    *phase = SyncPhase::Trie(None);
+                    return Ok(ExecOutput::in_progress(input.checkpoint()))
+                }
+
+                provider.save_stage_checkpoint_progress(
+                    StageId::SnapSync,
+                    SnapSyncProgress::Download(download.ranges()).encode(),
+                )?;
+                info!(
+                    target: "sync::stages::snap",
+                    number = pivot.number,
+                    progress = %format_args!("{:.2}%", download.progress() * 100.0),
+                    accounts = download.stats().accounts,
+                    storage_slots = download.stats().storage_slots,
+                    bytecodes = download.stats().bytecodes,
+                    "Downloading state"
+                );
+
+                if download.take_stale() {
+                    info!(target: "sync::stages::snap", number = pivot.number, "State of the pivot block is no longer served by peers, waiting for a newer target");
+                    return Ok(ExecOutput::done(input.checkpoint()))
+                }
+            }
+            SyncPhase::Trie(state) => {
+                let tx = provider.tx_ref();
+                let progress = reth_trie_db::with_adapter!(provider, |A| {
+                    DbStateRoot::<_, A>::from_tx(tx)
+                        .with_intermediate_state(state.take().map(|state| *state))
+                        .with_threshold(self.config.commit_threshold)
+                        .root_with_progress()
+                })
+                .map_err(|error| StageError::Fatal(Box::new(error)))?;
+
+                match progress {
+                    StateRootProgress::Progress(intermediate, entries, updates) => {
+                        provider.write_trie_updates(updates)?;
+                        debug!(target: "sync::stages::snap", entries, "Computing state trie");
+                        *state = Some(intermediate);
+                    }
+                    StateRootProgress::Complete(root, _, updates) => {
+                        provider.write_trie_updates(updates)?;
+                        if root == pivot.state_root {
+                            return self.finish(provider, pivot)
+                        }
+
+                        info!(target: "sync::stages::snap", number = pivot.number, %root, expected = %pivot.state_root, "Healing state trie");
+                        *phase = SyncPhase::Heal(StateHeal::new(pivot.state_root));
+                    }
+                }
+            }
+            SyncPhase::Heal(heal) => {
+                heal.write(provider)?;
+                debug!(
+                    target: "sync::stages::snap",
+                    number = pivot.number,
+                    accounts = heal.stats().accounts,
+                    storage_slots = heal.stats().storage_slots,
+                    trie_nodes = heal.stats().trie_nodes,
+                    "Healing state trie"
+                );
+
+                if heal.take_stale() {
+                    info!(target: "sync::stages::snap", number = pivot.number, "State of the pivot block is no longer served by peers, waiting for a newer target");
+                    return Ok(ExecOutput::done(input.checkpoint()))
+                }
+
+                if heal.is_round_finished() {
+                    let root = heal.update_trie(provider)?;
+                    if root == pivot.state_root {
+                        return self.finish(provider, pivot)
+                    }
+
+                    debug!(target: "sync::stages::snap", %root, expected = %pivot.state_root, "Starting another healing round");
+                    heal.start_round(pivot.state_root);
+                }
+            }
+        }
+
+        Ok(ExecOutput::in_progress(input.checkpoint()))
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        _provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        // The state is unwound by the stages that maintain it after the pivot block.
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
+    }
+}
+
+/// The block whose state is synced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Pivot {
+    /// The number of the block.
+    number: BlockNumber,
+    /// The state root of the block.
+    state_root: B256,
+}
+
+/// The phases of the snap sync.
+enum SyncPhase<F> {
+    /// Downloading the ranges of the state.
+    Download(StateDownload<F>),
+    /// Computing the state trie from the downloaded state.
+    Trie(Option<Box<IntermediateStateRootState>>),
+    /// Healing the state trie with the trie nodes of the pivot state.
+    Heal(StateHeal<F>),
+}
+
+impl<F> SyncPhase<F> {
+    /// Returns `true` if there are no requests in flight.
+    fn is_idle(&self) -> bool {
+        match self {
+            Self::Download(download) => download.is_idle(),
+            Self::Trie(_) => true,
+            Self::Heal(heal) => heal.is_idle(),
+        }
+    }
+}
+
+impl<F> fmt::Debug for SyncPhase<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Download(download) => f.debug_tuple("Download").field(download).finish(),
+            Self::Trie(state) => f.debug_tuple("Trie").field(&state.is_some()).finish(),
+            Self::Heal(heal) => f.debug_tuple("Heal").field(heal).finish(),
+        }
+    }
+}
+
+/// The progress of the snap sync that is persisted in the stage checkpoint.
+///
+/// The trie is rebuilt from scratch after a restart, so the healing progress is not persisted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SnapSyncProgress {
+    /// The account ranges that are not downloaded yet, as pairs of the next and the last key.
+    Download(Vec<(B256, B256)>),
+    /// The state is downloaded, the trie needs to be computed and healed.
+    Trie,
+}
+
+impl SnapSyncProgress {
+    const DOWNLOAD: u8 = 0;
+    const TRIE: u8 = 1;
+
+    fn encode(&self) -> Vec<u8> {
+        match self {
+            Self::Download(ranges) => {
+                let mut buf = Vec::with_capacity(1 + ranges.len() * 64);
+                buf.push(Self::DOWNLOAD);
+                for (next, limit) in ranges {
+                    buf.extend_from_slice(next.as_slice());
+                    buf.extend_from_slice(limit.as_slice());
+                }
+                buf
+            }
+            Self::Trie => vec![Self::TRIE],
+        }
+    }
+
+    fn decode(buf: &[u8]) -> Option<Self> {
+        match buf.split_first()? {
+            (&Self::DOWNLOAD, ranges) if ranges.len() % 64 == 0 => Some(Self::Download(
+                ranges
+                    .chunks_exact(64)
+                    .map(|range| (B256::from_slice(&range[..32]), B256::from_slice(&range[32..])))
+                    .collect(),
+            )),
+            (&Self::TRIE, []) => Some(Self::Trie),
+            _ => None,
+        }
+    }
+}
+
+/// A request in flight, along with the context it was sent for.
+struct SnapRequest<R, F> {
+    /// The context of the request, taken when the response is received.
+    request: Option<R>,
+    /// The response future.
+    fut: F,
+}
+
+impl<R, F> SnapRequest<R, F> {
+    const fn new(request: R, fut: F) -> Self {
+        Self { request: Some(request), fut }
+    }
+}
+
+impl<R, F> Future for SnapRequest<R, F>
+where
+    R: Unpin,
+    F: Future<Output = PeerRequestResult<SnapResponse>> + Unpin,
+{
+    type Output = (R, PeerRequestResult<SnapResponse>);
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let response = ready!(self.fut.poll_unpin(cx));
+        Poll::Ready((self.request.take().expect("polled after completion"), response))
+    }
+}
+
+/// Splits the key space into the given number of consecutive ranges.
+fn account_ranges(count: usize) -> Vec<(B256, B256)> {
+    let count = count.max(1);
+    let step = alloy_primitives::U256::MAX / alloy_primitives::U256::from(count);
+    (0..count)
+        .map(|index| {
+            let next = step * alloy_primitives::U256::from(index);
+            let limit = if index + 1 == count {
+                alloy_primitives::U256::MAX
+            } else {
+                next + step - alloy_primitives::U256::from(1)
+            };
+            (B256::from(next), B256::from(limit))
+        })
+        .collect()
+}
+
+/// Clears the trie tables, so that the trie can be computed from scratch.
+fn clear_trie<Provider: DBProvider<Tx: DbTxMut>>(provider: &Provider) -> Result<(), StageError> {
+    provider.tx_ref().clear::<tables::AccountsTrie>()?;
+    provider.tx_ref().clear::<tables::StoragesTrie>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress_roundtrip() {
+        for progress in [
+            SnapSyncProgress::Download(Vec::new()),
+            SnapSyncProgress::Download(account_ranges(16)),
+            SnapSyncProgress::Trie,
+        ] {
+            assert_eq!(SnapSyncProgress::decode(&progress.encode()), Some(progress));
+        }
+
+        assert_eq!(SnapSyncProgress::decode(&[]), None);
+        assert_eq!(SnapSyncProgress::decode(&[SnapSyncProgress::DOWNLOAD, 0]), None);
+        assert_eq!(SnapSyncProgress::decode(&[SnapSyncProgress::TRIE, 0]), None);
+    }
+
+    #[test]
+    fn account_ranges_cover_key_space() {
+        for count in [0, 1, 3, 16] {
+            let ranges = account_ranges(count);
+            assert_eq!(ranges.len(), count.max(1));
+            assert_eq!(ranges.first().unwrap().0, B256::ZERO);
+            assert_eq!(ranges.last().unwrap().1, B256::repeat_byte(0xff));
+            for pair in ranges.windows(2) {
+                let (_, limit) = pair[0];
+                let (next, _) = pair[1];
+                assert_eq!(
+                    alloy_primitives::U256::from_be_bytes(limit.0) +
+                        alloy_primitives::U256::from(1),
+                    alloy_primitives::U256::from_be_bytes(next.0)
+                );
+            }
+        }
+    }
+}
@@ -620,3 +620,104 @@ where
     list.clear();
     Ok(())
 }
+
+/// Loads the collected indices into a table sharded by [`ShardedKey`], merging them with the last
+/// shard of each key unless the table is being built from scratch.
+///
+/// Unlike [`load_account_history`], the keys of the collector are the partial keys with the
+/// highest index of each collected list.
+pub(crate) fn load_sharded_indices<T, K, C>(
+    mut collector: Collector<T::Key, BlockNumberList>,
+    append_only: bool,
+    cursor: &mut C,
+) -> Result<(), StageError>
+where
+    T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+    K: Copy + Eq,
+    C: DbCursorRO<T> + DbCursorRW<T>,
+{
+    let mut current_key = None;
+    let mut current_list = Vec::<u64>::new();
+
+    for element in collector.iter()? {
+        let (k, v) = element?;
+        let sharded_key = T::Key::decode_owned(k)?;
+        let new_list = BlockNumberList::decompress_owned(v)?;
+        let key = sharded_key.key;
+
+        if current_key != Some(key) {
+            if let Some(prev_key) = current_key {
+                flush_sharded_indices::<T, _, _>(
+                    prev_key,
+                    &mut current_list,
+                    true,
+                    append_only,
+                    cursor,
+                )?;
+            }
+
+            current_key = Some(key);
+            if !append_only &&
+                let Some((_, last_shard)) = cursor.seek_exact(ShardedKey::last(key))?
+            {
+                current_list.extend(last_shard.iter());
+            }
+        }
+
+        current_list.extend(new_list.iter());
+        flush_sharded_indices::<T, _, _>(key, &mut current_list, false, append_only, cursor)?;
+    }
+
+    if let Some(key) = current_key {
+        flush_sharded_indices::<T, _, _>(key, &mut current_list, true, append_only, cursor)?;
+    }
+
+    Ok(())
+}
+
+/// Writes the complete shards of `list`.
+///
+/// Unless `is_last` is set, the trailing shard is kept buffered so that further indices can be
+/// appended to it. Otherwise it's written under the `u64::MAX` key.
+fn flush_sharded_indices<T, K, C>(
+    key: K,
+    list: &mut Vec<u64>,
+    is_last: bool,
+    append_only: bool,
+    cursor: &mut C,
+) -> Result<(), StageError>
+where
+    T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+    K: Copy,
+    C: DbCursorRO<T> + DbCursorRW<T>,
+{
+    let keep = if is_last {
+        0
+    } else {
+        match list.len() % NUM_OF_INDICES_IN_SHARD {
+            0 => list.len().min(NUM_OF_INDICES_IN_SHARD),
+            remainder => remainder,
+        }
+    };
+    let flush_len = list.len() - keep;
+
+    let num_chunks = flush_len.div_ceil(NUM_OF_INDICES_IN_SHARD);
+    for (i, chunk) in list[..flush_len].chunks(NUM_OF_INDICES_IN_SHARD).enumerate() {
+        let highest = if is_last && i == num_chunks - 1 {
+            u64::MAX
+        } else {
+            *chunk.last().expect("chunk is non-empty")
+        };
+        let sharded_key = ShardedKey::new(key, highest);
+        let value = BlockNumberList::new_pre_sorted(chunk.iter().copied());
+
+        if append_only {
+            cursor.append(sharded_key, &value)?;
+        } else {
+            cursor.upsert(sharded_key, &value)?;
+        }
+    }
+
+    list.drain(..flush_len);
+    Ok(())
+}
@@ -443,6 +443,27 @@ tables! {
         type Value = BlockNumberList;
     }
 
+    /// Stores pointers to the transactions that touched an address, as a sender, recipient,
+    /// created contract or log emitter.
+    ///
+    /// Sharded like [`AccountsHistory`], with the last shard of an address keyed by `u64::MAX`,
+    /// but the list holds [`TxNumber`]s instead of block numbers.
+    table AddressTransactions {
+        type Key = ShardedKey<Address>;
+        type Value = BlockNumberList;
+    }
+
+    /// Stores the positions of the logs emitted by an address or carrying a topic.
+    ///
+    /// Keys are the hashed log values of `reth_storage_api::log_index`, one per emitter address
+    /// and one per topic and topic position. Sharded like [`AccountsHistory`], but the list holds
+    /// log positions, the block number in the upper 40 bits and the index of the log in its block
+    /// in the lower 24 bits.
+    table LogIndex {
+        type Key = ShardedKey<B256>;
+        type Value = BlockNumberList;
+    }
+
     /// Stores the state of an account before a certain transaction changed it.
     /// Change on state can be: account is created, selfdestructed, touched while empty
     /// or changed balance,nonce.
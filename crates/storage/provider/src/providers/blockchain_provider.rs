@@ -17,7 +17,8 @@ use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash, TxNumber, B256};
 use alloy_rpc_types_engine::ForkchoiceState;
 use reth_chain_state::{
     BlockState, CanonicalInMemoryState, ForkChoiceNotifications, ForkChoiceSubscriptions,
-    MemoryOverlayStateProvider, PersistedBlockNotifications, PersistedBlockSubscriptions,
+    LazyOverlay, MemoryOverlayStateProvider, PersistedBlockNotifications,
+    PersistedBlockSubscriptions,
 };
 use reth_chainspec::ChainInfo;
 use reth_db_api::models::{AccountBeforeTx, BlockNumberAddress, StoredBlockBodyIndices};
@@ -27,7 +28,11 @@ use reth_primitives_traits::{Account, RecoveredBlock, SealedHeader, StorageEntry
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
-use reth_storage_api::{BlockBodyIndicesProvider, NodePrimitivesProvider, StorageChangeSetReader};
+use reth_storage_api::{
+    AddressTransactionsReader, BlockBodyIndicesProvider, HashedStateProviderFactory,
+    HashedStateRangeReaderBox, LogIndexQuery, LogIndexReader, NodePrimitivesProvider,
+    PersistedHashedState, StorageChangeSetReader,
+};
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{HashedPostState, KeccakKeyHasher};
 use revm_database::BundleState;
@@ -149,6 +154,29 @@ impl<N: NodeTypesWithDB> BalProvider for BlockchainProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> HashedStateProviderFactory for BlockchainProvider<N> {
+    fn persisted_hashed_state(&self) -> ProviderResult<PersistedHashedState> {
+        self.database.persisted_hashed_state()
+    }
+
+    fn hashed_state_by_block_hash(
+        &self,
+        block_hash: B256,
+    ) -> ProviderResult<HashedStateRangeReaderBox> {
+        let Some(block_state) = self.canonical_in_memory_state.state_by_hash(block_hash) else {
+            return self.database.hashed_state_by_block_hash(block_hash)
+        };
+
+        // overlay the in-memory blocks on top of the persisted block they are anchored on
+        let anchor_hash = block_state.anchor().hash;
+        let handles =
+            block_state.chain().map(|state| state.block_ref().trie_data_handle()).collect();
+        trace!(target: "providers::blockchain", ?block_hash, ?anchor_hash, "Returning in-memory hashed state provider");
+        self.database
+            .overlay_hashed_state(anchor_hash, Some(LazyOverlay::new(anchor_hash, handles)))
+    }
+}
+
 impl<N: ProviderNodeTypes> DatabaseProviderFactory for BlockchainProvider<N> {
     type DB = N::DB;
     type Provider = <ProviderFactory<N> as DatabaseProviderFactory>::Provider;
@@ -483,6 +511,47 @@ impl<N: ProviderNodeTypes> StageCheckpointReader for BlockchainProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> AddressTransactionsReader for BlockchainProvider<N> {
+    fn address_transactions_block_range(
+        &self,
+    ) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.consistent_provider()?.address_transactions_block_range()
+    }
+
+    fn address_transactions_before(
+        &self,
+        address: Address,
+        tx_number: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.consistent_provider()?.address_transactions_before(address, tx_number, limit)
+    }
+
+    fn address_transactions_after(
+        &self,
+        address: Address,
+        tx_number: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.consistent_provider()?.address_transactions_after(address, tx_number, limit)
+    }
+}
+
+impl<N: ProviderNodeTypes> LogIndexReader for BlockchainProvider<N> {
+    fn log_index_block_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.consistent_provider()?.log_index_block_range()
+    }
+
+    fn log_index_positions(
+        &self,
+        query: &LogIndexQuery,
+        block_range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<u64>> {
+        self.consistent_provider()?.log_index_positions(query, block_range, limit)
+    }
+}
+
 impl<N: ProviderNodeTypes> PruneCheckpointReader for BlockchainProvider<N> {
     fn get_prune_checkpoint(
         &self,
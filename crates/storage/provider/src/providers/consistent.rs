@@ -23,8 +23,9 @@ use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
-    BlockBodyIndicesProvider, DatabaseProviderFactory, NodePrimitivesProvider, StateProvider,
-    StateProviderBox, StorageChangeSetReader, TryIntoHistoricalStateProvider,
+    AddressTransactionsReader, BlockBodyIndicesProvider, DatabaseProviderFactory, LogIndexQuery,
+    LogIndexReader, NodePrimitivesProvider, StateProvider, StateProviderBox,
+    StorageChangeSetReader, TryIntoHistoricalStateProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
 use revm_database::states::PlainStorageRevert;
@@ -1047,6 +1048,47 @@ impl<N: ProviderNodeTypes> StageCheckpointReader for ConsistentProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> AddressTransactionsReader for ConsistentProvider<N> {
+    fn address_transactions_block_range(
+        &self,
+    ) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.storage_provider.address_transactions_block_range()
+    }
+
+    fn address_transactions_before(
+        &self,
+        address: Address,
+        tx_number: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.storage_provider.address_transactions_before(address, tx_number, limit)
+    }
+
+    fn address_transactions_after(
+        &self,
+        address: Address,
+        tx_number: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.storage_provider.address_transactions_after(address, tx_number, limit)
+    }
+}
+
+impl<N: ProviderNodeTypes> LogIndexReader for ConsistentProvider<N> {
+    fn log_index_block_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.storage_provider.log_index_block_range()
+    }
+
+    fn log_index_positions(
+        &self,
+        query: &LogIndexQuery,
+        block_range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<u64>> {
+        self.storage_provider.log_index_positions(query, block_range, limit)
+    }
+}
+
 impl<N: ProviderNodeTypes> PruneCheckpointReader for ConsistentProvider<N> {
     fn get_prune_checkpoint(
         &self,
@@ -1,7 +1,8 @@
 use crate::{
     providers::{
-        state::latest::LatestStateProvider, NodeTypesForProvider, RocksDBProvider,
-        StaticFileProvider, StaticFileProviderRWRefMut,
+        state::latest::LatestStateProvider, NodeTypesForProvider, OverlayBuilder,
+        OverlayStateProviderFactory, RocksDBProvider, StaticFileProvider,
+        StaticFileProviderRWRefMut,
     },
     to_range,
     traits::{BlockSource, ReceiptProvider},
@@ -11,12 +12,13 @@ use crate::{
     RocksDBProviderFactory, StageCheckpointReader, StateProviderBox, StaticFileProviderFactory,
     StaticFileWriter, TransactionVariant, TransactionsProvider,
 };
-use alloy_consensus::transaction::TransactionMeta;
+use alloy_consensus::{transaction::TransactionMeta, BlockHeader};
 use alloy_eips::BlockHashOrNumber;
 use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash, TxNumber, B256};
 use core::fmt;
 use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use parking_lot::RwLock;
+use reth_chain_state::LazyOverlay;
 use reth_chainspec::ChainInfo;
 use reth_db::{init_db, mdbx::DatabaseArguments, DatabaseEnv};
 use reth_db_api::{database::Database, models::StoredBlockBodyIndices};
@@ -29,8 +31,10 @@ use reth_prune_types::{PruneCheckpoint, PruneModes, PruneSegment, MINIMUM_UNWIND
 use reth_stages_types::{PipelineTarget, StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
-    BlockBodyIndicesProvider, ChainStateBlockReader, ChainStateBlockWriter, DBProvider,
-    NodePrimitivesProvider, StorageSettings, StorageSettingsCache, TryIntoHistoricalStateProvider,
+    AddressTransactionsReader, BlockBodyIndicesProvider, ChainStateBlockReader,
+    ChainStateBlockWriter, DBProvider, DatabaseProviderROFactory, HashedStateProviderFactory,
+    HashedStateRangeReaderBox, LogIndexQuery, LogIndexReader, NodePrimitivesProvider,
+    PersistedHashedState, StorageSettings, StorageSettingsCache, TryIntoHistoricalStateProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::HashedPostState;
@@ -592,6 +596,48 @@ impl<N: NodeTypesWithDB> BalProvider for ProviderFactory<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> HashedStateProviderFactory for ProviderFactory<N> {
+    fn persisted_hashed_state(&self) -> ProviderResult<PersistedHashedState> {
+        let provider = self.database_provider_ro()?;
+        let number = provider.best_block_number()?;
+        let header = provider
+            .sealed_header(number)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?;
+        trace!(target: "providers::db", ?number, "Returning persisted hashed state provider");
+        Ok(PersistedHashedState {
+            block: header.num_hash(),
+            state_root: header.state_root(),
+            provider: Box::new(LatestStateProvider::new(provider)),
+        })
+    }
+
+    fn hashed_state_by_block_hash(
+        &self,
+        block_hash: B256,
+    ) -> ProviderResult<HashedStateRangeReaderBox> {
+        trace!(target: "providers::db", ?block_hash, "Returning hashed state provider for block");
+        self.overlay_hashed_state(block_hash, None)
+    }
+}
+
+impl<N: ProviderNodeTypes> ProviderFactory<N> {
+    /// Returns the hashed state after the given persisted block, with the optional lazy overlay
+    /// applied on top of it.
+    pub(crate) fn overlay_hashed_state(
+        &self,
+        block_hash: B256,
+        lazy_overlay: Option<LazyOverlay>,
+    ) -> ProviderResult<HashedStateRangeReaderBox> {
+        let overlay_builder = OverlayBuilder::new(self.changeset_cache.clone())
+            .with_block_hash(Some(block_hash))
+            .with_lazy_overlay(lazy_overlay);
+        Ok(Box::new(
+            OverlayStateProviderFactory::new(self.clone(), overlay_builder)
+                .database_provider_ro()?,
+        ))
+    }
+}
+
 impl<N: ProviderNodeTypes> DatabaseProviderFactory for ProviderFactory<N> {
     type DB = N::DB;
     type Provider = DatabaseProvider<<N::DB as Database>::TX, N>;
@@ -917,6 +963,47 @@ impl<N: ProviderNodeTypes> StageCheckpointReader for ProviderFactory<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> AddressTransactionsReader for ProviderFactory<N> {
+    fn address_transactions_block_range(
+        &self,
+    ) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.provider()?.address_transactions_block_range()
+    }
+
+    fn address_transactions_before(
+        &self,
+        address: Address,
+        tx_number: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.provider()?.address_transactions_before(address, tx_number, limit)
+    }
+
+    fn address_transactions_after(
+        &self,
+        address: Address,
+        tx_number: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.provider()?.address_transactions_after(address, tx_number, limit)
+    }
+}
+
+impl<N: ProviderNodeTypes> LogIndexReader for ProviderFactory<N> {
+    fn log_index_block_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.provider()?.log_index_block_range()
+    }
+
+    fn log_index_positions(
+        &self,
+        query: &LogIndexQuery,
+        block_range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<u64>> {
+        self.provider()?.log_index_positions(query, block_range, limit)
+    }
+}
+
 impl<N: NodeTypesWithDB> ChainSpecProvider for ProviderFactory<N> {
     type ChainSpec = N::ChainSpec;
 
@@ -60,9 +60,11 @@ use reth_prune_types::{
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
-    BlockBodyIndicesProvider, BlockBodyReader, MetadataProvider, MetadataWriter,
-    NodePrimitivesProvider, StateProvider, StateReader, StateWriteConfig, StorageChangeSetReader,
-    StoragePath, StorageSettingsCache, TryIntoHistoricalStateProvider, WriteStateInput,
+    log_index_keys, log_position, touched_addresses, AddressTransactionsReader,
+    BlockBodyIndicesProvider, BlockBodyReader, LogIndexQuery, LogIndexReader, LogIndexWriter,
+    MetadataProvider, MetadataWriter, NodePrimitivesProvider, StateProvider, StateReader,
+    StateWriteConfig, StorageChangeSetReader, StoragePath, StorageSettingsCache,
+    TryIntoHistoricalStateProvider, WriteStateInput,
 };
 use reth_storage_errors::provider::{ProviderResult, StaticFileWriterError};
 use reth_trie::{
@@ -737,10 +739,22 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
                 timings.write_trie_updates += start.elapsed();
             }
 
-            // Full mode: update history indices
+            // Full mode: update history and log indices
             if save_mode.with_state() {
                 let start = Instant::now();
                 self.update_history_indices(first_number..=last_block_number)?;
+                if self.is_log_index_enabled() {
+                    if self.log_index_start()?.is_none() {
+                        self.write_log_index_start(first_number)?;
+                    }
+                    self.insert_blocks_log_index(&blocks)?;
+                }
+                if self.is_address_transactions_enabled() {
+                    if self.address_transactions_start()?.is_none() {
+                        self.write_address_transactions_start(first_number)?;
+                    }
+                    self.insert_blocks_address_transactions(&blocks, &tx_nums)?;
+                }
                 timings.update_history_indices = start.elapsed();
             }
 
@@ -1029,6 +1043,54 @@ where
     Ok(Vec::new())
 }
 
+/// Iterates over the positions of the logs of a single key in [`tables::LogIndex`].
+struct LogPostings<C> {
+    cursor: C,
+    key: B256,
+    /// Positions of the loaded shard.
+    shard: Vec<u64>,
+    /// Highest position covered by the loaded shard, `None` if no shard was loaded yet.
+    shard_end: Option<u64>,
+}
+
+impl<C: DbCursorRO<tables::LogIndex>> LogPostings<C> {
+    const fn new(cursor: C, key: B256) -> Self {
+        Self { cursor, key, shard: Vec::new(), shard_end: None }
+    }
+
+    /// Returns the first position of the key that is greater than or equal to `position`.
+    ///
+    /// Loaded shards are reused, so `position` is expected to only grow between calls.
+    fn seek(&mut self, mut position: u64) -> ProviderResult<Option<u64>> {
+        loop {
+            if let Some(shard_end) = self.shard_end &&
+                position <= shard_end
+            {
+                let idx = self.shard.partition_point(|p| *p < position);
+                if let Some(found) = self.shard.get(idx) {
+                    return Ok(Some(*found))
+                }
+                if shard_end == u64::MAX {
+                    return Ok(None)
+                }
+                position = shard_end + 1;
+            }
+
+            match self.cursor.seek(ShardedKey::new(self.key, position))? {
+                Some((sharded_key, list)) if sharded_key.key == self.key => {
+                    self.shard = list.iter().collect();
+                    self.shard_end = Some(sharded_key.highest_block_number);
+                }
+                _ => {
+                    self.shard.clear();
+                    self.shard_end = Some(u64::MAX);
+                    return Ok(None)
+                }
+            }
+        }
+    }
+}
+
 impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
     /// Creates a provider with an inner read-only transaction.
     #[expect(clippy::too_many_arguments)]
@@ -2253,6 +2315,177 @@ impl<TX: DbTx, N: NodeTypes> StageCheckpointReader for DatabaseProvider<TX, N> {
     }
 }
 
+impl<TX: DbTx, N: NodeTypes> AddressTransactionsReader for DatabaseProvider<TX, N> {
+    fn address_transactions_block_range(
+        &self,
+    ) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        if self.prune_modes.address_transactions.is_some_and(|mode| mode.is_full()) {
+            return Ok(None)
+        }
+        let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexAddressTransactions)? else {
+            return Ok(None)
+        };
+        // The checkpoint is also advanced by the engine, so only the blocks from the recorded
+        // start on are known to be indexed.
+        let Some(indexed_from) = self.address_transactions_start()? else { return Ok(None) };
+
+        let start = self
+            .get_prune_checkpoint(PruneSegment::AddressTransactions)?
+            .and_then(|checkpoint| checkpoint.block_number)
+            .map_or(0, |block_number| block_number + 1)
+            .max(indexed_from);
+        Ok((start <= checkpoint.block_number).then(|| start..=checkpoint.block_number))
+    }
+
+    fn address_transactions_before(
+        &self,
+        address: Address,
+        tx_number: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        let mut txs = Vec::new();
+        if limit == 0 || tx_number == 0 {
+            return Ok(txs)
+        }
+
+        // Start from the shard that would contain `tx_number - 1`, or the last one of the address
+        // if all its transactions are lower.
+        let mut cursor = self.tx.cursor_read::<tables::AddressTransactions>()?;
+        let mut entry = match cursor.seek(ShardedKey::new(address, tx_number - 1))? {
+            Some((key, list)) if key.key == address => Some((key, list)),
+            Some(_) => cursor.prev()?,
+            None => cursor.last()?,
+        };
+        while let Some((key, list)) = entry {
+            if key.key != address {
+                break
+            }
+
+            for tx in list.iter().rev().skip_while(|tx| *tx >= tx_number) {
+                txs.push(tx);
+                if txs.len() == limit {
+                    return Ok(txs)
+                }
+            }
+            entry = cursor.prev()?;
+        }
+
+        Ok(txs)
+    }
+
+    fn address_transactions_after(
+        &self,
+        address: Address,
+        tx_number: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        let mut txs = Vec::new();
+        if limit == 0 {
+            return Ok(txs)
+        }
+
+        let mut cursor = self.tx.cursor_read::<tables::AddressTransactions>()?;
+        for entry in cursor.walk(Some(ShardedKey::new(address, tx_number)))? {
+            let (key, list) = entry?;
+            if key.key != address {
+                break
+            }
+
+            for tx in list.iter().skip_while(|tx| *tx < tx_number) {
+                txs.push(tx);
+                if txs.len() == limit {
+                    return Ok(txs)
+                }
+            }
+        }
+
+        Ok(txs)
+    }
+}
+
+impl<TX: DbTx, N: NodeTypes> LogIndexReader for DatabaseProvider<TX, N> {
+    fn log_index_block_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        if self.prune_modes.log_index.is_some_and(|mode| mode.is_full()) {
+            return Ok(None)
+        }
+        let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexLogs)? else {
+            return Ok(None)
+        };
+        // The checkpoint is also advanced by the engine, so only the blocks from the recorded
+        // start on are known to be indexed.
+        let Some(indexed_from) = self.log_index_start()? else { return Ok(None) };
+
+        let start = self
+            .get_prune_checkpoint(PruneSegment::LogIndex)?
+            .and_then(|checkpoint| checkpoint.block_number)
+            .map_or(0, |block_number| block_number + 1)
+            .max(indexed_from);
+        Ok((start <= checkpoint.block_number).then(|| start..=checkpoint.block_number))
+    }
+
+    fn log_index_positions(
+        &self,
+        query: &LogIndexQuery,
+        block_range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<u64>> {
+        let mut positions = Vec::new();
+        if query.is_empty() || limit == 0 {
+            return Ok(positions)
+        }
+
+        let mut conditions = query
+            .keys
+            .iter()
+            .map(|keys| {
+                keys.iter()
+                    .map(|key| {
+                        Ok(LogPostings::new(self.tx.cursor_read::<tables::LogIndex>()?, *key))
+                    })
+                    .collect::<ProviderResult<Vec<_>>>()
+            })
+            .collect::<ProviderResult<Vec<_>>>()?;
+
+        let end = log_position(block_range.end().saturating_add(1), 0);
+        let mut next = log_position(*block_range.start(), 0);
+
+        // Leapfrog over the conditions: every condition is asked for its first position at or
+        // above the current candidate, until all of them agree on it.
+        'positions: while positions.len() < limit {
+            let mut candidate = next;
+            let mut agreeing = 0;
+            for idx in (0..conditions.len()).cycle() {
+                let mut first = None;
+                for postings in &mut conditions[idx] {
+                    if let Some(position) = postings.seek(candidate)? {
+                        first = Some(first.map_or(position, |first: u64| first.min(position)));
+                    }
+                }
+
+                match first {
+                    Some(position) if position < end => {
+                        if position == candidate {
+                            agreeing += 1;
+                        } else {
+                            candidate = position;
+                            agreeing = 1;
+                        }
+                    }
+                    _ => break 'positions,
+                }
+
+                if agreeing == conditions.len() {
+                    positions.push(candidate);
+                    next = candidate + 1;
+                    continue 'positions
+                }
+            }
+        }
+
+        Ok(positions)
+    }
+}
+
 impl<TX: DbTxMut, N: NodeTypes> StageCheckpointWriter for DatabaseProvider<TX, N> {
     /// Save stage checkpoint.
     fn save_stage_checkpoint(
@@ -3443,6 +3676,165 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvi
     }
 }
 
+impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> LogIndexWriter
+    for DatabaseProvider<TX, N>
+{
+    fn insert_log_index(
+        &self,
+        index_updates: impl IntoIterator<Item = (B256, impl IntoIterator<Item = u64>)>,
+    ) -> ProviderResult<()> {
+        self.append_history_index::<_, tables::LogIndex>(index_updates, ShardedKey::new)
+    }
+
+    fn unwind_log_index(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<usize> {
+        let first_position = log_position(*range.start(), 0);
+
+        let mut keys = Vec::new();
+        for receipts in self.receipts_by_block_range(range)? {
+            for log in receipts.iter().flat_map(|receipt| receipt.logs()) {
+                keys.extend(log_index_keys(log));
+            }
+        }
+        keys.sort_unstable();
+        keys.dedup();
+
+        let mut cursor = self.tx.cursor_write::<tables::LogIndex>()?;
+        for &key in &keys {
+            let partial_shard = unwind_history_shards::<_, tables::LogIndex, _>(
+                &mut cursor,
+                ShardedKey::last(key),
+                first_position,
+                |sharded_key| sharded_key.key == key,
+            )?;
+
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(key),
+                    &BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        Ok(keys.len())
+    }
+}
+
+impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
+    /// Returns `true` if the log index is maintained.
+    fn is_log_index_enabled(&self) -> bool {
+        self.prune_modes.log_index.is_none_or(|mode| !mode.is_full())
+    }
+
+    /// Adds the logs of the given executed blocks to the log index.
+    fn insert_blocks_log_index(
+        &self,
+        blocks: &[ExecutedBlock<N::Primitives>],
+    ) -> ProviderResult<()> {
+        let mut index = BTreeMap::<B256, Vec<u64>>::new();
+        for block in blocks {
+            let block_number = block.recovered_block().number();
+            let logs = block.execution_outcome().receipts.iter().flat_map(|receipt| receipt.logs());
+            for (log_index, log) in logs.enumerate() {
+                let position = log_position(block_number, log_index as u64);
+                for key in log_index_keys(log) {
+                    index.entry(key).or_default().push(position);
+                }
+            }
+        }
+        self.insert_log_index(index)
+    }
+
+    /// Removes the logs of the blocks above `block` from the log index, if it's maintained.
+    fn unwind_log_index_above(&self, block: BlockNumber) -> ProviderResult<()> {
+        if self.is_log_index_enabled() {
+            self.unwind_log_index(block + 1..=self.last_block_number()?)?;
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the address transactions index is maintained.
+    fn is_address_transactions_enabled(&self) -> bool {
+        self.prune_modes.address_transactions.is_none_or(|mode| !mode.is_full())
+    }
+
+    /// Adds the transactions of the given executed blocks to the address transactions index.
+    ///
+    /// `tx_nums` holds the number of the first transaction of each block.
+    fn insert_blocks_address_transactions(
+        &self,
+        blocks: &[ExecutedBlock<N::Primitives>],
+        tx_nums: &[TxNumber],
+    ) -> ProviderResult<()> {
+        let mut index = BTreeMap::<Address, Vec<u64>>::new();
+        for (block, first_tx_num) in blocks.iter().zip(tx_nums) {
+            let recovered_block = block.recovered_block();
+            let transactions = recovered_block.transactions_with_sender();
+            let receipts = &block.execution_outcome().receipts;
+            for ((tx_num, (sender, transaction)), receipt) in
+                (*first_tx_num..).zip(transactions).zip(receipts)
+            {
+                for address in touched_addresses(*sender, transaction, receipt.logs()) {
+                    index.entry(address).or_default().push(tx_num);
+                }
+            }
+        }
+        self.append_history_index::<_, tables::AddressTransactions>(index, ShardedKey::new)
+    }
+
+    /// Removes the transactions of the blocks above `block` from the address transactions index,
+    /// if it's maintained.
+    fn unwind_address_transactions_above(&self, block: BlockNumber) -> ProviderResult<()> {
+        if !self.is_address_transactions_enabled() {
+            return Ok(())
+        }
+
+        let bodies = self.block_body_indices_range(block + 1..=self.last_block_number()?)?;
+        let (Some(first), Some(last)) = (bodies.first(), bodies.last()) else { return Ok(()) };
+        let tx_range = first.first_tx_num()..last.next_tx_num();
+
+        let transactions = self.transactions_by_tx_range(tx_range.clone())?;
+        let senders = self.senders_by_tx_range(tx_range.clone())?;
+        let receipts = self.receipts_by_tx_range(tx_range)?;
+        let has_senders = senders.len() == transactions.len();
+        let has_receipts = receipts.len() == transactions.len();
+
+        let mut addresses = Vec::new();
+        for (idx, transaction) in transactions.iter().enumerate() {
+            let sender = if has_senders {
+                senders[idx]
+            } else {
+                transaction
+                    .recover_signer_unchecked()
+                    .map_err(|_| ProviderError::SenderRecoveryError)?
+            };
+            let logs = if has_receipts { receipts[idx].logs() } else { &[] };
+            addresses.extend(touched_addresses(sender, transaction, logs));
+        }
+        addresses.sort_unstable();
+        addresses.dedup();
+
+        let first_tx = first.first_tx_num();
+        let mut cursor = self.tx.cursor_write::<tables::AddressTransactions>()?;
+        for address in addresses {
+            let partial_shard = unwind_history_shards::<_, tables::AddressTransactions, _>(
+                &mut cursor,
+                ShardedKey::last(address),
+                first_tx,
+                |sharded_key| sharded_key.key == address,
+            )?;
+
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(address),
+                    &BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> BlockExecutionWriter
     for DatabaseProvider<TX, N>
 {
@@ -3453,6 +3845,8 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> BlockExecutionWriter
         let range = block + 1..=self.last_block_number()?;
 
         self.unwind_trie_state_from(block + 1)?;
+        self.unwind_log_index_above(block)?;
+        self.unwind_address_transactions_above(block)?;
 
         // get execution res
         let execution_state = self.take_state_above(block)?;
@@ -3471,6 +3865,8 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> BlockExecutionWriter
 
     fn remove_block_and_execution_above(&self, block: BlockNumber) -> ProviderResult<()> {
         self.unwind_trie_state_from(block + 1)?;
+        self.unwind_log_index_above(block)?;
+        self.unwind_address_transactions_above(block)?;
 
         // remove execution res
         self.remove_state_above(block)?;
@@ -3946,7 +4342,9 @@ mod tests {
     use reth_execution_types::{AccountRevertInit, BlockExecutionOutput, BlockExecutionResult};
     use reth_primitives_traits::SealedBlock;
     use reth_storage_api::MetadataWriter;
-    use reth_testing_utils::generators::{self, random_block, BlockParams};
+    use reth_testing_utils::generators::{
+        self, random_block, random_block_range, random_receipt, BlockParams, BlockRangeParams,
+    };
     use reth_trie::{
         HashedPostState, KeccakKeyHasher, Nibbles, StoredNibbles, StoredNibblesSubKey,
     };
@@ -5260,6 +5658,126 @@ mod tests {
         run_save_blocks_and_verify(StorageMode::V2);
     }
 
+    #[test]
+    fn test_log_index_block_range_starts_at_recorded_start() {
+        let factory = create_test_provider_factory();
+
+        // A checkpoint that was never produced by indexing covers nothing
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.save_stage_checkpoint(StageId::IndexLogs, StageCheckpoint::new(100)).unwrap();
+        provider_rw.commit().unwrap();
+        assert_eq!(factory.provider().unwrap().log_index_block_range().unwrap(), None);
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.write_log_index_start(90).unwrap();
+        provider_rw.commit().unwrap();
+        assert_eq!(factory.provider().unwrap().log_index_block_range().unwrap(), Some(90..=100));
+    }
+
+    #[test]
+    fn test_address_transactions_block_range_starts_at_recorded_start() {
+        let factory = create_test_provider_factory();
+
+        // A checkpoint that was never produced by indexing covers nothing
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .save_stage_checkpoint(StageId::IndexAddressTransactions, StageCheckpoint::new(100))
+            .unwrap();
+        provider_rw.commit().unwrap();
+        assert_eq!(factory.provider().unwrap().address_transactions_block_range().unwrap(), None);
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.write_address_transactions_start(90).unwrap();
+        provider_rw.commit().unwrap();
+        assert_eq!(
+            factory.provider().unwrap().address_transactions_block_range().unwrap(),
+            Some(90..=100)
+        );
+    }
+
+    #[test]
+    fn test_save_blocks_indexes_address_transactions() {
+        let factory = create_test_provider_factory();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(
+            &mut rng,
+            0..=4,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 1..3, ..Default::default() },
+        );
+        let blocks = blocks
+            .into_iter()
+            .map(|block| {
+                let receipts = block
+                    .body()
+                    .transactions
+                    .iter()
+                    .map(|transaction| random_receipt(&mut rng, transaction, Some(1), None))
+                    .collect();
+                ExecutedBlock::new(
+                    Arc::new(block.try_recover().unwrap()),
+                    Arc::new(BlockExecutionOutput {
+                        result: BlockExecutionResult {
+                            receipts,
+                            requests: Default::default(),
+                            gas_used: 0,
+                            blob_gas_used: 0,
+                        },
+                        state: Default::default(),
+                    }),
+                    ComputedTrieData::default(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let mut expected = BTreeMap::<Address, Vec<TxNumber>>::new();
+        let mut tx_num = 0;
+        for block in &blocks {
+            let transactions = block.recovered_block().transactions_with_sender();
+            for ((sender, transaction), receipt) in
+                transactions.zip(&block.execution_outcome().receipts)
+            {
+                for address in touched_addresses(*sender, transaction, receipt.logs()) {
+                    expected.entry(address).or_default().push(tx_num);
+                }
+                tx_num += 1;
+            }
+        }
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.save_blocks(blocks, SaveBlocksMode::Full).unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        for (address, tx_nums) in &expected {
+            assert_eq!(
+                &provider.address_transactions_after(*address, 0, usize::MAX).unwrap(),
+                tx_nums
+            );
+        }
+        assert_eq!(provider.address_transactions_block_range().unwrap(), Some(0..=4));
+        drop(provider);
+
+        // Unwinding removes the transactions of the removed blocks from the index
+        let provider_rw = factory.provider_rw().unwrap();
+        let first_removed = provider_rw.block_body_indices(3).unwrap().unwrap().first_tx_num();
+        provider_rw.remove_block_and_execution_above(2).unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        for (address, tx_nums) in &expected {
+            let remaining = tx_nums
+                .iter()
+                .copied()
+                .filter(|tx_num| *tx_num < first_removed)
+                .collect::<Vec<_>>();
+            assert_eq!(
+                provider.address_transactions_after(*address, 0, usize::MAX).unwrap(),
+                remaining
+            );
+        }
+    }
+
     #[test]
     fn test_write_and_remove_state_roundtrip_v2() {
         let factory = create_test_provider_factory();
@@ -1,11 +1,16 @@
 use crate::{
     AccountReader, BlockHashReader, HashedPostStateProvider, StateProvider, StateRootProvider,
 };
-use alloy_primitives::{Address, BlockNumber, Bytes, StorageKey, StorageValue, B256};
-use reth_db_api::{cursor::DbDupCursorRO, tables, transaction::DbTx};
-use reth_primitives_traits::{Account, Bytecode};
+use alloy_primitives::{Address, BlockNumber, Bytes, StorageKey, StorageValue, B256, U256};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    tables,
+    transaction::DbTx,
+};
+use reth_primitives_traits::{Account, Bytecode, StorageEntry};
 use reth_storage_api::{
-    BytecodeReader, DBProvider, StateProofProvider, StorageRootProvider, StorageSettingsCache,
+    BytecodeReader, DBProvider, HashedStateRangeReader, StateProofProvider, StorageRootProvider,
+    StorageSettingsCache,
 };
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use reth_trie::{
@@ -283,6 +288,50 @@ impl<Provider: DBProvider + BlockHashReader + StorageSettingsCache> StateProvide
     }
 }
 
+impl<Provider: DBProvider + StorageSettingsCache> HashedStateRangeReader
+    for LatestStateProviderRef<'_, Provider>
+{
+    fn hashed_accounts_from(
+        &self,
+        start: B256,
+        max_entries: usize,
+    ) -> ProviderResult<Vec<(B256, Account)>> {
+        let mut cursor = self.tx().cursor_read::<tables::HashedAccounts>()?;
+        cursor.walk(Some(start))?.take(max_entries).map(|entry| Ok(entry?)).collect()
+    }
+
+    fn hashed_storages_from(
+        &self,
+        hashed_address: B256,
+        start: B256,
+        max_entries: usize,
+    ) -> ProviderResult<Vec<(B256, U256)>> {
+        let mut cursor = self.tx().cursor_dup_read::<tables::HashedStorages>()?;
+        let mut entries = Vec::new();
+        let mut entry = cursor.seek_by_key_subkey(hashed_address, start)?;
+        while let Some(StorageEntry { key, value }) = entry {
+            if entries.len() >= max_entries {
+                break
+            }
+            entries.push((key, value));
+            entry = cursor.next_dup_val()?;
+        }
+        Ok(entries)
+    }
+
+    fn hashed_storage_root(&self, hashed_address: B256) -> ProviderResult<B256> {
+        reth_trie_db::with_adapter!(self.0, |A| {
+            <DbStorageRoot<'_, _, A>>::from_tx_hashed(self.tx(), hashed_address)
+                .root()
+                .map_err(|err| ProviderError::Database(err.into()))
+        })
+    }
+
+    fn hashed_multiproof(&self, targets: MultiProofTargets) -> ProviderResult<MultiProof> {
+        self.multiproof(TrieInput::default(), targets)
+    }
+}
+
 impl<Provider: DBProvider + BlockHashReader> BytecodeReader
     for LatestStateProviderRef<'_, Provider>
 {
@@ -312,6 +361,35 @@ impl<Provider: DBProvider> LatestStateProvider<Provider> {
 // Delegates all provider impls to [LatestStateProviderRef]
 reth_storage_api::macros::delegate_provider_impls!(LatestStateProvider<Provider> where [Provider: DBProvider + BlockHashReader + StorageSettingsCache]);
 
+impl<Provider: DBProvider + StorageSettingsCache> HashedStateRangeReader
+    for LatestStateProvider<Provider>
+{
+    fn hashed_accounts_from(
+        &self,
+        start: B256,
+        max_entries: usize,
+    ) -> ProviderResult<Vec<(B256, Account)>> {
+        self.as_ref().hashed_accounts_from(start, max_entries)
+    }
+
+    fn hashed_storages_from(
+        &self,
+        hashed_address: B256,
+        start: B256,
+        max_entries: usize,
+    ) -> ProviderResult<Vec<(B256, U256)>> {
+        self.as_ref().hashed_storages_from(hashed_address, start, max_entries)
+    }
+
+    fn hashed_storage_root(&self, hashed_address: B256) -> ProviderResult<B256> {
+        self.as_ref().hashed_storage_root(hashed_address)
+    }
+
+    fn hashed_multiproof(&self, targets: MultiProofTargets) -> ProviderResult<MultiProof> {
+        self.as_ref().hashed_multiproof(targets)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
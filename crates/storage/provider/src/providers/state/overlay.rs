@@ -1,22 +1,27 @@
-use alloy_primitives::{BlockNumber, B256};
+use alloy_primitives::{BlockNumber, B256, U256};
 use metrics::{Counter, Histogram};
 use reth_chain_state::LazyOverlay;
 use reth_db_api::{tables, transaction::DbTx, DatabaseError};
 use reth_errors::{ProviderError, ProviderResult};
 use reth_metrics::Metrics;
-use reth_primitives_traits::dashmap::{self, DashMap};
+use reth_primitives_traits::{
+    dashmap::{self, DashMap},
+    Account,
+};
 use reth_prune_types::PruneSegment;
 use reth_stages_types::StageId;
 use reth_storage_api::{
     BlockNumReader, ChangeSetReader, DBProvider, DatabaseProviderFactory,
-    DatabaseProviderROFactory, PruneCheckpointReader, StageCheckpointReader,
-    StorageChangeSetReader, StorageSettingsCache,
+    DatabaseProviderROFactory, HashedStateRangeReader, PruneCheckpointReader,
+    StageCheckpointReader, StorageChangeSetReader, StorageSettingsCache,
 };
 use reth_trie::{
-    hashed_cursor::{HashedCursorFactory, HashedPostStateCursorFactory},
+    hashed_cursor::{HashedCursor, HashedCursorFactory, HashedPostStateCursorFactory},
+    metrics::TrieRootMetrics,
+    proof::Proof,
     trie_cursor::{InMemoryTrieCursor, TrieCursor, TrieCursorFactory, TrieStorageCursor},
     updates::TrieUpdatesSorted,
-    HashedPostStateSorted,
+    HashedPostStateSorted, MultiProof, MultiProofTargets, StorageRoot, TrieType,
 };
 use reth_trie_db::{
     ChangesetCache, DatabaseAccountTrieCursor, DatabaseHashedCursorFactory,
@@ -622,3 +627,61 @@ where
         hashed_cursor_factory.hashed_storage_cursor(hashed_address)
     }
 }
+
+impl<Provider> HashedStateRangeReader for OverlayStateProvider<Provider>
+where
+    Provider: DBProvider,
+{
+    fn hashed_accounts_from(
+        &self,
+        start: B256,
+        max_entries: usize,
+    ) -> ProviderResult<Vec<(B256, Account)>> {
+        let mut cursor = self.hashed_account_cursor()?;
+        let mut entries = Vec::new();
+        let mut entry = cursor.seek(start)?;
+        while let Some(account) = entry {
+            if entries.len() >= max_entries {
+                break
+            }
+            entries.push(account);
+            entry = cursor.next()?;
+        }
+        Ok(entries)
+    }
+
+    fn hashed_storages_from(
+        &self,
+        hashed_address: B256,
+        start: B256,
+        max_entries: usize,
+    ) -> ProviderResult<Vec<(B256, U256)>> {
+        let mut cursor = self.hashed_storage_cursor(hashed_address)?;
+        let mut entries = Vec::new();
+        let mut entry = cursor.seek(start)?;
+        while let Some(slot) = entry {
+            if entries.len() >= max_entries {
+                break
+            }
+            entries.push(slot);
+            entry = cursor.next()?;
+        }
+        Ok(entries)
+    }
+
+    fn hashed_storage_root(&self, hashed_address: B256) -> ProviderResult<B256> {
+        StorageRoot::new_hashed(
+            self,
+            self,
+            hashed_address,
+            Default::default(),
+            TrieRootMetrics::new(TrieType::Storage),
+        )
+        .root()
+        .map_err(|err| ProviderError::Database(err.into()))
+    }
+
+    fn hashed_multiproof(&self, targets: MultiProofTargets) -> ProviderResult<MultiProof> {
+        Proof::new(self, self).multiproof(targets).map_err(ProviderError::from)
+    }
+}
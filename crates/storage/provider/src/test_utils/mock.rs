@@ -10,7 +10,7 @@ use alloy_consensus::{
     transaction::{TransactionMeta, TxHashRef},
     BlockHeader,
 };
-use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumberOrTag};
+use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumHash, BlockNumberOrTag};
 use alloy_primitives::{
     keccak256,
     map::{AddressMap, B256Map, HashMap},
@@ -33,9 +33,11 @@ use reth_primitives_traits::{
 use reth_prune_types::{PruneCheckpoint, PruneModes, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    BlockBodyIndicesProvider, BytecodeReader, DBProvider, DatabaseProviderFactory,
-    HashedPostStateProvider, NodePrimitivesProvider, StageCheckpointReader, StateProofProvider,
-    StorageChangeSetReader, StorageRootProvider, StorageSettingsCache,
+    AddressTransactionsReader, BlockBodyIndicesProvider, BytecodeReader, DBProvider,
+    DatabaseProviderFactory, HashedPostStateProvider, HashedStateProviderFactory,
+    HashedStateRangeReaderBox, LogIndexQuery, LogIndexReader, NodePrimitivesProvider, NoopProvider,
+    PersistedHashedState, StageCheckpointReader, StateProofProvider, StorageChangeSetReader,
+    StorageRootProvider, StorageSettingsCache,
 };
 use reth_storage_errors::provider::{ConsistentViewError, ProviderError, ProviderResult};
 use reth_trie::{
@@ -68,6 +70,8 @@ pub struct MockEthProvider<T: NodePrimitives = EthPrimitives, ChainSpec = reth_c
     pub state_roots: Arc<Mutex<Vec<B256>>>,
     /// Local block body indices store
     pub block_body_indices: Arc<Mutex<HashMap<BlockNumber, StoredBlockBodyIndices>>>,
+    /// Local account changeset store indexed by block number
+    pub account_changesets: Arc<Mutex<BTreeMap<BlockNumber, Vec<AccountBeforeTx>>>>,
     /// Local BAL store handle
     pub bal_store: BalStoreHandle,
     tx: TxMock,
@@ -87,6 +91,7 @@ where
             chain_spec: self.chain_spec.clone(),
             state_roots: self.state_roots.clone(),
             block_body_indices: self.block_body_indices.clone(),
+            account_changesets: self.account_changesets.clone(),
             bal_store: self.bal_store.clone(),
             tx: self.tx.clone(),
             prune_modes: self.prune_modes.clone(),
@@ -105,6 +110,7 @@ impl<T: NodePrimitives> MockEthProvider<T, reth_chainspec::ChainSpec> {
             chain_spec: Arc::new(reth_chainspec::ChainSpecBuilder::mainnet().build()),
             state_roots: Default::default(),
             block_body_indices: Default::default(),
+            account_changesets: Default::default(),
             bal_store: Default::default(),
             tx: Default::default(),
             prune_modes: Default::default(),
@@ -174,6 +180,15 @@ impl<T: NodePrimitives, ChainSpec> MockEthProvider<T, ChainSpec> {
         self.block_body_indices.lock().insert(block_number, indices);
     }
 
+    /// Add the account changeset of a block to local changeset store
+    pub fn add_account_changeset(
+        &self,
+        block_number: BlockNumber,
+        changeset: Vec<AccountBeforeTx>,
+    ) {
+        self.account_changesets.lock().insert(block_number, changeset);
+    }
+
     /// Add state root to local state root store
     pub fn add_state_root(&self, state_root: B256) {
         self.state_roots.lock().push(state_root);
@@ -189,6 +204,7 @@ impl<T: NodePrimitives, ChainSpec> MockEthProvider<T, ChainSpec> {
             chain_spec: Arc::new(chain_spec),
             state_roots: self.state_roots,
             block_body_indices: self.block_body_indices,
+            account_changesets: self.account_changesets,
             bal_store: self.bal_store,
             tx: self.tx,
             prune_modes: self.prune_modes,
@@ -674,18 +690,28 @@ impl<T: NodePrimitives, ChainSpec: EthChainSpec + Send + Sync + 'static> BlockRe
 
     fn recovered_block(
         &self,
-        _id: BlockHashOrNumber,
+        id: BlockHashOrNumber,
         _transaction_kind: TransactionVariant,
     ) -> ProviderResult<Option<RecoveredBlock<Self::Block>>> {
-        Ok(None)
+        let lock = self.blocks.lock();
+        let block = match id {
+            BlockHashOrNumber::Hash(hash) => lock.get_key_value(&hash),
+            BlockHashOrNumber::Number(num) => lock.iter().find(|(_, b)| b.header().number() == num),
+        };
+        block
+            .map(|(hash, block)| {
+                let senders = block.body().recover_signers()?;
+                Ok(RecoveredBlock::new(block.clone(), senders, *hash))
+            })
+            .transpose()
     }
 
     fn sealed_block_with_senders(
         &self,
-        _id: BlockHashOrNumber,
-        _transaction_kind: TransactionVariant,
+        id: BlockHashOrNumber,
+        transaction_kind: TransactionVariant,
     ) -> ProviderResult<Option<RecoveredBlock<Self::Block>>> {
-        Ok(None)
+        self.recovered_block(id, transaction_kind)
     }
 
     fn block_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<Vec<Self::Block>> {
@@ -1006,23 +1032,106 @@ impl<T: NodePrimitives, ChainSpec: Send + Sync> BlockBodyIndicesProvider
 impl<T: NodePrimitives, ChainSpec: Send + Sync> ChangeSetReader for MockEthProvider<T, ChainSpec> {
     fn account_block_changeset(
         &self,
-        _block_number: BlockNumber,
+        block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
-        Ok(Vec::default())
+        Ok(self.account_changesets.lock().get(&block_number).cloned().unwrap_or_default())
     }
 
     fn get_account_before_block(
         &self,
-        _block_number: BlockNumber,
-        _address: Address,
+        block_number: BlockNumber,
+        address: Address,
     ) -> ProviderResult<Option<AccountBeforeTx>> {
-        Ok(None)
+        Ok(self
+            .account_changesets
+            .lock()
+            .get(&block_number)
+            .and_then(|changeset| changeset.iter().find(|change| change.address == address))
+            .cloned())
     }
 
     fn account_changesets_range(
         &self,
-        _range: impl core::ops::RangeBounds<BlockNumber>,
+        range: impl core::ops::RangeBounds<BlockNumber>,
     ) -> ProviderResult<Vec<(BlockNumber, AccountBeforeTx)>> {
+        Ok(self
+            .account_changesets
+            .lock()
+            .range(range)
+            .flat_map(|(block_number, changeset)| {
+                changeset.iter().map(|change| (*block_number, change.clone()))
+            })
+            .collect())
+    }
+}
+
+/// Serves an empty hashed state for all known headers.
+impl<T: NodePrimitives, ChainSpec: Send + Sync + 'static> HashedStateProviderFactory
+    for MockEthProvider<T, ChainSpec>
+{
+    fn persisted_hashed_state(&self) -> ProviderResult<PersistedHashedState> {
+        let headers = self.headers.lock();
+        let (hash, header) = headers
+            .iter()
+            .max_by_key(|(_, header)| header.number())
+            .ok_or(ProviderError::BestBlockNotFound)?;
+        Ok(PersistedHashedState {
+            block: BlockNumHash::new(header.number(), *hash),
+            state_root: header.state_root(),
+            provider: Box::new(NoopProvider::<ChainSpec, T>::new(self.chain_spec.clone())),
+        })
+    }
+
+    fn hashed_state_by_block_hash(
+        &self,
+        block_hash: B256,
+    ) -> ProviderResult<HashedStateRangeReaderBox> {
+        if !self.headers.lock().contains_key(&block_hash) {
+            return Err(ProviderError::BlockHashNotFound(block_hash))
+        }
+        Ok(Box::new(NoopProvider::<ChainSpec, T>::new(self.chain_spec.clone())))
+    }
+}
+
+impl<T: NodePrimitives, ChainSpec: Send + Sync> AddressTransactionsReader
+    for MockEthProvider<T, ChainSpec>
+{
+    fn address_transactions_block_range(
+        &self,
+    ) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        Ok(None)
+    }
+
+    fn address_transactions_before(
+        &self,
+        _address: Address,
+        _tx_number: TxNumber,
+        _limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        Ok(Vec::default())
+    }
+
+    fn address_transactions_after(
+        &self,
+        _address: Address,
+        _tx_number: TxNumber,
+        _limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        Ok(Vec::default())
+    }
+}
+
+impl<T: NodePrimitives, ChainSpec: Send + Sync> LogIndexReader for MockEthProvider<T, ChainSpec> {
+    fn log_index_block_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        Ok(None)
+    }
+
+    fn log_index_positions(
+        &self,
+        _query: &LogIndexQuery,
+        _block_range: RangeInclusive<BlockNumber>,
+        _limit: usize,
+    ) -> ProviderResult<Vec<u64>> {
         Ok(Vec::default())
     }
 }
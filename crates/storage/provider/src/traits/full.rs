@@ -10,7 +10,10 @@ use reth_chain_state::{
     CanonStateSubscriptions, ForkChoiceSubscriptions, PersistedBlockSubscriptions,
 };
 use reth_node_types::{BlockTy, HeaderTy, NodeTypesWithDB, ReceiptTy, TxTy};
-use reth_storage_api::{NodePrimitivesProvider, StorageChangeSetReader, StorageSettingsCache};
+use reth_storage_api::{
+    AddressTransactionsReader, HashedStateProviderFactory, LogIndexReader, NodePrimitivesProvider,
+    StorageChangeSetReader, StorageSettingsCache,
+};
 use std::fmt::Debug;
 
 /// Helper trait to unify all provider traits for simplicity.
@@ -34,6 +37,7 @@ pub trait FullProvider<N: NodeTypesWithDB>:
     > + AccountReader
     + BalProvider
     + StateProviderFactory
+    + HashedStateProviderFactory
     + StateReader
     + HashedPostStateProvider
     + ChainSpecProvider<ChainSpec = N::ChainSpec>
@@ -43,6 +47,8 @@ pub trait FullProvider<N: NodeTypesWithDB>:
     + ForkChoiceSubscriptions<Header = HeaderTy<N>>
     + PersistedBlockSubscriptions
     + StageCheckpointReader
+    + AddressTransactionsReader
+    + LogIndexReader
     + Clone
     + Debug
     + Unpin
@@ -70,6 +76,7 @@ impl<T, N: NodeTypesWithDB> FullProvider<N> for T where
         > + AccountReader
         + BalProvider
         + StateProviderFactory
+        + HashedStateProviderFactory
         + StateReader
         + HashedPostStateProvider
         + ChainSpecProvider<ChainSpec = N::ChainSpec>
@@ -79,6 +86,8 @@ impl<T, N: NodeTypesWithDB> FullProvider<N> for T where
         + ForkChoiceSubscriptions<Header = HeaderTy<N>>
         + PersistedBlockSubscriptions
         + StageCheckpointReader
+        + AddressTransactionsReader
+        + LogIndexReader
         + Clone
         + Debug
         + Unpin
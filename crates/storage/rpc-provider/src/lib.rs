@@ -54,8 +54,9 @@ use reth_prune_types::{PruneCheckpoint, PruneSegment};
 pub mod rpc_response;
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    BlockBodyIndicesProvider, BlockReaderIdExt, BlockSource, DBProvider, NodePrimitivesProvider,
-    ReceiptProviderIdExt, StatsReader,
+    AddressTransactionsReader, BlockBodyIndicesProvider, BlockReaderIdExt, BlockSource, DBProvider,
+    HashedStateProviderFactory, HashedStateRangeReaderBox, LogIndexQuery, LogIndexReader,
+    NodePrimitivesProvider, PersistedHashedState, ReceiptProviderIdExt, StatsReader,
 };
 use reth_trie::{
     updates::TrieUpdates, AccountProof, HashedPostState, KeccakKeyHasher, MultiProof, TrieInput,
@@ -1732,6 +1733,77 @@ where
     }
 }
 
+impl<P, Node, N> AddressTransactionsReader for RpcBlockchainStateProvider<P, Node, N>
+where
+    P: Provider<N> + Clone + 'static,
+    N: Network,
+    Node: NodeTypes,
+{
+    fn address_transactions_block_range(
+        &self,
+    ) -> Result<Option<RangeInclusive<BlockNumber>>, ProviderError> {
+        // there is no local index
+        Ok(None)
+    }
+
+    fn address_transactions_before(
+        &self,
+        _address: Address,
+        _tx_number: TxNumber,
+        _limit: usize,
+    ) -> Result<Vec<TxNumber>, ProviderError> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn address_transactions_after(
+        &self,
+        _address: Address,
+        _tx_number: TxNumber,
+        _limit: usize,
+    ) -> Result<Vec<TxNumber>, ProviderError> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+}
+
+impl<P, Node, N> LogIndexReader for RpcBlockchainStateProvider<P, Node, N>
+where
+    P: Provider<N> + Clone + 'static,
+    N: Network,
+    Node: NodeTypes,
+{
+    fn log_index_block_range(&self) -> Result<Option<RangeInclusive<BlockNumber>>, ProviderError> {
+        // there is no local index, logs are looked up the regular way
+        Ok(None)
+    }
+
+    fn log_index_positions(
+        &self,
+        _query: &LogIndexQuery,
+        _block_range: RangeInclusive<BlockNumber>,
+        _limit: usize,
+    ) -> Result<Vec<u64>, ProviderError> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+}
+
+impl<P, Node, N> HashedStateProviderFactory for RpcBlockchainStateProvider<P, Node, N>
+where
+    P: Provider<N> + Clone + 'static,
+    N: Network,
+    Node: NodeTypes,
+{
+    fn persisted_hashed_state(&self) -> Result<PersistedHashedState, ProviderError> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn hashed_state_by_block_hash(
+        &self,
+        _block_hash: B256,
+    ) -> Result<HashedStateRangeReaderBox, ProviderError> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+}
+
 impl<P, Node, N> ChangeSetReader for RpcBlockchainStateProvider<P, Node, N>
 where
     P: Provider<N> + Clone + 'static,
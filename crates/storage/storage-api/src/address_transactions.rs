@@ -0,0 +1,56 @@
+use alloc::vec::Vec;
+use alloy_consensus::Transaction;
+use alloy_primitives::{Address, BlockNumber, Log, TxKind, TxNumber};
+use core::ops::RangeInclusive;
+use reth_storage_errors::provider::ProviderResult;
+
+/// Returns the addresses touched by `transaction` sent by `sender`, sorted and deduplicated.
+///
+/// These are the sender, the recipient or the created contract, and the emitters of `logs`.
+pub fn touched_addresses<'a>(
+    sender: Address,
+    transaction: &impl Transaction,
+    logs: impl IntoIterator<Item = &'a Log>,
+) -> Vec<Address> {
+    let mut touched = Vec::from([sender]);
+    match transaction.kind() {
+        TxKind::Call(to) => touched.push(to),
+        TxKind::Create => touched.push(sender.create(transaction.nonce())),
+    }
+    touched.extend(logs.into_iter().map(|log| log.address));
+
+    touched.sort_unstable();
+    touched.dedup();
+    touched
+}
+
+/// Reads the index of transactions that touched an address.
+///
+/// A transaction touches an address if the address is its sender, its recipient, the contract it
+/// created or the emitter of one of its logs.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait AddressTransactionsReader: Send {
+    /// Returns the range of blocks covered by the address transactions index, or `None` if it's
+    /// not maintained.
+    fn address_transactions_block_range(
+        &self,
+    ) -> ProviderResult<Option<RangeInclusive<BlockNumber>>>;
+
+    /// Returns up to `limit` numbers of the transactions that touched `address` and are lower than
+    /// `tx_number`, in descending order.
+    fn address_transactions_before(
+        &self,
+        address: Address,
+        tx_number: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>>;
+
+    /// Returns up to `limit` numbers of the transactions that touched `address` and are greater
+    /// than or equal to `tx_number`, in ascending order.
+    fn address_transactions_after(
+        &self,
+        address: Address,
+        tx_number: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>>;
+}
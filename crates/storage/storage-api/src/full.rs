@@ -3,7 +3,8 @@
 use reth_chainspec::{ChainSpecProvider, EthereumHardforks};
 
 use crate::{
-    BlockReaderIdExt, HeaderProvider, StageCheckpointReader, StateProviderFactory,
+    AddressTransactionsReader, BlockReaderIdExt, ChangeSetReader, HashedStateProviderFactory,
+    HeaderProvider, LogIndexReader, StageCheckpointReader, StateProviderFactory,
     TransactionsProvider,
 };
 
@@ -16,6 +17,10 @@ pub trait FullRpcProvider:
     + HeaderProvider
     + TransactionsProvider
     + StageCheckpointReader
+    + AddressTransactionsReader
+    + LogIndexReader
+    + HashedStateProviderFactory
+    + ChangeSetReader
     + Clone
     + Unpin
     + 'static
@@ -29,6 +34,10 @@ impl<T> FullRpcProvider for T where
         + HeaderProvider
         + TransactionsProvider
         + StageCheckpointReader
+        + AddressTransactionsReader
+        + LogIndexReader
+        + HashedStateProviderFactory
+        + ChangeSetReader
         + Clone
         + Unpin
         + 'static
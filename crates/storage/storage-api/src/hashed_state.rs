@@ -0,0 +1,90 @@
+use crate::StateProvider;
+use alloc::{boxed::Box, vec::Vec};
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{B256, U256};
+use auto_impl::auto_impl;
+use core::fmt;
+use reth_primitives_traits::Account;
+use reth_storage_errors::provider::ProviderResult;
+use reth_trie_common::{MultiProof, MultiProofTargets};
+
+/// Reads ranges of the hashed state, ordered by hashed key.
+///
+/// This gives access to the state in trie order, which is required to serve range requests such
+/// as the ones defined by the `snap` protocol.
+#[auto_impl(&, Box)]
+pub trait HashedStateRangeReader {
+    /// Returns up to `max_entries` accounts whose hashed address is greater than or equal to
+    /// `start`, in ascending order of hashed address.
+    fn hashed_accounts_from(
+        &self,
+        start: B256,
+        max_entries: usize,
+    ) -> ProviderResult<Vec<(B256, Account)>>;
+
+    /// Returns up to `max_entries` storage slots of the account with the given hashed address whose
+    /// hashed slot is greater than or equal to `start`, in ascending order of hashed slot.
+    fn hashed_storages_from(
+        &self,
+        hashed_address: B256,
+        start: B256,
+        max_entries: usize,
+    ) -> ProviderResult<Vec<(B256, U256)>>;
+
+    /// Returns the storage root of the account with the given hashed address.
+    fn hashed_storage_root(&self, hashed_address: B256) -> ProviderResult<B256>;
+
+    /// Returns the proof of the given targets against the state.
+    fn hashed_multiproof(&self, targets: MultiProofTargets) -> ProviderResult<MultiProof>;
+}
+
+/// Type alias of boxed [`HashedStateRangeReader`].
+pub type HashedStateRangeReaderBox = Box<dyn HashedStateRangeReader + Send + 'static>;
+
+/// A [`StateProvider`] that also exposes its hashed state in trie order.
+pub trait HashedStateProvider: StateProvider + HashedStateRangeReader {}
+
+impl<T> HashedStateProvider for T where T: StateProvider + HashedStateRangeReader {}
+
+/// Type alias of boxed [`HashedStateProvider`].
+pub type HashedStateProviderBox = Box<dyn HashedStateProvider + Send + 'static>;
+
+/// The hashed state of the latest persisted block.
+pub struct PersistedHashedState {
+    /// Number and hash of the latest persisted block.
+    pub block: BlockNumHash,
+    /// State root of the latest persisted block.
+    pub state_root: B256,
+    /// Provider over the state of the latest persisted block.
+    pub provider: HashedStateProviderBox,
+}
+
+impl fmt::Debug for PersistedHashedState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PersistedHashedState")
+            .field("block", &self.block)
+            .field("state_root", &self.state_root)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Provides access to the hashed state of the latest persisted block.
+///
+/// Unlike [`StateProviderFactory::latest`](crate::StateProviderFactory::latest), this never
+/// includes blocks that are only kept in memory, so the returned state always matches the state
+/// root of a block header.
+#[auto_impl(&, Arc)]
+pub trait HashedStateProviderFactory: Send + Sync {
+    /// Returns the hashed state of the latest persisted block.
+    fn persisted_hashed_state(&self) -> ProviderResult<PersistedHashedState>;
+
+    /// Returns the hashed state after the execution of the given canonical block.
+    ///
+    /// Blocks below the persisted tip are served by reverting the hashed tables using changesets,
+    /// blocks that are only kept in memory are served by overlaying their state on top of the
+    /// persisted tables.
+    fn hashed_state_by_block_hash(
+        &self,
+        block_hash: B256,
+    ) -> ProviderResult<HashedStateRangeReaderBox>;
+}
@@ -19,6 +19,9 @@ pub use bal::*;
 mod account;
 pub use account::*;
 
+mod address_transactions;
+pub use address_transactions::*;
+
 mod block;
 pub use block::*;
 
@@ -36,6 +39,9 @@ pub use chain::*;
 mod header;
 pub use header::*;
 
+mod log_index;
+pub use log_index::*;
+
 mod prune_checkpoint;
 pub use prune_checkpoint::*;
 
@@ -57,6 +63,9 @@ pub use transactions::*;
 mod trie;
 pub use trie::*;
 
+mod hashed_state;
+pub use hashed_state::*;
+
 mod chain_info;
 pub use chain_info::*;
 
@@ -0,0 +1,94 @@
+use alloc::vec::Vec;
+use alloy_primitives::{keccak256, Address, BlockNumber, Log, B256};
+use core::ops::RangeInclusive;
+use reth_storage_errors::provider::ProviderResult;
+
+/// Number of low bits of a log position that hold the index of the log in its block.
+pub const LOG_POSITION_INDEX_BITS: u32 = 24;
+
+/// Returns the position of the `log_index`-th log of block `block_number` in the log index.
+pub const fn log_position(block_number: BlockNumber, log_index: u64) -> u64 {
+    (block_number << LOG_POSITION_INDEX_BITS) | log_index
+}
+
+/// Returns the number of the block that contains the log at `position`.
+pub const fn log_position_block(position: u64) -> BlockNumber {
+    position >> LOG_POSITION_INDEX_BITS
+}
+
+/// Returns the log index key of the logs emitted by `address`.
+pub fn log_index_address_key(address: Address) -> B256 {
+    keccak256(address)
+}
+
+/// Returns the log index key of the logs that carry `topic` as their `index`-th topic.
+pub fn log_index_topic_key(index: usize, topic: B256) -> B256 {
+    let mut preimage = [0u8; 33];
+    preimage[0] = index as u8;
+    preimage[1..].copy_from_slice(topic.as_slice());
+    keccak256(preimage)
+}
+
+/// Returns the log index keys of `log`, the key of its emitter followed by one key per topic.
+pub fn log_index_keys(log: &Log) -> impl Iterator<Item = B256> + '_ {
+    core::iter::once(log_index_address_key(log.address)).chain(
+        log.topics().iter().enumerate().map(|(index, topic)| log_index_topic_key(index, *topic)),
+    )
+}
+
+/// Conditions on the keys of the logs to look up in the log index.
+///
+/// A log matches if it has at least one of the keys of every entry of [`Self::keys`], e.g. one of
+/// the keys of a set of emitters and one of the keys of a set of first topics.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogIndexQuery {
+    /// Sets of keys, a log must match one key of each set.
+    pub keys: Vec<Vec<B256>>,
+}
+
+impl LogIndexQuery {
+    /// Returns `true` if the query has no condition.
+    ///
+    /// Such a query matches every log, so it can't be answered by the log index.
+    pub const fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+/// Reads the log index, which maps emitter addresses and topics to the positions of their logs.
+///
+/// The index is keyed by hashes, so lookups can return a few logs that don't actually match and
+/// the logs at the returned positions should be checked again.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait LogIndexReader: Send {
+    /// Returns the range of blocks covered by the log index, or `None` if it's not maintained.
+    fn log_index_block_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>>;
+
+    /// Returns up to `limit` positions of the logs of `block_range` that match `query`, in
+    /// ascending order.
+    ///
+    /// An empty query returns no positions.
+    fn log_index_positions(
+        &self,
+        query: &LogIndexQuery,
+        block_range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<u64>>;
+}
+
+/// Writes the log index.
+pub trait LogIndexWriter: Send {
+    /// Appends log positions to the index.
+    ///
+    /// The positions of every key must be sorted and higher than the ones already indexed.
+    fn insert_log_index(
+        &self,
+        index_updates: impl IntoIterator<Item = (B256, impl IntoIterator<Item = u64>)>,
+    ) -> ProviderResult<()>;
+
+    /// Removes the logs of the blocks in `range` and above from the index.
+    ///
+    /// The keys to unwind are read from the receipts of `range`, so they must still be available.
+    /// Returns the number of keys that were unwound.
+    fn unwind_log_index(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<usize>;
+}
@@ -1,6 +1,7 @@
 //! Metadata provider trait for reading and writing node metadata.
 
 use alloc::vec::Vec;
+use alloy_primitives::BlockNumber;
 use reth_db_api::models::StorageSettings;
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 
@@ -8,6 +9,10 @@ use reth_storage_errors::provider::{ProviderError, ProviderResult};
 pub mod keys {
     /// Storage configuration settings for this node.
     pub const STORAGE_SETTINGS: &str = "storage_settings";
+    /// First block whose logs were written to the log index.
+    pub const LOG_INDEX_START: &str = "log_index_start";
+    /// First block whose transactions were written to the address transactions index.
+    pub const ADDRESS_TRANSACTIONS_START: &str = "address_transactions_start";
 }
 
 /// Client trait for reading node metadata from the database.
@@ -26,6 +31,28 @@ pub trait MetadataProvider: Send {
             .get_metadata(keys::STORAGE_SETTINGS)?
             .and_then(|bytes| serde_json::from_slice(&bytes).ok()))
     }
+
+    /// Get the first block whose logs were written to the log index.
+    ///
+    /// Blocks below it may be covered by the `IndexLogs` checkpoint without having been indexed,
+    /// e.g. if the node ran without the log index before.
+    fn log_index_start(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(self
+            .get_metadata(keys::LOG_INDEX_START)?
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(BlockNumber::from_be_bytes))
+    }
+
+    /// Get the first block whose transactions were written to the address transactions index.
+    ///
+    /// Blocks below it may be covered by the `IndexAddressTransactions` checkpoint without having
+    /// been indexed, e.g. if the node ran without the index before.
+    fn address_transactions_start(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(self
+            .get_metadata(keys::ADDRESS_TRANSACTIONS_START)?
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(BlockNumber::from_be_bytes))
+    }
 }
 
 /// Client trait for writing node metadata to the database.
@@ -43,6 +70,16 @@ pub trait MetadataWriter: Send {
             serde_json::to_vec(&settings).map_err(ProviderError::other)?,
         )
     }
+
+    /// Write the first block whose logs were written to the log index.
+    fn write_log_index_start(&self, block: BlockNumber) -> ProviderResult<()> {
+        self.write_metadata(keys::LOG_INDEX_START, block.to_be_bytes().to_vec())
+    }
+
+    /// Write the first block whose transactions were written to the address transactions index.
+    fn write_address_transactions_start(&self, block: BlockNumber) -> ProviderResult<()> {
+        self.write_metadata(keys::ADDRESS_TRANSACTIONS_START, block.to_be_bytes().to_vec())
+    }
 }
 
 /// Trait for caching storage settings on a provider factory.
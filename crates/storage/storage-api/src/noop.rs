@@ -3,9 +3,11 @@
 pub use crate::bal::NoopBalStore;
 
 use crate::{
-    AccountReader, BalProvider, BalStoreHandle, BlockBodyIndicesProvider, BlockHashReader,
-    BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt, BlockSource, BytecodeReader,
-    ChangeSetReader, HashedPostStateProvider, HeaderProvider, NodePrimitivesProvider,
+    AccountReader, AddressTransactionsReader, BalProvider, BalStoreHandle,
+    BlockBodyIndicesProvider, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader,
+    BlockReaderIdExt, BlockSource, BytecodeReader, ChangeSetReader, HashedPostStateProvider,
+    HashedStateProviderFactory, HashedStateRangeReader, HashedStateRangeReaderBox, HeaderProvider,
+    LogIndexQuery, LogIndexReader, NodePrimitivesProvider, PersistedHashedState,
     PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt, StageCheckpointReader,
     StateProofProvider, StateProvider, StateProviderBox, StateProviderFactory, StateReader,
     StateRootProvider, StorageRootProvider, TransactionVariant, TransactionsProvider,
@@ -14,10 +16,10 @@ use crate::{
 #[cfg(feature = "db-api")]
 use crate::{DBProvider, DatabaseProviderFactory, StorageChangeSetReader, StorageSettingsCache};
 use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
-use alloy_consensus::transaction::TransactionMeta;
+use alloy_consensus::{constants::EMPTY_ROOT_HASH, transaction::TransactionMeta};
 use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumberOrTag};
 use alloy_primitives::{
-    Address, BlockHash, BlockNumber, Bytes, StorageKey, StorageValue, TxHash, TxNumber, B256,
+    Address, BlockHash, BlockNumber, Bytes, StorageKey, StorageValue, TxHash, TxNumber, B256, U256,
 };
 use core::{
     fmt::Debug,
@@ -617,6 +619,52 @@ impl<C: Send + Sync + 'static, N: NodePrimitives> StateProviderFactory for NoopP
     }
 }
 
+impl<C: Send + Sync, N: NodePrimitives> HashedStateRangeReader for NoopProvider<C, N> {
+    fn hashed_accounts_from(
+        &self,
+        _start: B256,
+        _max_entries: usize,
+    ) -> ProviderResult<Vec<(B256, Account)>> {
+        Ok(Vec::new())
+    }
+
+    fn hashed_storages_from(
+        &self,
+        _hashed_address: B256,
+        _start: B256,
+        _max_entries: usize,
+    ) -> ProviderResult<Vec<(B256, U256)>> {
+        Ok(Vec::new())
+    }
+
+    fn hashed_storage_root(&self, _hashed_address: B256) -> ProviderResult<B256> {
+        Ok(EMPTY_ROOT_HASH)
+    }
+
+    fn hashed_multiproof(&self, _targets: MultiProofTargets) -> ProviderResult<MultiProof> {
+        Ok(MultiProof::default())
+    }
+}
+
+impl<C: Send + Sync + 'static, N: NodePrimitives> HashedStateProviderFactory
+    for NoopProvider<C, N>
+{
+    fn persisted_hashed_state(&self) -> ProviderResult<PersistedHashedState> {
+        Ok(PersistedHashedState {
+            block: Default::default(),
+            state_root: EMPTY_ROOT_HASH,
+            provider: Box::new(self.clone()),
+        })
+    }
+
+    fn hashed_state_by_block_hash(
+        &self,
+        _block_hash: B256,
+    ) -> ProviderResult<HashedStateRangeReaderBox> {
+        Ok(Box::new(self.clone()))
+    }
+}
+
 impl<C: Send + Sync, N: NodePrimitives> StageCheckpointReader for NoopProvider<C, N> {
     fn get_stage_checkpoint(&self, _id: StageId) -> ProviderResult<Option<StageCheckpoint>> {
         Ok(None)
@@ -631,6 +679,47 @@ impl<C: Send + Sync, N: NodePrimitives> StageCheckpointReader for NoopProvider<C
     }
 }
 
+impl<C: Send + Sync, N: NodePrimitives> AddressTransactionsReader for NoopProvider<C, N> {
+    fn address_transactions_block_range(
+        &self,
+    ) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        Ok(None)
+    }
+
+    fn address_transactions_before(
+        &self,
+        _address: Address,
+        _tx_number: TxNumber,
+        _limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        Ok(Vec::new())
+    }
+
+    fn address_transactions_after(
+        &self,
+        _address: Address,
+        _tx_number: TxNumber,
+        _limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        Ok(Vec::new())
+    }
+}
+
+impl<C: Send + Sync, N: NodePrimitives> LogIndexReader for NoopProvider<C, N> {
+    fn log_index_block_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        Ok(None)
+    }
+
+    fn log_index_positions(
+        &self,
+        _query: &LogIndexQuery,
+        _block_range: RangeInclusive<BlockNumber>,
+        _limit: usize,
+    ) -> ProviderResult<Vec<u64>> {
+        Ok(Vec::new())
+    }
+}
+
 impl<C: Send + Sync, N: NodePrimitives> PruneCheckpointReader for NoopProvider<C, N> {
     fn get_prune_checkpoint(
         &self,
@@ -0,0 +1,584 @@
+//! Bundle pool for transaction bundles submitted via `eth_sendBundle` and `mev_sendBundle`.
+//!
+//! A bundle is an ordered list of transactions that must be included atomically, in order, in a
+//! block within the bundle's target range. Bundles are kept separately from the regular pool
+//! transactions and are handed to the payload builder which decides whether including a bundle is
+//! more profitable than including the next best pool transaction.
+
+use crate::{validate::AdmissionPolicy, TransactionOrigin};
+use alloy_consensus::Transaction;
+use alloy_primitives::{
+    keccak256,
+    map::{AddressMap, B256Map, B256Set, HashMap},
+    TxHash, B256,
+};
+use parking_lot::RwLock;
+use reth_primitives_traits::{Recovered, SignedTransaction};
+use std::sync::Arc;
+use tracing::trace;
+
+/// Default maximum number of bundles kept in the bundle pool.
+pub const DEFAULT_MAX_BUNDLES: usize = 1024;
+
+/// Configuration for the [`BundlePool`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BundlePoolConfig {
+    /// Maximum number of bundles kept in the pool.
+    pub max_bundles: usize,
+}
+
+impl Default for BundlePoolConfig {
+    fn default() -> Self {
+        Self { max_bundles: DEFAULT_MAX_BUNDLES }
+    }
+}
+
+/// A bundle of transactions that must be included atomically and in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolBundle<T> {
+    /// The transactions of the bundle, in execution order.
+    pub transactions: Vec<Recovered<T>>,
+    /// The first block the bundle is valid for.
+    pub block_number: u64,
+    /// The last block the bundle is valid for.
+    pub max_block_number: u64,
+    /// Minimum timestamp of the block the bundle can be included in.
+    pub min_timestamp: Option<u64>,
+    /// Maximum timestamp of the block the bundle can be included in.
+    pub max_timestamp: Option<u64>,
+    /// Hashes of transactions that are allowed to revert without invalidating the bundle.
+    pub reverting_tx_hashes: B256Set,
+    /// UUID that can be used to replace or cancel the bundle.
+    pub replacement_uuid: Option<String>,
+}
+
+impl<T: SignedTransaction> PoolBundle<T> {
+    /// Creates a new bundle that targets a single block.
+    pub fn new(transactions: Vec<Recovered<T>>, block_number: u64) -> Self {
+        Self {
+            transactions,
+            block_number,
+            max_block_number: block_number,
+            min_timestamp: None,
+            max_timestamp: None,
+            reverting_tx_hashes: Default::default(),
+            replacement_uuid: None,
+        }
+    }
+
+    /// Returns the bundle hash.
+    ///
+    /// This is the keccak256 hash of the concatenated transaction hashes, see also
+    /// `EthSendBundle::bundle_hash`.
+    pub fn hash(&self) -> B256 {
+        let hashes = self.transactions.iter().flat_map(|tx| tx.tx_hash().0).collect::<Vec<_>>();
+        keccak256(hashes)
+    }
+
+    /// Returns true if the transaction with the given hash is allowed to revert.
+    pub fn can_revert(&self, tx_hash: &TxHash) -> bool {
+        self.reverting_tx_hashes.contains(tx_hash)
+    }
+
+    /// Returns true if the bundle can be included in a block with the given number and
+    /// timestamp.
+    pub fn is_eligible(&self, block_number: u64, timestamp: u64) -> bool {
+        (self.block_number..=self.max_block_number).contains(&block_number) &&
+            self.min_timestamp.is_none_or(|min| timestamp >= min) &&
+            self.max_timestamp.is_none_or(|max| timestamp <= max)
+    }
+
+    /// Returns true if the bundle can no longer be included in any block after the given tip.
+    pub fn is_expired(&self, tip_number: u64, tip_timestamp: u64) -> bool {
+        self.max_block_number <= tip_number ||
+            self.max_timestamp.is_some_and(|max| max <= tip_timestamp)
+    }
+
+    /// Validates the bundle against the current tip.
+    pub fn ensure_valid(&self, tip_number: u64, tip_timestamp: u64) -> Result<(), BundleError> {
+        if self.transactions.is_empty() {
+            return Err(BundleError::Empty)
+        }
+        if self.max_block_number < self.block_number {
+            return Err(BundleError::InvalidBlockRange {
+                block_number: self.block_number,
+                max_block_number: self.max_block_number,
+            })
+        }
+        if let (Some(min), Some(max)) = (self.min_timestamp, self.max_timestamp) &&
+            min > max
+        {
+            return Err(BundleError::InvalidTimestampRange { min, max })
+        }
+        if self.is_expired(tip_number, tip_timestamp) {
+            return Err(BundleError::Expired { tip: tip_number })
+        }
+        Ok(())
+    }
+
+    /// Checks that the transactions of the bundle can pay the given base fee.
+    pub fn ensure_fees(&self, base_fee: u64) -> Result<(), BundleError> {
+        for tx in &self.transactions {
+            if tx.max_fee_per_gas() < base_fee as u128 {
+                return Err(BundleError::Underpriced {
+                    tx_hash: *tx.tx_hash(),
+                    max_fee_per_gas: tx.max_fee_per_gas(),
+                    base_fee,
+                })
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that the nonces of each sender's transactions are consecutive and not lower than
+    /// the sender's current nonce.
+    ///
+    /// Senders that are missing from `account_nonces` are treated as new accounts.
+    pub fn ensure_nonces(&self, account_nonces: &AddressMap<u64>) -> Result<(), BundleError> {
+        let mut next_nonces = AddressMap::<u64>::default();
+        for tx in &self.transactions {
+            let nonce = tx.nonce();
+            let valid = match next_nonces.insert(tx.signer(), nonce.saturating_add(1)) {
+                Some(next) => nonce == next,
+                None => nonce >= account_nonces.get(&tx.signer()).copied().unwrap_or_default(),
+            };
+            if !valid {
+                return Err(BundleError::InvalidNonce { tx_hash: *tx.tx_hash(), nonce })
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the average priority fee per gas the bundle's transactions pay at the given base
+    /// fee, weighted by their gas limits.
+    pub fn priority_fee_per_gas(&self, base_fee: u64) -> u128 {
+        let (fees, gas) = self.transactions.iter().fold((0u128, 0u128), |(fees, gas), tx| {
+            let tip = tx.effective_tip_per_gas(base_fee).unwrap_or_default();
+            (
+                fees.saturating_add(tip.saturating_mul(tx.gas_limit() as u128)),
+                gas + tx.gas_limit() as u128,
+            )
+        });
+        fees.checked_div(gas).unwrap_or_default()
+    }
+
+    /// Checks all transactions of the bundle against the admission rules.
+    ///
+    /// Bundle transactions are not propagated, so they are checked as
+    /// [`TransactionOrigin::Private`] transactions.
+    pub fn ensure_admitted(&self, policy: &AdmissionPolicy) -> Result<(), BundleError> {
+        for tx in &self.transactions {
+            policy.check_with_sender(TransactionOrigin::Private, tx.signer(), tx.inner()).map_err(
+                |err| BundleError::Rejected { tx_hash: *tx.tx_hash(), reason: err.to_string() },
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Errors that can occur when adding a bundle to the [`BundlePool`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BundleError {
+    /// The bundle contains no transactions.
+    #[error("bundle contains no transactions")]
+    Empty,
+    /// The bundle's max block is lower than its first block.
+    #[error("bundle max block {max_block_number} is lower than block {block_number}")]
+    InvalidBlockRange {
+        /// The first block of the bundle.
+        block_number: u64,
+        /// The last block of the bundle.
+        max_block_number: u64,
+    },
+    /// The bundle's min timestamp is greater than its max timestamp.
+    #[error("bundle min timestamp {min} is greater than max timestamp {max}")]
+    InvalidTimestampRange {
+        /// The min timestamp of the bundle.
+        min: u64,
+        /// The max timestamp of the bundle.
+        max: u64,
+    },
+    /// The bundle can't be included in any block after the current tip.
+    #[error("bundle can't be included after block {tip}")]
+    Expired {
+        /// The current tip.
+        tip: u64,
+    },
+    /// The bundle pool reached its configured capacity.
+    #[error("bundle pool is full")]
+    PoolFull,
+    /// A transaction of the bundle can't pay the current base fee.
+    #[error("bundle transaction {tx_hash} max fee per gas {max_fee_per_gas} is lower than the base fee {base_fee}")]
+    Underpriced {
+        /// The hash of the underpriced transaction.
+        tx_hash: TxHash,
+        /// The max fee per gas of the transaction.
+        max_fee_per_gas: u128,
+        /// The current base fee.
+        base_fee: u64,
+    },
+    /// A transaction of the bundle has a nonce that is too low or not consecutive to the previous
+    /// transaction of the same sender.
+    #[error("bundle transaction {tx_hash} has invalid nonce {nonce}")]
+    InvalidNonce {
+        /// The hash of the transaction.
+        tx_hash: TxHash,
+        /// The nonce of the transaction.
+        nonce: u64,
+    },
+    /// A transaction of the bundle is rejected by the admission rules.
+    #[error("bundle transaction {tx_hash} rejected: {reason}")]
+    Rejected {
+        /// The hash of the rejected transaction.
+        tx_hash: TxHash,
+        /// Why the transaction was rejected.
+        reason: String,
+    },
+}
+
+/// A pool of transaction bundles.
+///
+/// This type is cheap to clone and can be shared between the RPC and the payload builder.
+#[derive(Debug)]
+pub struct BundlePool<T> {
+    inner: Arc<RwLock<BundlePoolInner<T>>>,
+}
+
+impl<T> Clone for BundlePool<T> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+#[derive(Debug)]
+struct BundlePoolInner<T> {
+    /// Pool settings.
+    config: BundlePoolConfig,
+    /// Admission rules applied to the transactions of new bundles.
+    admission_policy: Option<AdmissionPolicy>,
+    /// All bundles by bundle hash.
+    bundles: B256Map<Arc<PoolBundle<T>>>,
+    /// Bundle hashes by replacement UUID.
+    by_uuid: HashMap<String, B256>,
+    /// Number of the last seen canonical block.
+    tip_number: u64,
+    /// Timestamp of the last seen canonical block.
+    tip_timestamp: u64,
+    /// Base fee of the block after the last seen canonical block.
+    pending_basefee: u64,
+}
+
+impl<T: SignedTransaction> BundlePool<T> {
+    /// Creates a new, empty bundle pool.
+    pub fn new(config: BundlePoolConfig) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(BundlePoolInner {
+                config,
+                admission_policy: None,
+                bundles: Default::default(),
+                by_uuid: Default::default(),
+                tip_number: 0,
+                tip_timestamp: 0,
+                pending_basefee: 0,
+            })),
+        }
+    }
+
+    /// Sets the admission rules that the transactions of new bundles must satisfy.
+    ///
+    /// This should be the same policy as the one of the pool's validator, so that bundles can't be
+    /// used to get around it.
+    pub fn set_admission_policy(&self, admission_policy: Option<AdmissionPolicy>) {
+        self.inner.write().admission_policy = admission_policy;
+    }
+
+    /// Returns the number of bundles in the pool.
+    pub fn len(&self) -> usize {
+        self.inner.read().bundles.len()
+    }
+
+    /// Returns true if the pool contains no bundles.
+    pub fn is_empty(&self) -> bool {
+        self.inner.read().bundles.is_empty()
+    }
+
+    /// Adds a bundle to the pool and returns its hash.
+    ///
+    /// If the bundle has a replacement UUID, any bundle previously submitted with the same UUID is
+    /// replaced.
+    ///
+    /// The nonces of the bundle's transactions are not checked here, see
+    /// [`PoolBundle::ensure_nonces`].
+    pub fn add_bundle(&self, bundle: PoolBundle<T>) -> Result<B256, BundleError> {
+        let mut inner = self.inner.write();
+        bundle.ensure_valid(inner.tip_number, inner.tip_timestamp)?;
+        bundle.ensure_fees(inner.pending_basefee)?;
+        if let Some(policy) = &inner.admission_policy {
+            bundle.ensure_admitted(policy)?;
+        }
+
+        let hash = bundle.hash();
+        let is_replacement =
+            bundle.replacement_uuid.as_ref().is_some_and(|uuid| inner.by_uuid.contains_key(uuid));
+        if !is_replacement &&
+            !inner.bundles.contains_key(&hash) &&
+            inner.bundles.len() >= inner.config.max_bundles
+        {
+            return Err(BundleError::PoolFull)
+        }
+
+        if let Some(uuid) = bundle.replacement_uuid.clone() &&
+            let Some(replaced) = inner.by_uuid.insert(uuid, hash) &&
+            replaced != hash
+        {
+            trace!(target: "txpool::bundle", %replaced, %hash, "replacing bundle");
+            inner.bundles.remove(&replaced);
+        }
+        inner.bundles.insert(hash, Arc::new(bundle));
+
+        Ok(hash)
+    }
+
+    /// Removes the bundle with the given replacement UUID.
+    ///
+    /// Returns the removed bundle, if any.
+    pub fn cancel_bundle(&self, replacement_uuid: &str) -> Option<Arc<PoolBundle<T>>> {
+        let mut inner = self.inner.write();
+        let hash = inner.by_uuid.remove(replacement_uuid)?;
+        inner.bundles.remove(&hash)
+    }
+
+    /// Removes the bundle with the given hash.
+    ///
+    /// Returns the removed bundle, if any.
+    pub fn remove_bundle(&self, hash: &B256) -> Option<Arc<PoolBundle<T>>> {
+        let mut inner = self.inner.write();
+        let bundle = inner.bundles.remove(hash)?;
+        inner.by_uuid.retain(|_, bundle_hash| bundle_hash != hash);
+        Some(bundle)
+    }
+
+    /// Returns the bundle with the given hash.
+    pub fn get(&self, hash: &B256) -> Option<Arc<PoolBundle<T>>> {
+        self.inner.read().bundles.get(hash).cloned()
+    }
+
+    /// Returns up to `limit` bundles that can be included in a block with the given number and
+    /// timestamp.
+    ///
+    /// Bundles that pay a higher priority fee at the pending base fee are preferred, ties are
+    /// broken by bundle hash so the order is deterministic.
+    pub fn bundles_for_block(
+        &self,
+        block_number: u64,
+        timestamp: u64,
+        limit: usize,
+    ) -> Vec<Arc<PoolBundle<T>>> {
+        let inner = self.inner.read();
+        let mut bundles = inner
+            .bundles
+            .iter()
+            .filter(|(_, bundle)| bundle.is_eligible(block_number, timestamp))
+            .map(|(hash, bundle)| {
+                (bundle.priority_fee_per_gas(inner.pending_basefee), hash, bundle)
+            })
+            .collect::<Vec<_>>();
+        bundles.sort_unstable_by(|(fee_a, hash_a, _), (fee_b, hash_b, _)| {
+            fee_b.cmp(fee_a).then_with(|| hash_a.cmp(hash_b))
+        });
+        bundles.into_iter().take(limit).map(|(_, _, bundle)| Arc::clone(bundle)).collect()
+    }
+
+    /// Removes all bundles with transactions that are rejected by the current admission rules.
+    ///
+    /// This should be called after the rules are updated.
+    pub fn remove_rejected(&self) {
+        let mut inner = self.inner.write();
+        let BundlePoolInner { admission_policy: Some(policy), bundles, by_uuid, .. } = &mut *inner
+        else {
+            return
+        };
+
+        let before = bundles.len();
+        bundles.retain(|_, bundle| bundle.ensure_admitted(policy).is_ok());
+        if bundles.len() != before {
+            by_uuid.retain(|_, hash| bundles.contains_key(hash));
+            trace!(target: "txpool::bundle", removed = before - bundles.len(), "removed rejected bundles");
+        }
+    }
+
+    /// Updates the pool with a new canonical tip.
+    ///
+    /// This drops all bundles that contain any of the mined transactions and all bundles that can
+    /// no longer be included after the new tip.
+    pub fn on_canonical_state_change<'a>(
+        &self,
+        tip_number: u64,
+        tip_timestamp: u64,
+        pending_basefee: u64,
+        mined_transactions: impl IntoIterator<Item = &'a TxHash>,
+    ) {
+        let mined = mined_transactions.into_iter().copied().collect::<B256Set>();
+        let mut inner = self.inner.write();
+        inner.tip_number = tip_number;
+        inner.tip_timestamp = tip_timestamp;
+        inner.pending_basefee = pending_basefee;
+
+        let before = inner.bundles.len();
+        inner.bundles.retain(|_, bundle| {
+            !bundle.is_expired(tip_number, tip_timestamp) &&
+                !bundle.transactions.iter().any(|tx| mined.contains(tx.tx_hash()))
+        });
+        if inner.bundles.len() != before {
+            let BundlePoolInner { bundles, by_uuid, .. } = &mut *inner;
+            by_uuid.retain(|_, hash| bundles.contains_key(hash));
+            trace!(target: "txpool::bundle", removed = before - bundles.len(), tip_number, "removed included or expired bundles");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::MockTransaction, validate::AdmissionPolicyConfig, PoolTransaction};
+    use alloy_primitives::Address;
+    use reth_ethereum_primitives::TransactionSigned;
+
+    fn bundle(nonce: u64, block_number: u64) -> PoolBundle<TransactionSigned> {
+        let mut tx = MockTransaction::eip1559();
+        for _ in 0..nonce {
+            tx = tx.inc_nonce();
+        }
+        PoolBundle::new(vec![tx.into_consensus()], block_number)
+    }
+
+    #[test]
+    fn replaces_bundle_with_same_uuid() {
+        let pool = BundlePool::new(BundlePoolConfig::default());
+
+        let mut first = bundle(0, 10);
+        first.replacement_uuid = Some("uuid".to_string());
+        let first_hash = pool.add_bundle(first).unwrap();
+
+        let mut second = bundle(1, 10);
+        second.replacement_uuid = Some("uuid".to_string());
+        let second_hash = pool.add_bundle(second).unwrap();
+
+        assert_eq!(pool.len(), 1);
+        assert!(pool.get(&first_hash).is_none());
+        assert!(pool.get(&second_hash).is_some());
+
+        assert!(pool.cancel_bundle("uuid").is_some());
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn selects_bundles_by_block_and_timestamp() {
+        let pool = BundlePool::new(BundlePoolConfig::default());
+
+        let mut bundle = bundle(0, 10);
+        bundle.min_timestamp = Some(100);
+        bundle.max_timestamp = Some(200);
+        pool.add_bundle(bundle).unwrap();
+
+        assert_eq!(pool.bundles_for_block(10, 150, 16).len(), 1);
+        assert!(pool.bundles_for_block(11, 150, 16).is_empty());
+        assert!(pool.bundles_for_block(10, 99, 16).is_empty());
+        assert!(pool.bundles_for_block(10, 201, 16).is_empty());
+    }
+
+    #[test]
+    fn drops_included_and_expired_bundles() {
+        let pool = BundlePool::new(BundlePoolConfig::default());
+
+        let included = bundle(0, 12);
+        let included_tx = *included.transactions[0].tx_hash();
+        pool.add_bundle(included).unwrap();
+        let expired = pool.add_bundle(bundle(1, 10)).unwrap();
+        let pending = pool.add_bundle(bundle(2, 12)).unwrap();
+
+        pool.on_canonical_state_change(10, 120, 0, [&included_tx]);
+
+        assert_eq!(pool.len(), 1);
+        assert!(pool.get(&expired).is_none());
+        assert!(pool.get(&pending).is_some());
+
+        assert_eq!(pool.add_bundle(bundle(3, 9)), Err(BundleError::Expired { tip: 10 }));
+    }
+
+    #[test]
+    fn removes_bundle_by_hash() {
+        let pool = BundlePool::new(BundlePoolConfig::default());
+
+        let mut replaceable = bundle(0, 10);
+        replaceable.replacement_uuid = Some("uuid".to_string());
+        let hash = pool.add_bundle(replaceable).unwrap();
+        let other = pool.add_bundle(bundle(1, 10)).unwrap();
+
+        assert_eq!(pool.remove_bundle(&hash).map(|bundle| bundle.hash()), Some(hash));
+        assert!(pool.remove_bundle(&hash).is_none());
+        assert!(pool.cancel_bundle("uuid").is_none());
+        assert!(pool.get(&other).is_some());
+    }
+
+    #[test]
+    fn applies_admission_policy() {
+        let pool = BundlePool::new(BundlePoolConfig::default());
+        let policy = AdmissionPolicy::default();
+        pool.set_admission_policy(Some(policy.clone()));
+
+        let admitted = pool.add_bundle(bundle(0, 10)).unwrap();
+
+        let denied = MockTransaction::eip1559().with_sender(Address::random()).into_consensus();
+        let sender = denied.signer();
+        policy.update(AdmissionPolicyConfig {
+            denied_addresses: [sender].into_iter().collect(),
+            ..Default::default()
+        });
+        assert!(matches!(
+            pool.add_bundle(PoolBundle::new(vec![denied], 10)),
+            Err(BundleError::Rejected { .. })
+        ));
+        assert!(pool.get(&admitted).is_some());
+
+        let admitted_sender = pool.get(&admitted).unwrap().transactions[0].signer();
+        policy.update(AdmissionPolicyConfig {
+            denied_addresses: [admitted_sender].into_iter().collect(),
+            ..Default::default()
+        });
+        pool.remove_rejected();
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn rejects_underpriced_bundles() {
+        let pool = BundlePool::new(BundlePoolConfig::default());
+        pool.on_canonical_state_change(9, 90, u64::MAX, []);
+        assert!(matches!(pool.add_bundle(bundle(0, 10)), Err(BundleError::Underpriced { .. })));
+    }
+
+    #[test]
+    fn checks_bundle_nonces() {
+        let tx = MockTransaction::eip1559().with_nonce(5);
+        let sender = tx.sender();
+        let txs = vec![tx.clone().into_consensus(), tx.clone().inc_nonce().into_consensus()];
+        let nonces = [(sender, 5)].into_iter().collect::<AddressMap<u64>>();
+        assert_eq!(PoolBundle::new(txs.clone(), 10).ensure_nonces(&nonces), Ok(()));
+        assert!(PoolBundle::new(txs, 10)
+            .ensure_nonces(&[(sender, 6)].into_iter().collect())
+            .is_err());
+
+        let gap = vec![tx.clone().into_consensus(), tx.skip(1).into_consensus()];
+        assert!(matches!(
+            PoolBundle::new(gap, 10).ensure_nonces(&nonces),
+            Err(BundleError::InvalidNonce { nonce: 7, .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_bundles_when_full() {
+        let pool = BundlePool::new(BundlePoolConfig { max_bundles: 1 });
+        pool.add_bundle(bundle(0, 10)).unwrap();
+        assert_eq!(pool.add_bundle(bundle(1, 10)), Err(BundleError::PoolFull));
+    }
+}
@@ -0,0 +1,209 @@
+//! Support for conditional transactions.
+//!
+//! A [`TransactionConditional`] restricts the inclusion of a transaction to a block number and
+//! timestamp window and to states in which the storage of certain accounts holds the expected
+//! values, see `eth_sendRawTransactionConditional`.
+//!
+//! The conditions are stored alongside the pool transaction, see
+//! [`PoolTransaction::conditional`](crate::PoolTransaction::conditional), and are checked when the
+//! transaction is validated, on every canonical state change and when the transaction is picked for
+//! a payload.
+
+use crate::error::PoolTransactionError;
+use alloy_primitives::{Address, B256, U256};
+use reth_storage_api::{errors::provider::ProviderError, StateProvider};
+use std::any::Any;
+
+pub use alloy_consensus::conditional::BlockConditionalAttributes;
+pub use alloy_rpc_types_eth::erc4337::{AccountStorage, TransactionConditional};
+
+/// The maximum [cost](TransactionConditional::cost) of a conditional, i.e. the number of storage
+/// lookups required to check it.
+pub const MAX_CONDITIONAL_COST: u64 = 5000;
+
+/// Errors returned if a [`TransactionConditional`] is not satisfied.
+#[derive(Debug, thiserror::Error)]
+pub enum TransactionConditionalError {
+    /// The conditional requires too many storage lookups.
+    #[error("conditional cost {cost} exceeds the maximum of {max}")]
+    CostExceeded {
+        /// Cost of the conditional.
+        cost: u64,
+        /// Maximum allowed cost.
+        max: u64,
+    },
+    /// The maximum block number or timestamp of the conditional has passed.
+    #[error("conditional block range has passed")]
+    Expired,
+    /// The minimum block number or timestamp of the conditional has not been reached yet.
+    #[error("conditional block range has not been reached yet")]
+    NotYetValid,
+    /// The storage of a known account doesn't hold the expected values.
+    #[error("storage of known account {0} does not match")]
+    KnownAccountMismatch(Address),
+    /// Failed to read the state to check the known accounts.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+}
+
+impl TransactionConditionalError {
+    /// Returns `true` if the conditional can't be satisfied by any future block.
+    ///
+    /// A mismatch of a known account is considered permanent, the transaction is only valid
+    /// against the state it was submitted for.
+    pub const fn is_permanent(&self) -> bool {
+        matches!(self, Self::CostExceeded { .. } | Self::Expired | Self::KnownAccountMismatch(_))
+    }
+}
+
+impl PoolTransactionError for TransactionConditionalError {
+    fn is_bad_transaction(&self) -> bool {
+        // the transaction itself is valid, only its conditions are not met
+        false
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Returns the attributes of the earliest block that can follow the block with the given number
+/// and timestamp.
+pub const fn next_block_attributes(number: u64, timestamp: u64) -> BlockConditionalAttributes {
+    BlockConditionalAttributes {
+        number: number.saturating_add(1),
+        timestamp: timestamp.saturating_add(1),
+    }
+}
+
+/// Checks that the cost of the conditional is within [`MAX_CONDITIONAL_COST`].
+pub fn validate_conditional_cost(
+    conditional: &TransactionConditional,
+) -> Result<(), TransactionConditionalError> {
+    let cost = conditional.cost();
+    if cost > MAX_CONDITIONAL_COST {
+        return Err(TransactionConditionalError::CostExceeded { cost, max: MAX_CONDITIONAL_COST })
+    }
+    Ok(())
+}
+
+/// Checks the block number and timestamp window of the conditional against the given block.
+pub const fn validate_block_attributes(
+    conditional: &TransactionConditional,
+    block: &BlockConditionalAttributes,
+) -> Result<(), TransactionConditionalError> {
+    if conditional.has_exceeded_block_attributes(block) {
+        return Err(TransactionConditionalError::Expired)
+    }
+    if !conditional.matches_block_attributes(block) {
+        return Err(TransactionConditionalError::NotYetValid)
+    }
+    Ok(())
+}
+
+/// Checks the storage of the known accounts of the conditional against the given state.
+pub fn validate_known_accounts<S>(
+    conditional: &TransactionConditional,
+    state: &S,
+) -> Result<(), TransactionConditionalError>
+where
+    S: StateProvider + ?Sized,
+{
+    for (address, storage) in &conditional.known_accounts {
+        let matches = match storage {
+            AccountStorage::RootHash(root) => {
+                state.storage_root(*address, Default::default())? == *root
+            }
+            AccountStorage::Slots(slots) => {
+                let mut matches = true;
+                for (slot, value) in slots {
+                    let current = state.storage(*address, B256::from(*slot))?.unwrap_or_default();
+                    if current != U256::from_be_bytes(value.0) {
+                        matches = false;
+                        break
+                    }
+                }
+                matches
+            }
+        };
+        if !matches {
+            return Err(TransactionConditionalError::KnownAccountMismatch(*address))
+        }
+    }
+    Ok(())
+}
+
+/// Checks the block window and the known accounts of the conditional.
+pub fn validate_conditional<S>(
+    conditional: &TransactionConditional,
+    block: &BlockConditionalAttributes,
+    state: &S,
+) -> Result<(), TransactionConditionalError>
+where
+    S: StateProvider + ?Sized,
+{
+    validate_block_attributes(conditional, block)?;
+    validate_known_accounts(conditional, state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, map::HashMap};
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+
+    #[test]
+    fn block_window() {
+        let conditional = TransactionConditional {
+            block_number_min: Some(10),
+            block_number_max: Some(20),
+            timestamp_max: Some(1000),
+            ..Default::default()
+        };
+        let block = |number, timestamp| BlockConditionalAttributes { number, timestamp };
+
+        assert!(validate_block_attributes(&conditional, &block(15, 500)).is_ok());
+        assert!(matches!(
+            validate_block_attributes(&conditional, &block(5, 500)),
+            Err(TransactionConditionalError::NotYetValid)
+        ));
+        assert!(matches!(
+            validate_block_attributes(&conditional, &block(21, 500)),
+            Err(TransactionConditionalError::Expired)
+        ));
+        assert!(matches!(
+            validate_block_attributes(&conditional, &block(15, 1001)),
+            Err(TransactionConditionalError::Expired)
+        ));
+    }
+
+    #[test]
+    fn known_account_slots() {
+        let account = address!("0x00000000000000000000000000000000000000aa");
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            account,
+            ExtendedAccount::new(0, U256::ZERO)
+                .extend_storage([(B256::with_last_byte(1), U256::from(42))]),
+        );
+
+        let conditional = |slots: HashMap<U256, B256>| TransactionConditional {
+            known_accounts: [(account, AccountStorage::Slots(slots))].into_iter().collect(),
+            ..Default::default()
+        };
+
+        let matching = conditional(
+            [(U256::from(1), B256::from(U256::from(42))), (U256::from(2), B256::ZERO)]
+                .into_iter()
+                .collect(),
+        );
+        assert!(validate_known_accounts(&matching, &provider).is_ok());
+
+        let mismatching =
+            conditional([(U256::from(1), B256::from(U256::from(43)))].into_iter().collect());
+        assert!(matches!(
+            validate_known_accounts(&mismatching, &provider),
+            Err(TransactionConditionalError::KnownAccountMismatch(address)) if address == account
+        ));
+    }
+}
@@ -1,4 +1,6 @@
 use crate::{
+    bundle::BundlePoolConfig,
+    history::TransactionHistoryConfig,
     maintain::MAX_QUEUED_TRANSACTION_LIFETIME,
     pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
     PoolSize, TransactionOrigin,
@@ -72,6 +74,14 @@ pub struct PoolConfig {
     ///
     /// This restricts how many executable transaction a delegated sender can stack.
     pub max_inflight_delegated_slot_limit: usize,
+    /// Settings for the bundle pool.
+    ///
+    /// If `None`, bundles are not supported.
+    pub bundle_pool: Option<BundlePoolConfig>,
+    /// Settings for the on-disk history of transaction events.
+    ///
+    /// If `None`, transaction events are not recorded.
+    pub transaction_history: Option<TransactionHistoryConfig>,
 }
 
 impl PoolConfig {
@@ -100,6 +110,21 @@ impl PoolConfig {
         self
     }
 
+    /// Enables the bundle pool with the given settings.
+    pub const fn with_bundle_pool(mut self, bundle_pool: BundlePoolConfig) -> Self {
+        self.bundle_pool = Some(bundle_pool);
+        self
+    }
+
+    /// Enables the history of transaction events with the given settings.
+    pub fn with_transaction_history(
+        mut self,
+        transaction_history: TransactionHistoryConfig,
+    ) -> Self {
+        self.transaction_history = Some(transaction_history);
+        self
+    }
+
     /// Returns whether the size and amount constraints in any sub-pools are exceeded.
     #[inline]
     pub const fn is_exceeded(&self, pool_size: PoolSize) -> bool {
@@ -129,6 +154,8 @@ impl Default for PoolConfig {
             max_new_pending_txs_notifications: MAX_NEW_PENDING_TXS_NOTIFICATIONS,
             max_queued_lifetime: MAX_QUEUED_TRANSACTION_LIFETIME,
             max_inflight_delegated_slot_limit: DEFAULT_MAX_INFLIGHT_DELEGATED_SLOTS,
+            bundle_pool: None,
+            transaction_history: None,
         }
     }
 }
@@ -0,0 +1,724 @@
+//! Bounded on-disk history of transaction pool events.
+//!
+//! The history records the lifecycle events of transactions, see [`FullTransactionEvent`], so
+//! that it can be looked up after the fact why a transaction was queued, replaced or dropped.
+//!
+//! Events are stored in a ring buffer of fixed-size records, so the file never exceeds the
+//! configured number of events and the oldest events are overwritten first. The file starts with
+//! [`HISTORY_MAGIC`], the format version and the capacity, followed by the record slots:
+//!
+//! ```text
+//! seq (8) | timestamp ms (8) | tx hash (32) | sender (20) | event kind (1) | event data (32)
+//! ```
+//!
+//! Slots with sequence number 0 are empty.
+
+use crate::{
+    pool::{DiscardReason, FullTransactionEvent, InvalidReason, QueuedReason},
+    traits::TransactionPool,
+};
+use alloy_primitives::{
+    map::{AddressMap, B256Map},
+    Address, TxHash, B256,
+};
+use futures_util::StreamExt;
+use parking_lot::Mutex;
+use reth_fs_util::FsPathError;
+use std::{
+    collections::VecDeque,
+    fs::{File, OpenOptions},
+    io::{Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tracing::{debug, warn};
+
+/// Magic bytes at the start of a history file.
+pub const HISTORY_MAGIC: &[u8; 8] = b"rethtxhs";
+
+/// Current version of the history file format.
+pub const HISTORY_VERSION: u8 = 1;
+
+/// Default maximum number of events kept in the history.
+pub const DEFAULT_MAX_HISTORY_EVENTS: usize = 100_000;
+
+/// Length of the file header: magic, version and capacity.
+const HEADER_LEN: u64 = 8 + 1 + 8;
+
+/// Length of a single event record.
+const RECORD_LEN: usize = 8 + 8 + 32 + 20 + 1 + 32;
+
+/// Configuration for the [`TransactionHistory`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionHistoryConfig {
+    /// Path to the history file.
+    pub path: PathBuf,
+    /// Maximum number of events kept in the history.
+    pub max_events: usize,
+}
+
+impl TransactionHistoryConfig {
+    /// Creates a new config that keeps up to [`DEFAULT_MAX_HISTORY_EVENTS`] events in the file at
+    /// the given path.
+    pub const fn new(path: PathBuf) -> Self {
+        Self { path, max_events: DEFAULT_MAX_HISTORY_EVENTS }
+    }
+
+    /// Sets the maximum number of events kept in the history.
+    pub const fn with_max_events(mut self, max_events: usize) -> Self {
+        self.max_events = max_events;
+        self
+    }
+}
+
+/// A transition of a transaction that is recorded in the [`TransactionHistory`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionHistoryEvent {
+    /// Transaction was added to or promoted to the pending pool.
+    Pending,
+    /// Transaction was added to the queued pool, with the reason if known.
+    Queued(Option<QueuedReason>),
+    /// Transaction was included in the block with the given hash.
+    Mined(B256),
+    /// Transaction was replaced by the transaction with the given hash.
+    Replaced(TxHash),
+    /// Transaction was dropped from the pool, with the reason why.
+    Discarded(DiscardReason),
+    /// Transaction was rejected or removed because it became invalid, with the kind of error.
+    Invalid(InvalidReason),
+}
+
+impl TransactionHistoryEvent {
+    /// Returns the name of the event.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Queued(_) => "queued",
+            Self::Mined(_) => "mined",
+            Self::Replaced(_) => "replaced",
+            Self::Discarded(_) => "discarded",
+            Self::Invalid(_) => "invalid",
+        }
+    }
+
+    /// Returns a human readable reason for the transition, if any.
+    pub fn reason(&self) -> Option<String> {
+        let reason = match self {
+            Self::Pending | Self::Queued(None) => return None,
+            Self::Queued(Some(reason)) => match reason {
+                QueuedReason::NonceGap => "nonce gap",
+                QueuedReason::ParkedAncestors => "parked ancestor transactions",
+                QueuedReason::InsufficientBalance => "insufficient balance",
+                QueuedReason::TooMuchGas => "gas limit exceeds block gas limit",
+                QueuedReason::InsufficientBaseFee => "max fee below base fee",
+                QueuedReason::InsufficientBlobFee => "max blob fee below blob base fee",
+            }
+            .to_string(),
+            Self::Mined(block_hash) => format!("included in block {block_hash}"),
+            Self::Replaced(replaced_by) => format!("replaced by transaction {replaced_by}"),
+            Self::Discarded(reason) => match reason {
+                DiscardReason::PoolLimits => "evicted due to pool limits",
+                DiscardReason::Removed => "removed from the pool",
+                DiscardReason::ValidationFailed => "transaction could not be validated",
+            }
+            .to_string(),
+            Self::Invalid(reason) => match reason {
+                InvalidReason::NonceTooLow => "nonce too low",
+                InvalidReason::InsufficientFunds => "insufficient funds",
+                InvalidReason::Underpriced => "transaction underpriced",
+                InvalidReason::ExceedsGasLimit => "gas limit exceeds the allowed limit",
+                InvalidReason::Oversized => "transaction is oversized",
+                InvalidReason::Unsupported => "transaction type not supported",
+                InvalidReason::Other => "transaction is invalid",
+            }
+            .to_string(),
+        };
+        Some(reason)
+    }
+
+    fn encode(&self) -> (u8, [u8; 32]) {
+        let mut data = [0; 32];
+        let kind = match self {
+            Self::Pending => 0,
+            Self::Queued(reason) => {
+                data[0] = match reason {
+                    None => 0,
+                    Some(QueuedReason::NonceGap) => 1,
+                    Some(QueuedReason::ParkedAncestors) => 2,
+                    Some(QueuedReason::InsufficientBalance) => 3,
+                    Some(QueuedReason::TooMuchGas) => 4,
+                    Some(QueuedReason::InsufficientBaseFee) => 5,
+                    Some(QueuedReason::InsufficientBlobFee) => 6,
+                };
+                1
+            }
+            Self::Mined(block_hash) => {
+                data = block_hash.0;
+                2
+            }
+            Self::Replaced(replaced_by) => {
+                data = replaced_by.0;
+                3
+            }
+            Self::Discarded(reason) => {
+                data[0] = match reason {
+                    DiscardReason::PoolLimits => 0,
+                    DiscardReason::Removed => 1,
+                    DiscardReason::ValidationFailed => 2,
+                };
+                4
+            }
+            Self::Invalid(reason) => {
+                data[0] = match reason {
+                    InvalidReason::Other => 0,
+                    InvalidReason::NonceTooLow => 1,
+                    InvalidReason::InsufficientFunds => 2,
+                    InvalidReason::Underpriced => 3,
+                    InvalidReason::ExceedsGasLimit => 4,
+                    InvalidReason::Oversized => 5,
+                    InvalidReason::Unsupported => 6,
+                };
+                5
+            }
+        };
+        (kind, data)
+    }
+
+    fn decode(kind: u8, data: [u8; 32]) -> Option<Self> {
+        let event = match kind {
+            0 => Self::Pending,
+            1 => Self::Queued(match data[0] {
+                1 => Some(QueuedReason::NonceGap),
+                2 => Some(QueuedReason::ParkedAncestors),
+                3 => Some(QueuedReason::InsufficientBalance),
+                4 => Some(QueuedReason::TooMuchGas),
+                5 => Some(QueuedReason::InsufficientBaseFee),
+                6 => Some(QueuedReason::InsufficientBlobFee),
+                _ => None,
+            }),
+            2 => Self::Mined(B256::from(data)),
+            3 => Self::Replaced(TxHash::from(data)),
+            4 => Self::Discarded(match data[0] {
+                1 => DiscardReason::Removed,
+                2 => DiscardReason::ValidationFailed,
+                _ => DiscardReason::PoolLimits,
+            }),
+            5 => Self::Invalid(match data[0] {
+                1 => InvalidReason::NonceTooLow,
+                2 => InvalidReason::InsufficientFunds,
+                3 => InvalidReason::Underpriced,
+                4 => InvalidReason::ExceedsGasLimit,
+                5 => InvalidReason::Oversized,
+                6 => InvalidReason::Unsupported,
+                _ => InvalidReason::Other,
+            }),
+            _ => return None,
+        };
+        Some(event)
+    }
+}
+
+/// An event recorded in the [`TransactionHistory`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionHistoryEntry {
+    /// Time of the event in milliseconds since the unix epoch.
+    pub timestamp: u64,
+    /// Hash of the transaction.
+    pub hash: TxHash,
+    /// Sender of the transaction, if known.
+    pub sender: Option<Address>,
+    /// The transition of the transaction.
+    pub event: TransactionHistoryEvent,
+}
+
+impl TransactionHistoryEntry {
+    fn encode(&self, seq: u64) -> [u8; RECORD_LEN] {
+        let (kind, data) = self.event.encode();
+        let mut record = [0; RECORD_LEN];
+        record[..8].copy_from_slice(&seq.to_be_bytes());
+        record[8..16].copy_from_slice(&self.timestamp.to_be_bytes());
+        record[16..48].copy_from_slice(self.hash.as_slice());
+        record[48..68].copy_from_slice(self.sender.unwrap_or_default().as_slice());
+        record[68] = kind;
+        record[69..].copy_from_slice(&data);
+        record
+    }
+
+    /// Decodes a record, returning `None` for empty or corrupt slots.
+    fn decode(record: &[u8; RECORD_LEN]) -> Option<(u64, Self)> {
+        let seq = u64::from_be_bytes(record[..8].try_into().ok()?);
+        if seq == 0 {
+            return None
+        }
+        let timestamp = u64::from_be_bytes(record[8..16].try_into().ok()?);
+        let hash = TxHash::from_slice(&record[16..48]);
+        let sender = Address::from_slice(&record[48..68]);
+        let event = TransactionHistoryEvent::decode(record[68], record[69..].try_into().ok()?)?;
+        Some((seq, Self { timestamp, hash, sender: (!sender.is_zero()).then_some(sender), event }))
+    }
+}
+
+/// Errors returned when opening a [`TransactionHistory`].
+#[derive(Debug, thiserror::Error)]
+pub enum TransactionHistoryError {
+    /// The file is not a history file or was written with an unsupported version.
+    #[error("invalid transaction history file header")]
+    InvalidHeader,
+    /// The configured capacity is zero.
+    #[error("transaction history capacity must be greater than zero")]
+    ZeroCapacity,
+    /// Error reading or writing the history file.
+    #[error(transparent)]
+    FsPath(#[from] FsPathError),
+}
+
+/// A bounded, persistent history of transaction pool events that can be queried by transaction
+/// hash and sender.
+///
+/// Recorded events are kept in memory until they are written to the file with
+/// [`TransactionHistory::flush`].
+///
+/// Clones share the same history.
+#[derive(Debug, Clone)]
+pub struct TransactionHistory {
+    inner: Arc<Mutex<TransactionHistoryInner>>,
+    file: Arc<Mutex<HistoryFileWriter>>,
+}
+
+impl TransactionHistory {
+    /// Opens the history file of the config, creating it if it doesn't exist.
+    ///
+    /// If the file was written with a different capacity, the most recent events are kept.
+    pub fn open(config: TransactionHistoryConfig) -> Result<Self, TransactionHistoryError> {
+        let TransactionHistoryConfig { path, max_events } = config;
+        if max_events == 0 {
+            return Err(TransactionHistoryError::ZeroCapacity)
+        }
+        if let Some(parent) = path.parent() {
+            reth_fs_util::create_dir_all(parent)?;
+        }
+
+        let existing = if path.exists() { read_history_file(&path)? } else { None };
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .map_err(|err| FsPathError::open(err, &path))?;
+
+        let mut file = HistoryFileWriter { path, file };
+        let mut inner = TransactionHistoryInner {
+            capacity: max_events as u64,
+            next_seq: 1,
+            entries: VecDeque::new(),
+            by_hash: Default::default(),
+            by_sender: Default::default(),
+            unwritten: Vec::new(),
+        };
+
+        match existing {
+            Some((capacity, mut entries)) if capacity == inner.capacity => {
+                entries.sort_unstable_by_key(|(seq, _)| *seq);
+                inner.next_seq = entries.last().map_or(1, |(seq, _)| seq + 1);
+                for (seq, entry) in entries {
+                    inner.index(seq, entry);
+                }
+            }
+            existing => {
+                // the layout of the slots depends on the capacity, so the file is rewritten
+                file.file.set_len(0).map_err(|err| FsPathError::write(err, &file.path))?;
+                file.write_at(0, &header(inner.capacity))?;
+                if let Some((_, mut entries)) = existing {
+                    entries.sort_unstable_by_key(|(seq, _)| *seq);
+                    let skip = entries.len().saturating_sub(max_events);
+                    for (_, entry) in entries.into_iter().skip(skip) {
+                        inner.push(entry);
+                    }
+                }
+                file.write_records(&std::mem::take(&mut inner.unwritten))?;
+            }
+        }
+
+        debug!(target: "txpool", path=?file.path, events=inner.entries.len(), "Opened transaction history");
+        Ok(Self { inner: Arc::new(Mutex::new(inner)), file: Arc::new(Mutex::new(file)) })
+    }
+
+    /// Records an event of the transaction with the given hash.
+    ///
+    /// If the sender is not known, the sender of the previous events of the transaction is used.
+    pub fn record(&self, hash: TxHash, sender: Option<Address>, event: TransactionHistoryEvent) {
+        let timestamp =
+            SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64;
+        let mut inner = self.inner.lock();
+        let sender = sender.or_else(|| inner.sender_of(&hash));
+        inner.push(TransactionHistoryEntry { timestamp, hash, sender, event });
+    }
+
+    /// Writes all recorded events that are not yet written to the history file.
+    ///
+    /// This does blocking file IO.
+    pub fn flush(&self) -> Result<(), FsPathError> {
+        // the file is locked first, so that concurrent flushes write the events in order
+        let mut file = self.file.lock();
+        let unwritten = std::mem::take(&mut self.inner.lock().unwritten);
+        file.write_records(&unwritten)
+    }
+
+    /// Returns all recorded events of the transaction with the given hash, oldest first.
+    pub fn transaction_history(&self, hash: &TxHash) -> Vec<TransactionHistoryEntry> {
+        let inner = self.inner.lock();
+        inner.by_hash.get(hash).map(|seqs| inner.entries_of(seqs)).unwrap_or_default()
+    }
+
+    /// Returns all recorded events of transactions of the given sender, oldest first.
+    pub fn sender_history(&self, sender: &Address) -> Vec<TransactionHistoryEntry> {
+        let inner = self.inner.lock();
+        inner.by_sender.get(sender).map(|seqs| inner.entries_of(seqs)).unwrap_or_default()
+    }
+
+    /// Returns the number of recorded events.
+    pub fn len(&self) -> usize {
+        self.inner.lock().entries.len()
+    }
+
+    /// Returns `true` if no events are recorded.
+    pub fn is_empty(&self) -> bool {
+        self.inner.lock().entries.is_empty()
+    }
+}
+
+#[derive(Debug)]
+struct TransactionHistoryInner {
+    /// Maximum number of events, the number of slots in the file.
+    capacity: u64,
+    /// Sequence number of the next event.
+    next_seq: u64,
+    /// The recorded events, oldest first, the first one has sequence number
+    /// `next_seq - entries.len()`.
+    entries: VecDeque<TransactionHistoryEntry>,
+    /// Sequence numbers of the events by transaction hash.
+    by_hash: B256Map<VecDeque<u64>>,
+    /// Sequence numbers of the events by sender.
+    by_sender: AddressMap<VecDeque<u64>>,
+    /// Encoded events that are not yet written, with the offsets of their slots in the file.
+    unwritten: Vec<(u64, [u8; RECORD_LEN])>,
+}
+
+impl TransactionHistoryInner {
+    /// Returns the sequence number of the oldest event.
+    fn first_seq(&self) -> u64 {
+        self.next_seq - self.entries.len() as u64
+    }
+
+    /// Returns the events with the given sequence numbers.
+    fn entries_of(&self, seqs: &VecDeque<u64>) -> Vec<TransactionHistoryEntry> {
+        let first_seq = self.first_seq();
+        seqs.iter()
+            .filter_map(|seq| self.entries.get((seq - first_seq) as usize).cloned())
+            .collect()
+    }
+
+    /// Returns the sender of the most recent event of the transaction with a known sender.
+    fn sender_of(&self, hash: &TxHash) -> Option<Address> {
+        let first_seq = self.first_seq();
+        self.by_hash
+            .get(hash)?
+            .iter()
+            .rev()
+            .find_map(|seq| self.entries.get((seq - first_seq) as usize)?.sender)
+    }
+
+    /// Adds a loaded event to the in-memory indices.
+    fn index(&mut self, seq: u64, entry: TransactionHistoryEntry) {
+        self.by_hash.entry(entry.hash).or_default().push_back(seq);
+        if let Some(sender) = entry.sender {
+            self.by_sender.entry(sender).or_default().push_back(seq);
+        }
+        self.entries.push_back(entry);
+    }
+
+    /// Appends the event, evicting the oldest event if the history is full, and queues it for
+    /// writing to its slot in the file.
+    fn push(&mut self, entry: TransactionHistoryEntry) {
+        if self.entries.len() as u64 >= self.capacity {
+            self.evict_oldest();
+        }
+
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        let offset = HEADER_LEN + ((seq - 1) % self.capacity) * RECORD_LEN as u64;
+        self.unwritten.push((offset, entry.encode(seq)));
+        self.index(seq, entry);
+    }
+
+    /// Removes the oldest event from memory, its slot is overwritten by the next event.
+    fn evict_oldest(&mut self) {
+        let seq = self.first_seq();
+        let Some(entry) = self.entries.pop_front() else { return };
+
+        // the oldest event is always the first one of its transaction and sender
+        if let Some(seqs) = self.by_hash.get_mut(&entry.hash) {
+            seqs.pop_front();
+            if seqs.is_empty() {
+                self.by_hash.remove(&entry.hash);
+            }
+        }
+        if let Some(sender) = entry.sender &&
+            let Some(seqs) = self.by_sender.get_mut(&sender)
+        {
+            debug_assert_eq!(seqs.front(), Some(&seq));
+            seqs.pop_front();
+            if seqs.is_empty() {
+                self.by_sender.remove(&sender);
+            }
+        }
+    }
+}
+
+/// The history file of a [`TransactionHistory`].
+#[derive(Debug)]
+struct HistoryFileWriter {
+    /// Path of the history file.
+    path: PathBuf,
+    /// The history file.
+    file: File,
+}
+
+impl HistoryFileWriter {
+    fn write_at(&mut self, offset: u64, data: &[u8]) -> Result<(), FsPathError> {
+        self.file
+            .seek(SeekFrom::Start(offset))
+            .and_then(|_| self.file.write_all(data))
+            .map_err(|err| FsPathError::write(err, &self.path))
+    }
+
+    /// Writes the encoded events to their slots, merging consecutive slots into a single write.
+    fn write_records(&mut self, records: &[(u64, [u8; RECORD_LEN])]) -> Result<(), FsPathError> {
+        let mut buf = Vec::new();
+        let mut buf_offset = 0;
+        for (offset, record) in records {
+            if !buf.is_empty() && buf_offset + buf.len() as u64 != *offset {
+                self.write_at(buf_offset, &buf)?;
+                buf.clear();
+            }
+            if buf.is_empty() {
+                buf_offset = *offset;
+            }
+            buf.extend_from_slice(record);
+        }
+        if !buf.is_empty() {
+            self.write_at(buf_offset, &buf)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the file header for the given capacity.
+fn header(capacity: u64) -> [u8; HEADER_LEN as usize] {
+    let mut header = [0; HEADER_LEN as usize];
+    header[..8].copy_from_slice(HISTORY_MAGIC);
+    header[8] = HISTORY_VERSION;
+    header[9..].copy_from_slice(&capacity.to_be_bytes());
+    header
+}
+
+/// The capacity of a history file and its events with their sequence numbers.
+type HistoryFile = (u64, Vec<(u64, TransactionHistoryEntry)>);
+
+/// Reads the capacity and all events of a history file.
+///
+/// Returns `None` if the file is empty.
+fn read_history_file(path: &Path) -> Result<Option<HistoryFile>, TransactionHistoryError> {
+    let mut data = Vec::new();
+    File::open(path)
+        .and_then(|mut file| file.read_to_end(&mut data))
+        .map_err(|err| FsPathError::read(err, path))?;
+    if data.is_empty() {
+        return Ok(None)
+    }
+
+    let Some((header, records)) = data.split_first_chunk::<{ HEADER_LEN as usize }>() else {
+        return Err(TransactionHistoryError::InvalidHeader)
+    };
+    if &header[..8] != HISTORY_MAGIC || header[8] != HISTORY_VERSION {
+        return Err(TransactionHistoryError::InvalidHeader)
+    }
+    let capacity = u64::from_be_bytes(header[9..].try_into().expect("8 bytes"));
+
+    // a torn write of the last record is ignored
+    let entries = records
+        .chunks_exact(RECORD_LEN)
+        .filter_map(|record| TransactionHistoryEntry::decode(record.try_into().ok()?))
+        .collect();
+
+    Ok(Some((capacity, entries)))
+}
+
+/// Maximum number of events that are written to the history file at once.
+const MAX_WRITE_BATCH: usize = 1024;
+
+/// Records the events of all transactions of the pool into the pool's
+/// [`TransactionHistory`](TransactionPool::transaction_history), if enabled.
+///
+/// Events that arrive together are written to the file in one batch on a blocking task.
+pub async fn record_transaction_history<P>(pool: P)
+where
+    P: TransactionPool,
+{
+    let Some(history) = pool.transaction_history().cloned() else { return };
+    let mut events = pool.all_transactions_event_listener().ready_chunks(MAX_WRITE_BATCH);
+
+    while let Some(batch) = events.next().await {
+        for event in batch {
+            let (hash, sender, event) = match event {
+                FullTransactionEvent::Pending(hash) => {
+                    (hash, pool.get(&hash).map(|tx| tx.sender()), TransactionHistoryEvent::Pending)
+                }
+                FullTransactionEvent::Queued(hash, reason) => (
+                    hash,
+                    pool.get(&hash).map(|tx| tx.sender()),
+                    TransactionHistoryEvent::Queued(reason),
+                ),
+                FullTransactionEvent::Mined { tx_hash, block_hash } => {
+                    (tx_hash, None, TransactionHistoryEvent::Mined(block_hash))
+                }
+                FullTransactionEvent::Replaced { transaction, replaced_by } => (
+                    *transaction.hash(),
+                    Some(transaction.sender()),
+                    TransactionHistoryEvent::Replaced(replaced_by),
+                ),
+                FullTransactionEvent::Discarded(hash, reason) => {
+                    (hash, None, TransactionHistoryEvent::Discarded(reason))
+                }
+                FullTransactionEvent::Invalid(hash, reason) => {
+                    (hash, None, TransactionHistoryEvent::Invalid(reason))
+                }
+                FullTransactionEvent::Propagated(_) => continue,
+            };
+            history.record(hash, sender, event);
+        }
+
+        let history = history.clone();
+        match tokio::task::spawn_blocking(move || history.flush()).await {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => {
+                warn!(target: "txpool", %err, "Failed to write transaction history events")
+            }
+            Err(err) => warn!(target: "txpool", %err, "Transaction history write task failed"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open(path: &Path, max_events: usize) -> TransactionHistory {
+        TransactionHistory::open(
+            TransactionHistoryConfig::new(path.to_path_buf()).with_max_events(max_events),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn record_and_reload_history() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("txpool-history.bin");
+        let sender = Address::random();
+        let (tx, replacement) = (TxHash::random(), TxHash::random());
+
+        let history = open(&path, 10);
+        history.record(
+            tx,
+            Some(sender),
+            TransactionHistoryEvent::Queued(Some(QueuedReason::NonceGap)),
+        );
+        history.record(tx, Some(sender), TransactionHistoryEvent::Pending);
+        history.record(tx, None, TransactionHistoryEvent::Replaced(replacement));
+
+        let events = history.transaction_history(&tx);
+        assert_eq!(
+            events.iter().map(|entry| entry.event.clone()).collect::<Vec<_>>(),
+            vec![
+                TransactionHistoryEvent::Queued(Some(QueuedReason::NonceGap)),
+                TransactionHistoryEvent::Pending,
+                TransactionHistoryEvent::Replaced(replacement),
+            ]
+        );
+        // the sender of the replaced event is taken from the previous events
+        assert_eq!(history.sender_history(&sender).len(), 3);
+        history.flush().unwrap();
+        drop(history);
+
+        let history = open(&path, 10);
+        assert_eq!(history.transaction_history(&tx), events);
+        assert_eq!(history.sender_history(&sender), events);
+    }
+
+    #[test]
+    fn persist_event_reasons() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("txpool-history.bin");
+        let (invalid, discarded) = (TxHash::random(), TxHash::random());
+
+        let history = open(&path, 10);
+        history.record(invalid, None, TransactionHistoryEvent::Invalid(InvalidReason::NonceTooLow));
+        history.record(discarded, None, TransactionHistoryEvent::Discarded(DiscardReason::Removed));
+        history.flush().unwrap();
+        drop(history);
+
+        let history = open(&path, 10);
+        let event = history.transaction_history(&invalid).remove(0).event;
+        assert_eq!(event, TransactionHistoryEvent::Invalid(InvalidReason::NonceTooLow));
+        assert_eq!(event.reason().as_deref(), Some("nonce too low"));
+        assert_eq!(
+            history.transaction_history(&discarded).remove(0).event,
+            TransactionHistoryEvent::Discarded(DiscardReason::Removed)
+        );
+    }
+
+    #[test]
+    fn evict_oldest_events() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("txpool-history.bin");
+        let sender = Address::random();
+        let txs = (0..5).map(|_| TxHash::random()).collect::<Vec<_>>();
+
+        let history = open(&path, 3);
+        for tx in &txs {
+            history.record(*tx, Some(sender), TransactionHistoryEvent::Pending);
+        }
+        assert_eq!(history.len(), 3);
+        assert!(history.transaction_history(&txs[1]).is_empty());
+        assert_eq!(
+            history.sender_history(&sender).iter().map(|entry| entry.hash).collect::<Vec<_>>(),
+            txs[2..]
+        );
+        history.flush().unwrap();
+        drop(history);
+
+        // the slots wrapped around, the order is restored from the sequence numbers
+        let history = open(&path, 3);
+        assert_eq!(
+            history.sender_history(&sender).iter().map(|entry| entry.hash).collect::<Vec<_>>(),
+            txs[2..]
+        );
+
+        // reopening with a smaller capacity keeps the most recent events
+        drop(history);
+        let history = open(&path, 2);
+        assert_eq!(
+            history.sender_history(&sender).iter().map(|entry| entry.hash).collect::<Vec<_>>(),
+            txs[3..]
+        );
+        history.record(
+            txs[0],
+            Some(sender),
+            TransactionHistoryEvent::Discarded(DiscardReason::PoolLimits),
+        );
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.transaction_history(&txs[4]).len(), 1);
+    }
+}
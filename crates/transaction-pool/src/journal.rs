@@ -0,0 +1,401 @@
+//! Journal of all transactions in the pool, to restore the pool after a restart.
+//!
+//! Unlike the local transactions backup of [`maintain`](crate::maintain), the journal contains
+//! the transactions of all subpools and is written periodically, so that the pool can also be
+//! restored after a crash.
+//!
+//! The journal is a binary file that starts with [`JOURNAL_MAGIC`] and the format version,
+//! followed by one entry per transaction:
+//!
+//! ```text
+//! origin (1 byte) | length (4 bytes, big endian) | EIP-2718 network encoding of the transaction
+//! ```
+//!
+//! Blob transactions are stored with their sidecar. Conditional transactions are not journaled,
+//! the format has no room for their [`TransactionConditional`](crate::conditional) and restoring
+//! them without it would lift their inclusion restrictions.
+
+use crate::{
+    traits::{EthPoolTransaction, TransactionPool},
+    PoolTransaction, TransactionOrigin,
+};
+use alloy_consensus::Typed2718;
+use alloy_eips::{Decodable2718, Encodable2718};
+use reth_fs_util::FsPathError;
+use reth_primitives_traits::transaction::signed::SignedTransaction;
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tracing::{debug, error, info, warn};
+
+/// Magic bytes at the start of a journal file.
+pub const JOURNAL_MAGIC: &[u8; 8] = b"rethpool";
+
+/// Current version of the journal format.
+pub const JOURNAL_VERSION: u8 = 1;
+
+/// Default interval at which the journal is written.
+pub const DEFAULT_JOURNAL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Settings for the pool journal task.
+#[derive(Debug, Clone)]
+pub struct PoolJournalConfig {
+    /// Path to the journal file.
+    pub path: PathBuf,
+    /// Interval at which the journal is written.
+    pub interval: Duration,
+}
+
+impl PoolJournalConfig {
+    /// Creates a new config that writes the journal to the given path at the
+    /// [`DEFAULT_JOURNAL_INTERVAL`].
+    pub const fn new(path: PathBuf) -> Self {
+        Self { path, interval: DEFAULT_JOURNAL_INTERVAL }
+    }
+
+    /// Sets the interval at which the journal is written.
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+}
+
+/// Errors possible when writing or loading the pool journal.
+#[derive(Debug, thiserror::Error)]
+pub enum PoolJournalError {
+    /// The file does not start with the journal header.
+    #[error("invalid journal header")]
+    InvalidHeader,
+    /// The journal was written with an unsupported format version.
+    #[error("unsupported journal version {0}")]
+    UnsupportedVersion(u8),
+    /// Error reading or writing the journal file.
+    #[error(transparent)]
+    FsPath(#[from] FsPathError),
+    /// The blocking task writing the journal failed.
+    #[error("pool journal task failed: {0}")]
+    Task(#[from] tokio::task::JoinError),
+}
+
+/// Writes all transactions of the pool, including blob transactions with their sidecars, to the
+/// journal at `path`.
+///
+/// The file is replaced atomically, so a crash while writing keeps the previous journal. Returns
+/// the number of journaled transactions.
+pub fn save_pool_journal<P>(pool: &P, path: &Path) -> Result<usize, PoolJournalError>
+where
+    P: TransactionPool<Transaction: EthPoolTransaction>,
+{
+    let mut transactions = pool.get_all(pool.all_transaction_hashes());
+    // write transactions in nonce order, so that they can be reinserted without nonce gaps
+    transactions.sort_unstable_by_key(|tx| (tx.sender(), tx.nonce()));
+
+    let mut journal = Vec::with_capacity(JOURNAL_MAGIC.len() + 1);
+    journal.extend_from_slice(JOURNAL_MAGIC);
+    journal.push(JOURNAL_VERSION);
+
+    let mut count = 0;
+    for tx in transactions {
+        if tx.transaction.conditional().is_some() {
+            debug!(target: "txpool", tx_hash=?tx.hash(), "Skipping conditional transaction");
+            continue
+        }
+
+        let pooled = if tx.transaction.is_eip4844() {
+            // blob transactions can only be reinserted with their sidecar
+            let Ok(Some(sidecar)) = pool.get_blob(*tx.hash()) else {
+                debug!(target: "txpool", tx_hash=?tx.hash(), "Skipping blob transaction without sidecar");
+                continue
+            };
+            tx.transaction.clone().try_into_pooled_eip4844(sidecar)
+        } else {
+            tx.transaction.clone_into_pooled().ok()
+        };
+        let Some(pooled) = pooled else { continue };
+
+        let encoded = pooled.inner().encoded_2718();
+        journal.push(encode_origin(tx.origin));
+        journal.extend_from_slice(&(encoded.len() as u32).to_be_bytes());
+        journal.extend_from_slice(&encoded);
+        count += 1;
+    }
+
+    if let Some(parent) = path.parent() {
+        reth_fs_util::create_dir_all(parent)?;
+    }
+    reth_fs_util::atomic_write_file(path, |file| file.write_all(&journal))?;
+
+    Ok(count)
+}
+
+/// Loads the journal at `path` and adds its transactions to the pool.
+///
+/// The transactions are validated against the current state like any new transaction, so
+/// transactions that were mined or became invalid while the node was offline are dropped.
+/// Returns the number of transactions that were added to the pool.
+pub async fn load_pool_journal<P>(pool: &P, path: &Path) -> Result<usize, PoolJournalError>
+where
+    P: TransactionPool,
+{
+    if !path.exists() {
+        return Ok(0)
+    }
+
+    let data = reth_fs_util::read(path)?;
+    let entries = decode_journal::<P::Transaction>(&data)?;
+    let total = entries.len();
+
+    let mut by_origin = [
+        (TransactionOrigin::Local, Vec::new()),
+        (TransactionOrigin::External, Vec::new()),
+        (TransactionOrigin::Private, Vec::new()),
+    ];
+    for (origin, tx) in entries {
+        by_origin[encode_origin(origin) as usize].1.push(tx);
+    }
+
+    let mut added = 0;
+    for (origin, transactions) in by_origin {
+        if transactions.is_empty() {
+            continue
+        }
+        added += pool
+            .add_transactions(origin, transactions)
+            .await
+            .into_iter()
+            .filter(Result::is_ok)
+            .count();
+    }
+
+    info!(target: "txpool", journal=?path, total, added, "Restored transactions from pool journal");
+    Ok(added)
+}
+
+/// Writes the journal on a blocking task, see [`save_pool_journal`].
+async fn save_pool_journal_blocking<P>(pool: &P, path: &Path) -> Result<usize, PoolJournalError>
+where
+    P: TransactionPool<Transaction: EthPoolTransaction> + Clone + 'static,
+{
+    let (pool, path) = (pool.clone(), path.to_path_buf());
+    tokio::task::spawn_blocking(move || save_pool_journal(&pool, &path)).await?
+}
+
+/// Decodes the transactions of a journal.
+///
+/// Entries that can't be decoded are skipped. A truncated last entry ends the journal.
+fn decode_journal<T: PoolTransaction>(
+    mut data: &[u8],
+) -> Result<Vec<(TransactionOrigin, T)>, PoolJournalError> {
+    let Some(rest) = data.strip_prefix(JOURNAL_MAGIC) else {
+        return Err(PoolJournalError::InvalidHeader)
+    };
+    let Some((&version, rest)) = rest.split_first() else {
+        return Err(PoolJournalError::InvalidHeader)
+    };
+    if version != JOURNAL_VERSION {
+        return Err(PoolJournalError::UnsupportedVersion(version))
+    }
+    data = rest;
+
+    let mut entries = Vec::new();
+    let mut skipped = 0;
+    while let Some((&origin, rest)) = data.split_first() {
+        let Some((len, rest)) = rest
+            .split_first_chunk::<4>()
+            .filter(|(len, rest)| rest.len() >= u32::from_be_bytes(**len) as usize)
+        else {
+            warn!(target: "txpool", "Pool journal is truncated");
+            break
+        };
+        let (encoded, rest) = rest.split_at(u32::from_be_bytes(*len) as usize);
+        data = rest;
+
+        let Some(origin) = decode_origin(origin) else {
+            skipped += 1;
+            continue
+        };
+        match T::Pooled::decode_2718_exact(encoded).map(|pooled| pooled.try_into_recovered()) {
+            Ok(Ok(recovered)) => entries.push((origin, T::from_pooled(recovered))),
+            _ => skipped += 1,
+        }
+    }
+    if skipped > 0 {
+        warn!(target: "txpool", skipped, "Skipped pool journal entries that could not be decoded");
+    }
+
+    Ok(entries)
+}
+
+const fn encode_origin(origin: TransactionOrigin) -> u8 {
+    match origin {
+        TransactionOrigin::Local => 0,
+        TransactionOrigin::External => 1,
+        TransactionOrigin::Private => 2,
+    }
+}
+
+const fn decode_origin(origin: u8) -> Option<TransactionOrigin> {
+    match origin {
+        0 => Some(TransactionOrigin::Local),
+        1 => Some(TransactionOrigin::External),
+        2 => Some(TransactionOrigin::Private),
+        _ => None,
+    }
+}
+
+/// Task which restores the pool from the journal on startup, and then writes the journal
+/// periodically and once more on shutdown.
+pub async fn pool_journal_task<P>(
+    shutdown: reth_tasks::shutdown::GracefulShutdown,
+    pool: P,
+    config: PoolJournalConfig,
+) where
+    P: TransactionPool<Transaction: EthPoolTransaction> + Clone + 'static,
+{
+    let PoolJournalConfig { path, interval } = config;
+
+    if let Err(err) = load_pool_journal(&pool, &path).await {
+        error!(target: "txpool", %err, journal=?path, "Failed to restore transactions from pool journal");
+    }
+
+    let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut shutdown = std::pin::pin!(shutdown);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                match save_pool_journal_blocking(&pool, &path).await {
+                    Ok(count) => debug!(target: "txpool", journal=?path, count, "Wrote pool journal"),
+                    Err(err) => warn!(target: "txpool", %err, journal=?path, "Failed to write pool journal"),
+                }
+            }
+            graceful_guard = &mut shutdown => {
+                match save_pool_journal_blocking(&pool, &path).await {
+                    Ok(count) => info!(target: "txpool", journal=?path, count, "Wrote pool journal"),
+                    Err(err) => warn!(target: "txpool", %err, journal=?path, "Failed to write pool journal"),
+                }
+                drop(graceful_guard);
+                break
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        blobstore::InMemoryBlobStore, conditional::TransactionConditional,
+        validate::EthTransactionValidatorBuilder, CoinbaseTipOrdering, EthPooledTransaction, Pool,
+    };
+    use alloy_primitives::{hex, U256};
+    use reth_ethereum_primitives::PooledTransactionVariant;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn save_and_load_pool_journal() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("txpool-journal.bin");
+
+        let tx_bytes = hex!(
+            "02f87201830655c2808505ef61f08482565f94388c818ca8b9251b393131c08a736a67ccb192978801049e39c4b5b1f580c001a01764ace353514e8abdfb92446de356b260e3c1225b73fc4c8876a6258d12a129a04f02294aa61ca7676061cd99f29275491218b4754b46a0248e5e42bc5091f507"
+        );
+        let tx = PooledTransactionVariant::decode_2718(&mut &tx_bytes[..]).unwrap();
+        let transaction = EthPooledTransaction::from_pooled(tx.try_into_recovered().unwrap());
+        let provider = MockEthProvider::default().with_genesis_block();
+        let sender = hex!("1f9090aaE28b8a3dCeaDf281B0F12828e676c326").into();
+        provider.add_account(sender, ExtendedAccount::new(42, U256::MAX));
+
+        let new_pool = || {
+            let blob_store = InMemoryBlobStore::default();
+            let validator =
+                EthTransactionValidatorBuilder::new(provider.clone(), EthEvmConfig::mainnet())
+                    .build(blob_store.clone());
+            Pool::new(validator, CoinbaseTipOrdering::default(), blob_store, Default::default())
+        };
+
+        let pool = new_pool();
+        pool.add_transaction(TransactionOrigin::External, transaction.clone()).await.unwrap();
+        assert_eq!(save_pool_journal(&pool, &path).unwrap(), 1);
+
+        let restored = new_pool();
+        assert_eq!(load_pool_journal(&restored, &path).await.unwrap(), 1);
+        let tx = restored.get(transaction.hash()).unwrap();
+        assert_eq!(tx.origin, TransactionOrigin::External);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn skips_conditional_transactions() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("txpool-journal.bin");
+
+        let tx_bytes = hex!(
+            "02f87201830655c2808505ef61f08482565f94388c818ca8b9251b393131c08a736a67ccb192978801049e39c4b5b1f580c001a01764ace353514e8abdfb92446de356b260e3c1225b73fc4c8876a6258d12a129a04f02294aa61ca7676061cd99f29275491218b4754b46a0248e5e42bc5091f507"
+        );
+        let tx = PooledTransactionVariant::decode_2718(&mut &tx_bytes[..]).unwrap();
+        let mut transaction = EthPooledTransaction::from_pooled(tx.try_into_recovered().unwrap());
+        transaction.try_set_conditional(TransactionConditional::default()).unwrap();
+        let provider = MockEthProvider::default().with_genesis_block();
+        let sender = hex!("1f9090aaE28b8a3dCeaDf281B0F12828e676c326").into();
+        provider.add_account(sender, ExtendedAccount::new(42, U256::MAX));
+
+        let blob_store = InMemoryBlobStore::default();
+        let validator = EthTransactionValidatorBuilder::new(provider, EthEvmConfig::mainnet())
+            .build(blob_store.clone());
+        let pool =
+            Pool::new(validator, CoinbaseTipOrdering::default(), blob_store, Default::default());
+        pool.add_transaction(TransactionOrigin::External, transaction).await.unwrap();
+
+        assert_eq!(save_pool_journal(&pool, &path).unwrap(), 0);
+    }
+
+    #[test]
+    fn decode_invalid_journal() {
+        assert!(matches!(
+            decode_journal::<EthPooledTransaction>(b"invalid"),
+            Err(PoolJournalError::InvalidHeader)
+        ));
+
+        let mut journal = JOURNAL_MAGIC.to_vec();
+        journal.push(JOURNAL_VERSION + 1);
+        assert!(matches!(
+            decode_journal::<EthPooledTransaction>(&journal),
+            Err(PoolJournalError::UnsupportedVersion(_))
+        ));
+
+        journal.pop();
+        journal.push(JOURNAL_VERSION);
+        assert!(decode_journal::<EthPooledTransaction>(&journal).unwrap().is_empty());
+
+        // a truncated entry ends the journal
+        journal.extend_from_slice(&[0, 0, 0, 0, 10, 1]);
+        assert!(decode_journal::<EthPooledTransaction>(&journal).unwrap().is_empty());
+    }
+
+    #[test]
+    fn skip_undecodable_journal_entries() {
+        let tx_bytes = hex!(
+            "02f87201830655c2808505ef61f08482565f94388c818ca8b9251b393131c08a736a67ccb192978801049e39c4b5b1f580c001a01764ace353514e8abdfb92446de356b260e3c1225b73fc4c8876a6258d12a129a04f02294aa61ca7676061cd99f29275491218b4754b46a0248e5e42bc5091f507"
+        );
+        let mut journal = JOURNAL_MAGIC.to_vec();
+        journal.push(JOURNAL_VERSION);
+        // an entry that is not a transaction
+        journal.extend_from_slice(&[1, 0, 0, 0, 3, 0xde, 0xad, 0xbe]);
+        // an entry with an unknown origin
+        journal.push(9);
+        journal.extend_from_slice(&(tx_bytes.len() as u32).to_be_bytes());
+        journal.extend_from_slice(&tx_bytes);
+        // a valid entry
+        journal.push(1);
+        journal.extend_from_slice(&(tx_bytes.len() as u32).to_be_bytes());
+        journal.extend_from_slice(&tx_bytes);
+
+        let entries = decode_journal::<EthPooledTransaction>(&journal).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].0, TransactionOrigin::External);
+    }
+}
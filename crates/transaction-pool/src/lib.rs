@@ -292,8 +292,8 @@ pub use crate::{
     ordering::{CoinbaseTipOrdering, Priority, TransactionOrdering},
     pool::{
         blob_tx_priority, fee_delta, state::SubPool, AddedTransactionOutcome,
-        AllTransactionsEvents, FullTransactionEvent, NewTransactionEvent, TransactionEvent,
-        TransactionEvents, TransactionListenerKind,
+        AllTransactionsEvents, DiscardReason, FullTransactionEvent, InvalidReason,
+        NewTransactionEvent, TransactionEvent, TransactionEvents, TransactionListenerKind,
     },
     traits::*,
     validate::{
@@ -301,7 +301,9 @@ pub use crate::{
         TransactionValidator, ValidPoolTransaction,
     },
 };
-use crate::{identifier::TransactionId, pool::PoolInner};
+use crate::{
+    bundle::BundlePool, history::TransactionHistory, identifier::TransactionId, pool::PoolInner,
+};
 use alloy_eips::{
     eip4844::{BlobAndProofV1, BlobAndProofV2},
     eip7594::BlobTransactionSidecarVariant,
@@ -328,8 +330,12 @@ pub mod validate;
 
 pub mod batcher;
 pub mod blobstore;
+pub mod bundle;
+pub mod conditional;
 mod config;
+pub mod history;
 pub mod identifier;
+pub mod journal;
 mod ordering;
 mod traits;
 
@@ -632,6 +638,10 @@ where
         self.pool.queued_transactions()
     }
 
+    fn conditional_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        self.pool.conditional_transactions()
+    }
+
     fn pending_and_queued_txn_count(&self) -> (usize, usize) {
         let data = self.pool.get_pool_data();
         let pending = data.pending_transactions_count();
@@ -808,6 +818,16 @@ where
     ) -> Result<Vec<Option<BlobAndProofV2>>, BlobStoreError> {
         self.pool.blob_store().get_by_versioned_hashes_v3(versioned_hashes)
     }
+
+    fn bundle_pool(
+        &self,
+    ) -> Option<&BundlePool<<Self::Transaction as PoolTransaction>::Consensus>> {
+        self.pool.bundle_pool()
+    }
+
+    fn transaction_history(&self) -> Option<&TransactionHistory> {
+        self.pool.transaction_history()
+    }
 }
 
 impl<V, T, S> TransactionPoolExt for Pool<V, T, S>
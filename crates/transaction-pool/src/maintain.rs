@@ -2,11 +2,13 @@
 
 use crate::{
     blobstore::{BlobSidecarConverter, BlobStoreCanonTracker, BlobStoreUpdates},
+    conditional::{next_block_attributes, validate_block_attributes, validate_known_accounts},
     error::PoolError,
     metrics::MaintainPoolMetrics,
     traits::{CanonicalStateUpdate, EthPoolTransaction, TransactionPool, TransactionPoolExt},
+    validate::AdmissionPolicy,
     AllPoolTransactions, BlobTransactionSidecarVariant, BlockInfo, PoolTransaction, PoolUpdateKind,
-    TransactionOrigin,
+    TransactionOrigin, ValidPoolTransaction,
 };
 use alloy_consensus::{transaction::TxHashRef, BlockHeader, Typed2718};
 use alloy_eips::{BlockNumberOrTag, Decodable2718, Encodable2718};
@@ -430,6 +432,16 @@ pub async fn maintain_transaction_pool<N, Client, P, St>(
                 metrics.inc_reinserted_transactions(pruned_old_transactions.len());
                 let _ = pool.add_external_transactions(pruned_old_transactions).await;
 
+                spawn_remove_failed_conditional_transactions(
+                    &client,
+                    &pool,
+                    &task_spawner,
+                    &metrics,
+                    new_tip.hash(),
+                    new_tip.number(),
+                    new_tip.timestamp(),
+                );
+
                 // keep track of new mined blob transactions
                 blob_store_tracker.add_new_chain_blocks(&new_blocks);
             }
@@ -504,6 +516,16 @@ pub async fn maintain_transaction_pool<N, Client, P, St>(
                 };
                 pool.on_canonical_state_change(update);
 
+                spawn_remove_failed_conditional_transactions(
+                    &client,
+                    &pool,
+                    &task_spawner,
+                    &metrics,
+                    tip.hash(),
+                    tip.number(),
+                    tip.timestamp(),
+                );
+
                 // keep track of mined blob transactions
                 blob_store_tracker.add_new_chain_blocks(&blocks);
 
@@ -692,6 +714,95 @@ where
     Ok(res)
 }
 
+/// Checks the conditionals of the pooled conditional transactions against the given tip on a
+/// blocking task, see [`remove_failed_conditional_transactions`].
+///
+/// Nothing is spawned if the pool holds no conditional transactions.
+fn spawn_remove_failed_conditional_transactions<Client, P>(
+    client: &Client,
+    pool: &P,
+    task_spawner: &Runtime,
+    metrics: &MaintainPoolMetrics,
+    tip_hash: BlockHash,
+    tip_number: BlockNumber,
+    tip_timestamp: u64,
+) where
+    Client: StateProviderFactory + Clone + 'static,
+    P: TransactionPool + 'static,
+{
+    let conditional_transactions = pool.conditional_transactions();
+    if conditional_transactions.is_empty() {
+        return
+    }
+
+    let (client, pool, metrics) = (client.clone(), pool.clone(), metrics.clone());
+    task_spawner.spawn_blocking_task(async move {
+        let removed = remove_failed_conditional_transactions(
+            &client,
+            &pool,
+            &conditional_transactions,
+            tip_hash,
+            tip_number,
+            tip_timestamp,
+        );
+        metrics.inc_removed_conditional_transactions(removed);
+    });
+}
+
+/// Removes the given conditional transactions whose conditional can no longer be satisfied on top
+/// of the given tip, either because its block range has passed or because the storage of a known
+/// account changed.
+///
+/// Descendants of the removed transactions are removed as well. Returns the number of removed
+/// transactions.
+fn remove_failed_conditional_transactions<Client, P>(
+    client: &Client,
+    pool: &P,
+    conditional_transactions: &[Arc<ValidPoolTransaction<P::Transaction>>],
+    tip_hash: BlockHash,
+    tip_number: BlockNumber,
+    tip_timestamp: u64,
+) -> usize
+where
+    Client: StateProviderFactory,
+    P: TransactionPool,
+{
+    let state = match client.state_by_block_hash(tip_hash) {
+        Ok(state) => state,
+        Err(err) => {
+            debug!(target: "txpool", %err, ?tip_hash, "failed to check transaction conditionals");
+            return 0
+        }
+    };
+
+    let next_block = next_block_attributes(tip_number, tip_timestamp);
+    let failed = conditional_transactions
+        .iter()
+        .filter(|tx| {
+            let Some(conditional) = tx.transaction.conditional() else { return false };
+            let result = validate_block_attributes(conditional, &next_block)
+                .and_then(|()| validate_known_accounts(conditional, &*state));
+            match result {
+                Err(err) if err.is_permanent() => {
+                    trace!(target: "txpool", tx=?tx.hash(), %err, "removing conditional transaction");
+                    true
+                }
+                Err(err) => {
+                    trace!(target: "txpool", tx=?tx.hash(), %err, "failed to check conditional");
+                    false
+                }
+                Ok(()) => false,
+            }
+        })
+        .map(|tx| *tx.hash())
+        .collect::<Vec<_>>();
+    if failed.is_empty() {
+        return 0
+    }
+
+    pool.remove_transactions_and_descendants(failed).len()
+}
+
 /// Loads transactions from a file, decodes them from the JSON or RLP format, and
 /// inserts them into the transaction pool on node boot up.
 /// The file is removed after the transactions have been successfully processed.
@@ -849,15 +960,45 @@ pub async fn backup_local_transactions_task<P>(
     drop(graceful_guard)
 }
 
+/// Removes pooled transactions and bundles that are rejected by the given [`AdmissionPolicy`]
+/// whenever its rules are updated.
+///
+/// Transactions are only checked against the rules when they enter the pool, without this task
+/// transactions of newly denied senders would stay in the pool and could still be included.
+pub async fn maintain_admission_policy<P>(pool: P, policy: AdmissionPolicy)
+where
+    P: TransactionPool,
+{
+    let mut updates = policy.subscribe();
+    while updates.changed().await.is_ok() {
+        let rejected = pool
+            .all_transactions()
+            .into_iter()
+            .filter(|tx| policy.check(tx.origin, &tx.transaction).is_err())
+            .map(|tx| *tx.hash())
+            .collect::<Vec<_>>();
+        if !rejected.is_empty() {
+            let removed = pool.remove_transactions(rejected);
+            debug!(target: "txpool", removed = removed.len(), "Removed transactions rejected by the updated admission policy");
+        }
+
+        if let Some(bundle_pool) = pool.bundle_pool() {
+            bundle_pool.remove_rejected();
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
-        blobstore::InMemoryBlobStore, validate::EthTransactionValidatorBuilder,
+        blobstore::InMemoryBlobStore,
+        conditional::{AccountStorage, TransactionConditional},
+        validate::EthTransactionValidatorBuilder,
         CoinbaseTipOrdering, EthPooledTransaction, Pool, TransactionOrigin,
     };
     use alloy_eips::eip2718::Decodable2718;
-    use alloy_primitives::{hex, U256};
+    use alloy_primitives::{hex, B256, U256};
     use reth_ethereum_primitives::PooledTransactionVariant;
     use reth_evm_ethereum::EthEvmConfig;
     use reth_fs_util as fs;
@@ -922,6 +1063,61 @@ mod tests {
         temp_dir.close().unwrap();
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn removes_conditional_transactions_on_state_change() {
+        let tx_bytes = hex!(
+            "02f87201830655c2808505ef61f08482565f94388c818ca8b9251b393131c08a736a67ccb192978801049e39c4b5b1f580c001a01764ace353514e8abdfb92446de356b260e3c1225b73fc4c8876a6258d12a129a04f02294aa61ca7676061cd99f29275491218b4754b46a0248e5e42bc5091f507"
+        );
+        let tx = PooledTransactionVariant::decode_2718(&mut &tx_bytes[..]).unwrap();
+        let provider = MockEthProvider::default().with_genesis_block();
+        let sender = hex!("1f9090aaE28b8a3dCeaDf281B0F12828e676c326").into();
+        provider.add_account(sender, ExtendedAccount::new(42, U256::MAX));
+        let known_account = Address::with_last_byte(0xaa);
+        let set_slot = |value: u64| {
+            provider.add_account(
+                known_account,
+                ExtendedAccount::new(0, U256::ZERO)
+                    .extend_storage([(B256::ZERO, U256::from(value))]),
+            )
+        };
+        set_slot(1);
+
+        let mut transaction = EthPooledTransaction::from_pooled(tx.try_into_recovered().unwrap());
+        let slots = [(U256::ZERO, B256::from(U256::from(1)))].into_iter().collect();
+        transaction
+            .try_set_conditional(TransactionConditional {
+                known_accounts: [(known_account, AccountStorage::Slots(slots))]
+                    .into_iter()
+                    .collect(),
+                ..Default::default()
+            })
+            .unwrap();
+
+        let blob_store = InMemoryBlobStore::default();
+        let validator =
+            EthTransactionValidatorBuilder::new(provider.clone(), EthEvmConfig::mainnet())
+                .build(blob_store.clone());
+        let txpool =
+            Pool::new(validator, CoinbaseTipOrdering::default(), blob_store, Default::default());
+        let hash =
+            txpool.add_transaction(TransactionOrigin::External, transaction).await.unwrap().hash;
+
+        let conditional_transactions = txpool.conditional_transactions();
+        assert_eq!(conditional_transactions.len(), 1);
+        let remove = |txs: &[_]| {
+            remove_failed_conditional_transactions(&provider, &txpool, txs, B256::ZERO, 0, 0)
+        };
+
+        // the known account still holds the expected value
+        assert_eq!(remove(&conditional_transactions), 0);
+        assert!(txpool.contains(&hash));
+
+        set_slot(2);
+        assert_eq!(remove(&conditional_transactions), 1);
+        assert!(!txpool.contains(&hash));
+        assert!(txpool.conditional_transactions().is_empty());
+    }
+
     #[test]
     fn test_update_with_higher_finalized_block() {
         let mut tracker = FinalizedBlockTracker::new(Some(10));
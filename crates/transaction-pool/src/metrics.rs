@@ -79,7 +79,7 @@ pub struct BlobStoreMetrics {
 }
 
 /// Transaction pool maintenance metrics
-#[derive(Metrics)]
+#[derive(Metrics, Clone)]
 #[metrics(scope = "transaction_pool")]
 pub struct MaintainPoolMetrics {
     /// Gauge indicating the number of addresses with pending updates in the pool,
@@ -93,6 +93,8 @@ pub struct MaintainPoolMetrics {
     pub reinserted_transactions: Counter,
     /// Counter for the number of finalized blob transactions that have been removed from tracking.
     pub deleted_tracked_finalized_blobs: Counter,
+    /// Counter for the number of transactions removed because their conditional failed.
+    pub removed_conditional_transactions: Counter,
 }
 
 impl MaintainPoolMetrics {
@@ -114,6 +116,12 @@ impl MaintainPoolMetrics {
         self.deleted_tracked_finalized_blobs.increment(count as u64);
     }
 
+    /// Increments the count of transactions removed because their conditional failed.
+    #[inline]
+    pub fn inc_removed_conditional_transactions(&self, count: usize) {
+        self.removed_conditional_transactions.increment(count as u64);
+    }
+
     /// Increments the drift count by one.
     #[inline]
     pub fn inc_drift(&self) {
@@ -1,5 +1,9 @@
-use crate::{traits::PropagateKind, PoolTransaction, SubPool, ValidPoolTransaction};
+use crate::{
+    error::InvalidPoolTransactionError, traits::PropagateKind, PoolTransaction, SubPool,
+    ValidPoolTransaction,
+};
 use alloy_primitives::{TxHash, B256};
+use reth_primitives_traits::transaction::error::InvalidTransactionError;
 use std::sync::Arc;
 
 use crate::pool::QueuedReason;
@@ -31,10 +35,10 @@ pub enum FullTransactionEvent<T: PoolTransaction> {
         /// The transaction that replaced the event subject.
         replaced_by: TxHash,
     },
-    /// Transaction was dropped due to configured limits.
-    Discarded(TxHash),
-    /// Transaction became invalid indefinitely.
-    Invalid(TxHash),
+    /// Transaction was dropped from the pool, with the reason why.
+    Discarded(TxHash, DiscardReason),
+    /// Transaction became invalid indefinitely, with the kind of error that made it invalid.
+    Invalid(TxHash, InvalidReason),
     /// Transaction was propagated to peers.
     Propagated(Arc<Vec<PropagateKind>>),
 }
@@ -50,13 +54,78 @@ impl<T: PoolTransaction> Clone for FullTransactionEvent<T> {
             Self::Replaced { transaction, replaced_by } => {
                 Self::Replaced { transaction: Arc::clone(transaction), replaced_by: *replaced_by }
             }
-            Self::Discarded(hash) => Self::Discarded(*hash),
-            Self::Invalid(hash) => Self::Invalid(*hash),
+            Self::Discarded(hash, reason) => Self::Discarded(*hash, *reason),
+            Self::Invalid(hash, reason) => Self::Invalid(*hash, *reason),
             Self::Propagated(propagated) => Self::Propagated(Arc::clone(propagated)),
         }
     }
 }
 
+/// The reason why a transaction was discarded from the pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiscardReason {
+    /// Transaction was evicted to enforce the size limits of the pool.
+    PoolLimits,
+    /// Transaction was removed from the pool on request.
+    Removed,
+    /// Transaction could not be validated, e.g. due to a database error.
+    ValidationFailed,
+}
+
+/// The kind of error that made a transaction invalid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidReason {
+    /// Transaction nonce is lower than the sender's nonce.
+    NonceTooLow,
+    /// Sender can't pay for the transaction.
+    InsufficientFunds,
+    /// Transaction fees are below the required minimum.
+    Underpriced,
+    /// Transaction gas limit exceeds the block or transaction gas limit.
+    ExceedsGasLimit,
+    /// Transaction exceeds the size limits.
+    Oversized,
+    /// Transaction type or one of its fields is not supported.
+    Unsupported,
+    /// Any other error.
+    Other,
+}
+
+impl From<&InvalidPoolTransactionError> for InvalidReason {
+    fn from(err: &InvalidPoolTransactionError) -> Self {
+        match err {
+            InvalidPoolTransactionError::Consensus(err) => match err {
+                InvalidTransactionError::NonceNotConsistent { .. } if err.is_nonce_too_low() => {
+                    Self::NonceTooLow
+                }
+                InvalidTransactionError::InsufficientFunds(_) => Self::InsufficientFunds,
+                InvalidTransactionError::FeeCapTooLow | InvalidTransactionError::TipAboveFeeCap => {
+                    Self::Underpriced
+                }
+                InvalidTransactionError::GasTooHigh | InvalidTransactionError::GasLimitTooHigh => {
+                    Self::ExceedsGasLimit
+                }
+                InvalidTransactionError::OldLegacyChainId |
+                InvalidTransactionError::ChainIdMismatch |
+                InvalidTransactionError::Eip2930Disabled |
+                InvalidTransactionError::Eip1559Disabled |
+                InvalidTransactionError::Eip4844Disabled |
+                InvalidTransactionError::Eip7702Disabled |
+                InvalidTransactionError::TxTypeNotSupported => Self::Unsupported,
+                _ => Self::Other,
+            },
+            InvalidPoolTransactionError::ExceedsGasLimit(..) |
+            InvalidPoolTransactionError::MaxTxGasLimitExceeded(..) => Self::ExceedsGasLimit,
+            InvalidPoolTransactionError::Overdraft { .. } => Self::InsufficientFunds,
+            InvalidPoolTransactionError::ExceedsMaxInitCodeSize(..) |
+            InvalidPoolTransactionError::OversizedData { .. } => Self::Oversized,
+            InvalidPoolTransactionError::Underpriced |
+            InvalidPoolTransactionError::PriorityFeeBelowMinimum { .. } => Self::Underpriced,
+            _ => Self::Other,
+        }
+    }
+}
+
 /// Various events that describe status changes of a transaction.
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
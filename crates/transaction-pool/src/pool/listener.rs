@@ -2,7 +2,10 @@
 
 use crate::{
     pool::{
-        events::{FullTransactionEvent, NewTransactionEvent, TransactionEvent},
+        events::{
+            DiscardReason, FullTransactionEvent, InvalidReason, NewTransactionEvent,
+            TransactionEvent,
+        },
         QueuedReason,
     },
     traits::{NewBlobSidecar, PropagateKind},
@@ -188,23 +191,35 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
 
     /// Notify listeners about all discarded transactions.
     #[inline]
-    pub fn discarded_many(&mut self, discarded: &[Arc<ValidPoolTransaction<T>>]) {
+    pub fn discarded_many(
+        &mut self,
+        discarded: &[Arc<ValidPoolTransaction<T>>],
+        reason: DiscardReason,
+    ) {
         if self.is_empty() {
             return
         }
         for tx in discarded {
-            self.discarded(tx.hash());
+            self.discarded(tx.hash(), reason);
         }
     }
 
     /// Notify listeners about a transaction that was discarded.
-    pub fn discarded(&mut self, tx: &TxHash) {
-        self.broadcast_event(tx, TransactionEvent::Discarded, FullTransactionEvent::Discarded(*tx));
+    pub fn discarded(&mut self, tx: &TxHash, reason: DiscardReason) {
+        self.broadcast_event(
+            tx,
+            TransactionEvent::Discarded,
+            FullTransactionEvent::Discarded(*tx, reason),
+        );
     }
 
     /// Notify listeners about a transaction that was invalid.
-    pub fn invalid(&mut self, tx: &TxHash) {
-        self.broadcast_event(tx, TransactionEvent::Invalid, FullTransactionEvent::Invalid(*tx));
+    pub fn invalid(&mut self, tx: &TxHash, reason: InvalidReason) {
+        self.broadcast_event(
+            tx,
+            TransactionEvent::Invalid,
+            FullTransactionEvent::Invalid(*tx, reason),
+        );
     }
 
     /// Notify listeners that the transaction was mined
@@ -67,7 +67,9 @@
 
 use crate::{
     blobstore::BlobStore,
+    bundle::BundlePool,
     error::{PoolError, PoolErrorKind, PoolResult},
+    history::TransactionHistory,
     identifier::{SenderId, SenderIdentifiers, TransactionId},
     metrics::BlobStoreMetrics,
     pool::{
@@ -112,7 +114,9 @@ use tracing::{debug, trace, warn};
 mod events;
 pub use best::{BestTransactionFilter, BestTransactionsWithPrioritizedSenders};
 pub use blob::{blob_tx_priority, fee_delta, BlobOrd, BlobTransactions};
-pub use events::{FullTransactionEvent, NewTransactionEvent, TransactionEvent};
+pub use events::{
+    DiscardReason, FullTransactionEvent, InvalidReason, NewTransactionEvent, TransactionEvent,
+};
 pub use listener::{AllTransactionsEvents, TransactionEvents, TransactionListenerKind};
 pub use parked::{BasefeeOrd, ParkedOrd, ParkedPool, QueuedOrd};
 pub use pending::PendingPool;
@@ -163,6 +167,10 @@ where
     blob_transaction_sidecar_listener: Mutex<Vec<BlobTransactionSidecarListener>>,
     /// Metrics for the blob store
     blob_store_metrics: BlobStoreMetrics,
+    /// Pool of transaction bundles, if enabled.
+    bundle_pool: Option<BundlePool<<T::Transaction as PoolTransaction>::Consensus>>,
+    /// History of transaction events, if enabled.
+    transaction_history: Option<TransactionHistory>,
 }
 
 // === impl PoolInner ===
@@ -175,6 +183,13 @@ where
 {
     /// Create a new transaction pool instance.
     pub fn new(validator: V, ordering: T, blob_store: S, config: PoolConfig) -> Self {
+        let transaction_history = config.transaction_history.clone().and_then(|history_config| {
+            TransactionHistory::open(history_config)
+                .inspect_err(
+                    |err| warn!(target: "txpool", %err, "Failed to open transaction history"),
+                )
+                .ok()
+        });
         Self {
             identifiers: Default::default(),
             validator,
@@ -184,6 +199,8 @@ where
             pending_transaction_listener: Default::default(),
             transaction_listener: Default::default(),
             blob_transaction_sidecar_listener: Default::default(),
+            bundle_pool: config.bundle_pool.map(BundlePool::new),
+            transaction_history,
             config,
             blob_store,
             blob_store_metrics: Default::default(),
@@ -195,6 +212,18 @@ where
         &self.blob_store
     }
 
+    /// Returns the bundle pool, if enabled.
+    pub const fn bundle_pool(
+        &self,
+    ) -> Option<&BundlePool<<T::Transaction as PoolTransaction>::Consensus>> {
+        self.bundle_pool.as_ref()
+    }
+
+    /// Returns the history of transaction events, if enabled.
+    pub const fn transaction_history(&self) -> Option<&TransactionHistory> {
+        self.transaction_history.as_ref()
+    }
+
     /// Returns stats about the size of the pool.
     pub fn size(&self) -> PoolSize {
         self.get_pool_data().size()
@@ -528,11 +557,21 @@ where
         trace!(target: "txpool", ?update, "updating pool on canonical state change");
 
         let block_info = update.block_info();
+        let tip_timestamp = update.timestamp();
         let CanonicalStateUpdate {
             new_tip, changed_accounts, mined_transactions, update_kind, ..
         } = update;
         self.validator.on_new_head_block(new_tip);
 
+        if let Some(bundle_pool) = &self.bundle_pool {
+            bundle_pool.on_canonical_state_change(
+                block_info.last_seen_block_number,
+                tip_timestamp,
+                block_info.pending_basefee,
+                &mined_transactions,
+            );
+        }
+
         let changed_senders = self.changed_senders(changed_accounts.into_iter());
 
         // update the pool
@@ -621,11 +660,13 @@ where
                 (Ok(AddedTransactionOutcome { hash, state }), Some(meta))
             }
             TransactionValidationOutcome::Invalid(tx, err) => {
-                self.with_event_listener(|listener| listener.invalid(tx.hash()));
+                self.with_event_listener(|listener| listener.invalid(tx.hash(), (&err).into()));
                 (Err(PoolError::new(*tx.hash(), err)), None)
             }
             TransactionValidationOutcome::Error(tx_hash, err) => {
-                self.with_event_listener(|listener| listener.discarded(&tx_hash));
+                self.with_event_listener(|listener| {
+                    listener.discarded(&tx_hash, DiscardReason::ValidationFailed)
+                });
                 (Err(PoolError::other(tx_hash, err)), None)
             }
         }
@@ -706,7 +747,9 @@ where
         if !discarded.is_empty() {
             // Delete any blobs associated with discarded blob transactions
             self.delete_discarded_blobs(discarded.iter());
-            self.with_event_listener(|listener| listener.discarded_many(&discarded));
+            self.with_event_listener(|listener| {
+                listener.discarded_many(&discarded, DiscardReason::PoolLimits)
+            });
 
             let discarded_hashes =
                 discarded.into_iter().map(|tx| *tx.hash()).collect::<HashSet<_>>();
@@ -892,7 +935,7 @@ where
                 listener.pending(tx.hash(), None);
             }
             for tx in &discarded {
-                listener.discarded(tx.hash());
+                listener.discarded(tx.hash(), DiscardReason::PoolLimits);
             }
         })
     }
@@ -959,7 +1002,7 @@ where
                 listener.pending(tx.hash(), None);
             }
             for tx in &discarded {
-                listener.discarded(tx.hash());
+                listener.discarded(tx.hash(), DiscardReason::PoolLimits);
             }
         });
 
@@ -988,7 +1031,7 @@ where
                     listener.pending(tx.hash(), None);
                 }
                 for tx in discarded {
-                    listener.discarded(tx.hash());
+                    listener.discarded(tx.hash(), DiscardReason::PoolLimits);
                 }
             }
             AddedTransaction::Parked { transaction, replaced, queued_reason, .. } => {
@@ -1042,6 +1085,11 @@ where
         }
     }
 
+    /// Returns all transactions in the pool that were submitted with a conditional
+    pub fn conditional_transactions(&self) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        self.get_pool_data().all().conditional_transactions().cloned().collect()
+    }
+
     /// Returns _all_ transactions in the pool
     pub fn all_transaction_hashes(&self) -> Vec<TxHash> {
         self.get_pool_data().all().transactions_iter().map(|tx| *tx.hash()).collect()
@@ -1060,7 +1108,9 @@ where
         }
         let removed = self.pool.write().remove_transactions(hashes);
 
-        self.with_event_listener(|listener| listener.discarded_many(&removed));
+        self.with_event_listener(|listener| {
+            listener.discarded_many(&removed, DiscardReason::Removed)
+        });
 
         removed
     }
@@ -1078,7 +1128,7 @@ where
 
         self.with_event_listener(|listener| {
             for tx in &removed {
-                listener.discarded(tx.hash());
+                listener.discarded(tx.hash(), DiscardReason::Removed);
             }
         });
 
@@ -1093,7 +1143,9 @@ where
         let Some(sender_id) = self.sender_id(&sender) else { return Vec::new() };
         let removed = self.pool.write().remove_transactions_by_sender(sender_id);
 
-        self.with_event_listener(|listener| listener.discarded_many(&removed));
+        self.with_event_listener(|listener| {
+            listener.discarded_many(&removed, DiscardReason::Removed)
+        });
 
         removed
     }
@@ -1416,6 +1416,8 @@ pub(crate) struct AllTransactions<T: PoolTransaction> {
     local_transactions_config: LocalTransactionConfig,
     /// All accounts with a pooled authorization
     auths: FxHashMap<SenderId, B256Set>,
+    /// Hashes of all transactions that were submitted with a conditional
+    conditionals: B256Set,
     /// All Transactions metrics
     metrics: AllTransactionsMetrics,
 }
@@ -1446,6 +1448,13 @@ impl<T: PoolTransaction> AllTransactions<T> {
         self.by_hash.values()
     }
 
+    /// Returns all transactions that were submitted with a conditional.
+    pub(crate) fn conditional_transactions(
+        &self,
+    ) -> impl Iterator<Item = &Arc<ValidPoolTransaction<T>>> + '_ {
+        self.conditionals.iter().filter_map(|hash| self.by_hash.get(hash))
+    }
+
     /// Returns if the transaction for the given hash is already included in this pool
     pub(crate) fn contains(&self, tx_hash: &TxHash) -> bool {
         self.by_hash.contains_key(tx_hash)
@@ -1754,6 +1763,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
         let tx = self.by_hash.remove(tx_hash)?;
         let internal = self.txs.remove(&tx.transaction_id)?;
         self.remove_auths(&internal);
+        self.conditionals.remove(tx_hash);
         // decrement the counter for the sender.
         self.tx_decr(tx.sender_id());
         Some((tx, internal.subpool))
@@ -1769,6 +1779,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
         let internal = self.txs.remove(tx_id)?;
         let tx = self.by_hash.remove(internal.transaction.hash())?;
         self.remove_auths(&internal);
+        self.conditionals.remove(tx.hash());
         // decrement the counter for the sender.
         self.tx_decr(tx.sender_id());
         Some((tx, internal.subpool))
@@ -1820,6 +1831,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
             self.by_hash.remove(internal.transaction.hash()).map(|tx| (tx, internal.subpool));
 
         self.remove_auths(&internal);
+        self.conditionals.remove(internal.transaction.hash());
 
         result
     }
@@ -2081,6 +2093,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
                 self.by_hash.insert(new_hash, new_transaction);
 
                 self.remove_auths(&replaced);
+                self.conditionals.remove(replaced.transaction.hash());
 
                 // also remove the hash
                 replaced_tx = Some((replaced.transaction, replaced.subpool));
@@ -2094,6 +2107,10 @@ impl<T: PoolTransaction> AllTransactions<T> {
             }
         }
 
+        if transaction.transaction.conditional().is_some() {
+            self.conditionals.insert(*transaction.hash());
+        }
+
         // The next transaction of this sender
         let on_chain_id = TransactionId::new(transaction.sender_id(), on_chain_nonce);
         {
@@ -2186,6 +2203,10 @@ impl<T: PoolTransaction> AllTransactions<T> {
     pub(crate) fn assert_invariants(&self) {
         assert_eq!(self.by_hash.len(), self.txs.len(), "by_hash.len() != txs.len()");
         assert!(self.auths.len() <= self.txs.len(), "auths.len() > txs.len()");
+        assert!(
+            self.conditionals.iter().all(|hash| self.by_hash.contains_key(hash)),
+            "conditionals contains unknown transaction"
+        );
     }
 }
 
@@ -2215,6 +2236,7 @@ impl<T: PoolTransaction> Default for AllTransactions<T> {
             price_bumps: Default::default(),
             local_transactions_config: Default::default(),
             auths: Default::default(),
+            conditionals: Default::default(),
             metrics: Default::default(),
         }
     }
@@ -52,7 +52,10 @@
 
 use crate::{
     blobstore::BlobStoreError,
+    bundle::BundlePool,
+    conditional::TransactionConditional,
     error::{InvalidPoolTransactionError, PoolError, PoolResult},
+    history::TransactionHistory,
     pool::{
         state::SubPool, BestTransactionFilter, NewTransactionEvent, TransactionEvents,
         TransactionListenerKind,
@@ -455,6 +458,20 @@ pub trait TransactionPool: Clone + Debug + Send + Sync {
     /// Consumer: RPC
     fn all_transactions(&self) -> AllPoolTransactions<Self::Transaction>;
 
+    /// Returns all transactions in the pool that were submitted with a conditional.
+    ///
+    /// See also [`PoolTransaction::conditional`].
+    ///
+    /// Consumer: Pool maintenance
+    fn conditional_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        let AllPoolTransactions { pending, queued } = self.all_transactions();
+        pending
+            .into_iter()
+            .chain(queued)
+            .filter(|tx| tx.transaction.conditional().is_some())
+            .collect()
+    }
+
     /// Returns the _hashes_ of all transactions regardless of whether they can be propagated or
     /// not.
     ///
@@ -722,6 +739,23 @@ pub trait TransactionPool: Clone + Debug + Send + Sync {
         &self,
         versioned_hashes: &[B256],
     ) -> Result<Vec<Option<BlobAndProofV2>>, BlobStoreError>;
+
+    /// Returns the pool of transaction bundles submitted via `eth_sendBundle` and
+    /// `mev_sendBundle`.
+    ///
+    /// Returns `None` if the pool does not support bundles.
+    fn bundle_pool(
+        &self,
+    ) -> Option<&BundlePool<<Self::Transaction as PoolTransaction>::Consensus>> {
+        None
+    }
+
+    /// Returns the on-disk history of transaction events.
+    ///
+    /// Returns `None` if the history is not enabled.
+    fn transaction_history(&self) -> Option<&TransactionHistory> {
+        None
+    }
 }
 
 /// Extension for [`TransactionPool`] trait that allows to set the current block info.
@@ -1366,6 +1400,24 @@ pub trait PoolTransaction:
     fn requires_nonce_check(&self) -> bool {
         true
     }
+
+    /// Returns the conditions under which the transaction may be included, if it was submitted
+    /// with any.
+    ///
+    /// See also [`conditional`](crate::conditional).
+    fn conditional(&self) -> Option<&TransactionConditional> {
+        None
+    }
+
+    /// Attaches the conditions under which the transaction may be included.
+    ///
+    /// Returns the conditional back if this transaction type can't carry one.
+    fn try_set_conditional(
+        &mut self,
+        conditional: TransactionConditional,
+    ) -> Result<(), TransactionConditional> {
+        Err(conditional)
+    }
 }
 
 /// Super trait for transactions that can be converted to and from Eth transactions intended for the
@@ -1430,6 +1482,9 @@ pub struct EthPooledTransaction<T = TransactionSigned> {
 
     /// The blob side car for this transaction
     pub blob_sidecar: EthBlobTransactionSidecar,
+
+    /// The conditions under which the transaction may be included, if any.
+    pub conditional: Option<Box<TransactionConditional>>,
 }
 
 impl<T: SignedTransaction> EthPooledTransaction<T> {
@@ -1458,7 +1513,7 @@ impl<T: SignedTransaction> EthPooledTransaction<T> {
             blob_sidecar = EthBlobTransactionSidecar::Missing;
         }
 
-        Self { transaction, cost, encoded_length, blob_sidecar }
+        Self { transaction, cost, encoded_length, blob_sidecar, conditional: None }
     }
 
     /// Return the reference to the underlying transaction.
@@ -1538,6 +1593,18 @@ impl PoolTransaction for EthPooledTransaction {
     fn encoded_length(&self) -> usize {
         self.encoded_length
     }
+
+    fn conditional(&self) -> Option<&TransactionConditional> {
+        self.conditional.as_deref()
+    }
+
+    fn try_set_conditional(
+        &mut self,
+        conditional: TransactionConditional,
+    ) -> Result<(), TransactionConditional> {
+        self.conditional = Some(Box::new(conditional));
+        Ok(())
+    }
 }
 
 impl<T: Typed2718> Typed2718 for EthPooledTransaction<T> {
@@ -0,0 +1,327 @@
+//! Configurable admission rules for transactions entering the pool.
+
+use crate::{
+    error::{InvalidPoolTransactionError, PoolTransactionError},
+    traits::{PoolTransaction, TransactionOrigin},
+};
+use alloy_consensus::Transaction;
+use alloy_primitives::{map::AddressSet, Address};
+use parking_lot::RwLock;
+use std::{any::Any, sync::Arc};
+use tokio::sync::watch;
+
+/// Admission rules that are applied on top of the built-in transaction validation.
+///
+/// All rules are disabled by default.
+///
+/// ```toml
+/// denied_addresses = ["0x0000000000000000000000000000000000000001"]
+/// deny_contract_creation = true
+/// max_calldata_bytes = 4096
+///
+/// [min_priority_fee]
+/// external = 1000000000
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct AdmissionPolicyConfig {
+    /// Addresses whose transactions are rejected.
+    ///
+    /// This applies to the sender, the recipient and the authorities of EIP-7702 authorizations.
+    pub denied_addresses: AddressSet,
+    /// Minimum priority fee per origin of the transaction.
+    pub min_priority_fee: OriginPriorityFees,
+    /// Whether to reject transactions that create a contract.
+    pub deny_contract_creation: bool,
+    /// Maximum size of the calldata of a transaction in bytes.
+    pub max_calldata_bytes: Option<usize>,
+}
+
+/// A minimum priority fee per [`TransactionOrigin`].
+///
+/// For legacy transactions, the gas price is compared against the minimum.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct OriginPriorityFees {
+    /// Minimum priority fee of [`TransactionOrigin::Local`] transactions.
+    pub local: Option<u128>,
+    /// Minimum priority fee of [`TransactionOrigin::External`] transactions.
+    pub external: Option<u128>,
+    /// Minimum priority fee of [`TransactionOrigin::Private`] transactions.
+    pub private: Option<u128>,
+}
+
+impl OriginPriorityFees {
+    /// Returns the minimum priority fee for transactions of the given origin.
+    pub const fn for_origin(&self, origin: TransactionOrigin) -> Option<u128> {
+        match origin {
+            TransactionOrigin::Local => self.local,
+            TransactionOrigin::External => self.external,
+            TransactionOrigin::Private => self.private,
+        }
+    }
+}
+
+/// Errors returned if a transaction violates the [`AdmissionPolicyConfig`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AdmissionPolicyError {
+    /// The transaction involves a denied address.
+    #[error("address {0} is denied")]
+    DeniedAddress(Address),
+    /// The transaction creates a contract.
+    #[error("contract creation is not allowed")]
+    ContractCreation,
+    /// The calldata of the transaction exceeds the limit.
+    #[error("calldata size {size} exceeds the limit of {limit} bytes")]
+    CalldataTooLarge {
+        /// Size of the calldata.
+        size: usize,
+        /// Maximum allowed size.
+        limit: usize,
+    },
+}
+
+impl PoolTransactionError for AdmissionPolicyError {
+    fn is_bad_transaction(&self) -> bool {
+        // the transactions are valid, they are only rejected by local policy
+        false
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// A shared [`AdmissionPolicyConfig`] that can be updated while the pool is running.
+///
+/// Clones share the same config, so an update through one of them is applied to all transactions
+/// validated afterwards.
+#[derive(Debug, Clone)]
+pub struct AdmissionPolicy {
+    config: Arc<RwLock<AdmissionPolicyConfig>>,
+    /// Notifies subscribers about updated rules.
+    updates: Arc<watch::Sender<()>>,
+}
+
+impl Default for AdmissionPolicy {
+    fn default() -> Self {
+        Self::new(AdmissionPolicyConfig::default())
+    }
+}
+
+impl AdmissionPolicy {
+    /// Creates a new policy with the given rules.
+    pub fn new(config: AdmissionPolicyConfig) -> Self {
+        Self { config: Arc::new(RwLock::new(config)), updates: Arc::new(watch::Sender::new(())) }
+    }
+
+    /// Returns the rules currently in effect.
+    pub fn config(&self) -> AdmissionPolicyConfig {
+        self.config.read().clone()
+    }
+
+    /// Replaces the rules with the given ones.
+    ///
+    /// Transactions that are already in the pool are not affected by this, subscribers of
+    /// [`subscribe`](Self::subscribe) are notified so that they can remove them, see
+    /// [`maintain_admission_policy`](crate::maintain::maintain_admission_policy).
+    pub fn update(&self, config: AdmissionPolicyConfig) {
+        *self.config.write() = config;
+        self.updates.send_replace(());
+    }
+
+    /// Returns a receiver that is notified whenever the rules are updated.
+    pub fn subscribe(&self) -> watch::Receiver<()> {
+        self.updates.subscribe()
+    }
+
+    /// Checks the transaction against the rules.
+    pub fn check<T: PoolTransaction>(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &T,
+    ) -> Result<(), InvalidPoolTransactionError> {
+        self.check_with_sender(origin, transaction.sender(), transaction)
+    }
+
+    /// Checks the transaction sent by `sender` against the rules.
+    ///
+    /// This can be used for transactions that are not pool transactions, like the transactions of
+    /// a bundle.
+    pub fn check_with_sender<T: Transaction>(
+        &self,
+        origin: TransactionOrigin,
+        sender: Address,
+        transaction: &T,
+    ) -> Result<(), InvalidPoolTransactionError> {
+        let config = self.config.read();
+
+        if !config.denied_addresses.is_empty() {
+            if config.denied_addresses.contains(&sender) {
+                return Err(InvalidPoolTransactionError::other(AdmissionPolicyError::DeniedAddress(
+                    sender,
+                )))
+            }
+            if let Some(to) = transaction.to() &&
+                config.denied_addresses.contains(&to)
+            {
+                return Err(InvalidPoolTransactionError::other(AdmissionPolicyError::DeniedAddress(
+                    to,
+                )))
+            }
+            // authorizations with an invalid signature are skipped during execution
+            if let Some(authority) = transaction
+                .authorization_list()
+                .into_iter()
+                .flatten()
+                .filter_map(|auth| auth.recover_authority().ok())
+                .find(|authority| config.denied_addresses.contains(authority))
+            {
+                return Err(InvalidPoolTransactionError::other(AdmissionPolicyError::DeniedAddress(
+                    authority,
+                )))
+            }
+        }
+
+        if config.deny_contract_creation && transaction.is_create() {
+            return Err(InvalidPoolTransactionError::other(AdmissionPolicyError::ContractCreation))
+        }
+
+        if let Some(limit) = config.max_calldata_bytes {
+            let size = transaction.input().len();
+            if size > limit {
+                return Err(InvalidPoolTransactionError::other(
+                    AdmissionPolicyError::CalldataTooLarge { size, limit },
+                ))
+            }
+        }
+
+        if let Some(minimum_priority_fee) = config.min_priority_fee.for_origin(origin) &&
+            transaction.priority_fee_or_price() < minimum_priority_fee
+        {
+            return Err(InvalidPoolTransactionError::PriorityFeeBelowMinimum {
+                minimum_priority_fee,
+            })
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockTransaction;
+    use alloy_primitives::{address, TxKind};
+
+    fn denied(err: InvalidPoolTransactionError) -> Option<AdmissionPolicyError> {
+        err.downcast_other_ref::<AdmissionPolicyError>().cloned()
+    }
+
+    fn eip1559_with_kind(kind: TxKind) -> MockTransaction {
+        let mut tx = MockTransaction::eip1559();
+        if let MockTransaction::Eip1559 { to, .. } = &mut tx {
+            *to = kind;
+        }
+        tx
+    }
+
+    #[test]
+    fn deny_sender_and_recipient() {
+        let denied_address = address!("0x0000000000000000000000000000000000000bad");
+        let policy = AdmissionPolicy::new(AdmissionPolicyConfig {
+            denied_addresses: [denied_address].into_iter().collect(),
+            ..Default::default()
+        });
+
+        let tx = MockTransaction::eip1559();
+        assert!(policy.check(TransactionOrigin::External, &tx).is_ok());
+
+        let tx = MockTransaction::eip1559().with_sender(denied_address);
+        assert_eq!(
+            denied(policy.check(TransactionOrigin::Local, &tx).unwrap_err()),
+            Some(AdmissionPolicyError::DeniedAddress(denied_address))
+        );
+
+        let tx = eip1559_with_kind(TxKind::Call(denied_address));
+        assert_eq!(
+            denied(policy.check(TransactionOrigin::External, &tx).unwrap_err()),
+            Some(AdmissionPolicyError::DeniedAddress(denied_address))
+        );
+
+        let mut updates = policy.subscribe();
+        policy.update(AdmissionPolicyConfig::default());
+        assert!(updates.has_changed().unwrap());
+        assert!(policy.check(TransactionOrigin::External, &tx).is_ok());
+    }
+
+    #[test]
+    fn deny_contract_creation_and_large_calldata() {
+        let policy = AdmissionPolicy::new(AdmissionPolicyConfig {
+            deny_contract_creation: true,
+            max_calldata_bytes: Some(4),
+            ..Default::default()
+        });
+
+        let tx = eip1559_with_kind(TxKind::Create);
+        assert_eq!(
+            denied(policy.check(TransactionOrigin::External, &tx).unwrap_err()),
+            Some(AdmissionPolicyError::ContractCreation)
+        );
+
+        let tx = MockTransaction::eip1559().with_input(vec![0; 5].into());
+        assert_eq!(
+            denied(policy.check(TransactionOrigin::External, &tx).unwrap_err()),
+            Some(AdmissionPolicyError::CalldataTooLarge { size: 5, limit: 4 })
+        );
+    }
+
+    #[test]
+    fn min_priority_fee_per_origin() {
+        let policy = AdmissionPolicy::new(AdmissionPolicyConfig {
+            min_priority_fee: OriginPriorityFees { external: Some(10), ..Default::default() },
+            ..Default::default()
+        });
+
+        let tx = MockTransaction::eip1559().with_priority_fee(5).with_max_fee(100);
+        assert!(policy.check(TransactionOrigin::Local, &tx).is_ok());
+        assert!(matches!(
+            policy.check(TransactionOrigin::External, &tx),
+            Err(InvalidPoolTransactionError::PriorityFeeBelowMinimum { minimum_priority_fee: 10 })
+        ));
+
+        let tx = MockTransaction::legacy().with_gas_price(10);
+        assert!(policy.check(TransactionOrigin::External, &tx).is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn parse_toml_config() {
+        let config: AdmissionPolicyConfig = toml::from_str(
+            r#"
+            denied_addresses = ["0x0000000000000000000000000000000000000bad"]
+            max_calldata_bytes = 4096
+
+            [min_priority_fee]
+            external = 1000000000
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config,
+            AdmissionPolicyConfig {
+                denied_addresses: [address!("0x0000000000000000000000000000000000000bad")]
+                    .into_iter()
+                    .collect(),
+                min_priority_fee: OriginPriorityFees {
+                    external: Some(1_000_000_000),
+                    ..Default::default()
+                },
+                deny_contract_creation: false,
+                max_calldata_bytes: Some(4096),
+            }
+        );
+    }
+}
@@ -3,12 +3,13 @@
 use super::constants::DEFAULT_MAX_TX_INPUT_BYTES;
 use crate::{
     blobstore::BlobStore,
+    conditional::{next_block_attributes, validate_conditional_cost, TransactionConditionalError},
     error::{
         Eip4844PoolTransactionError, Eip7702PoolTransactionError, InvalidPoolTransactionError,
     },
     metrics::TxPoolValidationMetrics,
     traits::TransactionOrigin,
-    validate::ValidTransaction,
+    validate::{AdmissionPolicy, ValidTransaction},
     Address, BlobTransactionSidecarVariant, EthBlobTransactionSidecar, EthPoolTransaction,
     LocalTransactionConfig, TransactionValidationOutcome, TransactionValidationTaskExecutor,
     TransactionValidator,
@@ -119,6 +120,9 @@ pub struct EthTransactionValidator<Client, T, Evm> {
     /// When false, EIP-7594 (v1) sidecars are always rejected and EIP-4844 (v0) sidecars
     /// are always accepted, regardless of Osaka fork activation.
     eip7594: bool,
+    /// Optional admission rules applied at the end of
+    /// [`validate_stateless`](Self::validate_stateless).
+    admission_policy: Option<AdmissionPolicy>,
     /// Optional additional stateless validation check applied at the end of
     /// [`validate_stateless`](Self::validate_stateless).
     additional_stateless_validation: Option<StatelessValidationFn<T>>,
@@ -142,6 +146,7 @@ impl<Client, Tx, Evm> fmt::Debug for EthTransactionValidator<Client, Tx, Evm> {
             .field("max_tx_gas_limit", &self.max_tx_gas_limit)
             .field("disable_balance_check", &self.disable_balance_check)
             .field("eip7594", &self.eip7594)
+            .field("admission_policy", &self.admission_policy)
             .field(
                 "additional_stateless_validation",
                 &self.additional_stateless_validation.as_ref().map(|_| "..."),
@@ -237,6 +242,11 @@ impl<Client, Tx, Evm> EthTransactionValidator<Client, Tx, Evm> {
         self.disable_balance_check
     }
 
+    /// Returns the configured admission policy, if any.
+    pub const fn admission_policy(&self) -> Option<&AdmissionPolicy> {
+        self.admission_policy.as_ref()
+    }
+
     /// Sets an additional stateless validation check that is applied at the end of
     /// [`validate_stateless`](Self::validate_stateless).
     ///
@@ -582,6 +592,23 @@ where
             return Err(InvalidTransactionError::GasLimitTooHigh.into())
         }
 
+        // Reject conditional transactions that can't be included in any future block
+        if let Some(conditional) = transaction.conditional() {
+            validate_conditional_cost(conditional).map_err(InvalidPoolTransactionError::other)?;
+            let next_block = next_block_attributes(
+                self.fork_tracker.tip_number(),
+                self.fork_tracker.tip_timestamp(),
+            );
+            if conditional.has_exceeded_block_attributes(&next_block) {
+                return Err(InvalidPoolTransactionError::other(TransactionConditionalError::Expired))
+            }
+        }
+
+        // Apply the configured admission rules
+        if let Some(policy) = &self.admission_policy {
+            policy.check(origin, transaction)?;
+        }
+
         // Run additional stateless validation if configured
         if let Some(check) = &self.additional_stateless_validation {
             check(origin, transaction)?;
@@ -856,6 +883,9 @@ where
         self.fork_tracker
             .tip_timestamp
             .store(new_tip_block.timestamp(), std::sync::atomic::Ordering::Relaxed);
+        self.fork_tracker
+            .tip_number
+            .store(new_tip_block.number(), std::sync::atomic::Ordering::Relaxed);
 
         if let Some(blob_params) =
             self.chain_spec().blob_params_at_timestamp(new_tip_block.timestamp())
@@ -966,6 +996,8 @@ pub struct EthTransactionValidatorBuilder<Client, Evm> {
     osaka: bool,
     /// Timestamp of the tip block.
     tip_timestamp: u64,
+    /// Number of the tip block.
+    tip_number: u64,
     /// Max blob count at the block's timestamp.
     max_blob_count: u64,
     /// Whether using EIP-2718 type transactions is allowed
@@ -1007,6 +1039,8 @@ pub struct EthTransactionValidatorBuilder<Client, Evm> {
     /// When false, EIP-7594 (v1) sidecars are always rejected and EIP-4844 (v0) sidecars
     /// are always accepted, regardless of Osaka fork activation.
     eip7594: bool,
+    /// Admission rules applied on top of the built-in validation.
+    admission_policy: Option<AdmissionPolicy>,
 }
 
 impl<Client, Evm> EthTransactionValidatorBuilder<Client, Evm> {
@@ -1056,6 +1090,7 @@ impl<Client, Evm> EthTransactionValidatorBuilder<Client, Evm> {
             osaka: chain_spec.is_osaka_active_at_timestamp(tip.timestamp()),
 
             tip_timestamp: tip.timestamp(),
+            tip_number: tip.number(),
 
             max_blob_count: chain_spec
                 .blob_params_at_timestamp(tip.timestamp())
@@ -1078,6 +1113,9 @@ impl<Client, Evm> EthTransactionValidatorBuilder<Client, Evm> {
 
             // EIP-7594 sidecars are accepted by default (standard Ethereum behavior)
             eip7594: true,
+
+            // no admission rules by default
+            admission_policy: None,
         }
     }
 
@@ -1249,6 +1287,14 @@ impl<Client, Evm> EthTransactionValidatorBuilder<Client, Evm> {
         self
     }
 
+    /// Sets the admission rules that are applied on top of the built-in validation.
+    ///
+    /// The rules can be updated through the [`AdmissionPolicy`] while the validator is running.
+    pub fn with_admission_policy(mut self, admission_policy: Option<AdmissionPolicy>) -> Self {
+        self.admission_policy = admission_policy;
+        self
+    }
+
     /// Adds a custom transaction type to the validator.
     pub const fn with_custom_tx_type(mut self, tx_type: u8) -> Self {
         self.other_tx_types.set_bit(tx_type as usize, true);
@@ -1268,6 +1314,7 @@ impl<Client, Evm> EthTransactionValidatorBuilder<Client, Evm> {
             prague,
             osaka,
             tip_timestamp,
+            tip_number,
             eip2718,
             eip1559,
             eip4844,
@@ -1286,6 +1333,7 @@ impl<Client, Evm> EthTransactionValidatorBuilder<Client, Evm> {
             max_initcode_size,
             tx_gas_limit_cap,
             eip7594,
+            admission_policy,
         } = self;
 
         let fork_tracker = ForkTracker {
@@ -1294,6 +1342,7 @@ impl<Client, Evm> EthTransactionValidatorBuilder<Client, Evm> {
             prague: AtomicBool::new(prague),
             osaka: AtomicBool::new(osaka),
             tip_timestamp: AtomicU64::new(tip_timestamp),
+            tip_number: AtomicU64::new(tip_number),
             max_blob_count: AtomicU64::new(max_blob_count),
             max_initcode_size: AtomicUsize::new(max_initcode_size),
             tx_gas_limit_cap: AtomicU64::new(tx_gas_limit_cap),
@@ -1320,6 +1369,7 @@ impl<Client, Evm> EthTransactionValidatorBuilder<Client, Evm> {
             validation_metrics: TxPoolValidationMetrics::default(),
             other_tx_types,
             eip7594,
+            admission_policy,
             additional_stateless_validation: None,
             additional_stateful_validation: None,
         }
@@ -1360,6 +1410,8 @@ pub struct ForkTracker {
     pub max_blob_count: AtomicU64,
     /// Tracks the timestamp of the tip block.
     pub tip_timestamp: AtomicU64,
+    /// Tracks the number of the tip block.
+    pub tip_number: AtomicU64,
     /// Cached max initcode size from EVM config
     pub max_initcode_size: AtomicUsize,
     /// Cached transaction gas limit cap from EVM config (0 = no cap)
@@ -1392,6 +1444,11 @@ impl ForkTracker {
         self.tip_timestamp.load(std::sync::atomic::Ordering::Relaxed)
     }
 
+    /// Returns the number of the tip block.
+    pub fn tip_number(&self) -> u64 {
+        self.tip_number.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
     /// Returns the max allowed blob count per transaction.
     pub fn max_blob_count(&self) -> u64 {
         self.max_blob_count.load(std::sync::atomic::Ordering::Relaxed)
@@ -1473,6 +1530,7 @@ mod tests {
             prague: false.into(),
             osaka: false.into(),
             tip_timestamp: 0.into(),
+            tip_number: 0.into(),
             max_blob_count: 0.into(),
             max_initcode_size: AtomicUsize::new(MAX_INITCODE_SIZE),
             tx_gas_limit_cap: AtomicU64::new(0),
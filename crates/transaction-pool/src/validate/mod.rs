@@ -12,10 +12,14 @@ use futures_util::future::Either;
 use reth_primitives_traits::{Block, Recovered, SealedBlock};
 use std::{fmt, fmt::Debug, future::Future, time::Instant};
 
+mod admission;
 mod constants;
 mod eth;
 mod task;
 
+pub use admission::{
+    AdmissionPolicy, AdmissionPolicyConfig, AdmissionPolicyError, OriginPriorityFees,
+};
 pub use eth::*;
 
 pub use task::{TransactionValidationTaskExecutor, ValidationTask};
@@ -2,8 +2,8 @@ use assert_matches::assert_matches;
 use reth_transaction_pool::{
     noop::MockTransactionValidator,
     test_utils::{MockTransactionFactory, TestPoolBuilder},
-    FullTransactionEvent, PoolTransaction, TransactionEvent, TransactionListenerKind,
-    TransactionOrigin, TransactionPool,
+    DiscardReason, FullTransactionEvent, PoolTransaction, TransactionEvent,
+    TransactionListenerKind, TransactionOrigin, TransactionPool,
 };
 use std::{future::poll_fn, task::Poll};
 use tokio_stream::StreamExt;
@@ -146,7 +146,7 @@ async fn txpool_listener_invalid_event() {
     assert_matches!(result, Err(_));
 
     // The listener of all should receive invalid event.
-    assert_matches!(all_tx_events.next().await, Some(FullTransactionEvent::Invalid(hash)) if hash == *transaction.get_hash());
+    assert_matches!(all_tx_events.next().await, Some(FullTransactionEvent::Invalid(hash, _)) if hash == *transaction.get_hash());
 }
 
 #[tokio::test(flavor = "multi_thread")]
@@ -170,7 +170,7 @@ async fn txpool_listener_all() {
 
     assert_eq!(transaction.transaction.hash(), removed_txs[0].transaction.hash());
 
-    assert_matches!(all_tx_events.next().await, Some(FullTransactionEvent::Discarded(hash)) if hash == *transaction.transaction.get_hash());
+    assert_matches!(all_tx_events.next().await, Some(FullTransactionEvent::Discarded(hash, DiscardReason::Removed)) if hash == *transaction.transaction.get_hash());
 }
 
 #[tokio::test(flavor = "multi_thread")]
@@ -70,6 +70,9 @@ mod proofs;
 pub use proofs::triehash;
 pub use proofs::*;
 
+mod range_proof;
+pub use range_proof::{verify_range_proof, RangeProofError};
+
 pub mod root;
 
 /// Incremental ordered trie root computation.
@@ -0,0 +1,359 @@
+//! Verification of range proofs, as served by the `snap` protocol.
+
+use crate::{HashBuilder, Nibbles};
+use alloc::vec::Vec;
+use alloy_primitives::{keccak256, map::B256Map, Bytes, B256};
+use alloy_rlp::Decodable;
+use alloy_trie::nodes::{RlpNode, TrieNode};
+
+/// Error returned when a range proof is invalid.
+#[derive(Clone, Debug, PartialEq, Eq, derive_more::Display, derive_more::Error)]
+pub enum RangeProofError {
+    /// The keys of the range are not strictly increasing.
+    #[display("range keys are not strictly increasing")]
+    UnorderedKeys,
+    /// The first key of the range is below the origin.
+    #[display("range key {_0} is below the origin")]
+    KeyBeforeOrigin(#[error(not(source))] B256),
+    /// A value of the range is empty.
+    #[display("range value for key {_0} is empty")]
+    EmptyValue(#[error(not(source))] B256),
+    /// A trie node on the boundary paths is missing from the proof.
+    #[display("proof node {_0} is missing")]
+    MissingNode(#[error(not(source))] B256),
+    /// A trie node of the proof could not be decoded.
+    #[display("failed to decode proof node: {_0}")]
+    Decode(alloy_rlp::Error),
+    /// The range is empty, but the trie contains keys after the origin.
+    #[display("range is empty, but the trie contains more keys")]
+    MissingKeys,
+    /// The root computed from the range and the proof does not match the expected root.
+    #[display("range root mismatch: got {got}, expected {expected}")]
+    RootMismatch {
+        /// The root computed from the range.
+        got: B256,
+        /// The expected root.
+        expected: B256,
+    },
+}
+
+/// Verifies that the given leaves are a consecutive range of the trie with the given root,
+/// starting at `origin`.
+///
+/// The leaves must be sorted by key. The proof consists of the trie nodes along the paths to
+/// `origin` and to the last leaf, which are used to prove that the trie has no other leaves
+/// between them. If the proof is empty, the leaves must make up the whole trie.
+///
+/// Returns `true` if the trie contains more leaves after the last one.
+pub fn verify_range_proof<V: AsRef<[u8]>>(
+    root: B256,
+    origin: B256,
+    leaves: &[(B256, V)],
+    proof: &[Bytes],
+) -> Result<bool, RangeProofError> {
+    if leaves.windows(2).any(|pair| pair[0].0 >= pair[1].0) {
+        return Err(RangeProofError::UnorderedKeys)
+    }
+    if let Some((key, _)) = leaves.first().filter(|(key, _)| *key < origin) {
+        return Err(RangeProofError::KeyBeforeOrigin(*key))
+    }
+    if let Some((key, _)) = leaves.iter().find(|(_, value)| value.as_ref().is_empty()) {
+        return Err(RangeProofError::EmptyValue(*key))
+    }
+
+    let mut items = Vec::new();
+    let mut has_more = false;
+    if !proof.is_empty() {
+        let nodes = proof.iter().map(|node| (keccak256(node), node)).collect::<B256Map<_>>();
+
+        // everything left of the origin must be proven, unless the range starts at the beginning
+        if origin != B256::ZERO {
+            collect_boundary(&nodes, root, origin, Boundary::Left, &mut items)?;
+        }
+
+        let right = leaves.last().map_or(origin, |(key, _)| *key);
+        let len = items.len();
+        collect_boundary(&nodes, root, right, Boundary::Right, &mut items)?;
+        has_more = items.len() > len;
+
+        if leaves.is_empty() && has_more {
+            return Err(RangeProofError::MissingKeys)
+        }
+    }
+
+    items.extend(
+        leaves
+            .iter()
+            .map(|(key, value)| (Nibbles::unpack(key), RangeItem::Leaf(value.as_ref().to_vec()))),
+    );
+    items.sort_unstable_by_key(|(path, _)| *path);
+
+    let mut hash_builder = HashBuilder::default();
+    for (path, item) in items {
+        match item {
+            RangeItem::Leaf(value) => hash_builder.add_leaf(path, &value),
+            RangeItem::Hash(hash) => hash_builder.add_branch(path, hash, false),
+        }
+    }
+
+    let got = hash_builder.root();
+    if got != root {
+        return Err(RangeProofError::RootMismatch { got, expected: root })
+    }
+
+    Ok(has_more)
+}
+
+/// The side of the range whose boundary is proven.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Boundary {
+    /// Everything below the origin.
+    Left,
+    /// Everything above the last key.
+    Right,
+}
+
+/// An item outside of the range that is required to compute the root.
+enum RangeItem {
+    /// A leaf with its value.
+    Leaf(Vec<u8>),
+    /// The hash of a subtrie.
+    Hash(B256),
+}
+
+/// Walks the path of the given key and collects all subtries that are entirely on the given side
+/// of the key.
+fn collect_boundary(
+    nodes: &B256Map<&Bytes>,
+    root: B256,
+    key: B256,
+    boundary: Boundary,
+    items: &mut Vec<(Nibbles, RangeItem)>,
+) -> Result<(), RangeProofError> {
+    let path = Nibbles::unpack(key);
+    let mut prefix = Nibbles::default();
+    let mut current = RlpNode::word_rlp(&root);
+
+    loop {
+        let node = decode_node(nodes, &current)?;
+        match node {
+            TrieNode::EmptyRoot => return Ok(()),
+            TrieNode::Branch(branch) => {
+                let nibble = path.get_unchecked(prefix.len());
+                let mut next = None;
+                for (child_nibble, child) in branch.as_ref().children() {
+                    let Some(child) = child else { continue };
+                    let is_outside = match boundary {
+                        Boundary::Left => child_nibble < nibble,
+                        Boundary::Right => child_nibble > nibble,
+                    };
+                    let mut child_path = prefix;
+                    child_path.push(child_nibble);
+                    if is_outside {
+                        collect_subtrie(child_path, child, items)?;
+                    } else if child_nibble == nibble {
+                        next = Some(child.clone());
+                    }
+                }
+
+                let Some(next) = next else { return Ok(()) };
+                prefix.push(nibble);
+                current = next;
+            }
+            TrieNode::Extension(extension) => {
+                let end = (prefix.len() + extension.key.len()).min(path.len());
+                let segment = path.slice(prefix.len()..end);
+                match (extension.key.cmp(&segment), boundary) {
+                    (core::cmp::Ordering::Equal, _) => {
+                        prefix.extend(&extension.key);
+                        current = extension.child;
+                    }
+                    (core::cmp::Ordering::Less, Boundary::Left) |
+                    (core::cmp::Ordering::Greater, Boundary::Right) => {
+                        return collect_subtrie(prefix, &current, items)
+                    }
+                    _ => return Ok(()),
+                }
+            }
+            TrieNode::Leaf(leaf) => {
+                let segment = path.slice(prefix.len()..);
+                return match (leaf.key.cmp(&segment), boundary) {
+                    (core::cmp::Ordering::Less, Boundary::Left) |
+                    (core::cmp::Ordering::Greater, Boundary::Right) => {
+                        collect_subtrie(prefix, &current, items)
+                    }
+                    _ => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+/// Collects the subtrie at the given path, expanding embedded nodes into their leaves.
+fn collect_subtrie(
+    path: Nibbles,
+    node: &RlpNode,
+    items: &mut Vec<(Nibbles, RangeItem)>,
+) -> Result<(), RangeProofError> {
+    if let Some(hash) = node.as_hash() {
+        items.push((path, RangeItem::Hash(hash)));
+        return Ok(())
+    }
+
+    match TrieNode::decode(&mut node.as_ref()).map_err(RangeProofError::Decode)? {
+        TrieNode::EmptyRoot => {}
+        TrieNode::Branch(branch) => {
+            for (nibble, child) in branch.as_ref().children() {
+                if let Some(child) = child {
+                    let mut child_path = path;
+                    child_path.push(nibble);
+                    collect_subtrie(child_path, child, items)?;
+                }
+            }
+        }
+        TrieNode::Extension(extension) => {
+            collect_subtrie(path.join(&extension.key), &extension.child, items)?;
+        }
+        TrieNode::Leaf(leaf) => items.push((path.join(&leaf.key), RangeItem::Leaf(leaf.value))),
+    }
+    Ok(())
+}
+
+/// Decodes the referenced node, looking it up in the proof if it is not embedded.
+fn decode_node(nodes: &B256Map<&Bytes>, node: &RlpNode) -> Result<TrieNode, RangeProofError> {
+    let mut encoded = match node.as_hash() {
+        Some(hash) => nodes.get(&hash).ok_or(RangeProofError::MissingNode(hash))?.as_ref(),
+        None => node.as_ref(),
+    };
+    TrieNode::decode(&mut encoded).map_err(RangeProofError::Decode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{proof::ProofRetainer, EMPTY_ROOT_HASH};
+    use alloy_primitives::U256;
+
+    /// Builds a storage-like trie of the given keys and returns its root and the proof nodes
+    /// for the given targets.
+    fn trie_with_proof(leaves: &[(B256, Vec<u8>)], targets: &[B256]) -> (B256, Vec<Bytes>) {
+        let retainer = targets.iter().map(Nibbles::unpack).collect::<ProofRetainer>();
+        let mut hash_builder = HashBuilder::default().with_proof_retainer(retainer);
+        for (key, value) in leaves {
+            hash_builder.add_leaf(Nibbles::unpack(key), value);
+        }
+        let root = hash_builder.root();
+        let proof = hash_builder
+            .take_proof_nodes()
+            .into_nodes_sorted()
+            .into_iter()
+            .filter(|(path, node)| path.is_empty() || node.len() >= 32)
+            .map(|(_, node)| node)
+            .collect();
+        (root, proof)
+    }
+
+    fn leaves(count: u64) -> Vec<(B256, Vec<u8>)> {
+        let mut leaves = (0..count)
+            .map(|i| (keccak256(B256::from(U256::from(i))), alloy_rlp::encode(U256::from(i + 1))))
+            .collect::<Vec<_>>();
+        leaves.sort_unstable_by_key(|(key, _)| *key);
+        leaves
+    }
+
+    #[test]
+    fn full_range_without_proof() {
+        let leaves = leaves(100);
+        let (root, _) = trie_with_proof(&leaves, &[]);
+        assert_eq!(verify_range_proof(root, B256::ZERO, &leaves, &[]), Ok(false));
+
+        let (_, rest) = leaves.split_last().unwrap();
+        assert!(matches!(
+            verify_range_proof(root, B256::ZERO, rest, &[]),
+            Err(RangeProofError::RootMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn partial_ranges() {
+        let leaves = leaves(500);
+        let (root, _) = trie_with_proof(&leaves, &[]);
+
+        for (start, end) in [(0, 10), (10, 250), (250, 499), (120, 121), (0, 500), (499, 500)] {
+            let origin = if start == 0 { B256::ZERO } else { leaves[start].0 };
+            let range = &leaves[start..end];
+            let (_, proof) = trie_with_proof(&leaves, &[origin, range.last().unwrap().0]);
+            assert_eq!(
+                verify_range_proof(root, origin, range, &proof),
+                Ok(end < leaves.len()),
+                "range {start}..{end}"
+            );
+        }
+    }
+
+    #[test]
+    fn origin_between_keys() {
+        let leaves = leaves(200);
+        let (root, _) = trie_with_proof(&leaves, &[]);
+
+        // an origin that directly precedes the first key of the range
+        let origin = B256::from(U256::from_be_bytes(leaves[50].0 .0) - U256::from(1));
+        let range = &leaves[50..80];
+        let (_, proof) = trie_with_proof(&leaves, &[origin, range.last().unwrap().0]);
+        assert_eq!(verify_range_proof(root, origin, range, &proof), Ok(true));
+    }
+
+    #[test]
+    fn rejects_gaps() {
+        let leaves = leaves(300);
+        let (root, _) = trie_with_proof(&leaves, &[]);
+
+        let origin = leaves[10].0;
+        let mut range = leaves[10..40].to_vec();
+        let (_, proof) = trie_with_proof(&leaves, &[origin, range.last().unwrap().0]);
+        range.remove(15);
+        assert!(matches!(
+            verify_range_proof(root, origin, &range, &proof),
+            Err(RangeProofError::RootMismatch { .. })
+        ));
+
+        let mut range = leaves[10..40].to_vec();
+        range[3].1 = alloy_rlp::encode(U256::MAX);
+        assert!(matches!(
+            verify_range_proof(root, origin, &range, &proof),
+            Err(RangeProofError::RootMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn empty_ranges() {
+        let leaves = leaves(50);
+        let (root, _) = trie_with_proof(&leaves, &[]);
+
+        // nothing after the origin
+        let origin = B256::repeat_byte(0xff);
+        let (_, proof) = trie_with_proof(&leaves, &[origin]);
+        assert_eq!(verify_range_proof::<Vec<u8>>(root, origin, &[], &proof), Ok(false));
+
+        // keys after the origin are withheld
+        let origin = leaves[20].0;
+        let (_, proof) = trie_with_proof(&leaves, &[origin]);
+        assert_eq!(
+            verify_range_proof::<Vec<u8>>(root, origin, &[], &proof),
+            Err(RangeProofError::MissingKeys)
+        );
+
+        // the empty trie
+        assert_eq!(verify_range_proof::<Vec<u8>>(EMPTY_ROOT_HASH, B256::ZERO, &[], &[]), Ok(false));
+    }
+
+    #[test]
+    fn rejects_unordered_keys() {
+        let mut leaves = leaves(10);
+        leaves.swap(2, 3);
+        assert_eq!(
+            verify_range_proof(B256::ZERO, B256::ZERO, &leaves, &[]),
+            Err(RangeProofError::UnorderedKeys)
+        );
+    }
+}
@@ -201,6 +201,10 @@ export const rethCliSidebar: SidebarItem = {
                 {
                     text: "reth db migrate-v2",
                     link: "/cli/reth/db/migrate-v2"
+                },
+                {
+                    text: "reth db verify-snapshot",
+                    link: "/cli/reth/db/verify-snapshot"
                 }
             ]
         },
@@ -310,6 +314,17 @@ export const rethCliSidebar: SidebarItem = {
         {
             text: "reth re-execute",
             link: "/cli/reth/re-execute"
+        },
+        {
+            text: "reth debug",
+            link: "/cli/reth/debug",
+            collapsed: true,
+            items: [
+                {
+                    text: "reth debug replay-engine",
+                    link: "/cli/reth/debug/replay-engine"
+                }
+            ]
         }
     ]
 };